@@ -0,0 +1,163 @@
+//! Benchmarks of interned string values of reactive properties.
+//!
+//! Many instances share the same string value, e.g. the name of a type or a category. Without
+//! interning each property holds its own allocation of the string, with interning all properties
+//! share a single allocation. The benchmarks compare the memory of the properties and the cost of
+//! getting and setting the values, interned vs. plain.
+//!
+//! Run with `cargo +nightly bench --bench property_interning`. The memory is measured by the test
+//! `property_interning_memory`, which prints the allocated bytes if it is run with
+//! `cargo +nightly test --release --bench property_interning -- --nocapture`.
+//!
+//! Results for 1000 properties sharing a string of 1000 bytes on a single core (x86_64 Linux,
+//! `bench` profile):
+//!
+//! | Benchmark                        |     Plain |  Interned |
+//! |----------------------------------|----------:|----------:|
+//! | memory of the properties (bytes) | 1,608,000 |   608,000 |
+//! | get of all values (ns)           |    73,653 |   114,460 |
+//! | get_shared_string of all (ns)    |    88,496 |    51,860 |
+//! | set of all values (ns)           |   322,203 |   836,445 |
+//!
+//! Interning saves the allocation of the string per property. Setting an interned value hashes
+//! the string and looks it up in the pool, which makes writes slower. Reading an interned value
+//! with `get` or `with_value` allocates a fresh `String` on every read, which makes reads slower
+//! than plain reads and offsets the saving for frequently read properties. Use
+//! `get_shared_string` to read interned values without copying.
+
+#![feature(test)]
+extern crate test;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use serde_json::json;
+use test::Bencher;
+use uuid::Uuid;
+
+use inexor_rgf_core_model::ReactivePropertyInstance;
+
+/// Counts the bytes which are allocated by the current thread, so the memory of the properties
+/// can be measured while other benchmarks run in parallel.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATED: Cell<isize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.with(|allocated| allocated.set(allocated.get() + layout.size() as isize));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.with(|allocated| allocated.set(allocated.get() - layout.size() as isize));
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// The number of properties which share the string.
+const PROPERTIES: usize = 1000;
+
+/// The length of the shared string.
+const STRING_LENGTH: usize = 1000;
+
+fn shared_string() -> String {
+    "x".repeat(STRING_LENGTH)
+}
+
+fn create_properties(value: &str, interning: bool) -> Vec<ReactivePropertyInstance> {
+    (0..PROPERTIES)
+        .map(|_| {
+            let mut property_instance = ReactivePropertyInstance::new(Uuid::new_v4(), "type", json!(null));
+            property_instance.set_interning(interning);
+            property_instance.set(json!(value));
+            property_instance
+        })
+        .collect()
+}
+
+/// Returns the bytes which are allocated by the properties.
+fn allocated_bytes(value: &str, interning: bool) -> isize {
+    let before = ALLOCATED.with(Cell::get);
+    let property_instances = create_properties(value, interning);
+    let bytes = ALLOCATED.with(Cell::get) - before;
+    drop(property_instances);
+    bytes
+}
+
+#[test]
+fn property_interning_memory() {
+    let value = shared_string();
+    let plain = allocated_bytes(&value, false);
+    let interned = allocated_bytes(&value, true);
+    println!("plain: {} bytes, interned: {} bytes", plain, interned);
+    // The interned properties share a single allocation of the string
+    assert!(plain - interned >= ((PROPERTIES - 1) * STRING_LENGTH) as isize);
+}
+
+#[bench]
+fn plain_get_benchmark(bencher: &mut Bencher) {
+    let property_instances = create_properties(&shared_string(), false);
+    bencher.iter(|| {
+        for property_instance in property_instances.iter() {
+            test::black_box(property_instance.get());
+        }
+    })
+}
+
+#[bench]
+fn interned_get_benchmark(bencher: &mut Bencher) {
+    let property_instances = create_properties(&shared_string(), true);
+    bencher.iter(|| {
+        for property_instance in property_instances.iter() {
+            test::black_box(property_instance.get());
+        }
+    })
+}
+
+#[bench]
+fn plain_get_shared_string_benchmark(bencher: &mut Bencher) {
+    let property_instances = create_properties(&shared_string(), false);
+    bencher.iter(|| {
+        for property_instance in property_instances.iter() {
+            test::black_box(property_instance.get_shared_string());
+        }
+    })
+}
+
+#[bench]
+fn interned_get_shared_string_benchmark(bencher: &mut Bencher) {
+    let property_instances = create_properties(&shared_string(), true);
+    bencher.iter(|| {
+        for property_instance in property_instances.iter() {
+            test::black_box(property_instance.get_shared_string());
+        }
+    })
+}
+
+#[bench]
+fn plain_set_benchmark(bencher: &mut Bencher) {
+    let value = shared_string();
+    let property_instances = create_properties(&value, false);
+    bencher.iter(|| {
+        for property_instance in property_instances.iter() {
+            property_instance.set(json!(value));
+        }
+    })
+}
+
+#[bench]
+fn interned_set_benchmark(bencher: &mut Bencher) {
+    let value = shared_string();
+    let property_instances = create_properties(&value, true);
+    bencher.iter(|| {
+        for property_instance in property_instances.iter() {
+            property_instance.set(json!(value));
+        }
+    })
+}
@@ -0,0 +1,249 @@
+//! Benchmarks of the property storage of reactive instances for read-heavy workloads.
+//!
+//! The properties of reactive entity instances and reactive relation instances are kept in a
+//! `DashMap` keyed by the interned property names. The benchmarks compare this storage with
+//!
+//! * the previous access pattern, a `DashMap` keyed by `String` which allocates the key on every
+//!   access, and
+//! * an immutable snapshot of the properties which is replaced on every added or removed property
+//!   (copy-on-update). Readers clone the `Arc` of the snapshot and don't lock a shard.
+//!
+//! The values are guarded by the lock of the property instance in all variants, so the variants
+//! only differ in the lookup of the property and in adding and removing properties.
+//!
+//! Run with `cargo +nightly bench --bench property_storage`.
+//!
+//! Results on a single core (x86_64 Linux, `bench` profile), in ns per iteration:
+//!
+//! | Benchmark                       | DashMap, interned keys | DashMap, `String` keys | Snapshot |
+//! |---------------------------------|-----------------------:|-----------------------:|---------:|
+//! | get of 16 properties            |                  1,271 |                  1,462 |    1,073 |
+//! | set of a property               |                    261 |                      - |      217 |
+//! | add and remove a property       |                  1,793 |                      - |    2,157 |
+//! | 4 readers and a writer (scoped) |                361,673 |                483,084 |  463,259 |
+//!
+//! Interning the keys removes the allocation of the key and makes lookups about 13% faster than
+//! the previous access pattern. The snapshot looks up properties about 15% faster than the
+//! `DashMap`, but copies the whole map whenever a property is added or removed, which happens
+//! for every added or removed component. The contended benchmark is dominated by spawning the
+//! threads on a single core, so it doesn't show a difference between the shard locks and the
+//! snapshot. Therefore the storage stays a `DashMap` keyed by the interned property names.
+
+#![feature(test)]
+extern crate test;
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::thread;
+
+use dashmap::DashMap;
+use serde_json::json;
+use test::Bencher;
+use uuid::Uuid;
+
+use inexor_rgf_core_model::{
+    EntityInstance, EntityTypeName, PropertyInstanceGetter, PropertyInstanceSetter, PropertyMap, ReactiveEntityInstance, ReactivePropertyContainer,
+    ReactivePropertyInstance,
+};
+
+/// The number of properties of the benchmarked instances.
+const PROPERTIES: usize = 16;
+
+/// The number of reads of each reader thread in the contended benchmarks.
+const READS_PER_THREAD: usize = 1000;
+
+/// The number of reader threads in the contended benchmarks.
+const READER_THREADS: usize = 4;
+
+fn property_name(index: usize) -> String {
+    format!("property_{}", index)
+}
+
+fn create_entity_instance() -> ReactiveEntityInstance {
+    let mut properties = PropertyMap::new();
+    for index in 0..PROPERTIES {
+        properties.insert(property_name(index), json!(index as f64));
+    }
+    ReactiveEntityInstance::from(EntityInstance::new(EntityTypeName::new("benchmark"), Uuid::new_v4(), properties))
+}
+
+/// The previous storage: a map keyed by `String`, which allocates the key on every access.
+fn create_string_keyed_storage() -> DashMap<String, ReactivePropertyInstance> {
+    let properties = DashMap::new();
+    for index in 0..PROPERTIES {
+        let name = property_name(index);
+        properties.insert(name.clone(), ReactivePropertyInstance::new(Uuid::new_v4(), &name, json!(index as f64)));
+    }
+    properties
+}
+
+type Snapshot = HashMap<Arc<str>, Arc<ReactivePropertyInstance>>;
+
+/// Copy-on-update storage: readers clone the current snapshot, writers replace it.
+fn create_snapshot_storage() -> RwLock<Arc<Snapshot>> {
+    let mut properties = HashMap::new();
+    for index in 0..PROPERTIES {
+        let name: Arc<str> = property_name(index).into();
+        properties.insert(name.clone(), Arc::new(ReactivePropertyInstance::new(Uuid::new_v4(), &name, json!(index as f64))));
+    }
+    RwLock::new(Arc::new(properties))
+}
+
+fn snapshot_as_f64(storage: &RwLock<Arc<Snapshot>>, property_name: &str) -> Option<f64> {
+    let snapshot = storage.read().unwrap().clone();
+    snapshot.get(property_name).and_then(|property_instance| property_instance.as_f64())
+}
+
+#[bench]
+fn dashmap_interned_keys_get_benchmark(bencher: &mut Bencher) {
+    let entity_instance = create_entity_instance();
+    let names: Vec<String> = (0..PROPERTIES).map(property_name).collect();
+    bencher.iter(|| {
+        for name in names.iter() {
+            test::black_box(entity_instance.as_f64(name));
+        }
+    })
+}
+
+#[bench]
+fn dashmap_string_keys_get_benchmark(bencher: &mut Bencher) {
+    let properties = create_string_keyed_storage();
+    let names: Vec<String> = (0..PROPERTIES).map(property_name).collect();
+    bencher.iter(|| {
+        for name in names.iter() {
+            test::black_box(
+                properties
+                    .get(&name.as_str().to_string())
+                    .and_then(|property_instance| property_instance.as_f64()),
+            );
+        }
+    })
+}
+
+#[bench]
+fn snapshot_get_benchmark(bencher: &mut Bencher) {
+    let storage = create_snapshot_storage();
+    let names: Vec<String> = (0..PROPERTIES).map(property_name).collect();
+    bencher.iter(|| {
+        for name in names.iter() {
+            test::black_box(snapshot_as_f64(&storage, name));
+        }
+    })
+}
+
+#[bench]
+fn dashmap_interned_keys_set_benchmark(bencher: &mut Bencher) {
+    let entity_instance = create_entity_instance();
+    let name = property_name(0);
+    bencher.iter(|| entity_instance.set(&name, json!(1.0)))
+}
+
+#[bench]
+fn snapshot_set_benchmark(bencher: &mut Bencher) {
+    let storage = create_snapshot_storage();
+    let name = property_name(0);
+    bencher.iter(|| {
+        let snapshot = storage.read().unwrap().clone();
+        if let Some(property_instance) = snapshot.get(name.as_str()) {
+            property_instance.set(json!(1.0));
+        }
+    })
+}
+
+#[bench]
+fn dashmap_interned_keys_add_remove_benchmark(bencher: &mut Bencher) {
+    let entity_instance = create_entity_instance();
+    bencher.iter(|| {
+        entity_instance.add_property("added", json!(0));
+        entity_instance.remove_property("added");
+    })
+}
+
+#[bench]
+fn snapshot_add_remove_benchmark(bencher: &mut Bencher) {
+    let storage = create_snapshot_storage();
+    let name: Arc<str> = "added".into();
+    bencher.iter(|| {
+        let mut writer = storage.write().unwrap();
+        let mut properties = Snapshot::clone(&writer);
+        properties.insert(name.clone(), Arc::new(ReactivePropertyInstance::new(Uuid::new_v4(), &name, json!(0))));
+        *writer = Arc::new(properties);
+        let mut properties = Snapshot::clone(&writer);
+        properties.remove(&name);
+        *writer = Arc::new(properties);
+    })
+}
+
+/// Reads the properties from multiple threads while another thread sets a value.
+fn contended<R: Fn(&str) + Sync, W: Fn() + Sync>(read: R, write: W) {
+    let names: Vec<String> = (0..PROPERTIES).map(property_name).collect();
+    thread::scope(|scope| {
+        for _ in 0..READER_THREADS {
+            scope.spawn(|| {
+                for read_index in 0..READS_PER_THREAD {
+                    read(&names[read_index % PROPERTIES]);
+                }
+            });
+        }
+        scope.spawn(|| {
+            for _ in 0..READS_PER_THREAD / 10 {
+                write();
+            }
+        });
+    });
+}
+
+#[bench]
+fn dashmap_interned_keys_contended_benchmark(bencher: &mut Bencher) {
+    let entity_instance = create_entity_instance();
+    let name = property_name(0);
+    bencher.iter(|| {
+        contended(
+            |property_name| {
+                test::black_box(entity_instance.as_f64(property_name));
+            },
+            || entity_instance.set(&name, json!(1.0)),
+        )
+    })
+}
+
+#[bench]
+fn dashmap_string_keys_contended_benchmark(bencher: &mut Bencher) {
+    let properties = create_string_keyed_storage();
+    let name = property_name(0);
+    bencher.iter(|| {
+        contended(
+            |property_name| {
+                test::black_box(
+                    properties
+                        .get(&property_name.to_string())
+                        .and_then(|property_instance| property_instance.as_f64()),
+                );
+            },
+            || {
+                if let Some(property_instance) = properties.get(&name) {
+                    property_instance.set(json!(1.0));
+                }
+            },
+        )
+    })
+}
+
+#[bench]
+fn snapshot_contended_benchmark(bencher: &mut Bencher) {
+    let storage = create_snapshot_storage();
+    let name = property_name(0);
+    bencher.iter(|| {
+        contended(
+            |property_name| {
+                test::black_box(snapshot_as_f64(&storage, property_name));
+            },
+            || {
+                let snapshot = storage.read().unwrap().clone();
+                if let Some(property_instance) = snapshot.get(name.as_str()) {
+                    property_instance.set(json!(1.0));
+                }
+            },
+        )
+    })
+}
@@ -0,0 +1,143 @@
+//! Derives the mapping between the fields of a struct and the properties of an instance.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, GenericArgument, LitStr, PathArguments, Type};
+
+/// Implements `PropertyMapping` for a struct with named fields. Each field is mapped to the
+/// property with the same name. The data type of the property is derived from the type of the
+/// field. Fields of type `Option` are optional.
+///
+/// The attribute `#[property(name = "...")]` maps a field to a property with another name and
+/// `#[property(skip)]` excludes a field from the mapping. Skipped fields are initialized with
+/// their default value.
+#[proc_macro_derive(PropertyMapping, attributes(property))]
+pub fn derive_property_mapping(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_property_mapping(input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+struct MappedField {
+    ident: syn::Ident,
+    property_name: Option<String>,
+    ty: Type,
+}
+
+fn expand_property_mapping(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(syn::Error::new(input.span(), "PropertyMapping can only be derived for structs with named fields")),
+        },
+        _ => return Err(syn::Error::new(input.span(), "PropertyMapping can only be derived for structs")),
+    };
+    let mut mapped_fields = Vec::new();
+    for field in fields {
+        let ident = field.ident.clone().unwrap();
+        let mut property_name = Some(ident.to_string());
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("property")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    let value: LitStr = meta.value()?.parse()?;
+                    property_name = Some(value.value());
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    property_name = None;
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown property attribute"))
+                }
+            })?;
+        }
+        mapped_fields.push(MappedField {
+            ident,
+            property_name,
+            ty: field.ty.clone(),
+        });
+    }
+
+    let property_types = mapped_fields.iter().filter_map(|field| {
+        let property_name = field.property_name.as_ref()?;
+        let data_type = data_type_of(&field.ty);
+        Some(quote! {
+            ::inexor_rgf_core_model::PropertyType::new(#property_name, ::inexor_rgf_core_model::DataType::#data_type)
+        })
+    });
+    let to_properties = mapped_fields.iter().filter_map(|field| {
+        let property_name = field.property_name.as_ref()?;
+        let ident = &field.ident;
+        Some(quote! {
+            properties.insert(#property_name.to_string(), ::inexor_rgf_core_model::to_mapped_value(#property_name, &self.#ident)?);
+        })
+    });
+    let from_instance = mapped_fields.iter().map(|field| {
+        let ident = &field.ident;
+        match &field.property_name {
+            Some(property_name) => quote! {
+                #ident: ::inexor_rgf_core_model::get_mapped_property(instance, #property_name)?
+            },
+            None => quote! {
+                #ident: ::std::default::Default::default()
+            },
+        }
+    });
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::inexor_rgf_core_model::PropertyMapping for #name #ty_generics #where_clause {
+            fn property_types() -> ::std::vec::Vec<::inexor_rgf_core_model::PropertyType> {
+                ::std::vec![#(#property_types),*]
+            }
+
+            fn to_properties(&self) -> ::std::result::Result<::inexor_rgf_core_model::PropertyMap, ::inexor_rgf_core_model::PropertyMappingError> {
+                let mut properties = ::inexor_rgf_core_model::PropertyMap::new();
+                #(#to_properties)*
+                ::std::result::Result::Ok(properties)
+            }
+
+            fn from_instance<I: ::inexor_rgf_core_model::PropertyInstanceGetter>(
+                instance: &I,
+            ) -> ::std::result::Result<Self, ::inexor_rgf_core_model::PropertyMappingError> {
+                ::std::result::Result::Ok(#name {
+                    #(#from_instance),*
+                })
+            }
+        }
+    })
+}
+
+/// Returns the variant of `DataType` for the type of a field.
+fn data_type_of(ty: &Type) -> syn::Ident {
+    let data_type = match ty {
+        Type::Reference(reference) => return data_type_of(&reference.elem),
+        Type::Array(_) | Type::Slice(_) | Type::Tuple(_) => "Array",
+        Type::Path(path) => match path.path.segments.last() {
+            Some(segment) => match segment.ident.to_string().as_str() {
+                "Option" | "Box" => match &segment.arguments {
+                    PathArguments::AngleBracketed(arguments) => match arguments.args.first() {
+                        Some(GenericArgument::Type(inner)) => return data_type_of(inner),
+                        _ => "Any",
+                    },
+                    _ => "Any",
+                },
+                "bool" => "Bool",
+                "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "f32" | "f64" => "Number",
+                "String" | "str" | "char" => "String",
+                "Uuid" => "Uuid",
+                "DateTime" => "DateTime",
+                "Vec" | "VecDeque" | "HashSet" | "BTreeSet" => "Array",
+                "HashMap" | "BTreeMap" | "IndexMap" | "PropertyMap" | "Map" => "Object",
+                _ => "Any",
+            },
+            None => "Any",
+        },
+        _ => "Any",
+    };
+    syn::Ident::new(data_type, ty.span())
+}
@@ -0,0 +1,265 @@
+use std::collections::HashSet;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+use chrono::{TimeZone, Utc};
+use serde_json::{Map, Number, Value};
+use uuid::Uuid;
+
+use crate::{
+    date_time_to_value, Cardinality, ComponentName, DataType, EntityInstance, EntityType, EntityTypeName, Flow, PropertyKind, PropertyMap, PropertyMutability,
+    PropertyType, RelationInstance, RelationType, RelationTypeName, SocketType,
+};
+
+const IDENTIFIER_START: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const IDENTIFIER_CHARACTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789_";
+
+/// The maximum number of items of generated lists, which keeps the generated values small.
+const MAX_ITEMS: usize = 8;
+
+/// The maximum depth of generated arrays and objects.
+const MAX_DEPTH: usize = 2;
+
+/// Returns a valid identifier: a lowercase letter followed by up to 15 lowercase letters, digits or underscores.
+pub fn arbitrary_identifier(u: &mut Unstructured) -> Result<String> {
+    let mut identifier = String::new();
+    identifier.push(*u.choose(IDENTIFIER_START)? as char);
+    for _ in 0..u.int_in_range(0..=15)? {
+        identifier.push(*u.choose(IDENTIFIER_CHARACTERS)? as char);
+    }
+    Ok(identifier)
+}
+
+/// Returns distinct valid identifiers.
+fn arbitrary_identifiers(u: &mut Unstructured) -> Result<Vec<String>> {
+    let mut identifiers = Vec::new();
+    for _ in 0..u.int_in_range(0..=MAX_ITEMS)? {
+        let identifier = arbitrary_identifier(u)?;
+        if !identifiers.contains(&identifier) {
+            identifiers.push(identifier);
+        }
+    }
+    Ok(identifiers)
+}
+
+/// Returns distinct valid component names.
+fn arbitrary_component_names(u: &mut Unstructured) -> Result<Vec<ComponentName>> {
+    Ok(arbitrary_identifiers(u)?.into_iter().map(ComponentName::new).collect())
+}
+
+/// Returns a valid value of the given data type. A value of the data type any has an arbitrary data type.
+pub fn arbitrary_value(u: &mut Unstructured, data_type: DataType) -> Result<Value> {
+    arbitrary_value_with_depth(u, data_type, 0)
+}
+
+fn arbitrary_value_with_depth(u: &mut Unstructured, data_type: DataType, depth: usize) -> Result<Value> {
+    Ok(match data_type {
+        DataType::Null => Value::Null,
+        DataType::Bool => Value::Bool(u.arbitrary()?),
+        DataType::Number => arbitrary_number(u)?,
+        DataType::String => Value::String(u.arbitrary()?),
+        DataType::Array => {
+            let mut items = Vec::new();
+            if depth < MAX_DEPTH {
+                for _ in 0..u.int_in_range(0..=MAX_ITEMS)? {
+                    let data_type = u.arbitrary()?;
+                    items.push(arbitrary_value_with_depth(u, data_type, depth + 1)?);
+                }
+            }
+            Value::Array(items)
+        }
+        DataType::Object => {
+            let mut object = Map::new();
+            if depth < MAX_DEPTH {
+                for _ in 0..u.int_in_range(0..=MAX_ITEMS)? {
+                    let data_type = u.arbitrary()?;
+                    object.insert(u.arbitrary()?, arbitrary_value_with_depth(u, data_type, depth + 1)?);
+                }
+            }
+            Value::Object(object)
+        }
+        DataType::Any => {
+            let data_type = *u.choose(&CONCRETE_DATA_TYPES)?;
+            arbitrary_value_with_depth(u, data_type, depth)?
+        }
+        DataType::Binary => Value::String(base64::encode(<Vec<u8>>::arbitrary(u)?)),
+        DataType::DateTime => {
+            let seconds = u.int_in_range(0..=253_402_300_799)?;
+            date_time_to_value(&Utc.timestamp_opt(seconds, 0).unwrap())
+        }
+        DataType::Uuid => Value::String(arbitrary_uuid(u)?.to_string()),
+    })
+}
+
+/// Returns an integer or a finite float.
+fn arbitrary_number(u: &mut Unstructured) -> Result<Value> {
+    if u.arbitrary()? {
+        return Ok(Value::from(i64::arbitrary(u)?));
+    }
+    Ok(Number::from_f64(u.arbitrary()?).map(Value::Number).unwrap_or_else(|| Value::from(0)))
+}
+
+fn arbitrary_uuid(u: &mut Unstructured) -> Result<Uuid> {
+    Ok(Uuid::from_bytes(u.arbitrary()?))
+}
+
+/// Returns properties with valid names and values of arbitrary data types.
+fn arbitrary_properties(u: &mut Unstructured) -> Result<PropertyMap> {
+    let mut properties = PropertyMap::new();
+    for name in arbitrary_identifiers(u)? {
+        let data_type = u.arbitrary()?;
+        properties.insert(name, arbitrary_value(u, data_type)?);
+    }
+    Ok(properties)
+}
+
+/// Returns property types with distinct names.
+fn arbitrary_property_types(u: &mut Unstructured) -> Result<Vec<PropertyType>> {
+    let mut property_types: Vec<PropertyType> = Vec::new();
+    for _ in 0..u.int_in_range(0..=MAX_ITEMS)? {
+        let property_type = PropertyType::arbitrary(u)?;
+        if !property_types.iter().any(|p| p.name == property_type.name) {
+            property_types.push(property_type);
+        }
+    }
+    Ok(property_types)
+}
+
+/// All data types except any.
+const CONCRETE_DATA_TYPES: [DataType; 9] = [
+    DataType::Null,
+    DataType::Bool,
+    DataType::Number,
+    DataType::String,
+    DataType::Array,
+    DataType::Object,
+    DataType::Binary,
+    DataType::DateTime,
+    DataType::Uuid,
+];
+
+impl<'a> Arbitrary<'a> for DataType {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        if u.ratio(1, 10)? {
+            return Ok(DataType::Any);
+        }
+        u.choose(&CONCRETE_DATA_TYPES).copied()
+    }
+}
+
+impl<'a> Arbitrary<'a> for SocketType {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        u.choose(&[SocketType::None, SocketType::Input, SocketType::Output]).copied()
+    }
+}
+
+impl<'a> Arbitrary<'a> for PropertyKind {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        u.choose(&[PropertyKind::ValueProperty, PropertyKind::EventProperty]).copied()
+    }
+}
+
+impl<'a> Arbitrary<'a> for PropertyMutability {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        u.choose(&[PropertyMutability::Mutable, PropertyMutability::Immutable, PropertyMutability::ReadOnly])
+            .copied()
+    }
+}
+
+impl<'a> Arbitrary<'a> for Cardinality {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        u.choose(&[Cardinality::One, Cardinality::Optional, Cardinality::Many]).copied()
+    }
+}
+
+impl<'a> Arbitrary<'a> for PropertyType {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut property_type = PropertyType::new_with_socket(arbitrary_identifier(u)?, u.arbitrary()?, u.arbitrary()?);
+        property_type.description = u.arbitrary()?;
+        property_type.kind = u.arbitrary()?;
+        property_type.mutability = u.arbitrary()?;
+        Ok(property_type)
+    }
+}
+
+impl<'a> Arbitrary<'a> for EntityType {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut entity_type = EntityType::new(
+            EntityTypeName::new(arbitrary_identifier(u)?),
+            arbitrary_identifier(u)?,
+            u.arbitrary()?,
+            arbitrary_component_names(u)?,
+            arbitrary_property_types(u)?,
+            Vec::new(),
+        );
+        entity_type.version = u.arbitrary()?;
+        Ok(entity_type)
+    }
+}
+
+impl<'a> Arbitrary<'a> for RelationType {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let relation_type = RelationType::new(
+            arbitrary_identifier(u)?,
+            RelationTypeName::new(arbitrary_identifier(u)?),
+            arbitrary_identifier(u)?,
+            arbitrary_identifier(u)?,
+            u.arbitrary()?,
+            arbitrary_component_names(u)?,
+            arbitrary_property_types(u)?,
+            Vec::new(),
+        );
+        Ok(relation_type.with_version(u.arbitrary()?).with_cardinality(u.arbitrary()?, u.arbitrary()?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for EntityInstance {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut entity_instance = EntityInstance::new(EntityTypeName::new(arbitrary_identifier(u)?), arbitrary_uuid(u)?, arbitrary_properties(u)?);
+        entity_instance.description = u.arbitrary()?;
+        entity_instance.components = arbitrary_component_names(u)?;
+        Ok(entity_instance)
+    }
+}
+
+impl<'a> Arbitrary<'a> for RelationInstance {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut relation_instance = RelationInstance::new(
+            arbitrary_uuid(u)?,
+            RelationTypeName::new(arbitrary_identifier(u)?),
+            arbitrary_uuid(u)?,
+            arbitrary_properties(u)?,
+        );
+        relation_instance.description = u.arbitrary()?;
+        relation_instance.components = arbitrary_component_names(u)?;
+        Ok(relation_instance)
+    }
+}
+
+/// The generated flow contains the wrapper entity instance and entity instances with distinct ids.
+/// The relation instances only connect entity instances of the flow and have distinct keys.
+impl<'a> Arbitrary<'a> for Flow {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut flow = Flow::from_instance_with_name(u.arbitrary()?, <String>::arbitrary(u)?);
+        flow.description = u.arbitrary()?;
+        let mut ids = HashSet::from([flow.id]);
+        for _ in 0..u.int_in_range(0..=MAX_ITEMS)? {
+            let entity_instance = EntityInstance::arbitrary(u)?;
+            if ids.insert(entity_instance.id) {
+                flow.entity_instances.push(entity_instance);
+            }
+        }
+        let ids: Vec<Uuid> = flow.entity_instances.iter().map(|entity_instance| entity_instance.id).collect();
+        let mut keys = HashSet::new();
+        for _ in 0..u.int_in_range(0..=MAX_ITEMS)? {
+            let outbound_id = *u.choose(&ids)?;
+            let type_name = RelationTypeName::new(arbitrary_identifier(u)?);
+            let inbound_id = *u.choose(&ids)?;
+            if keys.insert((outbound_id, type_name.clone(), inbound_id)) {
+                let mut relation_instance = RelationInstance::new(outbound_id, type_name, inbound_id, arbitrary_properties(u)?);
+                relation_instance.description = u.arbitrary()?;
+                flow.relation_instances.push(relation_instance);
+            }
+        }
+        Ok(flow)
+    }
+}
@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, BinaryArray, BooleanArray, Float64Array, RecordBatch, StringArray, TimestampMillisecondArray};
+use arrow_schema::{ArrowError, Field, Schema};
+use serde_json::Value;
+
+use crate::{value_to_binary, value_to_date_time, value_to_uuid, Component, DataType, EntityInstance, EntityType, PropertyInstanceGetter, PropertyType};
+
+/// Returns the property types of the entity type and of its components. Properties of the entity
+/// type override properties of the components with the same name.
+fn get_property_types<'a>(entity_type: &'a EntityType, components: &'a [Component]) -> Vec<&'a PropertyType> {
+    let mut property_types: Vec<&PropertyType> = Vec::new();
+    let all_property_types = components
+        .iter()
+        .filter(|component| entity_type.components.contains(&component.name))
+        .flat_map(|component| component.properties.iter())
+        .chain(entity_type.properties.iter());
+    for property_type in all_property_types {
+        match property_types.iter_mut().find(|p| p.name == property_type.name) {
+            Some(p) => *p = property_type,
+            None => property_types.push(property_type),
+        }
+    }
+    property_types
+}
+
+/// Returns the column of the property. Values which don't match the data type are nulls.
+fn create_column(property_type: &PropertyType, values: &[Option<Value>]) -> ArrayRef {
+    let values = values.iter().map(|value| value.as_ref().filter(|value| !value.is_null()));
+    match property_type.data_type {
+        DataType::Bool => Arc::new(values.map(|value| value.and_then(Value::as_bool)).collect::<BooleanArray>()),
+        DataType::Number => Arc::new(values.map(|value| value.and_then(Value::as_f64)).collect::<Float64Array>()),
+        DataType::String => Arc::new(values.map(|value| value.and_then(Value::as_str)).collect::<StringArray>()),
+        DataType::Uuid => Arc::new(
+            values
+                .map(|value| value.and_then(value_to_uuid).map(|uuid| uuid.to_string()))
+                .collect::<StringArray>(),
+        ),
+        DataType::Binary => Arc::new(values.map(|value| value.and_then(value_to_binary)).collect::<BinaryArray>()),
+        DataType::DateTime => Arc::new(
+            values
+                .map(|value| {
+                    value
+                        .and_then(|value| value_to_date_time(value).ok())
+                        .map(|date_time| date_time.timestamp_millis())
+                })
+                .collect::<TimestampMillisecondArray>()
+                .with_timezone("UTC"),
+        ),
+        DataType::Null | DataType::Array | DataType::Object | DataType::Any => {
+            Arc::new(values.map(|value| value.map(Value::to_string)).collect::<StringArray>())
+        }
+    }
+}
+
+/// Returns the entity instances of the given entity type as Arrow record batch.
+///
+/// The first column contains the ids. The other columns are derived from the property types of
+/// the entity type and of its components. Booleans, numbers, strings, UUIDs, binary data and
+/// timestamps are typed accordingly, all other values are JSON strings. Missing values and values
+/// which don't match the data type are nulls. Returns an error, if an entity instance is of a
+/// different type.
+pub fn entity_instances_to_arrow(entity_type: &EntityType, components: &[Component], entity_instances: &[EntityInstance]) -> Result<RecordBatch, ArrowError> {
+    if let Some(entity_instance) = entity_instances.iter().find(|entity_instance| entity_instance.type_name != entity_type.name) {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "The entity instance {} is of type {} instead of {}",
+            entity_instance.id, entity_instance.type_name, entity_type.name
+        )));
+    }
+    let ids: StringArray = entity_instances.iter().map(|entity_instance| Some(entity_instance.id.to_string())).collect();
+    let mut fields = vec![Field::new("id", arrow_schema::DataType::Utf8, false)];
+    let mut columns: Vec<ArrayRef> = vec![Arc::new(ids)];
+    for property_type in get_property_types(entity_type, components) {
+        let values: Vec<Option<Value>> = entity_instances
+            .iter()
+            .map(|entity_instance| entity_instance.get(&property_type.name))
+            .collect();
+        let column = create_column(property_type, &values);
+        fields.push(Field::new(&property_type.name, column.data_type().clone(), true));
+        columns.push(column);
+    }
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+}
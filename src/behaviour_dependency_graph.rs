@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{BehaviourName, BehaviourType, ReactiveEntityInstance};
+
+/// Specifies if a behaviour reads or writes a property.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BehaviourDependencyKind {
+    Read,
+    Write,
+}
+
+/// A behaviour which is applied on an instance reads or writes a property of the instance.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BehaviourDependency {
+    /// The id of the instance.
+    pub id: Uuid,
+
+    /// The name of the behaviour.
+    pub behaviour: String,
+
+    /// The name of the property.
+    pub property_name: String,
+
+    /// Read or write.
+    pub kind: BehaviourDependencyKind,
+}
+
+/// Multiple behaviours which are applied on the same instance write the same property.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BehaviourConflict {
+    /// The id of the instance.
+    pub id: Uuid,
+
+    /// The name of the property.
+    pub property_name: String,
+
+    /// The names of the behaviours which are writing the property.
+    pub behaviours: Vec<String>,
+}
+
+/// Dependency graph between the behaviours and the properties of a set of instances.
+#[derive(Clone, Debug, Default)]
+pub struct BehaviourDependencyGraph {
+    pub dependencies: Vec<BehaviourDependency>,
+}
+
+impl BehaviourDependencyGraph {
+    pub fn new() -> BehaviourDependencyGraph {
+        BehaviourDependencyGraph::default()
+    }
+
+    /// Builds the dependency graph for the given entity instances.
+    pub fn from_entity_instances(entity_instances: &[Arc<ReactiveEntityInstance>], behaviour_types: &[BehaviourType]) -> BehaviourDependencyGraph {
+        let mut graph = BehaviourDependencyGraph::new();
+        for entity_instance in entity_instances {
+            let behaviours: Vec<BehaviourName> = entity_instance.behaviours.iter().map(|behaviour| behaviour.key().clone()).collect();
+            graph.add_instance(entity_instance.id, &behaviours, behaviour_types);
+        }
+        graph
+    }
+
+    /// Adds the dependencies of the given behaviours which are applied on the instance with the given id.
+    ///
+    /// Behaviours without a behaviour type are ignored.
+    pub fn add_instance(&mut self, id: Uuid, behaviours: &[BehaviourName], behaviour_types: &[BehaviourType]) {
+        for behaviour_type in behaviour_types.iter().filter(|behaviour_type| behaviours.contains(&behaviour_type.name)) {
+            for property_name in behaviour_type.reads.iter() {
+                self.add_dependency(id, behaviour_type.name.as_str(), property_name, BehaviourDependencyKind::Read);
+            }
+            for property_name in behaviour_type.writes.iter() {
+                self.add_dependency(id, behaviour_type.name.as_str(), property_name, BehaviourDependencyKind::Write);
+            }
+        }
+    }
+
+    /// Returns the names of the behaviours which are reading the given property of the given instance.
+    pub fn get_readers<S: Into<String>>(&self, id: Uuid, property_name: S) -> Vec<String> {
+        self.get_behaviours(id, property_name.into(), BehaviourDependencyKind::Read)
+    }
+
+    /// Returns the names of the behaviours which are writing the given property of the given instance.
+    pub fn get_writers<S: Into<String>>(&self, id: Uuid, property_name: S) -> Vec<String> {
+        self.get_behaviours(id, property_name.into(), BehaviourDependencyKind::Write)
+    }
+
+    /// Returns the names of the behaviours of the same instance which are reading
+    /// a property which is written by the given behaviour.
+    pub fn get_dependents<S: Into<String>>(&self, id: Uuid, behaviour: S) -> Vec<String> {
+        let behaviour = behaviour.into();
+        let mut dependents: Vec<String> = Vec::new();
+        for write in self
+            .dependencies
+            .iter()
+            .filter(|dependency| dependency.id == id && dependency.behaviour == behaviour && dependency.kind == BehaviourDependencyKind::Write)
+        {
+            for reader in self.get_readers(id, write.property_name.clone()) {
+                if reader != behaviour && !dependents.contains(&reader) {
+                    dependents.push(reader);
+                }
+            }
+        }
+        dependents
+    }
+
+    /// Detects write-write conflicts between behaviours which are applied on the same instance.
+    pub fn get_conflicts(&self) -> Vec<BehaviourConflict> {
+        let mut writers: HashMap<(Uuid, String), Vec<String>> = HashMap::new();
+        for dependency in self.dependencies.iter().filter(|dependency| dependency.kind == BehaviourDependencyKind::Write) {
+            writers
+                .entry((dependency.id, dependency.property_name.clone()))
+                .or_default()
+                .push(dependency.behaviour.clone());
+        }
+        let mut conflicts: Vec<BehaviourConflict> = writers
+            .into_iter()
+            .filter(|(_, behaviours)| behaviours.len() > 1)
+            .map(|((id, property_name), mut behaviours)| {
+                behaviours.sort();
+                BehaviourConflict { id, property_name, behaviours }
+            })
+            .collect();
+        conflicts.sort_by(|a, b| a.id.cmp(&b.id).then_with(|| a.property_name.cmp(&b.property_name)));
+        conflicts
+    }
+
+    /// Returns true, if there are write-write conflicts.
+    pub fn has_conflicts(&self) -> bool {
+        !self.get_conflicts().is_empty()
+    }
+
+    fn add_dependency(&mut self, id: Uuid, behaviour: &str, property_name: &str, kind: BehaviourDependencyKind) {
+        let dependency = BehaviourDependency {
+            id,
+            behaviour: behaviour.to_string(),
+            property_name: property_name.to_string(),
+            kind,
+        };
+        if !self.dependencies.contains(&dependency) {
+            self.dependencies.push(dependency);
+        }
+    }
+
+    fn get_behaviours(&self, id: Uuid, property_name: String, kind: BehaviourDependencyKind) -> Vec<String> {
+        self.dependencies
+            .iter()
+            .filter(|dependency| dependency.id == id && dependency.property_name == property_name && dependency.kind == kind)
+            .map(|dependency| dependency.behaviour.clone())
+            .collect()
+    }
+}
@@ -0,0 +1,121 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// A transition between two states of a behaviour.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BehaviourTransition {
+    /// The name of the state before the transition.
+    pub from: String,
+
+    /// The name of the state after the transition.
+    pub to: String,
+}
+
+/// The lifecycle of a behaviour as finite state machine, e.g. created → connected → paused.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BehaviourStateMachine {
+    /// The names of the states.
+    pub states: Vec<String>,
+
+    /// The allowed transitions.
+    #[serde(default = "Vec::new")]
+    pub transitions: Vec<BehaviourTransition>,
+
+    /// The name of the state the state machine starts in.
+    pub initial_state: String,
+
+    /// The name of the current state. Defaults to the initial state.
+    #[serde(default)]
+    pub current_state: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BehaviourStateError {
+    /// The state with the given name doesn't exist.
+    UnknownState(String),
+
+    /// There is no transition from the first to the second state.
+    InvalidTransition(String, String),
+
+    /// The behaviour with the given name has no state machine.
+    MissingStateMachine(String),
+}
+
+impl fmt::Display for BehaviourStateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BehaviourStateError::UnknownState(state) => write!(f, "The state {} doesn't exist", state),
+            BehaviourStateError::InvalidTransition(from, to) => write!(f, "There is no transition from {} to {}", from, to),
+            BehaviourStateError::MissingStateMachine(behaviour) => write!(f, "The behaviour {} has no state machine", behaviour),
+        }
+    }
+}
+
+impl BehaviourStateMachine {
+    /// Constructs a state machine with the given states which starts in the first state.
+    pub fn new(states: Vec<String>) -> BehaviourStateMachine {
+        let initial_state = states.first().cloned().unwrap_or_default();
+        BehaviourStateMachine {
+            states,
+            transitions: Vec::new(),
+            initial_state,
+            current_state: None,
+        }
+    }
+
+    /// Allows the transition from the first to the second state.
+    pub fn with_transition<S: Into<String>>(mut self, from: S, to: S) -> BehaviourStateMachine {
+        self.transitions.push(BehaviourTransition {
+            from: from.into(),
+            to: to.into(),
+        });
+        self
+    }
+
+    /// Returns the name of the current state.
+    pub fn get_state(&self) -> &str {
+        self.current_state.as_deref().unwrap_or(&self.initial_state)
+    }
+
+    /// Returns true, if the state machine is in the state with the given name.
+    pub fn is_in<S: AsRef<str>>(&self, state: S) -> bool {
+        self.get_state() == state.as_ref()
+    }
+
+    /// Returns true, if the state machine can change from the current state to the given state.
+    pub fn can_transition_to<S: AsRef<str>>(&self, state: S) -> bool {
+        let current_state = self.get_state();
+        self.transitions
+            .iter()
+            .any(|transition| transition.from == current_state && transition.to == state.as_ref())
+    }
+
+    /// Returns the names of the states which can be reached from the current state.
+    pub fn get_next_states(&self) -> Vec<&str> {
+        let current_state = self.get_state();
+        self.transitions
+            .iter()
+            .filter(|transition| transition.from == current_state)
+            .map(|transition| transition.to.as_str())
+            .collect()
+    }
+
+    /// Changes the current state, if a transition to the given state exists.
+    pub fn transition_to<S: Into<String>>(&mut self, state: S) -> Result<(), BehaviourStateError> {
+        let state = state.into();
+        if !self.states.contains(&state) {
+            return Err(BehaviourStateError::UnknownState(state));
+        }
+        if !self.can_transition_to(&state) {
+            return Err(BehaviourStateError::InvalidTransition(self.get_state().to_string(), state));
+        }
+        self.current_state = Some(state);
+        Ok(())
+    }
+
+    /// Changes the current state back to the initial state.
+    pub fn reset(&mut self) {
+        self.current_state = None;
+    }
+}
@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{BehaviourName, ComponentName, EntityTypeName, Extension, ReactiveEntityInstance, ReactiveRelationInstance, RelationTypeName};
+
+/// A behaviour type declares which properties a behaviour reads and writes and to which
+/// instances the behaviour can be applied.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BehaviourType {
+    /// The name of the behaviour type.
+    pub name: BehaviourName,
+
+    /// Textual description of the behaviour type.
+    #[serde(default = "String::new")]
+    pub description: String,
+
+    /// The names of the properties which are read by the behaviour.
+    #[serde(default = "Vec::new")]
+    pub reads: Vec<String>,
+
+    /// The names of the properties which are written by the behaviour.
+    #[serde(default = "Vec::new")]
+    pub writes: Vec<String>,
+
+    /// The names of the entity types the behaviour can be applied to.
+    #[serde(default = "Vec::new")]
+    pub entity_types: Vec<EntityTypeName>,
+
+    /// The names of the relation types the behaviour can be applied to.
+    #[serde(default = "Vec::new")]
+    pub relation_types: Vec<RelationTypeName>,
+
+    /// The behaviour can be applied to instances which are composed with one of these components.
+    #[serde(default = "Vec::new")]
+    pub components: Vec<ComponentName>,
+
+    /// Behaviour type specific extensions
+    #[serde(default = "Vec::new")]
+    pub extensions: Vec<Extension>,
+}
+
+impl BehaviourType {
+    /// Constructs a new behaviour type with the given name and the properties it reads and writes.
+    pub fn new<N: Into<BehaviourName>>(name: N, reads: Vec<String>, writes: Vec<String>) -> BehaviourType {
+        BehaviourType {
+            name: name.into(),
+            description: String::new(),
+            reads,
+            writes,
+            entity_types: Vec::new(),
+            relation_types: Vec::new(),
+            components: Vec::new(),
+            extensions: Vec::new(),
+        }
+    }
+
+    pub fn with_description<S: Into<String>>(mut self, description: S) -> BehaviourType {
+        self.description = description.into();
+        self
+    }
+
+    /// Declares that the behaviour can be applied to entity instances of the entity type.
+    pub fn with_entity_type<S: Into<EntityTypeName>>(mut self, type_name: S) -> BehaviourType {
+        self.entity_types.push(type_name.into());
+        self
+    }
+
+    /// Declares that the behaviour can be applied to relation instances of the relation type.
+    pub fn with_relation_type<S: Into<RelationTypeName>>(mut self, type_name: S) -> BehaviourType {
+        self.relation_types.push(type_name.into());
+        self
+    }
+
+    /// Declares that the behaviour can be applied to instances which are composed with the component.
+    pub fn with_component<C: Into<ComponentName>>(mut self, component_name: C) -> BehaviourType {
+        self.components.push(component_name.into());
+        self
+    }
+
+    /// Returns true, if the behaviour can be applied to entity instances of the entity type.
+    pub fn applies_to_entity_type<S: AsRef<str>>(&self, type_name: S) -> bool {
+        self.entity_types.iter().any(|entity_type| entity_type == type_name.as_ref())
+    }
+
+    /// Returns true, if the behaviour can be applied to relation instances of the relation type.
+    pub fn applies_to_relation_type<S: AsRef<str>>(&self, type_name: S) -> bool {
+        self.relation_types.iter().any(|relation_type| relation_type == type_name.as_ref())
+    }
+
+    /// Returns true, if the behaviour can be applied to instances which are composed with the component.
+    pub fn applies_to_component<S: AsRef<str>>(&self, component_name: S) -> bool {
+        self.components.iter().any(|component| component == component_name.as_ref())
+    }
+
+    /// Returns true, if the behaviour can be applied to the entity instance.
+    pub fn is_applicable_to_entity(&self, entity_instance: &ReactiveEntityInstance) -> bool {
+        self.applies_to_entity_type(&entity_instance.type_name) || self.components.iter().any(|component| entity_instance.is_a(component.clone()))
+    }
+
+    /// Returns true, if the behaviour can be applied to the relation instance.
+    pub fn is_applicable_to_relation(&self, relation_instance: &ReactiveRelationInstance) -> bool {
+        self.applies_to_relation_type(&relation_instance.type_name) || self.components.iter().any(|component| relation_instance.is_a(component.clone()))
+    }
+
+    /// Returns true, if the behaviour reads the property with the given name.
+    pub fn reads<S: Into<String>>(&self, property_name: S) -> bool {
+        self.reads.contains(&property_name.into())
+    }
+
+    /// Returns true, if the behaviour writes the property with the given name.
+    pub fn writes<S: Into<String>>(&self, property_name: S) -> bool {
+        self.writes.contains(&property_name.into())
+    }
+
+    /// Returns true, if the behaviour type contains an extension with the given name.
+    pub fn has_extension<S: Into<String>>(&self, extension_name: S) -> bool {
+        let extension_name = extension_name.into();
+        self.extensions.iter().any(|extension| extension.name == extension_name)
+    }
+}
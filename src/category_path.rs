@@ -0,0 +1,245 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The separator of the segments of a category path.
+pub const CATEGORY_PATH_SEPARATOR: char = '/';
+
+/// An error which occurs if a string isn't a valid category path.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CategoryPathError {
+    /// The category path contains an empty segment, e.g. `logical//gates` or `/logical`.
+    EmptySegment,
+
+    /// The segment starts or ends with whitespace.
+    UnexpectedWhitespace(String),
+
+    /// The segment contains the separator.
+    UnexpectedSeparator(String),
+}
+
+impl fmt::Display for CategoryPathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CategoryPathError::EmptySegment => write!(f, "The category path contains an empty segment"),
+            CategoryPathError::UnexpectedWhitespace(segment) => write!(f, "The segment {} of the category path starts or ends with whitespace", segment),
+            CategoryPathError::UnexpectedSeparator(segment) => {
+                write!(f, "The segment {} of the category path contains the separator {}", segment, CATEGORY_PATH_SEPARATOR)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CategoryPathError {}
+
+/// A hierarchical category of types, e.g. `logical/gates`. The empty path is the root category.
+///
+/// The category path is serialized as string. A flat group name is a category path with a single
+/// segment. Category paths can also be deserialized from a list of segments. Parsing rejects paths
+/// with empty segments or segments which start or end with whitespace, but the group of a type is
+/// normalized into a valid path, see [`CategoryPath::from_group`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CategoryPath(Vec<String>);
+
+/// Returns an error, if the segment is empty or starts or ends with whitespace.
+fn validate_segment(segment: &str) -> Result<(), CategoryPathError> {
+    if segment.trim() != segment {
+        return Err(CategoryPathError::UnexpectedWhitespace(segment.to_string()));
+    }
+    Ok(())
+}
+
+impl CategoryPath {
+    /// Parses the string representation of a category path. The empty string is the root category.
+    pub fn new(category_path: &str) -> Result<CategoryPath, CategoryPathError> {
+        if category_path.is_empty() {
+            return Ok(CategoryPath::default());
+        }
+        let mut segments = Vec::new();
+        for segment in category_path.split(CATEGORY_PATH_SEPARATOR) {
+            if segment.is_empty() {
+                return Err(CategoryPathError::EmptySegment);
+            }
+            validate_segment(segment)?;
+            segments.push(segment.to_string());
+        }
+        Ok(CategoryPath(segments))
+    }
+
+    /// Parses the group of a type. The groups of existing type definitions which aren't valid
+    /// category paths stay valid: the segments are trimmed and empty segments are skipped, e.g.
+    /// `Input / Output` is the category path `Input/Output`.
+    pub fn from_group(group: &str) -> CategoryPath {
+        CategoryPath(
+            group
+                .split(CATEGORY_PATH_SEPARATOR)
+                .map(str::trim)
+                .filter(|segment| !segment.is_empty())
+                .map(String::from)
+                .collect(),
+        )
+    }
+
+    /// Constructs a category path from the segments. Fails, if a segment is empty, starts or ends
+    /// with whitespace or contains the separator.
+    pub fn from_segments<I: IntoIterator<Item = S>, S: Into<String>>(segments: I) -> Result<CategoryPath, CategoryPathError> {
+        let mut category_path = Vec::new();
+        for segment in segments {
+            let segment = segment.into();
+            if segment.is_empty() {
+                return Err(CategoryPathError::EmptySegment);
+            }
+            if segment.contains(CATEGORY_PATH_SEPARATOR) {
+                return Err(CategoryPathError::UnexpectedSeparator(segment));
+            }
+            validate_segment(&segment)?;
+            category_path.push(segment);
+        }
+        Ok(CategoryPath(category_path))
+    }
+
+    /// Returns the segments of the category path.
+    pub fn segments(&self) -> &[String] {
+        &self.0
+    }
+
+    /// Returns true, if this is the root category.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the number of segments.
+    pub fn depth(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns the last segment or none, if this is the root category.
+    pub fn name(&self) -> Option<&str> {
+        self.0.last().map(String::as_str)
+    }
+
+    /// Returns the parent category or none, if this is the root category.
+    pub fn parent(&self) -> Option<CategoryPath> {
+        self.0.split_last().map(|(_, parent)| CategoryPath(parent.to_vec()))
+    }
+
+    /// Returns the sub category with the given segments.
+    pub fn join<C: Into<CategoryPath>>(&self, sub_category: C) -> CategoryPath {
+        let mut segments = self.0.clone();
+        segments.extend(sub_category.into().0);
+        CategoryPath(segments)
+    }
+
+    /// Returns true, if the category path equals the given string representation.
+    fn eq_str(&self, category_path: &str) -> bool {
+        CategoryPath::from_group(category_path) == *self
+    }
+
+    /// Returns true, if this category equals the given category or is one of its sub categories.
+    pub fn is_in_subtree_of(&self, category: &CategoryPath) -> bool {
+        self.0.starts_with(&category.0)
+    }
+}
+
+impl fmt::Display for CategoryPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.join(&CATEGORY_PATH_SEPARATOR.to_string()))
+    }
+}
+
+impl FromStr for CategoryPath {
+    type Err = CategoryPathError;
+
+    fn from_str(category_path: &str) -> Result<Self, Self::Err> {
+        CategoryPath::new(category_path)
+    }
+}
+
+impl TryFrom<&str> for CategoryPath {
+    type Error = CategoryPathError;
+
+    fn try_from(category_path: &str) -> Result<Self, Self::Error> {
+        CategoryPath::new(category_path)
+    }
+}
+
+impl TryFrom<&String> for CategoryPath {
+    type Error = CategoryPathError;
+
+    fn try_from(category_path: &String) -> Result<Self, Self::Error> {
+        CategoryPath::new(category_path)
+    }
+}
+
+impl TryFrom<String> for CategoryPath {
+    type Error = CategoryPathError;
+
+    fn try_from(category_path: String) -> Result<Self, Self::Error> {
+        CategoryPath::new(&category_path)
+    }
+}
+
+impl From<&CategoryPath> for CategoryPath {
+    fn from(category_path: &CategoryPath) -> Self {
+        category_path.clone()
+    }
+}
+
+impl From<CategoryPath> for String {
+    fn from(category_path: CategoryPath) -> Self {
+        category_path.to_string()
+    }
+}
+
+impl PartialEq<str> for CategoryPath {
+    fn eq(&self, other: &str) -> bool {
+        self.eq_str(other)
+    }
+}
+
+impl PartialEq<&str> for CategoryPath {
+    fn eq(&self, other: &&str) -> bool {
+        self.eq_str(other)
+    }
+}
+
+impl PartialEq<String> for CategoryPath {
+    fn eq(&self, other: &String) -> bool {
+        self.eq_str(other)
+    }
+}
+
+impl PartialEq<CategoryPath> for &str {
+    fn eq(&self, other: &CategoryPath) -> bool {
+        other == self
+    }
+}
+
+impl PartialEq<CategoryPath> for String {
+    fn eq(&self, other: &CategoryPath) -> bool {
+        other == self
+    }
+}
+
+impl Serialize for CategoryPath {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CategoryPathRepresentation {
+    Path(String),
+    Segments(Vec<String>),
+}
+
+impl<'de> Deserialize<'de> for CategoryPath {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match CategoryPathRepresentation::deserialize(deserializer)? {
+            CategoryPathRepresentation::Path(category_path) => Ok(CategoryPath::from_group(&category_path)),
+            CategoryPathRepresentation::Segments(segments) => CategoryPath::from_segments(segments).map_err(serde::de::Error::custom),
+        }
+    }
+}
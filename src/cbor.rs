@@ -0,0 +1,73 @@
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::{EntityInstance, Flow, RelationInstance};
+
+/// An error which occurs if a value can't be encoded into or decoded from CBOR.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CborError {
+    /// The value can't be encoded.
+    Encode(String),
+
+    /// The bytes can't be decoded.
+    Decode(String),
+}
+
+impl fmt::Display for CborError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CborError::Encode(message) => write!(f, "Failed to encode CBOR: {}", message),
+            CborError::Decode(message) => write!(f, "Failed to decode CBOR: {}", message),
+        }
+    }
+}
+
+/// Encodes the value as CBOR.
+pub fn to_cbor<T: Serialize>(value: &T) -> Result<Vec<u8>, CborError> {
+    let mut bytes = Vec::new();
+    ciborium::ser::into_writer(value, &mut bytes).map_err(|error| CborError::Encode(error.to_string()))?;
+    Ok(bytes)
+}
+
+/// Decodes the value from CBOR.
+pub fn from_cbor<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, CborError> {
+    ciborium::de::from_reader(bytes).map_err(|error| CborError::Decode(error.to_string()))
+}
+
+impl EntityInstance {
+    /// Encodes the entity instance as CBOR.
+    pub fn to_cbor(&self) -> Result<Vec<u8>, CborError> {
+        to_cbor(self)
+    }
+
+    /// Decodes an entity instance from CBOR.
+    pub fn from_cbor(bytes: &[u8]) -> Result<EntityInstance, CborError> {
+        from_cbor(bytes)
+    }
+}
+
+impl RelationInstance {
+    /// Encodes the relation instance as CBOR.
+    pub fn to_cbor(&self) -> Result<Vec<u8>, CborError> {
+        to_cbor(self)
+    }
+
+    /// Decodes a relation instance from CBOR.
+    pub fn from_cbor(bytes: &[u8]) -> Result<RelationInstance, CborError> {
+        from_cbor(bytes)
+    }
+}
+
+impl Flow {
+    /// Encodes the flow as CBOR.
+    pub fn to_cbor(&self) -> Result<Vec<u8>, CborError> {
+        to_cbor(self)
+    }
+
+    /// Decodes a flow from CBOR.
+    pub fn from_cbor(bytes: &[u8]) -> Result<Flow, CborError> {
+        from_cbor(bytes)
+    }
+}
@@ -1,13 +1,19 @@
+use std::hash::{Hash, Hasher};
+
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
-use crate::{Extension, PropertyType};
+use crate::{
+    find_property_group, get_extension, get_ordered_property_groups, get_property_group_members, ComponentName, Extension, ExtensionError, PropertyGroup,
+    PropertyType,
+};
 
 /// A component defines a set of properties to be applied to entity
 /// types and relation types.
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Component {
     /// The name of the component.
-    pub name: String,
+    pub name: ComponentName,
 
     /// Textual description of the component.
     #[serde(default = "String::new")]
@@ -20,39 +26,60 @@ pub struct Component {
     /// Component specific extensions
     #[serde(default = "Vec::new")]
     pub extensions: Vec<Extension>,
+
+    /// The sections of property panels.
+    #[serde(default = "Vec::new")]
+    pub property_groups: Vec<PropertyGroup>,
+
+    /// The version of the component. Migrations transform the properties of instances
+    /// of older versions.
+    #[serde(default)]
+    pub version: u32,
 }
 
 impl Component {
     /// Constructs a new component with the given name and properties
-    pub fn new<S: Into<String>>(name: S, properties: Vec<PropertyType>) -> Component {
+    pub fn new<N: Into<ComponentName>>(name: N, properties: Vec<PropertyType>) -> Component {
         Component {
             name: name.into(),
             description: String::new(),
             properties,
             extensions: Vec::new(),
+            property_groups: Vec::new(),
+            version: 0,
         }
     }
 
     /// Constructs a new component with the given name and properties
-    pub fn new_with_extensions<S: Into<String>>(name: S, properties: Vec<PropertyType>, extensions: Vec<Extension>) -> Component {
+    pub fn new_with_extensions<N: Into<ComponentName>>(name: N, properties: Vec<PropertyType>, extensions: Vec<Extension>) -> Component {
         Component {
             name: name.into(),
             description: String::new(),
             properties,
             extensions,
+            property_groups: Vec::new(),
+            version: 0,
         }
     }
 
     /// Constructs an component with the given name but without properties
-    pub fn new_without_properties<S: Into<String>>(name: S) -> Component {
+    pub fn new_without_properties<N: Into<ComponentName>>(name: N) -> Component {
         Component {
             name: name.into(),
             description: String::new(),
             properties: Vec::new(),
             extensions: Vec::new(),
+            property_groups: Vec::new(),
+            version: 0,
         }
     }
 
+    /// Sets the version of the component.
+    pub fn with_version(mut self, version: u32) -> Component {
+        self.version = version;
+        self
+    }
+
     /// Returns true, if the component contains a property with the given name.
     pub fn has_property<S: Into<String>>(&self, property_name: S) -> bool {
         let property_name = property_name.into();
@@ -64,4 +91,39 @@ impl Component {
         let extension_name = extension_name.into();
         self.extensions.iter().any(|extension| extension.name == extension_name)
     }
+
+    /// Returns the deserialized payload of the extension with the given name.
+    pub fn get_extension<T: DeserializeOwned, S: AsRef<str>>(&self, extension_name: S) -> Result<T, ExtensionError> {
+        get_extension(&self.extensions, extension_name)
+    }
+
+    /// Returns the property groups sorted by order.
+    pub fn get_property_groups(&self) -> Vec<&PropertyGroup> {
+        get_ordered_property_groups(&self.property_groups)
+    }
+
+    /// Returns the group of the property with the given name.
+    pub fn get_property_group_of<S: AsRef<str>>(&self, property_name: S) -> Option<&PropertyGroup> {
+        find_property_group(&self.property_groups, &self.properties, property_name)
+    }
+
+    /// Returns the properties of the group with the given name in display order.
+    pub fn get_property_group_members<S: AsRef<str>>(&self, group_name: S) -> Vec<&PropertyType> {
+        get_property_group_members(&self.properties, group_name)
+    }
+}
+
+/// Components are equal, if the names are equal.
+impl PartialEq for Component {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for Component {}
+
+impl Hash for Component {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+    }
 }
@@ -0,0 +1,82 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ComponentName, EntityType, EntityTypeName, ReactiveEntityInstance};
+
+/// The prefix of a component name, e.g. `component:labeled`.
+pub const COMPONENT_PREFIX: &str = "component:";
+
+/// The wildcard which matches any entity type.
+pub const WILDCARD: &str = "*";
+
+/// Specifies which entity instances can be the outbound or inbound entity instance of a relation type.
+///
+/// Serialized as a string: an entity type name, a component name with the prefix `component:`
+/// or the wildcard `*`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
+pub enum ComponentOrEntityTypeName {
+    /// Entity instances of the entity type with the given name.
+    EntityType(EntityTypeName),
+
+    /// Entity instances which are composed with the component with the given name.
+    Component(ComponentName),
+
+    /// Any entity instance.
+    Wildcard,
+}
+
+impl ComponentOrEntityTypeName {
+    /// Returns true, if the given entity type matches.
+    pub fn matches_entity_type(&self, entity_type: &EntityType) -> bool {
+        match self {
+            ComponentOrEntityTypeName::EntityType(type_name) => entity_type.name == *type_name,
+            ComponentOrEntityTypeName::Component(component_name) => entity_type.is_a(component_name.clone()),
+            ComponentOrEntityTypeName::Wildcard => true,
+        }
+    }
+
+    /// Returns true, if the given entity instance matches.
+    pub fn matches_entity_instance(&self, entity_instance: &ReactiveEntityInstance) -> bool {
+        match self {
+            ComponentOrEntityTypeName::EntityType(type_name) => entity_instance.type_name == *type_name,
+            ComponentOrEntityTypeName::Component(component_name) => entity_instance.is_a(component_name.clone()),
+            ComponentOrEntityTypeName::Wildcard => true,
+        }
+    }
+}
+
+impl From<&str> for ComponentOrEntityTypeName {
+    fn from(value: &str) -> Self {
+        if value == WILDCARD {
+            return ComponentOrEntityTypeName::Wildcard;
+        }
+        match value.strip_prefix(COMPONENT_PREFIX) {
+            Some(component_name) => ComponentOrEntityTypeName::Component(ComponentName::new(component_name)),
+            None => ComponentOrEntityTypeName::EntityType(EntityTypeName::new(value)),
+        }
+    }
+}
+
+impl From<String> for ComponentOrEntityTypeName {
+    fn from(value: String) -> Self {
+        ComponentOrEntityTypeName::from(value.as_str())
+    }
+}
+
+impl From<ComponentOrEntityTypeName> for String {
+    fn from(value: ComponentOrEntityTypeName) -> Self {
+        value.to_string()
+    }
+}
+
+impl fmt::Display for ComponentOrEntityTypeName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ComponentOrEntityTypeName::EntityType(type_name) => write!(f, "{}", type_name),
+            ComponentOrEntityTypeName::Component(component_name) => write!(f, "{}{}", COMPONENT_PREFIX, component_name),
+            ComponentOrEntityTypeName::Wildcard => write!(f, "{}", WILDCARD),
+        }
+    }
+}
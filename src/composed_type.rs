@@ -0,0 +1,123 @@
+use std::fmt;
+
+use crate::type_inheritance::InheritanceConflict;
+use crate::{compose_components, validate_properties, Component, EntityType, Extension, PropertyMap, PropertyType, RelationType, ValidationReport};
+
+/// Implements the accessors of the effective properties and extensions of a composed type.
+macro_rules! impl_composed_type {
+    ($composed_type: ident, $kind: literal) => {
+        impl $composed_type {
+            /// Returns the effective property type with the given name.
+            pub fn get_property<S: AsRef<str>>(&self, property_name: S) -> Option<&PropertyType> {
+                self.properties.iter().find(|property_type| property_type.name == property_name.as_ref())
+            }
+
+            #[doc = concat!("Returns true, if the ", $kind, " or one of its components contains the property with the given name.")]
+            pub fn has_property<S: AsRef<str>>(&self, property_name: S) -> bool {
+                self.get_property(property_name).is_some()
+            }
+
+            #[doc = concat!("Returns true, if the ", $kind, " or one of its components contains the extension with the given name.")]
+            pub fn has_extension<S: AsRef<str>>(&self, extension_name: S) -> bool {
+                self.extensions.iter().any(|extension| extension.name == extension_name.as_ref())
+            }
+
+            /// Validates the property values against the effective property types.
+            pub fn validate(&self, properties: &PropertyMap) -> ValidationReport {
+                validate_properties(properties, &self.properties)
+            }
+        }
+    };
+}
+
+/// An entity type together with the effective properties and extensions of its components.
+#[derive(Clone, Debug)]
+pub struct ComposedEntityType {
+    /// The entity type.
+    pub entity_type: EntityType,
+
+    /// The properties of the components and the own properties of the entity type.
+    pub properties: Vec<PropertyType>,
+
+    /// The extensions of the components and the own extensions of the entity type.
+    pub extensions: Vec<Extension>,
+}
+
+impl_composed_type!(ComposedEntityType, "entity type");
+
+/// A relation type together with the effective properties and extensions of its components.
+#[derive(Clone, Debug)]
+pub struct ComposedRelationType {
+    /// The relation type.
+    pub relation_type: RelationType,
+
+    /// The properties of the components and the own properties of the relation type.
+    pub properties: Vec<PropertyType>,
+
+    /// The extensions of the components and the own extensions of the relation type.
+    pub extensions: Vec<Extension>,
+}
+
+impl_composed_type!(ComposedRelationType, "relation type");
+
+impl EntityType {
+    /// Returns the entity type with the effective properties and extensions of the given
+    /// components, see [`crate::TypeSystem::compose_entity_type`] for the rules.
+    pub fn compose(&self, components: &[Component]) -> Result<ComposedEntityType, ComposeError> {
+        let (properties, extensions) = compose_components(&self.components, &self.properties, &self.extensions, |component_name| {
+            components.iter().find(|component| component.name == *component_name)
+        })?;
+        Ok(ComposedEntityType {
+            entity_type: self.clone(),
+            properties,
+            extensions,
+        })
+    }
+}
+
+impl RelationType {
+    /// Returns the relation type with the effective properties and extensions of the given
+    /// components, see [`crate::TypeSystem::compose_relation_type`] for the rules.
+    pub fn compose(&self, components: &[Component]) -> Result<ComposedRelationType, ComposeError> {
+        let (properties, extensions) = compose_components(&self.components, &self.properties, &self.extensions, |component_name| {
+            components.iter().find(|component| component.name == *component_name)
+        })?;
+        Ok(ComposedRelationType {
+            relation_type: self.clone(),
+            properties,
+            extensions,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ComposeError {
+    /// The component with the given name doesn't exist.
+    MissingComponent(String),
+
+    /// The components or the type define the property with the given name with a different data
+    /// type or socket type.
+    ConflictingProperty(String),
+
+    /// Components define the extension with the given name differently.
+    ConflictingExtension(String),
+}
+
+impl From<InheritanceConflict> for ComposeError {
+    fn from(conflict: InheritanceConflict) -> Self {
+        match conflict {
+            InheritanceConflict::Property(name) => ComposeError::ConflictingProperty(name),
+            InheritanceConflict::Extension(name) => ComposeError::ConflictingExtension(name),
+        }
+    }
+}
+
+impl fmt::Display for ComposeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ComposeError::MissingComponent(name) => write!(f, "The component {} doesn't exist", name),
+            ComposeError::ConflictingProperty(name) => write!(f, "The property {} is defined differently", name),
+            ComposeError::ConflictingExtension(name) => write!(f, "The components define the extension {} differently", name),
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::RelationInstanceKey;
+
+/// The instance whose components or behaviours have changed.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CompositionTarget {
+    /// The entity instance with the given id.
+    Entity(Uuid),
+
+    /// The relation instance with the given key.
+    Relation(RelationInstanceKey),
+}
+
+/// The kind of change of the composition of an instance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompositionChange {
+    ComponentAdded,
+    ComponentRemoved,
+    BehaviourAdded,
+    BehaviourRemoved,
+}
+
+/// A component or behaviour has been added to or removed from an instance.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompositionEvent {
+    /// The instance which has been changed.
+    pub target: CompositionTarget,
+
+    /// The kind of change.
+    pub change: CompositionChange,
+
+    /// The name of the component or behaviour.
+    pub name: String,
+}
+
+/// Callback which is called after a component or behaviour has been added or removed.
+pub type CompositionObserver = Arc<dyn Fn(&CompositionEvent) + Send + Sync>;
@@ -0,0 +1,197 @@
+use std::fmt;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use uuid::Uuid;
+
+use crate::{
+    InstanceConversionError, PropertyInstanceGetter, PropertyMap, ReactiveEntityInstance, ReactiveRelationInstance, RelationInstance, RelationTypeName,
+};
+
+/// The name of the property which contains the name of the outbound property.
+pub const OUTBOUND_PROPERTY_NAME: &str = "outbound_property_name";
+
+/// The name of the property which contains the name of the inbound property.
+pub const INBOUND_PROPERTY_NAME: &str = "inbound_property_name";
+
+/// The name of the property which contains the buffer size.
+pub const BUFFER_SIZE: &str = "buffer_size";
+
+/// The name of the property which contains the name of the mapping function.
+pub const FUNCTION_NAME: &str = "function_name";
+
+/// The name of the property which contains the throttle in milliseconds.
+pub const THROTTLE: &str = "throttle";
+
+/// A connector propagates the values of a property of the outbound entity instance to a
+/// property of the inbound entity instance.
+pub type Connector = ConnectorDefinition;
+
+/// The standard properties of a connector-style relation instance, which propagates
+/// the values of a property of the outbound entity instance to a property of the
+/// inbound entity instance.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ConnectorDefinition {
+    /// The name of the property of the outbound entity instance.
+    pub outbound_property_name: String,
+
+    /// The name of the property of the inbound entity instance.
+    pub inbound_property_name: String,
+
+    /// The number of values which are buffered, if any.
+    #[serde(default)]
+    pub buffer_size: Option<usize>,
+
+    /// The name of the function which maps the propagated values, if any.
+    #[serde(default)]
+    pub function_name: Option<String>,
+
+    /// The minimum time between two propagated values in milliseconds, if any.
+    #[serde(default)]
+    pub throttle: Option<u64>,
+}
+
+impl ConnectorDefinition {
+    pub fn new<S: Into<String>>(outbound_property_name: S, inbound_property_name: S) -> ConnectorDefinition {
+        ConnectorDefinition {
+            outbound_property_name: outbound_property_name.into(),
+            inbound_property_name: inbound_property_name.into(),
+            buffer_size: None,
+            function_name: None,
+            throttle: None,
+        }
+    }
+
+    /// Reads the connector from the standard properties of a relation instance. Returns none, if
+    /// the property names are missing.
+    pub fn from_properties<P: PropertyInstanceGetter>(properties: &P) -> Option<ConnectorDefinition> {
+        Some(ConnectorDefinition {
+            outbound_property_name: properties.as_string(OUTBOUND_PROPERTY_NAME)?,
+            inbound_property_name: properties.as_string(INBOUND_PROPERTY_NAME)?,
+            buffer_size: properties.as_u64(BUFFER_SIZE).map(|buffer_size| buffer_size as usize),
+            function_name: properties.as_string(FUNCTION_NAME),
+            throttle: properties.as_u64(THROTTLE),
+        })
+    }
+
+    /// Sets the number of values which are buffered.
+    pub fn with_buffer_size(mut self, buffer_size: usize) -> ConnectorDefinition {
+        self.buffer_size = Some(buffer_size);
+        self
+    }
+
+    /// Sets the name of the function which maps the propagated values.
+    pub fn with_function_name<S: Into<String>>(mut self, function_name: S) -> ConnectorDefinition {
+        self.function_name = Some(function_name.into());
+        self
+    }
+
+    /// Sets the minimum time between two propagated values in milliseconds.
+    pub fn with_throttle(mut self, throttle: u64) -> ConnectorDefinition {
+        self.throttle = Some(throttle);
+        self
+    }
+
+    /// Returns an error, if the property names are empty, the buffer size or the throttle is
+    /// zero or the entity instances don't have the properties.
+    pub fn validate(&self, outbound: &ReactiveEntityInstance, inbound: &ReactiveEntityInstance) -> Result<(), ConnectorDefinitionError> {
+        if self.outbound_property_name.is_empty() {
+            return Err(ConnectorDefinitionError::EmptyPropertyName(OUTBOUND_PROPERTY_NAME));
+        }
+        if self.inbound_property_name.is_empty() {
+            return Err(ConnectorDefinitionError::EmptyPropertyName(INBOUND_PROPERTY_NAME));
+        }
+        if self.buffer_size == Some(0) {
+            return Err(ConnectorDefinitionError::InvalidBufferSize);
+        }
+        if self.throttle == Some(0) {
+            return Err(ConnectorDefinitionError::InvalidThrottle);
+        }
+        if self.function_name.as_ref().is_some_and(|function_name| function_name.is_empty()) {
+            return Err(ConnectorDefinitionError::EmptyPropertyName(FUNCTION_NAME));
+        }
+        if !outbound.properties.contains_key(self.outbound_property_name.as_str()) {
+            return Err(ConnectorDefinitionError::MissingOutboundProperty(self.outbound_property_name.clone()));
+        }
+        if !inbound.properties.contains_key(self.inbound_property_name.as_str()) {
+            return Err(ConnectorDefinitionError::MissingInboundProperty(self.inbound_property_name.clone()));
+        }
+        Ok(())
+    }
+
+    /// Returns the standard properties of the relation instance.
+    pub fn get_properties(&self) -> PropertyMap {
+        let mut properties = PropertyMap::new();
+        properties.insert(OUTBOUND_PROPERTY_NAME.to_string(), json!(self.outbound_property_name));
+        properties.insert(INBOUND_PROPERTY_NAME.to_string(), json!(self.inbound_property_name));
+        if let Some(buffer_size) = self.buffer_size {
+            properties.insert(BUFFER_SIZE.to_string(), json!(buffer_size));
+        }
+        if let Some(function_name) = &self.function_name {
+            properties.insert(FUNCTION_NAME.to_string(), json!(function_name));
+        }
+        if let Some(throttle) = self.throttle {
+            properties.insert(THROTTLE.to_string(), json!(throttle));
+        }
+        properties
+    }
+
+    /// Validates the definition and creates a relation instance with the standard properties
+    /// and the given additional properties.
+    pub fn create_relation_instance<S: Into<RelationTypeName>>(
+        &self,
+        outbound: Arc<ReactiveEntityInstance>,
+        type_name: S,
+        inbound: Arc<ReactiveEntityInstance>,
+        properties: PropertyMap,
+    ) -> Result<ReactiveRelationInstance, ConnectorDefinitionError> {
+        self.validate(&outbound, &inbound)?;
+        let mut all_properties = properties;
+        all_properties.extend(self.get_properties());
+        ReactiveRelationInstance::builder(outbound, type_name, inbound)
+            .properties(all_properties)
+            .build()
+            .map_err(ConnectorDefinitionError::InvalidRelationInstance)
+    }
+
+    /// Creates a non-reactive relation instance with the standard properties.
+    pub fn to_relation_instance<S: Into<RelationTypeName>>(&self, outbound_id: Uuid, type_name: S, inbound_id: Uuid) -> RelationInstance {
+        RelationInstance::new(outbound_id, type_name, inbound_id, self.get_properties())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConnectorDefinitionError {
+    /// The property name with the given name is empty.
+    EmptyPropertyName(&'static str),
+
+    /// The buffer size is zero.
+    InvalidBufferSize,
+
+    /// The throttle is zero.
+    InvalidThrottle,
+
+    /// The outbound entity instance doesn't have the property.
+    MissingOutboundProperty(String),
+
+    /// The inbound entity instance doesn't have the property.
+    MissingInboundProperty(String),
+
+    /// The relation instance can't be created, e.g. because the type name isn't a valid identifier.
+    InvalidRelationInstance(InstanceConversionError),
+}
+
+impl fmt::Display for ConnectorDefinitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConnectorDefinitionError::EmptyPropertyName(name) => write!(f, "The {} is empty", name),
+            ConnectorDefinitionError::InvalidBufferSize => write!(f, "The buffer size must be greater than zero"),
+            ConnectorDefinitionError::InvalidThrottle => write!(f, "The throttle must be greater than zero"),
+            ConnectorDefinitionError::MissingOutboundProperty(name) => write!(f, "The outbound entity instance has no property {}", name),
+            ConnectorDefinitionError::MissingInboundProperty(name) => write!(f, "The inbound entity instance has no property {}", name),
+            ConnectorDefinitionError::InvalidRelationInstance(error) => write!(f, "The relation instance can't be created: {}", error),
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{EntityInstance, Flow, PropertyMap, RelationInstance};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// A FNV-1a hasher. Unlike the hasher of the standard library the hashes are stable across
+/// platforms and releases, so they can be persisted.
+struct ContentHasher(u64);
+
+impl ContentHasher {
+    fn new() -> ContentHasher {
+        ContentHasher(FNV_OFFSET_BASIS)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    /// The length is written first, so that consecutive strings can't be confused.
+    fn write_str(&mut self, value: &str) {
+        self.write_u64(value.len() as u64);
+        self.write(value.as_bytes());
+    }
+
+    fn write_uuid(&mut self, id: &Uuid) {
+        self.write(id.as_bytes());
+    }
+
+    /// Writes the canonical form of the value. The keys of objects are sorted and numbers with
+    /// the same value are equal, regardless whether they are integers or floats.
+    fn write_value(&mut self, value: &Value) {
+        match value {
+            Value::Null => self.write(&[0]),
+            Value::Bool(b) => self.write(&[1, *b as u8]),
+            Value::Number(n) => {
+                self.write(&[2]);
+                match (n.as_i64(), n.as_u64(), n.as_f64()) {
+                    (Some(i), _, _) => self.write(&(i as i128).to_le_bytes()),
+                    (None, Some(u), _) => self.write(&(u as i128).to_le_bytes()),
+                    (None, None, Some(f)) if f.fract() == 0.0 && f >= i128::MIN as f64 && f < i128::MAX as f64 => self.write(&(f as i128).to_le_bytes()),
+                    (None, None, f) => self.write_u64(f.unwrap_or_default().to_bits()),
+                }
+            }
+            Value::String(s) => {
+                self.write(&[3]);
+                self.write_str(s);
+            }
+            Value::Array(values) => {
+                self.write(&[4]);
+                self.write_u64(values.len() as u64);
+                values.iter().for_each(|value| self.write_value(value));
+            }
+            Value::Object(object) => {
+                self.write(&[5]);
+                self.write_u64(object.len() as u64);
+                let mut entries: Vec<(&String, &Value)> = object.iter().collect();
+                entries.sort_by_key(|(key, _)| *key);
+                for (key, value) in entries {
+                    self.write_str(key);
+                    self.write_value(value);
+                }
+            }
+        }
+    }
+
+    fn write_properties(&mut self, properties: &PropertyMap) {
+        self.write_u64(properties.len() as u64);
+        let mut properties: Vec<(&String, &Value)> = properties.iter().collect();
+        properties.sort_by_key(|(name, _)| *name);
+        for (name, value) in properties {
+            self.write_str(name);
+            self.write_value(value);
+        }
+    }
+
+    /// Writes the labels in order.
+    fn write_labels(&mut self, labels: &[String]) {
+        let mut labels: Vec<&String> = labels.iter().collect();
+        labels.sort();
+        labels.dedup();
+        self.write_u64(labels.len() as u64);
+        labels.into_iter().for_each(|label| self.write_str(label));
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Returns a stable hash of the value. The keys of objects are sorted and numbers with the same
+/// value are equal, regardless whether they are integers or floats.
+pub fn value_content_hash(value: &Value) -> u64 {
+    let mut hasher = ContentHasher::new();
+    hasher.write_value(value);
+    hasher.finish()
+}
+
+impl EntityInstance {
+    /// Returns a stable hash over the type, the id, the properties and the labels. The order of
+    /// the properties, of the keys of objects and of the labels doesn't matter.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = ContentHasher::new();
+        hasher.write_str(self.type_name.as_str());
+        hasher.write_uuid(&self.id);
+        hasher.write_properties(&self.properties);
+        hasher.write_labels(&self.labels);
+        hasher.finish()
+    }
+}
+
+impl RelationInstance {
+    /// Returns a stable hash over the type, the ids of the outbound and inbound entity instances,
+    /// the properties and the labels. The order of the properties, of the keys of objects and of
+    /// the labels doesn't matter.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = ContentHasher::new();
+        hasher.write_uuid(&self.outbound_id);
+        hasher.write_str(self.type_name.as_str());
+        hasher.write_uuid(&self.inbound_id);
+        hasher.write_properties(&self.properties);
+        hasher.write_labels(&self.labels);
+        hasher.finish()
+    }
+}
+
+impl Flow {
+    /// Returns a stable hash over the type, the id and the content hashes of the entity
+    /// instances and relation instances. The order of the instances doesn't matter.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = ContentHasher::new();
+        hasher.write_str(&self.type_name);
+        hasher.write_uuid(&self.id);
+        let mut entity_hashes: Vec<u64> = self.entity_instances.iter().map(EntityInstance::content_hash).collect();
+        entity_hashes.sort_unstable();
+        hasher.write_u64(entity_hashes.len() as u64);
+        entity_hashes.into_iter().for_each(|hash| hasher.write_u64(hash));
+        let mut relation_hashes: Vec<u64> = self.relation_instances.iter().map(RelationInstance::content_hash).collect();
+        relation_hashes.sort_unstable();
+        hasher.write_u64(relation_hashes.len() as u64);
+        relation_hashes.into_iter().for_each(|hash| hasher.write_u64(hash));
+        hasher.finish()
+    }
+}
@@ -5,9 +5,14 @@ use std::fmt::Formatter;
 
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use uuid::Uuid;
+
+#[cfg(feature = "time")]
+use crate::value_to_date_time;
 
 /// Derived from serde_json::Value but without value payload.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Copy, Eq)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
 #[serde(rename_all = "lowercase")]
 pub enum DataType {
     /// Represents a JSON null value.
@@ -30,6 +35,15 @@ pub enum DataType {
 
     /// Represents any type (relations).
     Any,
+
+    /// Represents binary data as a base64 encoded JSON string.
+    Binary,
+
+    /// Represents a timestamp as an RFC 3339 formatted JSON string.
+    DateTime,
+
+    /// Represents an UUID as a JSON string.
+    Uuid,
 }
 
 impl DataType {
@@ -52,22 +66,159 @@ impl DataType {
             DataType::String => json!(""),
             DataType::Array => json!(Vec::<Value>::new()),
             DataType::Object => json!(HashMap::<String, Value>::new()),
+            DataType::DateTime => json!("1970-01-01T00:00:00Z"),
+            DataType::Uuid => uuid_to_value(Uuid::nil()),
             _ => json!(""),
         }
     }
+
+    /// Returns true, if the value is a valid representation of the data type.
+    pub fn is_valid(&self, value: &Value) -> bool {
+        match self {
+            DataType::Null => value.is_null(),
+            DataType::Bool => value.is_boolean(),
+            DataType::Number => value.is_number(),
+            DataType::String => value.is_string(),
+            DataType::Array => value.is_array(),
+            DataType::Object => value.is_object(),
+            DataType::Any => true,
+            DataType::Binary => value_to_binary(value).is_some(),
+            #[cfg(feature = "time")]
+            DataType::DateTime => value_to_date_time(value).is_ok(),
+            #[cfg(not(feature = "time"))]
+            DataType::DateTime => value.is_string(),
+            DataType::Uuid => value_to_uuid(value).is_some(),
+        }
+    }
+
+    /// Converts the value into a valid representation of the data type. Numbers and booleans
+    /// are converted into each other and from and into strings. Strings like `true`, `1`, `yes`
+    /// or `on` are converted into booleans and JSON encoded arrays like `[1, 2]` are parsed.
+    /// Returns none, if the value can't be converted.
+    pub fn coerce(&self, value: &Value) -> Option<Value> {
+        if self.is_valid(value) {
+            return Some(value.clone());
+        }
+        match (self, value) {
+            (DataType::Number, Value::Bool(b)) => Some(json!(*b as u8)),
+            (DataType::Number, Value::String(s)) => match s.trim().parse::<i64>() {
+                Ok(i) => Some(json!(i)),
+                Err(_) => s.trim().parse::<f64>().ok().and_then(serde_json::Number::from_f64).map(Value::Number),
+            },
+            (DataType::Bool, Value::Number(n)) => n.as_f64().map(|n| json!(n != 0.0)),
+            (DataType::Bool, Value::String(s)) => match s.trim().to_lowercase().as_str() {
+                "true" | "1" | "yes" | "on" => Some(json!(true)),
+                "false" | "0" | "no" | "off" => Some(json!(false)),
+                _ => None,
+            },
+            (DataType::String, Value::Number(_) | Value::Bool(_)) => Some(json!(value.to_string())),
+            (DataType::Array, Value::String(s)) => serde_json::from_str::<Value>(s.trim()).ok().filter(Value::is_array),
+            _ => None,
+        }
+    }
+
+    /// Returns true, if every value of this data type is also a valid value of the given data type.
+    /// Every data type widens to any and binary data, timestamps and UUIDs widen to strings. Values
+    /// which would have to be converted, e.g. booleans to numbers, don't widen.
+    pub fn is_widening_to(&self, target: &DataType) -> bool {
+        match (self, target) {
+            (source, target) if source == target => true,
+            (_, DataType::Any) => true,
+            (DataType::Binary | DataType::DateTime | DataType::Uuid, DataType::String) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Converts binary data into a base64 encoded JSON string.
+pub fn binary_to_value<T: AsRef<[u8]>>(data: T) -> Value {
+    Value::String(base64::encode(data))
+}
+
+/// Converts a base64 encoded JSON string into binary data.
+pub fn value_to_binary(value: &Value) -> Option<Vec<u8>> {
+    value.as_str().and_then(|data| base64::decode(data).ok())
+}
+
+/// Converts an UUID into a JSON string.
+pub fn uuid_to_value(uuid: Uuid) -> Value {
+    Value::String(uuid.to_string())
+}
+
+/// Converts a JSON string into an UUID.
+pub fn value_to_uuid(value: &Value) -> Option<Uuid> {
+    value.as_str().and_then(|uuid| Uuid::parse_str(uuid).ok())
+}
+
+/// Converts a number, a boolean or a numeric string into an integer. Floats are converted, if
+/// they have no fractional part.
+pub fn coerce_to_i64(value: &Value) -> Option<i64> {
+    let float_to_i64 = |f: f64| {
+        if f.fract() == 0.0 && f >= i64::MIN as f64 && f < i64::MAX as f64 {
+            Some(f as i64)
+        } else {
+            None
+        }
+    };
+    match value {
+        Value::Number(n) => n.as_i64().or_else(|| n.as_f64().and_then(float_to_i64)),
+        Value::Bool(b) => Some(*b as i64),
+        Value::String(s) => match s.trim().parse::<i64>() {
+            Ok(i) => Some(i),
+            Err(_) => s.trim().parse::<f64>().ok().and_then(float_to_i64),
+        },
+        _ => None,
+    }
+}
+
+/// Converts a number, a boolean or a numeric string into a float.
+pub fn coerce_to_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::Bool(b) => Some(*b as u8 as f64),
+        Value::String(s) => s.trim().parse::<f64>().ok(),
+        _ => None,
+    }
+}
+
+/// Converts a boolean, a number or a string like `true`, `1`, `yes` or `on` into a boolean.
+/// Numbers other than zero are true. The value is converted like `DataType::Bool.coerce`.
+pub fn coerce_to_bool(value: &Value) -> Option<bool> {
+    DataType::Bool.coerce(value).and_then(|value| value.as_bool())
+}
+
+/// Converts a string, a number or a boolean into a string.
+pub fn coerce_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(_) | Value::Bool(_) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+impl DataType {
+    /// Returns the data type with the given name, ignoring the case, or none, if the name isn't a
+    /// data type.
+    pub fn parse(value: &str) -> Option<DataType> {
+        match value.to_lowercase().as_str() {
+            "bool" => Some(Self::Bool),
+            "number" => Some(Self::Number),
+            "string" => Some(Self::String),
+            "array" => Some(Self::Array),
+            "object" => Some(Self::Object),
+            "any" => Some(Self::Any),
+            "binary" => Some(Self::Binary),
+            "datetime" => Some(Self::DateTime),
+            "uuid" => Some(Self::Uuid),
+            _ => None,
+        }
+    }
 }
 
+/// Unknown names are converted into [`DataType::String`], see [`DataType::parse`].
 impl From<&str> for DataType {
     fn from(value: &str) -> Self {
-        return match value.to_lowercase().as_str() {
-            "bool" => Self::Bool,
-            "number" => Self::Number,
-            "string" => Self::String,
-            "array" => Self::Array,
-            "object" => Self::Object,
-            "any" => Self::Any,
-            _ => Self::String,
-        };
+        DataType::parse(value).unwrap_or(Self::String)
     }
 }
 
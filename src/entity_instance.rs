@@ -1,11 +1,18 @@
-use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 
+#[cfg(feature = "graph-db")]
 use indradb::VertexProperties;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 use uuid::Uuid;
 
-use crate::{MutablePropertyInstanceSetter, PropertyInstanceGetter};
+#[cfg(feature = "graph-db")]
+use crate::instance_conversion::{convert_named_properties, convert_type_name};
+use crate::instance_labels::deserialize_labels;
+#[cfg(feature = "graph-db")]
+use crate::InstanceConversionError;
+use crate::{stable_id, BehaviourName, ComponentName, EntityTypeName, MutablePropertyInstanceSetter, PropertyDiff, PropertyInstanceGetter, PropertyMap};
 
 /// Entity instances represents an typed object which contains properties.
 ///
@@ -17,7 +24,7 @@ use crate::{MutablePropertyInstanceSetter, PropertyInstanceGetter};
 pub struct EntityInstance {
     /// The name of the entity type.
     #[serde(alias = "type")]
-    pub type_name: String,
+    pub type_name: EntityTypeName,
 
     /// The unique identifier of the entity instance.
     pub id: Uuid,
@@ -32,84 +39,176 @@ pub struct EntityInstance {
     /// a representation of a JSON. Therefore the value can be boolean, number, string,
     /// array or an object. For more information about the data types please look at
     /// https://docs.serde.rs/serde_json/value/enum.Value.html
-    #[serde(default = "HashMap::new")]
-    pub properties: HashMap<String, Value>,
+    #[serde(default = "PropertyMap::new")]
+    pub properties: PropertyMap,
+
+    /// The names of the components which are applied on the entity instance.
+    ///
+    /// By default, no components are applied.
+    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<ComponentName>,
+
+    /// The labels of the entity instance, e.g. `debug` or `level:3`.
+    ///
+    /// Labels group instances independently of their type. By default, the entity instance has
+    /// no labels. Duplicate labels are removed on deserialization.
+    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty", deserialize_with = "deserialize_labels")]
+    pub labels: Vec<String>,
+
+    /// The names of the behaviours which were applied on the entity instance.
+    ///
+    /// The behaviours are only persisted on request, so that the runtime can re-attach them.
+    /// By default, no behaviours are persisted.
+    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    pub behaviours: Vec<BehaviourName>,
 }
 
 impl EntityInstance {
     /// Constructs a new entity instance with the given type, id and properties
-    pub fn new<S: Into<String>>(type_name: S, id: Uuid, properties: HashMap<String, Value>) -> EntityInstance {
+    pub fn new<S: Into<EntityTypeName>>(type_name: S, id: Uuid, properties: PropertyMap) -> EntityInstance {
         EntityInstance {
             type_name: type_name.into(),
             id,
             description: String::new(),
             properties,
+            components: Vec::new(),
+            labels: Vec::new(),
+            behaviours: Vec::new(),
         }
     }
 
+    /// Constructs a new entity instance with the stable id of the key within the namespace, so the
+    /// entity instance has the same id across restarts and machines. See [`stable_namespace`].
+    pub fn new_with_stable_id<K: AsRef<str>, S: Into<EntityTypeName>>(namespace: &Uuid, key: K, type_name: S, properties: PropertyMap) -> EntityInstance {
+        EntityInstance::new(type_name, stable_id(namespace, key), properties)
+    }
+
     /// Constructs a new entity instance with the given type and id but without properties
-    pub fn new_without_properties<S: Into<String>>(type_name: S, id: Uuid) -> EntityInstance {
+    pub fn new_without_properties<S: Into<EntityTypeName>>(type_name: S, id: Uuid) -> EntityInstance {
         EntityInstance {
             type_name: type_name.into(),
             id,
             description: String::new(),
-            properties: HashMap::new(),
+            properties: PropertyMap::new(),
+            components: Vec::new(),
+            labels: Vec::new(),
+            behaviours: Vec::new(),
         }
     }
+
+    /// Returns the changes which turn the properties of this entity instance into the properties
+    /// of the given entity instance.
+    pub fn diff(&self, other: &EntityInstance) -> PropertyDiff {
+        PropertyDiff::new(&self.properties, &other.properties)
+    }
+
+    /// Applies the changes to the properties of this entity instance.
+    pub fn apply(&mut self, diff: PropertyDiff) {
+        diff.apply_to(&mut self.properties);
+    }
 }
 
+#[cfg(feature = "graph-db")]
 impl From<VertexProperties> for EntityInstance {
     fn from(properties: VertexProperties) -> Self {
-        let type_name = properties.vertex.t.to_string();
+        let type_name = EntityTypeName::new(properties.vertex.t.to_string());
         // let type_name = properties.vertex.t.0.clone();
         let id = properties.vertex.id;
-        let properties: HashMap<String, Value> = properties.props.iter().map(|p| (p.name.to_string(), p.value.clone())).collect();
+        let properties: PropertyMap = properties.props.iter().map(|p| (p.name.to_string(), p.value.clone())).collect();
         EntityInstance {
             type_name,
             id,
             description: String::new(),
             properties,
+            components: Vec::new(),
+            labels: Vec::new(),
+            behaviours: Vec::new(),
         }
     }
 }
 
+/// Fails, if the type name or a property name is invalid or if a property occurs multiple times.
+#[cfg(feature = "graph-db")]
+impl TryFrom<&VertexProperties> for EntityInstance {
+    type Error = InstanceConversionError;
+
+    fn try_from(properties: &VertexProperties) -> Result<Self, InstanceConversionError> {
+        Ok(EntityInstance {
+            type_name: EntityTypeName::new(convert_type_name(&properties.vertex.t)?),
+            id: properties.vertex.id,
+            description: String::new(),
+            properties: convert_named_properties(&properties.props)?,
+            components: Vec::new(),
+            labels: Vec::new(),
+            behaviours: Vec::new(),
+        })
+    }
+}
+
 impl PropertyInstanceGetter for EntityInstance {
-    fn get<S: Into<String>>(&self, property_name: S) -> Option<Value> {
-        self.properties.get(&property_name.into()).cloned()
+    fn get<S: AsRef<str>>(&self, property_name: S) -> Option<Value> {
+        self.properties.get(property_name.as_ref()).cloned()
+    }
+
+    fn with_property<S: AsRef<str>, R, F: FnOnce(&Value) -> R>(&self, property_name: S, f: F) -> Option<R> {
+        self.properties.get(property_name.as_ref()).map(f)
     }
 
-    fn as_bool<S: Into<String>>(&self, property_name: S) -> Option<bool> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_bool())
+    fn as_bool<S: AsRef<str>>(&self, property_name: S) -> Option<bool> {
+        self.properties.get(property_name.as_ref()).and_then(|p| p.as_bool())
     }
 
-    fn as_u64<S: Into<String>>(&self, property_name: S) -> Option<u64> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_u64())
+    fn as_u64<S: AsRef<str>>(&self, property_name: S) -> Option<u64> {
+        self.properties.get(property_name.as_ref()).and_then(|p| p.as_u64())
     }
 
-    fn as_i64<S: Into<String>>(&self, property_name: S) -> Option<i64> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_i64())
+    fn as_i64<S: AsRef<str>>(&self, property_name: S) -> Option<i64> {
+        self.properties.get(property_name.as_ref()).and_then(|p| p.as_i64())
     }
 
-    fn as_f64<S: Into<String>>(&self, property_name: S) -> Option<f64> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_f64())
+    fn as_f64<S: AsRef<str>>(&self, property_name: S) -> Option<f64> {
+        self.properties.get(property_name.as_ref()).and_then(|p| p.as_f64())
     }
 
-    fn as_string<S: Into<String>>(&self, property_name: S) -> Option<String> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_str().map(|s| s.to_string()))
+    fn as_string<S: AsRef<str>>(&self, property_name: S) -> Option<String> {
+        self.properties.get(property_name.as_ref()).and_then(|p| p.as_str().map(|s| s.to_string()))
     }
 
-    fn as_array<S: Into<String>>(&self, property_name: S) -> Option<Vec<Value>> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_array().map(Vec::clone))
+    fn as_array<S: AsRef<str>>(&self, property_name: S) -> Option<Vec<Value>> {
+        self.properties.get(property_name.as_ref()).and_then(|p| p.as_array().cloned())
     }
 
-    fn as_object<S: Into<String>>(&self, property_name: S) -> Option<Map<String, Value>> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_object().map(Map::clone))
+    fn as_object<S: AsRef<str>>(&self, property_name: S) -> Option<Map<String, Value>> {
+        self.properties.get(property_name.as_ref()).and_then(|p| p.as_object().cloned())
     }
 }
 
 impl MutablePropertyInstanceSetter for EntityInstance {
-    fn set<S: Into<String>>(&mut self, property_name: S, value: Value) {
-        let property_value = self.properties.get_mut(&property_name.into()).unwrap();
+    fn set<S: AsRef<str>>(&mut self, property_name: S, value: Value) {
+        let property_value = self.properties.get_mut(property_name.as_ref()).unwrap();
         *property_value = value
     }
 }
+
+/// Entity instances are equal, if the ids and the properties are equal.
+impl PartialEq for EntityInstance {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && self.properties == other.properties
+    }
+}
+
+impl Eq for EntityInstance {}
+
+/// The hash of an entity instance is the hash of its id.
+impl Hash for EntityInstance {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// Formats the entity instance as `type_name(id)`.
+impl fmt::Display for EntityInstance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}({})", self.type_name, self.id)
+    }
+}
@@ -0,0 +1,113 @@
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{
+    generate_id, stable_id, validate_identifier, validate_properties, BehaviourName, ComponentName, EntityInstance, EntityType, EntityTypeName, IdGenerator,
+    PropertyMap, ValidationReport,
+};
+
+/// Builds an entity instance step by step.
+#[derive(Clone, Debug, Default)]
+pub struct EntityInstanceBuilder {
+    type_name: EntityTypeName,
+    id: Option<Uuid>,
+    description: String,
+    properties: PropertyMap,
+    components: Vec<ComponentName>,
+    labels: Vec<String>,
+    behaviours: Vec<BehaviourName>,
+}
+
+impl EntityInstanceBuilder {
+    pub fn new() -> EntityInstanceBuilder {
+        EntityInstanceBuilder::default()
+    }
+
+    /// Sets the name of the entity type.
+    pub fn type_name<S: Into<EntityTypeName>>(mut self, type_name: S) -> EntityInstanceBuilder {
+        self.type_name = type_name.into();
+        self
+    }
+
+    /// Sets the id. If no id is set, an id is generated by the global id generator.
+    pub fn id(mut self, id: Uuid) -> EntityInstanceBuilder {
+        self.id = Some(id);
+        self
+    }
+
+    /// Sets an id which is generated by the given id generator.
+    pub fn generated_id(self, id_generator: &dyn IdGenerator) -> EntityInstanceBuilder {
+        self.id(id_generator.generate())
+    }
+
+    /// Sets the stable id of the key within the namespace.
+    pub fn stable_id<K: AsRef<str>>(self, namespace: &Uuid, key: K) -> EntityInstanceBuilder {
+        self.id(stable_id(namespace, key))
+    }
+
+    /// Sets the description.
+    pub fn description<S: Into<String>>(mut self, description: S) -> EntityInstanceBuilder {
+        self.description = description.into();
+        self
+    }
+
+    /// Sets the value of the property with the given name.
+    pub fn property<S: Into<String>>(mut self, property_name: S, value: Value) -> EntityInstanceBuilder {
+        self.properties.insert(property_name.into(), value);
+        self
+    }
+
+    /// Adds the component with the given name.
+    pub fn component<C: Into<ComponentName>>(mut self, component: C) -> EntityInstanceBuilder {
+        self.components.push(component.into());
+        self
+    }
+
+    /// Adds the label, e.g. `debug` or `level:3`. Labels which have been added already are ignored.
+    pub fn label<S: Into<String>>(mut self, label: S) -> EntityInstanceBuilder {
+        let label = label.into();
+        if !self.labels.contains(&label) {
+            self.labels.push(label);
+        }
+        self
+    }
+
+    /// Adds the behaviour with the given name, which has to be re-attached by the runtime.
+    pub fn behaviour<B: Into<BehaviourName>>(mut self, behaviour: B) -> EntityInstanceBuilder {
+        self.behaviours.push(behaviour.into());
+        self
+    }
+
+    /// Builds the entity instance.
+    pub fn build(self) -> EntityInstance {
+        EntityInstance {
+            type_name: self.type_name,
+            id: self.id.unwrap_or_else(generate_id),
+            description: self.description,
+            properties: self.properties,
+            components: self.components,
+            labels: self.labels,
+            behaviours: self.behaviours,
+        }
+    }
+
+    /// Builds the entity instance and validates it against the entity type. If no type name
+    /// is set, the name of the entity type is used.
+    pub fn build_checked(mut self, entity_type: &EntityType) -> Result<EntityInstance, ValidationReport> {
+        if self.type_name.as_str().is_empty() {
+            self.type_name = entity_type.name.clone();
+        }
+        let mut report = ValidationReport::new();
+        report.check(validate_identifier(&self.type_name));
+        report.merge(validate_properties(&self.properties, &entity_type.properties));
+        report.into_result()?;
+        Ok(self.build())
+    }
+}
+
+impl EntityInstance {
+    /// Returns a builder for an entity instance.
+    pub fn builder() -> EntityInstanceBuilder {
+        EntityInstanceBuilder::new()
+    }
+}
@@ -0,0 +1,103 @@
+use crate::PropertyInstanceSetter;
+use crate::ReactiveEntityInstance;
+use crate::ReactivePropertyContainer;
+
+/// Specifies which property values are taken over from the replaced entity instance.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PropertyReplacementPolicy {
+    /// The property values of the new entity instance are kept.
+    Keep,
+
+    /// The values of the properties which exist in both entity instances are taken over.
+    #[default]
+    CopyCommon,
+
+    /// The values of all properties are taken over. Properties which don't exist in the
+    /// new entity instance are added.
+    CopyAll,
+}
+
+/// Specifies what is migrated from a replaced entity instance to the new entity instance.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReplacementPolicy {
+    /// Which property values are taken over.
+    pub properties: PropertyReplacementPolicy,
+
+    /// If true, the components are taken over.
+    pub components: bool,
+
+    /// If true, the behaviours are taken over.
+    pub behaviours: bool,
+
+    /// If true, the observers of the properties are taken over. The streams of the properties
+    /// which exist in both entity instances are shared. The observers which have been registered
+    /// on the new entity instance are kept, but observers which are subscribed to the streams of
+    /// the new entity instance directly are dropped.
+    pub observers: bool,
+
+    /// If true, the relation instances of the replaced entity instance are attached to the new entity instance.
+    pub relations: bool,
+}
+
+impl Default for ReplacementPolicy {
+    fn default() -> Self {
+        ReplacementPolicy {
+            properties: PropertyReplacementPolicy::CopyCommon,
+            components: true,
+            behaviours: true,
+            observers: true,
+            relations: true,
+        }
+    }
+}
+
+/// Migrates the property values, components, behaviours and observers of the old entity
+/// instance to the new entity instance according to the policy. Values are taken over
+/// without propagation.
+pub fn migrate_entity_instance(old_entity: &ReactiveEntityInstance, new_entity: &ReactiveEntityInstance, policy: &ReplacementPolicy) {
+    if std::ptr::eq(old_entity, new_entity) {
+        return;
+    }
+    for property_instance in old_entity.properties.iter() {
+        let name = property_instance.key();
+        match policy.properties {
+            PropertyReplacementPolicy::Keep => {}
+            PropertyReplacementPolicy::CopyCommon => {
+                if new_entity.properties.contains_key(name) {
+                    new_entity.set_no_propagate(name.as_ref(), property_instance.get());
+                }
+            }
+            PropertyReplacementPolicy::CopyAll => {
+                if new_entity.properties.contains_key(name) {
+                    new_entity.set_no_propagate(name.as_ref(), property_instance.get());
+                } else {
+                    new_entity.add_property(name, property_instance.get());
+                }
+            }
+        }
+        if policy.observers {
+            if let Some(mut new_property_instance) = new_entity.properties.get_mut(name) {
+                // The observers which have been registered on the new entity instance are kept
+                property_instance
+                    .prioritized_observers
+                    .take_over(&property_instance.stream.read().unwrap(), &new_property_instance.prioritized_observers);
+                new_property_instance.stream = property_instance.stream.clone();
+                new_property_instance.prioritized_observers = property_instance.prioritized_observers.clone();
+                new_property_instance.take_observer_handles(&property_instance);
+            }
+        }
+    }
+    if policy.observers {
+        new_entity.observers.take_over(&old_entity.observers, &new_entity.properties);
+    }
+    if policy.components {
+        for component in old_entity.components.iter() {
+            new_entity.add_component(component.key());
+        }
+    }
+    if policy.behaviours {
+        for behaviour in old_entity.behaviours.iter() {
+            new_entity.add_behaviour(behaviour.key());
+        }
+    }
+}
@@ -1,22 +1,33 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::str::FromStr;
+use std::sync::Arc;
 
-use indradb::Identifier;
-use serde::{Deserialize, Serialize};
+use serde::de::{self, DeserializeOwned};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::extension::Extension;
-use crate::PropertyType;
+use crate::extension::{get_extension, Extension, ExtensionError};
+use crate::type_inheritance::{resolve_inheritance, InheritableType, InheritanceError, InheritedDefinitions};
+use crate::Identifier;
+use crate::{
+    find_property_group, get_ordered_property_groups, get_property_group_members, instantiate_inner_flow, validate_identifier, CategoryPath, ComponentName,
+    EntityTypeName, Flow, PropertyGroup, PropertyType, ReactiveEntityInstance, ReactiveFlow, ReactiveFlowConstructionError, SocketType, ValidationError,
+    INNER_FLOW_EXTENSION,
+};
 
 /// Entity types defines the type of an entity instance.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(remote = "Self")]
 pub struct EntityType {
     /// The name of the entity type.
     ///
     /// The name is the unique identifier for entity types.
-    pub name: String,
+    #[serde(alias = "type_name", alias = "type")]
+    pub name: EntityTypeName,
 
-    /// The entity type belongs to the given group of entity types.
-    #[serde(default = "String::new")]
-    pub group: String,
+    /// The category of the entity type, e.g. `logical/gates`. A flat group name is a category with a single segment.
+    #[serde(default)]
+    pub group: CategoryPath,
 
     /// Textual description of the entity type.
     #[serde(default = "String::new")]
@@ -24,7 +35,7 @@ pub struct EntityType {
 
     /// The names of the components of the entity type.
     #[serde(default = "Vec::new")]
-    pub components: Vec<String>,
+    pub components: Vec<ComponentName>,
 
     /// The properties which are defined by the entity type.
     #[serde(default = "Vec::new")]
@@ -34,16 +45,36 @@ pub struct EntityType {
     #[serde(default = "Vec::new")]
     pub extensions: Vec<Extension>,
 
+    /// The sections of property panels.
+    #[serde(default = "Vec::new")]
+    pub property_groups: Vec<PropertyGroup>,
+
+    /// The names of the entity types this entity type extends. The components, properties
+    /// and extensions of the base entity types are inherited.
+    #[serde(default = "Vec::new")]
+    pub extends: Vec<String>,
+
+    /// The version of the entity type. Migrations transform the properties of instances
+    /// of older versions.
+    #[serde(default)]
+    pub version: u32,
+
+    /// Abstract entity types are only used for inheritance or grouping and cannot be instantiated.
+    #[serde(default, rename = "abstract")]
+    pub is_abstract: bool,
+
     #[serde(skip)]
     pub t: Identifier,
 }
 
 impl EntityType {
-    pub fn new<S: Into<String>>(
-        name: S,
+    /// Constructs a new entity type. Panics, if the name isn't a valid type name. A group which isn't
+    /// a valid category path is normalized, see [`CategoryPath::from_group`].
+    pub fn new<N: Into<EntityTypeName>, S: Into<String>>(
+        name: N,
         group: S,
         description: S,
-        components: Vec<String>,
+        components: Vec<ComponentName>,
         properties: Vec<PropertyType>,
         extensions: Vec<Extension>,
     ) -> EntityType {
@@ -51,30 +82,226 @@ impl EntityType {
         let t = Identifier::from_str(name.as_str()).unwrap();
         EntityType {
             name,
-            group: group.into(),
+            group: CategoryPath::from_group(&group.into()),
             description: description.into(),
             components,
             properties,
             extensions,
+            property_groups: Vec::new(),
+            extends: Vec::new(),
+            is_abstract: false,
+            version: 0,
             t,
         }
     }
 
+    /// Constructs a new entity type. Fails, if the name isn't a valid type name, see [`crate::sanitize_type_name`].
+    /// The group is normalized like in `new`, see [`CategoryPath::from_group`].
+    pub fn try_new<N: Into<EntityTypeName>, S: Into<String>>(
+        name: N,
+        group: S,
+        description: S,
+        components: Vec<ComponentName>,
+        properties: Vec<PropertyType>,
+        extensions: Vec<Extension>,
+    ) -> Result<EntityType, ValidationError> {
+        let name = name.into();
+        validate_identifier(name.as_str())?;
+        Ok(EntityType::new(name, group.into(), description.into(), components, properties, extensions))
+    }
+
+    /// Returns true, if entity instances of this type can be created.
+    pub fn is_instantiable(&self) -> bool {
+        !self.is_abstract
+    }
+
     /// Returns true, if the entity type is a component with the given name.
-    pub fn is_a<S: Into<String>>(&self, component_name: S) -> bool {
+    pub fn is_a<C: Into<ComponentName>>(&self, component_name: C) -> bool {
         self.components.contains(&component_name.into())
     }
 
     /// Returns true, if the entity type contains an own property with the given name.
-    /// Doesn't respect properties from potential components.
+    /// Doesn't respect properties from potential components, see [`EntityType::compose`].
     pub fn has_own_property<S: Into<String>>(&self, property_name: S) -> bool {
         let property_name = property_name.into();
         self.properties.iter().any(|p| p.name == property_name)
     }
 
+    /// Returns the own properties which act as input socket.
+    pub fn inputs(&self) -> impl Iterator<Item = &PropertyType> {
+        self.properties.iter().filter(|property_type| property_type.socket_type == SocketType::Input)
+    }
+
+    /// Returns the own properties which act as output socket.
+    pub fn outputs(&self) -> impl Iterator<Item = &PropertyType> {
+        self.properties.iter().filter(|property_type| property_type.socket_type == SocketType::Output)
+    }
+
+    /// Returns true, if the entity type belongs to the given category or to one of its sub categories.
+    /// Returns false, if the category isn't a valid category path.
+    pub fn is_in_category_subtree<C: TryInto<CategoryPath>>(&self, category: C) -> bool {
+        category.try_into().is_ok_and(|category| self.group.is_in_subtree_of(&category))
+    }
+
     /// Returns true, if the entity type contains an extension with the given name.
     pub fn has_own_extension<S: Into<String>>(&self, extension_name: S) -> bool {
         let extension_name = extension_name.into();
         self.extensions.iter().any(|extension| extension.name == extension_name)
     }
+
+    /// Returns the deserialized payload of the extension with the given name.
+    pub fn get_extension<T: DeserializeOwned, S: AsRef<str>>(&self, extension_name: S) -> Result<T, ExtensionError> {
+        get_extension(&self.extensions, extension_name)
+    }
+
+    /// Returns the property groups sorted by order.
+    pub fn get_property_groups(&self) -> Vec<&PropertyGroup> {
+        get_ordered_property_groups(&self.property_groups)
+    }
+
+    /// Returns the group of the own property with the given name.
+    pub fn get_property_group_of<S: AsRef<str>>(&self, property_name: S) -> Option<&PropertyGroup> {
+        find_property_group(&self.property_groups, &self.properties, property_name)
+    }
+
+    /// Returns the own properties of the group with the given name in display order.
+    pub fn get_property_group_members<S: AsRef<str>>(&self, group_name: S) -> Vec<&PropertyType> {
+        get_property_group_members(&self.properties, group_name)
+    }
+
+    /// Sets the version of the entity type.
+    pub fn with_version(mut self, version: u32) -> EntityType {
+        self.version = version;
+        self
+    }
+
+    /// Extends the entity type with the given name.
+    pub fn with_extends<S: Into<String>>(mut self, base_type_name: S) -> EntityType {
+        self.extends.push(base_type_name.into());
+        self
+    }
+
+    /// Returns the flattened entity type which contains the inherited components, properties,
+    /// extensions and property groups of all base entity types. Own definitions override inherited ones
+    /// with the same name. Base entity types must not define the same property with a different
+    /// data type or socket type or the same extension with a different value.
+    pub fn resolve(&self, entity_types: &[EntityType]) -> Result<EntityType, EntityTypeResolveError> {
+        resolve_inheritance(self, entity_types, &mut Vec::new()).map_err(EntityTypeResolveError::from)
+    }
+
+    /// Returns the default inner flow which is declared by the inner flow extension. Returns an
+    /// error, if the extension doesn't exist or doesn't contain a flow.
+    pub fn get_inner_flow(&self) -> Result<Flow, ExtensionError> {
+        self.get_extension(INNER_FLOW_EXTENSION)
+    }
+
+    /// Instantiates the default inner flow for the given entity instance of this entity type.
+    /// Returns none, if the entity type doesn't declare an inner flow.
+    pub fn instantiate_inner_flow(&self, entity_instance: Arc<ReactiveEntityInstance>) -> Option<Result<ReactiveFlow, ReactiveFlowConstructionError>> {
+        match self.get_inner_flow() {
+            Ok(inner_flow) => Some(instantiate_inner_flow(&inner_flow, entity_instance)),
+            Err(ExtensionError::Missing(_)) => None,
+            Err(error) => Some(Err(ReactiveFlowConstructionError::InvalidInnerFlow(error))),
+        }
+    }
+}
+
+/// Entity types are equal, if the names are equal.
+impl PartialEq for EntityType {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for EntityType {}
+
+impl Hash for EntityType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+    }
+}
+
+impl Serialize for EntityType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        EntityType::serialize(self, serializer)
+    }
+}
+
+/// The identifier is constructed from the name of the entity type.
+impl<'de> Deserialize<'de> for EntityType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut entity_type = EntityType::deserialize(deserializer)?;
+        entity_type.t = Identifier::new(entity_type.name.as_str()).map_err(|_| {
+            de::Error::custom(ValidationError::InvalidIdentifier {
+                identifier: entity_type.name.to_string(),
+            })
+        })?;
+        Ok(entity_type)
+    }
+}
+
+/// Formats the entity type as its name, e.g. `player`.
+impl fmt::Display for EntityType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum EntityTypeResolveError {
+    /// The base entity type with the given name doesn't exist.
+    MissingBaseType(String),
+
+    /// The entity type with the given name extends itself.
+    Cycle(String),
+
+    /// Base entity types define the property with the given name differently.
+    ConflictingProperty(String),
+
+    /// Base entity types define the extension with the given name differently.
+    ConflictingExtension(String),
+}
+
+impl fmt::Display for EntityTypeResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntityTypeResolveError::MissingBaseType(name) => write!(f, "The base entity type {} doesn't exist", name),
+            EntityTypeResolveError::Cycle(name) => write!(f, "The entity type {} extends itself", name),
+            EntityTypeResolveError::ConflictingProperty(name) => write!(f, "The base entity types define the property {} differently", name),
+            EntityTypeResolveError::ConflictingExtension(name) => write!(f, "The base entity types define the extension {} differently", name),
+        }
+    }
+}
+
+impl InheritableType for EntityType {
+    type Name = EntityTypeName;
+
+    fn inheritance_name(&self) -> &EntityTypeName {
+        &self.name
+    }
+
+    fn has_name(&self, name: &str) -> bool {
+        self.name == *name
+    }
+
+    fn inherited_definitions(&mut self) -> InheritedDefinitions<'_> {
+        InheritedDefinitions {
+            extends: &mut self.extends,
+            components: &mut self.components,
+            properties: &mut self.properties,
+            extensions: &mut self.extensions,
+            property_groups: Some(&mut self.property_groups),
+        }
+    }
+}
+
+impl From<InheritanceError> for EntityTypeResolveError {
+    fn from(error: InheritanceError) -> Self {
+        match error {
+            InheritanceError::MissingBaseType(name) => EntityTypeResolveError::MissingBaseType(name),
+            InheritanceError::Cycle(name) => EntityTypeResolveError::Cycle(name),
+            InheritanceError::ConflictingProperty(name) => EntityTypeResolveError::ConflictingProperty(name),
+            InheritanceError::ConflictingExtension(name) => EntityTypeResolveError::ConflictingExtension(name),
+        }
+    }
 }
@@ -1,6 +1,11 @@
+use std::fmt;
+
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::{Version, VersionReq};
+
 /// Extension on a type. The extension allows to extend information
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Extension {
@@ -9,10 +14,68 @@ pub struct Extension {
 
     /// The extension as JSON representation.
     pub extension: Value,
+
+    /// The version of the format of the extension payload.
+    #[serde(default)]
+    pub version: Option<Version>,
 }
 
 impl Extension {
     pub fn new<S: Into<String>>(name: S, extension: Value) -> Extension {
-        Extension { name: name.into(), extension }
+        Extension {
+            name: name.into(),
+            extension,
+            version: None,
+        }
+    }
+
+    /// Constructs an extension whose payload has the given format version.
+    pub fn new_with_version<S: Into<String>>(name: S, extension: Value, version: Version) -> Extension {
+        Extension {
+            name: name.into(),
+            extension,
+            version: Some(version),
+        }
+    }
+
+    /// Returns true, if the version of the extension satisfies the requirement. Extensions
+    /// without version are treated as version `0.0.0`.
+    pub fn is_compatible_with(&self, req: &VersionReq) -> bool {
+        match &self.version {
+            Some(version) => req.matches(version),
+            None => req.matches(&Version::new(0, 0, 0)),
+        }
+    }
+
+    /// Deserializes the extension payload.
+    pub fn get<T: DeserializeOwned>(&self) -> Result<T, ExtensionError> {
+        serde_json::from_value(self.extension.clone()).map_err(|error| ExtensionError::Invalid(self.name.clone(), error))
+    }
+}
+
+/// Returns the deserialized payload of the extension with the given name.
+pub fn get_extension<T: DeserializeOwned, S: AsRef<str>>(extensions: &[Extension], name: S) -> Result<T, ExtensionError> {
+    extensions
+        .iter()
+        .find(|extension| extension.name == name.as_ref())
+        .ok_or_else(|| ExtensionError::Missing(name.as_ref().to_string()))?
+        .get()
+}
+
+#[derive(Debug)]
+pub enum ExtensionError {
+    /// The extension with the given name doesn't exist.
+    Missing(String),
+
+    /// The payload of the extension with the given name cannot be deserialized.
+    Invalid(String, serde_json::Error),
+}
+
+impl fmt::Display for ExtensionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExtensionError::Missing(name) => write!(f, "The extension {} doesn't exist", name),
+            ExtensionError::Invalid(name, error) => write!(f, "The extension {} is invalid: {}", name, error),
+        }
     }
 }
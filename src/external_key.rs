@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{Component, EntityInstance, EntityType, ReactiveEntityInstance, RelationInstanceKey, RelationType};
+
+/// The default separator of the parts of a string key.
+pub const DEFAULT_KEY_SEPARATOR: &str = "/";
+
+/// Specifies how the id of an instance is formatted as external key.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyStrategy {
+    /// The hyphenated uuid, e.g. `67e55044-10b1-426f-9247-bb680e5fe0c8`.
+    #[default]
+    Hyphenated,
+
+    /// The uuid without hyphens, e.g. `67e5504410b1426f9247bb680e5fe0c8`.
+    Simple,
+
+    /// The type name followed by the hyphenated uuid, e.g. `player/67e55044-10b1-426f-9247-bb680e5fe0c8`.
+    TypePrefixed,
+}
+
+/// The key of an instance or type in an external database.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[serde(untagged)]
+pub enum ExternalKey {
+    /// A string primary key.
+    Single(String),
+
+    /// A composite key, e.g. the outbound id, the type name and the inbound id of a relation instance.
+    Composite(Vec<String>),
+}
+
+impl ExternalKey {
+    /// Returns the parts of the key.
+    pub fn parts(&self) -> Vec<&str> {
+        match self {
+            ExternalKey::Single(key) => vec![key.as_str()],
+            ExternalKey::Composite(parts) => parts.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+impl fmt::Display for ExternalKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.parts().join(DEFAULT_KEY_SEPARATOR))
+    }
+}
+
+/// The error of mapping identities to external keys or of parsing external keys.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ExternalKeyError {
+    /// The key cannot be parsed.
+    InvalidKey(String),
+
+    /// The name contains the separator, so the string key would be ambiguous.
+    SeparatorInName(String),
+
+    /// Different identities are mapped to the same key.
+    Collision(String),
+}
+
+impl fmt::Display for ExternalKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExternalKeyError::InvalidKey(key) => write!(f, "{} is not a valid key", key),
+            ExternalKeyError::SeparatorInName(name) => write!(f, "The name {} contains the key separator", name),
+            ExternalKeyError::Collision(key) => write!(f, "The key {} is used by different identities", key),
+        }
+    }
+}
+
+impl std::error::Error for ExternalKeyError {}
+
+/// Maps the identities of instances and types to the keys of an external database.
+///
+/// Persistence adapters which share a mapper agree on the key formats.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ExternalKeyMapper {
+    /// The format of instance ids.
+    #[serde(default)]
+    pub strategy: KeyStrategy,
+
+    /// The separator of the parts of string keys.
+    #[serde(default = "default_key_separator")]
+    pub separator: String,
+}
+
+fn default_key_separator() -> String {
+    DEFAULT_KEY_SEPARATOR.to_string()
+}
+
+impl ExternalKeyMapper {
+    pub fn new(strategy: KeyStrategy) -> ExternalKeyMapper {
+        ExternalKeyMapper {
+            strategy,
+            separator: default_key_separator(),
+        }
+    }
+
+    pub fn with_separator<S: Into<String>>(mut self, separator: S) -> ExternalKeyMapper {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Returns the key of the instance with the given type and id.
+    pub fn instance_key(&self, type_name: &str, id: Uuid) -> Result<String, ExternalKeyError> {
+        match self.strategy {
+            KeyStrategy::Hyphenated => Ok(id.to_hyphenated().to_string()),
+            KeyStrategy::Simple => Ok(id.to_simple().to_string()),
+            KeyStrategy::TypePrefixed => Ok(format!("{}{}{}", self.check_name(type_name)?, self.separator, id.to_hyphenated())),
+        }
+    }
+
+    /// Returns the key of the entity instance.
+    pub fn entity_key(&self, entity_instance: &EntityInstance) -> Result<String, ExternalKeyError> {
+        self.instance_key(entity_instance.type_name.as_str(), entity_instance.id)
+    }
+
+    /// Returns the key of the reactive entity instance.
+    pub fn reactive_entity_key(&self, entity_instance: &ReactiveEntityInstance) -> Result<String, ExternalKeyError> {
+        self.instance_key(entity_instance.type_name.as_str(), entity_instance.id)
+    }
+
+    /// Returns the id of the instance with the given key.
+    pub fn parse_instance_key(&self, key: &str) -> Result<Uuid, ExternalKeyError> {
+        let id = match self.strategy {
+            KeyStrategy::Hyphenated | KeyStrategy::Simple => key,
+            KeyStrategy::TypePrefixed => key
+                .rsplit_once(self.separator.as_str())
+                .map(|(_, id)| id)
+                .ok_or_else(|| ExternalKeyError::InvalidKey(key.to_string()))?,
+        };
+        Uuid::parse_str(id).map_err(|_| ExternalKeyError::InvalidKey(key.to_string()))
+    }
+
+    /// Returns the composite key of the relation instance: the key of the outbound id, the name
+    /// of the relation type and the key of the inbound id.
+    pub fn relation_key(&self, relation_instance_key: &RelationInstanceKey) -> ExternalKey {
+        ExternalKey::Composite(vec![
+            self.format_id(relation_instance_key.outbound_id),
+            relation_instance_key.type_name.clone(),
+            self.format_id(relation_instance_key.inbound_id),
+        ])
+    }
+
+    /// Returns the composite key of the relation instance as string primary key.
+    pub fn relation_string_key(&self, relation_instance_key: &RelationInstanceKey) -> Result<String, ExternalKeyError> {
+        self.check_name(&relation_instance_key.type_name)?;
+        Ok(self.relation_key(relation_instance_key).parts().join(&self.separator))
+    }
+
+    /// Returns the relation instance key of the given string primary key.
+    pub fn parse_relation_string_key(&self, key: &str) -> Result<RelationInstanceKey, ExternalKeyError> {
+        let parts: Vec<&str> = key.split(self.separator.as_str()).collect();
+        match parts.as_slice() {
+            [outbound_id, type_name, inbound_id] => {
+                let outbound_id = Uuid::parse_str(outbound_id).map_err(|_| ExternalKeyError::InvalidKey(key.to_string()))?;
+                let inbound_id = Uuid::parse_str(inbound_id).map_err(|_| ExternalKeyError::InvalidKey(key.to_string()))?;
+                Ok(RelationInstanceKey::new(outbound_id, *type_name, inbound_id))
+            }
+            _ => Err(ExternalKeyError::InvalidKey(key.to_string())),
+        }
+    }
+
+    /// Returns the key of the component.
+    pub fn component_key(&self, component: &Component) -> Result<String, ExternalKeyError> {
+        self.type_key("component", component.name.as_str())
+    }
+
+    /// Returns the key of the entity type.
+    pub fn entity_type_key(&self, entity_type: &EntityType) -> Result<String, ExternalKeyError> {
+        self.type_key("entity_type", entity_type.name.as_str())
+    }
+
+    /// Returns the key of the relation type.
+    pub fn relation_type_key(&self, relation_type: &RelationType) -> Result<String, ExternalKeyError> {
+        self.type_key("relation_type", relation_type.type_name.as_str())
+    }
+
+    /// Returns an error, if different identities are mapped to the same key.
+    pub fn check_collisions<T: PartialEq, I: IntoIterator<Item = (String, T)>>(&self, keys: I) -> Result<(), ExternalKeyError> {
+        let mut ids: HashMap<String, T> = HashMap::new();
+        for (key, id) in keys {
+            match ids.get(&key) {
+                Some(existing) if *existing != id => return Err(ExternalKeyError::Collision(key)),
+                Some(_) => {}
+                None => {
+                    ids.insert(key, id);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn format_id(&self, id: Uuid) -> String {
+        match self.strategy {
+            KeyStrategy::Simple => id.to_simple().to_string(),
+            KeyStrategy::Hyphenated | KeyStrategy::TypePrefixed => id.to_hyphenated().to_string(),
+        }
+    }
+
+    fn type_key(&self, kind: &str, name: &str) -> Result<String, ExternalKeyError> {
+        Ok(format!("{}{}{}", kind, self.separator, self.check_name(name)?))
+    }
+
+    fn check_name<'a>(&self, name: &'a str) -> Result<&'a str, ExternalKeyError> {
+        if !self.separator.is_empty() && name.contains(self.separator.as_str()) {
+            return Err(ExternalKeyError::SeparatorInName(name.to_string()));
+        }
+        Ok(name)
+    }
+}
+
+impl Default for ExternalKeyMapper {
+    fn default() -> Self {
+        ExternalKeyMapper::new(KeyStrategy::default())
+    }
+}
@@ -0,0 +1,94 @@
+use std::sync::{Arc, LazyLock, RwLock};
+
+use crate::{ReactiveEntityInstance, ReactiveRelationInstance, WILDCARD};
+
+/// Callback which is called after an entity instance has been constructed from its entity type,
+/// e.g. to add components, set computed defaults or attach behaviours.
+pub type EntityInstanceHook = Arc<dyn Fn(&ReactiveEntityInstance) + Send + Sync>;
+
+/// Callback which is called after a relation instance has been constructed from its relation type.
+pub type RelationInstanceHook = Arc<dyn Fn(&ReactiveRelationInstance) + Send + Sync>;
+
+/// Registry of the creation hooks per entity type and relation type.
+///
+/// The hooks are invoked by `ReactiveEntityInstance::new_from_type` and
+/// `ReactiveRelationInstance::new_from_type` in the order of their registration. Hooks which
+/// are registered for the type name `*` are invoked for all types before the hooks of the type.
+#[derive(Default)]
+pub struct FactoryRegistry {
+    entity_hooks: RwLock<Vec<(String, EntityInstanceHook)>>,
+    relation_hooks: RwLock<Vec<(String, RelationInstanceHook)>>,
+}
+
+static FACTORY_REGISTRY: LazyLock<FactoryRegistry> = LazyLock::new(FactoryRegistry::default);
+
+/// Returns the global factory registry which is used by the `new_from_type` constructors.
+pub fn get_factory_registry() -> &'static FactoryRegistry {
+    &FACTORY_REGISTRY
+}
+
+impl FactoryRegistry {
+    pub fn new() -> FactoryRegistry {
+        FactoryRegistry::default()
+    }
+
+    /// Registers a hook for entity instances of the entity type with the given name.
+    pub fn register_entity_hook<S: Into<String>, F: Fn(&ReactiveEntityInstance) + Send + Sync + 'static>(&self, type_name: S, hook: F) {
+        self.entity_hooks.write().unwrap().push((type_name.into(), Arc::new(hook)));
+    }
+
+    /// Registers a hook for relation instances of the relation type with the given name.
+    pub fn register_relation_hook<S: Into<String>, F: Fn(&ReactiveRelationInstance) + Send + Sync + 'static>(&self, type_name: S, hook: F) {
+        self.relation_hooks.write().unwrap().push((type_name.into(), Arc::new(hook)));
+    }
+
+    /// Unregisters the hooks of the entity type with the given name.
+    pub fn unregister_entity_hooks<S: AsRef<str>>(&self, type_name: S) {
+        self.entity_hooks.write().unwrap().retain(|(name, _)| name != type_name.as_ref());
+    }
+
+    /// Unregisters the hooks of the relation type with the given name.
+    pub fn unregister_relation_hooks<S: AsRef<str>>(&self, type_name: S) {
+        self.relation_hooks.write().unwrap().retain(|(name, _)| name != type_name.as_ref());
+    }
+
+    /// Returns true, if hooks are registered for the entity type with the given name.
+    pub fn has_entity_hooks<S: AsRef<str>>(&self, type_name: S) -> bool {
+        self.entity_hooks.read().unwrap().iter().any(|(name, _)| name == type_name.as_ref())
+    }
+
+    /// Returns true, if hooks are registered for the relation type with the given name.
+    pub fn has_relation_hooks<S: AsRef<str>>(&self, type_name: S) -> bool {
+        self.relation_hooks.read().unwrap().iter().any(|(name, _)| name == type_name.as_ref())
+    }
+
+    /// Invokes the hooks of the entity type of the entity instance.
+    pub fn apply_entity_hooks(&self, entity_instance: &ReactiveEntityInstance) {
+        // The hooks are invoked after the lock has been released, so hooks can register hooks
+        let hooks = FactoryRegistry::get_hooks(&self.entity_hooks, entity_instance.type_name.as_str());
+        for hook in hooks {
+            hook(entity_instance);
+        }
+    }
+
+    /// Invokes the hooks of the relation type of the relation instance.
+    pub fn apply_relation_hooks(&self, relation_instance: &ReactiveRelationInstance) {
+        let hooks = FactoryRegistry::get_hooks(&self.relation_hooks, relation_instance.type_name.as_str());
+        for hook in hooks {
+            hook(relation_instance);
+        }
+    }
+
+    /// Unregisters all hooks.
+    pub fn clear(&self) {
+        self.entity_hooks.write().unwrap().clear();
+        self.relation_hooks.write().unwrap().clear();
+    }
+
+    fn get_hooks<T: Clone>(hooks: &RwLock<Vec<(String, T)>>, type_name: &str) -> Vec<T> {
+        let reader = hooks.read().unwrap();
+        let wildcard_hooks = reader.iter().filter(|(name, _)| name == WILDCARD);
+        let type_hooks = reader.iter().filter(|(name, _)| name == type_name && name != WILDCARD);
+        wildcard_hooks.chain(type_hooks).map(|(_, hook)| hook.clone()).collect()
+    }
+}
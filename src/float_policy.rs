@@ -0,0 +1,112 @@
+use std::cell::Cell;
+use std::fmt;
+use std::sync::RwLock;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value};
+
+/// The string representation of NaN.
+pub const NAN: &str = "NaN";
+
+/// The string representation of positive infinity.
+pub const INFINITY: &str = "Infinity";
+
+/// The string representation of negative infinity.
+pub const NEG_INFINITY: &str = "-Infinity";
+
+/// Specifies how NaN and infinite values are stored in properties, because JSON
+/// numbers can't represent them.
+///
+/// Finite values always survive serialization, deserialization and propagation
+/// without loss of precision.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NonFiniteFloatPolicy {
+    /// Non-finite values are rejected.
+    Reject,
+
+    /// Non-finite values are stored as null.
+    #[default]
+    Null,
+
+    /// Non-finite values are stored as the strings `NaN`, `Infinity` and `-Infinity`.
+    String,
+}
+
+static NON_FINITE_FLOAT_POLICY: RwLock<NonFiniteFloatPolicy> = RwLock::new(NonFiniteFloatPolicy::Null);
+
+thread_local! {
+    /// The policy of the current thread, which overrides the global policy.
+    static THREAD_NON_FINITE_FLOAT_POLICY: Cell<Option<NonFiniteFloatPolicy>> = const { Cell::new(None) };
+}
+
+/// Sets the global policy for non-finite values.
+pub fn set_non_finite_float_policy(policy: NonFiniteFloatPolicy) {
+    *NON_FINITE_FLOAT_POLICY.write().unwrap() = policy;
+}
+
+/// Returns the policy for non-finite values of the current thread or the global policy.
+pub fn get_non_finite_float_policy() -> NonFiniteFloatPolicy {
+    THREAD_NON_FINITE_FLOAT_POLICY
+        .with(Cell::get)
+        .unwrap_or_else(|| *NON_FINITE_FLOAT_POLICY.read().unwrap())
+}
+
+/// Restores the previous policy of the thread, even if the function panics.
+struct ThreadPolicyGuard(Option<NonFiniteFloatPolicy>);
+
+impl Drop for ThreadPolicyGuard {
+    fn drop(&mut self) {
+        THREAD_NON_FINITE_FLOAT_POLICY.with(|policy| policy.set(self.0));
+    }
+}
+
+/// Calls the function with the given policy for non-finite values. The policy only applies to
+/// the current thread, the global policy and other threads are not affected.
+pub fn with_non_finite_float_policy<R, F: FnOnce() -> R>(policy: NonFiniteFloatPolicy, f: F) -> R {
+    let _guard = ThreadPolicyGuard(THREAD_NON_FINITE_FLOAT_POLICY.with(|thread_policy| thread_policy.replace(Some(policy))));
+    f()
+}
+
+/// A non-finite value has been rejected.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NonFiniteFloatError(pub f64);
+
+impl fmt::Display for NonFiniteFloatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The non-finite value {} cannot be stored", self.0)
+    }
+}
+
+/// Converts the f64 into a JSON value using the global policy for non-finite values.
+pub fn f64_to_value(value: f64) -> Result<Value, NonFiniteFloatError> {
+    f64_to_value_with_policy(value, get_non_finite_float_policy())
+}
+
+/// Converts the f64 into a JSON value using the given policy for non-finite values.
+pub fn f64_to_value_with_policy(value: f64, policy: NonFiniteFloatPolicy) -> Result<Value, NonFiniteFloatError> {
+    if let Some(number) = Number::from_f64(value) {
+        return Ok(Value::Number(number));
+    }
+    match policy {
+        NonFiniteFloatPolicy::Reject => Err(NonFiniteFloatError(value)),
+        NonFiniteFloatPolicy::Null => Ok(Value::Null),
+        NonFiniteFloatPolicy::String if value.is_nan() => Ok(Value::String(NAN.to_string())),
+        NonFiniteFloatPolicy::String if value.is_sign_positive() => Ok(Value::String(INFINITY.to_string())),
+        NonFiniteFloatPolicy::String => Ok(Value::String(NEG_INFINITY.to_string())),
+    }
+}
+
+/// Converts a JSON number or a string encoded non-finite value into a f64.
+pub fn value_to_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(s) => match s.as_str() {
+            NAN => Some(f64::NAN),
+            INFINITY => Some(f64::INFINITY),
+            NEG_INFINITY => Some(f64::NEG_INFINITY),
+            _ => None,
+        },
+        _ => None,
+    }
+}
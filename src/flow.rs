@@ -1,10 +1,29 @@
+//! Flows and the types which operate on flows.
+
+use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::{EntityInstance, ReactiveFlow, RelationInstance};
+use crate::{EntityInstance, ReactiveEntityInstance, RelationInstance, RelationInstanceKey};
+
+pub use crate::flow_checkpoint::*;
+#[cfg(feature = "graph-db")]
+pub use crate::flow_conversion::*;
+pub use crate::flow_expectation::*;
+pub use crate::flow_layer::*;
+pub use crate::flow_loading::*;
+pub use crate::flow_property_override::*;
+pub use crate::flow_template::*;
+pub use crate::flow_variable::*;
+pub use crate::graph_constraint::*;
+pub use crate::graph_partition::*;
+pub use crate::inner_flow::*;
+pub use crate::instance_group::*;
+pub use crate::projection::*;
+pub use crate::reactive_flow::*;
 
 #[derive(Debug)]
 pub struct FlowCreationError;
@@ -55,6 +74,24 @@ pub struct Flow {
     /// By default, no relation instances are contained in this flow.
     #[serde(default = "Vec::new", alias = "relations")]
     pub relation_instances: Vec<RelationInstance>,
+
+    /// The layers of the flow.
+    ///
+    /// The instances of disabled layers are excluded from reactive instantiation.
+    #[serde(default = "Vec::new")]
+    pub layers: Vec<FlowLayer>,
+
+    /// The versions of the entity types and relation types at the time the flow was stored.
+    ///
+    /// Types which are missing are considered to be current, so the instances of these types are
+    /// not migrated. Flows which have been stored with an older version of a type have to record
+    /// that version.
+    #[serde(default = "HashMap::new")]
+    pub type_versions: HashMap<String, u32>,
+
+    /// The named inputs and outputs of the flow.
+    #[serde(default = "Vec::new")]
+    pub variables: Vec<FlowVariable>,
 }
 
 impl Flow {
@@ -62,72 +99,176 @@ impl Flow {
     pub fn from_instance_with_name<S: Into<String>>(wrapper_entity_instance: EntityInstance, name: S) -> Flow {
         Flow {
             id: wrapper_entity_instance.id,
-            type_name: wrapper_entity_instance.type_name.clone(),
+            type_name: wrapper_entity_instance.type_name.to_string(),
             name: name.into(),
             description: String::new(),
             entity_instances: vec![wrapper_entity_instance],
             relation_instances: Vec::new(),
+            layers: Vec::new(),
+            type_versions: HashMap::new(),
+            variables: Vec::new(),
+        }
+    }
+
+    /// Returns the version of the type with the given name at the time the flow was stored or
+    /// none, if the version hasn't been recorded.
+    pub fn get_type_version<S: AsRef<str>>(&self, type_name: S) -> Option<u32> {
+        self.type_versions.get(type_name.as_ref()).copied()
+    }
+
+    /// Returns true, if an entity instance or a relation instance of the flow is of the type with the given name.
+    pub fn uses_type<S: AsRef<str>>(&self, type_name: S) -> bool {
+        let type_name = type_name.as_ref();
+        self.entity_instances.iter().any(|entity_instance| entity_instance.type_name == type_name)
+            || self.relation_instances.iter().any(|relation_instance| relation_instance.type_name == type_name)
+    }
+
+    /// Returns the layer with the given name.
+    pub fn get_layer<S: Into<String>>(&self, name: S) -> Option<&FlowLayer> {
+        let name = name.into();
+        self.layers.iter().find(|layer| layer.name == name)
+    }
+
+    /// Enables or disables the layer with the given name. Returns false, if the layer doesn't exist.
+    pub fn set_layer_enabled<S: Into<String>>(&mut self, name: S, enabled: bool) -> bool {
+        let name = name.into();
+        match self.layers.iter_mut().find(|layer| layer.name == name) {
+            Some(layer) => {
+                layer.enabled = enabled;
+                true
+            }
+            None => false,
         }
     }
+
+    /// Returns the variable with the given name.
+    pub fn get_variable<S: AsRef<str>>(&self, name: S) -> Option<&FlowVariable> {
+        self.variables.iter().find(|variable| variable.name == name.as_ref())
+    }
+
+    /// Returns the input variables.
+    pub fn get_inputs(&self) -> Vec<&FlowVariable> {
+        self.variables.iter().filter(|variable| variable.is_input()).collect()
+    }
+
+    /// Returns the output variables.
+    pub fn get_outputs(&self) -> Vec<&FlowVariable> {
+        self.variables.iter().filter(|variable| variable.is_output()).collect()
+    }
+
+    /// Returns an error, if the names of the variables are not unique or the properties of the
+    /// variables don't exist in the flow.
+    pub fn validate_variables(&self) -> Result<(), FlowVariableError> {
+        for (index, variable) in self.variables.iter().enumerate() {
+            if self.variables[..index].iter().any(|other| other.name == variable.name) {
+                return Err(FlowVariableError::Duplicate(variable.name.clone()));
+            }
+            let entity_instance = self
+                .entity_instances
+                .iter()
+                .find(|entity_instance| entity_instance.id == variable.entity_id)
+                .ok_or_else(|| FlowVariableError::MissingEntityInstance(variable.name.clone(), variable.entity_id))?;
+            if !entity_instance.properties.contains_key(&variable.property_name) {
+                return Err(FlowVariableError::MissingProperty(variable.name.clone(), variable.property_name.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns true, if the entity instance isn't assigned to a disabled layer.
+    ///
+    /// The wrapper entity instance is always enabled.
+    pub fn is_entity_enabled(&self, id: Uuid) -> bool {
+        id == self.id || !self.layers.iter().any(|layer| !layer.enabled && layer.has_entity(id))
+    }
+
+    /// Returns true, if the relation instance isn't assigned to a disabled layer and
+    /// both the outbound and the inbound entity instances are enabled.
+    pub fn is_relation_enabled(&self, relation_instance: &RelationInstance) -> bool {
+        let key = RelationInstanceKey::from(relation_instance);
+        self.is_entity_enabled(relation_instance.outbound_id)
+            && self.is_entity_enabled(relation_instance.inbound_id)
+            && !self.layers.iter().any(|layer| !layer.enabled && layer.has_relation(&key))
+    }
 }
 
 impl From<EntityInstance> for Flow {
     fn from(wrapper_entity_instance: EntityInstance) -> Flow {
         Flow {
             id: wrapper_entity_instance.id,
-            type_name: wrapper_entity_instance.type_name.clone(),
+            type_name: wrapper_entity_instance.type_name.to_string(),
             name: String::new(),
             description: String::new(),
             entity_instances: vec![wrapper_entity_instance],
             relation_instances: Vec::new(),
+            layers: Vec::new(),
+            type_versions: HashMap::new(),
+            variables: Vec::new(),
         }
     }
 }
 
-impl TryFrom<ReactiveFlow> for Flow {
-    type Error = FlowCreationError;
+impl Flow {
+    /// Converts the reactive flow and persists the names of the behaviours which are applied on
+    /// the entity instances and relation instances, so that they can be re-attached after a restart.
+    pub fn from_reactive_with_behaviours(reactive_flow: &ReactiveFlow) -> Result<Flow, FlowCreationError> {
+        Flow::from_reactive(reactive_flow, true)
+    }
 
-    fn try_from(reactive_flow: ReactiveFlow) -> Result<Self, FlowCreationError> {
+    fn from_reactive(reactive_flow: &ReactiveFlow, with_behaviours: bool) -> Result<Flow, FlowCreationError> {
         let wrapper = reactive_flow.get_entity(reactive_flow.id);
         if wrapper.is_none() {
             return Err(FlowCreationError);
         }
         let wrapper = wrapper.unwrap();
-        let entity_instance: EntityInstance = wrapper.clone().into();
-        let mut flow = Flow::from(entity_instance);
-        flow.description = wrapper.description.clone();
+        let to_entity_instance = |entity: Arc<ReactiveEntityInstance>| {
+            if with_behaviours {
+                EntityInstance::from_reactive_with_behaviours(entity)
+            } else {
+                EntityInstance::from(entity)
+            }
+        };
+        let mut flow = Flow::from_instance_with_name(to_entity_instance(wrapper.clone()), reactive_flow.name.clone());
+        flow.description = wrapper.description.get();
+        flow.variables = reactive_flow.variables.read().unwrap().clone();
+        flow.layers = reactive_flow.layers.clone();
+        flow.type_versions = reactive_flow.type_versions.clone();
         reactive_flow.entity_instances.read().unwrap().iter().for_each(|(_, entity)| {
             if entity.id != reactive_flow.id {
-                flow.entity_instances.push(entity.clone().into());
+                flow.entity_instances.push(to_entity_instance(entity.clone()));
             }
         });
         reactive_flow.relation_instances.read().unwrap().iter().for_each(|(_, relation_instance)| {
-            flow.relation_instances.push(relation_instance.clone().into());
+            flow.relation_instances.push(if with_behaviours {
+                RelationInstance::from_reactive_with_behaviours(relation_instance.clone())
+            } else {
+                RelationInstance::from(relation_instance.clone())
+            });
+        });
+        flow.entity_instances.extend(reactive_flow.disabled_entity_instances.iter().cloned());
+        flow.relation_instances.extend(reactive_flow.disabled_relation_instances.iter().cloned());
+        // Runtime overrides are not part of the authored flow content
+        reactive_flow.overridden_values.read().unwrap().iter().for_each(|((id, property_name), value)| {
+            if let Some(entity_instance) = flow.entity_instances.iter_mut().find(|entity_instance| entity_instance.id == *id) {
+                entity_instance.properties.insert(property_name.clone(), value.clone());
+            }
         });
         Ok(flow)
     }
 }
 
+impl TryFrom<ReactiveFlow> for Flow {
+    type Error = FlowCreationError;
+
+    fn try_from(reactive_flow: ReactiveFlow) -> Result<Self, FlowCreationError> {
+        Flow::from_reactive(&reactive_flow, false)
+    }
+}
+
 impl TryFrom<Arc<ReactiveFlow>> for Flow {
     type Error = FlowCreationError;
 
     fn try_from(reactive_flow: Arc<ReactiveFlow>) -> Result<Self, FlowCreationError> {
-        let wrapper = reactive_flow.get_entity(reactive_flow.id);
-        if wrapper.is_none() {
-            return Err(FlowCreationError);
-        }
-        let wrapper = wrapper.unwrap();
-        let entity_instance: EntityInstance = wrapper.clone().into();
-        let mut flow = Flow::from(entity_instance);
-        flow.description = wrapper.description.clone();
-        reactive_flow.entity_instances.read().unwrap().iter().for_each(|(_, entity)| {
-            if entity.id != reactive_flow.id {
-                flow.entity_instances.push(entity.clone().into());
-            }
-        });
-        reactive_flow.relation_instances.read().unwrap().iter().for_each(|(_, relation_instance)| {
-            flow.relation_instances.push(relation_instance.clone().into());
-        });
-        Ok(flow)
+        Flow::from_reactive(&reactive_flow, false)
     }
 }
@@ -0,0 +1,244 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::hash::Hash;
+use std::sync::Arc;
+
+use dashmap::{DashMap, DashSet};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::EdgeKey;
+use crate::{
+    ComponentName, EntityInstance, Flow, FlowCreationError, Instant, PropertyMap, ReactiveEntityInstance, ReactiveFlow, ReactivePropertyInstance,
+    ReactiveRelationInstance, RelationInstance, RelationInstanceKey,
+};
+
+/// The sorted components and labels of an instance.
+pub type InstanceTags = (Vec<ComponentName>, Vec<String>);
+
+/// The state of a reactive flow at a point in time. The changes of the flow since then can be
+/// collected as flow document.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FlowCheckpoint {
+    /// The monotonic time of the checkpoint.
+    pub instant: Instant,
+
+    /// The number of entity instances which had been added to the flow at the time of the checkpoint.
+    pub entities_added: usize,
+
+    /// The number of relation instances which had been added to the flow at the time of the checkpoint.
+    pub relations_added: usize,
+
+    /// The number of entity instances which had been removed from the flow at the time of the checkpoint.
+    pub entities_removed: usize,
+
+    /// The number of relation instances which had been removed from the flow at the time of the checkpoint.
+    pub relations_removed: usize,
+
+    /// The components and labels of the entity instances at the time of the checkpoint.
+    pub entity_tags: HashMap<Uuid, InstanceTags>,
+
+    /// The components and labels of the relation instances at the time of the checkpoint.
+    pub relation_tags: HashMap<RelationInstanceKey, InstanceTags>,
+}
+
+/// The changes of a reactive flow since a checkpoint.
+#[derive(Clone, Debug)]
+pub struct FlowChanges {
+    /// The added and the changed instances as flow document.
+    pub flow: Flow,
+
+    /// The ids of the entity instances which have been removed since the checkpoint.
+    pub removed_entity_instances: Vec<Uuid>,
+
+    /// The keys of the relation instances which have been removed since the checkpoint.
+    pub removed_relation_instances: Vec<RelationInstanceKey>,
+}
+
+/// Returns the properties which have been set since the given time.
+fn get_properties_modified_since(properties: &DashMap<Arc<str>, ReactivePropertyInstance>, instant: Instant) -> PropertyMap {
+    let mut modified_properties: Vec<(String, Value)> = properties
+        .iter()
+        .filter(|property_instance| matches!(property_instance.last_modified(), Some(last_modified) if last_modified >= instant))
+        .map(|property_instance| (property_instance.key().to_string(), property_instance.get()))
+        .collect();
+    modified_properties.sort_by(|(a, _), (b, _)| a.cmp(b));
+    modified_properties.into_iter().collect()
+}
+
+/// Returns the sorted components and labels.
+fn get_tags(components: &DashSet<ComponentName>, labels: &DashSet<String>) -> InstanceTags {
+    (get_sorted(components), get_sorted(labels))
+}
+
+/// Returns the names in order.
+fn get_sorted<T: Clone + Eq + Hash + Ord>(names: &DashSet<T>) -> Vec<T> {
+    let mut names: Vec<T> = names.iter().map(|name| name.clone()).collect();
+    names.sort();
+    names
+}
+
+impl ReactiveFlow {
+    /// Returns a checkpoint of the current state of the flow.
+    pub fn checkpoint(&self) -> FlowCheckpoint {
+        FlowCheckpoint {
+            instant: Instant::now(),
+            entities_added: self.entities_added.read().unwrap().len(),
+            relations_added: self.relations_added.read().unwrap().len(),
+            entities_removed: self.entities_removed.read().unwrap().len(),
+            relations_removed: self.relations_removed.read().unwrap().len(),
+            entity_tags: self
+                .entity_instances
+                .read()
+                .unwrap()
+                .values()
+                .map(|entity_instance| (entity_instance.id, get_tags(&entity_instance.components, &entity_instance.labels)))
+                .collect(),
+            relation_tags: self
+                .relation_instances
+                .read()
+                .unwrap()
+                .values()
+                .map(|relation_instance| {
+                    (
+                        RelationInstanceKey::from(relation_instance.as_ref()),
+                        get_tags(&relation_instance.components, &relation_instance.labels),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the changes since the checkpoint.
+    ///
+    /// Entity instances and relation instances which have been added since the checkpoint are
+    /// contained in the flow document with all properties. Other instances are contained with
+    /// the properties which have been set since the checkpoint and with their components and
+    /// labels; instances without changed properties, components or labels are omitted. The
+    /// wrapper entity instance is always contained. Runtime overrides are replaced by the values
+    /// defined by the flow. Instances which have been removed since the checkpoint and are not
+    /// part of the flow anymore are reported as removed. Returns an error, if the wrapper entity
+    /// instance doesn't exist.
+    pub fn changes_since(&self, checkpoint: &FlowCheckpoint) -> Result<FlowChanges, FlowCreationError> {
+        let wrapper = self.get_wrapper_entity_instance().ok_or(FlowCreationError)?;
+        let added_entities: HashSet<Uuid> = self.entities_added.read().unwrap().iter().skip(checkpoint.entities_added).copied().collect();
+        let added_relations: HashSet<EdgeKey> = self.relations_added.read().unwrap().iter().skip(checkpoint.relations_added).cloned().collect();
+        let entity_changes = |entity_instance: &Arc<ReactiveEntityInstance>| {
+            if added_entities.contains(&entity_instance.id) {
+                return Some(EntityInstance::from(entity_instance.clone()));
+            }
+            let properties = get_properties_modified_since(&entity_instance.properties, checkpoint.instant);
+            let tags = get_tags(&entity_instance.components, &entity_instance.labels);
+            if properties.is_empty() && checkpoint.entity_tags.get(&entity_instance.id) == Some(&tags) && entity_instance.id != self.id {
+                return None;
+            }
+            let (components, labels) = tags;
+            Some(EntityInstance {
+                type_name: entity_instance.type_name.clone(),
+                id: entity_instance.id,
+                description: entity_instance.description.get(),
+                properties,
+                components,
+                labels,
+                behaviours: Vec::new(),
+            })
+        };
+        let relation_changes = |relation_instance: &Arc<ReactiveRelationInstance>| {
+            if added_relations.contains(relation_instance.key()) {
+                return Some(RelationInstance::from(relation_instance.clone()));
+            }
+            let properties = get_properties_modified_since(&relation_instance.properties, checkpoint.instant);
+            let tags = get_tags(&relation_instance.components, &relation_instance.labels);
+            if properties.is_empty() && checkpoint.relation_tags.get(&RelationInstanceKey::from(relation_instance.as_ref())) == Some(&tags) {
+                return None;
+            }
+            let (components, labels) = tags;
+            Some(RelationInstance {
+                outbound_id: relation_instance.outbound.id,
+                type_name: relation_instance.type_name.clone(),
+                inbound_id: relation_instance.inbound.id,
+                description: relation_instance.description.get(),
+                properties,
+                components,
+                labels,
+                behaviours: Vec::new(),
+            })
+        };
+        let mut flow = Flow::from(entity_changes(&wrapper).ok_or(FlowCreationError)?);
+        flow.description = wrapper.description.get();
+        let mut entity_instances: Vec<EntityInstance> = self
+            .entity_instances
+            .read()
+            .unwrap()
+            .values()
+            .filter(|entity_instance| entity_instance.id != self.id)
+            .filter_map(entity_changes)
+            .collect();
+        entity_instances.sort_by_key(|entity_instance| entity_instance.id);
+        flow.entity_instances.extend(entity_instances);
+        flow.relation_instances = self.relation_instances.read().unwrap().values().filter_map(relation_changes).collect();
+        flow.relation_instances
+            .sort_by(|r1, r2| (r1.outbound_id, &r1.type_name, r1.inbound_id).cmp(&(r2.outbound_id, &r2.type_name, r2.inbound_id)));
+        // Runtime overrides are not part of the authored flow content
+        self.overridden_values.read().unwrap().iter().for_each(|((id, property_name), value)| {
+            if let Some(entity_instance) = flow.entity_instances.iter_mut().find(|entity_instance| entity_instance.id == *id) {
+                if let Some(property_value) = entity_instance.properties.get_mut(property_name) {
+                    *property_value = value.clone();
+                }
+            }
+        });
+        let removed_entity_instances: BTreeSet<Uuid> = self.entities_removed.read().unwrap()[checkpoint.entities_removed..]
+            .iter()
+            .filter(|id| !self.has_entity_by_id(**id))
+            .copied()
+            .collect();
+        let mut removed_relation_instances: Vec<RelationInstanceKey> = self.relations_removed.read().unwrap()[checkpoint.relations_removed..]
+            .iter()
+            .filter(|edge_key| !self.has_relation_by_key((*edge_key).clone()))
+            .map(|edge_key| RelationInstanceKey::from(edge_key.clone()))
+            .collect();
+        removed_relation_instances.sort_by(|k1, k2| (k1.outbound_id, &k1.type_name, k1.inbound_id).cmp(&(k2.outbound_id, &k2.type_name, k2.inbound_id)));
+        removed_relation_instances.dedup();
+        Ok(FlowChanges {
+            flow,
+            removed_entity_instances: removed_entity_instances.into_iter().collect(),
+            removed_relation_instances,
+        })
+    }
+}
+
+impl Flow {
+    /// Applies the changes which have been collected from the reactive flow since a checkpoint.
+    /// The properties of existing instances are replaced by the changed properties and their
+    /// components and labels are replaced. New instances are added and removed instances are
+    /// removed.
+    pub fn apply_changes(&mut self, changes: FlowChanges) {
+        self.entity_instances
+            .retain(|entity_instance| !changes.removed_entity_instances.contains(&entity_instance.id));
+        self.relation_instances
+            .retain(|relation_instance| !changes.removed_relation_instances.contains(&RelationInstanceKey::from(relation_instance)));
+        for entity_changes in changes.flow.entity_instances {
+            match self.entity_instances.iter_mut().find(|entity_instance| entity_instance.id == entity_changes.id) {
+                Some(entity_instance) => {
+                    entity_instance.properties.extend(entity_changes.properties);
+                    entity_instance.components = entity_changes.components;
+                    entity_instance.labels = entity_changes.labels;
+                }
+                None => self.entity_instances.push(entity_changes),
+            }
+        }
+        for relation_changes in changes.flow.relation_instances {
+            match self.relation_instances.iter_mut().find(|relation_instance| {
+                relation_instance.outbound_id == relation_changes.outbound_id
+                    && relation_instance.type_name == relation_changes.type_name
+                    && relation_instance.inbound_id == relation_changes.inbound_id
+            }) {
+                Some(relation_instance) => {
+                    relation_instance.properties.extend(relation_changes.properties);
+                    relation_instance.components = relation_changes.components;
+                    relation_instance.labels = relation_changes.labels;
+                }
+                None => self.relation_instances.push(relation_changes),
+            }
+        }
+    }
+}
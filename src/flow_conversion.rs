@@ -0,0 +1,110 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use indradb::{BulkInsertItem, EdgeProperties, Identifier, Vertex, VertexProperties};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::reactive_relation_instance::create_edge_key;
+use crate::{validate_identifier, EntityInstance, Flow, InstanceConversionError, PropertyMap, RelationInstance};
+
+/// An error which occurs if a flow can't be assembled from the vertices and edges of the graph database.
+#[derive(Debug, PartialEq, Eq)]
+pub enum FlowConversionError {
+    /// The vertex of the wrapper entity instance with the given id is missing.
+    MissingWrapper(Uuid),
+
+    /// A vertex or an edge can't be converted into an instance.
+    InvalidInstance(InstanceConversionError),
+}
+
+impl fmt::Display for FlowConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FlowConversionError::MissingWrapper(id) => write!(f, "The wrapper entity instance {} is missing", id),
+            FlowConversionError::InvalidInstance(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl From<InstanceConversionError> for FlowConversionError {
+    fn from(error: InstanceConversionError) -> Self {
+        FlowConversionError::InvalidInstance(error)
+    }
+}
+
+/// Returns the identifier of the property name, if the property name is valid.
+fn convert_property_name(name: &str) -> Result<Identifier, InstanceConversionError> {
+    validate_identifier(name).map_err(|_| InstanceConversionError::InvalidPropertyName(name.to_string()))?;
+    Identifier::new(name).map_err(|_| InstanceConversionError::InvalidPropertyName(name.to_string()))
+}
+
+/// Returns the property names and values, sorted by name.
+fn convert_properties(properties: &PropertyMap) -> Result<Vec<(Identifier, Value)>, InstanceConversionError> {
+    let mut items = Vec::with_capacity(properties.len());
+    for (name, value) in properties.iter() {
+        items.push((convert_property_name(name)?, value.clone()));
+    }
+    items.sort_by(|(name_1, _), (name_2, _)| name_1.as_str().cmp(name_2.as_str()));
+    Ok(items)
+}
+
+impl Flow {
+    /// Assembles the flow with the given wrapper entity instance from the vertices and edges of
+    /// the graph database, e.g. the result of a traversal starting at the wrapper entity instance.
+    ///
+    /// Vertices and edges which occur multiple times are contained once. Edges whose outbound or
+    /// inbound entity instance is not one of the vertices don't belong to the flow and are skipped.
+    pub fn try_from_graph(id: Uuid, vertices: &[VertexProperties], edges: &[EdgeProperties]) -> Result<Flow, FlowConversionError> {
+        let wrapper = vertices
+            .iter()
+            .find(|vertex_properties| vertex_properties.vertex.id == id)
+            .ok_or(FlowConversionError::MissingWrapper(id))?;
+        let mut flow = Flow::from(EntityInstance::try_from(wrapper)?);
+        let mut ids = HashSet::from([id]);
+        for vertex_properties in vertices.iter() {
+            if ids.insert(vertex_properties.vertex.id) {
+                flow.entity_instances.push(EntityInstance::try_from(vertex_properties)?);
+            }
+        }
+        let mut keys = HashSet::new();
+        for edge_properties in edges.iter() {
+            let key = &edge_properties.edge.key;
+            if ids.contains(&key.outbound_id) && ids.contains(&key.inbound_id) && keys.insert(key.clone()) {
+                flow.relation_instances.push(RelationInstance::try_from(edge_properties)?);
+            }
+        }
+        Ok(flow)
+    }
+
+    /// Returns the items which insert the entity instances, the relation instances and their
+    /// properties of the flow into the graph database in one bulk operation.
+    ///
+    /// The vertices come first, then the edges and then the properties, so the items can be
+    /// inserted in order. The name, the description and the layers of the flow are not stored in
+    /// the graph database.
+    pub fn to_bulk_insert_items(&self) -> Result<Vec<BulkInsertItem>, InstanceConversionError> {
+        let mut vertices = Vec::with_capacity(self.entity_instances.len());
+        let mut edges = Vec::with_capacity(self.relation_instances.len());
+        let mut properties = Vec::new();
+        for entity_instance in self.entity_instances.iter() {
+            let t = Identifier::new(entity_instance.type_name.as_str())
+                .map_err(|_| InstanceConversionError::InvalidIdentifier(entity_instance.type_name.to_string()))?;
+            vertices.push(BulkInsertItem::Vertex(Vertex::with_id(entity_instance.id, t)));
+            for (name, value) in convert_properties(&entity_instance.properties)? {
+                properties.push(BulkInsertItem::VertexProperty(entity_instance.id, name, value));
+            }
+        }
+        for relation_instance in self.relation_instances.iter() {
+            let key = create_edge_key(relation_instance.outbound_id, relation_instance.type_name.as_str(), relation_instance.inbound_id)?;
+            for (name, value) in convert_properties(&relation_instance.properties)? {
+                properties.push(BulkInsertItem::EdgeProperty(key.clone().into(), name, value));
+            }
+            edges.push(BulkInsertItem::Edge(key.into()));
+        }
+        let mut items = vertices;
+        items.append(&mut edges);
+        items.append(&mut properties);
+        Ok(items)
+    }
+}
@@ -0,0 +1,214 @@
+use std::fmt;
+use std::sync::Arc;
+
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{ComponentName, PropertyInstanceGetter, ReactiveEntityInstance, ReactiveFlow};
+
+/// Selects the entity instances an expectation applies to.
+#[derive(Clone, Debug)]
+pub enum InstanceSelector {
+    /// The entity instance with the given id.
+    Id(Uuid),
+
+    /// All entity instances of the given type.
+    Type(String),
+
+    /// All entity instances which are composed with the given component.
+    Component(String),
+}
+
+impl InstanceSelector {
+    pub fn matches(&self, entity_instance: &ReactiveEntityInstance) -> bool {
+        match self {
+            InstanceSelector::Id(id) => entity_instance.id == *id,
+            InstanceSelector::Type(type_name) => entity_instance.type_name == *type_name,
+            InstanceSelector::Component(component) => entity_instance.is_a(ComponentName::new(component.as_str())),
+        }
+    }
+}
+
+impl fmt::Display for InstanceSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InstanceSelector::Id(id) => write!(f, "id {}", id),
+            InstanceSelector::Type(type_name) => write!(f, "type {}", type_name),
+            InstanceSelector::Component(component) => write!(f, "component {}", component),
+        }
+    }
+}
+
+/// Matches the value of a property.
+#[derive(Clone, Debug)]
+pub enum PropertyMatcher {
+    /// The property exists.
+    Exists,
+
+    /// The value is equal to the given value.
+    Equals(Value),
+
+    /// The value is not equal to the given value.
+    NotEquals(Value),
+
+    /// The value is a number greater than the given number.
+    GreaterThan(f64),
+
+    /// The value is a number less than the given number.
+    LessThan(f64),
+
+    /// The value is a number within the given inclusive range.
+    Between(f64, f64),
+}
+
+impl PropertyMatcher {
+    pub fn matches(&self, value: &Value) -> bool {
+        match self {
+            PropertyMatcher::Exists => true,
+            PropertyMatcher::Equals(expected) => value == expected,
+            PropertyMatcher::NotEquals(expected) => value != expected,
+            PropertyMatcher::GreaterThan(min) => value.as_f64().is_some_and(|value| value > *min),
+            PropertyMatcher::LessThan(max) => value.as_f64().is_some_and(|value| value < *max),
+            PropertyMatcher::Between(min, max) => value.as_f64().is_some_and(|value| value >= *min && value <= *max),
+        }
+    }
+}
+
+impl fmt::Display for PropertyMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PropertyMatcher::Exists => write!(f, "exists"),
+            PropertyMatcher::Equals(expected) => write!(f, "== {}", expected),
+            PropertyMatcher::NotEquals(expected) => write!(f, "!= {}", expected),
+            PropertyMatcher::GreaterThan(min) => write!(f, "> {}", min),
+            PropertyMatcher::LessThan(max) => write!(f, "< {}", max),
+            PropertyMatcher::Between(min, max) => write!(f, "between {} and {}", min, max),
+        }
+    }
+}
+
+/// The expected steady state of the selected entity instances.
+#[derive(Clone, Debug)]
+pub struct FlowExpectation {
+    /// Selects the entity instances.
+    pub selector: InstanceSelector,
+
+    /// The matchers of the properties of the selected entity instances.
+    pub properties: Vec<(String, PropertyMatcher)>,
+}
+
+impl FlowExpectation {
+    pub fn new(selector: InstanceSelector) -> FlowExpectation {
+        FlowExpectation {
+            selector,
+            properties: Vec::new(),
+        }
+    }
+
+    /// Adds a matcher for the given property.
+    pub fn property<S: Into<String>>(mut self, property_name: S, matcher: PropertyMatcher) -> FlowExpectation {
+        self.properties.push((property_name.into(), matcher));
+        self
+    }
+
+    /// Returns the failures of this expectation.
+    pub fn check(&self, entity_instances: &[Arc<ReactiveEntityInstance>]) -> Vec<FlowExpectationFailure> {
+        let selected: Vec<&Arc<ReactiveEntityInstance>> = entity_instances
+            .iter()
+            .filter(|entity_instance| self.selector.matches(entity_instance))
+            .collect();
+        if selected.is_empty() {
+            return vec![FlowExpectationFailure::NoInstance(self.selector.clone())];
+        }
+        let mut failures = Vec::new();
+        for entity_instance in selected {
+            for (property_name, matcher) in self.properties.iter() {
+                let actual = entity_instance.get(property_name.as_str());
+                if actual.as_ref().is_some_and(|actual| matcher.matches(actual)) {
+                    continue;
+                }
+                failures.push(FlowExpectationFailure::Mismatch {
+                    instance: entity_instance.to_string(),
+                    property_name: property_name.clone(),
+                    expected: matcher.clone(),
+                    actual,
+                });
+            }
+        }
+        failures
+    }
+}
+
+/// A failed expectation.
+#[derive(Clone, Debug)]
+pub enum FlowExpectationFailure {
+    /// No entity instance matches the selector.
+    NoInstance(InstanceSelector),
+
+    /// The property of the entity instance doesn't match. The actual value is
+    /// none, if the property doesn't exist.
+    Mismatch {
+        instance: String,
+        property_name: String,
+        expected: PropertyMatcher,
+        actual: Option<Value>,
+    },
+}
+
+impl fmt::Display for FlowExpectationFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FlowExpectationFailure::NoInstance(selector) => write!(f, "No entity instance with {}", selector),
+            FlowExpectationFailure::Mismatch {
+                instance,
+                property_name,
+                expected,
+                actual: Some(actual),
+            } => write!(f, "{}.{}: expected {} but was {}", instance, property_name, expected, actual),
+            FlowExpectationFailure::Mismatch {
+                instance,
+                property_name,
+                expected,
+                actual: None,
+            } => write!(f, "{}.{}: expected {} but the property doesn't exist", instance, property_name, expected),
+        }
+    }
+}
+
+/// Provides the entity instances whose state is checked.
+pub trait FlowState {
+    fn get_entity_instances(&self) -> Vec<Arc<ReactiveEntityInstance>>;
+}
+
+impl FlowState for ReactiveFlow {
+    fn get_entity_instances(&self) -> Vec<Arc<ReactiveEntityInstance>> {
+        self.entity_instances.read().unwrap().values().cloned().collect()
+    }
+}
+
+impl FlowState for [Arc<ReactiveEntityInstance>] {
+    fn get_entity_instances(&self) -> Vec<Arc<ReactiveEntityInstance>> {
+        self.to_vec()
+    }
+}
+
+impl FlowState for Vec<Arc<ReactiveEntityInstance>> {
+    fn get_entity_instances(&self) -> Vec<Arc<ReactiveEntityInstance>> {
+        self.clone()
+    }
+}
+
+/// Returns the failures of the expectations.
+pub fn check_flow_state<T: FlowState + ?Sized>(state: &T, expectations: &[FlowExpectation]) -> Vec<FlowExpectationFailure> {
+    let entity_instances = state.get_entity_instances();
+    expectations.iter().flat_map(|expectation| expectation.check(&entity_instances)).collect()
+}
+
+/// Panics with a report of all failures, if the state doesn't match the expectations.
+pub fn assert_flow_state<T: FlowState + ?Sized>(state: &T, expectations: &[FlowExpectation]) {
+    let failures = check_flow_state(state, expectations);
+    if !failures.is_empty() {
+        let report: Vec<String> = failures.iter().map(|failure| format!("  {}", failure)).collect();
+        panic!("The flow state doesn't match {} expectation(s):\n{}", failures.len(), report.join("\n"));
+    }
+}
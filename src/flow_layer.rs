@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::RelationInstanceKey;
+
+/// A named layer of a flow.
+///
+/// Instances of a flow can be assigned to layers. The instances of a disabled
+/// layer are excluded from the reactive instantiation of the flow, so whole
+/// subsystems can be toggled without deleting content.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FlowLayer {
+    /// The name of the layer.
+    pub name: String,
+
+    /// Textual description of the layer.
+    #[serde(default = "String::new")]
+    pub description: String,
+
+    /// If false, the instances of the layer are not instantiated.
+    #[serde(default = "FlowLayer::default_enabled")]
+    pub enabled: bool,
+
+    /// The ids of the entity instances which are assigned to the layer.
+    #[serde(default = "Vec::new", alias = "entities")]
+    pub entity_instances: Vec<Uuid>,
+
+    /// The keys of the relation instances which are assigned to the layer.
+    #[serde(default = "Vec::new", alias = "relations")]
+    pub relation_instances: Vec<RelationInstanceKey>,
+}
+
+impl FlowLayer {
+    /// Constructs a new enabled layer without instances.
+    pub fn new<S: Into<String>>(name: S) -> FlowLayer {
+        FlowLayer {
+            name: name.into(),
+            description: String::new(),
+            enabled: true,
+            entity_instances: Vec::new(),
+            relation_instances: Vec::new(),
+        }
+    }
+
+    fn default_enabled() -> bool {
+        true
+    }
+
+    /// Returns true, if the entity instance with the given id is assigned to the layer.
+    pub fn has_entity(&self, id: Uuid) -> bool {
+        self.entity_instances.contains(&id)
+    }
+
+    /// Returns true, if the relation instance with the given key is assigned to the layer.
+    pub fn has_relation(&self, key: &RelationInstanceKey) -> bool {
+        self.relation_instances.contains(key)
+    }
+}
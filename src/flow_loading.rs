@@ -0,0 +1,117 @@
+use std::fmt;
+use std::io;
+use std::io::{BufReader, Read};
+
+use crate::Flow;
+
+/// The progress of loading a flow.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FlowLoadingProgress {
+    /// The number of bytes which have been read.
+    pub bytes_read: u64,
+
+    /// The number of entity instances which have been instantiated.
+    pub entities_done: usize,
+
+    /// The number of entity instances to instantiate.
+    pub entities_total: usize,
+
+    /// The number of relation instances which have been instantiated.
+    pub relations_done: usize,
+
+    /// The number of relation instances to instantiate.
+    pub relations_total: usize,
+}
+
+impl FlowLoadingProgress {
+    /// Returns true, if all entity instances and relation instances have been instantiated.
+    pub fn is_done(&self) -> bool {
+        self.entities_done == self.entities_total && self.relations_done == self.relations_total
+    }
+}
+
+/// The error of loading a flow from a reader.
+#[derive(Debug)]
+pub enum FlowLoadingError {
+    /// The flow cannot be deserialized.
+    Json(serde_json::Error),
+
+    /// The progress callback has cancelled the loading.
+    Cancelled,
+}
+
+impl fmt::Display for FlowLoadingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FlowLoadingError::Json(error) => write!(f, "The flow cannot be deserialized: {}", error),
+            FlowLoadingError::Cancelled => write!(f, "Loading the flow has been cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for FlowLoadingError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FlowLoadingError::Json(error) => Some(error),
+            FlowLoadingError::Cancelled => None,
+        }
+    }
+}
+
+/// The number of bytes which are read between two reports of the progress.
+pub const FLOW_LOADING_PROGRESS_INTERVAL: u64 = 64 * 1024;
+
+/// Reports the number of bytes read to the progress callback, once per interval and at the end
+/// of the input.
+struct ProgressReader<R: Read, F: FnMut(&FlowLoadingProgress) -> bool> {
+    reader: R,
+    callback: F,
+    progress: FlowLoadingProgress,
+    reported_bytes: u64,
+    cancelled: bool,
+}
+
+impl<R: Read, F: FnMut(&FlowLoadingProgress) -> bool> Read for ProgressReader<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let bytes_read = self.reader.read(buf)?;
+        self.progress.bytes_read += bytes_read as u64;
+        if bytes_read > 0 && self.progress.bytes_read - self.reported_bytes < FLOW_LOADING_PROGRESS_INTERVAL {
+            return Ok(bytes_read);
+        }
+        self.reported_bytes = self.progress.bytes_read;
+        if !(self.callback)(&self.progress) {
+            self.cancelled = true;
+            return Err(io::Error::other("cancelled"));
+        }
+        Ok(bytes_read)
+    }
+}
+
+impl Flow {
+    /// Reads a flow and reports the number of bytes read to the callback. The progress is
+    /// reported every `FLOW_LOADING_PROGRESS_INTERVAL` bytes. Loading is cancelled, if the
+    /// callback returns false.
+    ///
+    /// The last report contains the number of entity instances and relation instances
+    /// of the flow.
+    pub fn from_reader_with_progress<R: Read, F: FnMut(&FlowLoadingProgress) -> bool>(reader: R, callback: F) -> Result<Flow, FlowLoadingError> {
+        let mut reader = ProgressReader {
+            reader,
+            callback,
+            progress: FlowLoadingProgress::default(),
+            reported_bytes: 0,
+            cancelled: false,
+        };
+        let flow: Flow = match serde_json::from_reader(BufReader::new(&mut reader)) {
+            Ok(flow) => flow,
+            Err(_) if reader.cancelled => return Err(FlowLoadingError::Cancelled),
+            Err(error) => return Err(FlowLoadingError::Json(error)),
+        };
+        reader.progress.entities_total = flow.entity_instances.len();
+        reader.progress.relations_total = flow.relation_instances.len();
+        if !(reader.callback)(&reader.progress) {
+            return Err(FlowLoadingError::Cancelled);
+        }
+        Ok(flow)
+    }
+}
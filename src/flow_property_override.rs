@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+/// A runtime override of a property of an entity instance which is contained in a flow.
+///
+/// The override value takes precedence over the value defined by the flow. The
+/// overrides are kept separately from the flow, so tweaking a flow at runtime
+/// doesn't modify the authored flow content.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FlowPropertyOverride {
+    /// The id of the entity instance.
+    pub id: Uuid,
+
+    /// The name of the property.
+    pub property_name: String,
+
+    /// The value which overrides the value defined by the flow.
+    pub value: Value,
+}
+
+impl FlowPropertyOverride {
+    pub fn new<S: Into<String>>(id: Uuid, property_name: S, value: Value) -> FlowPropertyOverride {
+        FlowPropertyOverride {
+            id,
+            property_name: property_name.into(),
+            value,
+        }
+    }
+}
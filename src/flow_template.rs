@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{DataType, Flow, GlobalIdGenerator, IdGenerator, PropertyMap};
+
+/// Starts a placeholder in a string property value.
+pub const PLACEHOLDER_START: &str = "${";
+
+/// Escapes the start of a placeholder, `$${` is inserted as `${`.
+pub const PLACEHOLDER_ESCAPE: &str = "$${";
+
+/// Ends a placeholder in a string property value.
+pub const PLACEHOLDER_END: &str = "}";
+
+/// Separates the name of the variable and the data type of a placeholder.
+pub const PLACEHOLDER_DATA_TYPE_SEPARATOR: char = ':';
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum FlowTemplateError {
+    /// There is no value for the variable with the given name.
+    MissingVariable(String),
+
+    /// The value of the variable with the given name can't be converted into the data type.
+    InvalidConversion(String, DataType),
+
+    /// The placeholder isn't terminated.
+    UnterminatedPlaceholder(String),
+
+    /// The placeholder of the variable with the given name has an unknown data type.
+    UnknownDataType(String, String),
+}
+
+impl fmt::Display for FlowTemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FlowTemplateError::MissingVariable(name) => write!(f, "There is no value for the variable {}", name),
+            FlowTemplateError::InvalidConversion(name, data_type) => write!(f, "The value of the variable {} cannot be converted into {}", name, data_type),
+            FlowTemplateError::UnterminatedPlaceholder(value) => write!(f, "The placeholder in {} is not terminated", value),
+            FlowTemplateError::UnknownDataType(name, data_type) => {
+                write!(f, "The placeholder of the variable {} has the unknown data type {}", name, data_type)
+            }
+        }
+    }
+}
+
+/// A placeholder `${name}` or `${name:data_type}`.
+struct Placeholder<'a> {
+    name: &'a str,
+    data_type: Option<DataType>,
+}
+
+impl<'a> Placeholder<'a> {
+    fn parse(placeholder: &'a str) -> Result<Placeholder<'a>, FlowTemplateError> {
+        match placeholder.split_once(PLACEHOLDER_DATA_TYPE_SEPARATOR) {
+            Some((name, data_type)) => Ok(Placeholder {
+                name: name.trim(),
+                data_type: Some(
+                    DataType::parse(data_type.trim())
+                        .ok_or_else(|| FlowTemplateError::UnknownDataType(name.trim().to_string(), data_type.trim().to_string()))?,
+                ),
+            }),
+            None => Ok(Placeholder {
+                name: placeholder.trim(),
+                data_type: None,
+            }),
+        }
+    }
+
+    /// Returns the value of the variable, converted into the data type of the placeholder.
+    fn resolve(&self, variables: &HashMap<String, Value>) -> Result<Value, FlowTemplateError> {
+        let value = variables
+            .get(self.name)
+            .ok_or_else(|| FlowTemplateError::MissingVariable(self.name.to_string()))?;
+        match self.data_type {
+            Some(data_type) => data_type
+                .coerce(value)
+                .ok_or_else(|| FlowTemplateError::InvalidConversion(self.name.to_string(), data_type)),
+            None => Ok(value.clone()),
+        }
+    }
+}
+
+/// A part of a string property value.
+enum Segment<'a> {
+    Literal(&'a str),
+    Placeholder(Placeholder<'a>),
+}
+
+/// Splits the string into the literal text and the placeholders. An escaped placeholder start is
+/// a literal.
+fn parse_segments(value: &str) -> Result<Vec<Segment<'_>>, FlowTemplateError> {
+    let mut segments = Vec::new();
+    let mut rest = value;
+    while let Some(start) = rest.find(PLACEHOLDER_START) {
+        // The escape ends with the placeholder start, so an escape is found before its placeholder start
+        if let Some(escape) = rest.find(PLACEHOLDER_ESCAPE).filter(|escape| *escape < start) {
+            segments.push(Segment::Literal(&rest[..escape]));
+            segments.push(Segment::Literal(PLACEHOLDER_START));
+            rest = &rest[escape + PLACEHOLDER_ESCAPE.len()..];
+            continue;
+        }
+        segments.push(Segment::Literal(&rest[..start]));
+        let after_start = &rest[start + PLACEHOLDER_START.len()..];
+        let end = after_start
+            .find(PLACEHOLDER_END)
+            .ok_or_else(|| FlowTemplateError::UnterminatedPlaceholder(value.to_string()))?;
+        segments.push(Segment::Placeholder(Placeholder::parse(&after_start[..end])?));
+        rest = &after_start[end + PLACEHOLDER_END.len()..];
+    }
+    segments.push(Segment::Literal(rest));
+    Ok(segments)
+}
+
+/// Substitutes the placeholders in the value. A string which consists of a single placeholder is
+/// replaced by the value of the variable, otherwise the values of the variables are inserted as text.
+fn substitute(value: &Value, variables: &HashMap<String, Value>) -> Result<Value, FlowTemplateError> {
+    match value {
+        Value::String(s) => {
+            let segments = parse_segments(s)?;
+            if let [Segment::Literal(""), Segment::Placeholder(placeholder), Segment::Literal("")] = segments.as_slice() {
+                return placeholder.resolve(variables);
+            }
+            let mut text = String::new();
+            for segment in segments {
+                match segment {
+                    Segment::Literal(literal) => text.push_str(literal),
+                    Segment::Placeholder(placeholder) => match placeholder.resolve(variables)? {
+                        Value::String(value) => text.push_str(&value),
+                        value => text.push_str(&value.to_string()),
+                    },
+                }
+            }
+            Ok(Value::String(text))
+        }
+        Value::Array(items) => Ok(Value::Array(items.iter().map(|item| substitute(item, variables)).collect::<Result<_, _>>()?)),
+        Value::Object(object) => Ok(Value::Object(
+            object
+                .iter()
+                .map(|(key, value)| substitute(value, variables).map(|value| (key.clone(), value)))
+                .collect::<Result<_, _>>()?,
+        )),
+        _ => Ok(value.clone()),
+    }
+}
+
+fn substitute_properties(properties: &PropertyMap, variables: &HashMap<String, Value>) -> Result<PropertyMap, FlowTemplateError> {
+    properties
+        .iter()
+        .map(|(name, value)| substitute(value, variables).map(|value| (name.clone(), value)))
+        .collect()
+}
+
+fn collect_placeholders(value: &Value, names: &mut Vec<String>) -> Result<(), FlowTemplateError> {
+    match value {
+        Value::String(s) => {
+            for segment in parse_segments(s)? {
+                if let Segment::Placeholder(placeholder) = segment {
+                    if !names.iter().any(|name| name == placeholder.name) {
+                        names.push(placeholder.name.to_string());
+                    }
+                }
+            }
+            Ok(())
+        }
+        Value::Array(items) => items.iter().try_for_each(|item| collect_placeholders(item, names)),
+        Value::Object(object) => object.values().try_for_each(|value| collect_placeholders(value, names)),
+        _ => Ok(()),
+    }
+}
+
+impl Flow {
+    /// Returns the names of the variables which are used by the placeholders in the property values.
+    pub fn get_template_variables(&self) -> Result<Vec<String>, FlowTemplateError> {
+        let mut names = Vec::new();
+        let properties = self
+            .entity_instances
+            .iter()
+            .map(|entity_instance| &entity_instance.properties)
+            .chain(self.relation_instances.iter().map(|relation_instance| &relation_instance.properties));
+        for properties in properties {
+            for value in properties.values() {
+                collect_placeholders(value, &mut names)?;
+            }
+        }
+        Ok(names)
+    }
+
+    /// Instantiates the flow as template.
+    ///
+    /// The placeholders `${name}` in the property values of the entity instances and relation
+    /// instances are substituted by the values of the variables. The placeholder `${name:data_type}`
+    /// converts the value into the data type, e.g. `${width:number}`. A string which consists of a
+    /// single placeholder is replaced by the value, otherwise the value is inserted as text. The
+    /// escape `$${` is inserted as literal `${`.
+    ///
+    /// The entity instances get new ids, so the template can be instantiated multiple times.
+    pub fn instantiate(&self, variables: &HashMap<String, Value>) -> Result<Flow, FlowTemplateError> {
+        self.instantiate_with_id_generator(variables, &GlobalIdGenerator::default())
+    }
+
+    /// Instantiates the flow as template. The new ids of the entity instances are generated by
+    /// the given id generator.
+    pub fn instantiate_with_id_generator(&self, variables: &HashMap<String, Value>, id_generator: &dyn IdGenerator) -> Result<Flow, FlowTemplateError> {
+        let ids: HashMap<Uuid, Uuid> = self
+            .entity_instances
+            .iter()
+            .map(|entity_instance| (entity_instance.id, id_generator.generate()))
+            .collect();
+        let map_id = |id: &Uuid| ids.get(id).copied().unwrap_or(*id);
+        let mut flow = self.clone();
+        flow.id = map_id(&self.id);
+        for entity_instance in flow.entity_instances.iter_mut() {
+            entity_instance.id = map_id(&entity_instance.id);
+            entity_instance.properties = substitute_properties(&entity_instance.properties, variables)?;
+        }
+        for relation_instance in flow.relation_instances.iter_mut() {
+            relation_instance.outbound_id = map_id(&relation_instance.outbound_id);
+            relation_instance.inbound_id = map_id(&relation_instance.inbound_id);
+            relation_instance.properties = substitute_properties(&relation_instance.properties, variables)?;
+        }
+        for layer in flow.layers.iter_mut() {
+            for id in layer.entity_instances.iter_mut() {
+                *id = map_id(id);
+            }
+            for key in layer.relation_instances.iter_mut() {
+                key.outbound_id = map_id(&key.outbound_id);
+                key.inbound_id = map_id(&key.inbound_id);
+            }
+        }
+        for variable in flow.variables.iter_mut() {
+            variable.entity_id = map_id(&variable.entity_id);
+        }
+        Ok(flow)
+    }
+}
@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{validate_value, CategoryPath, EntityTypeName, Flow, PropertyType, RelationTypeName, ValidationError, ValidationReport};
+
+/// Flow types describe the structure of flows which are used as templates.
+///
+/// A flow conforms to a flow type, if the wrapper entity instance is of the entity type of the
+/// flow type, the flow contains instances of all required entity types and relation types and
+/// the flow declares all variables of the flow type.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FlowType {
+    /// The name of the flow type.
+    pub name: String,
+
+    /// The category of the flow type, e.g. `logical/gates`. A flat group name is a category with a single segment.
+    #[serde(default)]
+    pub group: CategoryPath,
+
+    /// Textual description of the flow type.
+    #[serde(default = "String::new")]
+    pub description: String,
+
+    /// The entity type of the wrapper entity instance.
+    #[serde(alias = "type")]
+    pub type_name: String,
+
+    /// The entity types which must be instantiated at least once in the flow.
+    #[serde(default = "Vec::new")]
+    pub entity_types: Vec<EntityTypeName>,
+
+    /// The relation types which must be instantiated at least once in the flow.
+    #[serde(default = "Vec::new")]
+    pub relation_types: Vec<RelationTypeName>,
+
+    /// The variables which must be declared by the flow. The socket type defines whether the
+    /// variable is an input or an output.
+    #[serde(default = "Vec::new")]
+    pub variables: Vec<PropertyType>,
+}
+
+impl FlowType {
+    pub fn new<S: Into<String>>(name: S, type_name: S) -> FlowType {
+        FlowType {
+            name: name.into(),
+            group: CategoryPath::default(),
+            description: String::new(),
+            type_name: type_name.into(),
+            entity_types: Vec::new(),
+            relation_types: Vec::new(),
+            variables: Vec::new(),
+        }
+    }
+
+    /// Returns true, if the flow type belongs to the given category or to one of its sub categories.
+    /// Returns false, if the category isn't a valid category path.
+    pub fn is_in_category_subtree<C: TryInto<CategoryPath>>(&self, category: C) -> bool {
+        category.try_into().is_ok_and(|category| self.group.is_in_subtree_of(&category))
+    }
+
+    /// Requires an instance of the given entity type.
+    pub fn with_entity_type<S: Into<EntityTypeName>>(mut self, type_name: S) -> FlowType {
+        self.entity_types.push(type_name.into());
+        self
+    }
+
+    /// Requires an instance of the given relation type.
+    pub fn with_relation_type<S: Into<RelationTypeName>>(mut self, type_name: S) -> FlowType {
+        self.relation_types.push(type_name.into());
+        self
+    }
+
+    /// Requires the variable.
+    pub fn with_variable(mut self, variable: PropertyType) -> FlowType {
+        self.variables.push(variable);
+        self
+    }
+}
+
+impl Flow {
+    /// Validates the flow against the flow type.
+    pub fn conforms_to(&self, flow_type: &FlowType) -> ValidationReport {
+        let mut report = ValidationReport::new();
+        match self.entity_instances.iter().find(|entity_instance| entity_instance.id == self.id) {
+            Some(wrapper) if wrapper.type_name != flow_type.type_name => report.add(ValidationError::InvalidType {
+                expected: flow_type.type_name.clone(),
+                actual: wrapper.type_name.to_string(),
+            }),
+            Some(_) => {}
+            None => report.add(ValidationError::DanglingReference { id: self.id }),
+        }
+        for type_name in flow_type.entity_types.iter() {
+            if !self.entity_instances.iter().any(|entity_instance| entity_instance.type_name == *type_name) {
+                report.add(ValidationError::MissingEntityType {
+                    type_name: type_name.to_string(),
+                });
+            }
+        }
+        for type_name in flow_type.relation_types.iter() {
+            if !self
+                .relation_instances
+                .iter()
+                .any(|relation_instance| relation_instance.type_name == *type_name)
+            {
+                report.add(ValidationError::MissingRelationType {
+                    type_name: type_name.to_string(),
+                });
+            }
+        }
+        for property_type in flow_type.variables.iter() {
+            let variable = match self.get_variable(&property_type.name) {
+                Some(variable) => variable,
+                None => {
+                    report.add(ValidationError::MissingVariable {
+                        variable_name: property_type.name.clone(),
+                    });
+                    continue;
+                }
+            };
+            if variable.socket_type != property_type.socket_type {
+                report.add(ValidationError::InvalidSocketType {
+                    property_name: variable.name.clone(),
+                    expected: property_type.socket_type,
+                    actual: variable.socket_type,
+                });
+            }
+            // The data type is checked against the current value of the mapped property
+            let value = self
+                .entity_instances
+                .iter()
+                .find(|entity_instance| entity_instance.id == variable.entity_id)
+                .and_then(|entity_instance| entity_instance.properties.get(&variable.property_name));
+            if let Some(value) = value {
+                report.check(validate_value(property_type, value));
+            }
+        }
+        report
+    }
+}
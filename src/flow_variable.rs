@@ -0,0 +1,83 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::SocketType;
+
+/// A named input or output of a flow.
+///
+/// The variable maps to a property of an entity instance of the flow, so the flow can be treated
+/// as a black box with named inputs and outputs when it is embedded or instantiated elsewhere.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FlowVariable {
+    /// The name of the variable.
+    pub name: String,
+
+    /// Textual description of the variable.
+    #[serde(default = "String::new")]
+    pub description: String,
+
+    /// Input variables accept values, output variables provide values.
+    pub socket_type: SocketType,
+
+    /// The id of the entity instance which contains the property.
+    pub entity_id: Uuid,
+
+    /// The name of the property.
+    pub property_name: String,
+}
+
+impl FlowVariable {
+    pub fn new<S: Into<String>>(name: S, socket_type: SocketType, entity_id: Uuid, property_name: S) -> FlowVariable {
+        FlowVariable {
+            name: name.into(),
+            description: String::new(),
+            socket_type,
+            entity_id,
+            property_name: property_name.into(),
+        }
+    }
+
+    /// Constructs an input variable.
+    pub fn input<S: Into<String>>(name: S, entity_id: Uuid, property_name: S) -> FlowVariable {
+        FlowVariable::new(name, SocketType::Input, entity_id, property_name)
+    }
+
+    /// Constructs an output variable.
+    pub fn output<S: Into<String>>(name: S, entity_id: Uuid, property_name: S) -> FlowVariable {
+        FlowVariable::new(name, SocketType::Output, entity_id, property_name)
+    }
+
+    /// Returns true, if the variable is an input.
+    pub fn is_input(&self) -> bool {
+        self.socket_type == SocketType::Input
+    }
+
+    /// Returns true, if the variable is an output.
+    pub fn is_output(&self) -> bool {
+        self.socket_type == SocketType::Output
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum FlowVariableError {
+    /// Multiple variables have the given name.
+    Duplicate(String),
+
+    /// The entity instance of the variable with the given name is not contained in the flow.
+    MissingEntityInstance(String, Uuid),
+
+    /// The entity instance of the variable with the given name doesn't have the property.
+    MissingProperty(String, String),
+}
+
+impl fmt::Display for FlowVariableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FlowVariableError::Duplicate(name) => write!(f, "The variable {} is declared multiple times", name),
+            FlowVariableError::MissingEntityInstance(name, id) => write!(f, "The entity instance {} of the variable {} is not contained in the flow", id, name),
+            FlowVariableError::MissingProperty(name, property_name) => write!(f, "The property {} of the variable {} doesn't exist", property_name, name),
+        }
+    }
+}
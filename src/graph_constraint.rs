@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{PropertyInstanceGetter, ReactiveEntityInstance, ReactiveRelationInstance, RelationInstanceKey};
+
+/// The direction of the relation instances of an entity instance.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RelationDirection {
+    /// Relation instances from the entity instance.
+    Outbound,
+
+    /// Relation instances to the entity instance.
+    Inbound,
+}
+
+impl fmt::Display for RelationDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RelationDirection::Outbound => write!(f, "outbound"),
+            RelationDirection::Inbound => write!(f, "inbound"),
+        }
+    }
+}
+
+/// An invariant over a set of entity instances and relation instances.
+///
+/// Example: every camera must have exactly one current_camera relation from a player:
+///
+/// ```json
+/// { "constraint": "relation_count", "entity_type": "camera", "relation_type": "current_camera",
+///   "direction": "inbound", "other_type": "player", "min": 1, "max": 1 }
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "constraint", rename_all = "snake_case")]
+pub enum GraphConstraint {
+    /// Every entity instance of the entity type has at least `min` and at most `max` relation
+    /// instances of the relation type in the given direction. If the other type is given, only
+    /// relation instances from or to entity instances of the other type are counted.
+    RelationCount {
+        entity_type: String,
+        relation_type: String,
+        direction: RelationDirection,
+        #[serde(default)]
+        other_type: Option<String>,
+        #[serde(default)]
+        min: usize,
+        #[serde(default)]
+        max: Option<usize>,
+    },
+
+    /// Every entity instance of the entity type has the property.
+    PropertyExists { entity_type: String, property_name: String },
+
+    /// The values of the property are unique among the entity instances of the entity type.
+    UniqueProperty { entity_type: String, property_name: String },
+
+    /// For every relation instance of the relation type, the property whose name is stored in the
+    /// property `name_property` of the relation instance exists on the entity instance at the
+    /// given end, e.g. the outbound property of a connector.
+    ReferencedPropertyExists {
+        relation_type: String,
+        name_property: String,
+        end: RelationDirection,
+    },
+}
+
+/// A violation of a graph constraint.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ConstraintViolation {
+    /// The number of relation instances of the entity instance is out of range.
+    RelationCount {
+        id: Uuid,
+        relation_type: String,
+        direction: RelationDirection,
+        count: usize,
+        min: usize,
+        max: Option<usize>,
+    },
+
+    /// The entity instance doesn't have the property.
+    MissingProperty { id: Uuid, property_name: String },
+
+    /// The entity instances have the same value.
+    DuplicateValue { ids: Vec<Uuid>, property_name: String, value: Value },
+
+    /// The property which is referenced by the relation instance doesn't exist.
+    MissingReferencedProperty {
+        key: RelationInstanceKey,
+        end: RelationDirection,
+        property_name: String,
+    },
+}
+
+impl fmt::Display for ConstraintViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConstraintViolation::RelationCount {
+                id,
+                relation_type,
+                direction,
+                count,
+                min,
+                max,
+            } => match max {
+                Some(max) => write!(
+                    f,
+                    "The entity instance {} has {} {} relations of type {} but requires {} to {}",
+                    id, count, direction, relation_type, min, max
+                ),
+                None => write!(
+                    f,
+                    "The entity instance {} has {} {} relations of type {} but requires at least {}",
+                    id, count, direction, relation_type, min
+                ),
+            },
+            ConstraintViolation::MissingProperty { id, property_name } => write!(f, "The entity instance {} has no property {}", id, property_name),
+            ConstraintViolation::DuplicateValue { ids, property_name, value } => {
+                write!(f, "{} entity instances have the same value {} of the property {}", ids.len(), value, property_name)
+            }
+            ConstraintViolation::MissingReferencedProperty { key, end, property_name } => {
+                write!(f, "The {} entity instance of the relation instance {} has no property {}", end, key.type_name, property_name)
+            }
+        }
+    }
+}
+
+impl GraphConstraint {
+    /// Evaluates the constraint and returns the violations.
+    pub fn evaluate(&self, entity_instances: &[Arc<ReactiveEntityInstance>], relation_instances: &[Arc<ReactiveRelationInstance>]) -> Vec<ConstraintViolation> {
+        match self {
+            GraphConstraint::RelationCount {
+                entity_type,
+                relation_type,
+                direction,
+                other_type,
+                min,
+                max,
+            } => entity_instances
+                .iter()
+                .filter(|entity_instance| entity_instance.type_name == *entity_type)
+                .filter_map(|entity_instance| {
+                    let count = relation_instances
+                        .iter()
+                        .filter(|relation_instance| relation_instance.type_name == *relation_type)
+                        .filter_map(|relation_instance| match direction {
+                            RelationDirection::Outbound if relation_instance.outbound.id == entity_instance.id => Some(&relation_instance.inbound),
+                            RelationDirection::Inbound if relation_instance.inbound.id == entity_instance.id => Some(&relation_instance.outbound),
+                            _ => None,
+                        })
+                        .filter(|other| other_type.as_ref().is_none_or(|other_type| other.type_name == *other_type))
+                        .count();
+                    if count >= *min && max.is_none_or(|max| count <= max) {
+                        return None;
+                    }
+                    Some(ConstraintViolation::RelationCount {
+                        id: entity_instance.id,
+                        relation_type: relation_type.clone(),
+                        direction: *direction,
+                        count,
+                        min: *min,
+                        max: *max,
+                    })
+                })
+                .collect(),
+            GraphConstraint::PropertyExists { entity_type, property_name } => entity_instances
+                .iter()
+                .filter(|entity_instance| entity_instance.type_name == *entity_type && entity_instance.get(property_name).is_none())
+                .map(|entity_instance| ConstraintViolation::MissingProperty {
+                    id: entity_instance.id,
+                    property_name: property_name.clone(),
+                })
+                .collect(),
+            GraphConstraint::UniqueProperty { entity_type, property_name } => {
+                // The values are grouped by their serialization, because values aren't hashable
+                let mut groups: HashMap<String, (Value, Vec<Uuid>)> = HashMap::new();
+                for entity_instance in entity_instances.iter().filter(|entity_instance| entity_instance.type_name == *entity_type) {
+                    if let Some(value) = entity_instance.get(property_name) {
+                        groups
+                            .entry(value.to_string())
+                            .or_insert_with(|| (value, Vec::new()))
+                            .1
+                            .push(entity_instance.id);
+                    }
+                }
+                let mut violations: Vec<ConstraintViolation> = groups
+                    .into_values()
+                    .filter(|(_, ids)| ids.len() > 1)
+                    .map(|(value, mut ids)| {
+                        ids.sort();
+                        ConstraintViolation::DuplicateValue {
+                            ids,
+                            property_name: property_name.clone(),
+                            value,
+                        }
+                    })
+                    .collect();
+                violations.sort_by_key(|violation| match violation {
+                    ConstraintViolation::DuplicateValue { ids, .. } => ids.first().cloned(),
+                    _ => None,
+                });
+                violations
+            }
+            GraphConstraint::ReferencedPropertyExists {
+                relation_type,
+                name_property,
+                end,
+            } => relation_instances
+                .iter()
+                .filter(|relation_instance| relation_instance.type_name == *relation_type)
+                .filter_map(|relation_instance| {
+                    let property_name = relation_instance.as_string(name_property).unwrap_or_default();
+                    let entity_instance = match end {
+                        RelationDirection::Outbound => &relation_instance.outbound,
+                        RelationDirection::Inbound => &relation_instance.inbound,
+                    };
+                    if entity_instance.get(&property_name).is_some() {
+                        return None;
+                    }
+                    Some(ConstraintViolation::MissingReferencedProperty {
+                        key: RelationInstanceKey::from(relation_instance.as_ref()),
+                        end: *end,
+                        property_name,
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Evaluates the constraints and returns the violations in the order of the constraints.
+pub fn evaluate_constraints(
+    constraints: &[GraphConstraint],
+    entity_instances: &[Arc<ReactiveEntityInstance>],
+    relation_instances: &[Arc<ReactiveRelationInstance>],
+) -> Vec<ConstraintViolation> {
+    constraints
+        .iter()
+        .flat_map(|constraint| constraint.evaluate(entity_instances, relation_instances))
+        .collect()
+}
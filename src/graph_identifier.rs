@@ -0,0 +1,155 @@
+//! The identifiers of the types and the keys of the relation instances.
+//!
+//! The model uses its own types in both configurations, so that consumers which don't store the
+//! graph, e.g. editors and validators, don't depend on the graph database. With the feature
+//! `graph-db` the types are converted into the types of the graph database at the boundary.
+
+pub use self::identifier::*;
+
+mod identifier {
+    use std::fmt;
+    use std::ops::Deref;
+    use std::str::FromStr;
+
+    use serde::{Deserialize, Serialize};
+    use uuid::Uuid;
+
+    use crate::{is_valid_type_name_character, MAX_TYPE_NAME_LENGTH};
+
+    /// An error which occurs if a string isn't a valid identifier.
+    #[derive(Debug, PartialEq, Eq)]
+    pub enum IdentifierError {
+        /// The identifier is empty or contains characters other than letters, numbers, dashes and
+        /// underscores.
+        InvalidValue,
+
+        /// The identifier is longer than 255 bytes.
+        ValueTooLong,
+    }
+
+    impl fmt::Display for IdentifierError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                IdentifierError::InvalidValue => write!(f, "The identifier contains invalid characters"),
+                IdentifierError::ValueTooLong => write!(f, "The identifier is too long"),
+            }
+        }
+    }
+
+    /// The name of a type. Identifiers are limited to 255 bytes and can only contain letters,
+    /// numbers, dashes and underscores.
+    #[derive(Eq, PartialEq, Clone, Debug, Hash, Ord, PartialOrd, Serialize, Deserialize, Default)]
+    pub struct Identifier(String);
+
+    impl Identifier {
+        pub fn new<S: Into<String>>(s: S) -> Result<Self, IdentifierError> {
+            let s = s.into();
+            if s.len() > MAX_TYPE_NAME_LENGTH {
+                Err(IdentifierError::ValueTooLong)
+            } else if s.is_empty() || !s.chars().all(is_valid_type_name_character) {
+                Err(IdentifierError::InvalidValue)
+            } else {
+                Ok(Identifier(s))
+            }
+        }
+
+        /// Creates an identifier without validating it.
+        ///
+        /// # Safety
+        /// The string must be a valid identifier.
+        pub unsafe fn new_unchecked<S: Into<String>>(s: S) -> Self {
+            Identifier(s.into())
+        }
+
+        pub fn as_str(&self) -> &str {
+            &self.0
+        }
+
+        pub fn into_string(self) -> String {
+            self.0
+        }
+    }
+
+    impl Deref for Identifier {
+        type Target = String;
+
+        fn deref(&self) -> &String {
+            &self.0
+        }
+    }
+
+    impl FromStr for Identifier {
+        type Err = IdentifierError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Identifier::new(s)
+        }
+    }
+
+    impl fmt::Display for Identifier {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}", self.0)
+        }
+    }
+
+    /// The key of a relation instance.
+    #[derive(Eq, PartialEq, Clone, Debug, Hash, Ord, PartialOrd, Serialize, Deserialize)]
+    pub struct EdgeKey {
+        /// The id of the outbound entity instance.
+        pub outbound_id: Uuid,
+
+        /// The type of the relation instance.
+        pub t: Identifier,
+
+        /// The id of the inbound entity instance.
+        pub inbound_id: Uuid,
+    }
+
+    impl EdgeKey {
+        pub fn new(outbound_id: Uuid, t: Identifier, inbound_id: Uuid) -> EdgeKey {
+            EdgeKey { outbound_id, t, inbound_id }
+        }
+
+        /// Returns the key of the relation instance in the opposite direction.
+        pub fn reversed(&self) -> EdgeKey {
+            EdgeKey::new(self.inbound_id, self.t.clone(), self.outbound_id)
+        }
+    }
+}
+
+#[cfg(feature = "graph-db")]
+mod graph_db {
+    use super::{EdgeKey, Identifier, IdentifierError};
+
+    impl From<Identifier> for indradb::Identifier {
+        fn from(identifier: Identifier) -> Self {
+            // Safety: The identifiers of the model are validated by the same rules, which are
+            // even stricter, because empty identifiers are rejected
+            unsafe { indradb::Identifier::new_unchecked(identifier.into_string()) }
+        }
+    }
+
+    /// Fails, if the identifier of the graph database is empty.
+    impl TryFrom<indradb::Identifier> for Identifier {
+        type Error = IdentifierError;
+
+        fn try_from(identifier: indradb::Identifier) -> Result<Self, IdentifierError> {
+            Identifier::new(identifier.into_string())
+        }
+    }
+
+    impl From<EdgeKey> for indradb::EdgeKey {
+        fn from(key: EdgeKey) -> Self {
+            indradb::EdgeKey::new(key.outbound_id, key.t.into(), key.inbound_id)
+        }
+    }
+
+    /// Fails, if the type of the edge is empty.
+    impl TryFrom<indradb::EdgeKey> for EdgeKey {
+        type Error = IdentifierError;
+
+        fn try_from(key: indradb::EdgeKey) -> Result<Self, IdentifierError> {
+            Ok(EdgeKey::new(key.outbound_id, Identifier::try_from(key.t)?, key.inbound_id))
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use uuid::Uuid;
+
+use crate::ReactiveFlow;
+
+/// The maximum number of label propagation rounds.
+const MAX_LABEL_PROPAGATION_ROUNDS: usize = 32;
+
+/// The assignment of the entity instances of an instance graph to shards.
+///
+/// The entity instances are grouped into densely connected communities by label
+/// propagation. The communities are distributed over the shards, so that few
+/// relation instances cross shard borders and the shards are balanced. The
+/// partitioning is deterministic, so the same graph always results in the same
+/// shard ids.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GraphPartition {
+    /// The number of shards.
+    pub shard_count: usize,
+
+    /// The shard ids of the entity instances.
+    pub shards: HashMap<Uuid, usize>,
+}
+
+impl GraphPartition {
+    /// Partitions the entity instances into the given number of shards.
+    pub fn partition(entity_instances: &[Uuid], edges: &[(Uuid, Uuid)], shard_count: usize) -> GraphPartition {
+        GraphPartition::repartition(entity_instances, edges, shard_count, None)
+    }
+
+    /// Partitions the entity instances into the given number of shards. Communities keep
+    /// the shard id most of their members had in the previous partition, if the shard
+    /// has capacity left.
+    pub fn repartition(entity_instances: &[Uuid], edges: &[(Uuid, Uuid)], shard_count: usize, previous: Option<&GraphPartition>) -> GraphPartition {
+        let shard_count = shard_count.max(1);
+        let communities = detect_communities(entity_instances, edges);
+        let capacity = entity_instances.len().div_ceil(shard_count);
+        let mut loads = vec![0usize; shard_count];
+        let mut shards = HashMap::new();
+        for community in communities {
+            let preferred = previous
+                .and_then(|previous| previous.get_majority_shard(&community))
+                .filter(|shard| *shard < shard_count && loads[*shard] + community.len() <= capacity);
+            let shard = preferred.unwrap_or_else(|| least_loaded(&loads));
+            loads[shard] += community.len();
+            for id in community {
+                shards.insert(id, shard);
+            }
+        }
+        GraphPartition { shard_count, shards }
+    }
+
+    /// Partitions the entity instances of the flow into the given number of shards.
+    pub fn partition_flow(flow: &ReactiveFlow, shard_count: usize) -> GraphPartition {
+        let (entity_instances, edges) = flow_graph(flow);
+        GraphPartition::partition(&entity_instances, &edges, shard_count)
+    }
+
+    /// Returns the shard id of the entity instance with the given id.
+    pub fn get_shard(&self, id: Uuid) -> Option<usize> {
+        self.shards.get(&id).copied()
+    }
+
+    /// Returns the sorted ids of the entity instances of the given shard.
+    pub fn get_shard_members(&self, shard: usize) -> Vec<Uuid> {
+        let mut members: Vec<Uuid> = self.shards.iter().filter(|(_, s)| **s == shard).map(|(id, _)| *id).collect();
+        members.sort();
+        members
+    }
+
+    /// Returns the number of entity instances of each shard.
+    pub fn get_shard_sizes(&self) -> Vec<usize> {
+        let mut sizes = vec![0; self.shard_count];
+        for shard in self.shards.values() {
+            sizes[*shard] += 1;
+        }
+        sizes
+    }
+
+    /// Returns the number of edges whose ends are in different shards.
+    pub fn get_cut_size(&self, edges: &[(Uuid, Uuid)]) -> usize {
+        edges
+            .iter()
+            .filter(|(outbound_id, inbound_id)| self.get_shard(*outbound_id) != self.get_shard(*inbound_id))
+            .count()
+    }
+
+    /// Returns the shard most of the given entity instances are assigned to.
+    fn get_majority_shard(&self, ids: &[Uuid]) -> Option<usize> {
+        let mut counts: BTreeMap<usize, usize> = BTreeMap::new();
+        for shard in ids.iter().filter_map(|id| self.get_shard(*id)) {
+            *counts.entry(shard).or_insert(0) += 1;
+        }
+        // On ties the lowest shard id wins
+        counts.into_iter().rev().max_by_key(|(_, count)| *count).map(|(shard, _)| shard)
+    }
+}
+
+/// Returns the ids of the entity instances and the outbound and inbound ids of the relation instances of the flow.
+pub fn flow_graph(flow: &ReactiveFlow) -> (Vec<Uuid>, Vec<(Uuid, Uuid)>) {
+    let entity_instances = flow.entity_instances.read().unwrap().keys().copied().collect();
+    let edges = flow
+        .relation_instances
+        .read()
+        .unwrap()
+        .keys()
+        .map(|edge_key| (edge_key.outbound_id, edge_key.inbound_id))
+        .collect();
+    (entity_instances, edges)
+}
+
+/// Groups the entity instances into communities using label propagation. The communities
+/// are sorted by size (descending) and each community is sorted by id.
+///
+/// Edges are weighted by the number of common neighbours, so that bridges between densely
+/// connected regions don't let the labels leak from one region into the other.
+fn detect_communities(entity_instances: &[Uuid], edges: &[(Uuid, Uuid)]) -> Vec<Vec<Uuid>> {
+    let mut ids = entity_instances.to_vec();
+    ids.sort();
+    ids.dedup();
+    let mut neighbours: HashMap<Uuid, BTreeSet<Uuid>> = ids.iter().map(|id| (*id, BTreeSet::new())).collect();
+    for (outbound_id, inbound_id) in edges {
+        if outbound_id == inbound_id || !neighbours.contains_key(outbound_id) || !neighbours.contains_key(inbound_id) {
+            continue;
+        }
+        neighbours.get_mut(outbound_id).unwrap().insert(*inbound_id);
+        neighbours.get_mut(inbound_id).unwrap().insert(*outbound_id);
+    }
+    let weighted_neighbours: HashMap<Uuid, Vec<(Uuid, usize)>> = ids
+        .iter()
+        .map(|id| {
+            let weighted = neighbours[id]
+                .iter()
+                .map(|neighbour| (*neighbour, 1 + neighbours[id].intersection(&neighbours[neighbour]).count()))
+                .collect();
+            (*id, weighted)
+        })
+        .collect();
+    let mut labels: HashMap<Uuid, Uuid> = ids.iter().map(|id| (*id, *id)).collect();
+    for _ in 0..MAX_LABEL_PROPAGATION_ROUNDS {
+        let mut changed = false;
+        for id in ids.iter() {
+            let current = labels[id];
+            let mut weights: BTreeMap<Uuid, usize> = BTreeMap::new();
+            for (neighbour, weight) in weighted_neighbours[id].iter() {
+                *weights.entry(labels[neighbour]).or_insert(0) += weight;
+            }
+            // On ties the current label wins, otherwise the lowest label
+            let max = weights.values().copied().max().unwrap_or(0);
+            if weights.get(&current) == Some(&max) {
+                continue;
+            }
+            if let Some((label, _)) = weights.into_iter().find(|(_, weight)| *weight == max) {
+                labels.insert(*id, label);
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    let mut communities: BTreeMap<Uuid, Vec<Uuid>> = BTreeMap::new();
+    for id in ids {
+        communities.entry(labels[&id]).or_default().push(id);
+    }
+    let mut communities: Vec<Vec<Uuid>> = communities.into_values().collect();
+    communities.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a[0].cmp(&b[0])));
+    communities
+}
+
+/// Returns the shard with the lowest load. On ties the lowest shard id wins.
+fn least_loaded(loads: &[usize]) -> usize {
+    loads.iter().enumerate().min_by_key(|(_, load)| **load).map(|(shard, _)| shard).unwrap_or(0)
+}
@@ -0,0 +1,397 @@
+use std::sync::Arc;
+
+use async_graphql::{Json, Object, SimpleObject, ID};
+use serde_json::Value;
+
+use crate::{
+    Cardinality, Component, DataType, EntityInstance, EntityType, Extension, Flow, PropertyKind, PropertyMap, PropertyType, ReactiveEntityInstance,
+    ReactiveRelationInstance, RelationInstance, RelationType, SocketType,
+};
+
+/// Returns the properties with the given names or all properties, ordered by name.
+fn get_properties(properties: &PropertyMap, names: Option<Vec<String>>) -> Vec<GraphQLPropertyInstance> {
+    let mut properties: Vec<GraphQLPropertyInstance> = properties
+        .iter()
+        .filter(|(name, _)| names.as_ref().is_none_or(|names| names.contains(name)))
+        .map(|(name, value)| GraphQLPropertyInstance {
+            name: name.clone(),
+            value: Json(value.clone()),
+        })
+        .collect();
+    properties.sort_by(|property_1, property_2| property_1.name.cmp(&property_2.name));
+    properties
+}
+
+/// The name and the value of a property of an instance.
+#[derive(SimpleObject, Clone, Debug, PartialEq)]
+#[graphql(name = "PropertyInstance")]
+pub struct GraphQLPropertyInstance {
+    /// The name of the property.
+    pub name: String,
+
+    /// The value of the property.
+    pub value: Json<Value>,
+}
+
+/// An extension of a type.
+#[derive(Clone, Debug)]
+pub struct GraphQLExtension(pub Extension);
+
+#[Object(name = "Extension")]
+impl GraphQLExtension {
+    /// The name of the extension.
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    /// The value of the extension.
+    async fn extension(&self) -> Json<Value> {
+        Json(self.0.extension.clone())
+    }
+
+    /// The version of the extension.
+    async fn version(&self) -> Option<String> {
+        self.0.version.as_ref().map(|version| version.to_string())
+    }
+}
+
+/// A property type of a component, an entity type or a relation type.
+#[derive(Clone, Debug)]
+pub struct GraphQLPropertyType(pub PropertyType);
+
+#[Object(name = "PropertyType")]
+impl GraphQLPropertyType {
+    /// The name of the property.
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    /// The description of the property.
+    async fn description(&self) -> &str {
+        &self.0.description
+    }
+
+    /// The data type of the property.
+    async fn data_type(&self) -> DataType {
+        self.0.data_type
+    }
+
+    /// Specifies whether the property is an input or an output.
+    async fn socket_type(&self) -> SocketType {
+        self.0.socket_type
+    }
+
+    /// Specifies whether the property holds a value or emits events.
+    async fn kind(&self) -> PropertyKind {
+        self.0.kind
+    }
+
+    /// The unit of the values.
+    async fn unit(&self) -> Option<&str> {
+        self.0.unit.as_deref()
+    }
+
+    /// The meaning of the values.
+    async fn semantic(&self) -> Option<&str> {
+        self.0.semantic.as_deref()
+    }
+
+    /// The extensions of the property.
+    async fn extensions(&self) -> Vec<GraphQLExtension> {
+        self.0.extensions.iter().cloned().map(GraphQLExtension).collect()
+    }
+}
+
+/// A component.
+#[derive(Clone, Debug)]
+pub struct GraphQLComponent(pub Component);
+
+#[Object(name = "Component")]
+impl GraphQLComponent {
+    /// The name of the component.
+    async fn name(&self) -> &str {
+        self.0.name.as_str()
+    }
+
+    /// The description of the component.
+    async fn description(&self) -> &str {
+        &self.0.description
+    }
+
+    /// The version of the component.
+    async fn version(&self) -> u32 {
+        self.0.version
+    }
+
+    /// The properties of the component.
+    async fn properties(&self) -> Vec<GraphQLPropertyType> {
+        self.0.properties.iter().cloned().map(GraphQLPropertyType).collect()
+    }
+
+    /// The extensions of the component.
+    async fn extensions(&self) -> Vec<GraphQLExtension> {
+        self.0.extensions.iter().cloned().map(GraphQLExtension).collect()
+    }
+}
+
+/// An entity type.
+#[derive(Clone, Debug)]
+pub struct GraphQLEntityType(pub EntityType);
+
+#[Object(name = "EntityType")]
+impl GraphQLEntityType {
+    /// The name of the entity type.
+    async fn name(&self) -> &str {
+        self.0.name.as_str()
+    }
+
+    /// The category path of the entity type.
+    async fn group(&self) -> String {
+        self.0.group.to_string()
+    }
+
+    /// The description of the entity type.
+    async fn description(&self) -> &str {
+        &self.0.description
+    }
+
+    /// The version of the entity type.
+    async fn version(&self) -> u32 {
+        self.0.version
+    }
+
+    /// True, if the entity type can't be instantiated.
+    async fn is_abstract(&self) -> bool {
+        self.0.is_abstract
+    }
+
+    /// The names of the components of the entity type.
+    async fn components(&self) -> Vec<&str> {
+        self.0.components.iter().map(|name| name.as_str()).collect()
+    }
+
+    /// The names of the entity types this entity type extends.
+    async fn extends(&self) -> &Vec<String> {
+        &self.0.extends
+    }
+
+    /// The properties of the entity type.
+    async fn properties(&self) -> Vec<GraphQLPropertyType> {
+        self.0.properties.iter().cloned().map(GraphQLPropertyType).collect()
+    }
+
+    /// The extensions of the entity type.
+    async fn extensions(&self) -> Vec<GraphQLExtension> {
+        self.0.extensions.iter().cloned().map(GraphQLExtension).collect()
+    }
+}
+
+/// A relation type.
+#[derive(Clone, Debug)]
+pub struct GraphQLRelationType(pub RelationType);
+
+#[Object(name = "RelationType")]
+impl GraphQLRelationType {
+    /// The outbound entity type or component.
+    async fn outbound_type(&self) -> String {
+        self.0.outbound_type.to_string()
+    }
+
+    /// The name of the relation type.
+    async fn type_name(&self) -> &str {
+        self.0.type_name.as_str()
+    }
+
+    /// The inbound entity type or component.
+    async fn inbound_type(&self) -> String {
+        self.0.inbound_type.to_string()
+    }
+
+    /// The category path of the relation type.
+    async fn group(&self) -> String {
+        self.0.group.to_string()
+    }
+
+    /// The description of the relation type.
+    async fn description(&self) -> &str {
+        &self.0.description
+    }
+
+    /// The version of the relation type.
+    async fn version(&self) -> u32 {
+        self.0.version
+    }
+
+    /// True, if the relation type can't be instantiated.
+    async fn is_abstract(&self) -> bool {
+        self.0.is_abstract
+    }
+
+    /// The number of relation instances an outbound entity instance may have.
+    async fn outbound_cardinality(&self) -> Cardinality {
+        self.0.outbound_cardinality
+    }
+
+    /// The number of relation instances an inbound entity instance may have.
+    async fn inbound_cardinality(&self) -> Cardinality {
+        self.0.inbound_cardinality
+    }
+
+    /// The names of the components of the relation type.
+    async fn components(&self) -> Vec<&str> {
+        self.0.components.iter().map(|name| name.as_str()).collect()
+    }
+
+    /// The properties of the relation type.
+    async fn properties(&self) -> Vec<GraphQLPropertyType> {
+        self.0.properties.iter().cloned().map(GraphQLPropertyType).collect()
+    }
+
+    /// The extensions of the relation type.
+    async fn extensions(&self) -> Vec<GraphQLExtension> {
+        self.0.extensions.iter().cloned().map(GraphQLExtension).collect()
+    }
+}
+
+/// An entity instance.
+#[derive(Clone, Debug)]
+pub struct GraphQLEntityInstance(pub EntityInstance);
+
+#[Object(name = "EntityInstance")]
+impl GraphQLEntityInstance {
+    /// The id of the entity instance.
+    async fn id(&self) -> ID {
+        ID(self.0.id.to_string())
+    }
+
+    /// The name of the entity type.
+    async fn type_name(&self) -> &str {
+        self.0.type_name.as_str()
+    }
+
+    /// The description of the entity instance.
+    async fn description(&self) -> &str {
+        &self.0.description
+    }
+
+    /// The properties with the given names or all properties, ordered by name.
+    async fn properties(&self, names: Option<Vec<String>>) -> Vec<GraphQLPropertyInstance> {
+        get_properties(&self.0.properties, names)
+    }
+
+    /// The names of the components of the entity instance.
+    async fn components(&self) -> Vec<&str> {
+        self.0.components.iter().map(|name| name.as_str()).collect()
+    }
+
+    /// The labels of the entity instance.
+    async fn labels(&self) -> &Vec<String> {
+        &self.0.labels
+    }
+
+    /// The names of the behaviours of the entity instance.
+    async fn behaviours(&self) -> Vec<&str> {
+        self.0.behaviours.iter().map(|name| name.as_str()).collect()
+    }
+}
+
+/// A relation instance.
+#[derive(Clone, Debug)]
+pub struct GraphQLRelationInstance(pub RelationInstance);
+
+#[Object(name = "RelationInstance")]
+impl GraphQLRelationInstance {
+    /// The key of the relation instance in the format `outbound_id--type_name--inbound_id`.
+    async fn key(&self) -> ID {
+        ID(self.0.get_relation_key().to_string())
+    }
+
+    /// The id of the outbound entity instance.
+    async fn outbound_id(&self) -> ID {
+        ID(self.0.outbound_id.to_string())
+    }
+
+    /// The name of the relation type.
+    async fn type_name(&self) -> &str {
+        self.0.type_name.as_str()
+    }
+
+    /// The id of the inbound entity instance.
+    async fn inbound_id(&self) -> ID {
+        ID(self.0.inbound_id.to_string())
+    }
+
+    /// The description of the relation instance.
+    async fn description(&self) -> &str {
+        &self.0.description
+    }
+
+    /// The properties with the given names or all properties, ordered by name.
+    async fn properties(&self, names: Option<Vec<String>>) -> Vec<GraphQLPropertyInstance> {
+        get_properties(&self.0.properties, names)
+    }
+
+    /// The names of the components of the relation instance.
+    async fn components(&self) -> Vec<&str> {
+        self.0.components.iter().map(|name| name.as_str()).collect()
+    }
+
+    /// The labels of the relation instance.
+    async fn labels(&self) -> &Vec<String> {
+        &self.0.labels
+    }
+
+    /// The names of the behaviours of the relation instance.
+    async fn behaviours(&self) -> Vec<&str> {
+        self.0.behaviours.iter().map(|name| name.as_str()).collect()
+    }
+}
+
+/// A flow.
+#[derive(Clone, Debug)]
+pub struct GraphQLFlow(pub Flow);
+
+#[Object(name = "Flow")]
+impl GraphQLFlow {
+    /// The id of the flow, which is the id of the wrapper entity instance.
+    async fn id(&self) -> ID {
+        ID(self.0.id.to_string())
+    }
+
+    /// The name of the entity type of the wrapper entity instance.
+    async fn type_name(&self) -> &str {
+        &self.0.type_name
+    }
+
+    /// The name of the flow.
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    /// The description of the flow.
+    async fn description(&self) -> &str {
+        &self.0.description
+    }
+
+    /// The entity instances of the flow, including the wrapper entity instance.
+    async fn entity_instances(&self) -> Vec<GraphQLEntityInstance> {
+        self.0.entity_instances.iter().cloned().map(GraphQLEntityInstance).collect()
+    }
+
+    /// The relation instances of the flow.
+    async fn relation_instances(&self) -> Vec<GraphQLRelationInstance> {
+        self.0.relation_instances.iter().cloned().map(GraphQLRelationInstance).collect()
+    }
+}
+
+impl From<Arc<ReactiveEntityInstance>> for GraphQLEntityInstance {
+    fn from(entity_instance: Arc<ReactiveEntityInstance>) -> Self {
+        GraphQLEntityInstance(EntityInstance::from(entity_instance))
+    }
+}
+
+impl From<Arc<ReactiveRelationInstance>> for GraphQLRelationInstance {
+    fn from(relation_instance: Arc<ReactiveRelationInstance>) -> Self {
+        GraphQLRelationInstance(RelationInstance::from(relation_instance))
+    }
+}
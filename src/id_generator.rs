@@ -0,0 +1,120 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use uuid::Uuid;
+
+/// The root namespace of the namespaces of stable ids.
+pub const STABLE_ID_NAMESPACE: Uuid = Uuid::from_u128(0x6e1f_7a3c_52d4_4b8e_9c0a_3f2d_81b6_e475);
+
+/// Returns the namespace with the given name, e.g. the name of a plugin. The same name always
+/// results in the same namespace.
+pub fn stable_namespace<S: AsRef<str>>(name: S) -> Uuid {
+    Uuid::new_v5(&STABLE_ID_NAMESPACE, name.as_ref().as_bytes())
+}
+
+/// Returns the deterministic (version 5) id of the key within the namespace. The same namespace
+/// and key result in the same id across restarts and machines.
+pub fn stable_id<S: AsRef<str>>(namespace: &Uuid, key: S) -> Uuid {
+    Uuid::new_v5(namespace, key.as_ref().as_bytes())
+}
+
+/// Generates the ids of instances and the handle ids of observers.
+pub trait IdGenerator: Send + Sync {
+    fn generate(&self) -> Uuid;
+}
+
+/// Generates random (version 4) ids. This is the default id generator.
+#[derive(Debug, Default)]
+pub struct RandomIdGenerator {}
+
+impl IdGenerator for RandomIdGenerator {
+    fn generate(&self) -> Uuid {
+        Uuid::new_v4()
+    }
+}
+
+/// Generates deterministic (version 5) ids from a namespace and a counter.
+///
+/// Generators with the same namespace generate the same sequence of ids,
+/// generators with different namespaces don't collide.
+#[derive(Debug)]
+pub struct NamespacedIdGenerator {
+    /// The namespace of the generated ids.
+    pub namespace: Uuid,
+
+    /// The number of generated ids.
+    counter: AtomicU64,
+}
+
+impl NamespacedIdGenerator {
+    pub fn new(namespace: Uuid) -> NamespacedIdGenerator {
+        NamespacedIdGenerator {
+            namespace,
+            counter: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the id of the given name within the namespace.
+    pub fn generate_for<S: AsRef<str>>(&self, name: S) -> Uuid {
+        stable_id(&self.namespace, name)
+    }
+}
+
+impl IdGenerator for NamespacedIdGenerator {
+    fn generate(&self) -> Uuid {
+        let count = self.counter.fetch_add(1, Ordering::SeqCst);
+        self.generate_for(count.to_string())
+    }
+}
+
+/// Generates sequential ids starting with the given number, e.g. for tests.
+#[derive(Debug, Default)]
+pub struct SequentialIdGenerator {
+    next: AtomicU64,
+}
+
+impl SequentialIdGenerator {
+    pub fn new(start: u64) -> SequentialIdGenerator {
+        SequentialIdGenerator { next: AtomicU64::new(start) }
+    }
+}
+
+impl IdGenerator for SequentialIdGenerator {
+    fn generate(&self) -> Uuid {
+        Uuid::from_u128(self.next.fetch_add(1, Ordering::SeqCst) as u128)
+    }
+}
+
+/// Delegates to the global id generator. This is the id generator of the constructors and
+/// builders which accept an id generator, if none is given.
+#[derive(Debug, Default)]
+pub struct GlobalIdGenerator {}
+
+impl IdGenerator for GlobalIdGenerator {
+    fn generate(&self) -> Uuid {
+        generate_id()
+    }
+}
+
+static ID_GENERATOR: RwLock<Option<Arc<dyn IdGenerator>>> = RwLock::new(None);
+
+/// Registers the global id generator. Replaces a previously registered id generator.
+///
+/// The global id generator is shared by all threads. Prefer passing an id generator to the
+/// builders and constructors, e.g. in tests.
+pub fn set_id_generator(id_generator: Arc<dyn IdGenerator>) {
+    *ID_GENERATOR.write().unwrap() = Some(id_generator);
+}
+
+/// Unregisters the global id generator. Random ids are generated afterwards.
+pub fn clear_id_generator() {
+    *ID_GENERATOR.write().unwrap() = None;
+}
+
+/// Generates an id using the registered global id generator.
+pub fn generate_id() -> Uuid {
+    match ID_GENERATOR.read().unwrap().as_ref() {
+        Some(id_generator) => id_generator.generate(),
+        None => Uuid::new_v4(),
+    }
+}
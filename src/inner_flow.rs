@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    Flow, GlobalIdGenerator, IdGenerator, ReactiveEntityInstance, ReactiveFlow, ReactiveFlowConstructionError, ReactivePropertyContainer,
+    ReactiveRelationInstance,
+};
+
+/// The name of the extension of an entity type which contains the default inner flow.
+///
+/// The inner flow is instantiated whenever an entity instance of the entity type is
+/// created. The wrapper entity instance of the inner flow is the created entity instance.
+pub const INNER_FLOW_EXTENSION: &str = "inner_flow";
+
+/// Instantiates the inner flow for the given wrapper entity instance.
+///
+/// The entity instances of the inner flow get new ids, so that every wrapper entity
+/// instance has its own inner flow. Properties of the wrapper entity instance of the
+/// inner flow which are missing on the given wrapper entity instance are added. The
+/// instances of disabled layers are excluded.
+pub fn instantiate_inner_flow(inner_flow: &Flow, wrapper_entity_instance: Arc<ReactiveEntityInstance>) -> Result<ReactiveFlow, ReactiveFlowConstructionError> {
+    instantiate_inner_flow_with_id_generator(inner_flow, wrapper_entity_instance, &GlobalIdGenerator::default())
+}
+
+/// Instantiates the inner flow for the given wrapper entity instance. The new ids of the entity
+/// instances are generated by the given id generator.
+pub fn instantiate_inner_flow_with_id_generator(
+    inner_flow: &Flow,
+    wrapper_entity_instance: Arc<ReactiveEntityInstance>,
+    id_generator: &dyn IdGenerator,
+) -> Result<ReactiveFlow, ReactiveFlowConstructionError> {
+    let mut ids: HashMap<Uuid, Uuid> = HashMap::new();
+    ids.insert(inner_flow.id, wrapper_entity_instance.id);
+    let reactive_flow = ReactiveFlow::new(wrapper_entity_instance.clone());
+    let mut has_wrapper = false;
+    {
+        let mut entity_instances = reactive_flow.entity_instances.write().unwrap();
+        for entity_instance in inner_flow.entity_instances.iter() {
+            if entity_instance.id == inner_flow.id {
+                has_wrapper = true;
+                for (property_name, value) in entity_instance.properties.iter() {
+                    wrapper_entity_instance.add_property(property_name, value.clone());
+                }
+                continue;
+            }
+            if !inner_flow.is_entity_enabled(entity_instance.id) {
+                continue;
+            }
+            let mut entity_instance = entity_instance.clone();
+            let id = id_generator.generate();
+            ids.insert(entity_instance.id, id);
+            entity_instance.id = id;
+            entity_instances.insert(id, Arc::new(ReactiveEntityInstance::from(entity_instance)));
+        }
+    }
+    if !has_wrapper {
+        return Err(ReactiveFlowConstructionError::MissingWrapperInstance);
+    }
+    let mut relation_instances = reactive_flow.relation_instances.write().unwrap();
+    let entity_instances = reactive_flow.entity_instances.read().unwrap();
+    for relation_instance in inner_flow.relation_instances.iter() {
+        if !inner_flow.is_relation_enabled(relation_instance) {
+            continue;
+        }
+        let outbound = ids
+            .get(&relation_instance.outbound_id)
+            .and_then(|id| entity_instances.get(id))
+            .ok_or(ReactiveFlowConstructionError::MissingOutboundEntityInstance(relation_instance.outbound_id))?;
+        let inbound = ids
+            .get(&relation_instance.inbound_id)
+            .and_then(|id| entity_instances.get(id))
+            .ok_or(ReactiveFlowConstructionError::MissingInboundEntityInstance(relation_instance.inbound_id))?;
+        let mut relation_instance = relation_instance.clone();
+        relation_instance.outbound_id = outbound.id;
+        relation_instance.inbound_id = inbound.id;
+        if let Ok(reactive_relation_instance) = ReactiveRelationInstance::from_instance(outbound.clone(), inbound.clone(), relation_instance) {
+            relation_instances.insert(reactive_relation_instance.key().clone(), Arc::new(reactive_relation_instance));
+        }
+    }
+    drop(entity_instances);
+    drop(relation_instances);
+    Ok(reactive_flow)
+}
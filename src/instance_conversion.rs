@@ -0,0 +1,63 @@
+use std::fmt;
+
+#[cfg(feature = "graph-db")]
+use indradb::{Identifier, NamedProperty};
+use uuid::Uuid;
+
+#[cfg(feature = "graph-db")]
+use crate::{validate_identifier, PropertyMap};
+
+/// An error which occurs if a vertex or an edge of the graph database can't be converted into an instance.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InstanceConversionError {
+    /// The type name isn't a valid identifier.
+    InvalidIdentifier(String),
+
+    /// The outbound entity instance with the given id is missing.
+    MissingOutbound(Uuid),
+
+    /// The inbound entity instance with the given id is missing.
+    MissingInbound(Uuid),
+
+    /// The name of the property isn't a valid identifier.
+    InvalidPropertyName(String),
+
+    /// The property with the given name occurs multiple times.
+    DuplicateProperty(String),
+}
+
+impl fmt::Display for InstanceConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InstanceConversionError::InvalidIdentifier(type_name) => write!(f, "The type name {} is not a valid identifier", type_name),
+            InstanceConversionError::MissingOutbound(id) => write!(f, "The outbound entity instance {} is missing", id),
+            InstanceConversionError::MissingInbound(id) => write!(f, "The inbound entity instance {} is missing", id),
+            InstanceConversionError::InvalidPropertyName(name) => write!(f, "The property name {} is not a valid identifier", name),
+            InstanceConversionError::DuplicateProperty(name) => write!(f, "The property {} occurs multiple times", name),
+        }
+    }
+}
+
+/// Returns the type name, if the identifier is valid.
+#[cfg(feature = "graph-db")]
+pub(crate) fn convert_type_name(t: &Identifier) -> Result<String, InstanceConversionError> {
+    validate_identifier(t.as_str()).map_err(|_| InstanceConversionError::InvalidIdentifier(t.to_string()))?;
+    Ok(t.to_string())
+}
+
+/// Returns the property values, if the property names are valid and unique.
+#[cfg(feature = "graph-db")]
+pub(crate) fn convert_named_properties(props: &[NamedProperty]) -> Result<PropertyMap, InstanceConversionError> {
+    let mut properties = PropertyMap::new();
+    for named_property in props {
+        let name = named_property.name.to_string();
+        if validate_identifier(&name).is_err() {
+            return Err(InstanceConversionError::InvalidPropertyName(name));
+        }
+        if properties.contains_key(&name) {
+            return Err(InstanceConversionError::DuplicateProperty(name));
+        }
+        properties.insert(name, named_property.value.clone());
+    }
+    Ok(properties)
+}
@@ -0,0 +1,124 @@
+use std::fmt;
+use std::sync::RwLock;
+
+use inexor_rgf_core_frp::Stream;
+
+/// The description of a reactive instance which can be changed at runtime.
+///
+/// Observers are notified with the new description after it has been changed.
+pub struct InstanceDescription {
+    /// The current description.
+    value: RwLock<String>,
+
+    /// The observers of the description.
+    stream: RwLock<Stream<'static, String>>,
+}
+
+impl InstanceDescription {
+    pub fn new<S: Into<String>>(description: S) -> InstanceDescription {
+        InstanceDescription {
+            value: RwLock::new(description.into()),
+            stream: RwLock::new(Stream::new()),
+        }
+    }
+
+    /// Returns the current description.
+    pub fn get(&self) -> String {
+        self.value.read().unwrap().clone()
+    }
+
+    /// Changes the description and notifies the observers.
+    pub fn set<S: Into<String>>(&self, description: S) {
+        let description = description.into();
+        *self.value.write().unwrap() = description.clone();
+        self.stream.read().unwrap().send(&description);
+    }
+
+    /// Observes changes of the description.
+    pub fn observe_with_handle<F: Fn(&String) + Send + Sync + 'static>(&self, subscriber: F, handle_id: u128) {
+        self.stream.read().unwrap().observe_with_handle(subscriber, handle_id);
+    }
+
+    /// Removes the observer with the given handle id.
+    pub fn remove(&self, handle_id: u128) {
+        self.stream.read().unwrap().remove(handle_id);
+    }
+
+    /// Returns true, if the description is empty.
+    pub fn is_empty(&self) -> bool {
+        self.value.read().unwrap().is_empty()
+    }
+}
+
+impl Default for InstanceDescription {
+    fn default() -> Self {
+        InstanceDescription::new(String::new())
+    }
+}
+
+/// Copies the current description. The observers are not copied.
+impl Clone for InstanceDescription {
+    fn clone(&self) -> Self {
+        InstanceDescription::new(self.get())
+    }
+}
+
+impl From<String> for InstanceDescription {
+    fn from(description: String) -> Self {
+        InstanceDescription::new(description)
+    }
+}
+
+impl From<&str> for InstanceDescription {
+    fn from(description: &str) -> Self {
+        InstanceDescription::new(description)
+    }
+}
+
+impl PartialEq for InstanceDescription {
+    fn eq(&self, other: &Self) -> bool {
+        self.get() == other.get()
+    }
+}
+
+impl PartialEq<str> for InstanceDescription {
+    fn eq(&self, other: &str) -> bool {
+        *self.value.read().unwrap() == other
+    }
+}
+
+impl PartialEq<&str> for InstanceDescription {
+    fn eq(&self, other: &&str) -> bool {
+        *self.value.read().unwrap() == *other
+    }
+}
+
+impl PartialEq<String> for InstanceDescription {
+    fn eq(&self, other: &String) -> bool {
+        *self.value.read().unwrap() == *other
+    }
+}
+
+impl PartialEq<InstanceDescription> for String {
+    fn eq(&self, other: &InstanceDescription) -> bool {
+        other == self
+    }
+}
+
+impl PartialEq<InstanceDescription> for &str {
+    fn eq(&self, other: &InstanceDescription) -> bool {
+        other == self
+    }
+}
+
+impl fmt::Debug for InstanceDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.get())
+    }
+}
+
+impl fmt::Display for InstanceDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.get())
+    }
+}
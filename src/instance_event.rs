@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::{CompositionChange, CompositionEvent};
+
+/// An event of a reactive instance.
+#[derive(Clone, Debug, PartialEq)]
+pub enum InstanceEvent {
+    /// The value of the property with the given name has changed.
+    PropertyChanged { name: String, value: Value },
+
+    /// The property with the given name has been added.
+    PropertyAdded { name: String, value: Value },
+
+    /// The property with the given name has been removed. The value is the last value of the property.
+    PropertyRemoved { name: String, value: Value },
+
+    /// The component with the given name has been added.
+    ComponentAdded(String),
+
+    /// The component with the given name has been removed.
+    ComponentRemoved(String),
+
+    /// The behaviour with the given name has been added.
+    BehaviourAdded(String),
+
+    /// The behaviour with the given name has been removed.
+    BehaviourRemoved(String),
+
+    /// A value has been set on the property with the given name, which doesn't exist. The value
+    /// has been rejected, because the unknown property policy of the instance is error.
+    UnknownPropertyRejected { name: String, value: Value },
+
+    /// The instance has been destroyed. No further events are sent.
+    Destroyed,
+}
+
+impl InstanceEvent {
+    /// Returns the event of the given change of the composition.
+    pub(crate) fn composition(change: CompositionChange, name: String) -> Self {
+        match change {
+            CompositionChange::ComponentAdded => InstanceEvent::ComponentAdded(name),
+            CompositionChange::ComponentRemoved => InstanceEvent::ComponentRemoved(name),
+            CompositionChange::BehaviourAdded => InstanceEvent::BehaviourAdded(name),
+            CompositionChange::BehaviourRemoved => InstanceEvent::BehaviourRemoved(name),
+        }
+    }
+}
+
+impl From<&CompositionEvent> for InstanceEvent {
+    fn from(event: &CompositionEvent) -> Self {
+        InstanceEvent::composition(event.change, event.name.clone())
+    }
+}
+
+/// Callback which is called with the events of a reactive instance.
+pub type InstanceEventObserver = Arc<dyn Fn(&InstanceEvent) + Send + Sync>;
@@ -0,0 +1,65 @@
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures::channel::mpsc::{unbounded, UnboundedReceiver};
+use futures::Stream;
+
+use crate::{InstanceEvent, ReactiveEntityInstance, ReactiveRelationInstance};
+
+/// Asynchronous stream of the events of a reactive instance.
+///
+/// The observer is removed when the stream is dropped.
+pub struct InstanceEventStream {
+    /// Removes the observer.
+    unsubscribe: Option<Box<dyn FnOnce() + Send + Sync>>,
+
+    /// Receives the events.
+    receiver: UnboundedReceiver<InstanceEvent>,
+}
+
+impl Stream for InstanceEventStream {
+    type Item = InstanceEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
+
+impl Drop for InstanceEventStream {
+    fn drop(&mut self) {
+        if let Some(unsubscribe) = self.unsubscribe.take() {
+            unsubscribe();
+        }
+    }
+}
+
+impl ReactiveEntityInstance {
+    /// Returns an asynchronous stream of the events of this entity instance.
+    pub fn as_event_stream(self: &Arc<Self>) -> InstanceEventStream {
+        let (sender, receiver) = unbounded();
+        let handle_id = self.observe_events(move |event| {
+            let _ = sender.unbounded_send(event.clone());
+        });
+        let observers = self.observers.clone();
+        InstanceEventStream {
+            unsubscribe: Some(Box::new(move || observers.remove(handle_id))),
+            receiver,
+        }
+    }
+}
+
+impl ReactiveRelationInstance {
+    /// Returns an asynchronous stream of the events of this relation instance.
+    pub fn as_event_stream(self: &Arc<Self>) -> InstanceEventStream {
+        let (sender, receiver) = unbounded();
+        let handle_id = self.observe_events(move |event| {
+            let _ = sender.unbounded_send(event.clone());
+        });
+        let observers = self.observers.clone();
+        InstanceEventStream {
+            unsubscribe: Some(Box::new(move || observers.remove(handle_id))),
+            receiver,
+        }
+    }
+}
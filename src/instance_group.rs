@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+use crate::{MutablePropertyInstanceSetter, PropertyInstanceGetter, RelationInstanceKey};
+
+/// A named set of entity instances and relation instances.
+///
+/// Instance groups are used for batch operations, editor selections and the
+/// layer-like organization of large flows. An instance can be a member of
+/// multiple groups.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct InstanceGroup {
+    /// The name of the group.
+    pub name: String,
+
+    /// Textual description of the group.
+    #[serde(default = "String::new")]
+    pub description: String,
+
+    /// The ids of the entity instances which are members of the group.
+    #[serde(default = "Vec::new", alias = "entities")]
+    pub entity_instances: Vec<Uuid>,
+
+    /// The keys of the relation instances which are members of the group.
+    #[serde(default = "Vec::new", alias = "relations")]
+    pub relation_instances: Vec<RelationInstanceKey>,
+
+    /// The properties of the group.
+    #[serde(default = "HashMap::new")]
+    pub properties: HashMap<String, Value>,
+}
+
+impl InstanceGroup {
+    /// Constructs a new empty group with the given name.
+    pub fn new<S: Into<String>>(name: S) -> InstanceGroup {
+        InstanceGroup {
+            name: name.into(),
+            description: String::new(),
+            entity_instances: Vec::new(),
+            relation_instances: Vec::new(),
+            properties: HashMap::new(),
+        }
+    }
+
+    /// Adds the entity instance with the given id. Returns false, if it is already a member.
+    pub fn add_entity(&mut self, id: Uuid) -> bool {
+        if self.has_entity(id) {
+            return false;
+        }
+        self.entity_instances.push(id);
+        true
+    }
+
+    /// Removes the entity instance with the given id. Returns false, if it isn't a member.
+    pub fn remove_entity(&mut self, id: Uuid) -> bool {
+        let len = self.entity_instances.len();
+        self.entity_instances.retain(|entity_instance_id| *entity_instance_id != id);
+        len != self.entity_instances.len()
+    }
+
+    /// Returns true, if the entity instance with the given id is a member of the group.
+    pub fn has_entity(&self, id: Uuid) -> bool {
+        self.entity_instances.contains(&id)
+    }
+
+    /// Adds the relation instance with the given key. Returns false, if it is already a member.
+    pub fn add_relation(&mut self, key: RelationInstanceKey) -> bool {
+        if self.has_relation(&key) {
+            return false;
+        }
+        self.relation_instances.push(key);
+        true
+    }
+
+    /// Removes the relation instance with the given key. Returns false, if it isn't a member.
+    pub fn remove_relation(&mut self, key: &RelationInstanceKey) -> bool {
+        let len = self.relation_instances.len();
+        self.relation_instances.retain(|relation_instance_key| relation_instance_key != key);
+        len != self.relation_instances.len()
+    }
+
+    /// Returns true, if the relation instance with the given key is a member of the group.
+    pub fn has_relation(&self, key: &RelationInstanceKey) -> bool {
+        self.relation_instances.contains(key)
+    }
+
+    /// Removes the entity instance with the given id and all relation instances which are connected to it.
+    pub fn remove_entity_and_relations(&mut self, id: Uuid) {
+        self.remove_entity(id);
+        self.relation_instances.retain(|key| key.outbound_id != id && key.inbound_id != id);
+    }
+
+    /// Returns the number of members.
+    pub fn len(&self) -> usize {
+        self.entity_instances.len() + self.relation_instances.len()
+    }
+
+    /// Returns true, if the group has no members.
+    pub fn is_empty(&self) -> bool {
+        self.entity_instances.is_empty() && self.relation_instances.is_empty()
+    }
+
+    /// Removes all members.
+    pub fn clear(&mut self) {
+        self.entity_instances.clear();
+        self.relation_instances.clear();
+    }
+}
+
+impl PropertyInstanceGetter for InstanceGroup {
+    fn get<S: AsRef<str>>(&self, property_name: S) -> Option<Value> {
+        self.properties.get(property_name.as_ref()).cloned()
+    }
+
+    fn as_bool<S: AsRef<str>>(&self, property_name: S) -> Option<bool> {
+        self.properties.get(property_name.as_ref()).and_then(|p| p.as_bool())
+    }
+
+    fn as_u64<S: AsRef<str>>(&self, property_name: S) -> Option<u64> {
+        self.properties.get(property_name.as_ref()).and_then(|p| p.as_u64())
+    }
+
+    fn as_i64<S: AsRef<str>>(&self, property_name: S) -> Option<i64> {
+        self.properties.get(property_name.as_ref()).and_then(|p| p.as_i64())
+    }
+
+    fn as_f64<S: AsRef<str>>(&self, property_name: S) -> Option<f64> {
+        self.properties.get(property_name.as_ref()).and_then(|p| p.as_f64())
+    }
+
+    fn as_string<S: AsRef<str>>(&self, property_name: S) -> Option<String> {
+        self.properties.get(property_name.as_ref()).and_then(|p| p.as_str().map(|s| s.to_string()))
+    }
+
+    fn as_array<S: AsRef<str>>(&self, property_name: S) -> Option<Vec<Value>> {
+        self.properties.get(property_name.as_ref()).and_then(|p| p.as_array().cloned())
+    }
+
+    fn as_object<S: AsRef<str>>(&self, property_name: S) -> Option<Map<String, Value>> {
+        self.properties.get(property_name.as_ref()).and_then(|p| p.as_object().cloned())
+    }
+}
+
+impl MutablePropertyInstanceSetter for InstanceGroup {
+    fn set<S: AsRef<str>>(&mut self, property_name: S, value: Value) {
+        self.properties.insert(property_name.as_ref().to_string(), value);
+    }
+}
@@ -0,0 +1,117 @@
+use std::sync::{Arc, Weak};
+
+use uuid::Uuid;
+
+use crate::EdgeKey;
+use crate::{ReactiveEntityInstance, ReactiveFlow, ReactiveRelationInstance, RelationInstanceKey};
+
+/// A weak handle to a reactive entity instance.
+///
+/// Relation instances hold strong references to their outbound and inbound entity instances.
+/// Behaviours and other long living objects should refer to relation instances and entity
+/// instances with handles, so that they don't create reference cycles and removed instances are
+/// dropped by their owner, e.g. the flow. The handles don't prevent cycles which are created
+/// with strong references.
+#[derive(Clone, Debug)]
+pub struct EntityInstanceHandle {
+    /// The id of the entity instance.
+    pub id: Uuid,
+
+    instance: Weak<ReactiveEntityInstance>,
+}
+
+impl EntityInstanceHandle {
+    pub fn new(entity_instance: &Arc<ReactiveEntityInstance>) -> EntityInstanceHandle {
+        EntityInstanceHandle {
+            id: entity_instance.id,
+            instance: Arc::downgrade(entity_instance),
+        }
+    }
+
+    /// Returns the entity instance or none, if it has been dropped.
+    pub fn upgrade(&self) -> Option<Arc<ReactiveEntityInstance>> {
+        self.instance.upgrade()
+    }
+
+    /// Returns true, if the entity instance hasn't been dropped.
+    pub fn is_alive(&self) -> bool {
+        self.instance.strong_count() > 0
+    }
+}
+
+/// A weak handle to a reactive relation instance.
+#[derive(Clone, Debug)]
+pub struct RelationInstanceHandle {
+    /// The key of the relation instance.
+    pub key: RelationInstanceKey,
+
+    instance: Weak<ReactiveRelationInstance>,
+}
+
+impl RelationInstanceHandle {
+    pub fn new(relation_instance: &Arc<ReactiveRelationInstance>) -> RelationInstanceHandle {
+        RelationInstanceHandle {
+            key: RelationInstanceKey::from(relation_instance.as_ref()),
+            instance: Arc::downgrade(relation_instance),
+        }
+    }
+
+    /// Returns the relation instance or none, if it has been dropped.
+    pub fn upgrade(&self) -> Option<Arc<ReactiveRelationInstance>> {
+        self.instance.upgrade()
+    }
+
+    /// Returns true, if the relation instance hasn't been dropped.
+    pub fn is_alive(&self) -> bool {
+        self.instance.strong_count() > 0
+    }
+}
+
+impl ReactiveEntityInstance {
+    /// Returns a weak handle to this entity instance.
+    pub fn handle(self: &Arc<Self>) -> EntityInstanceHandle {
+        EntityInstanceHandle::new(self)
+    }
+}
+
+impl ReactiveRelationInstance {
+    /// Returns a weak handle to this relation instance.
+    pub fn handle(self: &Arc<Self>) -> RelationInstanceHandle {
+        RelationInstanceHandle::new(self)
+    }
+
+    /// Returns a weak handle to the outbound entity instance.
+    pub fn outbound_handle(&self) -> EntityInstanceHandle {
+        EntityInstanceHandle::new(&self.outbound)
+    }
+
+    /// Returns a weak handle to the inbound entity instance.
+    pub fn inbound_handle(&self) -> EntityInstanceHandle {
+        EntityInstanceHandle::new(&self.inbound)
+    }
+}
+
+impl ReactiveFlow {
+    /// Removes the entity instance and all relation instances of the flow which are connected to
+    /// it. Because the relation instances hold strong references to their entity instances,
+    /// removing only the entity instance would keep it alive. The instances are removed while the
+    /// flow is locked, so no relation instance can be connected to the entity instance in the
+    /// meantime. Returns the removed relation instances.
+    pub fn remove_entity_with_relations(&self, id: Uuid) -> Vec<Arc<ReactiveRelationInstance>> {
+        // The relation instances are locked before the entity instances like in the construction of flows
+        let mut relation_instances = self.relation_instances.write().unwrap();
+        let mut entity_instances = self.entity_instances.write().unwrap();
+        let edge_keys: Vec<EdgeKey> = relation_instances
+            .iter()
+            .filter(|(_, relation_instance)| relation_instance.outbound.id == id || relation_instance.inbound.id == id)
+            .map(|(edge_key, _)| edge_key.clone())
+            .collect();
+        let removed = edge_keys.iter().filter_map(|edge_key| relation_instances.remove(edge_key)).collect();
+        entity_instances.remove(&id);
+        drop(entity_instances);
+        drop(relation_instances);
+        self.relations_removed.write().unwrap().extend(edge_keys);
+        self.entities_removed.write().unwrap().push(id);
+        removed
+    }
+}
@@ -0,0 +1,192 @@
+use std::sync::Arc;
+
+use dashmap::DashSet;
+use serde::{Deserialize, Deserializer};
+
+use crate::{EntityInstance, ReactiveEntityInstance, ReactiveFlow, ReactivePropertyContainer, ReactiveRelationInstance, RelationInstance};
+
+/// Separates the key and the value of a key/value label, e.g. `level:3`.
+pub const LABEL_SEPARATOR: char = ':';
+
+/// Returns the value of the label, if the label is a key/value label with the given key.
+pub fn get_label_value<'a>(label: &'a str, key: &str) -> Option<&'a str> {
+    label
+        .split_once(LABEL_SEPARATOR)
+        .filter(|(label_key, _)| *label_key == key)
+        .map(|(_, value)| value)
+}
+
+/// Deserializes the labels of an instance. Duplicate labels are removed, the first occurrence
+/// is kept.
+pub(crate) fn deserialize_labels<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    let mut labels: Vec<String> = Vec::new();
+    for label in Vec::<String>::deserialize(deserializer)? {
+        if !labels.contains(&label) {
+            labels.push(label);
+        }
+    }
+    Ok(labels)
+}
+
+/// The labels of an instance, e.g. `debug` or `level:3`. The lookups are implemented once for
+/// all instances.
+pub trait InstanceLabels {
+    /// Returns true, if the predicate is true for any label.
+    fn any_label<F: FnMut(&str) -> bool>(&self, predicate: F) -> bool;
+
+    /// Returns true, if the instance has the given label.
+    fn has_label<S: AsRef<str>>(&self, label: S) -> bool {
+        self.any_label(|l| l == label.as_ref())
+    }
+
+    /// Returns the labels in order.
+    fn get_labels(&self) -> Vec<String> {
+        let mut labels = Vec::new();
+        self.any_label(|label| {
+            labels.push(label.to_string());
+            false
+        });
+        labels.sort();
+        labels
+    }
+
+    /// Returns the value of the key/value label with the given key, e.g. `3` for the key `level`
+    /// and the label `level:3`. If there are multiple labels with the key, the first in order is used.
+    fn get_label_value<S: AsRef<str>>(&self, key: S) -> Option<String> {
+        self.get_labels()
+            .iter()
+            .find_map(|label| get_label_value(label, key.as_ref()).map(String::from))
+    }
+}
+
+/// Adds and removes the labels of an instance which is owned, e.g. an entity instance.
+pub trait MutableInstanceLabels: InstanceLabels {
+    /// Returns the labels.
+    fn labels_mut(&mut self) -> &mut Vec<String>;
+
+    /// Adds the label. Returns false, if the instance already has the label.
+    fn add_label<S: Into<String>>(&mut self, label: S) -> bool {
+        let label = label.into();
+        if self.has_label(&label) {
+            return false;
+        }
+        self.labels_mut().push(label);
+        true
+    }
+
+    /// Removes the label. Returns false, if the instance doesn't have the label.
+    fn remove_label<S: AsRef<str>>(&mut self, label: S) -> bool {
+        let labels = self.labels_mut();
+        let count = labels.len();
+        labels.retain(|l| l != label.as_ref());
+        labels.len() != count
+    }
+}
+
+/// Adds and removes the labels of a reactive instance. The revision of the instance is bumped
+/// if the labels have been changed.
+pub trait ReactiveInstanceLabels: InstanceLabels + ReactivePropertyContainer {
+    /// Returns the labels.
+    fn label_set(&self) -> &DashSet<String>;
+
+    /// Adds the label. Returns false, if the instance already has the label.
+    fn add_label<S: Into<String>>(&self, label: S) -> bool {
+        let added = self.label_set().insert(label.into());
+        if added {
+            self.revision_counter().bump();
+        }
+        added
+    }
+
+    /// Removes the label. Returns false, if the instance doesn't have the label.
+    fn remove_label<S: AsRef<str>>(&self, label: S) -> bool {
+        let removed = self.label_set().remove(label.as_ref()).is_some();
+        if removed {
+            self.revision_counter().bump();
+        }
+        removed
+    }
+}
+
+impl InstanceLabels for EntityInstance {
+    fn any_label<F: FnMut(&str) -> bool>(&self, mut predicate: F) -> bool {
+        self.labels.iter().any(|label| predicate(label))
+    }
+}
+
+impl MutableInstanceLabels for EntityInstance {
+    fn labels_mut(&mut self) -> &mut Vec<String> {
+        &mut self.labels
+    }
+}
+
+impl InstanceLabels for RelationInstance {
+    fn any_label<F: FnMut(&str) -> bool>(&self, mut predicate: F) -> bool {
+        self.labels.iter().any(|label| predicate(label))
+    }
+}
+
+impl MutableInstanceLabels for RelationInstance {
+    fn labels_mut(&mut self) -> &mut Vec<String> {
+        &mut self.labels
+    }
+}
+
+impl InstanceLabels for ReactiveEntityInstance {
+    fn any_label<F: FnMut(&str) -> bool>(&self, mut predicate: F) -> bool {
+        self.labels.iter().any(|label| predicate(label.as_str()))
+    }
+
+    fn has_label<S: AsRef<str>>(&self, label: S) -> bool {
+        self.labels.contains(label.as_ref())
+    }
+}
+
+impl ReactiveInstanceLabels for ReactiveEntityInstance {
+    fn label_set(&self) -> &DashSet<String> {
+        &self.labels
+    }
+}
+
+impl InstanceLabels for ReactiveRelationInstance {
+    fn any_label<F: FnMut(&str) -> bool>(&self, mut predicate: F) -> bool {
+        self.labels.iter().any(|label| predicate(label.as_str()))
+    }
+
+    fn has_label<S: AsRef<str>>(&self, label: S) -> bool {
+        self.labels.contains(label.as_ref())
+    }
+}
+
+impl ReactiveInstanceLabels for ReactiveRelationInstance {
+    fn label_set(&self) -> &DashSet<String> {
+        &self.labels
+    }
+}
+
+impl ReactiveFlow {
+    /// Returns the entity instances of the flow with the given label, ordered by id.
+    pub fn get_entities_by_label<S: AsRef<str>>(&self, label: S) -> Vec<Arc<ReactiveEntityInstance>> {
+        let mut entity_instances: Vec<Arc<ReactiveEntityInstance>> = self
+            .entity_instances
+            .read()
+            .unwrap()
+            .values()
+            .filter(|entity_instance| entity_instance.has_label(label.as_ref()))
+            .cloned()
+            .collect();
+        entity_instances.sort_by_key(|entity_instance| entity_instance.id);
+        entity_instances
+    }
+
+    /// Returns the relation instances of the flow with the given label.
+    pub fn get_relations_by_label<S: AsRef<str>>(&self, label: S) -> Vec<Arc<ReactiveRelationInstance>> {
+        self.relation_instances
+            .read()
+            .unwrap()
+            .values()
+            .filter(|relation_instance| relation_instance.has_label(label.as_ref()))
+            .cloned()
+            .collect()
+    }
+}
@@ -0,0 +1,260 @@
+use std::sync::{Arc, RwLock};
+
+use dashmap::DashMap;
+use serde_json::Value;
+
+use crate::prioritized_observers::WeakPrioritizedObservers;
+use crate::property_observer::subscribe;
+use crate::{
+    CompositionChange, CompositionEvent, CompositionObserver, CompositionTarget, InstanceEvent, InstanceEventObserver, PropertyObserver,
+    ReactivePropertyInstance,
+};
+
+/// An observer of all properties, of the events or of the composition of an instance.
+#[derive(Clone)]
+enum InstanceObserver {
+    Properties(PropertyObserver),
+    Events(InstanceEventObserver),
+    Composition(CompositionObserver),
+}
+
+impl InstanceObserver {
+    /// Returns the observer which is subscribed to the streams of the properties or none, if the
+    /// observer doesn't observe the properties.
+    fn property_observer(&self) -> Option<PropertyObserver> {
+        match self {
+            InstanceObserver::Properties(observer) => Some(observer.clone()),
+            InstanceObserver::Events(observer) => {
+                let observer = observer.clone();
+                Some(Arc::new(move |name, value| {
+                    observer(&InstanceEvent::PropertyChanged {
+                        name: name.to_string(),
+                        value: value.clone(),
+                    })
+                }))
+            }
+            InstanceObserver::Composition(_) => None,
+        }
+    }
+}
+
+struct InstanceObserverSubscription {
+    handle_id: u128,
+    observer: InstanceObserver,
+    /// The observers of the properties the observer is subscribed to.
+    properties: Vec<WeakPrioritizedObservers>,
+}
+
+impl InstanceObserverSubscription {
+    fn subscribe(&mut self, property_instance: &ReactivePropertyInstance) {
+        let observers = property_instance.prioritized_observers.downgrade();
+        if self.properties.iter().any(|subscribed| subscribed.ptr_eq(&observers)) {
+            return;
+        }
+        if let Some(observer) = self.observer.property_observer() {
+            subscribe(property_instance, self.handle_id, observer);
+            self.properties.push(observers);
+        }
+    }
+
+    fn unsubscribe_from(&mut self, property_instance: &ReactivePropertyInstance) {
+        let observers = property_instance.prioritized_observers.downgrade();
+        if let Some(index) = self.properties.iter().position(|subscribed| subscribed.ptr_eq(&observers)) {
+            self.properties.swap_remove(index);
+            property_instance.remove_observer(self.handle_id);
+        }
+    }
+
+    fn unsubscribe(&self) {
+        for observers in self.properties.iter() {
+            observers.remove(self.handle_id);
+        }
+    }
+}
+
+/// The observers of all properties, the event observers and the composition observers of a
+/// reactive instance.
+///
+/// The observers are owned by the instance and are subscribed to the properties which are added
+/// later. Each observer remembers the properties it is subscribed to, so it can be removed even if
+/// the properties have been removed or are shared with another instance.
+#[derive(Default)]
+pub struct InstanceObservers {
+    subscriptions: RwLock<Vec<InstanceObserverSubscription>>,
+}
+
+impl InstanceObservers {
+    /// Observes the current properties and the properties which are added later.
+    pub(crate) fn observe_properties(&self, properties: &DashMap<Arc<str>, ReactivePropertyInstance>, handle_id: u128, observer: PropertyObserver) {
+        self.observe(properties, handle_id, InstanceObserver::Properties(observer));
+    }
+
+    /// Observes the property changes, the added properties and the changes of the composition.
+    pub(crate) fn observe_events(&self, properties: &DashMap<Arc<str>, ReactivePropertyInstance>, handle_id: u128, observer: InstanceEventObserver) {
+        self.observe(properties, handle_id, InstanceObserver::Events(observer));
+    }
+
+    /// Observes the changes of the composition of the instance.
+    pub(crate) fn observe_composition(&self, handle_id: u128, observer: CompositionObserver) {
+        self.subscriptions.write().unwrap().push(InstanceObserverSubscription {
+            handle_id,
+            observer: InstanceObserver::Composition(observer),
+            properties: Vec::new(),
+        });
+    }
+
+    fn observe(&self, properties: &DashMap<Arc<str>, ReactivePropertyInstance>, handle_id: u128, observer: InstanceObserver) {
+        self.subscriptions.write().unwrap().push(InstanceObserverSubscription {
+            handle_id,
+            observer,
+            properties: Vec::new(),
+        });
+        self.subscribe_properties(&[handle_id], properties);
+    }
+
+    /// Subscribes the registered observers with the given handle ids to the properties.
+    ///
+    /// The observers have to be registered first, so properties which are added concurrently are
+    /// either attached to them or are subscribed here. The map isn't locked while subscribing.
+    fn subscribe_properties(&self, handle_ids: &[u128], properties: &DashMap<Arc<str>, ReactivePropertyInstance>) {
+        let property_instances: Vec<ReactivePropertyInstance> = properties.iter().map(|property_instance| property_instance.share()).collect();
+        let mut writer = self.subscriptions.write().unwrap();
+        for subscription in writer.iter_mut().filter(|subscription| handle_ids.contains(&subscription.handle_id)) {
+            for property_instance in property_instances.iter() {
+                subscription.subscribe(property_instance);
+            }
+        }
+    }
+
+    /// Returns true, if no observers are registered.
+    pub fn is_empty(&self) -> bool {
+        self.subscriptions.read().unwrap().is_empty()
+    }
+
+    /// Returns the number of registered observers.
+    pub fn len(&self) -> usize {
+        self.subscriptions.read().unwrap().len()
+    }
+
+    /// Removes the observer with the given handle id from the properties it is subscribed to.
+    pub fn remove(&self, handle_id: u128) {
+        let removed: Vec<InstanceObserverSubscription> = {
+            let mut writer = self.subscriptions.write().unwrap();
+            let (removed, kept) = writer.drain(..).partition(|subscription| subscription.handle_id == handle_id);
+            *writer = kept;
+            removed
+        };
+        for subscription in removed {
+            subscription.unsubscribe();
+        }
+    }
+
+    /// Takes over the observers of the given instance, e.g. if the instance is replaced. The
+    /// observers stay subscribed to the properties they are subscribed to. The observers of both
+    /// instances are subscribed to the given properties, whose observers may have been replaced.
+    pub(crate) fn take_over(&self, other: &InstanceObservers, properties: &DashMap<Arc<str>, ReactivePropertyInstance>) {
+        if std::ptr::eq(self, other) {
+            return;
+        }
+        let subscriptions: Vec<InstanceObserverSubscription> = other.subscriptions.write().unwrap().drain(..).collect();
+        let handle_ids: Vec<u128> = {
+            let mut writer = self.subscriptions.write().unwrap();
+            writer.extend(subscriptions);
+            for subscription in writer.iter_mut() {
+                subscription.properties.retain(WeakPrioritizedObservers::is_alive);
+            }
+            writer.iter().map(|subscription| subscription.handle_id).collect()
+        };
+        self.subscribe_properties(&handle_ids, properties);
+    }
+
+    /// Subscribes the observers to the added property.
+    pub(crate) fn attach(&self, property_instance: &ReactivePropertyInstance) {
+        let mut writer = self.subscriptions.write().unwrap();
+        for subscription in writer.iter_mut() {
+            subscription.subscribe(property_instance);
+        }
+    }
+
+    /// Unsubscribes the observers from the removed property.
+    pub(crate) fn detach(&self, property_instance: &ReactivePropertyInstance) {
+        let mut writer = self.subscriptions.write().unwrap();
+        for subscription in writer.iter_mut() {
+            subscription.unsubscribe_from(property_instance);
+        }
+    }
+
+    /// Notifies the event observers about the event.
+    pub(crate) fn notify(&self, event: &InstanceEvent) {
+        let observers: Vec<InstanceEventObserver> = {
+            let reader = self.subscriptions.read().unwrap();
+            reader
+                .iter()
+                .filter_map(|subscription| match &subscription.observer {
+                    InstanceObserver::Events(observer) => Some(observer.clone()),
+                    InstanceObserver::Properties(_) | InstanceObserver::Composition(_) => None,
+                })
+                .collect()
+        };
+        // The observers are called after the lock has been released, so they can register observers
+        for observer in observers {
+            observer(event);
+        }
+    }
+
+    /// Notifies the event observers about the added property.
+    pub(crate) fn notify_property_added(&self, name: &str, value: &Value) {
+        self.notify(&InstanceEvent::PropertyAdded {
+            name: name.to_string(),
+            value: value.clone(),
+        });
+    }
+
+    /// Notifies the event observers about the removed property.
+    pub(crate) fn notify_property_removed(&self, name: &str, value: &Value) {
+        self.notify(&InstanceEvent::PropertyRemoved {
+            name: name.to_string(),
+            value: value.clone(),
+        });
+    }
+
+    /// Notifies the event observers and the composition observers about the changed component or
+    /// behaviour. The target is only computed if composition observers are registered.
+    pub(crate) fn notify_composition<F: FnOnce() -> CompositionTarget>(&self, target: F, change: CompositionChange, name: &str) {
+        self.notify(&InstanceEvent::composition(change, name.to_string()));
+        let observers: Vec<CompositionObserver> = {
+            let reader = self.subscriptions.read().unwrap();
+            reader
+                .iter()
+                .filter_map(|subscription| match &subscription.observer {
+                    InstanceObserver::Composition(observer) => Some(observer.clone()),
+                    InstanceObserver::Properties(_) | InstanceObserver::Events(_) => None,
+                })
+                .collect()
+        };
+        if observers.is_empty() {
+            return;
+        }
+        let event = CompositionEvent {
+            target: target(),
+            change,
+            name: name.to_string(),
+        };
+        for observer in observers {
+            observer(&event);
+        }
+    }
+
+    /// Notifies the event observers that the instance has been destroyed and removes all observers.
+    pub(crate) fn notify_destroyed(&self) {
+        let subscriptions: Vec<InstanceObserverSubscription> = self.subscriptions.write().unwrap().drain(..).collect();
+        for subscription in subscriptions.iter() {
+            if let InstanceObserver::Events(observer) = &subscription.observer {
+                observer(&InstanceEvent::Destroyed);
+            }
+        }
+        for subscription in subscriptions {
+            subscription.unsubscribe();
+        }
+    }
+}
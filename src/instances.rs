@@ -0,0 +1,28 @@
+//! The serializable instances of the model, their builders and keys.
+
+#[cfg(feature = "arrow")]
+pub use crate::arrow_export::*;
+#[cfg(feature = "cbor")]
+pub use crate::cbor::*;
+pub use crate::connector_definition::*;
+pub use crate::content_hash::*;
+pub use crate::entity_instance::*;
+pub use crate::entity_instance_builder::*;
+pub use crate::external_key::*;
+#[cfg(feature = "graphql")]
+pub use crate::graphql::*;
+pub use crate::id_generator::*;
+pub use crate::instance_conversion::*;
+pub use crate::instance_labels::*;
+pub use crate::migration::*;
+pub use crate::property_diff::*;
+#[cfg(feature = "jsonpatch")]
+pub use crate::property_json_patch::*;
+pub use crate::property_map::*;
+pub use crate::property_mapping::*;
+pub use crate::property_nested::*;
+pub use crate::property_object::*;
+pub use crate::relation_instance::*;
+pub use crate::relation_instance_builder::*;
+pub use crate::relation_instance_key::*;
+pub use crate::relation_instance_type_id::*;
@@ -0,0 +1,9 @@
+//! The monotonic clock.
+//!
+//! `std::time::Instant` isn't available on `wasm32-unknown-unknown`, so the clock of the
+//! browser is used with the feature `wasm`. On other targets both are the same type.
+
+#[cfg(not(feature = "wasm"))]
+pub use std::time::Instant;
+#[cfg(feature = "wasm")]
+pub use web_time::Instant;
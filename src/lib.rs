@@ -2,40 +2,266 @@
 #![feature(test)]
 #![register_tool(tarpaulin)]
 
+#[cfg(feature = "derive")]
+extern crate self as inexor_rgf_core_model;
+
+#[cfg(feature = "derive")]
+pub use inexor_rgf_core_model_derive::PropertyMapping;
+
+#[cfg(feature = "arbitrary")]
+pub use arbitrary_model::*;
+#[cfg(feature = "arrow")]
+pub use arrow_export::*;
+pub use behaviour_dependency_graph::*;
+pub use behaviour_state_machine::*;
+pub use behaviour_type::*;
+pub use category_path::*;
+#[cfg(feature = "cbor")]
+pub use cbor::*;
 pub use component::*;
+pub use component_or_entity_type_name::*;
+pub use composed_type::*;
+pub use composition_event::*;
+pub use connector_definition::*;
+pub use content_hash::*;
 pub use data_type::*;
 pub use entity_instance::*;
+pub use entity_instance_builder::*;
+pub use entity_replacement::*;
 pub use entity_type::*;
 pub use extension::*;
+pub use external_key::*;
+pub use factory_registry::*;
+pub use float_policy::*;
 pub use flow::*;
+pub use flow_checkpoint::*;
+#[cfg(feature = "graph-db")]
+pub use flow_conversion::*;
+pub use flow_expectation::*;
+pub use flow_layer::*;
+pub use flow_loading::*;
+pub use flow_property_override::*;
+pub use flow_template::*;
+pub use flow_type::*;
+pub use flow_variable::*;
+pub use graph_constraint::*;
+pub use graph_identifier::*;
+pub use graph_partition::*;
+#[cfg(feature = "graphql")]
+pub use graphql::*;
+pub use id_generator::*;
+pub use inner_flow::*;
+pub use instance_conversion::*;
+pub use instance_description::*;
+pub use instance_event::*;
+#[cfg(feature = "futures")]
+pub use instance_event_stream::*;
+pub use instance_group::*;
+pub use instance_handle::*;
+pub use instance_labels::*;
+pub use instance_observers::*;
+pub use instant::*;
+pub use migration::*;
+pub use observer_guard::*;
+#[cfg(feature = "rayon")]
+pub use parallel_tick::*;
+pub use prioritized_observers::*;
+pub use projection::*;
+pub use propagation_audit::*;
+pub use propagation_suspension::*;
+pub use propagation_tracer::*;
+pub use property_conflation::*;
+pub use property_dependency_graph::*;
+pub use property_diff::*;
+pub use property_expression::*;
+pub use property_flags::*;
+pub use property_group::*;
+pub use property_history::*;
 pub use property_instance_accessor::*;
+pub use property_interning::*;
+#[cfg(feature = "jsonpatch")]
+pub use property_json_patch::*;
+#[cfg(feature = "jsonpath")]
+pub use property_jsonpath::*;
+pub use property_kind::*;
+pub use property_map::*;
+pub use property_mapping::*;
+#[cfg(feature = "metrics")]
+pub use property_metrics::*;
+pub use property_mutability::*;
+pub use property_nested::*;
+pub use property_object::*;
+pub use property_observer::*;
+pub use property_router::*;
+pub use property_statistics::*;
+#[cfg(feature = "time")]
+pub use property_time::*;
+pub use property_transaction::*;
 pub use property_type::*;
+pub use property_validator::*;
+#[cfg(feature = "futures")]
+pub use property_value_stream::*;
 pub use reactive_entity_instance::*;
 pub use reactive_flow::*;
+pub use reactive_instance::*;
+pub use reactive_property_container::*;
 pub use reactive_property_instance::*;
 pub use reactive_relation_instance::*;
+pub use relation_cardinality::*;
 pub use relation_instance::*;
+pub use relation_instance_builder::*;
+pub use relation_instance_key::*;
+pub use relation_instance_type_id::*;
+pub use relation_rewiring::*;
 pub use relation_type::*;
+pub use revision::*;
+pub use schema_exporter::*;
 pub use socket_type::*;
+pub use strict_property_mode::*;
+pub use tick_profiler::*;
+pub use topic_mapping::*;
+pub use type_enforcement::*;
+pub use type_name::*;
+pub use type_system::*;
+pub use validation::*;
+pub use version::*;
+pub use well_known_extension::*;
 
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary_model;
+#[cfg(feature = "arrow")]
+pub mod arrow_export;
+pub mod behaviour_state_machine;
+pub mod behaviour_type;
+pub mod category_path;
+#[cfg(feature = "cbor")]
+pub mod cbor;
 pub mod component;
+pub mod component_or_entity_type_name;
+pub mod composed_type;
 pub mod data_type;
+pub mod entity_replacement;
 pub mod entity_type;
 pub mod extension;
+pub mod float_policy;
+pub mod flow_type;
+pub mod property_group;
+pub mod property_kind;
 pub mod property_type;
+pub mod relation_cardinality;
 pub mod relation_type;
+pub mod schema_exporter;
 pub mod socket_type;
+pub mod type_name;
+pub mod version;
+pub mod well_known_extension;
 
+pub mod connector_definition;
+pub mod content_hash;
 pub mod entity_instance;
+pub mod entity_instance_builder;
+pub mod external_key;
 pub mod flow;
+pub mod flow_checkpoint;
+#[cfg(feature = "graph-db")]
+pub mod flow_conversion;
+pub mod flow_expectation;
+pub mod flow_layer;
+pub mod flow_loading;
+pub mod flow_property_override;
+pub mod flow_template;
+pub mod flow_variable;
+pub mod graph_constraint;
+pub mod graph_identifier;
+pub mod graph_partition;
+#[cfg(feature = "graphql")]
+pub mod graphql;
+pub mod id_generator;
+pub mod inner_flow;
+pub mod instance_group;
+pub mod instant;
+pub mod migration;
+pub mod projection;
+pub mod property_map;
+pub mod property_mapping;
+#[cfg(feature = "metrics")]
+pub mod property_metrics;
+pub mod property_mutability;
+pub mod property_nested;
+pub mod property_object;
 pub mod relation_instance;
+pub mod relation_instance_builder;
+pub mod relation_instance_key;
+pub mod relation_instance_type_id;
 
+pub mod composition_event;
+pub mod factory_registry;
+pub mod instance_conversion;
+pub mod instance_description;
+pub mod instance_event;
+#[cfg(feature = "futures")]
+pub mod instance_event_stream;
+pub mod instance_handle;
+pub mod instance_labels;
+pub mod instance_observers;
+pub mod observer_guard;
+pub mod prioritized_observers;
 pub mod reactive_entity_instance;
 pub mod reactive_flow;
+pub mod reactive_instance;
+pub mod reactive_property_container;
 pub mod reactive_property_instance;
 pub mod reactive_relation_instance;
+pub mod relation_rewiring;
+pub mod revision;
 
+#[cfg(feature = "rayon")]
+pub mod parallel_tick;
+pub mod propagation_audit;
+pub mod propagation_suspension;
+pub mod propagation_tracer;
+pub mod property_aggregation;
+pub mod property_conflation;
+pub mod property_declaration;
+pub mod property_dependency_graph;
+pub mod property_diff;
+pub mod property_expression;
+pub mod property_flags;
+pub mod property_history;
 pub mod property_instance_accessor;
+pub mod property_interning;
+#[cfg(feature = "jsonpatch")]
+pub mod property_json_patch;
+#[cfg(feature = "jsonpath")]
+pub mod property_jsonpath;
+pub mod property_observer;
+pub mod property_router;
+pub mod property_statistics;
+#[cfg(feature = "time")]
+pub mod property_time;
+pub mod property_transaction;
+pub mod property_validator;
+#[cfg(feature = "futures")]
+pub mod property_value_stream;
+#[cfg(feature = "tokio")]
+pub mod property_value_watch;
+
+pub mod strict_property_mode;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+pub mod tick_profiler;
+pub mod topic_mapping;
+pub mod type_enforcement;
+pub mod type_inheritance;
+pub mod type_system;
+pub mod validation;
+
+pub mod behaviour_dependency_graph;
+
+pub mod instances;
+pub mod prelude;
+pub mod reactive;
+pub mod types;
 
 #[cfg(test)]
 #[tarpaulin::ignore]
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::{EntityInstance, EntityType, Flow, PropertyMap, RelationInstance, RelationType};
+
+/// Transforms the properties of an instance from one version of its type to the next version.
+pub type PropertyMigration = Arc<dyn Fn(&mut PropertyMap) + Send + Sync>;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum MigrationError {
+    /// There is no migration of the type with the given name from the given version.
+    MissingMigration(String, u32),
+
+    /// The instance has a newer version than the type.
+    Downgrade(String, u32, u32),
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationError::MissingMigration(type_name, version) => {
+                write!(f, "No migration of {} from version {} to version {}", type_name, version, version + 1)
+            }
+            MigrationError::Downgrade(type_name, from_version, to_version) => {
+                write!(f, "Can't downgrade {} from version {} to version {}", type_name, from_version, to_version)
+            }
+        }
+    }
+}
+
+/// The registered migrations of entity types, relation types and components.
+///
+/// A migration transforms the property map of an instance from version N of a type to
+/// version N + 1. Migrating over several versions applies the migrations in sequence.
+#[derive(Clone, Default)]
+pub struct Migrations {
+    migrations: HashMap<(String, u32), PropertyMigration>,
+}
+
+impl Migrations {
+    pub fn new() -> Migrations {
+        Migrations::default()
+    }
+
+    /// Registers the migration of the type with the given name from the given version to the next version.
+    pub fn register<S: Into<String>, F: Fn(&mut PropertyMap) + Send + Sync + 'static>(&mut self, type_name: S, from_version: u32, migration: F) {
+        self.migrations.insert((type_name.into(), from_version), Arc::new(migration));
+    }
+
+    /// Returns true, if a migration of the type with the given name from the given version is registered.
+    pub fn has_migration<S: Into<String>>(&self, type_name: S, from_version: u32) -> bool {
+        self.migrations.contains_key(&(type_name.into(), from_version))
+    }
+
+    /// Migrates the properties from the given version to the target version. The properties
+    /// are only modified if all required migrations are registered.
+    pub fn migrate<S: Into<String>>(&self, type_name: S, properties: &mut PropertyMap, from_version: u32, to_version: u32) -> Result<(), MigrationError> {
+        let type_name = type_name.into();
+        if from_version > to_version {
+            return Err(MigrationError::Downgrade(type_name, from_version, to_version));
+        }
+        let migrations = (from_version..to_version)
+            .map(|version| {
+                self.migrations
+                    .get(&(type_name.clone(), version))
+                    .ok_or_else(|| MigrationError::MissingMigration(type_name.clone(), version))
+            })
+            .collect::<Result<Vec<&PropertyMigration>, MigrationError>>()?;
+        for migration in migrations {
+            migration(properties);
+        }
+        Ok(())
+    }
+
+    /// Migrates the entity instance from the given version to the version of the entity type.
+    pub fn migrate_entity_instance(&self, entity_instance: &mut EntityInstance, from_version: u32, entity_type: &EntityType) -> Result<(), MigrationError> {
+        self.migrate(entity_type.name.clone(), &mut entity_instance.properties, from_version, entity_type.version)
+    }
+
+    /// Migrates the relation instance from the given version to the version of the relation type.
+    pub fn migrate_relation_instance(
+        &self,
+        relation_instance: &mut RelationInstance,
+        from_version: u32,
+        relation_type: &RelationType,
+    ) -> Result<(), MigrationError> {
+        self.migrate(relation_type.type_name.clone(), &mut relation_instance.properties, from_version, relation_type.version)
+    }
+
+    /// Migrates the instances of the flow from the type versions recorded in the flow to the
+    /// versions of the given types and records the new type versions. Instances of unknown
+    /// types and instances of types without a recorded version are left unchanged.
+    pub fn migrate_flow(&self, flow: &mut Flow, entity_types: &[EntityType], relation_types: &[RelationType]) -> Result<(), MigrationError> {
+        let mut migrated = flow.clone();
+        for entity_instance in migrated.entity_instances.iter_mut() {
+            if let Some(entity_type) = entity_types.iter().find(|entity_type| entity_type.name == entity_instance.type_name) {
+                let from_version = flow.get_type_version(&entity_type.name).unwrap_or(entity_type.version);
+                self.migrate_entity_instance(entity_instance, from_version, entity_type)?;
+            }
+        }
+        for relation_instance in migrated.relation_instances.iter_mut() {
+            if let Some(relation_type) = relation_types
+                .iter()
+                .find(|relation_type| relation_type.type_name == relation_instance.type_name)
+            {
+                let from_version = flow.get_type_version(&relation_type.type_name).unwrap_or(relation_type.version);
+                self.migrate_relation_instance(relation_instance, from_version, relation_type)?;
+            }
+        }
+        for entity_type in entity_types.iter().filter(|entity_type| flow.uses_type(&entity_type.name)) {
+            migrated.type_versions.insert(entity_type.name.to_string(), entity_type.version);
+        }
+        for relation_type in relation_types.iter().filter(|relation_type| flow.uses_type(&relation_type.type_name)) {
+            migrated.type_versions.insert(relation_type.type_name.to_string(), relation_type.version);
+        }
+        *flow = migrated;
+        Ok(())
+    }
+}
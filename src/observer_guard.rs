@@ -0,0 +1,94 @@
+use serde_json::Value;
+
+use crate::{generate_id, InstanceEvent, ReactiveEntityInstance, ReactivePropertyInstance, ReactiveRelationInstance};
+
+/// Disconnects an observer when the guard is dropped.
+///
+/// The guard owns the handle id of the observer, so the observer can't be disconnected twice or
+/// with the handle id of another observer. The guard doesn't keep the observed instance alive, the
+/// observer is disconnected even if the instance has been dropped while its streams are still in use.
+#[must_use = "the observer is disconnected when the guard is dropped"]
+pub struct ObserverGuard {
+    handle_id: u128,
+    disconnect: Option<Box<dyn FnOnce(u128) + Send>>,
+}
+
+impl ObserverGuard {
+    /// Creates a guard which calls the given function with the handle id when dropped.
+    pub fn new<F: FnOnce(u128) + Send + 'static>(handle_id: u128, disconnect: F) -> ObserverGuard {
+        ObserverGuard {
+            handle_id,
+            disconnect: Some(Box::new(disconnect)),
+        }
+    }
+
+    /// Returns the handle id of the observer.
+    pub fn handle_id(&self) -> u128 {
+        self.handle_id
+    }
+
+    /// Disconnects the observer now.
+    pub fn disconnect(self) {}
+
+    /// Keeps the observer connected for the lifetime of the observed instance. Returns the handle
+    /// id, which can be used to disconnect the observer manually.
+    pub fn forget(mut self) -> u128 {
+        self.disconnect = None;
+        self.handle_id
+    }
+}
+
+impl Drop for ObserverGuard {
+    fn drop(&mut self) {
+        if let Some(disconnect) = self.disconnect.take() {
+            disconnect(self.handle_id);
+        }
+    }
+}
+
+impl ReactivePropertyInstance {
+    /// Observes the stream of the property. The observer is disconnected when the returned guard
+    /// is dropped.
+    pub fn observe_guarded<F: Fn(&Value) + Send + Sync + 'static>(&self, observer: F) -> ObserverGuard {
+        let handle_id = generate_id().as_u128();
+        self.observe_with_handle(observer, handle_id);
+        let observers = self.prioritized_observers.downgrade();
+        ObserverGuard::new(handle_id, move |handle_id| observers.remove(handle_id))
+    }
+}
+
+impl ReactiveEntityInstance {
+    /// Observes all properties, including the properties which are added later. The observer is
+    /// disconnected when the returned guard is dropped.
+    pub fn observe_all_guarded<F: Fn(&str, &Value) + Send + Sync + 'static>(&self, observer: F) -> ObserverGuard {
+        let handle_id = self.observe_all(observer);
+        let observers = self.observers.clone();
+        ObserverGuard::new(handle_id, move |handle_id| observers.remove(handle_id))
+    }
+
+    /// Observes the events of this entity instance. The observer is disconnected when the
+    /// returned guard is dropped.
+    pub fn observe_events_guarded<F: Fn(&InstanceEvent) + Send + Sync + 'static>(&self, observer: F) -> ObserverGuard {
+        let handle_id = self.observe_events(observer);
+        let observers = self.observers.clone();
+        ObserverGuard::new(handle_id, move |handle_id| observers.remove(handle_id))
+    }
+}
+
+impl ReactiveRelationInstance {
+    /// Observes all properties, including the properties which are added later. The observer is
+    /// disconnected when the returned guard is dropped.
+    pub fn observe_all_guarded<F: Fn(&str, &Value) + Send + Sync + 'static>(&self, observer: F) -> ObserverGuard {
+        let handle_id = self.observe_all(observer);
+        let observers = self.observers.clone();
+        ObserverGuard::new(handle_id, move |handle_id| observers.remove(handle_id))
+    }
+
+    /// Observes the events of this relation instance. The observer is disconnected when the
+    /// returned guard is dropped.
+    pub fn observe_events_guarded<F: Fn(&InstanceEvent) + Send + Sync + 'static>(&self, observer: F) -> ObserverGuard {
+        let handle_id = self.observe_events(observer);
+        let observers = self.observers.clone();
+        ObserverGuard::new(handle_id, move |handle_id| observers.remove(handle_id))
+    }
+}
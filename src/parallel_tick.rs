@@ -0,0 +1,79 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use rayon::prelude::*;
+
+use crate::{ReactiveEntityInstance, ReactiveFlow, ReactivePropertyContainer, ReactivePropertyInstance, ReactiveRelationInstance};
+
+/// Returns the names of the properties grouped by tick priority. The groups with a higher tick
+/// priority come first.
+fn get_tick_groups(properties: &DashMap<Arc<str>, ReactivePropertyInstance>) -> Vec<Vec<Arc<str>>> {
+    let mut tick_groups: BTreeMap<i32, Vec<Arc<str>>> = BTreeMap::new();
+    for property_instance in properties.iter() {
+        tick_groups
+            .entry(property_instance.tick_priority)
+            .or_default()
+            .push(property_instance.key().clone());
+    }
+    tick_groups.into_values().rev().collect()
+}
+
+/// Ticks the properties group by group. The properties of a group are ticked concurrently.
+fn tick_properties_parallel(properties: &DashMap<Arc<str>, ReactivePropertyInstance>) {
+    for tick_group in get_tick_groups(properties) {
+        tick_group.par_iter().for_each(|property_name| {
+            if let Some(property_instance) = properties.get(property_name) {
+                property_instance.tick();
+            }
+        });
+    }
+}
+
+impl ReactiveEntityInstance {
+    /// Ticks all properties concurrently. Properties with a higher tick priority are ticked
+    /// before properties with a lower tick priority. Properties with the same priority are
+    /// ticked concurrently in no particular order, so their observers must not depend on each
+    /// other. Afterwards the dirty flags are cleared.
+    pub fn tick_parallel(&self) {
+        tick_properties_parallel(&self.properties);
+        self.clear_dirty();
+    }
+}
+
+impl ReactiveRelationInstance {
+    /// Ticks all properties concurrently. Properties with a higher tick priority are ticked
+    /// before properties with a lower tick priority. Properties with the same priority are
+    /// ticked concurrently in no particular order, so their observers must not depend on each
+    /// other. Afterwards the dirty flags are cleared.
+    pub fn tick_parallel(&self) {
+        tick_properties_parallel(&self.properties);
+        self.clear_dirty();
+    }
+}
+
+impl ReactiveFlow {
+    /// Ticks the entity instances of the flow concurrently and afterwards the relation instances
+    /// of the flow concurrently. See `tick_entity_instances_parallel` and
+    /// `tick_relation_instances_parallel`. Unlike `tick_all`, connected instances are not ticked
+    /// in the order of the relations.
+    pub fn tick_parallel(&self) {
+        let entity_instances: Vec<Arc<ReactiveEntityInstance>> = self.entity_instances.read().unwrap().values().cloned().collect();
+        tick_entity_instances_parallel(&entity_instances);
+        let relation_instances: Vec<Arc<ReactiveRelationInstance>> = self.relation_instances.read().unwrap().values().cloned().collect();
+        tick_relation_instances_parallel(&relation_instances);
+    }
+}
+
+/// Ticks the entity instances concurrently. There is no order between the entity instances; the
+/// properties of each entity instance are ticked in the order of `ReactiveEntityInstance::tick_parallel`.
+pub fn tick_entity_instances_parallel(entity_instances: &[Arc<ReactiveEntityInstance>]) {
+    entity_instances.par_iter().for_each(|entity_instance| entity_instance.tick_parallel());
+}
+
+/// Ticks the relation instances concurrently. There is no order between the relation instances;
+/// the properties of each relation instance are ticked in the order of
+/// `ReactiveRelationInstance::tick_parallel`.
+pub fn tick_relation_instances_parallel(relation_instances: &[Arc<ReactiveRelationInstance>]) {
+    relation_instances.par_iter().for_each(|relation_instance| relation_instance.tick_parallel());
+}
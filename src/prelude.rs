@@ -0,0 +1,57 @@
+//! The commonly used traits and types.
+//!
+//! ```
+//! use inexor_rgf_core_model::prelude::*;
+//! ```
+
+pub use crate::property_instance_accessor::MutablePropertyInstanceSetter;
+pub use crate::property_instance_accessor::PropertyInstanceGetter;
+pub use crate::property_instance_accessor::PropertyInstanceSetter;
+
+pub use crate::instance_labels::InstanceLabels;
+pub use crate::instance_labels::MutableInstanceLabels;
+pub use crate::instance_labels::ReactiveInstanceLabels;
+
+pub use crate::entity_instance_builder::EntityInstanceBuilder;
+pub use crate::relation_instance_builder::ReactiveRelationInstanceBuilder;
+pub use crate::relation_instance_builder::RelationInstanceBuilder;
+
+pub use crate::property_mapping::PropertyMapping;
+#[cfg(feature = "derive")]
+pub use inexor_rgf_core_model_derive::PropertyMapping;
+
+pub use crate::component_or_entity_type_name::ComponentOrEntityTypeName;
+pub use crate::external_key::ExternalKey;
+pub use crate::relation_instance_key::RelationInstanceKey;
+pub use crate::relation_instance_key::RelationKey;
+
+pub use crate::behaviour_type::BehaviourType;
+pub use crate::category_path::CategoryPath;
+pub use crate::component::Component;
+pub use crate::data_type::DataType;
+pub use crate::entity_type::EntityType;
+pub use crate::flow_type::FlowType;
+pub use crate::property_type::PropertyType;
+pub use crate::relation_type::RelationType;
+pub use crate::socket_type::SocketType;
+pub use crate::type_name::BehaviourName;
+pub use crate::type_name::ComponentName;
+pub use crate::type_name::EntityTypeName;
+pub use crate::type_name::RelationTypeName;
+pub use crate::type_system::TypeSystem;
+pub use crate::well_known_extension::WellKnownExtension;
+pub use crate::well_known_extension::WellKnownExtensions;
+
+pub use crate::properties;
+
+pub use crate::entity_instance::EntityInstance;
+pub use crate::flow::Flow;
+pub use crate::relation_instance::RelationInstance;
+
+pub use crate::observer_guard::ObserverGuard;
+pub use crate::reactive_entity_instance::ReactiveEntityInstance;
+pub use crate::reactive_flow::ReactiveFlow;
+pub use crate::reactive_instance::ReactiveInstance;
+pub use crate::reactive_property_container::ReactivePropertyContainer;
+pub use crate::reactive_property_instance::ReactivePropertyInstance;
+pub use crate::reactive_relation_instance::ReactiveRelationInstance;
@@ -0,0 +1,215 @@
+use std::cmp::Reverse;
+use std::sync::{Arc, RwLock, Weak};
+
+use inexor_rgf_core_frp::Stream;
+use serde_json::Value;
+
+use crate::{generate_id, ReactivePropertyInstance};
+
+#[cfg(feature = "metrics")]
+use crate::instrument_observer;
+
+/// The priority of observers which are registered without a priority.
+pub const DEFAULT_OBSERVER_PRIORITY: i32 = 0;
+
+type PrioritizedObserver = Arc<dyn Fn(&Value) + Send + Sync>;
+
+struct PrioritizedObserverSubscription {
+    priority: i32,
+    sequence: u64,
+    handle_id: u128,
+    observer: PrioritizedObserver,
+}
+
+struct PrioritizedObserversState {
+    /// The handle id of the subscription which dispatches the values to the observers.
+    dispatcher_handle_id: u128,
+    /// True, if the dispatcher is subscribed to the stream.
+    subscribed: bool,
+    /// The observers ordered by priority and registration order.
+    subscriptions: Vec<PrioritizedObserverSubscription>,
+    /// The observers in the order they are invoked. The snapshot is replaced on every change, so
+    /// dispatching doesn't hold the lock while the observers are invoked.
+    snapshot: Arc<[PrioritizedObserver]>,
+    sequence: u64,
+}
+
+impl Default for PrioritizedObserversState {
+    fn default() -> Self {
+        PrioritizedObserversState {
+            dispatcher_handle_id: generate_id().as_u128(),
+            subscribed: false,
+            subscriptions: Vec::new(),
+            snapshot: Arc::new([]),
+            sequence: 0,
+        }
+    }
+}
+
+impl PrioritizedObserversState {
+    fn update_snapshot(&mut self) {
+        self.snapshot = self.subscriptions.iter().map(|subscription| subscription.observer.clone()).collect();
+    }
+}
+
+/// The observers of a reactive property which are invoked in a defined order.
+///
+/// Every observer which is registered through the property instance has a priority, the default
+/// priority is 0. Observers with a higher priority are invoked first. Observers with the same
+/// priority are invoked in the order they have been registered. The observers are dispatched by a
+/// single subscription of the stream, so observers which are subscribed to the stream directly
+/// are not ordered. Clones share the observers, like property instances share their stream.
+#[derive(Clone, Default)]
+pub struct PrioritizedObservers {
+    state: Arc<RwLock<PrioritizedObserversState>>,
+}
+
+impl PrioritizedObservers {
+    /// Registers the observer with the given priority and handle id and subscribes the dispatcher
+    /// to the stream, if it isn't subscribed yet. An observer with the same handle id is replaced.
+    ///
+    /// The dispatcher is subscribed only once, because subscribing locks the observers of the
+    /// stream, which are locked while a value is sent. So observers can register observers while
+    /// a value is dispatched.
+    pub(crate) fn observe(&self, stream: &Stream<'static, Value>, priority: i32, handle_id: u128, observer: PrioritizedObserver) {
+        let subscribe = {
+            let mut writer = self.state.write().unwrap();
+            writer.subscriptions.retain(|subscription| subscription.handle_id != handle_id);
+            writer.sequence += 1;
+            let subscription = PrioritizedObserverSubscription {
+                priority,
+                sequence: writer.sequence,
+                handle_id,
+                observer,
+            };
+            let key = (Reverse(priority), subscription.sequence);
+            let index = writer
+                .subscriptions
+                .partition_point(|subscription| (Reverse(subscription.priority), subscription.sequence) < key);
+            writer.subscriptions.insert(index, subscription);
+            writer.update_snapshot();
+            let subscribe = !writer.subscribed;
+            writer.subscribed = true;
+            subscribe.then_some(writer.dispatcher_handle_id)
+        };
+        if let Some(dispatcher_handle_id) = subscribe {
+            let state = Arc::downgrade(&self.state);
+            stream.observe_with_handle(move |value: &Value| dispatch(&state, value), dispatcher_handle_id);
+        }
+    }
+
+    /// Removes the observer with the given handle id.
+    pub(crate) fn remove(&self, handle_id: u128) {
+        let mut writer = self.state.write().unwrap();
+        let len = writer.subscriptions.len();
+        writer.subscriptions.retain(|subscription| subscription.handle_id != handle_id);
+        if writer.subscriptions.len() != len {
+            writer.update_snapshot();
+        }
+    }
+
+    /// Takes over the observers of the given observers, e.g. if the property is replaced. The
+    /// observers keep their priorities and are invoked after the observers of the same priority.
+    pub(crate) fn take_over(&self, stream: &Stream<'static, Value>, other: &PrioritizedObservers) {
+        if Arc::ptr_eq(&self.state, &other.state) {
+            return;
+        }
+        let subscriptions: Vec<PrioritizedObserverSubscription> = {
+            let mut writer = other.state.write().unwrap();
+            let subscriptions = writer.subscriptions.drain(..).collect();
+            writer.update_snapshot();
+            subscriptions
+        };
+        for subscription in subscriptions {
+            self.observe(stream, subscription.priority, subscription.handle_id, subscription.observer);
+        }
+    }
+
+    /// Returns a reference to the observers, which doesn't keep them alive.
+    pub(crate) fn downgrade(&self) -> WeakPrioritizedObservers {
+        WeakPrioritizedObservers(Arc::downgrade(&self.state))
+    }
+
+    /// Returns the number of observers.
+    pub fn len(&self) -> usize {
+        self.state.read().unwrap().subscriptions.len()
+    }
+
+    /// Returns true, if no observers are registered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A reference to the observers of a property, which doesn't keep the observers alive.
+#[derive(Clone)]
+pub(crate) struct WeakPrioritizedObservers(Weak<RwLock<PrioritizedObserversState>>);
+
+impl WeakPrioritizedObservers {
+    /// Removes the observer with the given handle id, if the observers are still alive.
+    pub(crate) fn remove(&self, handle_id: u128) {
+        if let Some(state) = self.0.upgrade() {
+            PrioritizedObservers { state }.remove(handle_id);
+        }
+    }
+
+    /// Returns true, if the observers are still alive.
+    pub(crate) fn is_alive(&self) -> bool {
+        self.0.strong_count() > 0
+    }
+
+    /// Returns true, if both refer to the observers of the same property.
+    pub(crate) fn ptr_eq(&self, other: &WeakPrioritizedObservers) -> bool {
+        self.0.ptr_eq(&other.0)
+    }
+}
+
+/// Invokes the observers in order. The lock is released before the observers are invoked, so
+/// that observers can register or remove observers.
+fn dispatch(state: &Weak<RwLock<PrioritizedObserversState>>, value: &Value) {
+    let observers = match state.upgrade() {
+        Some(state) => state.read().unwrap().snapshot.clone(),
+        None => return,
+    };
+    for observer in observers.iter() {
+        observer(value);
+    }
+}
+
+impl ReactivePropertyInstance {
+    /// Observes the stream with the default priority. An observer with the same handle id is
+    /// replaced.
+    pub fn observe_with_handle<F: Fn(&Value) + Send + Sync + 'static>(&self, observer: F, handle_id: u128) {
+        self.observe_prioritized(DEFAULT_OBSERVER_PRIORITY, handle_id, observer);
+    }
+
+    /// Observes the stream with the given priority. Observers with a higher priority are invoked
+    /// first, observers with the same priority in registration order. Returns the handle id.
+    pub fn observe_with_priority<F: Fn(&Value) + Send + Sync + 'static>(&self, priority: i32, observer: F) -> u128 {
+        let handle_id = generate_id().as_u128();
+        self.observe_prioritized(priority, handle_id, observer);
+        handle_id
+    }
+
+    /// Registers the observer. With the feature `metrics` the invocations of the observer are
+    /// recorded, so every observer which is registered through the property instance is counted.
+    fn observe_prioritized<F: Fn(&Value) + Send + Sync + 'static>(&self, priority: i32, handle_id: u128, observer: F) {
+        #[cfg(feature = "metrics")]
+        let observer = instrument_observer(self.id, self.name.clone(), observer);
+        self.prioritized_observers
+            .observe(&self.stream.read().unwrap(), priority, handle_id, Arc::new(observer));
+        let mut observer_handles = self.observer_handles.lock().unwrap();
+        if !observer_handles.contains(&handle_id) {
+            observer_handles.push(handle_id);
+        }
+    }
+
+    /// Removes the observer with the given handle id, regardless of its priority.
+    pub fn remove_observer(&self, handle_id: u128) {
+        self.prioritized_observers.remove(handle_id);
+        self.observer_handles
+            .lock()
+            .unwrap()
+            .retain(|observer_handle_id| *observer_handle_id != handle_id);
+    }
+}
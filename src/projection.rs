@@ -0,0 +1,280 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{PropertyInstanceGetter, ReactiveEntityInstance, ReactiveRelationInstance};
+
+/// The source of the values of a column of a projection.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "source", content = "name", rename_all = "snake_case")]
+pub enum ColumnSource {
+    /// The id of the entity instance.
+    Id,
+
+    /// The name of the entity type of the entity instance.
+    TypeName,
+
+    /// The property of the entity instance with the given name.
+    Property(String),
+
+    /// The property of the joined relation instance with the given name.
+    RelationProperty(String),
+
+    /// The name of the relation type of the joined relation instance.
+    RelationTypeName,
+
+    /// The id of the inbound entity instance of the joined relation instance.
+    JoinedId,
+
+    /// The property of the inbound entity instance of the joined relation instance with the given name.
+    JoinedProperty(String),
+}
+
+/// A column of a projection.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ProjectionColumn {
+    /// The name of the column.
+    pub name: String,
+
+    /// The source of the values of the column.
+    pub source: ColumnSource,
+}
+
+/// Projects entity instances or relation instances into flat records.
+///
+/// Each entity instance is projected into one record. If the projection joins along a relation
+/// type, each outbound relation instance of that type is projected into one record. Entity
+/// instances without such relation instances are projected into a single record with null values
+/// for the columns of the relation instance and the inbound entity instance.
+///
+/// Projected relation instances are joined with their outbound entity instance, so each relation
+/// instance is projected into one record.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Projection {
+    /// Only entity instances of the entity type with the given name are projected.
+    #[serde(default)]
+    pub type_name: Option<String>,
+
+    /// The name of the relation type to join along.
+    #[serde(default)]
+    pub join: Option<String>,
+
+    /// The columns of the projection.
+    #[serde(default = "Vec::new")]
+    pub columns: Vec<ProjectionColumn>,
+}
+
+impl Projection {
+    pub fn new() -> Projection {
+        Projection::default()
+    }
+
+    /// Projects only entity instances of the entity type with the given name.
+    pub fn for_type<S: Into<String>>(mut self, type_name: S) -> Projection {
+        self.type_name = Some(type_name.into());
+        self
+    }
+
+    /// Joins along the relation type with the given name.
+    pub fn join<S: Into<String>>(mut self, relation_type_name: S) -> Projection {
+        self.join = Some(relation_type_name.into());
+        self
+    }
+
+    /// Adds a column with the given name.
+    pub fn column<S: Into<String>>(mut self, name: S, source: ColumnSource) -> Projection {
+        self.columns.push(ProjectionColumn { name: name.into(), source });
+        self
+    }
+
+    /// Adds the column `id`.
+    pub fn id(self) -> Projection {
+        self.column("id", ColumnSource::Id)
+    }
+
+    /// Adds the column `type_name`.
+    pub fn type_name(self) -> Projection {
+        self.column("type_name", ColumnSource::TypeName)
+    }
+
+    /// Adds a column for the property of the entity instance.
+    pub fn property<S: Into<String>>(self, property_name: S) -> Projection {
+        let property_name = property_name.into();
+        self.column(property_name.clone(), ColumnSource::Property(property_name))
+    }
+
+    /// Adds the column `relation.<property_name>` for the property of the joined relation instance.
+    pub fn relation_property<S: Into<String>>(self, property_name: S) -> Projection {
+        let property_name = property_name.into();
+        self.column(format!("relation.{}", property_name), ColumnSource::RelationProperty(property_name))
+    }
+
+    /// Adds the column `relation.type_name` for the name of the relation type of the joined relation instance.
+    pub fn relation_type_name(self) -> Projection {
+        self.column("relation.type_name", ColumnSource::RelationTypeName)
+    }
+
+    /// Adds the column `joined.id` for the id of the inbound entity instance.
+    pub fn joined_id(self) -> Projection {
+        self.column("joined.id", ColumnSource::JoinedId)
+    }
+
+    /// Adds the column `joined.<property_name>` for the property of the inbound entity instance.
+    pub fn joined_property<S: Into<String>>(self, property_name: S) -> Projection {
+        let property_name = property_name.into();
+        self.column(format!("joined.{}", property_name), ColumnSource::JoinedProperty(property_name))
+    }
+
+    /// Projects the entity instances. The relation instances are only used for the join.
+    pub fn project(&self, entity_instances: &[Arc<ReactiveEntityInstance>], relation_instances: &[Arc<ReactiveRelationInstance>]) -> ProjectionTable {
+        let mut rows = Vec::new();
+        for entity_instance in entity_instances {
+            if self.type_name.as_ref().is_some_and(|type_name| *type_name != entity_instance.type_name) {
+                continue;
+            }
+            let joined: Vec<&Arc<ReactiveRelationInstance>> = match &self.join {
+                Some(relation_type_name) => {
+                    let mut joined: Vec<&Arc<ReactiveRelationInstance>> = relation_instances
+                        .iter()
+                        .filter(|relation_instance| relation_instance.outbound.id == entity_instance.id && relation_instance.type_name == *relation_type_name)
+                        .collect();
+                    joined.sort_by_key(|relation_instance| relation_instance.inbound.id);
+                    joined
+                }
+                None => Vec::new(),
+            };
+            if joined.is_empty() {
+                rows.push(self.row(entity_instance, None));
+            }
+            for relation_instance in joined {
+                rows.push(self.row(entity_instance, Some(relation_instance)));
+            }
+        }
+        ProjectionTable {
+            columns: self.columns.iter().map(|column| column.name.clone()).collect(),
+            rows,
+        }
+    }
+
+    /// Projects the relation instances. If the projection joins along a relation type, only relation
+    /// instances of that type are projected. If the projection is for an entity type, only relation
+    /// instances whose outbound entity instance is of that type are projected.
+    pub fn project_relations(&self, relation_instances: &[Arc<ReactiveRelationInstance>]) -> ProjectionTable {
+        let rows = relation_instances
+            .iter()
+            .filter(|relation_instance| {
+                self.join
+                    .as_ref()
+                    .is_none_or(|relation_type_name| relation_instance.type_name == *relation_type_name)
+            })
+            .filter(|relation_instance| {
+                self.type_name
+                    .as_ref()
+                    .is_none_or(|type_name| relation_instance.outbound.type_name == *type_name)
+            })
+            .map(|relation_instance| self.row(&relation_instance.outbound, Some(relation_instance)))
+            .collect();
+        ProjectionTable {
+            columns: self.columns.iter().map(|column| column.name.clone()).collect(),
+            rows,
+        }
+    }
+
+    fn row(&self, entity_instance: &ReactiveEntityInstance, relation_instance: Option<&Arc<ReactiveRelationInstance>>) -> Vec<Value> {
+        self.columns
+            .iter()
+            .map(|column| {
+                let value = match &column.source {
+                    ColumnSource::Id => Some(Value::String(entity_instance.id.to_string())),
+                    ColumnSource::TypeName => Some(Value::String(entity_instance.type_name.to_string())),
+                    ColumnSource::Property(property_name) => entity_instance.get(property_name),
+                    ColumnSource::RelationProperty(property_name) => relation_instance.and_then(|relation_instance| relation_instance.get(property_name)),
+                    ColumnSource::RelationTypeName => relation_instance.map(|relation_instance| Value::String(relation_instance.type_name.to_string())),
+                    ColumnSource::JoinedId => relation_instance.map(|relation_instance| Value::String(relation_instance.inbound.id.to_string())),
+                    ColumnSource::JoinedProperty(property_name) => relation_instance.and_then(|relation_instance| relation_instance.inbound.get(property_name)),
+                };
+                value.unwrap_or(Value::Null)
+            })
+            .collect()
+    }
+}
+
+/// The records of a projection. Missing values are null.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ProjectionTable {
+    /// The names of the columns.
+    pub columns: Vec<String>,
+
+    /// The values of the records in the order of the columns.
+    pub rows: Vec<Vec<Value>>,
+}
+
+impl ProjectionTable {
+    /// Returns the records as objects with the column names as keys.
+    pub fn to_records(&self) -> Vec<Map<String, Value>> {
+        self.rows
+            .iter()
+            .map(|row| self.columns.iter().cloned().zip(row.iter().cloned()).collect())
+            .collect()
+    }
+
+    /// Returns the values of the column with the given name.
+    pub fn get_column<S: AsRef<str>>(&self, name: S) -> Option<Vec<Value>> {
+        let index = self.columns.iter().position(|column| column == name.as_ref())?;
+        Some(self.rows.iter().map(|row| row.get(index).cloned().unwrap_or(Value::Null)).collect())
+    }
+
+    /// Returns the table as CSV. Strings are written as is, null values as empty fields and all
+    /// other values as JSON.
+    #[cfg(feature = "csv")]
+    pub fn to_csv(&self) -> Result<String, csv::Error> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record(&self.columns)?;
+        for row in self.rows.iter() {
+            writer.write_record(row.iter().map(|value| match value {
+                Value::Null => String::new(),
+                Value::String(s) => s.clone(),
+                value => value.to_string(),
+            }))?;
+        }
+        let bytes = writer.into_inner().map_err(|error| error.into_error())?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    /// Returns the table as Arrow record batch. Columns which contain only booleans, only integers
+    /// or only numbers are typed accordingly, all other columns contain the values as JSON strings.
+    /// Null values are nulls.
+    #[cfg(feature = "arrow")]
+    pub fn to_arrow(&self) -> Result<arrow_array::RecordBatch, arrow_schema::ArrowError> {
+        use arrow_array::{ArrayRef, BooleanArray, Float64Array, Int64Array, StringArray};
+        use arrow_schema::{DataType, Field, Schema};
+
+        let mut fields = Vec::new();
+        let mut arrays: Vec<ArrayRef> = Vec::new();
+        for (index, name) in self.columns.iter().enumerate() {
+            let values: Vec<&Value> = self.rows.iter().map(|row| row.get(index).unwrap_or(&Value::Null)).collect();
+            let non_null = || values.iter().filter(|value| !value.is_null());
+            let (data_type, array): (DataType, ArrayRef) = if non_null().count() > 0 && non_null().all(|value| value.is_boolean()) {
+                (DataType::Boolean, Arc::new(values.iter().map(|value| value.as_bool()).collect::<BooleanArray>()))
+            } else if non_null().count() > 0 && non_null().all(|value| value.is_i64()) {
+                (DataType::Int64, Arc::new(values.iter().map(|value| value.as_i64()).collect::<Int64Array>()))
+            } else if non_null().count() > 0 && non_null().all(|value| value.is_number()) {
+                (DataType::Float64, Arc::new(values.iter().map(|value| value.as_f64()).collect::<Float64Array>()))
+            } else {
+                let strings: StringArray = values
+                    .iter()
+                    .map(|value| match value {
+                        Value::Null => None,
+                        Value::String(s) => Some(s.clone()),
+                        value => Some(value.to_string()),
+                    })
+                    .collect();
+                (DataType::Utf8, Arc::new(strings))
+            };
+            fields.push(Field::new(name, data_type, true));
+            arrays.push(array);
+        }
+        arrow_array::RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+use std::thread;
+
+use uuid::Uuid;
+
+use crate::{clear_propagation_tracer, get_propagation_tracer, set_propagation_tracer, PropagationTrace, PropagationTracer};
+
+/// A recorded propagation callback.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PropagationEvent {
+    /// The id of the instance which contains the property.
+    pub id: Uuid,
+
+    /// The name of the property.
+    pub property_name: String,
+
+    /// True, if the value has been set without propagating it.
+    pub suppressed: bool,
+
+    /// The thread which has propagated the value.
+    pub thread: String,
+}
+
+/// A source of nondeterminism which has been detected by comparing two recordings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DeterminismViolation {
+    /// Both runs propagated the same events but in a different order (e.g. hash iteration order).
+    OrderMismatch {
+        index: usize,
+        expected: PropagationEvent,
+        actual: PropagationEvent,
+    },
+
+    /// The runs propagated different events.
+    DifferentEvents {
+        index: usize,
+        expected: Option<PropagationEvent>,
+        actual: Option<PropagationEvent>,
+    },
+
+    /// The values have been propagated by multiple threads (thread races).
+    MultipleThreads { threads: Vec<String> },
+}
+
+/// The exact order of the propagation callbacks of a run.
+#[derive(Clone, Debug, Default)]
+pub struct PropagationRecording {
+    pub events: Vec<PropagationEvent>,
+}
+
+impl PropagationRecording {
+    /// Keeps only the events of the given instances.
+    pub fn retain_instances(&mut self, ids: &[Uuid]) {
+        self.events.retain(|event| ids.contains(&event.id));
+    }
+
+    /// Returns the distinct instances which have propagated values.
+    pub fn get_instances(&self) -> HashSet<Uuid> {
+        self.events.iter().map(|event| event.id).collect()
+    }
+
+    /// Returns the names of the threads which have propagated values.
+    pub fn get_threads(&self) -> Vec<String> {
+        let mut threads: Vec<String> = Vec::new();
+        for event in self.events.iter() {
+            if !threads.contains(&event.thread) {
+                threads.push(event.thread.clone());
+            }
+        }
+        threads
+    }
+
+    /// Compares this recording (expected) with another recording (actual) and
+    /// flags the sources of nondeterminism.
+    pub fn compare(&self, other: &PropagationRecording) -> Vec<DeterminismViolation> {
+        let mut violations = Vec::new();
+        for recording in [self, other] {
+            let threads = recording.get_threads();
+            if threads.len() > 1 {
+                violations.push(DeterminismViolation::MultipleThreads { threads });
+            }
+        }
+        let index = self
+            .events
+            .iter()
+            .zip(other.events.iter())
+            .position(|(expected, actual)| !PropagationRecording::same_event(expected, actual))
+            .unwrap_or_else(|| self.events.len().min(other.events.len()));
+        if index == self.events.len() && index == other.events.len() {
+            return violations;
+        }
+        let expected = self.events.get(index).cloned();
+        let actual = other.events.get(index).cloned();
+        let violation = match (expected, actual) {
+            (Some(expected), Some(actual)) if self.has_same_events(other) => DeterminismViolation::OrderMismatch { index, expected, actual },
+            (expected, actual) => DeterminismViolation::DifferentEvents { index, expected, actual },
+        };
+        violations.push(violation);
+        violations
+    }
+
+    /// Returns true, if both recordings are equal regarding the order of the propagation callbacks.
+    pub fn is_deterministic_with(&self, other: &PropagationRecording) -> bool {
+        self.compare(other).is_empty()
+    }
+
+    fn has_same_events(&self, other: &PropagationRecording) -> bool {
+        let key = |event: &PropagationEvent| (event.id, event.property_name.clone(), event.suppressed);
+        let mut expected: Vec<_> = self.events.iter().map(key).collect();
+        let mut actual: Vec<_> = other.events.iter().map(key).collect();
+        expected.sort();
+        actual.sort();
+        expected == actual
+    }
+
+    fn same_event(expected: &PropagationEvent, actual: &PropagationEvent) -> bool {
+        expected.id == actual.id && expected.property_name == actual.property_name && expected.suppressed == actual.suppressed
+    }
+}
+
+/// Debug mode which records the exact order of the propagation callbacks.
+///
+/// While the audit is running it acts as the global propagation tracer. The
+/// previously registered tracer is restored when the audit is dropped, also if
+/// the audited run panics.
+#[must_use = "the recording is stopped when the audit is dropped"]
+pub struct PropagationAudit {
+    events: Arc<RwLock<Vec<PropagationEvent>>>,
+    previous_tracer: Option<PropagationTracer>,
+}
+
+impl PropagationAudit {
+    /// Starts recording the propagation callbacks.
+    pub fn start() -> PropagationAudit {
+        let events = Arc::new(RwLock::new(Vec::new()));
+        let previous_tracer = get_propagation_tracer();
+        {
+            let events = events.clone();
+            let previous_tracer = previous_tracer.clone();
+            set_propagation_tracer(Arc::new(move |trace: &PropagationTrace| {
+                events.write().unwrap().push(PropagationEvent {
+                    id: trace.id,
+                    property_name: trace.property_name.clone(),
+                    suppressed: trace.suppressed,
+                    thread: format!("{:?}", thread::current().id()),
+                });
+                if let Some(previous_tracer) = &previous_tracer {
+                    previous_tracer(trace);
+                }
+            }));
+        }
+        PropagationAudit { events, previous_tracer }
+    }
+
+    /// Stops recording and returns the recording.
+    pub fn stop(self) -> PropagationRecording {
+        let events = self.events.read().unwrap().clone();
+        PropagationRecording { events }
+    }
+
+    /// Records the propagation callbacks of the given run.
+    pub fn record<F: FnOnce()>(run: F) -> PropagationRecording {
+        let audit = PropagationAudit::start();
+        run();
+        audit.stop()
+    }
+}
+
+impl Drop for PropagationAudit {
+    fn drop(&mut self) {
+        match self.previous_tracer.take() {
+            Some(previous_tracer) => set_propagation_tracer(previous_tracer),
+            None => clear_propagation_tracer(),
+        }
+    }
+}
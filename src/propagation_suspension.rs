@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, RwLock, Weak};
+
+use inexor_rgf_core_frp::Stream;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::reactive_property_instance::propagate_resumed;
+
+/// Specifies what happens with the values which are set while the propagation is suspended.
+///
+/// If suspensions are nested, the mode of the innermost suspension applies to the values which
+/// are set while it exists.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SuspensionMode {
+    /// The latest value of each property is propagated once on resume.
+    #[default]
+    Consolidate,
+
+    /// The values are not propagated. A value which is pending from an outer suspension is
+    /// discarded, because it has been replaced.
+    Drop,
+}
+
+/// A value which is propagated on resume.
+struct PendingPropagation {
+    sequence: u64,
+    stream: Weak<RwLock<Stream<'static, Value>>>,
+    value: Value,
+}
+
+/// An active suspension. Suspensions which have been started later have a higher sequence.
+#[derive(Clone, Copy)]
+struct Suspension {
+    sequence: u64,
+    mode: SuspensionMode,
+}
+
+#[derive(Default)]
+struct Suspensions {
+    global: Vec<Suspension>,
+    instances: HashMap<Uuid, Vec<Suspension>>,
+    pending: HashMap<(Uuid, Arc<str>), PendingPropagation>,
+    sequence: u64,
+}
+
+impl Suspensions {
+    fn next_sequence(&mut self) -> u64 {
+        self.sequence += 1;
+        self.sequence
+    }
+
+    /// Returns the innermost suspension of the instance with the given id.
+    fn innermost(&self, id: Uuid) -> Option<Suspension> {
+        self.global
+            .iter()
+            .chain(self.instances.get(&id).into_iter().flatten())
+            .max_by_key(|suspension| suspension.sequence)
+            .copied()
+    }
+
+    fn is_suspended(&self, id: Uuid) -> bool {
+        !self.global.is_empty() || self.instances.contains_key(&id)
+    }
+
+    /// Removes and returns the pending propagations which are no longer suspended, in the
+    /// order in which the values have been set.
+    fn take_resumed(&mut self) -> Vec<((Uuid, Arc<str>), PendingPropagation)> {
+        let keys: Vec<(Uuid, Arc<str>)> = self.pending.keys().filter(|(id, _)| !self.is_suspended(*id)).cloned().collect();
+        let mut resumed: Vec<((Uuid, Arc<str>), PendingPropagation)> = keys
+            .into_iter()
+            .filter_map(|key| self.pending.remove(&key).map(|pending| (key, pending)))
+            .collect();
+        resumed.sort_by_key(|(_, pending)| pending.sequence);
+        resumed
+    }
+}
+
+/// The number of active suspensions. Allows to skip the lock, if nothing is suspended.
+static ACTIVE_SUSPENSIONS: AtomicUsize = AtomicUsize::new(0);
+
+static SUSPENSIONS: LazyLock<Mutex<Suspensions>> = LazyLock::new(|| Mutex::new(Suspensions::default()));
+
+/// Suspends the propagation as long as the guard exists. Suspensions can be nested; the
+/// propagation is resumed when the last guard of an instance is dropped.
+#[must_use = "the propagation is resumed when the guard is dropped"]
+pub struct PropagationGuard {
+    /// The ids of the suspended instances or none, if the propagation is suspended globally.
+    ids: Option<Vec<Uuid>>,
+
+    /// Identifies the suspension of this guard.
+    sequence: u64,
+}
+
+impl Drop for PropagationGuard {
+    fn drop(&mut self) {
+        let resumed = {
+            let mut suspensions = SUSPENSIONS.lock().unwrap();
+            let sequence = self.sequence;
+            match &self.ids {
+                Some(ids) => {
+                    for id in ids {
+                        if let Some(active) = suspensions.instances.get_mut(id) {
+                            active.retain(|suspension| suspension.sequence != sequence);
+                            if active.is_empty() {
+                                suspensions.instances.remove(id);
+                            }
+                        }
+                    }
+                }
+                None => suspensions.global.retain(|suspension| suspension.sequence != sequence),
+            }
+            ACTIVE_SUSPENSIONS.fetch_sub(1, Ordering::SeqCst);
+            suspensions.take_resumed()
+        };
+        // The values are propagated after the lock has been released, so observers can set values
+        for ((id, property_name), pending) in resumed {
+            if let Some(stream) = pending.stream.upgrade() {
+                propagate_resumed(id, &property_name, &stream, &pending.value);
+            }
+        }
+    }
+}
+
+/// Suspends the propagation of all property instances.
+pub fn suspend_propagation(mode: SuspensionMode) -> PropagationGuard {
+    let sequence = {
+        let mut suspensions = SUSPENSIONS.lock().unwrap();
+        let sequence = suspensions.next_sequence();
+        suspensions.global.push(Suspension { sequence, mode });
+        sequence
+    };
+    ACTIVE_SUSPENSIONS.fetch_add(1, Ordering::SeqCst);
+    PropagationGuard { ids: None, sequence }
+}
+
+/// Suspends the propagation of the property instances of the instances with the given ids.
+///
+/// The property instances of an entity instance or a relation instance have the id of the
+/// instance.
+pub fn suspend_instance_propagation(ids: Vec<Uuid>, mode: SuspensionMode) -> PropagationGuard {
+    let sequence = {
+        let mut suspensions = SUSPENSIONS.lock().unwrap();
+        let sequence = suspensions.next_sequence();
+        for id in ids.iter() {
+            suspensions.instances.entry(*id).or_default().push(Suspension { sequence, mode });
+        }
+        sequence
+    };
+    ACTIVE_SUSPENSIONS.fetch_add(1, Ordering::SeqCst);
+    PropagationGuard { ids: Some(ids), sequence }
+}
+
+/// Returns true, if the propagation of the instance with the given id is suspended.
+pub fn is_propagation_suspended(id: Uuid) -> bool {
+    ACTIVE_SUSPENSIONS.load(Ordering::SeqCst) > 0 && SUSPENSIONS.lock().unwrap().is_suspended(id)
+}
+
+/// Records the value for propagation on resume, if the propagation of the instance is suspended.
+/// Returns false, if the value has to be propagated immediately.
+///
+/// Must not be called while the value of the property is locked.
+pub(crate) fn defer_propagation(id: Uuid, property_name: &Arc<str>, stream: &Arc<RwLock<Stream<'static, Value>>>, value: &Value) -> bool {
+    if ACTIVE_SUSPENSIONS.load(Ordering::SeqCst) == 0 {
+        return false;
+    }
+    let mut suspensions = SUSPENSIONS.lock().unwrap();
+    let mode = match suspensions.innermost(id) {
+        Some(suspension) => suspension.mode,
+        None => return false,
+    };
+    let key = (id, property_name.clone());
+    match mode {
+        SuspensionMode::Consolidate => {
+            let sequence = suspensions.next_sequence();
+            suspensions.pending.insert(
+                key,
+                PendingPropagation {
+                    sequence,
+                    stream: Arc::downgrade(stream),
+                    value: value.clone(),
+                },
+            );
+        }
+        SuspensionMode::Drop => {
+            suspensions.pending.remove(&key);
+        }
+    }
+    true
+}
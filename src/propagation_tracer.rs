@@ -0,0 +1,65 @@
+use std::sync::{Arc, RwLock};
+
+use serde_json::Value;
+use uuid::Uuid;
+
+/// A trace of a property value which has been set or propagated.
+#[derive(Clone, Debug)]
+pub struct PropagationTrace {
+    /// The id of the instance which contains the property.
+    pub id: Uuid,
+
+    /// The name of the property.
+    pub property_name: String,
+
+    /// The value before the property has been set.
+    pub old_value: Value,
+
+    /// The value which has been set or propagated.
+    pub new_value: Value,
+
+    /// True, if the value has been set without propagating it to the observers. Values which
+    /// are set while the propagation is suspended are traced again, when they are propagated on resume.
+    pub suppressed: bool,
+}
+
+/// Callback which is called whenever a reactive property value is set or propagated.
+///
+/// The callback may be called while the value of the property is locked, so it must
+/// not access the property itself.
+pub type PropagationTracer = Arc<dyn Fn(&PropagationTrace) + Send + Sync>;
+
+static PROPAGATION_TRACER: RwLock<Option<PropagationTracer>> = RwLock::new(None);
+
+/// Registers the global propagation tracer. Replaces a previously registered tracer.
+pub fn set_propagation_tracer(tracer: PropagationTracer) {
+    *PROPAGATION_TRACER.write().unwrap() = Some(tracer);
+}
+
+/// Unregisters the global propagation tracer.
+pub fn clear_propagation_tracer() {
+    *PROPAGATION_TRACER.write().unwrap() = None;
+}
+
+/// Returns the registered global propagation tracer.
+pub fn get_propagation_tracer() -> Option<PropagationTracer> {
+    PROPAGATION_TRACER.read().unwrap().clone()
+}
+
+/// Returns true, if a propagation tracer is registered.
+pub fn has_propagation_tracer() -> bool {
+    PROPAGATION_TRACER.read().unwrap().is_some()
+}
+
+pub(crate) fn trace_propagation(id: Uuid, property_name: &str, old_value: Value, new_value: &Value, suppressed: bool) {
+    let tracer = PROPAGATION_TRACER.read().unwrap().clone();
+    if let Some(tracer) = tracer {
+        tracer(&PropagationTrace {
+            id,
+            property_name: property_name.to_string(),
+            old_value,
+            new_value: new_value.clone(),
+            suppressed,
+        });
+    }
+}
@@ -0,0 +1,102 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use serde_json::Value;
+
+use crate::{f64_to_value, generate_id, ObserverGuard, ReactivePropertyInstance};
+
+/// The aggregation of the numeric values of a property.
+enum Aggregation {
+    /// The sum of the last N values.
+    Sum(VecDeque<f64>, usize),
+
+    /// The average of the last N values.
+    Average(VecDeque<f64>, usize),
+
+    /// The minimum of all values.
+    Min(Option<f64>),
+
+    /// The maximum of all values.
+    Max(Option<f64>),
+}
+
+impl Aggregation {
+    /// Adds a value and returns the aggregated value.
+    fn update(&mut self, value: f64) -> f64 {
+        match self {
+            Aggregation::Sum(samples, window) | Aggregation::Average(samples, window) => {
+                samples.push_back(value);
+                while samples.len() > *window {
+                    samples.pop_front();
+                }
+            }
+            Aggregation::Min(min) => *min = Some(min.map_or(value, |min| min.min(value))),
+            Aggregation::Max(max) => *max = Some(max.map_or(value, |max| max.max(value))),
+        }
+        match self {
+            Aggregation::Sum(samples, _) => samples.iter().sum(),
+            Aggregation::Average(samples, _) => samples.iter().sum::<f64>() / samples.len() as f64,
+            Aggregation::Min(value) | Aggregation::Max(value) => value.unwrap_or_default(),
+        }
+    }
+}
+
+impl ReactivePropertyInstance {
+    /// Returns a new property with the given name which holds the sum of the last N numeric
+    /// values of this property.
+    pub fn sum_over<S: AsRef<str>>(&self, name: S, window: usize) -> (Arc<ReactivePropertyInstance>, ObserverGuard) {
+        self.aggregate(name, Aggregation::Sum(VecDeque::new(), window.max(1)))
+    }
+
+    /// Returns a new property with the given name which holds the average of the last N numeric
+    /// values of this property.
+    pub fn avg_over<S: AsRef<str>>(&self, name: S, window: usize) -> (Arc<ReactivePropertyInstance>, ObserverGuard) {
+        self.aggregate(name, Aggregation::Average(VecDeque::new(), window.max(1)))
+    }
+
+    /// Returns a new property with the given name which holds the minimum of the numeric values
+    /// of this property.
+    pub fn min<S: AsRef<str>>(&self, name: S) -> (Arc<ReactivePropertyInstance>, ObserverGuard) {
+        self.aggregate(name, Aggregation::Min(None))
+    }
+
+    /// Returns a new property with the given name which holds the maximum of the numeric values
+    /// of this property.
+    pub fn max<S: AsRef<str>>(&self, name: S) -> (Arc<ReactivePropertyInstance>, ObserverGuard) {
+        self.aggregate(name, Aggregation::Max(None))
+    }
+
+    /// Creates the derived property which is initialized with the current value and updated and
+    /// propagated on each numeric value of this property. Other values and aggregated values which
+    /// are rejected by the non-finite float policy are ignored. The derived property is updated
+    /// until the returned guard or the derived property is dropped.
+    fn aggregate<S: AsRef<str>>(&self, name: S, mut aggregation: Aggregation) -> (Arc<ReactivePropertyInstance>, ObserverGuard) {
+        let initial_value = self
+            .get()
+            .as_f64()
+            .and_then(|value| f64_to_value(aggregation.update(value)).ok())
+            .unwrap_or(Value::Null);
+        let derived = Arc::new(ReactivePropertyInstance::new(self.id, name, initial_value));
+        let weak_derived = Arc::downgrade(&derived);
+        let aggregation = Mutex::new(aggregation);
+        let handle_id = generate_id().as_u128();
+        self.observe_with_handle(
+            move |value: &Value| {
+                let value = match value.as_f64() {
+                    Some(value) => value,
+                    None => return,
+                };
+                if let Some(derived) = weak_derived.upgrade() {
+                    let aggregated_value = aggregation.lock().unwrap().update(value);
+                    if let Ok(aggregated_value) = f64_to_value(aggregated_value) {
+                        derived.set(aggregated_value);
+                    }
+                }
+            },
+            handle_id,
+        );
+        let observers = self.prioritized_observers.downgrade();
+        let guard = ObserverGuard::new(handle_id, move |handle_id| observers.remove(handle_id));
+        (derived, guard)
+    }
+}
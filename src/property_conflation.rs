@@ -0,0 +1,77 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Specifies how a reactive property handles values which are set while a
+/// previous value is still being propagated.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflationPolicy {
+    /// Every value is propagated. Producers wait until the previous value has been propagated.
+    #[default]
+    Queue,
+
+    /// Intermediate values are dropped. Only the latest value is propagated after
+    /// the current propagation has finished.
+    KeepLatest,
+}
+
+/// The conflation policy and the propagation state of a reactive property.
+#[derive(Debug, Default)]
+pub struct PropertyConflation {
+    /// The conflation policy.
+    pub policy: ConflationPolicy,
+
+    /// The propagation state. The state is shared with the pending propagations of the property,
+    /// which are propagated after the property has been released.
+    state: Arc<ConflationState>,
+}
+
+#[derive(Debug, Default)]
+struct ConflationState {
+    /// True, while a value is being propagated.
+    propagating: AtomicBool,
+
+    /// The latest value which has been set, but hasn't been propagated yet.
+    pending: Mutex<Option<Value>>,
+}
+
+impl PropertyConflation {
+    pub fn new(policy: ConflationPolicy) -> PropertyConflation {
+        PropertyConflation {
+            policy,
+            state: Arc::new(ConflationState::default()),
+        }
+    }
+
+    /// Returns a copy of the conflation which shares the propagation state.
+    pub(crate) fn share(&self) -> PropertyConflation {
+        PropertyConflation {
+            policy: self.policy,
+            state: self.state.clone(),
+        }
+    }
+
+    /// Marks that the value has to be propagated. A value which is still pending is replaced.
+    pub(crate) fn mark_pending(&self, value: Value) {
+        *self.state.pending.lock().unwrap() = Some(value);
+    }
+
+    /// Tries to become the propagator. Returns false, if another caller is already propagating.
+    pub(crate) fn begin(&self) -> bool {
+        !self.state.propagating.swap(true, Ordering::SeqCst)
+    }
+
+    /// Takes the pending value.
+    pub(crate) fn take_pending(&self) -> Option<Value> {
+        self.state.pending.lock().unwrap().take()
+    }
+
+    /// Stops propagating. Returns true, if a value has been set in the meantime.
+    pub(crate) fn end(&self) -> bool {
+        self.state.propagating.store(false, Ordering::SeqCst);
+        self.state.pending.lock().unwrap().is_some()
+    }
+}
@@ -0,0 +1,69 @@
+/// Declares the names and the types of a set of properties in one place.
+///
+/// Creates a unit struct with a constant for each property name, the constant `NAMES` with all
+/// property names in declaration order and the function `property_types()` which returns the
+/// property types. The properties are separated by semicolons. The socket type is optional and
+/// defaults to `SocketType::None`.
+///
+/// ```
+/// use inexor_rgf_core_model::{properties, DataType, SocketType};
+///
+/// properties!(
+///     /// The properties of an adder.
+///     AdderProperties {
+///         /// The left hand side.
+///         LHS: "lhs" => DataType::Number, SocketType::Input;
+///         /// The right hand side.
+///         RHS: "rhs" => DataType::Number, SocketType::Input;
+///         /// The sum.
+///         RESULT: "result" => DataType::Number, SocketType::Output;
+///         UNIT: "unit" => DataType::String;
+///     }
+/// );
+///
+/// assert_eq!("lhs", AdderProperties::LHS);
+/// assert_eq!(&["lhs", "rhs", "result", "unit"], AdderProperties::NAMES);
+/// assert_eq!(4, AdderProperties::property_types().len());
+/// ```
+#[macro_export]
+macro_rules! properties {
+    (
+        $(#[$meta:meta])*
+        $struct_name:ident {
+            $(
+                $(#[$property_meta:meta])*
+                $const_name:ident: $property_name:literal => $data_type:expr $(, $socket_type:expr)?
+            );* $(;)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub struct $struct_name;
+
+        impl $struct_name {
+            $(
+                $(#[$property_meta])*
+                pub const $const_name: &'static str = $property_name;
+            )*
+
+            /// The names of the properties in declaration order.
+            pub const NAMES: &'static [&'static str] = &[$($property_name),*];
+
+            /// Returns true, if a property with the given name is declared.
+            pub fn contains<S: AsRef<str>>(property_name: S) -> bool {
+                Self::NAMES.contains(&property_name.as_ref())
+            }
+
+            /// Returns the property types in declaration order.
+            pub fn property_types() -> Vec<$crate::PropertyType> {
+                vec![$($crate::properties!(@property_type $property_name, $data_type $(, $socket_type)?)),*]
+            }
+        }
+    };
+    (@property_type $property_name:literal, $data_type:expr) => {
+        $crate::PropertyType::new($property_name, $data_type)
+    };
+    (@property_type $property_name:literal, $data_type:expr, $socket_type:expr) => {
+        $crate::PropertyType::new_with_socket($property_name, $data_type, $socket_type)
+    };
+}
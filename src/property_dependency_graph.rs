@@ -0,0 +1,372 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+use serde_json::Value;
+
+use crate::EXPRESSION_EXTENSION;
+use crate::{generate_id, Expression, ExpressionError, PropertyInstanceGetter, PropertyInstanceSetter, PropertyType, ReactiveEntityInstance};
+
+/// The name of the property type extension which declares the properties a property is derived
+/// from, e.g. `{ "name": "derived_from", "extension": ["width", "height"] }`.
+pub const DERIVED_FROM_EXTENSION: &str = "derived_from";
+
+/// Computes the value of a derived property from the values of its dependencies. The values
+/// are in the order of the declared dependencies.
+pub type DerivationFunction = Arc<dyn Fn(&[Value]) -> Value + Send + Sync>;
+
+thread_local! {
+    /// The handle ids of the dependency graphs which are propagating on this thread.
+    static PROPAGATING: RefCell<HashSet<u128>> = RefCell::new(HashSet::new());
+}
+
+/// Marks the dependency graph with the handle id as propagating on this thread as long as the
+/// guard exists, even if a derivation function panics.
+struct PropagatingGuard {
+    handle_id: u128,
+}
+
+impl PropagatingGuard {
+    /// Returns none, if the dependency graph is already propagating on this thread.
+    fn enter(handle_id: u128) -> Option<PropagatingGuard> {
+        let entered = PROPAGATING.with(|propagating| propagating.borrow_mut().insert(handle_id));
+        entered.then_some(PropagatingGuard { handle_id })
+    }
+}
+
+impl Drop for PropagatingGuard {
+    fn drop(&mut self) {
+        PROPAGATING.with(|propagating| propagating.borrow_mut().remove(&self.handle_id));
+    }
+}
+
+/// A property whose value is derived from other properties of the same instance.
+#[derive(Clone)]
+pub struct DerivedProperty {
+    /// The name of the derived property.
+    pub name: String,
+
+    /// The names of the properties the property is derived from.
+    pub dependencies: Vec<String>,
+
+    /// Recomputes the value. Derived properties without function are marked stale instead.
+    pub function: Option<DerivationFunction>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PropertyDependencyError {
+    /// The property with the given name would depend on itself.
+    Cycle(String),
+
+    /// The expression of the property with the given name is invalid.
+    InvalidExpression(String, ExpressionError),
+}
+
+impl fmt::Display for PropertyDependencyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PropertyDependencyError::Cycle(name) => write!(f, "The property {} depends on itself", name),
+            PropertyDependencyError::InvalidExpression(name, error) => write!(f, "The expression of the property {} is invalid: {}", name, error),
+        }
+    }
+}
+
+/// Dependency graph between the properties of a single instance.
+///
+/// When a property changes, the properties which are derived from it are recomputed or,
+/// if they have no function, marked stale. Derived properties are updated in dependency
+/// order, so each derived property is updated at most once per change.
+pub struct PropertyDependencyGraph {
+    /// The handle id of the observers.
+    pub handle_id: u128,
+
+    /// The derived properties.
+    derived: Vec<DerivedProperty>,
+
+    /// The names of the stale properties.
+    stale: RwLock<HashSet<String>>,
+}
+
+impl PropertyDependencyGraph {
+    pub fn new() -> PropertyDependencyGraph {
+        PropertyDependencyGraph {
+            handle_id: generate_id().as_u128(),
+            derived: Vec::new(),
+            stale: RwLock::new(HashSet::new()),
+        }
+    }
+
+    /// Builds the dependency graph from the expression extensions and the derived from extensions
+    /// of the given property types. The derived from extension is either the name of a property or
+    /// a list of names.
+    pub fn from_property_types(property_types: &[PropertyType]) -> Result<PropertyDependencyGraph, PropertyDependencyError> {
+        let mut graph = PropertyDependencyGraph::new();
+        for property_type in property_types {
+            if let Ok(source) = property_type.get_extension::<String, _>(EXPRESSION_EXTENSION) {
+                let expression = Expression::parse(source).map_err(|error| PropertyDependencyError::InvalidExpression(property_type.name.clone(), error))?;
+                graph.add_expression(property_type.name.clone(), expression)?;
+                continue;
+            }
+            let dependencies = match property_type.get_extension::<Value, _>(DERIVED_FROM_EXTENSION) {
+                Ok(Value::String(name)) => vec![name],
+                Ok(value) => serde_json::from_value::<Vec<String>>(value).unwrap_or_default(),
+                Err(_) => continue,
+            };
+            graph.add_derived(property_type.name.clone(), dependencies)?;
+        }
+        Ok(graph)
+    }
+
+    /// Declares that the property is derived from the given properties. The property is marked
+    /// stale if one of the properties changes.
+    pub fn add_derived<S: Into<String>>(&mut self, name: S, dependencies: Vec<String>) -> Result<(), PropertyDependencyError> {
+        self.add(DerivedProperty {
+            name: name.into(),
+            dependencies,
+            function: None,
+        })
+    }
+
+    /// Declares that the property is derived from the given properties. The property is recomputed
+    /// using the function if one of the properties changes.
+    pub fn add_derived_with<S: Into<String>, F: Fn(&[Value]) -> Value + Send + Sync + 'static>(
+        &mut self,
+        name: S,
+        dependencies: Vec<String>,
+        function: F,
+    ) -> Result<(), PropertyDependencyError> {
+        self.add(DerivedProperty {
+            name: name.into(),
+            dependencies,
+            function: Some(Arc::new(function)),
+        })
+    }
+
+    /// Declares that the property is computed from the expression. The property is recomputed if
+    /// one of the properties the expression reads changes.
+    pub fn add_expression<S: Into<String>>(&mut self, name: S, expression: Expression) -> Result<(), PropertyDependencyError> {
+        let dependencies = expression.get_properties();
+        let names = dependencies.clone();
+        self.add_derived_with(name, dependencies, move |values| {
+            expression.evaluate(|name| {
+                names
+                    .iter()
+                    .position(|dependency| dependency == name)
+                    .and_then(|index| values.get(index).cloned())
+            })
+        })
+    }
+
+    fn add(&mut self, derived_property: DerivedProperty) -> Result<(), PropertyDependencyError> {
+        if derived_property
+            .dependencies
+            .iter()
+            .any(|dependency| *dependency == derived_property.name || self.depends_on(dependency, &derived_property.name))
+        {
+            return Err(PropertyDependencyError::Cycle(derived_property.name));
+        }
+        self.derived.retain(|derived| derived.name != derived_property.name);
+        self.derived.push(derived_property);
+        Ok(())
+    }
+
+    /// Returns true, if the property is derived directly or indirectly from the other property.
+    pub fn depends_on(&self, name: &str, other: &str) -> bool {
+        self.get_dependencies(name)
+            .iter()
+            .any(|dependency| dependency == other || self.depends_on(dependency, other))
+    }
+
+    /// Returns the names of the properties the property is derived from.
+    pub fn get_dependencies<S: AsRef<str>>(&self, name: S) -> Vec<String> {
+        self.get_derived_property(name).map(|derived| derived.dependencies.clone()).unwrap_or_default()
+    }
+
+    /// Returns the names of the properties which are derived directly or indirectly from the
+    /// property. The properties are in the order in which they have to be updated.
+    pub fn get_dependents<S: AsRef<str>>(&self, name: S) -> Vec<String> {
+        let mut visited = Vec::new();
+        self.visit_dependents(name.as_ref(), &mut visited);
+        visited.reverse();
+        visited
+    }
+
+    /// Depth first post order of the dependents.
+    fn visit_dependents(&self, name: &str, visited: &mut Vec<String>) {
+        for derived in self
+            .derived
+            .iter()
+            .filter(|derived| derived.dependencies.iter().any(|dependency| dependency == name))
+        {
+            if !visited.contains(&derived.name) {
+                self.visit_dependents(&derived.name, visited);
+                visited.push(derived.name.clone());
+            }
+        }
+    }
+
+    /// Returns the derived property with the given name.
+    pub fn get_derived_property<S: AsRef<str>>(&self, name: S) -> Option<&DerivedProperty> {
+        self.derived.iter().find(|derived| derived.name == name.as_ref())
+    }
+
+    /// Returns the derived properties.
+    pub fn get_derived_properties(&self) -> &[DerivedProperty] {
+        &self.derived
+    }
+
+    /// Returns the names of the derived properties.
+    pub fn get_derived_property_names(&self) -> Vec<String> {
+        self.derived.iter().map(|derived| derived.name.clone()).collect()
+    }
+
+    /// Returns the edges of the graph as pairs of the property and the derived property.
+    pub fn get_edges(&self) -> Vec<(String, String)> {
+        self.derived
+            .iter()
+            .flat_map(|derived| derived.dependencies.iter().map(move |dependency| (dependency.clone(), derived.name.clone())))
+            .collect()
+    }
+
+    /// Returns true, if the property is stale.
+    pub fn is_stale<S: AsRef<str>>(&self, name: S) -> bool {
+        self.stale.read().unwrap().contains(name.as_ref())
+    }
+
+    /// Returns the names of the stale properties.
+    pub fn get_stale(&self) -> Vec<String> {
+        let mut stale: Vec<String> = self.stale.read().unwrap().iter().cloned().collect();
+        stale.sort();
+        stale
+    }
+
+    /// Marks the property as up to date.
+    pub fn clear_stale<S: AsRef<str>>(&self, name: S) {
+        self.stale.write().unwrap().remove(name.as_ref());
+    }
+
+    /// Updates the properties which are derived from the property after the property has
+    /// changed to the given value.
+    pub fn propagate<S: AsRef<str>>(&self, entity_instance: &ReactiveEntityInstance, name: S, value: &Value) {
+        let _guard = match PropagatingGuard::enter(self.handle_id) {
+            Some(guard) => guard,
+            // The derived properties of this change are already updated by the outer propagation
+            None => return,
+        };
+        let mut values: HashMap<String, Value> = HashMap::new();
+        values.insert(name.as_ref().to_string(), value.clone());
+        let mut stale: HashSet<String> = HashSet::new();
+        let mut updated: Vec<(String, Value)> = Vec::new();
+        for derived_name in self.get_dependents(name.as_ref()) {
+            let derived = match self.get_derived_property(&derived_name) {
+                Some(derived) => derived,
+                None => continue,
+            };
+            let function = match &derived.function {
+                Some(function) if !derived.dependencies.iter().any(|dependency| stale.contains(dependency)) => function,
+                _ => {
+                    stale.insert(derived_name);
+                    continue;
+                }
+            };
+            let arguments: Vec<Value> = derived
+                .dependencies
+                .iter()
+                .map(|dependency| {
+                    values
+                        .get(dependency)
+                        .cloned()
+                        .or_else(|| entity_instance.get(dependency))
+                        .unwrap_or(Value::Null)
+                })
+                .collect();
+            let value = function(&arguments);
+            values.insert(derived_name.clone(), value.clone());
+            updated.push((derived_name, value));
+        }
+        {
+            let mut writer = self.stale.write().unwrap();
+            for (name, _) in updated.iter() {
+                writer.remove(name);
+            }
+            writer.extend(stale);
+        }
+        // The values are written before any value is propagated
+        for (name, value) in updated.iter() {
+            entity_instance.set_no_propagate(name.as_str(), value.clone());
+        }
+        for (name, value) in updated.iter() {
+            if let Some(property_instance) = entity_instance.properties.get(name.as_str()) {
+                property_instance.send(value);
+            }
+        }
+    }
+
+    /// Recomputes all derived properties which have a function in dependency order, e.g. after
+    /// the graph has been built. The values are set without propagation.
+    pub fn recompute(&self, entity_instance: &ReactiveEntityInstance) {
+        let mut order = Vec::new();
+        for derived in self.derived.iter() {
+            self.visit_dependencies(&derived.name, &mut order);
+        }
+        for name in order {
+            if let Some(DerivedProperty {
+                dependencies,
+                function: Some(function),
+                ..
+            }) = self.get_derived_property(&name)
+            {
+                let arguments: Vec<Value> = dependencies
+                    .iter()
+                    .map(|dependency| entity_instance.get(dependency).unwrap_or(Value::Null))
+                    .collect();
+                entity_instance.set_no_propagate(&name, function(&arguments));
+                self.clear_stale(&name);
+            }
+        }
+    }
+
+    /// Depth first post order of the dependencies.
+    fn visit_dependencies(&self, name: &str, visited: &mut Vec<String>) {
+        if visited.iter().any(|visited| visited == name) {
+            return;
+        }
+        for dependency in self.get_dependencies(name) {
+            self.visit_dependencies(&dependency, visited);
+        }
+        visited.push(name.to_string());
+    }
+
+    /// Observes the properties of the entity instance the derived properties depend on.
+    pub fn connect(self: &Arc<Self>, entity_instance: &Arc<ReactiveEntityInstance>) {
+        let names: HashSet<String> = self.derived.iter().flat_map(|derived| derived.dependencies.iter().cloned()).collect();
+        for name in names {
+            if let Some(property_instance) = entity_instance.properties.get(name.as_str()) {
+                let graph = Arc::downgrade(self);
+                let instance = Arc::downgrade(entity_instance);
+                property_instance.observe_with_handle(
+                    move |value: &Value| {
+                        if let (Some(graph), Some(instance)) = (graph.upgrade(), instance.upgrade()) {
+                            graph.propagate(&instance, name.as_str(), value);
+                        }
+                    },
+                    self.handle_id,
+                );
+            }
+        }
+    }
+
+    /// Stops observing the properties of the entity instance.
+    pub fn disconnect(&self, entity_instance: &ReactiveEntityInstance) {
+        for property_instance in entity_instance.properties.iter() {
+            property_instance.remove_observer(self.handle_id);
+        }
+    }
+}
+
+impl Default for PropertyDependencyGraph {
+    fn default() -> Self {
+        PropertyDependencyGraph::new()
+    }
+}
@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{remove_property_value, PropertyMap};
+
+/// The differences between the properties of two instances.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct PropertyDiff {
+    /// The properties which have been added with their values.
+    #[serde(default = "PropertyMap::new")]
+    pub added: PropertyMap,
+
+    /// The names of the properties which have been removed.
+    #[serde(default = "Vec::new")]
+    pub removed: Vec<String>,
+
+    /// The properties which have been changed with their new values.
+    #[serde(default = "PropertyMap::new")]
+    pub changed: PropertyMap,
+}
+
+impl PropertyDiff {
+    /// Returns the changes which turn the first properties into the second properties. The names
+    /// of the removed properties are sorted.
+    pub fn new(from: &PropertyMap, to: &PropertyMap) -> PropertyDiff {
+        let mut diff = PropertyDiff::default();
+        for (name, value) in to.iter() {
+            match from.get(name) {
+                None => {
+                    diff.added.insert(name.clone(), value.clone());
+                }
+                Some(old_value) if old_value != value => {
+                    diff.changed.insert(name.clone(), value.clone());
+                }
+                _ => {}
+            }
+        }
+        diff.removed = from.keys().filter(|name| !to.contains_key(*name)).cloned().collect();
+        diff.removed.sort();
+        diff
+    }
+
+    /// Returns true, if the properties are equal.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Applies the changes to the properties.
+    pub fn apply_to(self, properties: &mut PropertyMap) {
+        for name in self.removed.iter() {
+            remove_property_value(properties, name);
+        }
+        properties.extend(self.added);
+        properties.extend(self.changed);
+    }
+}
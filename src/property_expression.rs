@@ -0,0 +1,496 @@
+use std::fmt;
+use std::iter::Peekable;
+use std::str::CharIndices;
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::{f64_to_value, value_to_f64, PropertyDependencyError, PropertyDependencyGraph, PropertyType, ReactiveEntityInstance, ReactivePropertyContainer};
+
+/// The name of the property type extension which declares the expression a property is computed
+/// from, e.g. `{ "name": "expression", "extension": "(width + height) * 2" }`.
+pub const EXPRESSION_EXTENSION: &str = "expression";
+
+/// The maximum depth of a parsed expression. Parentheses, negations, function calls and
+/// operators each add a level.
+pub const MAX_EXPRESSION_DEPTH: usize = 256;
+
+/// An arithmetic operator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operator::Add => write!(f, "+"),
+            Operator::Subtract => write!(f, "-"),
+            Operator::Multiply => write!(f, "*"),
+            Operator::Divide => write!(f, "/"),
+            Operator::Remainder => write!(f, "%"),
+        }
+    }
+}
+
+/// An arithmetic expression over the properties of an instance.
+///
+/// Expressions consist of numbers, property names, the operators `+`, `-`, `*`, `/` and `%`,
+/// parentheses and the functions `abs`, `ceil`, `floor`, `round`, `sqrt`, `min` and `max`.
+///
+/// Integers stay integers unless they are divided or overflow. If a property is missing or
+/// isn't a number, the result is null.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expression {
+    /// A number literal.
+    Number(f64),
+
+    /// An integer literal.
+    Integer(i64),
+
+    /// The value of the property with the given name.
+    Property(String),
+
+    /// The negation of the expression.
+    Negate(Box<Expression>),
+
+    /// The operator applied on both expressions.
+    Binary(Operator, Box<Expression>, Box<Expression>),
+
+    /// The function with the given name applied on the arguments.
+    Call(String, Vec<Expression>),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ExpressionError {
+    /// The character at the given position is not allowed.
+    UnexpectedCharacter(char, usize),
+
+    /// The token at the given position is not expected.
+    UnexpectedToken(String, usize),
+
+    /// The expression ends unexpectedly.
+    UnexpectedEnd,
+
+    /// The function with the given name doesn't exist.
+    UnknownFunction(String),
+
+    /// The function with the given name is called with the wrong number of arguments.
+    InvalidArgumentCount(String, usize),
+
+    /// The rounded result of the function with the given name doesn't fit into an integer.
+    IntegerOverflow(String),
+
+    /// The expression is nested deeper than the maximum expression depth at the given position.
+    TooDeeplyNested(usize),
+}
+
+impl fmt::Display for ExpressionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExpressionError::UnexpectedCharacter(c, position) => write!(f, "Unexpected character {} at position {}", c, position),
+            ExpressionError::UnexpectedToken(token, position) => write!(f, "Unexpected token {} at position {}", token, position),
+            ExpressionError::UnexpectedEnd => write!(f, "Unexpected end of expression"),
+            ExpressionError::UnknownFunction(name) => write!(f, "The function {} doesn't exist", name),
+            ExpressionError::InvalidArgumentCount(name, count) => write!(f, "The function {} can't be called with {} arguments", name, count),
+            ExpressionError::IntegerOverflow(name) => write!(f, "The result of the function {} doesn't fit into an integer", name),
+            ExpressionError::TooDeeplyNested(position) => write!(f, "The expression is nested too deeply at position {}", position),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Integer(i64),
+    Identifier(String),
+    Operator(Operator),
+    OpenParenthesis,
+    CloseParenthesis,
+    Comma,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Number(number) => write!(f, "{}", number),
+            Token::Integer(integer) => write!(f, "{}", integer),
+            Token::Identifier(identifier) => write!(f, "{}", identifier),
+            Token::Operator(operator) => write!(f, "{}", operator),
+            Token::OpenParenthesis => write!(f, "("),
+            Token::CloseParenthesis => write!(f, ")"),
+            Token::Comma => write!(f, ","),
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, ExpressionError> {
+    let mut tokens = Vec::new();
+    let mut chars: Peekable<CharIndices> = source.char_indices().peekable();
+    while let Some((position, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '+' => Token::Operator(Operator::Add),
+            '-' => Token::Operator(Operator::Subtract),
+            '*' => Token::Operator(Operator::Multiply),
+            '/' => Token::Operator(Operator::Divide),
+            '%' => Token::Operator(Operator::Remainder),
+            '(' => Token::OpenParenthesis,
+            ')' => Token::CloseParenthesis,
+            ',' => Token::Comma,
+            c if c.is_ascii_digit() || c == '.' => {
+                let mut end = position + c.len_utf8();
+                while let Some((next_position, next)) = chars.peek() {
+                    if !next.is_ascii_digit() && *next != '.' {
+                        break;
+                    }
+                    end = next_position + next.len_utf8();
+                    chars.next();
+                }
+                let literal = &source[position..end];
+                match literal.parse::<i64>() {
+                    Ok(integer) => Token::Integer(integer),
+                    Err(_) => Token::Number(
+                        literal
+                            .parse::<f64>()
+                            .map_err(|_| ExpressionError::UnexpectedToken(literal.to_string(), position))?,
+                    ),
+                }
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut end = position + c.len_utf8();
+                while let Some((next_position, next)) = chars.peek() {
+                    if !next.is_alphanumeric() && *next != '_' {
+                        break;
+                    }
+                    end = next_position + next.len_utf8();
+                    chars.next();
+                }
+                Token::Identifier(source[position..end].to_string())
+            }
+            c => return Err(ExpressionError::UnexpectedCharacter(c, position)),
+        };
+        tokens.push((token, position));
+    }
+    Ok(tokens)
+}
+
+/// Recursive descent parser over the tokens.
+///
+/// The parse functions return the expression and its depth.
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    index: usize,
+
+    /// The number of nested parentheses, negations and function calls at the current token.
+    nesting: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index).map(|(token, _)| token)
+    }
+
+    fn next(&mut self) -> Result<(Token, usize), ExpressionError> {
+        let token = self.tokens.get(self.index).cloned().ok_or(ExpressionError::UnexpectedEnd)?;
+        self.index += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), ExpressionError> {
+        let (token, position) = self.next()?;
+        if token != expected {
+            return Err(ExpressionError::UnexpectedToken(token.to_string(), position));
+        }
+        Ok(())
+    }
+
+    /// Enters a nested expression. Fails before recursing, so deeply nested sources can't
+    /// overflow the stack.
+    fn enter(&mut self, position: usize) -> Result<(), ExpressionError> {
+        self.nesting += 1;
+        if self.nesting > MAX_EXPRESSION_DEPTH {
+            return Err(ExpressionError::TooDeeplyNested(position));
+        }
+        Ok(())
+    }
+
+    fn leave(&mut self) {
+        self.nesting -= 1;
+    }
+
+    /// expression = term { ("+" | "-") term }
+    fn expression(&mut self) -> Result<(Expression, usize), ExpressionError> {
+        let (mut left, mut depth) = self.term()?;
+        while let Some((Token::Operator(operator @ (Operator::Add | Operator::Subtract)), position)) = self.tokens.get(self.index).cloned() {
+            self.index += 1;
+            let (right, right_depth) = self.term()?;
+            depth = check_depth(depth.max(right_depth) + 1, position)?;
+            left = Expression::Binary(operator, Box::new(left), Box::new(right));
+        }
+        Ok((left, depth))
+    }
+
+    /// term = factor { ("*" | "/" | "%") factor }
+    fn term(&mut self) -> Result<(Expression, usize), ExpressionError> {
+        let (mut left, mut depth) = self.factor()?;
+        while let Some((Token::Operator(operator @ (Operator::Multiply | Operator::Divide | Operator::Remainder)), position)) =
+            self.tokens.get(self.index).cloned()
+        {
+            self.index += 1;
+            let (right, right_depth) = self.factor()?;
+            depth = check_depth(depth.max(right_depth) + 1, position)?;
+            left = Expression::Binary(operator, Box::new(left), Box::new(right));
+        }
+        Ok((left, depth))
+    }
+
+    /// factor = "-" factor | number | name | name "(" arguments ")" | "(" expression ")"
+    fn factor(&mut self) -> Result<(Expression, usize), ExpressionError> {
+        let (token, position) = self.next()?;
+        match token {
+            Token::Operator(Operator::Subtract) => {
+                self.enter(position)?;
+                let (expression, depth) = self.factor()?;
+                self.leave();
+                Ok((Expression::Negate(Box::new(expression)), check_depth(depth + 1, position)?))
+            }
+            Token::Number(number) => Ok((Expression::Number(number), 1)),
+            Token::Integer(integer) => Ok((Expression::Integer(integer), 1)),
+            Token::Identifier(name) if self.peek() == Some(&Token::OpenParenthesis) => {
+                self.index += 1;
+                self.enter(position)?;
+                let (argument, mut depth) = self.expression()?;
+                let mut arguments = vec![argument];
+                while self.peek() == Some(&Token::Comma) {
+                    self.index += 1;
+                    let (argument, argument_depth) = self.expression()?;
+                    arguments.push(argument);
+                    depth = depth.max(argument_depth);
+                }
+                self.expect(Token::CloseParenthesis)?;
+                self.leave();
+                check_function(&name, arguments.len())?;
+                Ok((Expression::Call(name, arguments), check_depth(depth + 1, position)?))
+            }
+            Token::Identifier(name) => Ok((Expression::Property(name), 1)),
+            Token::OpenParenthesis => {
+                self.enter(position)?;
+                let expression = self.expression()?;
+                self.expect(Token::CloseParenthesis)?;
+                self.leave();
+                Ok(expression)
+            }
+            token => Err(ExpressionError::UnexpectedToken(token.to_string(), position)),
+        }
+    }
+}
+
+/// Returns the depth, if it doesn't exceed the maximum expression depth.
+fn check_depth(depth: usize, position: usize) -> Result<usize, ExpressionError> {
+    if depth > MAX_EXPRESSION_DEPTH {
+        return Err(ExpressionError::TooDeeplyNested(position));
+    }
+    Ok(depth)
+}
+
+fn check_function(name: &str, argument_count: usize) -> Result<(), ExpressionError> {
+    let valid = match name {
+        "abs" | "ceil" | "floor" | "round" | "sqrt" => argument_count == 1,
+        "min" | "max" => argument_count >= 1,
+        _ => return Err(ExpressionError::UnknownFunction(name.to_string())),
+    };
+    if !valid {
+        return Err(ExpressionError::InvalidArgumentCount(name.to_string(), argument_count));
+    }
+    Ok(())
+}
+
+/// Intermediate result of the evaluation.
+#[derive(Clone, Copy)]
+enum Number {
+    Integer(i64),
+    Float(f64),
+}
+
+impl Number {
+    fn from_value(value: &Value) -> Option<Number> {
+        match value.as_i64() {
+            Some(integer) => Some(Number::Integer(integer)),
+            None => value_to_f64(value).map(Number::Float),
+        }
+    }
+
+    fn as_f64(self) -> f64 {
+        match self {
+            Number::Integer(integer) => integer as f64,
+            Number::Float(float) => float,
+        }
+    }
+
+    fn into_value(self) -> Value {
+        match self {
+            Number::Integer(integer) => Value::from(integer),
+            Number::Float(float) => f64_to_value(float).unwrap_or(Value::Null),
+        }
+    }
+}
+
+impl Expression {
+    /// Parses the expression. Expressions which are nested deeper than
+    /// [`MAX_EXPRESSION_DEPTH`] are rejected.
+    pub fn parse<S: AsRef<str>>(source: S) -> Result<Expression, ExpressionError> {
+        let mut parser = Parser {
+            tokens: tokenize(source.as_ref())?,
+            index: 0,
+            nesting: 0,
+        };
+        let (expression, _) = parser.expression()?;
+        if let Some((token, position)) = parser.tokens.get(parser.index) {
+            return Err(ExpressionError::UnexpectedToken(token.to_string(), *position));
+        }
+        Ok(expression)
+    }
+
+    /// Returns the names of the properties the expression reads in the order of their first occurrence.
+    pub fn get_properties(&self) -> Vec<String> {
+        let mut properties = Vec::new();
+        self.collect_properties(&mut properties);
+        properties
+    }
+
+    fn collect_properties(&self, properties: &mut Vec<String>) {
+        match self {
+            Expression::Number(_) | Expression::Integer(_) => {}
+            Expression::Property(name) => {
+                if !properties.contains(name) {
+                    properties.push(name.clone());
+                }
+            }
+            Expression::Negate(expression) => expression.collect_properties(properties),
+            Expression::Binary(_, left, right) => {
+                left.collect_properties(properties);
+                right.collect_properties(properties);
+            }
+            Expression::Call(_, arguments) => {
+                for argument in arguments {
+                    argument.collect_properties(properties);
+                }
+            }
+        }
+    }
+
+    /// Evaluates the expression. The values of the properties are resolved by the given function.
+    /// The result is null, if the expression can't be evaluated.
+    pub fn evaluate<F: Fn(&str) -> Option<Value>>(&self, resolve: F) -> Value {
+        self.try_evaluate(resolve).unwrap_or(Value::Null)
+    }
+
+    /// Evaluates the expression. The values of the properties are resolved by the given function.
+    /// The result is null, if a property is missing or isn't a number. Returns an error, if a
+    /// rounded result doesn't fit into an integer.
+    pub fn try_evaluate<F: Fn(&str) -> Option<Value>>(&self, resolve: F) -> Result<Value, ExpressionError> {
+        Ok(self.evaluate_number(&resolve)?.map(Number::into_value).unwrap_or(Value::Null))
+    }
+
+    fn evaluate_number<F: Fn(&str) -> Option<Value>>(&self, resolve: &F) -> Result<Option<Number>, ExpressionError> {
+        Ok(match self {
+            Expression::Number(number) => Some(Number::Float(*number)),
+            Expression::Integer(integer) => Some(Number::Integer(*integer)),
+            Expression::Property(name) => resolve(name).as_ref().and_then(Number::from_value),
+            Expression::Negate(expression) => expression.evaluate_number(resolve)?.map(|number| match number {
+                Number::Integer(integer) => integer.checked_neg().map(Number::Integer).unwrap_or(Number::Float(-(integer as f64))),
+                Number::Float(float) => Number::Float(-float),
+            }),
+            Expression::Binary(operator, left, right) => match (left.evaluate_number(resolve)?, right.evaluate_number(resolve)?) {
+                (Some(left), Some(right)) => Some(apply(*operator, left, right)),
+                _ => None,
+            },
+            Expression::Call(name, arguments) => {
+                let mut numbers = Vec::with_capacity(arguments.len());
+                for argument in arguments {
+                    match argument.evaluate_number(resolve)? {
+                        Some(number) => numbers.push(number),
+                        None => return Ok(None),
+                    }
+                }
+                call(name, &numbers)?
+            }
+        })
+    }
+}
+
+fn apply(operator: Operator, left: Number, right: Number) -> Number {
+    if let (Number::Integer(left), Number::Integer(right)) = (left, right) {
+        let result = match operator {
+            Operator::Add => left.checked_add(right),
+            Operator::Subtract => left.checked_sub(right),
+            Operator::Multiply => left.checked_mul(right),
+            Operator::Remainder => left.checked_rem(right),
+            Operator::Divide => None,
+        };
+        if let Some(result) = result {
+            return Number::Integer(result);
+        }
+    }
+    let (left, right) = (left.as_f64(), right.as_f64());
+    Number::Float(match operator {
+        Operator::Add => left + right,
+        Operator::Subtract => left - right,
+        Operator::Multiply => left * right,
+        Operator::Divide => left / right,
+        Operator::Remainder => left % right,
+    })
+}
+
+fn call(name: &str, arguments: &[Number]) -> Result<Option<Number>, ExpressionError> {
+    let rounded = |float: f64| {
+        if !float.is_finite() {
+            Ok(Number::Float(float))
+        } else if float >= i64::MIN as f64 && float < i64::MAX as f64 {
+            Ok(Number::Integer(float as i64))
+        } else {
+            Err(ExpressionError::IntegerOverflow(name.to_string()))
+        }
+    };
+    let first = match arguments.first() {
+        Some(first) => *first,
+        None => return Ok(None),
+    };
+    Ok(match (name, first) {
+        ("abs", Number::Integer(integer)) => Some(integer.checked_abs().map(Number::Integer).unwrap_or(Number::Float((integer as f64).abs()))),
+        ("abs", Number::Float(float)) => Some(Number::Float(float.abs())),
+        ("ceil" | "floor" | "round", Number::Integer(integer)) => Some(Number::Integer(integer)),
+        ("ceil", Number::Float(float)) => Some(rounded(float.ceil())?),
+        ("floor", Number::Float(float)) => Some(rounded(float.floor())?),
+        ("round", Number::Float(float)) => Some(rounded(float.round())?),
+        ("sqrt", number) => Some(Number::Float(number.as_f64().sqrt())),
+        ("min", _) => arguments.iter().copied().reduce(|a, b| if b.as_f64() < a.as_f64() { b } else { a }),
+        ("max", _) => arguments.iter().copied().reduce(|a, b| if b.as_f64() > a.as_f64() { b } else { a }),
+        _ => None,
+    })
+}
+
+/// Creates the properties of the entity instance which are computed from expressions and
+/// recomputes them when the properties they read change.
+///
+/// The computed properties are declared by the expression extension of the property types.
+/// Missing computed properties are added to the entity instance. The returned dependency graph
+/// must be kept alive as long as the computed properties should be updated.
+pub fn connect_computed_properties(
+    entity_instance: &Arc<ReactiveEntityInstance>,
+    property_types: &[PropertyType],
+) -> Result<Arc<PropertyDependencyGraph>, PropertyDependencyError> {
+    let graph = PropertyDependencyGraph::from_property_types(property_types)?;
+    for derived in graph.get_derived_properties().iter().filter(|derived| derived.function.is_some()) {
+        entity_instance.add_property(&derived.name, Value::Null);
+    }
+    graph.recompute(entity_instance);
+    let graph = Arc::new(graph);
+    graph.connect(entity_instance);
+    Ok(graph)
+}
@@ -0,0 +1,60 @@
+use serde_json::{json, Value};
+
+/// A flag of a property which represents a set of flags.
+///
+/// Bit flags are stored as a number, named flags as an array of strings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PropertyFlag {
+    /// The bit mask of the flag.
+    Bit(u64),
+
+    /// The name of the flag.
+    Name(String),
+}
+
+impl From<u64> for PropertyFlag {
+    fn from(mask: u64) -> Self {
+        PropertyFlag::Bit(mask)
+    }
+}
+
+impl From<&str> for PropertyFlag {
+    fn from(name: &str) -> Self {
+        PropertyFlag::Name(name.to_string())
+    }
+}
+
+impl From<String> for PropertyFlag {
+    fn from(name: String) -> Self {
+        PropertyFlag::Name(name)
+    }
+}
+
+/// Returns true, if the flag is set in the given value.
+pub fn value_has_flag(value: &Value, flag: &PropertyFlag) -> bool {
+    match flag {
+        PropertyFlag::Bit(mask) => value.as_u64().is_some_and(|bits| bits & mask == *mask),
+        PropertyFlag::Name(name) => value.as_array().is_some_and(|names| names.iter().any(|n| n.as_str() == Some(name.as_str()))),
+    }
+}
+
+/// Returns the given value with the flag set or cleared. Values of another representation
+/// are treated as the empty set of flags.
+pub fn value_with_flag(value: &Value, flag: &PropertyFlag, enabled: bool) -> Value {
+    match flag {
+        PropertyFlag::Bit(mask) => {
+            let bits = value.as_u64().unwrap_or(0);
+            json!(if enabled { bits | mask } else { bits & !mask })
+        }
+        PropertyFlag::Name(name) => {
+            let mut names: Vec<Value> = value
+                .as_array()
+                .map(|names| names.iter().filter(|n| n.as_str() != Some(name.as_str())).cloned().collect())
+                .unwrap_or_default();
+            if enabled {
+                names.push(json!(name));
+            }
+            Value::Array(names)
+        }
+    }
+}
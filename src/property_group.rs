@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{group_property_types, PropertyType};
+
+/// A named section of a property panel, e.g. "Transform", "Rendering" or "Advanced".
+///
+/// The properties of the group are the property types whose group is the name of the group, see
+/// `PropertyType::group`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PropertyGroup {
+    /// The name of the group.
+    pub name: String,
+
+    /// Textual description of the group.
+    #[serde(default = "String::new")]
+    pub description: String,
+
+    /// The position of the group. Groups with a lower order are displayed first.
+    #[serde(default)]
+    pub order: i32,
+}
+
+impl PropertyGroup {
+    pub fn new<S: Into<String>>(name: S, order: i32) -> PropertyGroup {
+        PropertyGroup {
+            name: name.into(),
+            description: String::new(),
+            order,
+        }
+    }
+}
+
+/// Returns the property groups sorted by order. Groups with the same order keep their declaration order.
+pub fn get_ordered_property_groups(property_groups: &[PropertyGroup]) -> Vec<&PropertyGroup> {
+    let mut ordered: Vec<&PropertyGroup> = property_groups.iter().collect();
+    ordered.sort_by_key(|property_group| property_group.order);
+    ordered
+}
+
+/// Returns the group of the property type with the given name.
+pub fn find_property_group<'a, S: AsRef<str>>(
+    property_groups: &'a [PropertyGroup],
+    property_types: &[PropertyType],
+    property_name: S,
+) -> Option<&'a PropertyGroup> {
+    let property_type = property_types.iter().find(|property_type| property_type.name == property_name.as_ref())?;
+    property_groups.iter().find(|property_group| property_group.name == property_type.group)
+}
+
+/// Returns the property types of the group with the given name in display order.
+pub fn get_property_group_members<S: AsRef<str>>(property_types: &[PropertyType], group_name: S) -> Vec<&PropertyType> {
+    group_property_types(property_types)
+        .into_iter()
+        .find(|(group, _)| group == group_name.as_ref())
+        .map(|(_, members)| members)
+        .unwrap_or_default()
+}
@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, RwLock, Weak};
+use std::thread::{self, ThreadId};
+
+use serde_json::Value;
+
+use crate::{generate_id, ReactivePropertyContainer};
+
+/// A recorded change of a property value.
+#[derive(Clone, Debug)]
+pub struct PropertyChange {
+    /// The name of the property.
+    pub property_name: String,
+
+    /// The value before the change.
+    pub old_value: Value,
+
+    /// The value after the change.
+    pub new_value: Value,
+}
+
+/// Opt-in history recorder for the properties of a reactive instance.
+///
+/// The history observes the property streams and records every change with
+/// a bounded depth. Changes can be reverted using undo() and reapplied using
+/// redo().
+pub struct PropertyHistory {
+    /// The handle id of the observers.
+    pub handle_id: u128,
+
+    /// The maximum number of recorded changes.
+    pub depth: usize,
+
+    /// The changes which can be reverted.
+    undo_stack: RwLock<VecDeque<PropertyChange>>,
+
+    /// The changes which can be reapplied.
+    redo_stack: RwLock<Vec<PropertyChange>>,
+
+    /// The last known values of the observed properties.
+    last_values: RwLock<HashMap<String, Value>>,
+
+    /// The values which are written by undo or redo. The propagation of such a value on the
+    /// writing thread is not recorded, other changes of the same property are.
+    applying: Mutex<Vec<(ThreadId, String, Value)>>,
+}
+
+impl PropertyHistory {
+    pub fn new(depth: usize) -> Arc<PropertyHistory> {
+        Arc::new(PropertyHistory {
+            handle_id: generate_id().as_u128(),
+            depth,
+            undo_stack: RwLock::new(VecDeque::new()),
+            redo_stack: RwLock::new(Vec::new()),
+            last_values: RwLock::new(HashMap::new()),
+            applying: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Starts recording the changes of the properties of the given instance.
+    pub fn attach<C: ReactivePropertyContainer>(self: &Arc<Self>, instance: &C) {
+        for property_instance in instance.properties().iter() {
+            let property_name = property_instance.key().to_string();
+            self.last_values.write().unwrap().insert(property_name.clone(), property_instance.get());
+            let history = Arc::downgrade(self);
+            property_instance.observe_with_handle(
+                move |value: &Value| {
+                    if let Some(history) = Weak::upgrade(&history) {
+                        history.record(property_name.clone(), value.clone());
+                    }
+                },
+                self.handle_id,
+            );
+        }
+    }
+
+    /// Stops recording the changes of the properties of the given instance.
+    pub fn detach<C: ReactivePropertyContainer>(&self, instance: &C) {
+        for property_instance in instance.properties().iter() {
+            property_instance.remove_observer(self.handle_id);
+        }
+    }
+
+    /// Reverts the last change. Returns false, if there is nothing to undo or the old value has
+    /// been rejected, e.g. because the property is read only. In that case the change stays on
+    /// the undo stack.
+    pub fn undo<C: ReactivePropertyContainer>(&self, instance: &C) -> bool {
+        let change = match self.undo_stack.write().unwrap().pop_back() {
+            Some(change) => change,
+            None => return false,
+        };
+        if !self.apply(instance, &change.property_name, change.old_value.clone()) {
+            self.undo_stack.write().unwrap().push_back(change);
+            return false;
+        }
+        self.redo_stack.write().unwrap().push(change);
+        true
+    }
+
+    /// Reapplies the last reverted change. Returns false, if there is nothing to redo or the new
+    /// value has been rejected. In that case the change stays on the redo stack.
+    pub fn redo<C: ReactivePropertyContainer>(&self, instance: &C) -> bool {
+        let change = match self.redo_stack.write().unwrap().pop() {
+            Some(change) => change,
+            None => return false,
+        };
+        if !self.apply(instance, &change.property_name, change.new_value.clone()) {
+            self.redo_stack.write().unwrap().push(change);
+            return false;
+        }
+        self.undo_stack.write().unwrap().push_back(change);
+        true
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.read().unwrap().is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.read().unwrap().is_empty()
+    }
+
+    /// Returns the recorded changes, the oldest change first.
+    pub fn changes(&self) -> Vec<PropertyChange> {
+        self.undo_stack.read().unwrap().iter().cloned().collect()
+    }
+
+    /// Forgets all recorded changes.
+    pub fn clear(&self) {
+        self.undo_stack.write().unwrap().clear();
+        self.redo_stack.write().unwrap().clear();
+    }
+
+    fn record(&self, property_name: String, new_value: Value) {
+        let old_value = self.last_values.write().unwrap().insert(property_name.clone(), new_value.clone());
+        if self.take_applying(&property_name, &new_value) {
+            return;
+        }
+        let old_value = old_value.unwrap_or(Value::Null);
+        if old_value == new_value {
+            return;
+        }
+        let mut undo_stack = self.undo_stack.write().unwrap();
+        undo_stack.push_back(PropertyChange {
+            property_name,
+            old_value,
+            new_value,
+        });
+        while undo_stack.len() > self.depth {
+            undo_stack.pop_front();
+        }
+        self.redo_stack.write().unwrap().clear();
+    }
+
+    /// Writes the value. Returns false, if the property doesn't exist or the value has been
+    /// rejected.
+    fn apply<C: ReactivePropertyContainer>(&self, instance: &C, property_name: &str, value: Value) -> bool {
+        let property_instance = match instance.properties().get(property_name) {
+            Some(property_instance) => property_instance,
+            None => return false,
+        };
+        let thread_id = thread::current().id();
+        self.applying.lock().unwrap().push((thread_id, property_name.to_string(), value.clone()));
+        let written = property_instance
+            .modify_value(|current_value| {
+                *current_value = value.clone();
+                Some(())
+            })
+            .is_some();
+        // The value hasn't been propagated, e.g. because it has been rejected
+        self.take_applying(property_name, &value);
+        written
+    }
+
+    /// Removes the value which is written by the current thread. Returns true, if the value has
+    /// been written by undo or redo.
+    fn take_applying(&self, property_name: &str, value: &Value) -> bool {
+        let thread_id = thread::current().id();
+        let mut applying = self.applying.lock().unwrap();
+        match applying
+            .iter()
+            .position(|(id, name, applied_value)| *id == thread_id && name == property_name && applied_value == value)
+        {
+            Some(index) => {
+                applying.swap_remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Optional ring buffer of the last values of a reactive property.
+///
+/// The capacity is zero by default, so no values are recorded.
+#[derive(Debug, Default)]
+pub struct PropertyValueHistory {
+    /// The maximum number of recorded values.
+    pub capacity: usize,
+
+    /// The recorded values, the oldest value first.
+    values: Mutex<VecDeque<Value>>,
+}
+
+impl PropertyValueHistory {
+    pub fn new(capacity: usize) -> PropertyValueHistory {
+        PropertyValueHistory {
+            capacity,
+            values: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Returns true, if values are recorded.
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    /// Records the value. The oldest value is dropped, if the capacity is exceeded.
+    pub(crate) fn push(&self, value: Value) {
+        if !self.is_enabled() {
+            return;
+        }
+        let mut values = self.values.lock().unwrap();
+        while values.len() >= self.capacity {
+            values.pop_front();
+        }
+        values.push_back(value);
+    }
+
+    /// Returns the recorded values, the oldest value first.
+    pub fn get(&self) -> Vec<Value> {
+        self.values.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Returns the value before the latest recorded value.
+    pub fn previous(&self) -> Option<Value> {
+        let values = self.values.lock().unwrap();
+        values.len().checked_sub(2).and_then(|index| values.get(index).cloned())
+    }
+}
+
+impl Clone for PropertyValueHistory {
+    fn clone(&self) -> Self {
+        PropertyValueHistory {
+            capacity: self.capacity,
+            values: Mutex::new(self.values.lock().unwrap().clone()),
+        }
+    }
+}
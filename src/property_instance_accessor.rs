@@ -1,41 +1,186 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "time")]
+use chrono::{DateTime, FixedOffset, TimeZone};
 use serde_json::{Map, Value};
+use uuid::Uuid;
+
+use crate::PropertyTransaction;
+use crate::{coerce_to_bool, coerce_to_f64, coerce_to_i64, coerce_to_string};
+#[cfg(feature = "time")]
+use crate::{date_time_to_value, value_to_date_time, DateTimeError};
+use crate::{f64_to_value, value_to_f64, NonFiniteFloatError};
+#[cfg(feature = "jsonpath")]
+use crate::{get_jsonpath_property_name, select_jsonpath};
+use crate::{get_nested_value, set_nested_value, split_nested_path, NestedPropertyError};
+use crate::{merge_object_value, remove_object_key};
+use crate::{validate_value, PropertyType, ValidationError};
+use crate::{value_has_flag, value_with_flag, PropertyFlag};
+use crate::{value_to_binary, value_to_uuid};
 
 pub trait PropertyInstanceGetter {
     /// Returns the json value of the given property by name
-    fn get<S: Into<String>>(&self, property_name: S) -> Option<Value>;
+    fn get<S: AsRef<str>>(&self, property_name: S) -> Option<Value>;
 
     /// Returns the boolean value of the given property by name
-    fn as_bool<S: Into<String>>(&self, property_name: S) -> Option<bool>;
+    fn as_bool<S: AsRef<str>>(&self, property_name: S) -> Option<bool>;
 
     /// Returns the u64 value of the given property by name
-    fn as_u64<S: Into<String>>(&self, property_name: S) -> Option<u64>;
+    fn as_u64<S: AsRef<str>>(&self, property_name: S) -> Option<u64>;
 
     /// Returns the i64 value of the given property by name
-    fn as_i64<S: Into<String>>(&self, property_name: S) -> Option<i64>;
+    fn as_i64<S: AsRef<str>>(&self, property_name: S) -> Option<i64>;
 
     /// Returns the f64 value of the given property by name
-    fn as_f64<S: Into<String>>(&self, property_name: S) -> Option<f64>;
+    fn as_f64<S: AsRef<str>>(&self, property_name: S) -> Option<f64>;
 
     /// Returns the string value of the given property by name
-    fn as_string<S: Into<String>>(&self, property_name: S) -> Option<String>;
+    fn as_string<S: AsRef<str>>(&self, property_name: S) -> Option<String>;
 
     /// Returns the string value of the given property by name
-    fn as_array<S: Into<String>>(&self, property_name: S) -> Option<Vec<Value>>;
+    fn as_array<S: AsRef<str>>(&self, property_name: S) -> Option<Vec<Value>>;
 
     /// Returns the string value of the given property by name
-    fn as_object<S: Into<String>>(&self, property_name: S) -> Option<Map<String, Value>>;
+    fn as_object<S: AsRef<str>>(&self, property_name: S) -> Option<Map<String, Value>>;
+
+    /// Calls the function with a reference to the value of the given property by name. The default
+    /// implementation clones the value.
+    fn with_property<S: AsRef<str>, R, F: FnOnce(&Value) -> R>(&self, property_name: S, f: F) -> Option<R> {
+        self.get(property_name).map(|value| f(&value))
+    }
+
+    /// Returns the nested value of the given property by name at the JSON Pointer, e.g. `/window/width`.
+    /// Only the nested value is cloned.
+    fn get_by_pointer<S: AsRef<str>, P: AsRef<str>>(&self, property_name: S, pointer: P) -> Option<Value> {
+        self.with_property(property_name, |value| value.pointer(pointer.as_ref()).cloned()).flatten()
+    }
+
+    /// Returns the nested value at the dotted path, e.g. `transform.position.x`. The first segment
+    /// of the path is the name of the property.
+    fn get_nested<P: AsRef<str>>(&self, path: P) -> Option<Value> {
+        let (property_name, fields) = split_nested_path(path.as_ref()).ok()?;
+        self.with_property(property_name, |value| get_nested_value(value, &fields).cloned()).flatten()
+    }
+
+    /// Returns the values selected by the JSONPath expression, e.g. `$.items[?(@.active)].id`.
+    /// The first segment of the expression is the name of the property.
+    #[cfg(feature = "jsonpath")]
+    fn get_jsonpath<S: AsRef<str>>(&self, path: S) -> Option<Vec<Value>> {
+        let path = path.as_ref();
+        let property_name = get_jsonpath_property_name(path)?;
+        let value = self.get(property_name)?;
+        select_jsonpath(property_name, value, path)
+    }
+
+    /// Returns the value of the given property by name as integer. Numeric strings, booleans
+    /// and floats without fractional part are converted.
+    fn coerce_i64<S: AsRef<str>>(&self, property_name: S) -> Option<i64> {
+        self.with_property(property_name, coerce_to_i64).flatten()
+    }
+
+    /// Returns the value of the given property by name as float. Numeric strings and booleans
+    /// are converted.
+    fn coerce_f64<S: AsRef<str>>(&self, property_name: S) -> Option<f64> {
+        self.with_property(property_name, coerce_to_f64).flatten()
+    }
+
+    /// Returns the value of the given property by name as boolean. Numbers and strings like
+    /// `true`, `1`, `yes` or `on` are converted.
+    fn coerce_bool<S: AsRef<str>>(&self, property_name: S) -> Option<bool> {
+        self.with_property(property_name, coerce_to_bool).flatten()
+    }
+
+    /// Returns the value of the given property by name as string. Numbers and booleans are
+    /// converted.
+    fn coerce_string<S: AsRef<str>>(&self, property_name: S) -> Option<String> {
+        self.with_property(property_name, coerce_to_string).flatten()
+    }
+
+    /// Returns the binary data of the given property by name (base64 encoded string)
+    fn as_binary<S: AsRef<str>>(&self, property_name: S) -> Option<Vec<u8>> {
+        self.with_property(property_name, value_to_binary).flatten()
+    }
+
+    /// Returns the timestamp of the given property by name with the stored offset.
+    /// Returns none, if the value is not an RFC 3339 formatted string.
+    #[cfg(feature = "time")]
+    fn as_date_time<S: AsRef<str>>(&self, property_name: S) -> Option<DateTime<FixedOffset>> {
+        self.with_property(property_name, |value| value_to_date_time(value).ok()).flatten()
+    }
+
+    /// Returns the uuid of the given property by name (string encoded UUID)
+    fn as_uuid<S: AsRef<str>>(&self, property_name: S) -> Option<Uuid> {
+        self.with_property(property_name, value_to_uuid).flatten()
+    }
+
+    /// Returns the f64 value of the given property by name. Non-finite values which are
+    /// encoded as strings are decoded.
+    fn get_f64<S: AsRef<str>>(&self, property_name: S) -> Option<f64> {
+        self.get(property_name).and_then(|value| value_to_f64(&value))
+    }
+
+    /// Returns true, if the given bit flag or named flag of the property is set.
+    fn has_flag<S: AsRef<str>, F: Into<PropertyFlag>>(&self, property_name: S, flag: F) -> bool {
+        self.get(property_name).is_some_and(|value| value_has_flag(&value, &flag.into()))
+    }
 
     // TODO: integrate with other non-primitive types
-    // as_byte_array (string BASE64 -> Vec<u8>)
     // as_date (string ISO8601 -> chrono::Date)
-    // as_date_time (string ISO8601 -> chrono::DateTime)
     // as_time (string ISO8601 -> chrono::naive::NaiveTime)
-    // as_uuid (string uuid -> Uuid)
 }
 
 pub trait MutablePropertyInstanceSetter: PropertyInstanceGetter {
     /// Sets the value of the given property by name
-    fn set<S: Into<String>>(&mut self, property_name: S, value: Value);
+    fn set<S: AsRef<str>>(&mut self, property_name: S, value: Value);
+
+    /// Sets the nested value at the dotted path, e.g. `transform.position.x`. The first segment
+    /// of the path is the name of the property. Missing objects on the way are created.
+    fn set_nested<P: AsRef<str>>(&mut self, path: P, value: Value) -> Result<(), NestedPropertyError> {
+        let (property_name, fields) = split_nested_path(path.as_ref())?;
+        let mut property_value = self
+            .get(property_name)
+            .ok_or_else(|| NestedPropertyError::MissingProperty(property_name.to_string()))?;
+        set_nested_value(property_name, &mut property_value, &fields, value)?;
+        self.set(property_name, property_value);
+        Ok(())
+    }
+
+    /// Merges the entries into the object value of the given property by name. Entries with the
+    /// same key are replaced. Returns false, if the property doesn't exist or isn't an object.
+    fn merge_object<S: AsRef<str>>(&mut self, property_name: S, partial: Map<String, Value>) -> bool {
+        let mut value = match self.get(property_name.as_ref()) {
+            Some(value) => value,
+            None => return false,
+        };
+        if !merge_object_value(&mut value, partial, false) {
+            return false;
+        }
+        self.set(property_name, value);
+        true
+    }
+
+    /// Merges the entries into the object value of the given property by name. Nested objects are
+    /// merged recursively. Returns false, if the property doesn't exist or isn't an object.
+    fn merge_object_deep<S: AsRef<str>>(&mut self, property_name: S, partial: Map<String, Value>) -> bool {
+        let mut value = match self.get(property_name.as_ref()) {
+            Some(value) => value,
+            None => return false,
+        };
+        if !merge_object_value(&mut value, partial, true) {
+            return false;
+        }
+        self.set(property_name, value);
+        true
+    }
+
+    /// Removes the entry with the given key from the object value of the given property by name
+    /// and returns its value.
+    fn remove_key<S: AsRef<str>, K: AsRef<str>>(&mut self, property_name: S, key: K) -> Option<Value> {
+        let mut value = self.get(property_name.as_ref())?;
+        let removed = remove_object_key(&mut value, key.as_ref())?;
+        self.set(property_name, value);
+        Some(removed)
+    }
 
     // TODO: Typed setters
     // set_bool(property_name, value)
@@ -44,12 +189,132 @@ pub trait MutablePropertyInstanceSetter: PropertyInstanceGetter {
     // set_date(property_name, chrono::Date -> string ISO8601)
     // set_date_time(property_name, chrono::Date -> string ISO8601)
     // as_time(property_name, chrono::naive::NaiveTime -> string ISO8601)
-    // as_uuid(property_name, Uuid -> string uuid)
 }
 
 pub trait PropertyInstanceSetter: PropertyInstanceGetter {
     /// Sets the value of the given property by name
-    fn set<S: Into<String>>(&self, property_name: S, value: Value);
+    fn set<S: AsRef<str>>(&self, property_name: S, value: Value);
+
+    fn set_no_propagate<S: AsRef<str>>(&self, property_name: S, value: Value);
+
+    /// Sets the values of multiple properties. All values are written before any
+    /// value is propagated, so observers see a consistent state.
+    fn set_many(&self, values: HashMap<String, Value>);
+
+    /// Modifies the value of the given property by name while it is locked and propagates the new
+    /// value, so concurrent modifications are not lost. Returns none and leaves the value unchanged,
+    /// if the property doesn't exist or the function returns none.
+    fn modify_value<S: AsRef<str>, R, F: FnOnce(&mut Value) -> Option<R>>(&self, property_name: S, f: F) -> Option<R>;
+
+    /// Sets the value of the property of the given property type, if the value is valid. The
+    /// value is checked against the data type and the registered validators.
+    fn set_checked(&self, property_type: &PropertyType, value: Value) -> Result<(), ValidationError> {
+        validate_value(property_type, &value)?;
+        self.set(property_type.name.as_str(), value);
+        Ok(())
+    }
+
+    /// Sets the f64 value of the given property by name. Non-finite values are handled
+    /// according to the global non-finite float policy.
+    fn set_f64<S: AsRef<str>>(&self, property_name: S, value: f64) -> Result<(), NonFiniteFloatError> {
+        self.set(property_name, f64_to_value(value)?);
+        Ok(())
+    }
+
+    /// Sets the timestamp of the given property by name as an RFC 3339 formatted string.
+    #[cfg(feature = "time")]
+    fn set_date_time<S: AsRef<str>, Tz: TimeZone>(&self, property_name: S, date_time: DateTime<Tz>)
+    where
+        Tz::Offset: std::fmt::Display,
+    {
+        self.set(property_name, date_time_to_value(&date_time));
+    }
+
+    /// Sets the timestamp of the given property by name, if the string is an RFC 3339 formatted timestamp.
+    #[cfg(feature = "time")]
+    fn set_date_time_str<S: AsRef<str>>(&self, property_name: S, date_time: &str) -> Result<(), DateTimeError> {
+        let value = Value::String(date_time.to_string());
+        value_to_date_time(&value)?;
+        self.set(property_name, value);
+        Ok(())
+    }
+
+    /// Sets the nested value at the dotted path, e.g. `transform.position.x`, and propagates the
+    /// value of the property once. The first segment of the path is the name of the property.
+    /// Missing objects on the way are created. Returns an error, if the property isn't writable
+    /// or the new value has been dropped.
+    fn set_nested<P: AsRef<str>>(&self, path: P, value: Value) -> Result<(), NestedPropertyError> {
+        let (property_name, fields) = split_nested_path(path.as_ref())?;
+        if self.with_property(property_name, |_| ()).is_none() {
+            return Err(NestedPropertyError::MissingProperty(property_name.to_string()));
+        }
+        let mut result = Ok(());
+        let written = self.modify_value(property_name, |property_value| {
+            result = set_nested_value(property_name, property_value, &fields, value);
+            result.as_ref().ok().copied()
+        });
+        result?;
+        written.ok_or_else(|| NestedPropertyError::Rejected(property_name.to_string()))
+    }
+
+    /// Merges the entries into the object value of the given property by name and propagates the
+    /// value once. Entries with the same key are replaced. Returns false, if the property doesn't
+    /// exist or isn't an object.
+    fn merge_object<S: AsRef<str>>(&self, property_name: S, partial: Map<String, Value>) -> bool {
+        self.modify_value(property_name, |value| merge_object_value(value, partial, false).then_some(()))
+            .is_some()
+    }
+
+    /// Merges the entries into the object value of the given property by name and propagates the
+    /// value once. Nested objects are merged recursively. Returns false, if the property doesn't
+    /// exist or isn't an object.
+    fn merge_object_deep<S: AsRef<str>>(&self, property_name: S, partial: Map<String, Value>) -> bool {
+        self.modify_value(property_name, |value| merge_object_value(value, partial, true).then_some(()))
+            .is_some()
+    }
+
+    /// Removes the entry with the given key from the object value of the given property by name,
+    /// propagates the value and returns the removed value.
+    fn remove_key<S: AsRef<str>, K: AsRef<str>>(&self, property_name: S, key: K) -> Option<Value> {
+        self.modify_value(property_name, |value| remove_object_key(value, key.as_ref()))
+    }
+
+    /// Sets the given bit flag or named flag of the property.
+    fn set_flag<S: AsRef<str>, F: Into<PropertyFlag>>(&self, property_name: S, flag: F) {
+        let flag = flag.into();
+        self.modify_value(property_name, |value| {
+            *value = value_with_flag(value, &flag, true);
+            Some(())
+        });
+    }
+
+    /// Clears the given bit flag or named flag of the property.
+    fn clear_flag<S: AsRef<str>, F: Into<PropertyFlag>>(&self, property_name: S, flag: F) {
+        let flag = flag.into();
+        self.modify_value(property_name, |value| {
+            *value = value_with_flag(value, &flag, false);
+            Some(())
+        });
+    }
+
+    /// Toggles the given bit flag or named flag of the property while the value is locked, so
+    /// concurrent toggles are not lost. Returns true, if the flag is set afterwards. Returns false,
+    /// if the property doesn't exist or the value has been rejected.
+    fn toggle_flag<S: AsRef<str>, F: Into<PropertyFlag>>(&self, property_name: S, flag: F) -> bool {
+        let flag = flag.into();
+        self.modify_value(property_name, |value| {
+            let enabled = !value_has_flag(value, &flag);
+            *value = value_with_flag(value, &flag, enabled);
+            Some(enabled)
+        })
+        .unwrap_or(false)
+    }
 
-    fn set_no_propagate<S: Into<String>>(&self, property_name: S, value: Value);
+    /// Begins a transaction which stages property changes until commit.
+    fn begin_transaction(&self) -> PropertyTransaction<'_, Self>
+    where
+        Self: Sized,
+    {
+        PropertyTransaction::new(self)
+    }
 }
@@ -0,0 +1,135 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, LazyLock, RwLock, Weak};
+
+/// The pool of interned strings which is shared by all reactive properties.
+///
+/// The pool only holds weak references, so a string is released as soon as it isn't used
+/// anymore. The entries of released strings are removed when the pool grows.
+#[derive(Default)]
+struct InternedStrings {
+    /// The interned strings by the hash of the string.
+    entries: HashMap<u64, Vec<Weak<str>>>,
+
+    /// The number of entries, including the entries of released strings.
+    len: usize,
+
+    /// The number of entries after the last removal of the entries of released strings.
+    retained: usize,
+}
+
+impl InternedStrings {
+    fn get(&self, hash: u64, s: &str) -> Option<Arc<str>> {
+        self.entries
+            .get(&hash)?
+            .iter()
+            .filter_map(Weak::upgrade)
+            .find(|interned| interned.as_ref() == s)
+    }
+
+    fn insert(&mut self, hash: u64, interned: &Arc<str>) {
+        if self.len >= 2 * self.retained.max(INTERNED_STRINGS_MIN_CAPACITY) {
+            self.release();
+        }
+        let entries = self.entries.entry(hash).or_default();
+        let len = entries.len();
+        entries.retain(|entry| entry.strong_count() > 0);
+        entries.push(Arc::downgrade(interned));
+        self.len = self.len + entries.len() - len;
+    }
+
+    /// Removes the entries of released strings. Returns the number of removed entries.
+    fn release(&mut self) -> usize {
+        self.entries.retain(|_, entries| {
+            entries.retain(|entry| entry.strong_count() > 0);
+            !entries.is_empty()
+        });
+        let len = self.len;
+        self.len = self.entries.values().map(Vec::len).sum();
+        self.retained = self.len;
+        len - self.len
+    }
+}
+
+/// The number of entries up to which released strings are not removed.
+const INTERNED_STRINGS_MIN_CAPACITY: usize = 1024;
+
+static INTERNED_STRINGS: LazyLock<RwLock<InternedStrings>> = LazyLock::new(|| RwLock::new(InternedStrings::default()));
+
+fn hash_string(s: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    s.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Returns the shared allocation of the given string.
+pub fn intern_string(s: &str) -> Arc<str> {
+    let hash = hash_string(s);
+    if let Some(interned) = INTERNED_STRINGS.read().unwrap().get(hash, s) {
+        return interned;
+    }
+    let mut interned_strings = INTERNED_STRINGS.write().unwrap();
+    if let Some(interned) = interned_strings.get(hash, s) {
+        return interned;
+    }
+    let interned: Arc<str> = Arc::from(s);
+    interned_strings.insert(hash, &interned);
+    interned
+}
+
+/// Returns the number of interned strings which are in use.
+pub fn get_interned_string_count() -> usize {
+    INTERNED_STRINGS
+        .read()
+        .unwrap()
+        .entries
+        .values()
+        .flatten()
+        .filter(|entry| entry.strong_count() > 0)
+        .count()
+}
+
+/// Removes the entries of the interned strings which are not used anymore. Returns the number of
+/// removed entries. The entries are removed automatically as well, when the pool grows.
+pub fn release_unused_interned_strings() -> usize {
+    INTERNED_STRINGS.write().unwrap().release()
+}
+
+/// Optional interning of the string values of a reactive property.
+///
+/// If enabled, a string value is stored as a shared allocation instead of
+/// the value of the property, so instances with identical string values
+/// share a single allocation.
+#[derive(Debug, Default)]
+pub struct PropertyInterning {
+    /// If true, string values are interned.
+    pub enabled: bool,
+
+    /// The interned string value.
+    value: RwLock<Option<Arc<str>>>,
+}
+
+impl PropertyInterning {
+    pub fn new(enabled: bool) -> PropertyInterning {
+        PropertyInterning {
+            enabled,
+            value: RwLock::new(None),
+        }
+    }
+
+    /// Returns the interned string value.
+    pub fn get(&self) -> Option<Arc<str>> {
+        self.value.read().unwrap().clone()
+    }
+
+    /// Stores the interned string value.
+    pub(crate) fn store(&self, value: Arc<str>) {
+        *self.value.write().unwrap() = Some(value);
+    }
+
+    /// Takes the interned string value.
+    pub(crate) fn take(&self) -> Option<Arc<str>> {
+        self.value.write().unwrap().take()
+    }
+}
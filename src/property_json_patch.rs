@@ -0,0 +1,184 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use json_patch::{Patch, PatchOperation};
+use serde_json::{Map, Value};
+
+use crate::{
+    EntityInstance, PropertyInstanceSetter, PropertyMap, ReactiveEntityInstance, ReactivePropertyContainer, ReactivePropertyInstance, ReactiveRelationInstance,
+    RelationInstance,
+};
+
+/// An error which occurs if a JSON Patch can't be applied to the properties of an instance.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PropertyPatchError {
+    /// An operation of the patch failed. None of the operations has been applied.
+    Patch(String),
+
+    /// The patch replaces the properties with a value which isn't an object.
+    NotAnObject,
+
+    /// The patch removes the property with the given name. The properties of reactive instances can't be removed.
+    RemoveProperty(String),
+}
+
+impl fmt::Display for PropertyPatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PropertyPatchError::Patch(message) => write!(f, "Failed to apply the patch: {}", message),
+            PropertyPatchError::NotAnObject => write!(f, "The properties must be an object"),
+            PropertyPatchError::RemoveProperty(name) => write!(f, "The property {} can't be removed", name),
+        }
+    }
+}
+
+/// Returns the name of the property which is the first token of the JSON Pointer or none, if the
+/// pointer refers to the whole document.
+fn get_pointer_property_name(pointer: &str) -> Option<String> {
+    let pointer = pointer.strip_prefix('/')?;
+    let token = pointer.split('/').next().unwrap_or_default();
+    Some(token.replace("~1", "/").replace("~0", "~"))
+}
+
+/// The names of the properties which are read and which are modified by the patch. None means
+/// that all properties are affected.
+struct AffectedProperties {
+    read: Option<HashSet<String>>,
+    modified: Option<HashSet<String>>,
+}
+
+impl AffectedProperties {
+    fn new(patch: &Patch) -> AffectedProperties {
+        let mut read = Some(HashSet::new());
+        let mut modified = Some(HashSet::new());
+        let insert = |names: &mut Option<HashSet<String>>, pointer: &str| match get_pointer_property_name(pointer) {
+            Some(name) => {
+                if let Some(names) = names.as_mut() {
+                    names.insert(name);
+                }
+            }
+            None => *names = None,
+        };
+        for operation in patch.0.iter() {
+            match operation {
+                PatchOperation::Add(operation) => insert(&mut modified, &operation.path),
+                PatchOperation::Remove(operation) => insert(&mut modified, &operation.path),
+                PatchOperation::Replace(operation) => insert(&mut modified, &operation.path),
+                PatchOperation::Move(operation) => {
+                    insert(&mut modified, &operation.from);
+                    insert(&mut modified, &operation.path);
+                }
+                PatchOperation::Copy(operation) => {
+                    insert(&mut read, &operation.from);
+                    insert(&mut modified, &operation.path);
+                }
+                PatchOperation::Test(operation) => insert(&mut read, &operation.path),
+            }
+        }
+        if let (Some(read), Some(modified)) = (read.as_mut(), modified.as_ref()) {
+            read.extend(modified.iter().cloned());
+        } else {
+            read = None;
+        }
+        AffectedProperties { read, modified }
+    }
+
+    fn is_read(&self, name: &str) -> bool {
+        self.read.as_ref().map(|names| names.contains(name)).unwrap_or(true)
+    }
+
+    fn is_modified(&self, name: &str) -> bool {
+        self.modified.as_ref().map(|names| names.contains(name)).unwrap_or(true)
+    }
+}
+
+/// Applies the patch to the properties. The properties are the root object of the patch, so the
+/// first token of the paths is the name of the property. If an operation fails, none of the
+/// operations is applied.
+pub fn apply_json_patch(properties: &mut PropertyMap, patch: &Patch) -> Result<(), PropertyPatchError> {
+    let mut document = Value::Object(properties.iter().map(|(name, value)| (name.clone(), value.clone())).collect());
+    json_patch::patch(&mut document, &patch.0).map_err(|error| PropertyPatchError::Patch(error.to_string()))?;
+    match document {
+        Value::Object(document) => {
+            *properties = document.into_iter().collect();
+            Ok(())
+        }
+        _ => Err(PropertyPatchError::NotAnObject),
+    }
+}
+
+/// Applies the patch to the properties of a reactive instance. Each modified property is
+/// propagated once, after all values have been written. Added properties are created.
+fn apply_reactive_json_patch<I: PropertyInstanceSetter, F: Fn(&str, Value)>(
+    instance: &I,
+    properties: &DashMap<Arc<str>, ReactivePropertyInstance>,
+    add_property: F,
+    patch: &Patch,
+) -> Result<(), PropertyPatchError> {
+    let affected = AffectedProperties::new(patch);
+    let mut document = Map::new();
+    for property_instance in properties.iter() {
+        if affected.is_read(property_instance.key()) {
+            document.insert(property_instance.key().to_string(), property_instance.get());
+        }
+    }
+    let existing: Vec<String> = document.keys().cloned().collect();
+    let mut document = Value::Object(document);
+    json_patch::patch(&mut document, &patch.0).map_err(|error| PropertyPatchError::Patch(error.to_string()))?;
+    let mut document = match document {
+        Value::Object(document) => document,
+        _ => return Err(PropertyPatchError::NotAnObject),
+    };
+    if let Some(name) = existing.into_iter().find(|name| !document.contains_key(name)) {
+        return Err(PropertyPatchError::RemoveProperty(name));
+    }
+    document.retain(|name, _| affected.is_modified(name));
+    let mut values = HashMap::new();
+    for (name, value) in document {
+        if properties.contains_key(name.as_str()) {
+            values.insert(name, value);
+        } else {
+            add_property(&name, value);
+        }
+    }
+    if !values.is_empty() {
+        instance.set_many(values);
+    }
+    Ok(())
+}
+
+impl EntityInstance {
+    /// Applies the JSON Patch to the properties of the entity instance. If an operation fails,
+    /// none of the operations is applied.
+    pub fn apply_json_patch(&mut self, patch: &Patch) -> Result<(), PropertyPatchError> {
+        apply_json_patch(&mut self.properties, patch)
+    }
+}
+
+impl RelationInstance {
+    /// Applies the JSON Patch to the properties of the relation instance. If an operation fails,
+    /// none of the operations is applied.
+    pub fn apply_json_patch(&mut self, patch: &Patch) -> Result<(), PropertyPatchError> {
+        apply_json_patch(&mut self.properties, patch)
+    }
+}
+
+impl ReactiveEntityInstance {
+    /// Applies the JSON Patch to the properties of the entity instance. All values are written
+    /// before each modified property is propagated once. Properties can be added but not removed.
+    /// If an operation fails, none of the operations is applied.
+    pub fn apply_json_patch(&self, patch: &Patch) -> Result<(), PropertyPatchError> {
+        apply_reactive_json_patch(self, &self.properties, |name, value| self.add_property(name, value), patch)
+    }
+}
+
+impl ReactiveRelationInstance {
+    /// Applies the JSON Patch to the properties of the relation instance. All values are written
+    /// before each modified property is propagated once. Properties can be added but not removed.
+    /// If an operation fails, none of the operations is applied.
+    pub fn apply_json_patch(&self, patch: &Patch) -> Result<(), PropertyPatchError> {
+        apply_reactive_json_patch(self, &self.properties, |name, value| self.add_property(name, value), patch)
+    }
+}
@@ -0,0 +1,26 @@
+use serde_json::{Map, Value};
+
+/// Returns the name of the property which is the first segment of the given
+/// JSONPath expression, e.g. `items` for `$.items[?(@.active)].id` or `$['items']`.
+pub fn get_jsonpath_property_name(path: &str) -> Option<&str> {
+    let path = path.strip_prefix('$')?;
+    if let Some(path) = path.strip_prefix('.') {
+        let end = path.find(['.', '[']).unwrap_or(path.len());
+        return Some(&path[..end]).filter(|property_name| !property_name.is_empty() && *property_name != "*");
+    }
+    let path = path.strip_prefix('[')?;
+    let quote = path.chars().next().filter(|c| *c == '\'' || *c == '"')?;
+    let path = &path[1..];
+    path.find(quote).map(|end| &path[..end])
+}
+
+/// Evaluates the JSONPath expression against the value of the given property.
+///
+/// The root of the expression is an object which contains the property, so
+/// the first segment of the expression is the name of the property.
+pub fn select_jsonpath(property_name: &str, value: Value, path: &str) -> Option<Vec<Value>> {
+    let mut root = Map::new();
+    root.insert(property_name.to_string(), value);
+    let root = Value::Object(root);
+    jsonpath_lib::select(&root, path).ok().map(|values| values.into_iter().cloned().collect())
+}
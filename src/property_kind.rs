@@ -0,0 +1,33 @@
+use core::fmt;
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+/// The kind of a property defines if the property holds a continuous value or
+/// emits discrete events.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Copy, Eq)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[serde(rename_all = "snake_case")]
+pub enum PropertyKind {
+    /// The property holds the last value which has been set.
+    #[default]
+    ValueProperty,
+
+    /// The property queues the emitted payloads instead of holding a last value.
+    EventProperty,
+}
+
+impl From<&str> for PropertyKind {
+    fn from(value: &str) -> Self {
+        match value.to_lowercase().as_str() {
+            "event_property" | "event" => Self::EventProperty,
+            _ => Self::ValueProperty,
+        }
+    }
+}
+
+impl Display for PropertyKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
@@ -0,0 +1,29 @@
+#[cfg(not(feature = "preserve_order"))]
+use std::collections::HashMap;
+
+#[cfg(feature = "preserve_order")]
+use indexmap::IndexMap;
+use serde_json::Value;
+
+/// The property values of an entity instance or a relation instance by property name.
+///
+/// With the feature `preserve_order` the properties keep their insertion order, so serialized
+/// instances and flows are stable and diffable. Use `remove_property_value` to remove a property
+/// without changing the order of the remaining properties.
+#[cfg(feature = "preserve_order")]
+pub type PropertyMap = IndexMap<String, Value>;
+
+/// The property values of an entity instance or a relation instance by property name.
+///
+/// The properties are unordered. Enable the feature `preserve_order` to keep the insertion order.
+#[cfg(not(feature = "preserve_order"))]
+pub type PropertyMap = HashMap<String, Value>;
+
+/// Removes the property with the given name and returns its value. The order of the remaining
+/// properties is kept.
+pub fn remove_property_value(properties: &mut PropertyMap, name: &str) -> Option<Value> {
+    #[cfg(feature = "preserve_order")]
+    return properties.shift_remove(name);
+    #[cfg(not(feature = "preserve_order"))]
+    return properties.remove(name);
+}
@@ -0,0 +1,249 @@
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::ser::{self, Error as _};
+use serde::Serialize;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{EntityInstance, EntityTypeName, PropertyInstanceGetter, PropertyInstanceSetter, PropertyMap, PropertyType};
+
+/// Maps the fields of a struct to the properties of an instance. With the feature `derive` the
+/// mapping can be derived with `#[derive(PropertyMapping)]`.
+pub trait PropertyMapping: Sized {
+    /// Returns the property types of the mapped fields.
+    fn property_types() -> Vec<PropertyType>;
+
+    /// Returns the values of the mapped fields as properties. Returns an error, if a field can't
+    /// be represented as property value.
+    fn to_properties(&self) -> Result<PropertyMap, PropertyMappingError>;
+
+    /// Reads the mapped fields from the properties of the given instance.
+    fn from_instance<I: PropertyInstanceGetter>(instance: &I) -> Result<Self, PropertyMappingError>;
+
+    /// Sets the properties of the given instance to the values of the mapped fields. No property
+    /// is set, if a field can't be represented as property value.
+    fn apply_to<I: PropertyInstanceSetter>(&self, instance: &I) -> Result<(), PropertyMappingError> {
+        for (property_name, value) in self.to_properties()? {
+            instance.set(property_name, value);
+        }
+        Ok(())
+    }
+
+    /// Creates an entity instance with the values of the mapped fields as properties.
+    fn to_entity_instance<S: Into<EntityTypeName>>(&self, type_name: S, id: Uuid) -> Result<EntityInstance, PropertyMappingError> {
+        Ok(EntityInstance::new(type_name, id, self.to_properties()?))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PropertyMappingError {
+    /// The instance has no property with the given name.
+    MissingProperty(String),
+
+    /// The value of the property with the given name can't be converted into the type of the field.
+    InvalidValue(String, String),
+
+    /// The field which is mapped to the property with the given name can't be represented as
+    /// property value, e.g. because it contains a non-finite float.
+    InvalidField(String, String),
+}
+
+impl fmt::Display for PropertyMappingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PropertyMappingError::MissingProperty(property_name) => write!(f, "The property {} is missing", property_name),
+            PropertyMappingError::InvalidValue(property_name, error) => write!(f, "The value of the property {} is invalid: {}", property_name, error),
+            PropertyMappingError::InvalidField(property_name, error) => write!(f, "The field of the property {} can't be mapped: {}", property_name, error),
+        }
+    }
+}
+
+/// Returns the value of the property converted into the type of the field. A missing property is
+/// only accepted, if the type of the field accepts null, e.g. an `Option`.
+pub fn get_mapped_property<T: DeserializeOwned, I: PropertyInstanceGetter>(instance: &I, property_name: &str) -> Result<T, PropertyMappingError> {
+    match instance.get(property_name) {
+        Some(value) => serde_json::from_value(value).map_err(|e| PropertyMappingError::InvalidValue(property_name.to_string(), e.to_string())),
+        None => serde_json::from_value(Value::Null).map_err(|_| PropertyMappingError::MissingProperty(property_name.to_string())),
+    }
+}
+
+/// Returns the value of the field as property value. Returns an error, if the field can't be
+/// serialized or contains a non-finite float, which JSON would silently turn into null.
+pub fn to_mapped_value<T: Serialize>(property_name: &str, value: &T) -> Result<Value, PropertyMappingError> {
+    value
+        .serialize(FiniteFloatCheck)
+        .and_then(|_| serde_json::to_value(value))
+        .map_err(|e| PropertyMappingError::InvalidField(property_name.to_string(), e.to_string()))
+}
+
+/// Walks through a value and fails on the first non-finite float.
+struct FiniteFloatCheck;
+
+impl FiniteFloatCheck {
+    fn check(value: f64) -> Result<(), serde_json::Error> {
+        if value.is_finite() {
+            Ok(())
+        } else {
+            Err(serde_json::Error::custom(format!("The non-finite value {} cannot be stored", value)))
+        }
+    }
+}
+
+macro_rules! accept {
+    ($($method: ident($ty: ty)),*) => {
+        $(fn $method(self, _: $ty) -> Result<(), serde_json::Error> {
+            Ok(())
+        })*
+    };
+}
+
+impl ser::Serializer for FiniteFloatCheck {
+    type Ok = ();
+    type Error = serde_json::Error;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    accept!(
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_i128(i128),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_u128(u128),
+        serialize_char(char),
+        serialize_str(&str),
+        serialize_bytes(&[u8]),
+        serialize_unit_struct(&'static str)
+    );
+
+    fn serialize_f32(self, value: f32) -> Result<(), serde_json::Error> {
+        FiniteFloatCheck::check(value as f64)
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<(), serde_json::Error> {
+        FiniteFloatCheck::check(value)
+    }
+
+    fn serialize_none(self) -> Result<(), serde_json::Error> {
+        Ok(())
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<(), serde_json::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), serde_json::Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(self, _: &'static str, _: u32, _: &'static str) -> Result<(), serde_json::Error> {
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(self, _: &'static str, value: &T) -> Result<(), serde_json::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(self, _: &'static str, _: u32, _: &'static str, value: &T) -> Result<(), serde_json::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self, serde_json::Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self, serde_json::Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self, serde_json::Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(self, _: &'static str, _: u32, _: &'static str, _: usize) -> Result<Self, serde_json::Error> {
+        Ok(self)
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self, serde_json::Error> {
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self, serde_json::Error> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(self, _: &'static str, _: u32, _: &'static str, _: usize) -> Result<Self, serde_json::Error> {
+        Ok(self)
+    }
+}
+
+macro_rules! check_elements {
+    ($($serialize: ident::$method: ident),*) => {
+        $(impl ser::$serialize for FiniteFloatCheck {
+            type Ok = ();
+            type Error = serde_json::Error;
+
+            fn $method<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), serde_json::Error> {
+                value.serialize(FiniteFloatCheck)
+            }
+
+            fn end(self) -> Result<(), serde_json::Error> {
+                Ok(())
+            }
+        })*
+    };
+}
+
+check_elements!(
+    SerializeSeq::serialize_element,
+    SerializeTuple::serialize_element,
+    SerializeTupleStruct::serialize_field,
+    SerializeTupleVariant::serialize_field
+);
+
+impl ser::SerializeMap for FiniteFloatCheck {
+    type Ok = ();
+    type Error = serde_json::Error;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, _: &T) -> Result<(), serde_json::Error> {
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), serde_json::Error> {
+        value.serialize(FiniteFloatCheck)
+    }
+
+    fn end(self) -> Result<(), serde_json::Error> {
+        Ok(())
+    }
+}
+
+macro_rules! check_fields {
+    ($($serialize: ident),*) => {
+        $(impl ser::$serialize for FiniteFloatCheck {
+            type Ok = ();
+            type Error = serde_json::Error;
+
+            fn serialize_field<T: ?Sized + Serialize>(&mut self, _: &'static str, value: &T) -> Result<(), serde_json::Error> {
+                value.serialize(FiniteFloatCheck)
+            }
+
+            fn end(self) -> Result<(), serde_json::Error> {
+                Ok(())
+            }
+        })*
+    };
+}
+
+check_fields!(SerializeStruct, SerializeStructVariant);
@@ -0,0 +1,269 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+/// A counted operation on a reactive property.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Metric {
+    /// The value of the property has been set.
+    PropertySet,
+
+    /// A value of the property has been propagated to the observers.
+    Propagation,
+
+    /// The value of the property has been set without propagating it (`set_no_propagate`).
+    SuppressedPropagation,
+
+    /// The property has been ticked.
+    Tick,
+
+    /// An observer of the property has been invoked.
+    ObserverInvocation,
+}
+
+impl fmt::Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Metric::PropertySet => write!(f, "property_set"),
+            Metric::Propagation => write!(f, "propagation"),
+            Metric::SuppressedPropagation => write!(f, "suppressed_propagation"),
+            Metric::Tick => write!(f, "tick"),
+            Metric::ObserverInvocation => write!(f, "observer_invocation"),
+        }
+    }
+}
+
+/// Records the metrics of the reactive properties, e.g. by forwarding them to a metrics backend.
+///
+/// The recorder is called while the reactive model is propagating values, so it must be cheap
+/// and must not access the properties.
+pub trait MetricsRecorder: Send + Sync {
+    /// Records the metric of the property of the instance with the given id.
+    fn record(&self, metric: Metric, id: Uuid, property_name: &str);
+}
+
+static RECORDING: AtomicBool = AtomicBool::new(false);
+
+static METRICS_RECORDER: RwLock<Option<Arc<dyn MetricsRecorder>>> = RwLock::new(None);
+
+/// Incremented whenever the recorder is registered or unregistered.
+static METRICS_RECORDER_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    /// The recorder of a generation, so that recording only locks the global recorder after the
+    /// recorder has been replaced.
+    static CACHED_METRICS_RECORDER: RefCell<(u64, Option<Arc<dyn MetricsRecorder>>)> = const { RefCell::new((0, None)) };
+}
+
+/// Registers the global metrics recorder. Replaces a previously registered recorder.
+///
+/// Each thread keeps a reference to the recorder until it records the next metric, so a replaced
+/// recorder may be dropped later.
+pub fn set_metrics_recorder(recorder: Arc<dyn MetricsRecorder>) {
+    *METRICS_RECORDER.write().unwrap() = Some(recorder);
+    METRICS_RECORDER_GENERATION.fetch_add(1, Ordering::Release);
+    RECORDING.store(true, Ordering::SeqCst);
+}
+
+/// Unregisters the global metrics recorder.
+pub fn clear_metrics_recorder() {
+    RECORDING.store(false, Ordering::SeqCst);
+    *METRICS_RECORDER.write().unwrap() = None;
+    METRICS_RECORDER_GENERATION.fetch_add(1, Ordering::Release);
+}
+
+/// Returns true, if a metrics recorder is registered.
+pub fn has_metrics_recorder() -> bool {
+    RECORDING.load(Ordering::SeqCst)
+}
+
+pub(crate) fn record_metric(metric: Metric, id: Uuid, property_name: &str) {
+    if !RECORDING.load(Ordering::Relaxed) {
+        return;
+    }
+    let generation = METRICS_RECORDER_GENERATION.load(Ordering::Acquire);
+    let recorder = CACHED_METRICS_RECORDER.with(|cached| {
+        let mut cached = cached.borrow_mut();
+        if cached.0 != generation {
+            *cached = (generation, METRICS_RECORDER.read().unwrap().clone());
+        }
+        cached.1.clone()
+    });
+    // The recorder is called after the cache has been released, so it may record metrics itself
+    if let Some(recorder) = recorder {
+        recorder.record(metric, id, property_name);
+    }
+}
+
+/// Wraps the observer, so that its invocations are recorded as observer invocations of the property.
+///
+/// The observers which are registered through the property instance, e.g. with
+/// `observe_with_handle` or `observe_with_priority`, are instrumented already. Use this for
+/// observers which are registered directly on the stream of a property.
+pub fn instrument_observer<S: Into<Arc<str>>, F: Fn(&Value) + Send + Sync + 'static>(
+    id: Uuid,
+    property_name: S,
+    observer: F,
+) -> impl Fn(&Value) + Send + Sync + 'static {
+    let property_name = property_name.into();
+    move |value: &Value| {
+        record_metric(Metric::ObserverInvocation, id, &property_name);
+        observer(value)
+    }
+}
+
+/// The counters of the metrics.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MetricCounts {
+    /// The number of times the value has been set.
+    pub property_sets: u64,
+
+    /// The number of values which have been propagated to the observers.
+    pub propagations: u64,
+
+    /// The number of values which have been set without propagating them.
+    pub suppressed_propagations: u64,
+
+    /// The number of ticks.
+    pub ticks: u64,
+
+    /// The number of observer invocations.
+    pub observer_invocations: u64,
+}
+
+impl MetricCounts {
+    /// Returns the counter of the metric.
+    pub fn get(&self, metric: Metric) -> u64 {
+        match metric {
+            Metric::PropertySet => self.property_sets,
+            Metric::Propagation => self.propagations,
+            Metric::SuppressedPropagation => self.suppressed_propagations,
+            Metric::Tick => self.ticks,
+            Metric::ObserverInvocation => self.observer_invocations,
+        }
+    }
+
+    /// Increments the counter of the metric.
+    pub fn increment(&mut self, metric: Metric) {
+        match metric {
+            Metric::PropertySet => self.property_sets += 1,
+            Metric::Propagation => self.propagations += 1,
+            Metric::SuppressedPropagation => self.suppressed_propagations += 1,
+            Metric::Tick => self.ticks += 1,
+            Metric::ObserverInvocation => self.observer_invocations += 1,
+        }
+    }
+
+    /// Adds the counters of the other metrics.
+    pub fn add(&mut self, other: &MetricCounts) {
+        self.property_sets += other.property_sets;
+        self.propagations += other.propagations;
+        self.suppressed_propagations += other.suppressed_propagations;
+        self.ticks += other.ticks;
+        self.observer_invocations += other.observer_invocations;
+    }
+}
+
+/// The metrics of a property.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PropertyMetrics {
+    /// The id of the instance which contains the property.
+    pub id: Uuid,
+
+    /// The name of the property.
+    pub property_name: String,
+
+    /// The counters of the property.
+    pub counts: MetricCounts,
+}
+
+/// A metrics recorder which counts the metrics per property in memory.
+#[derive(Default)]
+pub struct CountingMetricsRecorder {
+    properties: Mutex<HashMap<(Uuid, String), MetricCounts>>,
+}
+
+impl CountingMetricsRecorder {
+    pub fn new() -> CountingMetricsRecorder {
+        CountingMetricsRecorder::default()
+    }
+
+    /// Returns the counters of the property of the instance with the given id.
+    pub fn get_property<S: Into<String>>(&self, id: Uuid, property_name: S) -> MetricCounts {
+        self.properties.lock().unwrap().get(&(id, property_name.into())).copied().unwrap_or_default()
+    }
+
+    /// Returns the sum of the counters of the properties of the instance with the given id.
+    pub fn get_instance(&self, id: Uuid) -> MetricCounts {
+        let mut counts = MetricCounts::default();
+        for ((property_id, _), property_counts) in self.properties.lock().unwrap().iter() {
+            if *property_id == id {
+                counts.add(property_counts);
+            }
+        }
+        counts
+    }
+
+    /// Returns the sum of the counters of the instances per type. The function returns the name of
+    /// the type of the instance with the given id. Instances of unknown types are omitted.
+    pub fn get_by_type<F: Fn(Uuid) -> Option<String>>(&self, type_of: F) -> HashMap<String, MetricCounts> {
+        let mut types: HashMap<String, MetricCounts> = HashMap::new();
+        for ((id, _), counts) in self.properties.lock().unwrap().iter() {
+            if let Some(type_name) = type_of(*id) {
+                types.entry(type_name).or_default().add(counts);
+            }
+        }
+        types
+    }
+
+    /// Returns the metrics of all properties.
+    pub fn get_properties(&self) -> Vec<PropertyMetrics> {
+        self.properties
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|((id, property_name), counts)| PropertyMetrics {
+                id: *id,
+                property_name: property_name.clone(),
+                counts: *counts,
+            })
+            .collect()
+    }
+
+    /// Returns the properties with the highest counters of the metric, e.g. the properties which
+    /// are propagated most often.
+    pub fn hot_properties(&self, metric: Metric, limit: usize) -> Vec<PropertyMetrics> {
+        let mut properties = self.get_properties();
+        properties.sort_by(|a, b| {
+            b.counts
+                .get(metric)
+                .cmp(&a.counts.get(metric))
+                .then_with(|| a.id.cmp(&b.id))
+                .then_with(|| a.property_name.cmp(&b.property_name))
+        });
+        properties.truncate(limit);
+        properties
+    }
+
+    /// Resets all counters.
+    pub fn reset(&self) {
+        self.properties.lock().unwrap().clear();
+    }
+}
+
+impl MetricsRecorder for CountingMetricsRecorder {
+    fn record(&self, metric: Metric, id: Uuid, property_name: &str) {
+        self.properties
+            .lock()
+            .unwrap()
+            .entry((id, property_name.to_string()))
+            .or_default()
+            .increment(metric);
+    }
+}
@@ -0,0 +1,37 @@
+use core::fmt;
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+/// The mutability of a property defines if the value can be changed after the property has been
+/// initialized, e.g. for configuration constants and identifiers.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Copy, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PropertyMutability {
+    /// The value can be changed at any time.
+    #[default]
+    Mutable,
+
+    /// The value can only be set once. Further writes are dropped.
+    Immutable,
+
+    /// The value is derived from other properties and only written by its owner, e.g. the
+    /// statistics of a property. Writes from outside are dropped.
+    ReadOnly,
+}
+
+impl From<&str> for PropertyMutability {
+    fn from(value: &str) -> Self {
+        match value.to_lowercase().as_str() {
+            "immutable" => Self::Immutable,
+            "readonly" => Self::ReadOnly,
+            _ => Self::Mutable,
+        }
+    }
+}
+
+impl Display for PropertyMutability {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
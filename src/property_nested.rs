@@ -0,0 +1,62 @@
+use std::fmt;
+
+use serde_json::{Map, Value};
+
+/// An error which occurs if a nested value can't be set.
+#[derive(Debug, PartialEq, Eq)]
+pub enum NestedPropertyError {
+    /// The dotted path is empty or contains an empty segment.
+    InvalidPath(String),
+
+    /// The property with the given name doesn't exist.
+    MissingProperty(String),
+
+    /// The value at the given dotted path isn't an object.
+    NotAnObject(String),
+
+    /// The value of the property with the given name has been rejected, e.g. because the
+    /// property is read only or the new value has been dropped by the type enforcement.
+    Rejected(String),
+}
+
+impl fmt::Display for NestedPropertyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NestedPropertyError::InvalidPath(path) => write!(f, "The path {} is invalid", path),
+            NestedPropertyError::MissingProperty(name) => write!(f, "The property {} doesn't exist", name),
+            NestedPropertyError::NotAnObject(path) => write!(f, "The value at {} is not an object", path),
+            NestedPropertyError::Rejected(name) => write!(f, "The value of the property {} has been rejected", name),
+        }
+    }
+}
+
+/// Splits the dotted path, e.g. `transform.position.x`, into the name of the property and the
+/// names of the nested fields.
+pub fn split_nested_path(path: &str) -> Result<(&str, Vec<&str>), NestedPropertyError> {
+    let mut segments = path.split('.');
+    let property_name = segments.next().unwrap_or_default();
+    let fields: Vec<&str> = segments.collect();
+    if property_name.is_empty() || fields.iter().any(|field| field.is_empty()) {
+        return Err(NestedPropertyError::InvalidPath(path.to_string()));
+    }
+    Ok((property_name, fields))
+}
+
+/// Returns the nested value at the given fields.
+pub fn get_nested_value<'a>(value: &'a Value, fields: &[&str]) -> Option<&'a Value> {
+    fields.iter().try_fold(value, |value, field| value.as_object()?.get(*field))
+}
+
+/// Replaces the nested value at the given fields. Missing objects on the way are created. The
+/// property name is only used for the error messages.
+pub fn set_nested_value(property_name: &str, value: &mut Value, fields: &[&str], nested_value: Value) -> Result<(), NestedPropertyError> {
+    let mut current = value;
+    for (index, field) in fields.iter().enumerate() {
+        let object = current
+            .as_object_mut()
+            .ok_or_else(|| NestedPropertyError::NotAnObject([&[property_name], &fields[..index]].concat().join(".")))?;
+        current = object.entry(field.to_string()).or_insert_with(|| Value::Object(Map::new()));
+    }
+    *current = nested_value;
+    Ok(())
+}
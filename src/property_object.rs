@@ -0,0 +1,28 @@
+use serde_json::{Map, Value};
+
+/// Merges the entries into the object. Entries with the same key are replaced. If `deep` is
+/// true, nested objects are merged recursively instead of being replaced. Returns false, if the
+/// value isn't an object.
+pub fn merge_object_value(value: &mut Value, partial: Map<String, Value>, deep: bool) -> bool {
+    let object = match value.as_object_mut() {
+        Some(object) => object,
+        None => return false,
+    };
+    for (key, partial_value) in partial {
+        match (object.get_mut(&key), partial_value) {
+            (Some(existing @ Value::Object(_)), Value::Object(nested)) if deep => {
+                merge_object_value(existing, nested, true);
+            }
+            (_, partial_value) => {
+                object.insert(key, partial_value);
+            }
+        }
+    }
+    true
+}
+
+/// Removes the entry with the given key from the object and returns its value. Returns none, if
+/// the value isn't an object or doesn't contain the key.
+pub fn remove_object_key(value: &mut Value, key: &str) -> Option<Value> {
+    value.as_object_mut()?.remove(key)
+}
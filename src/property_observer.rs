@@ -0,0 +1,14 @@
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::ReactivePropertyInstance;
+
+/// Callback which is called with the name and the new value of a property.
+pub type PropertyObserver = Arc<dyn Fn(&str, &Value) + Send + Sync>;
+
+/// Subscribes the observer with the given handle id to the property with the default priority.
+pub(crate) fn subscribe(property_instance: &ReactivePropertyInstance, handle_id: u128, observer: PropertyObserver) {
+    let property_name = property_instance.name.clone();
+    property_instance.observe_with_handle(move |value: &Value| observer(&property_name, value), handle_id);
+}
@@ -0,0 +1,190 @@
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock, Weak};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{generate_id, value_content_hash, Extension, PropertyInstanceSetter, ReactiveEntityInstance};
+
+/// The name of the extension which declares the routing strategy of a routing
+/// relation type, e.g. `{ "strategy": "weighted_random", "weights": [3, 1] }`.
+pub const ROUTING_EXTENSION: &str = "routing";
+
+/// Specifies which of several inputs receives a value of an output property.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+pub enum RoutingStrategy {
+    /// The inputs receive the values in turn.
+    RoundRobin,
+
+    /// The input is chosen randomly. Inputs without weight have the weight 1.
+    WeightedRandom {
+        #[serde(default = "Vec::new")]
+        weights: Vec<u32>,
+    },
+
+    /// The input is chosen by the hash of the value. If a key is given and the
+    /// value is an object, the hash of the field with the name of the key is used.
+    /// The hash is stable, so equal values are routed to the same input across
+    /// restarts and machines.
+    Hash {
+        #[serde(default)]
+        key: Option<String>,
+    },
+}
+
+impl RoutingStrategy {
+    /// Returns the routing strategy declared by the routing extension.
+    pub fn from_extensions(extensions: &[Extension]) -> Option<RoutingStrategy> {
+        extensions
+            .iter()
+            .find(|extension| extension.name == ROUTING_EXTENSION)
+            .and_then(|extension| serde_json::from_value(extension.extension.clone()).ok())
+    }
+}
+
+/// An input property which can receive the routed values.
+pub struct RoutingTarget {
+    /// The entity instance.
+    pub instance: Weak<ReactiveEntityInstance>,
+
+    /// The name of the input property.
+    pub property_name: String,
+}
+
+/// Feeds the values of one output property into one of several input properties.
+pub struct PropertyRouter {
+    /// The handle id of the observer.
+    pub handle_id: u128,
+
+    /// The routing strategy.
+    pub strategy: RoutingStrategy,
+
+    /// The input properties.
+    targets: RwLock<Vec<RoutingTarget>>,
+
+    /// The index of the next input (round robin).
+    next: AtomicUsize,
+
+    /// The state of the random number generator (weighted random).
+    seed: AtomicU64,
+}
+
+impl PropertyRouter {
+    pub fn new(strategy: RoutingStrategy) -> Arc<PropertyRouter> {
+        PropertyRouter::with_seed(strategy, generate_id().as_u128() as u64)
+    }
+
+    /// Constructs a router with the given seed, so that weighted random routing is reproducible.
+    pub fn with_seed(strategy: RoutingStrategy, seed: u64) -> Arc<PropertyRouter> {
+        Arc::new(PropertyRouter {
+            handle_id: generate_id().as_u128(),
+            strategy,
+            targets: RwLock::new(Vec::new()),
+            next: AtomicUsize::new(0),
+            // The state of the xorshift generator must not be zero
+            seed: AtomicU64::new(seed | 1),
+        })
+    }
+
+    /// Adds an input property.
+    pub fn add_target<S: Into<String>>(&self, instance: &Arc<ReactiveEntityInstance>, property_name: S) {
+        self.targets.write().unwrap().push(RoutingTarget {
+            instance: Arc::downgrade(instance),
+            property_name: property_name.into(),
+        });
+    }
+
+    /// Returns the number of input properties.
+    pub fn get_target_count(&self) -> usize {
+        self.targets.read().unwrap().len()
+    }
+
+    /// Returns the index of the input which receives the given value.
+    pub fn select(&self, value: &Value) -> Option<usize> {
+        let count = self.get_target_count();
+        if count == 0 {
+            return None;
+        }
+        match &self.strategy {
+            RoutingStrategy::RoundRobin => Some(self.next.fetch_add(1, Ordering::SeqCst) % count),
+            RoutingStrategy::WeightedRandom { weights } => {
+                let weights: Vec<u64> = (0..count).map(|index| weights.get(index).copied().unwrap_or(1) as u64).collect();
+                let total: u64 = weights.iter().sum();
+                if total == 0 {
+                    return None;
+                }
+                let mut choice = self.next_random() % total;
+                weights.iter().position(|weight| {
+                    if choice < *weight {
+                        return true;
+                    }
+                    choice -= weight;
+                    false
+                })
+            }
+            RoutingStrategy::Hash { key } => {
+                let value = match key {
+                    Some(key) => value.get(key).unwrap_or(value),
+                    None => value,
+                };
+                Some((value_content_hash(value) % count as u64) as usize)
+            }
+        }
+    }
+
+    /// Sends the value to the selected input property. The targets are not locked while the value
+    /// is sent, so observers of the input property can add targets.
+    pub fn route(&self, value: &Value) {
+        let index = match self.select(value) {
+            Some(index) => index,
+            None => return,
+        };
+        let target = self
+            .targets
+            .read()
+            .unwrap()
+            .get(index)
+            .map(|target| (target.instance.clone(), target.property_name.clone()));
+        if let Some((instance, property_name)) = target {
+            if let Some(instance) = instance.upgrade() {
+                instance.set(property_name, value.clone());
+            }
+        }
+    }
+
+    /// Routes the values of the given output property. Returns false, if the property doesn't exist.
+    pub fn connect<S: AsRef<str>>(self: &Arc<Self>, outbound: &ReactiveEntityInstance, property_name: S) -> bool {
+        let property_instance = match outbound.properties.get(property_name.as_ref()) {
+            Some(property_instance) => property_instance,
+            None => return false,
+        };
+        let router = Arc::downgrade(self);
+        property_instance.observe_with_handle(
+            move |value: &Value| {
+                if let Some(router) = router.upgrade() {
+                    router.route(value);
+                }
+            },
+            self.handle_id,
+        );
+        true
+    }
+
+    /// Stops routing the values of the given output property.
+    pub fn disconnect<S: AsRef<str>>(&self, outbound: &ReactiveEntityInstance, property_name: S) {
+        if let Some(property_instance) = outbound.properties.get(property_name.as_ref()) {
+            property_instance.remove_observer(self.handle_id);
+        }
+    }
+
+    /// Returns the next number of the xorshift generator.
+    fn next_random(&self) -> u64 {
+        let mut x = self.seed.load(Ordering::SeqCst);
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.seed.store(x, Ordering::SeqCst);
+        x
+    }
+}
@@ -0,0 +1,219 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, RwLock, Weak};
+
+use serde_json::{json, Value};
+
+use crate::{generate_id, ObserverGuard, PropertyMutability, PropertyType, ReactiveEntityInstance, ReactivePropertyContainer, ReactiveRelationInstance};
+
+/// The name of the extension which declares that statistics should be
+/// maintained for a numeric property. The extension value is the size of
+/// the window of the last N average, e.g. `{ "window": 10 }`.
+pub const STATISTICS_EXTENSION: &str = "statistics";
+
+/// The default size of the window of the last N average.
+pub const STATISTICS_DEFAULT_WINDOW: usize = 10;
+
+pub const STATISTICS_SUFFIX_MIN: &str = "_min";
+pub const STATISTICS_SUFFIX_MAX: &str = "_max";
+pub const STATISTICS_SUFFIX_MEAN: &str = "_mean";
+pub const STATISTICS_SUFFIX_AVERAGE: &str = "_average";
+pub const STATISTICS_SUFFIX_RATE_OF_CHANGE: &str = "_rate_of_change";
+
+/// Derived statistics of a numeric property.
+#[derive(Clone, Debug)]
+pub struct PropertyStatistics {
+    /// The size of the window of the last N average.
+    pub window: usize,
+
+    /// The number of samples.
+    pub count: u64,
+
+    /// The minimum value.
+    pub min: Option<f64>,
+
+    /// The maximum value.
+    pub max: Option<f64>,
+
+    /// The sum of all samples.
+    pub sum: f64,
+
+    /// The change between the last two samples.
+    pub rate_of_change: f64,
+
+    /// The last N samples.
+    pub samples: VecDeque<f64>,
+}
+
+impl PropertyStatistics {
+    pub fn new(window: usize) -> PropertyStatistics {
+        PropertyStatistics {
+            window: window.max(1),
+            count: 0,
+            min: None,
+            max: None,
+            sum: 0.0,
+            rate_of_change: 0.0,
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Adds a sample.
+    pub fn update(&mut self, value: f64) {
+        if let Some(last) = self.samples.back() {
+            self.rate_of_change = value - last;
+        }
+        self.count += 1;
+        self.sum += value;
+        self.min = Some(self.min.map_or(value, |min| min.min(value)));
+        self.max = Some(self.max.map_or(value, |max| max.max(value)));
+        self.samples.push_back(value);
+        while self.samples.len() > self.window {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Returns the mean of all samples.
+    pub fn mean(&self) -> Option<f64> {
+        if self.count == 0 {
+            return None;
+        }
+        Some(self.sum / self.count as f64)
+    }
+
+    /// Returns the average of the last N samples.
+    pub fn average(&self) -> Option<f64> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        Some(self.samples.iter().sum::<f64>() / self.samples.len() as f64)
+    }
+
+    /// Returns the derived statistics as pairs of the suffix of the sibling property and the value.
+    pub fn values(&self) -> Vec<(&'static str, Value)> {
+        vec![
+            (STATISTICS_SUFFIX_MIN, json!(self.min)),
+            (STATISTICS_SUFFIX_MAX, json!(self.max)),
+            (STATISTICS_SUFFIX_MEAN, json!(self.mean())),
+            (STATISTICS_SUFFIX_AVERAGE, json!(self.average())),
+            (STATISTICS_SUFFIX_RATE_OF_CHANGE, json!(self.rate_of_change)),
+        ]
+    }
+}
+
+/// Maintains derived statistics of numeric properties as sibling properties.
+///
+/// For a property `x` the sibling properties `x_min`, `x_max`, `x_mean`,
+/// `x_average` and `x_rate_of_change` are updated on each set.
+pub struct StatisticsPropertyDecorator {}
+
+impl StatisticsPropertyDecorator {
+    /// Returns the window size, if the property type declares the statistics extension.
+    pub fn get_window(property_type: &PropertyType) -> Option<usize> {
+        property_type
+            .extensions
+            .iter()
+            .find(|extension| extension.name == STATISTICS_EXTENSION)
+            .map(|extension| {
+                extension
+                    .extension
+                    .get("window")
+                    .and_then(|window| window.as_u64())
+                    .map(|window| window as usize)
+                    .unwrap_or(STATISTICS_DEFAULT_WINDOW)
+            })
+    }
+
+    /// Returns the names of the sibling properties of the given property.
+    pub fn get_property_names<S: Into<String>>(property_name: S) -> Vec<String> {
+        let property_name = property_name.into();
+        [
+            STATISTICS_SUFFIX_MIN,
+            STATISTICS_SUFFIX_MAX,
+            STATISTICS_SUFFIX_MEAN,
+            STATISTICS_SUFFIX_AVERAGE,
+            STATISTICS_SUFFIX_RATE_OF_CHANGE,
+        ]
+        .iter()
+        .map(|suffix| format!("{}{}", property_name, suffix))
+        .collect()
+    }
+
+    /// Decorates all properties of the entity instance which declare the statistics extension.
+    /// The statistics are maintained as long as the returned guards exist.
+    pub fn decorate_entity(entity_instance: &Arc<ReactiveEntityInstance>, property_types: &[PropertyType]) -> Vec<ObserverGuard> {
+        StatisticsPropertyDecorator::decorate_all(entity_instance, property_types)
+    }
+
+    /// Decorates all properties of the relation instance which declare the statistics extension.
+    /// The statistics are maintained as long as the returned guards exist.
+    pub fn decorate_relation(relation_instance: &Arc<ReactiveRelationInstance>, property_types: &[PropertyType]) -> Vec<ObserverGuard> {
+        StatisticsPropertyDecorator::decorate_all(relation_instance, property_types)
+    }
+
+    /// Maintains statistics for the given property of the entity instance as long as the returned
+    /// guard exists. Returns none, if the property doesn't exist.
+    pub fn decorate_entity_property<S: Into<String>>(entity_instance: &Arc<ReactiveEntityInstance>, property_name: S, window: usize) -> Option<ObserverGuard> {
+        StatisticsPropertyDecorator::decorate(entity_instance, property_name.into(), window)
+    }
+
+    /// Maintains statistics for the given property of the relation instance as long as the
+    /// returned guard exists. Returns none, if the property doesn't exist.
+    pub fn decorate_relation_property<S: Into<String>>(
+        relation_instance: &Arc<ReactiveRelationInstance>,
+        property_name: S,
+        window: usize,
+    ) -> Option<ObserverGuard> {
+        StatisticsPropertyDecorator::decorate(relation_instance, property_name.into(), window)
+    }
+
+    fn decorate_all<T: ReactivePropertyContainer + Send + Sync + 'static>(instance: &Arc<T>, property_types: &[PropertyType]) -> Vec<ObserverGuard> {
+        property_types
+            .iter()
+            .filter_map(|property_type| {
+                let window = StatisticsPropertyDecorator::get_window(property_type)?;
+                StatisticsPropertyDecorator::decorate(instance, property_type.name.clone(), window)
+            })
+            .collect()
+    }
+
+    /// Adds the sibling properties, which are read only, and observes the source property.
+    fn decorate<T: ReactivePropertyContainer + Send + Sync + 'static>(instance: &Arc<T>, property_name: String, window: usize) -> Option<ObserverGuard> {
+        if !instance.has_property(&property_name) {
+            return None;
+        }
+        for sibling_property_name in StatisticsPropertyDecorator::get_property_names(property_name.clone()) {
+            instance.add_property(&sibling_property_name, Value::Null);
+            if let Some(mut sibling_property_instance) = instance.properties().get_mut(sibling_property_name.as_str()) {
+                sibling_property_instance.set_mutability(PropertyMutability::ReadOnly);
+            }
+        }
+        let property_instance = instance.properties().get(property_name.as_str())?;
+        let statistics = RwLock::new(PropertyStatistics::new(window));
+        let weak_instance: Weak<T> = Arc::downgrade(instance);
+        let source_property_name = property_name.clone();
+        let handle_id = generate_id().as_u128();
+        property_instance.observe_with_handle(
+            move |value: &Value| {
+                let value = match value.as_f64() {
+                    Some(value) => value,
+                    None => return,
+                };
+                let values = {
+                    let mut statistics = statistics.write().unwrap();
+                    statistics.update(value);
+                    statistics.values()
+                };
+                if let Some(instance) = weak_instance.upgrade() {
+                    for (suffix, value) in values {
+                        if let Some(sibling_property_instance) = instance.properties().get(format!("{}{}", source_property_name, suffix).as_str()) {
+                            sibling_property_instance.set_derived(value);
+                        }
+                    }
+                }
+            },
+            handle_id,
+        );
+        let observers = property_instance.prioritized_observers.downgrade();
+        Some(ObserverGuard::new(handle_id, move |handle_id| observers.remove(handle_id)))
+    }
+}
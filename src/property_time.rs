@@ -0,0 +1,40 @@
+use std::fmt;
+use std::fmt::Display;
+
+use chrono::{DateTime, FixedOffset, SecondsFormat, TimeZone};
+use serde_json::Value;
+
+/// A property value is not a valid RFC 3339 timestamp.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DateTimeError {
+    /// The value is not a string.
+    NotAString(Value),
+
+    /// The string is not an RFC 3339 formatted timestamp.
+    Invalid(String),
+}
+
+impl fmt::Display for DateTimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateTimeError::NotAString(value) => write!(f, "The value {} is not a string", value),
+            DateTimeError::Invalid(s) => write!(f, "{} is not an RFC 3339 timestamp", s),
+        }
+    }
+}
+
+/// Converts the timestamp into an RFC 3339 formatted JSON string. The offset is preserved.
+pub fn date_time_to_value<Tz: TimeZone>(date_time: &DateTime<Tz>) -> Value
+where
+    Tz::Offset: Display,
+{
+    Value::String(date_time.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+}
+
+/// Converts an RFC 3339 formatted JSON string into a timestamp with its offset.
+pub fn value_to_date_time(value: &Value) -> Result<DateTime<FixedOffset>, DateTimeError> {
+    match value {
+        Value::String(s) => DateTime::parse_from_rfc3339(s).map_err(|_| DateTimeError::Invalid(s.clone())),
+        value => Err(DateTimeError::NotAString(value.clone())),
+    }
+}
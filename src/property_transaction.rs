@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::PropertyInstanceSetter;
+
+/// Stages multiple property changes and propagates them in a single commit.
+///
+/// Observers are notified after all staged values have been written, so they
+/// see a consistent state of all properties (e.g. x/y/z of a position) instead
+/// of intermediate states. Dropping the transaction without commit discards
+/// the staged values.
+pub struct PropertyTransaction<'a, T: PropertyInstanceSetter> {
+    /// The instance which properties are modified.
+    instance: &'a T,
+
+    /// The staged property values.
+    values: HashMap<String, Value>,
+}
+
+impl<'a, T: PropertyInstanceSetter> PropertyTransaction<'a, T> {
+    pub fn new(instance: &'a T) -> PropertyTransaction<'a, T> {
+        PropertyTransaction {
+            instance,
+            values: HashMap::new(),
+        }
+    }
+
+    /// Stages the value of the given property.
+    pub fn set<S: Into<String>>(&mut self, property_name: S, value: Value) -> &mut Self {
+        self.values.insert(property_name.into(), value);
+        self
+    }
+
+    /// Returns the staged value of the given property.
+    pub fn get_staged<S: Into<String>>(&self, property_name: S) -> Option<&Value> {
+        self.values.get(&property_name.into())
+    }
+
+    /// Returns true, if no value has been staged.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Writes all staged values and propagates them afterwards.
+    pub fn commit(self) {
+        self.instance.set_many(self.values);
+    }
+
+    /// Discards all staged values.
+    pub fn rollback(self) {}
+}
@@ -1,7 +1,11 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
-use crate::extension::Extension;
-use crate::{DataType, SocketType};
+use crate::extension::{get_extension, Extension, ExtensionError};
+use crate::{DataType, PropertyKind, PropertyMutability, SocketType};
 
 /// Definition of a property. The definition contains
 /// the name of the property, the data type and the socket
@@ -22,9 +26,37 @@ pub struct PropertyType {
     #[serde(default = "SocketType::none")]
     pub socket_type: SocketType,
 
+    /// Specifies if the property holds a value or emits events.
+    #[serde(default)]
+    pub kind: PropertyKind,
+
+    /// Specifies if the value can be changed after the property has been initialized.
+    #[serde(default)]
+    pub mutability: PropertyMutability,
+
+    /// The unit of the values, e.g. `meters`, `degrees` or `ms`.
+    #[serde(default)]
+    pub unit: Option<String>,
+
+    /// The semantic tag of the values, e.g. `position` or `color`.
+    #[serde(default)]
+    pub semantic: Option<String>,
+
     /// Property specific extensions
     #[serde(default = "Vec::new")]
     pub extensions: Vec<Extension>,
+
+    /// The position of the property in property panels. Properties without order are displayed last.
+    #[serde(default)]
+    pub order: Option<i32>,
+
+    /// The name of the group of the property in property panels.
+    #[serde(default = "String::new")]
+    pub group: String,
+
+    /// The human-readable label of the property.
+    #[serde(default = "String::new")]
+    pub label: String,
 }
 
 impl PropertyType {
@@ -34,7 +66,14 @@ impl PropertyType {
             description: String::new(),
             data_type,
             socket_type: SocketType::None,
+            kind: PropertyKind::ValueProperty,
+            mutability: PropertyMutability::Mutable,
+            unit: None,
+            semantic: None,
             extensions: Vec::new(),
+            order: None,
+            group: String::new(),
+            label: String::new(),
         }
     }
 
@@ -44,7 +83,14 @@ impl PropertyType {
             description: String::new(),
             data_type,
             socket_type,
+            kind: PropertyKind::ValueProperty,
+            mutability: PropertyMutability::Mutable,
+            unit: None,
+            semantic: None,
             extensions: Vec::new(),
+            order: None,
+            group: String::new(),
+            label: String::new(),
         }
     }
 
@@ -54,7 +100,14 @@ impl PropertyType {
             description: String::new(),
             data_type,
             socket_type: SocketType::Input,
+            kind: PropertyKind::ValueProperty,
+            mutability: PropertyMutability::Mutable,
+            unit: None,
+            semantic: None,
             extensions: Vec::new(),
+            order: None,
+            group: String::new(),
+            label: String::new(),
         }
     }
 
@@ -64,7 +117,185 @@ impl PropertyType {
             description: String::new(),
             data_type,
             socket_type: SocketType::Output,
+            kind: PropertyKind::ValueProperty,
+            mutability: PropertyMutability::Mutable,
+            unit: None,
+            semantic: None,
+            extensions: Vec::new(),
+            order: None,
+            group: String::new(),
+            label: String::new(),
+        }
+    }
+
+    /// Creates an event property which queues the emitted payloads.
+    pub fn event<S: Into<String>>(name: S, data_type: DataType, socket_type: SocketType) -> PropertyType {
+        PropertyType {
+            name: name.into(),
+            description: String::new(),
+            data_type,
+            socket_type,
+            kind: PropertyKind::EventProperty,
+            mutability: PropertyMutability::Mutable,
+            unit: None,
+            semantic: None,
             extensions: Vec::new(),
+            order: None,
+            group: String::new(),
+            label: String::new(),
+        }
+    }
+
+    /// Returns true, if the property is an event property.
+    pub fn is_event(&self) -> bool {
+        self.kind == PropertyKind::EventProperty
+    }
+
+    /// Returns true, if instances have to provide a value of the property. Event properties don't
+    /// hold a value and the only value of the null data type is null.
+    pub fn is_mandatory(&self) -> bool {
+        !self.is_event() && self.data_type != DataType::Null
+    }
+
+    /// Makes the property immutable. The value can only be set once.
+    pub fn immutable(mut self) -> PropertyType {
+        self.mutability = PropertyMutability::Immutable;
+        self
+    }
+
+    /// Returns true, if the value can't be changed after the property has been initialized.
+    pub fn is_immutable(&self) -> bool {
+        self.mutability == PropertyMutability::Immutable
+    }
+
+    pub fn with_unit<S: Into<String>>(mut self, unit: S) -> PropertyType {
+        self.unit = Some(unit.into());
+        self
+    }
+
+    pub fn with_semantic<S: Into<String>>(mut self, semantic: S) -> PropertyType {
+        self.semantic = Some(semantic.into());
+        self
+    }
+
+    /// Sets the name of the group of the property in property panels.
+    pub fn with_group<S: Into<String>>(mut self, group: S) -> PropertyType {
+        self.group = group.into();
+        self
+    }
+
+    /// Returns true, if the values of the property can be passed to the given property without
+    /// conversion of units. Properties without unit are compatible with any unit.
+    pub fn is_unit_compatible(&self, other: &PropertyType) -> bool {
+        match (&self.unit, &other.unit) {
+            (Some(unit), Some(other_unit)) => unit == other_unit,
+            _ => true,
+        }
+    }
+
+    /// Adds the extension. An extension with the same name is replaced.
+    pub fn with_extension(mut self, extension: Extension) -> PropertyType {
+        self.extensions.retain(|e| e.name != extension.name);
+        self.extensions.push(extension);
+        self
+    }
+
+    /// Returns true, if the property contains an extension with the given name.
+    pub fn has_extension<S: AsRef<str>>(&self, extension_name: S) -> bool {
+        self.extensions.iter().any(|extension| extension.name == extension_name.as_ref())
+    }
+
+    /// Returns the deserialized payload of the extension with the given name.
+    pub fn get_extension<T: DeserializeOwned, S: AsRef<str>>(&self, extension_name: S) -> Result<T, ExtensionError> {
+        get_extension(&self.extensions, extension_name)
+    }
+
+    /// Returns the label of the property or the name, if the property has no label.
+    pub fn get_label(&self) -> &str {
+        if self.label.is_empty() {
+            &self.name
+        } else {
+            &self.label
+        }
+    }
+
+    /// Returns an error, if the property cannot be connected to the given property.
+    ///
+    /// The property must be an output socket and the given property must be an input socket.
+    /// The data type must be equal to or widen to the data type of the given property. Event
+    /// properties can only be connected to event properties. If both properties declare a unit,
+    /// the units must be equal.
+    pub fn is_connectable_to(&self, inbound: &PropertyType) -> Result<(), ConnectError> {
+        if self.socket_type != SocketType::Output {
+            return Err(ConnectError::NotAnOutput(self.name.clone(), self.socket_type));
+        }
+        if inbound.socket_type != SocketType::Input {
+            return Err(ConnectError::NotAnInput(inbound.name.clone(), inbound.socket_type));
+        }
+        if self.kind != inbound.kind {
+            return Err(ConnectError::IncompatibleKinds(self.kind, inbound.kind));
+        }
+        if !self.data_type.is_widening_to(&inbound.data_type) {
+            return Err(ConnectError::IncompatibleDataTypes(self.data_type, inbound.data_type));
+        }
+        if !self.is_unit_compatible(inbound) {
+            return Err(ConnectError::IncompatibleUnits(
+                self.unit.clone().unwrap_or_default(),
+                inbound.unit.clone().unwrap_or_default(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Groups the property types for property panels. The groups are in the order of their first
+/// occurrence. Within a group the properties are sorted by order and then by name.
+pub fn group_property_types(property_types: &[PropertyType]) -> Vec<(String, Vec<&PropertyType>)> {
+    let mut groups: Vec<(String, Vec<&PropertyType>)> = Vec::new();
+    for property_type in property_types {
+        match groups.iter_mut().find(|(group, _)| *group == property_type.group) {
+            Some((_, members)) => members.push(property_type),
+            None => groups.push((property_type.group.clone(), vec![property_type])),
+        }
+    }
+    for (_, members) in groups.iter_mut() {
+        members.sort_by(|a, b| match (a.order, b.order) {
+            (Some(order_a), Some(order_b)) => order_a.cmp(&order_b).then_with(|| a.name.cmp(&b.name)),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => a.name.cmp(&b.name),
+        });
+    }
+    groups
+}
+
+/// The reason why two properties cannot be connected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConnectError {
+    /// The outbound property isn't an output socket.
+    NotAnOutput(String, SocketType),
+
+    /// The inbound property isn't an input socket.
+    NotAnInput(String, SocketType),
+
+    /// The data type of the outbound property doesn't widen to the data type of the inbound property.
+    IncompatibleDataTypes(DataType, DataType),
+
+    /// A value property cannot be connected with an event property.
+    IncompatibleKinds(PropertyKind, PropertyKind),
+
+    /// The unit of the outbound property differs from the unit of the inbound property.
+    IncompatibleUnits(String, String),
+}
+
+impl fmt::Display for ConnectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConnectError::NotAnOutput(name, socket_type) => write!(f, "The property {} is not an output socket but {}", name, socket_type),
+            ConnectError::NotAnInput(name, socket_type) => write!(f, "The property {} is not an input socket but {}", name, socket_type),
+            ConnectError::IncompatibleDataTypes(outbound, inbound) => write!(f, "The data type {} cannot be converted into {}", outbound, inbound),
+            ConnectError::IncompatibleKinds(outbound, inbound) => write!(f, "A {} cannot be connected to a {}", outbound, inbound),
+            ConnectError::IncompatibleUnits(outbound, inbound) => write!(f, "The unit {} cannot be connected to the unit {}", outbound, inbound),
         }
     }
 }
@@ -0,0 +1,84 @@
+use std::sync::{Arc, RwLock};
+
+use serde_json::Value;
+
+use crate::{DataType, PropertyType, ValidationError};
+
+/// Validates a value of a property of the given property type. Returns the reason why the value is invalid.
+pub type ValueValidator = Arc<dyn Fn(&PropertyType, &Value) -> Result<(), String> + Send + Sync>;
+
+/// Specifies the property types a validator is applied to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValidatorKey {
+    /// The validator is applied to property types of the data type.
+    DataType(DataType),
+
+    /// The validator is applied to property types which have an extension with the name, e.g. "email".
+    Extension(String),
+}
+
+impl ValidatorKey {
+    /// Returns true, if the validator is applied to the given property type.
+    pub fn applies_to(&self, property_type: &PropertyType) -> bool {
+        match self {
+            ValidatorKey::DataType(data_type) => property_type.data_type == *data_type,
+            ValidatorKey::Extension(name) => property_type.extensions.iter().any(|extension| extension.name == *name),
+        }
+    }
+}
+
+impl From<DataType> for ValidatorKey {
+    fn from(data_type: DataType) -> Self {
+        ValidatorKey::DataType(data_type)
+    }
+}
+
+impl From<&str> for ValidatorKey {
+    fn from(extension_name: &str) -> Self {
+        ValidatorKey::Extension(extension_name.to_string())
+    }
+}
+
+static VALUE_VALIDATORS: RwLock<Vec<(ValidatorKey, ValueValidator)>> = RwLock::new(Vec::new());
+
+/// Registers a global validator. Multiple validators can be registered for the same key.
+pub fn register_validator<K: Into<ValidatorKey>, F: Fn(&PropertyType, &Value) -> Result<(), String> + Send + Sync + 'static>(key: K, validator: F) {
+    VALUE_VALIDATORS.write().unwrap().push((key.into(), Arc::new(validator)));
+}
+
+/// Unregisters the global validators of the given key.
+pub fn unregister_validators<K: Into<ValidatorKey>>(key: K) {
+    let key = key.into();
+    VALUE_VALIDATORS.write().unwrap().retain(|(k, _)| *k != key);
+}
+
+/// Unregisters all global validators.
+pub fn clear_validators() {
+    VALUE_VALIDATORS.write().unwrap().clear();
+}
+
+/// Validates the value of a property of the given property type. The value must be a valid
+/// representation of the data type and must be accepted by all validators which apply to
+/// the property type.
+pub fn validate_value(property_type: &PropertyType, value: &Value) -> Result<(), ValidationError> {
+    if !property_type.data_type.is_valid(value) {
+        return Err(ValidationError::InvalidDataType {
+            property_name: property_type.name.clone(),
+            expected: property_type.data_type,
+        });
+    }
+    let validators: Vec<ValueValidator> = VALUE_VALIDATORS
+        .read()
+        .unwrap()
+        .iter()
+        .filter(|(key, _)| key.applies_to(property_type))
+        .map(|(_, validator)| validator.clone())
+        .collect();
+    for validator in validators {
+        validator(property_type, value).map_err(|reason| ValidationError::InvalidValue {
+            property_name: property_type.name.clone(),
+            reason,
+        })?;
+    }
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::channel::mpsc::{unbounded, UnboundedReceiver};
+use futures::Stream;
+use serde_json::Value;
+
+use crate::{generate_id, PrioritizedObservers, ReactivePropertyInstance};
+
+/// Asynchronous stream of the values of a reactive property.
+///
+/// The stream receives every value which is sent down the reactive stream of
+/// the property. The observer is removed when the stream is dropped.
+pub struct PropertyValueStream {
+    /// The handle id of the observer.
+    handle_id: u128,
+
+    /// The observers of the property.
+    observers: PrioritizedObservers,
+
+    /// Receives the values.
+    receiver: UnboundedReceiver<Value>,
+}
+
+impl Stream for PropertyValueStream {
+    type Item = Value;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
+
+impl Drop for PropertyValueStream {
+    fn drop(&mut self) {
+        self.observers.remove(self.handle_id);
+    }
+}
+
+impl ReactivePropertyInstance {
+    /// Returns an asynchronous stream of the values of this property.
+    pub fn as_stream(&self) -> PropertyValueStream {
+        let (sender, receiver) = unbounded();
+        let handle_id = generate_id().as_u128();
+        self.observe_with_handle(
+            move |value: &Value| {
+                let _ = sender.unbounded_send(value.clone());
+            },
+            handle_id,
+        );
+        PropertyValueStream {
+            handle_id,
+            observers: self.prioritized_observers.clone(),
+            receiver,
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use serde_json::Value;
+use tokio::sync::watch;
+
+use crate::{generate_id, ReactivePropertyInstance};
+
+impl ReactivePropertyInstance {
+    /// Returns a receiver which is kept in sync with the value of this property.
+    ///
+    /// The receiver initially holds the current value. Async tasks can await
+    /// changes using `changed()`. The receiver is kept in sync as long as it
+    /// exists; the observer is removed with the next value after all receivers
+    /// have been dropped.
+    pub fn watch(&self) -> watch::Receiver<Value> {
+        let (sender, receiver) = watch::channel(self.get());
+        let handle_id = generate_id().as_u128();
+        let observers = self.prioritized_observers.downgrade();
+        self.observe_with_handle(
+            move |value: &Value| {
+                if sender.send(value.clone()).is_err() {
+                    observers.remove(handle_id);
+                }
+            },
+            handle_id,
+        );
+        receiver
+    }
+}
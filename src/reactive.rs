@@ -0,0 +1,52 @@
+//! The reactive instances and the propagation of the values of their properties.
+
+pub use crate::behaviour_dependency_graph::*;
+pub use crate::behaviour_state_machine::*;
+pub use crate::composition_event::*;
+pub use crate::entity_replacement::*;
+pub use crate::factory_registry::*;
+pub use crate::instance_description::*;
+pub use crate::instance_event::*;
+#[cfg(feature = "futures")]
+pub use crate::instance_event_stream::*;
+pub use crate::instance_handle::*;
+pub use crate::instance_observers::*;
+pub use crate::instant::*;
+pub use crate::observer_guard::*;
+#[cfg(feature = "rayon")]
+pub use crate::parallel_tick::*;
+pub use crate::prioritized_observers::*;
+pub use crate::propagation_audit::*;
+pub use crate::propagation_suspension::*;
+pub use crate::propagation_tracer::*;
+pub use crate::property_conflation::*;
+pub use crate::property_dependency_graph::*;
+pub use crate::property_expression::*;
+pub use crate::property_flags::*;
+pub use crate::property_history::*;
+pub use crate::property_instance_accessor::*;
+pub use crate::property_interning::*;
+#[cfg(feature = "jsonpath")]
+pub use crate::property_jsonpath::*;
+#[cfg(feature = "metrics")]
+pub use crate::property_metrics::*;
+pub use crate::property_observer::*;
+pub use crate::property_router::*;
+pub use crate::property_statistics::*;
+#[cfg(feature = "time")]
+pub use crate::property_time::*;
+pub use crate::property_transaction::*;
+pub use crate::property_validator::*;
+#[cfg(feature = "futures")]
+pub use crate::property_value_stream::*;
+pub use crate::reactive_entity_instance::*;
+pub use crate::reactive_instance::*;
+pub use crate::reactive_property_container::*;
+pub use crate::reactive_property_instance::*;
+pub use crate::reactive_relation_instance::*;
+pub use crate::relation_rewiring::*;
+pub use crate::revision::*;
+pub use crate::strict_property_mode::*;
+pub use crate::tick_profiler::*;
+pub use crate::topic_mapping::*;
+pub use crate::type_enforcement::*;
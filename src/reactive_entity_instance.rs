@@ -1,75 +1,328 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
 use dashmap::DashMap;
 use dashmap::DashSet;
+#[cfg(feature = "graph-db")]
 use indradb::VertexProperties;
-use serde_json::Map;
 use serde_json::Value;
 use uuid::Uuid;
 
+use crate::generate_id;
+use crate::get_factory_registry;
+use crate::reactive_property_container::share_revision;
+use crate::BehaviourName;
+use crate::BehaviourStateError;
+use crate::BehaviourStateMachine;
+use crate::Component;
+use crate::ComponentName;
 use crate::EntityInstance;
-use crate::PropertyInstanceGetter;
-use crate::PropertyInstanceSetter;
+use crate::EntityType;
+use crate::EntityTypeName;
+use crate::GlobalIdGenerator;
+use crate::IdGenerator;
+#[cfg(feature = "graph-db")]
+use crate::InstanceConversionError;
+use crate::InstanceDescription;
+use crate::InstanceEvent;
+use crate::InstanceObservers;
+use crate::ReactivePropertyContainer;
 use crate::ReactivePropertyInstance;
+use crate::Revision;
+use crate::RevisionError;
+use crate::StrictPropertyMode;
+use crate::UnknownPropertyPolicy;
+use crate::{suspend_instance_propagation, PropagationGuard, SuspensionMode};
+use crate::{CompositionChange, CompositionEvent, CompositionTarget};
 
 pub struct ReactiveEntityInstance {
-    pub type_name: String,
+    pub type_name: EntityTypeName,
 
     pub id: Uuid,
 
-    pub description: String,
+    pub description: InstanceDescription,
 
-    pub properties: DashMap<String, ReactivePropertyInstance>,
+    /// The reactive properties keyed by their interned names. Looking up a property takes the
+    /// lock of the shard of the map which contains the name, adding and removing properties
+    /// write-locks the shard. The value of a property is guarded by the lock of the property
+    /// instance, so setting a value doesn't block readers of other properties.
+    pub properties: DashMap<Arc<str>, ReactivePropertyInstance>,
 
     /// The names of the components which are applied on this entity instance.
-    pub components: DashSet<String>,
+    pub components: DashSet<ComponentName>,
+
+    /// The labels of this entity instance, e.g. `debug` or `level:3`.
+    pub labels: DashSet<String>,
 
     /// The names of the behaviours which are applied on this entity instance.
-    pub behaviours: DashSet<String>,
+    pub behaviours: DashSet<BehaviourName>,
+
+    /// The state machines of the behaviours which have a lifecycle.
+    pub behaviour_states: DashMap<BehaviourName, BehaviourStateMachine>,
+
+    /// Handling of values which are set on properties which don't exist.
+    pub strict_mode: StrictPropertyMode,
+
+    /// The revision is bumped on every mutation of this entity instance.
+    pub revision: Revision,
+
+    /// The observers of all properties and the event observers of this entity instance.
+    pub observers: Arc<InstanceObservers>,
 }
 
 impl ReactiveEntityInstance {
-    pub fn tick(&self) {
-        for property_instance in &self.properties {
-            property_instance.tick();
+    /// Constructs a new entity instance of the given entity type. The properties of the entity type
+    /// and of its components are created with the default values of their data types. Properties of
+    /// the entity type override properties of the components with the same name. Components which
+    /// are not in the given component definitions have no properties. Finally the hooks of the
+    /// factory registry are invoked.
+    pub fn new_from_type(entity_type: &EntityType, components: &[Component]) -> ReactiveEntityInstance {
+        ReactiveEntityInstance::new_from_type_with_id_generator(entity_type, components, &GlobalIdGenerator::default())
+    }
+
+    /// Constructs a new entity instance of the given entity type. The id is generated by the given
+    /// id generator.
+    pub fn new_from_type_with_id_generator(entity_type: &EntityType, components: &[Component], id_generator: &dyn IdGenerator) -> ReactiveEntityInstance {
+        let id = id_generator.generate();
+        let properties = DashMap::new();
+        let property_types = components
+            .iter()
+            .filter(|component| entity_type.components.contains(&component.name))
+            .flat_map(|component| component.properties.iter())
+            .chain(entity_type.properties.iter());
+        for property_type in property_types {
+            let property_instance = ReactivePropertyInstance::from_property_type(id, property_type);
+            properties.insert(property_instance.name.clone(), property_instance);
         }
+        let entity_instance = ReactiveEntityInstance {
+            type_name: entity_type.name.clone(),
+            id,
+            description: InstanceDescription::default(),
+            properties,
+            components: entity_type.components.iter().cloned().collect(),
+            labels: DashSet::new(),
+            behaviours: DashSet::new(),
+            behaviour_states: DashMap::new(),
+            strict_mode: StrictPropertyMode::default(),
+            revision: Revision::default(),
+            observers: Arc::default(),
+        };
+        share_revision(&entity_instance.properties, &entity_instance.revision);
+        get_factory_registry().apply_entity_hooks(&entity_instance);
+        entity_instance
+    }
+
+    /// Creates an independent copy of this entity instance with the given id. The current property
+    /// values, the configuration of the properties and the components are copied. The observers,
+    /// the behaviours and the revision are not copied, so behaviours have to be applied again.
+    pub fn duplicate(&self, id: Uuid) -> ReactiveEntityInstance {
+        let properties = self
+            .properties
+            .iter()
+            .map(|property_instance| (property_instance.key().clone(), property_instance.duplicate(id)))
+            .collect();
+        let entity_instance = ReactiveEntityInstance {
+            type_name: self.type_name.clone(),
+            id,
+            description: self.description.clone(),
+            properties,
+            components: self.components.clone(),
+            labels: self.labels.clone(),
+            behaviours: DashSet::new(),
+            behaviour_states: DashMap::new(),
+            strict_mode: StrictPropertyMode::new(self.strict_mode.get()),
+            revision: Revision::default(),
+            observers: Arc::default(),
+        };
+        share_revision(&entity_instance.properties, &entity_instance.revision);
+        entity_instance
+    }
+
+    /// Switches how values are handled which are set on properties which don't exist.
+    pub fn set_unknown_property_policy(&self, policy: UnknownPropertyPolicy) {
+        self.strict_mode.set(policy);
     }
 
-    pub fn add_property<S: Into<String>>(&self, name: S, value: Value) {
-        let name = name.into();
-        if !self.properties.contains_key(name.as_str()) {
-            let property_instance = ReactivePropertyInstance::new(self.id, name.clone(), value);
-            self.properties.insert(name, property_instance);
+    /// Suspends the propagation of the properties of this entity instance as long as the guard exists.
+    pub fn suspend_propagation(&self, mode: SuspensionMode) -> PropagationGuard {
+        suspend_instance_propagation(vec![self.id], mode)
+    }
+
+    /// Returns the revision of this entity instance.
+    pub fn revision(&self) -> u64 {
+        self.revision.get()
+    }
+
+    /// Sets the value of the property, if the revision equals the expected revision. The revision
+    /// is compared and bumped while the value of the property is locked. Returns the new revision.
+    pub fn set_if_revision<S: AsRef<str>>(&self, property_name: S, value: Value, expected_revision: u64) -> Result<u64, RevisionError> {
+        let instance = self
+            .properties
+            .get(property_name.as_ref())
+            .ok_or_else(|| RevisionError::MissingProperty(property_name.as_ref().to_string()))?;
+        instance.set_if_revision(value, expected_revision)
+    }
+
+    /// Returns true, if both are the same reactive entity instance and not only equal.
+    pub fn same_instance(self: &Arc<Self>, other: &Arc<ReactiveEntityInstance>) -> bool {
+        Arc::ptr_eq(self, other)
+    }
+
+    /// Changes the description of this entity instance and notifies the observers of the description.
+    pub fn set_description<S: Into<String>>(&self, description: S) {
+        self.description.set(description);
+        self.revision.bump();
+    }
+
+    /// Adds an event property which queues the emitted payloads.
+    pub fn add_event_property<S: AsRef<str>>(&self, name: S) {
+        if !self.properties.contains_key(name.as_ref()) {
+            let property_instance = ReactivePropertyInstance {
+                revision: self.revision.clone(),
+                ..ReactivePropertyInstance::new_event(self.id, name)
+            };
+            let (name, value) = (property_instance.name.clone(), property_instance.get());
+            self.properties.insert(name.clone(), property_instance);
+            // The observers are subscribed through a shared copy, so the map isn't locked while subscribing
+            let shared = self.properties.get(&name).map(|property_instance| property_instance.share());
+            if let Some(property_instance) = shared {
+                self.observers.attach(&property_instance);
+            }
+            self.observers.notify_property_added(&name, &value);
+            self.revision.bump();
         }
     }
 
-    pub fn add_component<S: Into<String>>(&self, component: S) {
-        self.components.insert(component.into());
+    /// Emits the payload on the event property with the given name.
+    pub fn emit<S: AsRef<str>>(&self, property_name: S, payload: Value) {
+        if let Some(property_instance) = self.properties.get(property_name.as_ref()) {
+            property_instance.emit(payload);
+        }
+    }
+
+    /// Removes and returns the queued payloads of the event property with the given name.
+    pub fn drain_events<S: AsRef<str>>(&self, property_name: S) -> Vec<Value> {
+        self.properties
+            .get(property_name.as_ref())
+            .map(|property_instance| property_instance.drain_events())
+            .unwrap_or_default()
+    }
+
+    /// Observes all properties of this entity instance, including the properties which are added
+    /// later. Returns the handle id of the observer.
+    pub fn observe_all<F: Fn(&str, &Value) + Send + Sync + 'static>(&self, observer: F) -> u128 {
+        let handle_id = generate_id().as_u128();
+        self.observers.observe_properties(&self.properties, handle_id, Arc::new(observer));
+        handle_id
+    }
+
+    /// Removes the observer of all properties with the given handle id.
+    pub fn remove_all_observer(&self, handle_id: u128) {
+        self.observers.remove(handle_id);
+    }
+
+    /// Observes the changed and added properties and the changes of the composition of this entity
+    /// instance. Returns the handle id of the observer.
+    pub fn observe_events<F: Fn(&InstanceEvent) + Send + Sync + 'static>(&self, observer: F) -> u128 {
+        let handle_id = generate_id().as_u128();
+        self.observers.observe_events(&self.properties, handle_id, Arc::new(observer));
+        handle_id
     }
 
-    pub fn remove_component<S: Into<String>>(&self, component: S) {
-        self.components.remove(component.into().as_str());
+    /// Removes the event observer with the given handle id.
+    pub fn remove_event_observer(&self, handle_id: u128) {
+        self.observers.remove(handle_id);
+    }
+
+    /// Observes the components and behaviours which are added to or removed from this entity instance.
+    pub fn observe_composition<F: Fn(&CompositionEvent) + Send + Sync + 'static>(&self, handle_id: u128, observer: F) {
+        self.observers.observe_composition(handle_id, Arc::new(observer));
+    }
+
+    /// Removes the composition observer with the given handle id.
+    pub fn remove_composition_observer(&self, handle_id: u128) {
+        self.observers.remove(handle_id);
+    }
+
+    pub fn add_component<C: Into<ComponentName>>(&self, component: C) {
+        let component = component.into();
+        if self.components.insert(component.clone()) {
+            self.observers
+                .notify_composition(|| CompositionTarget::Entity(self.id), CompositionChange::ComponentAdded, component.as_str());
+        }
+        self.revision.bump();
+    }
+
+    pub fn remove_component<C: Into<ComponentName>>(&self, component: C) {
+        let component = component.into();
+        if self.components.remove(component.as_str()).is_some() {
+            self.observers
+                .notify_composition(|| CompositionTarget::Entity(self.id), CompositionChange::ComponentRemoved, component.as_str());
+        }
+        self.revision.bump();
     }
 
     /// Returns true, if the entity instance is composed with the given component.
-    pub fn is_a<S: Into<String>>(&self, component: S) -> bool {
+    pub fn is_a<C: Into<ComponentName>>(&self, component: C) -> bool {
         self.components.contains(component.into().as_str())
     }
 
-    pub fn add_behaviour<S: Into<String>>(&self, behaviour: S) {
-        self.behaviours.insert(behaviour.into());
+    pub fn add_behaviour<B: Into<BehaviourName>>(&self, behaviour: B) {
+        let behaviour = behaviour.into();
+        if self.behaviours.insert(behaviour.clone()) {
+            self.observers
+                .notify_composition(|| CompositionTarget::Entity(self.id), CompositionChange::BehaviourAdded, behaviour.as_str());
+        }
+        self.revision.bump();
     }
 
-    pub fn remove_behaviour<S: Into<String>>(&self, behaviour: S) {
-        self.behaviours.remove(behaviour.into().as_str());
+    pub fn remove_behaviour<B: Into<BehaviourName>>(&self, behaviour: B) {
+        let behaviour = behaviour.into();
+        if self.behaviours.remove(behaviour.as_str()).is_some() {
+            self.observers
+                .notify_composition(|| CompositionTarget::Entity(self.id), CompositionChange::BehaviourRemoved, behaviour.as_str());
+        }
+        self.behaviour_states.remove(behaviour.as_str());
+        self.revision.bump();
     }
 
     /// Returns true, if the entity instance behaves as the given behaviour.
-    pub fn behaves_as<S: Into<String>>(&self, behaviour: S) -> bool {
+    pub fn behaves_as<B: Into<BehaviourName>>(&self, behaviour: B) -> bool {
         self.behaviours.contains(behaviour.into().as_str())
     }
+
+    /// Attaches the state machine to the behaviour with the given name.
+    pub fn set_behaviour_state_machine<B: Into<BehaviourName>>(&self, behaviour: B, state_machine: BehaviourStateMachine) {
+        self.behaviour_states.insert(behaviour.into(), state_machine);
+        self.revision.bump();
+    }
+
+    /// Returns the state machine of the behaviour with the given name.
+    pub fn get_behaviour_state_machine<S: AsRef<str>>(&self, behaviour: S) -> Option<BehaviourStateMachine> {
+        self.behaviour_states.get(behaviour.as_ref()).map(|state_machine| state_machine.clone())
+    }
+
+    /// Returns the current state of the behaviour with the given name.
+    pub fn get_behaviour_state<S: AsRef<str>>(&self, behaviour: S) -> Option<String> {
+        self.behaviour_states
+            .get(behaviour.as_ref())
+            .map(|state_machine| state_machine.get_state().to_string())
+    }
+
+    /// Changes the state of the behaviour with the given name.
+    pub fn transition_behaviour<B: AsRef<str>, S: Into<String>>(&self, behaviour: B, state: S) -> Result<(), BehaviourStateError> {
+        let mut state_machine = self
+            .behaviour_states
+            .get_mut(behaviour.as_ref())
+            .ok_or_else(|| BehaviourStateError::MissingStateMachine(behaviour.as_ref().to_string()))?;
+        state_machine.transition_to(state)?;
+        self.revision.bump();
+        Ok(())
+    }
 }
 
+#[cfg(feature = "graph-db")]
 impl From<VertexProperties> for ReactiveEntityInstance {
     fn from(properties: VertexProperties) -> Self {
         let id = properties.vertex.id;
@@ -77,104 +330,148 @@ impl From<VertexProperties> for ReactiveEntityInstance {
             .props
             .iter()
             .map(|named_property| {
-                (
-                    named_property.name.to_string(),
-                    ReactivePropertyInstance::new(id, named_property.name.to_string(), named_property.value.clone()),
-                )
+                let property_instance = ReactivePropertyInstance::new(id, named_property.name.as_str(), named_property.value.clone());
+                (property_instance.name.clone(), property_instance)
             })
             .collect();
-        ReactiveEntityInstance {
-            type_name: properties.vertex.t.to_string(),
+        let entity_instance = ReactiveEntityInstance {
+            type_name: EntityTypeName::new(properties.vertex.t.to_string()),
             id,
-            description: String::new(),
+            description: InstanceDescription::default(),
             properties: instance_properties,
             components: DashSet::new(),
+            labels: DashSet::new(),
             behaviours: DashSet::new(),
-        }
+            behaviour_states: DashMap::new(),
+            strict_mode: StrictPropertyMode::default(),
+            revision: Revision::default(),
+            observers: Arc::default(),
+        };
+        share_revision(&entity_instance.properties, &entity_instance.revision);
+        entity_instance
     }
 }
 
+/// Fails, if the type name or a property name is invalid or if a property occurs multiple times.
+#[cfg(feature = "graph-db")]
+impl TryFrom<&VertexProperties> for ReactiveEntityInstance {
+    type Error = InstanceConversionError;
+
+    fn try_from(properties: &VertexProperties) -> Result<Self, InstanceConversionError> {
+        EntityInstance::try_from(properties).map(ReactiveEntityInstance::from)
+    }
+}
+
+/// The mutability and the other settings of the property types are not part of the entity
+/// instance, so the properties are mutable. Use `new_from_type` to create the properties from
+/// the property types.
 impl From<EntityInstance> for ReactiveEntityInstance {
     fn from(instance: EntityInstance) -> Self {
         let properties = instance
             .properties
             .iter()
-            .map(|(name, value)| (name.clone(), ReactivePropertyInstance::new(instance.id, name.clone(), value.clone())))
+            .map(|(name, value)| {
+                let property_instance = ReactivePropertyInstance::new(instance.id, name, value.clone());
+                (property_instance.name.clone(), property_instance)
+            })
             .collect();
-        ReactiveEntityInstance {
+        let entity_instance = ReactiveEntityInstance {
             type_name: instance.type_name.clone(),
             id: instance.id,
-            description: instance.description,
+            description: instance.description.into(),
             properties,
-            components: DashSet::new(),
+            components: instance.components.into_iter().collect(),
+            labels: instance.labels.into_iter().collect(),
             behaviours: DashSet::new(),
-        }
+            behaviour_states: DashMap::new(),
+            strict_mode: StrictPropertyMode::default(),
+            revision: Revision::default(),
+            observers: Arc::default(),
+        };
+        share_revision(&entity_instance.properties, &entity_instance.revision);
+        entity_instance
     }
 }
 
 impl From<Arc<ReactiveEntityInstance>> for EntityInstance {
     fn from(instance: Arc<ReactiveEntityInstance>) -> Self {
-        let properties = instance
+        let mut properties: Vec<(String, Value)> = instance
             .properties
             .iter()
-            .map(|property_instance| (property_instance.key().clone(), property_instance.get()))
+            .map(|property_instance| (property_instance.key().to_string(), property_instance.get()))
             .collect();
+        // The reactive properties are unordered, so the properties are sorted by name
+        properties.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let mut components: Vec<ComponentName> = instance.components.iter().map(|component| component.clone()).collect();
+        components.sort();
+        let mut labels: Vec<String> = instance.labels.iter().map(|label| label.clone()).collect();
+        labels.sort();
         EntityInstance {
             type_name: instance.type_name.clone(),
             id: instance.id,
-            description: instance.description.clone(),
-            properties,
+            description: instance.description.get(),
+            properties: properties.into_iter().collect(),
+            components,
+            labels,
+            behaviours: Vec::new(),
         }
     }
 }
 
-impl PropertyInstanceGetter for ReactiveEntityInstance {
-    fn get<S: Into<String>>(&self, property_name: S) -> Option<Value> {
-        self.properties.get(&property_name.into()).map(|p| p.get())
-    }
-
-    fn as_bool<S: Into<String>>(&self, property_name: S) -> Option<bool> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_bool())
+impl EntityInstance {
+    /// Converts the reactive entity instance and persists the names of the applied behaviours,
+    /// so that they can be re-attached after a restart.
+    pub fn from_reactive_with_behaviours(instance: Arc<ReactiveEntityInstance>) -> EntityInstance {
+        let mut behaviours: Vec<BehaviourName> = instance.behaviours.iter().map(|behaviour| behaviour.clone()).collect();
+        behaviours.sort();
+        EntityInstance {
+            behaviours,
+            ..EntityInstance::from(instance)
+        }
     }
+}
 
-    fn as_u64<S: Into<String>>(&self, property_name: S) -> Option<u64> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_u64())
+impl ReactivePropertyContainer for ReactiveEntityInstance {
+    fn properties(&self) -> &DashMap<Arc<str>, ReactivePropertyInstance> {
+        &self.properties
     }
 
-    fn as_i64<S: Into<String>>(&self, property_name: S) -> Option<i64> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_i64())
+    fn revision_counter(&self) -> &Revision {
+        &self.revision
     }
 
-    fn as_f64<S: Into<String>>(&self, property_name: S) -> Option<f64> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_f64())
+    fn unknown_property_policy(&self) -> UnknownPropertyPolicy {
+        self.strict_mode.get()
     }
 
-    fn as_string<S: Into<String>>(&self, property_name: S) -> Option<String> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_string())
+    fn new_property_id(&self) -> Uuid {
+        self.id
     }
 
-    fn as_array<S: Into<String>>(&self, property_name: S) -> Option<Vec<Value>> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_array())
+    fn observers(&self) -> &InstanceObservers {
+        &self.observers
     }
+}
 
-    fn as_object<S: Into<String>>(&self, property_name: S) -> Option<Map<String, Value>> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_object())
+/// Reactive entity instances are equal, if the ids are equal.
+impl PartialEq for ReactiveEntityInstance {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
     }
 }
 
-impl PropertyInstanceSetter for ReactiveEntityInstance {
-    fn set<S: Into<String>>(&self, property_name: S, value: Value) {
-        if let Some(instance) = self.properties.get(&property_name.into()) {
-            instance.set(value);
-        }
-    }
+impl Eq for ReactiveEntityInstance {}
 
-    fn set_no_propagate<S: Into<String>>(&self, property_name: S, value: Value) {
-        if let Some(instance) = self.properties.get(&property_name.into()) {
-            instance.set_no_propagate(value);
-        }
+/// The hash of a reactive entity instance is the hash of its id.
+impl Hash for ReactiveEntityInstance {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
     }
+}
 
-    // TODO: fn set(&self, Map<String, Value>
-    // TODO: Set values transactional: first set all values internally, then send all affected streams
+/// Formats the entity instance as `type_name(id)`.
+impl fmt::Display for ReactiveEntityInstance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}({})", self.type_name, self.id)
+    }
 }
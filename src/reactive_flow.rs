@@ -1,30 +1,46 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::convert::TryFrom;
 use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, RwLock};
 
-use indradb::EdgeKey;
 use serde_json::{Map, Value};
 use uuid::Uuid;
 
-use crate::{Flow, ReactiveEntityInstance, ReactiveRelationInstance};
-use crate::{PropertyInstanceGetter, PropertyInstanceSetter};
+use crate::EdgeKey;
+use crate::ExtensionError;
+use crate::{evaluate_constraints, ConstraintViolation, GraphConstraint};
+use crate::{migrate_entity_instance, rewire_inbound, rewire_outbound, RelationRewiring, ReplacementPolicy};
+use crate::{profile, ProfileReport, Projection, ProjectionTable};
+use crate::{suspend_instance_propagation, PropagationGuard, SuspensionMode};
+use crate::{
+    EntityInstance, Flow, FlowLayer, FlowLoadingProgress, FlowPropertyOverride, FlowVariable, ReactiveEntityInstance, ReactivePropertyInstance,
+    ReactiveRelationInstance, RelationInstance, RelationInstanceKey,
+};
+use crate::{GlobalIdGenerator, IdGenerator};
+use crate::{PropertyInstanceGetter, PropertyInstanceSetter, ReactivePropertyContainer};
 
 #[derive(Debug)]
 pub enum ReactiveFlowConstructionError {
     MissingWrapperInstance,
     MissingOutboundEntityInstance(Uuid),
     MissingInboundEntityInstance(Uuid),
+    Cancelled,
+
+    /// The inner flow extension of the entity type doesn't contain a flow.
+    InvalidInnerFlow(ExtensionError),
 }
 
 impl fmt::Display for ReactiveFlowConstructionError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match *self {
+        match self {
             ReactiveFlowConstructionError::MissingWrapperInstance => {
                 write!(f, "Missing the wrapper entity instance. Check if an entity instance exists with the same id as the flow id")
             }
             ReactiveFlowConstructionError::MissingOutboundEntityInstance(id) => write!(f, "The outbound entity instance {} cannot be found", id),
             ReactiveFlowConstructionError::MissingInboundEntityInstance(id) => write!(f, "The inbound entity instance {} cannot be found", id),
+            ReactiveFlowConstructionError::Cancelled => write!(f, "The construction of the flow has been cancelled"),
+            ReactiveFlowConstructionError::InvalidInnerFlow(error) => write!(f, "The inner flow is invalid: {}", error),
         }
     }
 }
@@ -36,6 +52,9 @@ pub struct ReactiveFlow {
     /// The entity type of the flow.
     pub type_name: String,
 
+    /// The name of the flow.
+    pub name: String,
+
     /// The flow contains entity instances. The entity instance may also
     /// be contained in other flows.
     pub entity_instances: RwLock<HashMap<Uuid, Arc<ReactiveEntityInstance>>>,
@@ -55,16 +74,43 @@ pub struct ReactiveFlow {
 
     /// List of relations that has been removed since creation of the flow.
     pub relations_removed: RwLock<Vec<EdgeKey>>,
+
+    /// The runtime overrides of properties of the entity instances of the flow.
+    pub overrides: RwLock<HashMap<(Uuid, String), FlowPropertyOverride>>,
+
+    /// The values defined by the flow which are currently overridden. Values which are set while
+    /// the override is active replace the value defined by the flow.
+    pub overridden_values: Arc<RwLock<HashMap<(Uuid, String), Value>>>,
+
+    /// The named inputs and outputs of the flow.
+    pub variables: RwLock<Vec<FlowVariable>>,
+
+    /// The layers of the flow.
+    pub layers: Vec<FlowLayer>,
+
+    /// The entity instances of disabled layers, which are not instantiated. They are kept, so
+    /// that the flow can be converted back without losing content.
+    pub disabled_entity_instances: Vec<EntityInstance>,
+
+    /// The relation instances of disabled layers, which are not instantiated.
+    pub disabled_relation_instances: Vec<RelationInstance>,
+
+    /// The versions of the entity types and relation types at the time the flow was stored.
+    pub type_versions: HashMap<String, u32>,
+
+    /// True, if the flow has been destroyed.
+    pub destroyed: AtomicBool,
 }
 
 impl ReactiveFlow {
     pub fn new(wrapper_entity_instance: Arc<ReactiveEntityInstance>) -> ReactiveFlow {
-        let type_name = wrapper_entity_instance.type_name.clone();
+        let type_name = wrapper_entity_instance.type_name.to_string();
         let mut entity_instances = HashMap::new();
         entity_instances.insert(wrapper_entity_instance.id, wrapper_entity_instance.clone());
         ReactiveFlow {
             id: wrapper_entity_instance.id,
             type_name,
+            name: String::new(),
             entity_instances: RwLock::new(entity_instances),
             relation_instances: RwLock::new(HashMap::new()),
             // wrapper,
@@ -72,6 +118,14 @@ impl ReactiveFlow {
             entities_removed: RwLock::new(Vec::new()),
             relations_added: RwLock::new(Vec::new()),
             relations_removed: RwLock::new(Vec::new()),
+            overrides: RwLock::new(HashMap::new()),
+            overridden_values: Arc::new(RwLock::new(HashMap::new())),
+            variables: RwLock::new(Vec::new()),
+            layers: Vec::new(),
+            disabled_entity_instances: Vec::new(),
+            disabled_relation_instances: Vec::new(),
+            type_versions: HashMap::new(),
+            destroyed: AtomicBool::new(false),
         }
     }
 
@@ -106,10 +160,7 @@ impl ReactiveFlow {
     }
 
     pub fn has_relation(&self, relation_instance: Arc<ReactiveRelationInstance>) -> bool {
-        if let Some(edge_key) = relation_instance.get_key() {
-            return self.relation_instances.read().unwrap().contains_key(&edge_key);
-        }
-        false
+        self.relation_instances.read().unwrap().contains_key(relation_instance.key())
     }
 
     pub fn has_relation_by_key(&self, edge_key: EdgeKey) -> bool {
@@ -122,11 +173,10 @@ impl ReactiveFlow {
     }
 
     pub fn add_relation(&self, relation_instance: Arc<ReactiveRelationInstance>) {
-        if let Some(edge_key) = relation_instance.get_key() {
-            if !self.has_relation_by_key(edge_key.clone()) {
-                self.relation_instances.write().unwrap().insert(edge_key.clone(), relation_instance.clone());
-                self.relations_added.write().unwrap().push(edge_key);
-            }
+        let edge_key = relation_instance.key().clone();
+        if !self.has_relation_by_key(edge_key.clone()) {
+            self.relation_instances.write().unwrap().insert(edge_key.clone(), relation_instance.clone());
+            self.relations_added.write().unwrap().push(edge_key);
         }
     }
 
@@ -135,28 +185,476 @@ impl ReactiveFlow {
         self.relations_removed.write().unwrap().push(edge_key);
     }
 
+    /// Moves the relation instances of the flow which are outgoing from the old entity instance
+    /// to the new entity instance. The old edge keys are recorded as removed and the new edge
+    /// keys as added. Returns the rewired relation instances.
+    pub fn rewire_outbound(&self, old_entity: &ReactiveEntityInstance, new_entity: Arc<ReactiveEntityInstance>) -> Vec<Arc<ReactiveRelationInstance>> {
+        let relations: Vec<Arc<ReactiveRelationInstance>> = self.relation_instances.read().unwrap().values().cloned().collect();
+        self.apply_rewirings(rewire_outbound(&relations, old_entity, new_entity))
+    }
+
+    /// Moves the relation instances of the flow which are incoming to the old entity instance
+    /// to the new entity instance. The old edge keys are recorded as removed and the new edge
+    /// keys as added. Returns the rewired relation instances.
+    pub fn rewire_inbound(&self, old_entity: &ReactiveEntityInstance, new_entity: Arc<ReactiveEntityInstance>) -> Vec<Arc<ReactiveRelationInstance>> {
+        let relations: Vec<Arc<ReactiveRelationInstance>> = self.relation_instances.read().unwrap().values().cloned().collect();
+        self.apply_rewirings(rewire_inbound(&relations, old_entity, new_entity))
+    }
+
+    fn apply_rewirings(&self, rewirings: Vec<RelationRewiring>) -> Vec<Arc<ReactiveRelationInstance>> {
+        rewirings
+            .into_iter()
+            .map(|rewiring| {
+                if let Some(old_key) = rewiring.old_key {
+                    self.remove_relation(old_key);
+                }
+                self.add_relation(rewiring.relation_instance.clone());
+                rewiring.relation_instance
+            })
+            .collect()
+    }
+
+    /// Replaces the entity instance with the new entity instance. The property values, components,
+    /// behaviours, observers and relation instances are migrated according to the policy. The new
+    /// entity instance may have the id of the replaced entity instance. Returns the rewired relation
+    /// instances.
+    pub fn replace_entity(
+        &self,
+        old_entity: Arc<ReactiveEntityInstance>,
+        new_entity: Arc<ReactiveEntityInstance>,
+        policy: &ReplacementPolicy,
+    ) -> Vec<Arc<ReactiveRelationInstance>> {
+        migrate_entity_instance(&old_entity, &new_entity, policy);
+        self.remove_entity(old_entity.id);
+        self.add_entity(new_entity.clone());
+        if !policy.relations {
+            return Vec::new();
+        }
+        let mut rewired = self.rewire_outbound(&old_entity, new_entity.clone());
+        rewired.append(&mut self.rewire_inbound(&old_entity, new_entity));
+        // Relation instances from the entity instance to itself are rewired twice
+        rewired.retain(|relation_instance| {
+            self.get_relation(relation_instance.key().clone())
+                .is_some_and(|current| Arc::ptr_eq(&current, relation_instance))
+        });
+        rewired
+    }
+
+    /// Overrides the value of the property of the entity instance with the given id.
+    ///
+    /// The override takes precedence over the value defined by the flow: values which are set
+    /// while the override is active don't replace the override, but the value defined by the flow.
+    /// The value defined by the flow is restored if the override gets cleared.
+    /// Returns false, if the flow doesn't contain the entity instance or the property.
+    pub fn set_override<S: Into<String>>(&self, id: Uuid, property_name: S, value: Value) -> bool {
+        let property_name = property_name.into();
+        let entity_instance = match self.get_entity(id) {
+            Some(entity_instance) => entity_instance,
+            None => return false,
+        };
+        let property_instance = match entity_instance.properties.get(property_name.as_str()) {
+            Some(property_instance) => property_instance,
+            None => return false,
+        };
+        let key = (id, property_name.clone());
+        self.overridden_values
+            .write()
+            .unwrap()
+            .entry(key.clone())
+            .or_insert_with(|| property_instance.get());
+        self.overrides
+            .write()
+            .unwrap()
+            .insert(key, FlowPropertyOverride::new(id, property_name.clone(), value.clone()));
+        property_instance.remove_write_interceptor();
+        property_instance.set(value);
+        self.intercept_overridden_value(&property_instance, id, property_name);
+        true
+    }
+
+    /// Replaces the overridden value by the values which are set while the override is active.
+    fn intercept_overridden_value(&self, property_instance: &ReactivePropertyInstance, id: Uuid, property_name: String) {
+        let overridden_values = self.overridden_values.clone();
+        let key = (id, property_name);
+        property_instance.set_write_interceptor(move |value: &Value| {
+            if let Some(overridden_value) = overridden_values.write().unwrap().get_mut(&key) {
+                *overridden_value = value.clone();
+            }
+        });
+    }
+
+    /// Returns the override value of the property of the entity instance with the given id.
+    pub fn get_override<S: Into<String>>(&self, id: Uuid, property_name: S) -> Option<Value> {
+        self.overrides
+            .read()
+            .unwrap()
+            .get(&(id, property_name.into()))
+            .map(|property_override| property_override.value.clone())
+    }
+
+    /// Returns true, if the property of the entity instance with the given id is overridden.
+    pub fn has_override<S: Into<String>>(&self, id: Uuid, property_name: S) -> bool {
+        self.overrides.read().unwrap().contains_key(&(id, property_name.into()))
+    }
+
+    /// Clears the override and restores the value defined by the flow.
+    pub fn clear_override<S: Into<String>>(&self, id: Uuid, property_name: S) {
+        let key = (id, property_name.into());
+        self.overrides.write().unwrap().remove(&key);
+        let value = self.overridden_values.write().unwrap().remove(&key);
+        if let Some(entity_instance) = self.get_entity(id) {
+            if let Some(property_instance) = entity_instance.properties.get(key.1.as_str()) {
+                property_instance.remove_write_interceptor();
+                if let Some(value) = value {
+                    property_instance.set(value);
+                }
+            }
+        }
+    }
+
+    /// Clears all overrides and restores the values defined by the flow.
+    pub fn clear_overrides(&self) {
+        let keys: Vec<(Uuid, String)> = self.overrides.read().unwrap().keys().cloned().collect();
+        for (id, property_name) in keys {
+            self.clear_override(id, property_name);
+        }
+    }
+
+    /// Exports the override set separately from the flow.
+    pub fn get_overrides(&self) -> Vec<FlowPropertyOverride> {
+        self.overrides.read().unwrap().values().cloned().collect()
+    }
+
+    /// Applies the given override set.
+    pub fn apply_overrides(&self, overrides: Vec<FlowPropertyOverride>) {
+        for property_override in overrides {
+            self.set_override(property_override.id, property_override.property_name, property_override.value);
+        }
+    }
+
+    /// Declares the variable. Replaces a variable with the same name.
+    pub fn add_variable(&self, variable: FlowVariable) {
+        let mut variables = self.variables.write().unwrap();
+        variables.retain(|other| other.name != variable.name);
+        variables.push(variable);
+    }
+
+    /// Returns the variable with the given name.
+    pub fn get_variable<S: AsRef<str>>(&self, name: S) -> Option<FlowVariable> {
+        self.variables.read().unwrap().iter().find(|variable| variable.name == name.as_ref()).cloned()
+    }
+
+    /// Returns the value of the property the variable with the given name maps to.
+    pub fn get_variable_value<S: AsRef<str>>(&self, name: S) -> Option<Value> {
+        let variable = self.get_variable(name)?;
+        self.get_entity(variable.entity_id)?.get(&variable.property_name)
+    }
+
+    /// Sets the value of the property the input variable with the given name maps to. Returns
+    /// false, if the input variable or its property doesn't exist.
+    pub fn set_variable_value<S: AsRef<str>>(&self, name: S, value: Value) -> bool {
+        let variable = match self.get_variable(name) {
+            Some(variable) if variable.is_input() => variable,
+            _ => return false,
+        };
+        match self.get_entity(variable.entity_id) {
+            Some(entity_instance) if entity_instance.properties.contains_key(variable.property_name.as_str()) => {
+                entity_instance.set(&variable.property_name, value);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Suspends the propagation of the properties of the entity instances and relation instances
+    /// of this flow as long as the guard exists. Instances which are added afterwards are not suspended.
+    pub fn suspend_propagation(&self, mode: SuspensionMode) -> PropagationGuard {
+        let mut ids: Vec<Uuid> = self.entity_instances.read().unwrap().keys().copied().collect();
+        ids.extend(self.relation_instances.read().unwrap().values().map(|relation_instance| relation_instance.id));
+        suspend_instance_propagation(ids, mode)
+    }
+
     pub fn tick(&self) {
         let reader = self.entity_instances.read().unwrap();
         for (_, entity_instance) in reader.iter() {
             entity_instance.tick();
         }
     }
-}
 
-impl From<Arc<ReactiveEntityInstance>> for ReactiveFlow {
-    fn from(wrapper_entity_instance: Arc<ReactiveEntityInstance>) -> Self {
-        ReactiveFlow::new(wrapper_entity_instance)
+    /// Destroys the relation instances and entity instances of the flow, including the wrapper
+    /// entity instance. Instances which are shared with another flow have to be removed from this
+    /// flow before, otherwise they are destroyed too. Destroying the flow again does nothing.
+    pub fn destroy(&self) {
+        if self.destroyed.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        for relation_instance in self.relation_instances.read().unwrap().values() {
+            relation_instance.destroy();
+        }
+        for entity_instance in self.entity_instances.read().unwrap().values() {
+            entity_instance.destroy();
+        }
     }
-}
 
-impl TryFrom<Flow> for ReactiveFlow {
-    type Error = ReactiveFlowConstructionError;
+    /// Returns true, if the flow has been destroyed.
+    pub fn is_destroyed(&self) -> bool {
+        self.destroyed.load(Ordering::SeqCst)
+    }
 
-    fn try_from(flow: Flow) -> Result<Self, ReactiveFlowConstructionError> {
+    /// Ticks all entity instances and relation instances of the flow exactly once.
+    ///
+    /// The entity instances are ticked in the order of the relations, so each entity instance
+    /// is ticked after the entity instances it has inbound relations from. Each relation instance
+    /// is ticked right after its outbound entity instance; relation instances whose outbound
+    /// entity instance isn't part of the flow are ticked first. Entity instances which are ready
+    /// at the same time are ticked in the order of their ids. Cycles are broken at the entity
+    /// instance with the lowest id.
+    pub fn tick_all(&self) {
+        let entity_instances: BTreeMap<Uuid, Arc<ReactiveEntityInstance>> = self
+            .entity_instances
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(id, entity_instance)| (*id, entity_instance.clone()))
+            .collect();
+        let mut relation_instances: Vec<Arc<ReactiveRelationInstance>> = self.relation_instances.read().unwrap().values().cloned().collect();
+        relation_instances.sort_by(|r1, r2| (r1.outbound.id, r1.inbound.id, &r1.type_name).cmp(&(r2.outbound.id, r2.inbound.id, &r2.type_name)));
+        let mut in_degrees: HashMap<Uuid, usize> = entity_instances.keys().map(|id| (*id, 0)).collect();
+        let mut outbound_relations: HashMap<Uuid, Vec<Arc<ReactiveRelationInstance>>> = HashMap::new();
+        for relation_instance in relation_instances {
+            if !entity_instances.contains_key(&relation_instance.outbound.id) {
+                relation_instance.tick();
+                continue;
+            }
+            if relation_instance.outbound.id != relation_instance.inbound.id {
+                if let Some(in_degree) = in_degrees.get_mut(&relation_instance.inbound.id) {
+                    *in_degree += 1;
+                }
+            }
+            outbound_relations.entry(relation_instance.outbound.id).or_default().push(relation_instance);
+        }
+        let mut ready: BTreeSet<Uuid> = in_degrees.iter().filter(|(_, in_degree)| **in_degree == 0).map(|(id, _)| *id).collect();
+        let mut ticked: HashSet<Uuid> = HashSet::new();
+        while ticked.len() < entity_instances.len() {
+            let id = match ready.iter().next().copied() {
+                Some(id) => id,
+                None => match entity_instances.keys().find(|id| !ticked.contains(*id)) {
+                    Some(id) => *id,
+                    None => break,
+                },
+            };
+            ready.remove(&id);
+            if !ticked.insert(id) {
+                continue;
+            }
+            entity_instances[&id].tick();
+            for relation_instance in outbound_relations.remove(&id).unwrap_or_default() {
+                relation_instance.tick();
+                let inbound_id = relation_instance.inbound.id;
+                if let Some(in_degree) = in_degrees.get_mut(&inbound_id) {
+                    if inbound_id != id && *in_degree > 0 {
+                        *in_degree -= 1;
+                        if *in_degree == 0 && !ticked.contains(&inbound_id) {
+                            ready.insert(inbound_id);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Ticks the flow and returns the profile of the propagations.
+    pub fn tick_profiled(&self) -> ProfileReport {
+        profile(|| self.tick())
+    }
+
+    /// Projects the entity instances of this flow into flat records, ordered by id.
+    pub fn project(&self, projection: &Projection) -> ProjectionTable {
+        let mut entity_instances: Vec<Arc<ReactiveEntityInstance>> = self.entity_instances.read().unwrap().values().cloned().collect();
+        entity_instances.sort_by_key(|entity_instance| entity_instance.id);
+        let relation_instances: Vec<Arc<ReactiveRelationInstance>> = self.relation_instances.read().unwrap().values().cloned().collect();
+        projection.project(&entity_instances, &relation_instances)
+    }
+
+    /// Projects the relation instances of this flow into flat records, ordered by outbound id,
+    /// relation type and inbound id.
+    pub fn project_relations(&self, projection: &Projection) -> ProjectionTable {
+        let mut relation_instances: Vec<Arc<ReactiveRelationInstance>> = self.relation_instances.read().unwrap().values().cloned().collect();
+        relation_instances
+            .sort_by(|r1, r2| (r1.outbound.id, r1.type_name.as_str(), r1.inbound.id).cmp(&(r2.outbound.id, r2.type_name.as_str(), r2.inbound.id)));
+        projection.project_relations(&relation_instances)
+    }
+
+    /// Evaluates the constraints over the instances of this flow and returns the violations.
+    pub fn evaluate_constraints(&self, constraints: &[GraphConstraint]) -> Vec<ConstraintViolation> {
+        let mut entity_instances: Vec<Arc<ReactiveEntityInstance>> = self.entity_instances.read().unwrap().values().cloned().collect();
+        entity_instances.sort_by_key(|entity_instance| entity_instance.id);
+        let relation_instances: Vec<Arc<ReactiveRelationInstance>> = self.relation_instances.read().unwrap().values().cloned().collect();
+        evaluate_constraints(constraints, &entity_instances, &relation_instances)
+    }
+
+    /// Creates an independent copy of the flow. All entity instances and relation instances are
+    /// duplicated and get new ids.
+    pub fn duplicate(&self) -> ReactiveFlow {
+        self.duplicate_with_ids(&mut HashMap::new())
+    }
+
+    /// Creates an independent copy of the flow. The new ids are generated by the given id
+    /// generator.
+    pub fn duplicate_with_id_generator(&self, id_generator: &dyn IdGenerator) -> ReactiveFlow {
+        self.duplicate_with_ids_and_generator(&mut HashMap::new(), id_generator)
+    }
+
+    /// Creates an independent copy of the flow. The entity instances get the ids of the given
+    /// mapping from old ids to new ids. Missing ids are generated and added to the mapping.
+    ///
+    /// The relation instances are rebuilt between the duplicated entity instances. Ends which are
+    /// not contained in the flow are kept. The variables and overrides refer to the new ids.
+    pub fn duplicate_with_ids(&self, ids: &mut HashMap<Uuid, Uuid>) -> ReactiveFlow {
+        self.duplicate_with_ids_and_generator(ids, &GlobalIdGenerator::default())
+    }
+
+    fn duplicate_with_ids_and_generator(&self, ids: &mut HashMap<Uuid, Uuid>, id_generator: &dyn IdGenerator) -> ReactiveFlow {
+        let mut entity_instances = HashMap::new();
+        for (id, entity_instance) in self.entity_instances.read().unwrap().iter() {
+            let new_id = *ids.entry(*id).or_insert_with(|| id_generator.generate());
+            entity_instances.insert(new_id, Arc::new(entity_instance.duplicate(new_id)));
+        }
+        for entity_instance in self.disabled_entity_instances.iter() {
+            ids.entry(entity_instance.id).or_insert_with(|| id_generator.generate());
+        }
+        let duplicated_end = |entity_instance: &Arc<ReactiveEntityInstance>| {
+            ids.get(&entity_instance.id)
+                .and_then(|id| entity_instances.get(id))
+                .cloned()
+                .unwrap_or_else(|| entity_instance.clone())
+        };
+        let mut relation_instances = HashMap::new();
+        for relation_instance in self.relation_instances.read().unwrap().values() {
+            let duplicate = relation_instance.duplicate(duplicated_end(&relation_instance.outbound), duplicated_end(&relation_instance.inbound));
+            relation_instances.insert(duplicate.key().clone(), Arc::new(duplicate));
+        }
+        let remap = |id: &Uuid| ids.get(id).cloned().unwrap_or(*id);
+        let overrides = self
+            .overrides
+            .read()
+            .unwrap()
+            .values()
+            .map(|property_override| {
+                let id = remap(&property_override.id);
+                let property_override = FlowPropertyOverride::new(id, property_override.property_name.clone(), property_override.value.clone());
+                ((id, property_override.property_name.clone()), property_override)
+            })
+            .collect();
+        let overridden_values = self
+            .overridden_values
+            .read()
+            .unwrap()
+            .iter()
+            .map(|((id, property_name), value)| ((remap(id), property_name.clone()), value.clone()))
+            .collect();
+        let variables = self
+            .variables
+            .read()
+            .unwrap()
+            .iter()
+            .map(|variable| FlowVariable {
+                entity_id: remap(&variable.entity_id),
+                ..variable.clone()
+            })
+            .collect();
+        let layers = self
+            .layers
+            .iter()
+            .map(|layer| FlowLayer {
+                entity_instances: layer.entity_instances.iter().map(remap).collect(),
+                relation_instances: layer
+                    .relation_instances
+                    .iter()
+                    .map(|key| RelationInstanceKey::new(remap(&key.outbound_id), key.type_name.clone(), remap(&key.inbound_id)))
+                    .collect(),
+                ..layer.clone()
+            })
+            .collect();
+        let disabled_entity_instances = self
+            .disabled_entity_instances
+            .iter()
+            .map(|entity_instance| EntityInstance {
+                id: remap(&entity_instance.id),
+                ..entity_instance.clone()
+            })
+            .collect();
+        let disabled_relation_instances = self
+            .disabled_relation_instances
+            .iter()
+            .map(|relation_instance| RelationInstance {
+                outbound_id: remap(&relation_instance.outbound_id),
+                inbound_id: remap(&relation_instance.inbound_id),
+                ..relation_instance.clone()
+            })
+            .collect();
+        let reactive_flow = ReactiveFlow {
+            id: remap(&self.id),
+            type_name: self.type_name.clone(),
+            name: self.name.clone(),
+            entity_instances: RwLock::new(entity_instances),
+            relation_instances: RwLock::new(relation_instances),
+            entities_added: RwLock::new(Vec::new()),
+            entities_removed: RwLock::new(Vec::new()),
+            relations_added: RwLock::new(Vec::new()),
+            relations_removed: RwLock::new(Vec::new()),
+            overrides: RwLock::new(overrides),
+            overridden_values: Arc::new(RwLock::new(overridden_values)),
+            variables: RwLock::new(variables),
+            layers,
+            disabled_entity_instances,
+            disabled_relation_instances,
+            type_versions: self.type_versions.clone(),
+            destroyed: AtomicBool::new(false),
+        };
+        let keys: Vec<(Uuid, String)> = reactive_flow.overridden_values.read().unwrap().keys().cloned().collect();
+        for (id, property_name) in keys {
+            if let Some(entity_instance) = reactive_flow.get_entity(id) {
+                if let Some(property_instance) = entity_instance.properties.get(property_name.as_str()) {
+                    reactive_flow.intercept_overridden_value(&property_instance, id, property_name);
+                }
+            }
+        }
+        reactive_flow
+    }
+
+    /// Constructs the reactive flow in chunks of the given size. The progress is reported to the
+    /// callback before each chunk and after the construction. The construction is cancelled, if
+    /// the callback returns false.
+    pub fn try_from_with_progress<F: FnMut(&FlowLoadingProgress) -> bool>(
+        flow: Flow,
+        chunk_size: usize,
+        mut callback: F,
+    ) -> Result<ReactiveFlow, ReactiveFlowConstructionError> {
         let flow_id = flow.id;
         let mut entity_instances = HashMap::new();
         let mut wrapper = None;
-        for entity_instance in flow.entity_instances {
+        let (enabled_relation_instances, disabled_relation_instances): (Vec<RelationInstance>, Vec<RelationInstance>) = flow
+            .relation_instances
+            .iter()
+            .cloned()
+            .partition(|relation_instance| flow.is_relation_enabled(relation_instance));
+        let (enabled_entity_instances, disabled_entity_instances): (Vec<EntityInstance>, Vec<EntityInstance>) = flow
+            .entity_instances
+            .iter()
+            .cloned()
+            .partition(|entity_instance| flow.is_entity_enabled(entity_instance.id));
+        let chunk_size = chunk_size.max(1);
+        let mut progress = FlowLoadingProgress {
+            entities_total: enabled_entity_instances.len(),
+            relations_total: enabled_relation_instances.len(),
+            ..FlowLoadingProgress::default()
+        };
+        for entity_instance in enabled_entity_instances {
+            if progress.entities_done.is_multiple_of(chunk_size) && !callback(&progress) {
+                return Err(ReactiveFlowConstructionError::Cancelled);
+            }
+            progress.entities_done += 1;
             let id = entity_instance.id;
             let reactive_entity_instance = Arc::new(ReactiveEntityInstance::from(entity_instance));
             entity_instances.insert(id, reactive_entity_instance.clone());
@@ -168,7 +666,11 @@ impl TryFrom<Flow> for ReactiveFlow {
             return Err(ReactiveFlowConstructionError::MissingWrapperInstance);
         }
         let mut relation_instances = HashMap::new();
-        for relation_instance in flow.relation_instances {
+        for relation_instance in enabled_relation_instances {
+            if progress.relations_done.is_multiple_of(chunk_size) && !callback(&progress) {
+                return Err(ReactiveFlowConstructionError::Cancelled);
+            }
+            progress.relations_done += 1;
             if let Some(edge_key) = relation_instance.get_key() {
                 let outbound = entity_instances.get(&relation_instance.outbound_id);
                 if outbound.is_none() {
@@ -182,13 +684,18 @@ impl TryFrom<Flow> for ReactiveFlow {
                 }
                 let outbound = outbound.unwrap().clone();
                 let inbound = inbound.unwrap().clone();
-                let reactive_relation_instance = Arc::new(ReactiveRelationInstance::from_instance(outbound, inbound, relation_instance.clone()));
-                relation_instances.insert(edge_key.clone(), reactive_relation_instance);
+                if let Ok(reactive_relation_instance) = ReactiveRelationInstance::from_instance(outbound, inbound, relation_instance.clone()) {
+                    relation_instances.insert(edge_key.clone(), Arc::new(reactive_relation_instance));
+                }
             }
         }
+        if !callback(&progress) {
+            return Err(ReactiveFlowConstructionError::Cancelled);
+        }
         Ok(ReactiveFlow {
             id: flow_id,
             type_name: flow.type_name,
+            name: flow.name,
             entity_instances: RwLock::new(entity_instances),
             relation_instances: RwLock::new(relation_instances),
             // wrapper: wrapper.unwrap(),
@@ -196,68 +703,97 @@ impl TryFrom<Flow> for ReactiveFlow {
             entities_removed: RwLock::new(Vec::new()),
             relations_added: RwLock::new(Vec::new()),
             relations_removed: RwLock::new(Vec::new()),
+            overrides: RwLock::new(HashMap::new()),
+            overridden_values: Arc::new(RwLock::new(HashMap::new())),
+            variables: RwLock::new(flow.variables),
+            layers: flow.layers,
+            disabled_entity_instances,
+            disabled_relation_instances,
+            type_versions: flow.type_versions,
+            destroyed: AtomicBool::new(false),
         })
     }
 }
 
+impl From<Arc<ReactiveEntityInstance>> for ReactiveFlow {
+    fn from(wrapper_entity_instance: Arc<ReactiveEntityInstance>) -> Self {
+        ReactiveFlow::new(wrapper_entity_instance)
+    }
+}
+
+impl TryFrom<Flow> for ReactiveFlow {
+    type Error = ReactiveFlowConstructionError;
+
+    fn try_from(flow: Flow) -> Result<Self, ReactiveFlowConstructionError> {
+        ReactiveFlow::try_from_with_progress(flow, usize::MAX, |_| true)
+    }
+}
+
 impl PropertyInstanceGetter for ReactiveFlow {
-    fn get<S: Into<String>>(&self, property_name: S) -> Option<Value> {
-        self.get_entity(self.id).and_then(|e| e.properties.get(&property_name.into()).map(|p| p.get()))
+    fn get<S: AsRef<str>>(&self, property_name: S) -> Option<Value> {
+        self.get_entity(self.id).and_then(|e| e.properties.get(property_name.as_ref()).map(|p| p.get()))
     }
 
-    fn as_bool<S: Into<String>>(&self, property_name: S) -> Option<bool> {
+    fn as_bool<S: AsRef<str>>(&self, property_name: S) -> Option<bool> {
         self.get_entity(self.id)
-            .and_then(|e| e.properties.get(&property_name.into()).and_then(|p| p.as_bool()))
+            .and_then(|e| e.properties.get(property_name.as_ref()).and_then(|p| p.as_bool()))
     }
 
-    fn as_u64<S: Into<String>>(&self, property_name: S) -> Option<u64> {
+    fn as_u64<S: AsRef<str>>(&self, property_name: S) -> Option<u64> {
         self.get_entity(self.id)
-            .and_then(|e| e.properties.get(&property_name.into()).and_then(|p| p.as_u64()))
+            .and_then(|e| e.properties.get(property_name.as_ref()).and_then(|p| p.as_u64()))
     }
 
-    fn as_i64<S: Into<String>>(&self, property_name: S) -> Option<i64> {
+    fn as_i64<S: AsRef<str>>(&self, property_name: S) -> Option<i64> {
         self.get_entity(self.id)
-            .and_then(|e| e.properties.get(&property_name.into()).and_then(|p| p.as_i64()))
+            .and_then(|e| e.properties.get(property_name.as_ref()).and_then(|p| p.as_i64()))
     }
 
-    fn as_f64<S: Into<String>>(&self, property_name: S) -> Option<f64> {
+    fn as_f64<S: AsRef<str>>(&self, property_name: S) -> Option<f64> {
         self.get_entity(self.id)
-            .and_then(|e| e.properties.get(&property_name.into()).and_then(|p| p.as_f64()))
+            .and_then(|e| e.properties.get(property_name.as_ref()).and_then(|p| p.as_f64()))
     }
 
-    fn as_string<S: Into<String>>(&self, property_name: S) -> Option<String> {
+    fn as_string<S: AsRef<str>>(&self, property_name: S) -> Option<String> {
         self.get_entity(self.id)
-            .and_then(|e| e.properties.get(&property_name.into()).and_then(|p| p.as_string()))
+            .and_then(|e| e.properties.get(property_name.as_ref()).and_then(|p| p.as_string()))
     }
 
-    fn as_array<S: Into<String>>(&self, property_name: S) -> Option<Vec<Value>> {
+    fn as_array<S: AsRef<str>>(&self, property_name: S) -> Option<Vec<Value>> {
         self.get_entity(self.id)
-            .and_then(|e| e.properties.get(&property_name.into()).and_then(|p| p.as_array()))
+            .and_then(|e| e.properties.get(property_name.as_ref()).and_then(|p| p.as_array()))
     }
 
-    fn as_object<S: Into<String>>(&self, property_name: S) -> Option<Map<String, Value>> {
+    fn as_object<S: AsRef<str>>(&self, property_name: S) -> Option<Map<String, Value>> {
         self.get_entity(self.id)
-            .and_then(|e| e.properties.get(&property_name.into()).and_then(|p| p.as_object()))
+            .and_then(|e| e.properties.get(property_name.as_ref()).and_then(|p| p.as_object()))
     }
 }
 
 impl PropertyInstanceSetter for ReactiveFlow {
-    fn set<S: Into<String>>(&self, property_name: S, value: Value) {
+    fn set<S: AsRef<str>>(&self, property_name: S, value: Value) {
         if let Some(instance) = self.get_entity(self.id) {
-            if let Some(instance) = instance.properties.get(&property_name.into()) {
+            if let Some(instance) = instance.properties.get(property_name.as_ref()) {
                 instance.set(value);
             }
         }
     }
 
-    fn set_no_propagate<S: Into<String>>(&self, property_name: S, value: Value) {
+    fn set_no_propagate<S: AsRef<str>>(&self, property_name: S, value: Value) {
         if let Some(instance) = self.get_entity(self.id) {
-            if let Some(instance) = instance.properties.get(&property_name.into()) {
+            if let Some(instance) = instance.properties.get(property_name.as_ref()) {
                 instance.set_no_propagate(value);
             }
         }
     }
 
-    // TODO: fn set(&self, Map<String, Value>
-    // TODO: Set values transactional: first set all values internally, then send all affected streams
+    fn set_many(&self, values: HashMap<String, Value>) {
+        if let Some(instance) = self.get_entity(self.id) {
+            instance.set_many(values);
+        }
+    }
+
+    fn modify_value<S: AsRef<str>, R, F: FnOnce(&mut Value) -> Option<R>>(&self, property_name: S, f: F) -> Option<R> {
+        self.get_entity(self.id)?.modify_value(property_name, f)
+    }
 }
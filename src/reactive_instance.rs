@@ -0,0 +1,92 @@
+use crate::{
+    BehaviourName, ComponentName, PropertyInstanceGetter, PropertyInstanceSetter, ReactiveEntityInstance, ReactivePropertyContainer, ReactiveRelationInstance,
+};
+
+/// The common interface of reactive entity instances and reactive relation instances. Behaviours
+/// which only work on properties, components and behaviours can be written once for both. The
+/// properties are accessed through the reactive property container.
+pub trait ReactiveInstance: ReactivePropertyContainer + PropertyInstanceGetter + PropertyInstanceSetter + Send + Sync {
+    /// Returns the name of the entity type or relation type.
+    fn type_name(&self) -> &str;
+
+    /// Returns the revision, which is bumped on every mutation.
+    fn revision(&self) -> u64;
+
+    /// Adds the component with the given name.
+    fn add_component<C: Into<ComponentName>>(&self, component: C);
+
+    /// Removes the component with the given name.
+    fn remove_component<C: Into<ComponentName>>(&self, component: C);
+
+    /// Returns true, if the component with the given name is applied.
+    fn is_a<C: Into<ComponentName>>(&self, component: C) -> bool;
+
+    /// Returns the names of the components in order.
+    fn get_components(&self) -> Vec<ComponentName>;
+
+    /// Adds the behaviour with the given name.
+    fn add_behaviour<B: Into<BehaviourName>>(&self, behaviour: B);
+
+    /// Removes the behaviour with the given name.
+    fn remove_behaviour<B: Into<BehaviourName>>(&self, behaviour: B);
+
+    /// Returns true, if the behaviour with the given name is applied.
+    fn behaves_as<B: Into<BehaviourName>>(&self, behaviour: B) -> bool;
+
+    /// Returns the names of the behaviours in order.
+    fn get_behaviours(&self) -> Vec<BehaviourName>;
+}
+
+/// Implements the reactive instance trait by delegating to the inherent methods.
+macro_rules! impl_reactive_instance {
+    ($instance:ty) => {
+        impl ReactiveInstance for $instance {
+            fn type_name(&self) -> &str {
+                self.type_name.as_str()
+            }
+
+            fn revision(&self) -> u64 {
+                <$instance>::revision(self)
+            }
+
+            fn add_component<C: Into<ComponentName>>(&self, component: C) {
+                <$instance>::add_component(self, component)
+            }
+
+            fn remove_component<C: Into<ComponentName>>(&self, component: C) {
+                <$instance>::remove_component(self, component)
+            }
+
+            fn is_a<C: Into<ComponentName>>(&self, component: C) -> bool {
+                <$instance>::is_a(self, component)
+            }
+
+            fn get_components(&self) -> Vec<ComponentName> {
+                let mut components: Vec<ComponentName> = self.components.iter().map(|component| component.clone()).collect();
+                components.sort();
+                components
+            }
+
+            fn add_behaviour<B: Into<BehaviourName>>(&self, behaviour: B) {
+                <$instance>::add_behaviour(self, behaviour)
+            }
+
+            fn remove_behaviour<B: Into<BehaviourName>>(&self, behaviour: B) {
+                <$instance>::remove_behaviour(self, behaviour)
+            }
+
+            fn behaves_as<B: Into<BehaviourName>>(&self, behaviour: B) -> bool {
+                <$instance>::behaves_as(self, behaviour)
+            }
+
+            fn get_behaviours(&self) -> Vec<BehaviourName> {
+                let mut behaviours: Vec<BehaviourName> = self.behaviours.iter().map(|behaviour| behaviour.clone()).collect();
+                behaviours.sort();
+                behaviours
+            }
+        }
+    };
+}
+
+impl_reactive_instance!(ReactiveEntityInstance);
+impl_reactive_instance!(ReactiveRelationInstance);
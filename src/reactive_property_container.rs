@@ -0,0 +1,352 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use dashmap::mapref::multiple::RefMulti;
+use dashmap::DashMap;
+use serde_json::Map;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{
+    ConflationPolicy, InstanceEvent, InstanceObservers, Instant, PropertyInstanceGetter, PropertyInstanceSetter, ReactivePropertyInstance, Revision,
+    SetPropertyError, SocketType, TypeEnforcementPolicy, UnknownPropertyError, UnknownPropertyPolicy,
+};
+
+/// A container of reactive properties. The property accessors, adding and removing properties
+/// and ticking are implemented once for all containers, e.g. for reactive entity instances and
+/// reactive relation instances.
+pub trait ReactivePropertyContainer {
+    /// Returns the reactive properties.
+    fn properties(&self) -> &DashMap<Arc<str>, ReactivePropertyInstance>;
+
+    /// Returns the revision which is bumped on every mutation of the container. The revision is
+    /// shared with the properties, which bump it whenever a value is written.
+    fn revision_counter(&self) -> &Revision;
+
+    /// Returns how values are handled which are set on properties which don't exist.
+    fn unknown_property_policy(&self) -> UnknownPropertyPolicy;
+
+    /// Returns the id of a new property instance.
+    fn new_property_id(&self) -> Uuid;
+
+    /// Returns the observers of all properties and the event observers of the container.
+    fn observers(&self) -> &InstanceObservers;
+
+    /// Returns true, if a property with the given name exists.
+    fn has_property<S: AsRef<str>>(&self, property_name: S) -> bool {
+        self.properties().contains_key(property_name.as_ref())
+    }
+
+    /// Returns the names of the properties in order.
+    fn get_property_names(&self) -> Vec<String> {
+        let mut property_names: Vec<String> = self.properties().iter().map(|property_instance| property_instance.key().to_string()).collect();
+        property_names.sort();
+        property_names
+    }
+
+    /// Adds a property with the given name and initial value. Does nothing, if the property
+    /// already exists.
+    fn add_property<S: AsRef<str>>(&self, name: S, value: Value) {
+        if !self.properties().contains_key(name.as_ref()) {
+            let property_instance = ReactivePropertyInstance {
+                revision: self.revision_counter().clone(),
+                ..ReactivePropertyInstance::new(self.new_property_id(), name, value.clone())
+            };
+            let name = property_instance.name.clone();
+            self.properties().insert(name.clone(), property_instance);
+            // The observers are subscribed through a shared copy, so the map isn't locked while subscribing
+            let shared = self.properties().get(&name).map(|property_instance| property_instance.share());
+            if let Some(property_instance) = shared {
+                self.observers().attach(&property_instance);
+            }
+            self.observers().notify_property_added(&name, &value);
+            self.revision_counter().bump();
+        }
+    }
+
+    /// Removes the property with the given name. The observers of the container are detached from
+    /// the property and the event observers are notified. Returns the last value of the property
+    /// or none, if the property doesn't exist.
+    fn remove_property<S: AsRef<str>>(&self, name: S) -> Option<Value> {
+        let (name, property_instance) = self.properties().remove(name.as_ref())?;
+        self.observers().detach(&property_instance);
+        let value = property_instance.get();
+        self.observers().notify_property_removed(&name, &value);
+        self.revision_counter().bump();
+        Some(value)
+    }
+
+    /// Tears down the container deterministically. The event observers are notified with
+    /// `InstanceEvent::Destroyed`, afterwards the observers which have been registered through the
+    /// container and through its property instances are removed. Observers which have been
+    /// subscribed to the streams of the properties directly are kept, because the streams may be
+    /// shared with another instance. Destroying the container again does nothing.
+    ///
+    /// Instances are not destroyed on drop, because replaced entity instances share their streams
+    /// and their id with the new entity instance. The owner has to destroy removed instances.
+    fn destroy(&self) {
+        self.observers().notify_destroyed();
+        for property_instance in self.properties().iter() {
+            property_instance.destroy();
+        }
+        self.revision_counter().bump();
+    }
+
+    /// Sets the value of the property. Returns an error, if the property doesn't exist and the
+    /// unknown property policy is error, or if the property is read only or immutable and
+    /// already initialized.
+    fn try_set<S: AsRef<str>>(&self, property_name: S, value: Value) -> Result<(), SetPropertyError> {
+        match self.properties().get(property_name.as_ref()) {
+            Some(instance) => {
+                if !instance.is_writable() {
+                    return Err(SetPropertyError::NotWritable(instance.name.to_string()));
+                }
+                instance.set(value);
+                Ok(())
+            }
+            None => Ok(set_unknown_property(self, property_name, value)?),
+        }
+    }
+
+    /// Ticks all properties in a deterministic order. Properties with a higher tick
+    /// priority are ticked first, properties with the same priority are ticked in
+    /// the order of their names. Afterwards the dirty flags are cleared.
+    fn tick(&self) {
+        for property_name in self.get_tick_order() {
+            if let Some(property_instance) = self.properties().get(&property_name) {
+                property_instance.tick();
+            }
+        }
+        self.clear_dirty();
+    }
+
+    /// Returns the names of the properties in the order they are ticked.
+    fn get_tick_order(&self) -> Vec<Arc<str>> {
+        let mut tick_order: Vec<(i32, Arc<str>)> = self
+            .properties()
+            .iter()
+            .map(|property_instance| (property_instance.tick_priority, property_instance.key().clone()))
+            .collect();
+        tick_order.sort_by(|(priority_1, name_1), (priority_2, name_2)| priority_2.cmp(priority_1).then_with(|| name_1.cmp(name_2)));
+        tick_order.into_iter().map(|(_, property_name)| property_name).collect()
+    }
+
+    /// Returns true, if a property has been set since the last tick or since the dirty flags
+    /// have been cleared.
+    fn is_dirty(&self) -> bool {
+        self.properties().iter().any(|property_instance| property_instance.is_dirty())
+    }
+
+    /// Returns the names of the properties which have been set since the last tick or since
+    /// the dirty flags have been cleared.
+    fn changed_properties(&self) -> Vec<String> {
+        let mut changed_properties: Vec<String> = self
+            .properties()
+            .iter()
+            .filter(|property_instance| property_instance.is_dirty())
+            .map(|property_instance| property_instance.key().to_string())
+            .collect();
+        changed_properties.sort();
+        changed_properties
+    }
+
+    /// Returns the monotonic time when a property has been set the last time or none, if no
+    /// property has been set.
+    fn last_modified(&self) -> Option<Instant> {
+        self.properties().iter().filter_map(|property_instance| property_instance.last_modified()).max()
+    }
+
+    /// Clears the dirty flags of all properties.
+    fn clear_dirty(&self) {
+        for property_instance in self.properties().iter() {
+            property_instance.clear_dirty();
+        }
+    }
+
+    /// Returns the properties which act as input socket.
+    fn inputs(&self) -> impl Iterator<Item = RefMulti<'_, Arc<str>, ReactivePropertyInstance>> {
+        self.properties().iter().filter(|property_instance| property_instance.is_input())
+    }
+
+    /// Returns the properties which act as output socket.
+    fn outputs(&self) -> impl Iterator<Item = RefMulti<'_, Arc<str>, ReactivePropertyInstance>> {
+        self.properties().iter().filter(|property_instance| property_instance.is_output())
+    }
+
+    /// Sets the conflation policy of the property with the given name.
+    fn set_conflation_policy<S: AsRef<str>>(&self, property_name: S, policy: ConflationPolicy) {
+        if let Some(mut property_instance) = self.properties().get_mut(property_name.as_ref()) {
+            property_instance.set_conflation_policy(policy);
+        }
+    }
+
+    /// Records the last values of the property with the given name up to the given capacity.
+    fn set_value_history<S: AsRef<str>>(&self, property_name: S, capacity: usize) {
+        if let Some(mut property_instance) = self.properties().get_mut(property_name.as_ref()) {
+            property_instance.set_value_history(capacity);
+        }
+    }
+
+    /// Sets the maximum number of queued payloads of the event property with the given name.
+    fn set_event_capacity<S: AsRef<str>>(&self, property_name: S, capacity: usize) {
+        if let Some(mut property_instance) = self.properties().get_mut(property_name.as_ref()) {
+            property_instance.set_event_capacity(capacity);
+        }
+    }
+
+    /// Sets the type enforcement policy of the property with the given name.
+    fn set_type_enforcement_policy<S: AsRef<str>>(&self, property_name: S, policy: TypeEnforcementPolicy) {
+        if let Some(mut property_instance) = self.properties().get_mut(property_name.as_ref()) {
+            property_instance.set_type_enforcement_policy(policy);
+        }
+    }
+
+    /// Enables or disables interning of the string values of the property with the given name.
+    fn set_interning<S: AsRef<str>>(&self, property_name: S, enabled: bool) {
+        if let Some(mut property_instance) = self.properties().get_mut(property_name.as_ref()) {
+            property_instance.set_interning(enabled);
+        }
+    }
+
+    /// Sets the socket type of the property with the given name.
+    fn set_socket_type<S: AsRef<str>>(&self, property_name: S, socket_type: SocketType) {
+        if let Some(mut property_instance) = self.properties().get_mut(property_name.as_ref()) {
+            property_instance.socket_type = socket_type;
+        }
+    }
+
+    /// Sets the tick priority of the property with the given name.
+    fn set_tick_priority<S: AsRef<str>>(&self, property_name: S, tick_priority: i32) {
+        if let Some(mut property_instance) = self.properties().get_mut(property_name.as_ref()) {
+            property_instance.tick_priority = tick_priority;
+        }
+    }
+}
+
+/// Shares the revision with the properties, so writing a value bumps the revision.
+pub(crate) fn share_revision(properties: &DashMap<Arc<str>, ReactivePropertyInstance>, revision: &Revision) {
+    for mut property_instance in properties.iter_mut() {
+        property_instance.revision = revision.clone();
+    }
+}
+
+fn set_unknown_property<C: ReactivePropertyContainer + ?Sized, S: AsRef<str>>(
+    container: &C,
+    property_name: S,
+    value: Value,
+) -> Result<(), UnknownPropertyError> {
+    match container.unknown_property_policy() {
+        UnknownPropertyPolicy::Ignore => Ok(()),
+        UnknownPropertyPolicy::Create => {
+            container.add_property(property_name, value);
+            Ok(())
+        }
+        UnknownPropertyPolicy::Error => {
+            let name = property_name.as_ref().to_string();
+            container
+                .observers()
+                .notify(&InstanceEvent::UnknownPropertyRejected { name: name.clone(), value });
+            Err(UnknownPropertyError(name))
+        }
+    }
+}
+
+impl<C: ReactivePropertyContainer> PropertyInstanceGetter for C {
+    fn get<S: AsRef<str>>(&self, property_name: S) -> Option<Value> {
+        self.properties().get(property_name.as_ref()).map(|p| p.get())
+    }
+
+    fn with_property<S: AsRef<str>, R, F: FnOnce(&Value) -> R>(&self, property_name: S, f: F) -> Option<R> {
+        self.properties().get(property_name.as_ref()).map(|p| p.with_value(f))
+    }
+
+    fn as_bool<S: AsRef<str>>(&self, property_name: S) -> Option<bool> {
+        self.properties().get(property_name.as_ref()).and_then(|p| p.as_bool())
+    }
+
+    fn as_u64<S: AsRef<str>>(&self, property_name: S) -> Option<u64> {
+        self.properties().get(property_name.as_ref()).and_then(|p| p.as_u64())
+    }
+
+    fn as_i64<S: AsRef<str>>(&self, property_name: S) -> Option<i64> {
+        self.properties().get(property_name.as_ref()).and_then(|p| p.as_i64())
+    }
+
+    fn as_f64<S: AsRef<str>>(&self, property_name: S) -> Option<f64> {
+        self.properties().get(property_name.as_ref()).and_then(|p| p.as_f64())
+    }
+
+    fn as_string<S: AsRef<str>>(&self, property_name: S) -> Option<String> {
+        self.properties().get(property_name.as_ref()).and_then(|p| p.as_string())
+    }
+
+    fn as_array<S: AsRef<str>>(&self, property_name: S) -> Option<Vec<Value>> {
+        self.properties().get(property_name.as_ref()).and_then(|p| p.as_array())
+    }
+
+    fn as_object<S: AsRef<str>>(&self, property_name: S) -> Option<Map<String, Value>> {
+        self.properties().get(property_name.as_ref()).and_then(|p| p.as_object())
+    }
+}
+
+impl<C: ReactivePropertyContainer> PropertyInstanceSetter for C {
+    /// Sets the value of the property. If the property doesn't exist and the unknown property
+    /// policy is error, the value is dropped and the event observers are notified with
+    /// `InstanceEvent::UnknownPropertyRejected`. Use `try_set` to handle the error.
+    fn set<S: AsRef<str>>(&self, property_name: S, value: Value) {
+        let _ = self.try_set(property_name, value);
+    }
+
+    fn set_no_propagate<S: AsRef<str>>(&self, property_name: S, value: Value) {
+        match self.properties().get(property_name.as_ref()) {
+            Some(instance) => instance.set_no_propagate(value),
+            None => {
+                let _ = set_unknown_property(self, property_name, value);
+            }
+        }
+    }
+
+    fn modify_value<S: AsRef<str>, R, F: FnOnce(&mut Value) -> Option<R>>(&self, property_name: S, f: F) -> Option<R> {
+        // The properties are released before the new value is propagated, so observers can
+        // add or remove properties of the container
+        let (result, propagation) = self.properties().get(property_name.as_ref())?.modify_value_unpropagated(f)?;
+        if let Some(propagation) = propagation {
+            propagation.propagate();
+        }
+        Some(result)
+    }
+
+    /// Writes the values of the value properties first. Afterwards the payloads of the event
+    /// properties are emitted and the written values are propagated in tick order. Values which
+    /// have been rejected are not propagated.
+    fn set_many(&self, values: HashMap<String, Value>) {
+        let mut written = HashSet::new();
+        let mut events = HashMap::new();
+        for (property_name, value) in values {
+            match self.properties().get(property_name.as_str()) {
+                Some(instance) if instance.is_event() => {
+                    events.insert(property_name, value);
+                }
+                Some(instance) => {
+                    if instance.write_no_propagate(value) {
+                        written.insert(property_name);
+                    }
+                }
+                None => {
+                    let _ = set_unknown_property(self, property_name, value);
+                }
+            }
+        }
+        for property_name in self.get_tick_order() {
+            if let Some(payload) = events.remove(property_name.as_ref()) {
+                if let Some(instance) = self.properties().get(&property_name) {
+                    instance.emit(payload);
+                }
+            } else if written.contains(property_name.as_ref()) {
+                if let Some(instance) = self.properties().get(&property_name) {
+                    instance.tick();
+                }
+            }
+        }
+    }
+}
@@ -1,93 +1,781 @@
-use std::ops::{Deref, DerefMut};
-use std::sync::{Arc, RwLock};
+use std::collections::VecDeque;
+use std::ops::DerefMut;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 
+#[cfg(feature = "time")]
+use chrono::{DateTime, FixedOffset};
 use inexor_rgf_core_frp::Stream;
 use serde_json::{Map, Value};
 use uuid::Uuid;
 
+use crate::propagation_suspension::defer_propagation;
+#[cfg(feature = "metrics")]
+use crate::property_metrics::{record_metric, Metric};
+use crate::tick_profiler::profile_propagation;
+#[cfg(feature = "time")]
+use crate::value_to_date_time;
+use crate::{
+    has_propagation_tracer, intern_string, trace_propagation, ConflationPolicy, PrioritizedObservers, PropertyConflation, PropertyInterning, PropertyKind,
+    PropertyType, SocketType,
+};
+use crate::{
+    value_to_uuid, DataType, Instant, PropertyMutability, PropertyTypeEnforcement, PropertyValueHistory, Revision, RevisionError, TypeEnforcementPolicy,
+};
+
 pub struct ReactivePropertyInstance {
     /// Vertex uuid
     pub id: Uuid,
 
     /// Property name
-    pub name: String,
+    ///
+    /// The names are interned, so instances with the same property share a single allocation.
+    pub name: Arc<str>,
 
     /// The reactive stream
     pub stream: Arc<RwLock<Stream<'static, Value>>>,
 
+    /// The observers of the property, which are invoked in order of their priority.
+    pub prioritized_observers: PrioritizedObservers,
+
+    /// The handle ids of the observers which have been registered through this property
+    /// instance. Destroying the property instance removes exactly these observers.
+    pub observer_handles: Mutex<Vec<u128>>,
+
+    /// Takes the values which are set instead of the property, e.g. while the value is overridden.
+    pub write_interceptor: Arc<RwLock<Option<WriteInterceptor>>>,
+
     /// Store the current value
     pub value: RwLock<Value>,
+
+    /// Properties with a higher tick priority are ticked first.
+    pub tick_priority: i32,
+
+    /// Handling of values which are set while a previous value is still propagated.
+    pub conflation: PropertyConflation,
+
+    /// Optional interning of string values.
+    pub interning: PropertyInterning,
+
+    /// Value properties hold the last value, event properties queue the emitted payloads.
+    pub kind: PropertyKind,
+
+    /// Specifies if the property acts as input or output socket.
+    pub socket_type: SocketType,
+
+    /// The payloads which have been emitted but not yet drained.
+    pub events: Mutex<VecDeque<Value>>,
+
+    /// The maximum number of queued payloads. If the queue is full, the oldest payload is dropped.
+    pub event_capacity: usize,
+
+    /// The unit of the values, e.g. `meters`, `degrees` or `ms`.
+    pub unit: Option<String>,
+
+    /// The semantic tag of the values, e.g. `position` or `color`.
+    pub semantic: Option<String>,
+
+    /// Optional checking of the values against the data type.
+    pub type_enforcement: PropertyTypeEnforcement,
+
+    /// Immutable properties accept a single write, which initializes the value.
+    pub mutability: PropertyMutability,
+
+    /// True, if the value has been set since the dirty flag has been cleared.
+    pub dirty: AtomicBool,
+
+    /// The monotonic time when the value has been set the last time.
+    pub last_modified: RwLock<Option<Instant>>,
+
+    /// Optional history of the last values.
+    pub value_history: PropertyValueHistory,
+
+    /// The revision of the instance which contains the property. Writing the value bumps the
+    /// revision.
+    pub revision: Revision,
+}
+
+/// Receives the values which are set while the writes of a property are intercepted.
+pub type WriteInterceptor = Arc<dyn Fn(&Value) + Send + Sync>;
+
+/// The reason why a value hasn't been written.
+enum WriteRejection {
+    /// The property is read only or immutable and already initialized.
+    NotWritable,
+
+    /// The value has been dropped by the type enforcement.
+    Dropped,
+
+    /// The revision doesn't equal the expected revision. Contains the actual revision.
+    RevisionMismatch(u64),
 }
 
+/// The maximum number of queued payloads of an event property, unless another capacity is set.
+pub const DEFAULT_EVENT_CAPACITY: usize = 1024;
+
 impl ReactivePropertyInstance {
-    pub fn new<S: Into<String>>(id: Uuid, name: S, value: Value) -> ReactivePropertyInstance {
+    pub fn new<S: AsRef<str>>(id: Uuid, name: S, value: Value) -> ReactivePropertyInstance {
         ReactivePropertyInstance {
             id,
-            name: name.into(),
+            name: intern_string(name.as_ref()),
             stream: Arc::new(RwLock::new(Stream::new())),
+            prioritized_observers: PrioritizedObservers::default(),
+            observer_handles: Mutex::new(Vec::new()),
+            write_interceptor: Arc::new(RwLock::new(None)),
             value: RwLock::new(value),
+            tick_priority: 0,
+            conflation: PropertyConflation::default(),
+            interning: PropertyInterning::default(),
+            kind: PropertyKind::ValueProperty,
+            socket_type: SocketType::None,
+            events: Mutex::new(VecDeque::new()),
+            event_capacity: DEFAULT_EVENT_CAPACITY,
+            unit: None,
+            semantic: None,
+            type_enforcement: PropertyTypeEnforcement::default(),
+            mutability: PropertyMutability::Mutable,
+            dirty: AtomicBool::new(false),
+            last_modified: RwLock::new(None),
+            value_history: PropertyValueHistory::default(),
+            revision: Revision::default(),
+        }
+    }
+
+    /// Creates a property instance of the given property type. Value properties are initialized
+    /// with the default value of the data type.
+    pub fn from_property_type(id: Uuid, property_type: &PropertyType) -> ReactivePropertyInstance {
+        let property_instance = if property_type.is_event() {
+            ReactivePropertyInstance::new_event(id, &property_type.name)
+        } else {
+            ReactivePropertyInstance::new(id, &property_type.name, property_type.data_type.default_value())
+        };
+        ReactivePropertyInstance {
+            socket_type: property_type.socket_type,
+            unit: property_type.unit.clone(),
+            semantic: property_type.semantic.clone(),
+            type_enforcement: PropertyTypeEnforcement::new(TypeEnforcementPolicy::Disabled, property_type.data_type),
+            mutability: property_type.mutability,
+            ..property_instance
+        }
+    }
+
+    /// Creates an event property which queues the emitted payloads instead of holding a last value.
+    pub fn new_event<S: AsRef<str>>(id: Uuid, name: S) -> ReactivePropertyInstance {
+        ReactivePropertyInstance {
+            kind: PropertyKind::EventProperty,
+            ..ReactivePropertyInstance::new(id, name, Value::Null)
         }
     }
 
+    /// Creates an independent copy of the property for the instance with the given id. The
+    /// current value and the configuration are copied, the observers, the queued events and the
+    /// revision are not.
+    pub fn duplicate(&self, id: Uuid) -> ReactivePropertyInstance {
+        let mut property_instance = ReactivePropertyInstance {
+            tick_priority: self.tick_priority,
+            conflation: PropertyConflation::new(self.conflation.policy),
+            kind: self.kind,
+            socket_type: self.socket_type,
+            event_capacity: self.event_capacity,
+            unit: self.unit.clone(),
+            semantic: self.semantic.clone(),
+            type_enforcement: self.type_enforcement,
+            mutability: self.mutability,
+            ..ReactivePropertyInstance::new(id, &self.name, self.get())
+        };
+        // An initialized immutable property stays initialized
+        if !self.is_writable() {
+            *property_instance.last_modified.write().unwrap() = self.last_modified();
+        }
+        if self.interning.enabled {
+            property_instance.set_interning(true);
+        }
+        if self.value_history.is_enabled() {
+            property_instance.set_value_history(self.value_history.capacity);
+        }
+        property_instance
+    }
+
+    /// Creates a copy of the property which shares the stream, the prioritized observers and the
+    /// revision, so observers which are subscribed to the property are preserved. The current
+    /// value, the configuration, the recorded values and the modification time are copied.
+    pub fn share(&self) -> ReactivePropertyInstance {
+        let property_instance = ReactivePropertyInstance {
+            stream: self.stream.clone(),
+            prioritized_observers: self.prioritized_observers.clone(),
+            write_interceptor: self.write_interceptor.clone(),
+            value_history: self.value_history.clone(),
+            revision: self.revision.clone(),
+            ..self.duplicate(self.id)
+        };
+        *property_instance.last_modified.write().unwrap() = self.last_modified();
+        property_instance
+    }
+
+    /// Returns true, if the property is an event property.
+    pub fn is_event(&self) -> bool {
+        self.kind == PropertyKind::EventProperty
+    }
+
+    /// Returns true, if the property acts as input socket.
+    pub fn is_input(&self) -> bool {
+        self.socket_type == SocketType::Input
+    }
+
+    /// Returns true, if the property acts as output socket.
+    pub fn is_output(&self) -> bool {
+        self.socket_type == SocketType::Output
+    }
+
     pub fn get(&self) -> Value {
-        self.value.read().unwrap().clone()
+        self.with_value(Value::clone)
+    }
+
+    /// Calls the function with a reference to the current value. Unlike `get`, the value isn't
+    /// cloned, so reading a part of a large value is cheap.
+    ///
+    /// If interning is enabled, every read of an interned string allocates a fresh `String` for
+    /// the temporary value. The cost of these allocations offsets the memory which is saved by
+    /// interning, if the property is read frequently. `get_shared_string` returns the interned
+    /// string without copying.
+    pub fn with_value<R, F: FnOnce(&Value) -> R>(&self, f: F) -> R {
+        let reader = self.value.read().unwrap();
+        // The interned value is only locked, if interning is enabled
+        if self.interning.enabled {
+            if let Some(interned) = self.interning.get() {
+                return f(&Value::String(String::from(interned.as_ref())));
+            }
+        }
+        f(&reader)
+    }
+
+    /// Returns the string value as shared allocation or none, if the value isn't a string. If
+    /// interning is enabled, the interned string is returned without copying.
+    pub fn get_shared_string(&self) -> Option<Arc<str>> {
+        let reader = self.value.read().unwrap();
+        if self.interning.enabled {
+            if let Some(interned) = self.interning.get() {
+                return Some(interned);
+            }
+        }
+        reader.as_str().map(Arc::from)
+    }
+
+    /// Replaces the current value and returns the old value.
+    ///
+    /// If interning is enabled, string values are stored as shared allocations.
+    fn replace(&self, writer: &mut Value, value: Value) -> Value {
+        let old_value = match self.interning.take() {
+            Some(interned) => Value::String(String::from(interned.as_ref())),
+            None => std::mem::replace(writer, Value::Null),
+        };
+        match value {
+            Value::String(s) if self.interning.enabled => self.interning.store(intern_string(&s)),
+            value => *writer = value,
+        }
+        old_value
     }
 
     pub fn set(&self, value: Value) {
+        self.set_checked(value, false);
+    }
+
+    /// Sets the value of a read only property. Only the owner of a derived property sets its value.
+    pub(crate) fn set_derived(&self, value: Value) {
+        self.set_checked(value, true);
+    }
+
+    fn set_checked(&self, value: Value, derived: bool) {
+        if self.is_event() {
+            self.emit(value);
+            return;
+        }
+        let _ = self.write(value, derived, None);
+    }
+
+    /// Sets the value, if the revision of the instance equals the expected revision. The revision
+    /// is compared and bumped while the value is locked. Returns the new revision.
+    pub(crate) fn set_if_revision(&self, value: Value, expected_revision: u64) -> Result<u64, RevisionError> {
+        if self.is_event() {
+            return Err(RevisionError::Rejected(self.name.to_string()));
+        }
+        self.write(value, false, Some(expected_revision)).map_err(|rejection| match rejection {
+            WriteRejection::RevisionMismatch(actual) => RevisionError::Mismatch(expected_revision, actual),
+            WriteRejection::NotWritable | WriteRejection::Dropped => RevisionError::Rejected(self.name.to_string()),
+        })
+    }
+
+    /// Writes the value and propagates it. If the propagation is conflated, only the latest value
+    /// is propagated, if a propagation is already in progress. Returns the new revision.
+    fn write(&self, value: Value, derived: bool, expected_revision: Option<u64>) -> Result<u64, WriteRejection> {
+        let value = self.type_enforcement.enforce(self.id, &self.name, value).ok_or(WriteRejection::Dropped)?;
+        if self.intercept(&value) {
+            return Ok(self.revision.get());
+        }
+        let (revision, old_value) = {
+            let mut writer = self.value.write().unwrap();
+            let revision = self.mark_modified(&value, derived, expected_revision)?;
+            (revision, self.replace(writer.deref_mut(), value.clone()))
+        };
+        let deferred = defer_propagation(self.id, &self.name, &self.stream, &value);
+        trace_propagation(self.id, &self.name, old_value, &value, deferred);
+        if !deferred {
+            self.pending_propagation(value).propagate();
+        }
+        Ok(revision)
+    }
+
+    /// Returns the propagation of the value, which doesn't borrow the property.
+    fn pending_propagation(&self, value: Value) -> PendingPropagation {
+        PendingPropagation {
+            id: self.id,
+            name: self.name.clone(),
+            stream: self.stream.clone(),
+            conflation: self.conflation.share(),
+            value,
+        }
+    }
+
+    /// Modifies the value and propagates the new value. The value is modified while it is locked,
+    /// so concurrent modifications are not lost. Returns none, if the function returns none, the
+    /// new value is dropped or the writes are intercepted. In that case the value is left unchanged.
+    pub fn modify_value<R, F: FnOnce(&mut Value) -> Option<R>>(&self, f: F) -> Option<R> {
+        let (result, propagation) = self.modify_value_unpropagated(f)?;
+        if let Some(propagation) = propagation {
+            propagation.propagate();
+        }
+        Some(result)
+    }
+
+    /// Modifies the value like `modify_value`, but returns the propagation of the new value
+    /// instead of propagating it. The propagation is none, if the propagation is suspended.
+    pub(crate) fn modify_value_unpropagated<R, F: FnOnce(&mut Value) -> Option<R>>(&self, f: F) -> Option<(R, Option<PendingPropagation>)> {
+        if self.is_event() || !self.is_writable() || self.is_intercepted() {
+            return None;
+        }
+        let mut writer = self.value.write().unwrap();
+        let mut value = match self.interning.get() {
+            Some(interned) if self.interning.enabled => Value::String(String::from(interned.as_ref())),
+            _ => writer.clone(),
+        };
+        let result = f(&mut value)?;
+        let value = self.type_enforcement.enforce(self.id, &self.name, value)?;
+        self.mark_modified(&value, false, None).ok()?;
+        let old_value = self.replace(writer.deref_mut(), value.clone());
+        drop(writer);
+        let deferred = defer_propagation(self.id, &self.name, &self.stream, &value);
+        trace_propagation(self.id, &self.name, old_value, &value, deferred);
+        if deferred {
+            return Some((result, None));
+        }
+        Some((result, Some(self.pending_propagation(value))))
+    }
+
+    /// Modifies the array value and propagates the new value. Returns none, if the value isn't an
+    /// array, the function returns none or the new value is dropped.
+    fn modify_array<R, F: FnOnce(&mut Vec<Value>) -> Option<R>>(&self, f: F) -> Option<R> {
+        self.modify_value(|value| f(value.as_array_mut()?))
+    }
+
+    /// Appends the value to the array and propagates the array. Returns false, if the value
+    /// isn't an array.
+    pub fn push(&self, value: Value) -> bool {
+        self.modify_array(|array| {
+            array.push(value);
+            Some(())
+        })
+        .is_some()
+    }
+
+    /// Removes the last element of the array and propagates the array. Returns none, if the
+    /// value isn't an array or the array is empty.
+    pub fn pop(&self) -> Option<Value> {
+        self.modify_array(|array| array.pop())
+    }
+
+    /// Inserts the value at the given index and propagates the array. Returns false, if the
+    /// value isn't an array or the index is out of bounds.
+    pub fn insert_at(&self, index: usize, value: Value) -> bool {
+        self.modify_array(|array| {
+            if index > array.len() {
+                return None;
+            }
+            array.insert(index, value);
+            Some(())
+        })
+        .is_some()
+    }
+
+    /// Removes the element at the given index and propagates the array. Returns none, if the
+    /// value isn't an array or the index is out of bounds.
+    pub fn remove_at(&self, index: usize) -> Option<Value> {
+        self.modify_array(|array| if index < array.len() { Some(array.remove(index)) } else { None })
+    }
+
+    /// Returns the length of the array or none, if the value isn't an array.
+    pub fn len(&self) -> Option<usize> {
+        self.with_value(|value| value.as_array().map(Vec::len))
+    }
+
+    /// Returns true, if the array is empty or none, if the value isn't an array.
+    pub fn is_empty(&self) -> Option<bool> {
+        self.len().map(|len| len == 0)
+    }
+
+    /// Queues the payload and propagates it to the observers.
+    ///
+    /// Events are never conflated or deferred, each payload is propagated exactly once.
+    pub fn emit(&self, payload: Value) {
+        #[cfg(feature = "metrics")]
+        record_metric(Metric::PropertySet, self.id, &self.name);
+        {
+            let mut events = self.events.lock().unwrap();
+            events.push_back(payload.clone());
+            while events.len() > self.event_capacity {
+                events.pop_front();
+            }
+        }
+        if has_propagation_tracer() {
+            trace_propagation(self.id, &self.name, Value::Null, &payload, false);
+        }
+        self.propagate(&payload);
+    }
+
+    /// Removes and returns the queued payloads in the order in which they have been emitted.
+    pub fn drain_events(&self) -> Vec<Value> {
+        self.events.lock().unwrap().drain(..).collect()
+    }
+
+    /// Returns the number of queued payloads.
+    pub fn pending_events(&self) -> usize {
+        self.events.lock().unwrap().len()
+    }
+
+    /// Sets the maximum number of queued payloads. The oldest payloads which exceed the capacity
+    /// are dropped.
+    pub fn set_event_capacity(&mut self, capacity: usize) {
+        self.event_capacity = capacity;
+        let mut events = self.events.lock().unwrap();
+        while events.len() > capacity {
+            events.pop_front();
+        }
+    }
+
+    pub fn set_conflation_policy(&mut self, policy: ConflationPolicy) {
+        self.conflation.policy = policy;
+    }
+
+    /// Marks the property as dirty, records the time of the modification and the value and bumps
+    /// the revision of the instance. Must be called while the value is locked, so the revision is
+    /// bumped by the write which modifies the value.
+    ///
+    /// The mutability is checked while the modification time is locked, so an immutable property
+    /// accepts a single write even if it is set concurrently. If an expected revision is given,
+    /// the write is rejected, if the revision doesn't equal the expected revision.
+    fn mark_modified(&self, value: &Value, derived: bool, expected_revision: Option<u64>) -> Result<u64, WriteRejection> {
+        let revision = {
+            let mut last_modified = self.last_modified.write().unwrap();
+            let writable = match self.mutability {
+                PropertyMutability::Mutable => true,
+                PropertyMutability::Immutable => last_modified.is_none(),
+                PropertyMutability::ReadOnly => derived,
+            };
+            if !writable {
+                return Err(WriteRejection::NotWritable);
+            }
+            let revision = match expected_revision {
+                Some(expected_revision) => self.revision.bump_if(expected_revision).map_err(WriteRejection::RevisionMismatch)?,
+                None => self.revision.bump(),
+            };
+            *last_modified = Some(Instant::now());
+            revision
+        };
+        #[cfg(feature = "metrics")]
+        record_metric(Metric::PropertySet, self.id, &self.name);
+        self.dirty.store(true, Ordering::SeqCst);
+        if self.value_history.is_enabled() {
+            self.value_history.push(value.clone());
+        }
+        Ok(revision)
+    }
+
+    /// Propagates the value to the observers of the stream.
+    fn propagate(&self, value: &Value) {
+        send(self.id, &self.name, &self.stream, value);
+    }
+
+    /// Records the last values up to the given capacity, starting with the current value. A
+    /// capacity of zero disables the history.
+    pub fn set_value_history(&mut self, capacity: usize) {
+        self.value_history = PropertyValueHistory::new(capacity);
+        if !self.is_event() {
+            self.value_history.push(self.get());
+        }
+    }
+
+    /// Returns the recorded values, the oldest value first. The last value is the current value.
+    pub fn history(&self) -> Vec<Value> {
+        self.value_history.get()
+    }
+
+    /// Returns the value before the current value, if the history is enabled.
+    pub fn previous(&self) -> Option<Value> {
+        self.value_history.previous()
+    }
+
+    /// Returns the monotonic time when the value has been set the last time or none, if the
+    /// value has never been set.
+    pub fn last_modified(&self) -> Option<Instant> {
+        *self.last_modified.read().unwrap()
+    }
+
+    /// Returns true, if the value has been set since the dirty flag has been cleared.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty.load(Ordering::SeqCst)
+    }
+
+    /// Clears the dirty flag.
+    pub fn clear_dirty(&self) {
+        self.dirty.store(false, Ordering::SeqCst);
+    }
+
+    /// Removes the observers which have been registered through this property instance and the
+    /// queued payloads. Values which are set afterwards are not propagated to these observers.
+    /// The stream may be shared with other property instances, e.g. with the property instances
+    /// of a replacement, so observers which have been registered through them and observers
+    /// which have been subscribed to the stream directly are kept. Destroying the property
+    /// instance again does nothing.
+    pub fn destroy(&self) {
+        let handle_ids: Vec<u128> = self.observer_handles.lock().unwrap().drain(..).collect();
+        for handle_id in handle_ids {
+            self.prioritized_observers.remove(handle_id);
+        }
+        self.events.lock().unwrap().clear();
+    }
+
+    /// Moves the observers which have been registered through the given property instance to this
+    /// property instance, so destroying the given property instance keeps the observers.
+    pub(crate) fn take_observer_handles(&self, other: &ReactivePropertyInstance) {
+        let handle_ids: Vec<u128> = other.observer_handles.lock().unwrap().drain(..).collect();
+        let mut observer_handles = self.observer_handles.lock().unwrap();
+        for handle_id in handle_ids {
+            if !observer_handles.contains(&handle_id) {
+                observer_handles.push(handle_id);
+            }
+        }
+    }
+
+    /// Intercepts the writes of the property. The values which are set afterwards are passed to the
+    /// interceptor instead of being written, so the current value takes precedence until the
+    /// interceptor is removed. The interceptor is shared with the property instances which share
+    /// the stream.
+    pub fn set_write_interceptor<F: Fn(&Value) + Send + Sync + 'static>(&self, interceptor: F) {
+        *self.write_interceptor.write().unwrap() = Some(Arc::new(interceptor));
+    }
+
+    /// Removes the write interceptor, so values which are set afterwards are written again.
+    pub fn remove_write_interceptor(&self) {
+        *self.write_interceptor.write().unwrap() = None;
+    }
+
+    /// Returns true, if the writes of the property are intercepted.
+    pub fn is_intercepted(&self) -> bool {
+        self.write_interceptor.read().unwrap().is_some()
+    }
+
+    /// Passes the value to the write interceptor. Returns false, if the writes aren't intercepted.
+    fn intercept(&self, value: &Value) -> bool {
+        let interceptor = self.write_interceptor.read().unwrap().clone();
+        match interceptor {
+            Some(interceptor) => {
+                interceptor(value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Sets the type enforcement policy. The values are checked against the given data type.
+    pub fn set_type_enforcement(&mut self, policy: TypeEnforcementPolicy, data_type: DataType) {
+        self.type_enforcement = PropertyTypeEnforcement::new(policy, data_type);
+    }
+
+    /// Sets the type enforcement policy. Properties which are not created from a property type
+    /// have the data type any, which accepts every value.
+    pub fn set_type_enforcement_policy(&mut self, policy: TypeEnforcementPolicy) {
+        self.type_enforcement.policy = policy;
+    }
+
+    /// Sets the mutability. Immutable properties accept a single write, which initializes the value.
+    pub fn set_mutability(&mut self, mutability: PropertyMutability) {
+        self.mutability = mutability;
+    }
+
+    /// Returns false, if the property is read only or if the property is immutable and has
+    /// already been initialized.
+    pub fn is_writable(&self) -> bool {
+        match self.mutability {
+            PropertyMutability::Mutable => true,
+            PropertyMutability::Immutable => self.last_modified().is_none(),
+            PropertyMutability::ReadOnly => false,
+        }
+    }
+
+    /// Enables or disables interning of string values.
+    pub fn set_interning(&mut self, enabled: bool) {
+        let value = self.get();
+        self.interning.enabled = enabled;
         let mut writer = self.value.write().unwrap();
-        *writer.deref_mut() = value.clone();
-        self.stream.read().unwrap().send(&value);
+        self.replace(writer.deref_mut(), value);
     }
 
     pub fn set_no_propagate(&self, value: Value) {
+        if self.write_no_propagate(value) {
+            #[cfg(feature = "metrics")]
+            record_metric(Metric::SuppressedPropagation, self.id, &self.name);
+        }
+    }
+
+    /// Writes the value without propagating it. Returns false, if the property isn't writable or
+    /// the value has been dropped by the type enforcement.
+    pub(crate) fn write_no_propagate(&self, value: Value) -> bool {
+        if !self.is_writable() {
+            return false;
+        }
+        let value = match self.type_enforcement.enforce(self.id, &self.name, value) {
+            Some(value) => value,
+            None => return false,
+        };
+        if self.intercept(&value) {
+            return true;
+        }
         let mut writer = self.value.write().unwrap();
-        *writer.deref_mut() = value;
+        if self.mark_modified(&value, false, None).is_err() {
+            return false;
+        }
+        if has_propagation_tracer() {
+            let old_value = self.replace(writer.deref_mut(), value.clone());
+            trace_propagation(self.id, &self.name, old_value, &value, true);
+        } else {
+            self.replace(writer.deref_mut(), value);
+        }
+        true
     }
 
     /// Send a value down the stream, but does not change the current value
     pub fn send(&self, signal: &Value) {
-        self.stream.read().unwrap().send(signal);
+        if has_propagation_tracer() {
+            trace_propagation(self.id, &self.name, self.get(), signal, false);
+        }
+        self.propagate(signal);
     }
 
-    /// Resend the current value manually
+    /// Resend the current value manually. Event properties have no current value and are not ticked.
     pub fn tick(&self) {
         // println!("tick {}::{}", self.id, self.name);
-        let value = self.value.read().unwrap().deref().clone();
-        self.stream.read().unwrap().send(&value);
+        if self.is_event() {
+            return;
+        }
+        #[cfg(feature = "metrics")]
+        record_metric(Metric::Tick, self.id, &self.name);
+        let value = self.get();
+        let deferred = defer_propagation(self.id, &self.name, &self.stream, &value);
+        if has_propagation_tracer() {
+            trace_propagation(self.id, &self.name, value.clone(), &value, deferred);
+        }
+        if !deferred {
+            self.propagate(&value);
+        }
     }
 
     pub fn as_bool(&self) -> Option<bool> {
-        self.get().as_bool()
+        self.with_value(Value::as_bool)
     }
 
     pub fn as_u64(&self) -> Option<u64> {
-        self.get().as_u64()
+        self.with_value(Value::as_u64)
     }
 
     pub fn as_i64(&self) -> Option<i64> {
-        self.get().as_i64()
+        self.with_value(Value::as_i64)
     }
 
     pub fn as_f64(&self) -> Option<f64> {
-        self.get().as_f64()
+        self.with_value(Value::as_f64)
     }
 
     pub fn as_string(&self) -> Option<String> {
-        self.get().as_str().map(String::from)
+        self.with_value(|value| value.as_str().map(String::from))
     }
 
     pub fn as_array(&self) -> Option<Vec<Value>> {
-        self.get().as_array().map(Vec::clone)
+        self.with_value(|value| value.as_array().cloned())
     }
 
     pub fn as_object(&self) -> Option<Map<String, Value>> {
-        self.get().as_object().map(Map::clone)
+        self.with_value(|value| value.as_object().cloned())
+    }
+
+    /// Returns the uuid (string encoded UUID).
+    pub fn as_uuid(&self) -> Option<Uuid> {
+        self.with_value(value_to_uuid)
+    }
+
+    /// Returns the timestamp with the stored offset (RFC 3339 formatted string).
+    #[cfg(feature = "time")]
+    pub fn as_date_time(&self) -> Option<DateTime<FixedOffset>> {
+        self.with_value(|value| value_to_date_time(value).ok())
+    }
+}
+
+/// Sends the value to the observers of the stream of the property of the instance with the given id.
+fn send(id: Uuid, property_name: &str, stream: &RwLock<Stream<'static, Value>>, value: &Value) {
+    #[cfg(feature = "metrics")]
+    record_metric(Metric::Propagation, id, property_name);
+    profile_propagation(id, property_name, || stream.read().unwrap().send(value));
+}
+
+/// A value which has been written, but hasn't been propagated yet. The propagation doesn't borrow
+/// the property, so the container of the property can be released before the observers are invoked.
+pub(crate) struct PendingPropagation {
+    id: Uuid,
+    name: Arc<str>,
+    stream: Arc<RwLock<Stream<'static, Value>>>,
+    conflation: PropertyConflation,
+    value: Value,
+}
+
+impl PendingPropagation {
+    /// Propagates the value. If the propagation is conflated and a propagation is already in
+    /// progress, the latest value is propagated after it has finished.
+    pub(crate) fn propagate(self) {
+        if self.conflation.policy != ConflationPolicy::KeepLatest {
+            send(self.id, &self.name, &self.stream, &self.value);
+            return;
+        }
+        self.conflation.mark_pending(self.value);
+        while self.conflation.begin() {
+            while let Some(value) = self.conflation.take_pending() {
+                send(self.id, &self.name, &self.stream, &value);
+            }
+            if !self.conflation.end() {
+                break;
+            }
+        }
+    }
+}
+
+/// Propagates a value which has been set while the propagation has been suspended.
+pub(crate) fn propagate_resumed(id: Uuid, property_name: &str, stream: &RwLock<Stream<'static, Value>>, value: &Value) {
+    if has_propagation_tracer() {
+        trace_propagation(id, property_name, value.clone(), value, false);
     }
+    send(id, property_name, stream, value);
 }
 
 impl PartialEq for ReactivePropertyInstance {
     fn eq(&self, other: &Self) -> bool {
-        self.value.read().unwrap().deref() == other.value.read().unwrap().deref()
+        self.get() == other.get()
     }
 }
 
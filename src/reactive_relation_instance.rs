@@ -1,18 +1,43 @@
-use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
 use dashmap::DashMap;
 use dashmap::DashSet;
-use indradb::{EdgeKey, EdgeProperties, Identifier};
-use serde_json::Map;
+#[cfg(feature = "graph-db")]
+use indradb::EdgeProperties;
 use serde_json::Value;
 use uuid::Uuid;
 
-use crate::PropertyInstanceGetter;
-use crate::PropertyInstanceSetter;
+use crate::generate_id;
+use crate::get_factory_registry;
+use crate::reactive_property_container::share_revision;
+use crate::BehaviourName;
+use crate::BehaviourStateError;
+use crate::BehaviourStateMachine;
+use crate::Component;
+use crate::ComponentName;
+use crate::ComponentOrEntityTypeName;
+use crate::InstanceConversionError;
+use crate::InstanceDescription;
+use crate::InstanceEvent;
+use crate::InstanceObservers;
+use crate::PropertyMap;
 use crate::ReactiveEntityInstance;
+use crate::ReactivePropertyContainer;
 use crate::ReactivePropertyInstance;
 use crate::RelationInstance;
+use crate::RelationInstanceKey;
+use crate::RelationKey;
+use crate::RelationType;
+use crate::RelationTypeName;
+use crate::Revision;
+use crate::RevisionError;
+use crate::StrictPropertyMode;
+use crate::UnknownPropertyPolicy;
+use crate::{suspend_instance_propagation, PropagationGuard, SuspensionMode};
+use crate::{CompositionChange, CompositionEvent, CompositionTarget};
+use crate::{EdgeKey, Identifier};
 
 /// Reactive instance of a relation in the directed property graph.
 ///
@@ -36,215 +61,575 @@ use crate::RelationInstance;
 /// representations like the current camera of a player:
 /// Player--(CurrentCamera)-->Camera
 ///
+/// Ownership: The relation instance holds strong references to the outbound
+/// and inbound entity instances. Cycles are not prevented by the types: an
+/// object which is owned by an entity instance, e.g. a behaviour, and holds a
+/// strong reference to a connected relation instance keeps both alive. Such
+/// objects should refer to instances by weak handles, see `RelationInstanceHandle`.
+///
 pub struct ReactiveRelationInstance {
     /// The outbound entity instance.
     pub outbound: Arc<ReactiveEntityInstance>,
 
-    /// The name of the relation type.
-    pub type_name: String,
+    /// The name of the relation type. The name is part of the edge key, so it can't be changed.
+    pub(crate) type_name: RelationTypeName,
 
     /// The outbound entity instance.
     pub inbound: Arc<ReactiveEntityInstance>,
 
     /// An optional description of the relation.
-    pub description: String,
+    pub description: InstanceDescription,
 
-    /// The reactive properties.
-    pub properties: DashMap<String, ReactivePropertyInstance>,
+    /// The reactive properties keyed by their interned names. Looking up a property takes the
+    /// lock of the shard of the map which contains the name, adding and removing properties
+    /// write-locks the shard. The value of a property is guarded by the lock of the property
+    /// instance, so setting a value doesn't block readers of other properties.
+    pub properties: DashMap<Arc<str>, ReactivePropertyInstance>,
 
     /// The names of the components which are applied on this relation instance.
-    pub components: DashSet<String>,
+    pub components: DashSet<ComponentName>,
+
+    /// The labels of this relation instance, e.g. `debug` or `level:3`.
+    pub labels: DashSet<String>,
 
     /// The names of the behaviours which are applied on this relation instance.
-    pub behaviours: DashSet<String>,
+    pub behaviours: DashSet<BehaviourName>,
+
+    /// The state machines of the behaviours which have a lifecycle.
+    pub behaviour_states: DashMap<BehaviourName, BehaviourStateMachine>,
+
+    /// Handling of values which are set on properties which don't exist.
+    pub strict_mode: StrictPropertyMode,
+
+    /// The revision is bumped on every mutation of this relation instance.
+    pub revision: Revision,
+
+    /// The observers of all properties and the event observers of this relation instance.
+    pub observers: Arc<InstanceObservers>,
+
+    /// The edge key, which is computed once at construction.
+    pub(crate) key: EdgeKey,
+
+    /// The id of the relation instance, which is derived from the relation key. The property
+    /// instances of the relation instance have the same id.
+    pub id: Uuid,
+}
+
+/// Returns the edge key of a relation instance between the given entity instances.
+pub(crate) fn create_edge_key(outbound_id: Uuid, type_name: &str, inbound_id: Uuid) -> Result<EdgeKey, InstanceConversionError> {
+    let t = Identifier::new(type_name).map_err(|_| InstanceConversionError::InvalidIdentifier(type_name.to_string()))?;
+    Ok(EdgeKey::new(outbound_id, t, inbound_id))
 }
 
 impl ReactiveRelationInstance {
     // TODO: rename to "from_properties"
-    pub fn from(outbound: Arc<ReactiveEntityInstance>, inbound: Arc<ReactiveEntityInstance>, properties: EdgeProperties) -> ReactiveRelationInstance {
-        let type_name = properties.edge.key.t.to_string();
+    /// Creates a reactive relation instance from the properties of an edge of the graph database.
+    ///
+    /// Fails, if the type of the edge is not a valid identifier. Use the `TryFrom` implementation
+    /// to also validate the ends and the property names.
+    #[cfg(feature = "graph-db")]
+    pub fn from(
+        outbound: Arc<ReactiveEntityInstance>,
+        inbound: Arc<ReactiveEntityInstance>,
+        properties: EdgeProperties,
+    ) -> Result<ReactiveRelationInstance, InstanceConversionError> {
+        let type_name = RelationTypeName::new(properties.edge.key.t.to_string());
+        let key = create_edge_key(outbound.id, type_name.as_str(), inbound.id)?;
+        let id = RelationInstanceKey::from(key.clone()).id();
         let properties = properties
             .props
             .iter()
             .map(|named_property| {
-                (
-                    named_property.name.to_string(),
-                    ReactivePropertyInstance::new(
-                        Uuid::new_v4(), // or generate a combined uuid from "outbound_id + type + inbound_id"
-                        named_property.name.to_string(),
-                        named_property.value.clone(),
-                    ),
-                )
+                let property_instance = ReactivePropertyInstance::new(id, named_property.name.as_str(), named_property.value.clone());
+                (property_instance.name.clone(), property_instance)
             })
             .collect();
-        ReactiveRelationInstance {
+        let relation_instance = ReactiveRelationInstance {
             outbound,
             type_name,
             inbound,
-            description: String::new(),
+            description: InstanceDescription::default(),
             properties,
             components: DashSet::new(),
+            labels: DashSet::new(),
             behaviours: DashSet::new(),
+            behaviour_states: DashMap::new(),
+            strict_mode: StrictPropertyMode::default(),
+            revision: Revision::default(),
+            observers: Arc::default(),
+            key,
+            id,
+        };
+        share_revision(&relation_instance.properties, &relation_instance.revision);
+        Ok(relation_instance)
+    }
+
+    /// Creates a reactive relation instance from the given relation instance. The mutability of
+    /// the property types is not part of the relation instance, so the properties are mutable.
+    /// Fails, if the type name is not a valid identifier.
+    pub fn from_instance(
+        outbound: Arc<ReactiveEntityInstance>,
+        inbound: Arc<ReactiveEntityInstance>,
+        instance: RelationInstance,
+    ) -> Result<ReactiveRelationInstance, InstanceConversionError> {
+        ReactiveRelationInstance::builder(outbound, instance.type_name, inbound)
+            .description(instance.description)
+            .properties(instance.properties)
+            .components(instance.components)
+            .labels(instance.labels)
+            .build()
+    }
+
+    /// Creates a relation instance of the given relation type between the given entity instances.
+    ///
+    /// The properties of the components of the relation type and the properties of the relation
+    /// type are initialized with the default values of their data types. Properties of the relation
+    /// type override properties of components with the same name. Finally the hooks of the
+    /// factory registry are invoked.
+    pub fn new_from_type(
+        outbound: Arc<ReactiveEntityInstance>,
+        relation_type: &RelationType,
+        inbound: Arc<ReactiveEntityInstance>,
+        components: &[Component],
+    ) -> Result<ReactiveRelationInstance, RelationInstanceTypeError> {
+        if !relation_type.is_instantiable() {
+            return Err(RelationInstanceTypeError::Abstract(relation_type.type_name.to_string()));
+        }
+        if !relation_type.outbound_type.matches_entity_instance(&outbound) {
+            return Err(RelationInstanceTypeError::InvalidOutbound(outbound.id, relation_type.outbound_type.clone()));
         }
+        if !relation_type.inbound_type.matches_entity_instance(&inbound) {
+            return Err(RelationInstanceTypeError::InvalidInbound(inbound.id, relation_type.inbound_type.clone()));
+        }
+        let key = create_edge_key(outbound.id, relation_type.type_name.as_str(), inbound.id)
+            .map_err(|_| RelationInstanceTypeError::InvalidTypeName(relation_type.type_name.to_string()))?;
+        let id = RelationInstanceKey::from(key.clone()).id();
+        let properties = DashMap::new();
+        let property_types = components
+            .iter()
+            .filter(|component| relation_type.components.contains(&component.name))
+            .flat_map(|component| component.properties.iter())
+            .chain(relation_type.properties.iter());
+        for property_type in property_types {
+            let property_instance = ReactivePropertyInstance::from_property_type(id, property_type);
+            properties.insert(property_instance.name.clone(), property_instance);
+        }
+        let relation_instance = ReactiveRelationInstance {
+            outbound,
+            type_name: relation_type.type_name.clone(),
+            inbound,
+            description: InstanceDescription::default(),
+            properties,
+            components: relation_type.components.iter().cloned().collect(),
+            labels: DashSet::new(),
+            behaviours: DashSet::new(),
+            behaviour_states: DashMap::new(),
+            strict_mode: StrictPropertyMode::default(),
+            revision: Revision::default(),
+            observers: Arc::default(),
+            key,
+            id,
+        };
+        share_revision(&relation_instance.properties, &relation_instance.revision);
+        get_factory_registry().apply_relation_hooks(&relation_instance);
+        Ok(relation_instance)
     }
 
-    pub fn from_instance(outbound: Arc<ReactiveEntityInstance>, inbound: Arc<ReactiveEntityInstance>, instance: RelationInstance) -> ReactiveRelationInstance {
-        let properties = instance
+    /// Returns a copy of this relation instance between the given entity instances.
+    ///
+    /// The property instances share the streams of this relation instance, so observers
+    /// which are subscribed to the properties are preserved. The observers which have been
+    /// registered through the property instances are moved to the copy, so destroying this
+    /// relation instance keeps them. The values, the configuration of the properties, the
+    /// components and the behaviours are copied.
+    pub fn rewired(&self, outbound: Arc<ReactiveEntityInstance>, inbound: Arc<ReactiveEntityInstance>) -> ReactiveRelationInstance {
+        let key = EdgeKey::new(outbound.id, self.key.t.clone(), inbound.id);
+        let id = RelationInstanceKey::from(key.clone()).id();
+        let properties = self
             .properties
             .iter()
-            .map(|(name, value)| (name.clone(), ReactivePropertyInstance::new(Uuid::new_v4(), name.clone(), value.clone())))
+            .map(|shared_property_instance| {
+                let property_instance = ReactivePropertyInstance {
+                    id,
+                    ..shared_property_instance.share()
+                };
+                // The observers are preserved, even if this relation instance is destroyed
+                property_instance.take_observer_handles(&shared_property_instance);
+                (property_instance.name.clone(), property_instance)
+            })
             .collect();
-        ReactiveRelationInstance {
+        let relation_instance = ReactiveRelationInstance {
             outbound,
-            type_name: instance.type_name.clone(),
+            type_name: self.type_name.clone(),
             inbound,
-            description: instance.description,
+            description: self.description.clone(),
             properties,
-            components: DashSet::new(),
-            behaviours: DashSet::new(),
-        }
+            components: self.components.iter().map(|component| component.clone()).collect(),
+            labels: self.labels.iter().map(|label| label.clone()).collect(),
+            behaviours: self.behaviours.iter().map(|behaviour| behaviour.clone()).collect(),
+            behaviour_states: self.behaviour_states.iter().map(|entry| (entry.key().clone(), entry.value().clone())).collect(),
+            strict_mode: StrictPropertyMode::new(self.strict_mode.get()),
+            revision: Revision::default(),
+            observers: Arc::default(),
+            key,
+            id,
+        };
+        share_revision(&relation_instance.properties, &relation_instance.revision);
+        relation_instance
     }
 
-    // TODO: unit test
-    // TODO: rename to "new_with_properties"
-    pub fn create_with_properties<S: Into<String>>(
-        outbound: Arc<ReactiveEntityInstance>,
-        type_name: S,
-        inbound: Arc<ReactiveEntityInstance>,
-        properties: HashMap<String, Value>,
-    ) -> ReactiveRelationInstance {
-        let properties = properties
+    /// Creates an independent copy of this relation instance between the given entity instances.
+    ///
+    /// Unlike a rewired relation instance, the property instances don't share the streams of this
+    /// relation instance. The values and components are copied, the behaviours are not.
+    pub fn duplicate(&self, outbound: Arc<ReactiveEntityInstance>, inbound: Arc<ReactiveEntityInstance>) -> ReactiveRelationInstance {
+        let key = EdgeKey::new(outbound.id, self.key.t.clone(), inbound.id);
+        let id = RelationInstanceKey::from(key.clone()).id();
+        let properties = self
+            .properties
             .iter()
-            .map(|(name, value)| {
-                (
-                    name.clone(),
-                    ReactivePropertyInstance::new(
-                        Uuid::new_v4(), // or generate a combined uuid from "outbound_id + type + inbound_id"
-                        name.clone(),
-                        value.clone(),
-                    ),
-                )
-            })
+            .map(|property_instance| (property_instance.key().clone(), property_instance.duplicate(id)))
             .collect();
-        ReactiveRelationInstance {
+        let relation_instance = ReactiveRelationInstance {
             outbound,
-            type_name: type_name.into(),
+            type_name: self.type_name.clone(),
             inbound,
-            description: String::new(),
+            description: self.description.clone(),
             properties,
-            components: DashSet::new(),
+            components: self.components.clone(),
+            labels: self.labels.clone(),
             behaviours: DashSet::new(),
-        }
+            behaviour_states: DashMap::new(),
+            strict_mode: StrictPropertyMode::new(self.strict_mode.get()),
+            revision: Revision::default(),
+            observers: Arc::default(),
+            key,
+            id,
+        };
+        share_revision(&relation_instance.properties, &relation_instance.revision);
+        relation_instance
     }
 
+    /// Creates a reactive relation instance with the given properties. Fails, if the type name is
+    /// not a valid identifier.
+    pub fn create_with_properties<S: Into<RelationTypeName>>(
+        outbound: Arc<ReactiveEntityInstance>,
+        type_name: S,
+        inbound: Arc<ReactiveEntityInstance>,
+        properties: PropertyMap,
+    ) -> Result<ReactiveRelationInstance, InstanceConversionError> {
+        ReactiveRelationInstance::builder(outbound, type_name, inbound).properties(properties).build()
+    }
+
+    /// Returns the name of the relation type.
+    pub fn type_name(&self) -> &RelationTypeName {
+        &self.type_name
+    }
+
+    /// Returns the edge key of this relation instance.
+    pub fn key(&self) -> &EdgeKey {
+        &self.key
+    }
+
+    /// Returns the edge key of this relation instance. The edge key is always present.
     pub fn get_key(&self) -> Option<EdgeKey> {
-        Identifier::new(self.type_name.as_str())
-            .map(|t| EdgeKey::new(self.outbound.id, t, self.inbound.id))
-            .ok()
+        Some(self.key.clone())
     }
 
-    pub fn tick(&self) {
-        for property_instance in &self.properties {
-            property_instance.tick();
-        }
+    /// Returns the relation key of this relation instance.
+    pub fn get_relation_key(&self) -> RelationKey {
+        RelationKey::from(self)
     }
 
-    pub fn add_property<S: Into<String>>(&self, name: S, value: Value) {
-        let name = name.into();
-        if !self.properties.contains_key(name.as_str()) {
-            let property_instance = ReactivePropertyInstance::new(Uuid::new_v4(), name.clone(), value);
-            self.properties.insert(name, property_instance);
-        }
+    /// Switches how values are handled which are set on properties which don't exist.
+    pub fn set_unknown_property_policy(&self, policy: UnknownPropertyPolicy) {
+        self.strict_mode.set(policy);
+    }
+
+    /// Suspends the propagation of the properties of this relation instance as long as the guard exists.
+    pub fn suspend_propagation(&self, mode: SuspensionMode) -> PropagationGuard {
+        suspend_instance_propagation(vec![self.id], mode)
+    }
+
+    /// Returns the revision of this relation instance.
+    pub fn revision(&self) -> u64 {
+        self.revision.get()
+    }
+
+    /// Sets the value of the property, if the revision equals the expected revision. The revision
+    /// is compared and bumped while the value of the property is locked. Returns the new revision.
+    pub fn set_if_revision<S: AsRef<str>>(&self, property_name: S, value: Value, expected_revision: u64) -> Result<u64, RevisionError> {
+        let instance = self
+            .properties
+            .get(property_name.as_ref())
+            .ok_or_else(|| RevisionError::MissingProperty(property_name.as_ref().to_string()))?;
+        instance.set_if_revision(value, expected_revision)
+    }
+
+    /// Returns true, if both are the same reactive relation instance and not only equal.
+    pub fn same_instance(self: &Arc<Self>, other: &Arc<ReactiveRelationInstance>) -> bool {
+        Arc::ptr_eq(self, other)
+    }
+
+    /// Changes the description of this relation instance and notifies the observers of the description.
+    pub fn set_description<S: Into<String>>(&self, description: S) {
+        self.description.set(description);
+        self.revision.bump();
+    }
+
+    /// Observes all properties of this relation instance, including the properties which are added
+    /// later. Returns the handle id of the observer.
+    pub fn observe_all<F: Fn(&str, &Value) + Send + Sync + 'static>(&self, observer: F) -> u128 {
+        let handle_id = generate_id().as_u128();
+        self.observers.observe_properties(&self.properties, handle_id, Arc::new(observer));
+        handle_id
+    }
+
+    /// Removes the observer of all properties with the given handle id.
+    pub fn remove_all_observer(&self, handle_id: u128) {
+        self.observers.remove(handle_id);
     }
 
-    pub fn add_component<S: Into<String>>(&self, component: S) {
-        self.components.insert(component.into());
+    /// Observes the changed and added properties and the changes of the composition of this relation
+    /// instance. Returns the handle id of the observer.
+    pub fn observe_events<F: Fn(&InstanceEvent) + Send + Sync + 'static>(&self, observer: F) -> u128 {
+        let handle_id = generate_id().as_u128();
+        self.observers.observe_events(&self.properties, handle_id, Arc::new(observer));
+        handle_id
     }
 
-    pub fn remove_component<S: Into<String>>(&self, component: S) {
-        self.components.remove(component.into().as_str());
+    /// Removes the event observer with the given handle id.
+    pub fn remove_event_observer(&self, handle_id: u128) {
+        self.observers.remove(handle_id);
+    }
+
+    /// Observes the components and behaviours which are added to or removed from this relation instance.
+    pub fn observe_composition<F: Fn(&CompositionEvent) + Send + Sync + 'static>(&self, handle_id: u128, observer: F) {
+        self.observers.observe_composition(handle_id, Arc::new(observer));
+    }
+
+    /// Removes the composition observer with the given handle id.
+    pub fn remove_composition_observer(&self, handle_id: u128) {
+        self.observers.remove(handle_id);
+    }
+
+    pub fn add_component<C: Into<ComponentName>>(&self, component: C) {
+        let component = component.into();
+        if self.components.insert(component.clone()) {
+            self.observers.notify_composition(
+                || CompositionTarget::Relation(RelationInstanceKey::from(self)),
+                CompositionChange::ComponentAdded,
+                component.as_str(),
+            );
+        }
+        self.revision.bump();
+    }
+
+    pub fn remove_component<C: Into<ComponentName>>(&self, component: C) {
+        let component = component.into();
+        if self.components.remove(component.as_str()).is_some() {
+            self.observers.notify_composition(
+                || CompositionTarget::Relation(RelationInstanceKey::from(self)),
+                CompositionChange::ComponentRemoved,
+                component.as_str(),
+            );
+        }
+        self.revision.bump();
     }
 
     /// Returns true, if the relation instance is composed with the given component.
-    pub fn is_a<S: Into<String>>(&self, component: S) -> bool {
+    pub fn is_a<C: Into<ComponentName>>(&self, component: C) -> bool {
         self.components.contains(component.into().as_str())
     }
 
-    pub fn add_behaviour<S: Into<String>>(&self, behaviour: S) {
-        self.behaviours.insert(behaviour.into());
+    pub fn add_behaviour<B: Into<BehaviourName>>(&self, behaviour: B) {
+        let behaviour = behaviour.into();
+        if self.behaviours.insert(behaviour.clone()) {
+            self.observers.notify_composition(
+                || CompositionTarget::Relation(RelationInstanceKey::from(self)),
+                CompositionChange::BehaviourAdded,
+                behaviour.as_str(),
+            );
+        }
+        self.revision.bump();
     }
 
-    pub fn remove_behaviour<S: Into<String>>(&self, behaviour: S) {
-        self.behaviours.remove(behaviour.into().as_str());
+    pub fn remove_behaviour<B: Into<BehaviourName>>(&self, behaviour: B) {
+        let behaviour = behaviour.into();
+        if self.behaviours.remove(behaviour.as_str()).is_some() {
+            self.observers.notify_composition(
+                || CompositionTarget::Relation(RelationInstanceKey::from(self)),
+                CompositionChange::BehaviourRemoved,
+                behaviour.as_str(),
+            );
+        }
+        self.behaviour_states.remove(behaviour.as_str());
+        self.revision.bump();
     }
 
     /// Returns true, if the relation instance behaves as the given behaviour.
-    pub fn behaves_as<S: Into<String>>(&self, behaviour: S) -> bool {
+    pub fn behaves_as<B: Into<BehaviourName>>(&self, behaviour: B) -> bool {
         self.behaviours.contains(behaviour.into().as_str())
     }
+
+    /// Attaches the state machine to the behaviour with the given name.
+    pub fn set_behaviour_state_machine<B: Into<BehaviourName>>(&self, behaviour: B, state_machine: BehaviourStateMachine) {
+        self.behaviour_states.insert(behaviour.into(), state_machine);
+        self.revision.bump();
+    }
+
+    /// Returns the state machine of the behaviour with the given name.
+    pub fn get_behaviour_state_machine<S: AsRef<str>>(&self, behaviour: S) -> Option<BehaviourStateMachine> {
+        self.behaviour_states.get(behaviour.as_ref()).map(|state_machine| state_machine.clone())
+    }
+
+    /// Returns the current state of the behaviour with the given name.
+    pub fn get_behaviour_state<S: AsRef<str>>(&self, behaviour: S) -> Option<String> {
+        self.behaviour_states
+            .get(behaviour.as_ref())
+            .map(|state_machine| state_machine.get_state().to_string())
+    }
+
+    /// Changes the state of the behaviour with the given name.
+    pub fn transition_behaviour<B: AsRef<str>, S: Into<String>>(&self, behaviour: B, state: S) -> Result<(), BehaviourStateError> {
+        let mut state_machine = self
+            .behaviour_states
+            .get_mut(behaviour.as_ref())
+            .ok_or_else(|| BehaviourStateError::MissingStateMachine(behaviour.as_ref().to_string()))?;
+        state_machine.transition_to(state)?;
+        self.revision.bump();
+        Ok(())
+    }
+}
+
+/// Fails, if the entity instances are not the ends of the edge, if the type name or a property
+/// name is invalid or if a property occurs multiple times.
+#[cfg(feature = "graph-db")]
+impl TryFrom<(Arc<ReactiveEntityInstance>, Arc<ReactiveEntityInstance>, &EdgeProperties)> for ReactiveRelationInstance {
+    type Error = InstanceConversionError;
+
+    fn try_from(
+        (outbound, inbound, properties): (Arc<ReactiveEntityInstance>, Arc<ReactiveEntityInstance>, &EdgeProperties),
+    ) -> Result<Self, InstanceConversionError> {
+        let relation_instance = RelationInstance::try_from(properties)?;
+        if outbound.id != relation_instance.outbound_id {
+            return Err(InstanceConversionError::MissingOutbound(relation_instance.outbound_id));
+        }
+        if inbound.id != relation_instance.inbound_id {
+            return Err(InstanceConversionError::MissingInbound(relation_instance.inbound_id));
+        }
+        ReactiveRelationInstance::from_instance(outbound, inbound, relation_instance)
+    }
 }
 
 impl From<Arc<ReactiveRelationInstance>> for RelationInstance {
     fn from(instance: Arc<ReactiveRelationInstance>) -> Self {
-        let properties = instance
+        let mut properties: Vec<(String, Value)> = instance
             .properties
             .iter()
-            .map(|property_instance| (property_instance.key().clone(), property_instance.get()))
+            .map(|property_instance| (property_instance.key().to_string(), property_instance.get()))
             .collect();
+        // The reactive properties are unordered, so the properties are sorted by name
+        properties.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let mut components: Vec<ComponentName> = instance.components.iter().map(|component| component.clone()).collect();
+        components.sort();
+        let mut labels: Vec<String> = instance.labels.iter().map(|label| label.clone()).collect();
+        labels.sort();
         RelationInstance {
             outbound_id: instance.outbound.id,
             type_name: instance.type_name.clone(),
             inbound_id: instance.inbound.id,
-            description: instance.description.clone(),
-            properties,
+            description: instance.description.get(),
+            properties: properties.into_iter().collect(),
+            components,
+            labels,
+            behaviours: Vec::new(),
         }
     }
 }
 
-impl PropertyInstanceGetter for ReactiveRelationInstance {
-    fn get<S: Into<String>>(&self, property_name: S) -> Option<Value> {
-        self.properties.get(&property_name.into()).map(|p| p.get())
+impl RelationInstance {
+    /// Converts the reactive relation instance and persists the names of the applied behaviours,
+    /// so that they can be re-attached after a restart.
+    pub fn from_reactive_with_behaviours(instance: Arc<ReactiveRelationInstance>) -> RelationInstance {
+        let mut behaviours: Vec<BehaviourName> = instance.behaviours.iter().map(|behaviour| behaviour.clone()).collect();
+        behaviours.sort();
+        RelationInstance {
+            behaviours,
+            ..RelationInstance::from(instance)
+        }
     }
+}
 
-    fn as_bool<S: Into<String>>(&self, property_name: S) -> Option<bool> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_bool())
+impl ReactivePropertyContainer for ReactiveRelationInstance {
+    fn properties(&self) -> &DashMap<Arc<str>, ReactivePropertyInstance> {
+        &self.properties
     }
 
-    fn as_u64<S: Into<String>>(&self, property_name: S) -> Option<u64> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_u64())
+    fn revision_counter(&self) -> &Revision {
+        &self.revision
     }
 
-    fn as_i64<S: Into<String>>(&self, property_name: S) -> Option<i64> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_i64())
+    fn unknown_property_policy(&self) -> UnknownPropertyPolicy {
+        self.strict_mode.get()
     }
 
-    fn as_f64<S: Into<String>>(&self, property_name: S) -> Option<f64> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_f64())
+    fn new_property_id(&self) -> Uuid {
+        self.id
     }
 
-    fn as_string<S: Into<String>>(&self, property_name: S) -> Option<String> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_string())
+    fn observers(&self) -> &InstanceObservers {
+        &self.observers
     }
+}
 
-    fn as_array<S: Into<String>>(&self, property_name: S) -> Option<Vec<Value>> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_array())
+/// Reactive relation instances are equal, if the edge keys are equal.
+impl PartialEq for ReactiveRelationInstance {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
     }
+}
+
+impl Eq for ReactiveRelationInstance {}
 
-    fn as_object<S: Into<String>>(&self, property_name: S) -> Option<Map<String, Value>> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_object())
+/// The hash of a reactive relation instance is the hash of its edge key.
+impl Hash for ReactiveRelationInstance {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key.hash(state);
     }
 }
 
-impl PropertyInstanceSetter for ReactiveRelationInstance {
-    fn set<S: Into<String>>(&self, property_name: S, value: Value) {
-        if let Some(instance) = self.properties.get(&property_name.into()) {
-            instance.set(value);
-        }
+/// Formats the relation instance as `outbound--(type_name)-->inbound`, e.g. `player(..)--(looks_at)-->camera(..)`.
+/// The relation key is returned by `get_relation_key`.
+impl fmt::Display for ReactiveRelationInstance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}--({})-->{}", self.outbound, self.type_name, self.inbound)
     }
+}
+
+/// The reason why a relation instance cannot be created from a relation type.
+#[derive(Debug, PartialEq, Eq)]
+pub enum RelationInstanceTypeError {
+    /// The relation type with the given name is abstract.
+    Abstract(String),
+
+    /// The outbound entity instance with the given id doesn't match the outbound type.
+    InvalidOutbound(Uuid, ComponentOrEntityTypeName),
+
+    /// The inbound entity instance with the given id doesn't match the inbound type.
+    InvalidInbound(Uuid, ComponentOrEntityTypeName),
+
+    /// The name of the relation type is not a valid identifier.
+    InvalidTypeName(String),
+}
 
-    fn set_no_propagate<S: Into<String>>(&self, property_name: S, value: Value) {
-        if let Some(instance) = self.properties.get(&property_name.into()) {
-            instance.set_no_propagate(value);
+impl fmt::Display for RelationInstanceTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RelationInstanceTypeError::Abstract(type_name) => write!(f, "The relation type {} is abstract", type_name),
+            RelationInstanceTypeError::InvalidOutbound(id, outbound_type) => write!(f, "The outbound entity instance {} is not a {}", id, outbound_type),
+            RelationInstanceTypeError::InvalidInbound(id, inbound_type) => write!(f, "The inbound entity instance {} is not a {}", id, inbound_type),
+            RelationInstanceTypeError::InvalidTypeName(type_name) => write!(f, "The relation type name {} is not a valid identifier", type_name),
         }
     }
 }
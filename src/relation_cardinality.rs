@@ -0,0 +1,108 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// The number of relation instances of a relation type an entity instance may have.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[serde(rename_all = "lowercase")]
+pub enum Cardinality {
+    /// Exactly one relation instance.
+    One,
+
+    /// At most one relation instance.
+    Optional,
+
+    /// Any number of relation instances.
+    #[default]
+    Many,
+}
+
+impl Cardinality {
+    pub fn many() -> Self {
+        Cardinality::Many
+    }
+
+    /// Returns the maximum number of relation instances, if limited.
+    pub fn max(&self) -> Option<usize> {
+        match self {
+            Cardinality::One | Cardinality::Optional => Some(1),
+            Cardinality::Many => None,
+        }
+    }
+
+    /// Returns true, if another relation instance may be added to the given number of relation instances.
+    pub fn allows(&self, count: usize) -> bool {
+        self.max().is_none_or(|max| count < max)
+    }
+}
+
+/// Parses the name of the cardinality, ignoring the case.
+impl FromStr for Cardinality {
+    type Err = CardinalityParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "one" => Ok(Self::One),
+            "optional" => Ok(Self::Optional),
+            "many" => Ok(Self::Many),
+            _ => Err(CardinalityParseError::Unknown(value.to_string())),
+        }
+    }
+}
+
+impl TryFrom<&str> for Cardinality {
+    type Error = CardinalityParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Cardinality::from_str(value)
+    }
+}
+
+impl fmt::Display for Cardinality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CardinalityParseError {
+    /// The cardinality with the given name doesn't exist.
+    Unknown(String),
+}
+
+impl fmt::Display for CardinalityParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CardinalityParseError::Unknown(value) => write!(f, "The cardinality {} doesn't exist", value),
+        }
+    }
+}
+
+impl std::error::Error for CardinalityParseError {}
+
+/// The reason why another relation instance cannot be created.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CardinalityError {
+    /// The outbound entity instance already has the maximum number of relation instances.
+    OutboundExceeded(Uuid, Cardinality),
+
+    /// The inbound entity instance already has the maximum number of relation instances.
+    InboundExceeded(Uuid, Cardinality),
+}
+
+impl fmt::Display for CardinalityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CardinalityError::OutboundExceeded(id, cardinality) => {
+                write!(f, "The outbound entity instance {} cannot have another relation instance (cardinality {})", id, cardinality)
+            }
+            CardinalityError::InboundExceeded(id, cardinality) => {
+                write!(f, "The inbound entity instance {} cannot have another relation instance (cardinality {})", id, cardinality)
+            }
+        }
+    }
+}
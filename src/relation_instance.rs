@@ -1,12 +1,21 @@
-use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 
-use indradb::{EdgeKey, EdgeProperties, Identifier};
+#[cfg(feature = "graph-db")]
+use indradb::EdgeProperties;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 use uuid::Uuid;
 
-use crate::{MutablePropertyInstanceSetter, PropertyInstanceGetter};
+#[cfg(feature = "graph-db")]
+use crate::instance_conversion::{convert_named_properties, convert_type_name};
+use crate::instance_labels::deserialize_labels;
+#[cfg(feature = "graph-db")]
+use crate::InstanceConversionError;
+use crate::{BehaviourName, ComponentName};
+use crate::{EdgeKey, Identifier};
+use crate::{MutablePropertyInstanceSetter, PropertyDiff, PropertyInstanceGetter, PropertyMap, RelationKey, RelationTypeName};
 
 /// Relation instances are edges from an outbound entity instance to an
 /// inbound entity instance.
@@ -21,13 +30,15 @@ use crate::{MutablePropertyInstanceSetter, PropertyInstanceGetter};
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct RelationInstance {
     /// The id of the outbound vertex.
+    #[serde(alias = "outbound")]
     pub outbound_id: Uuid,
 
     /// The name of the relation type
     #[serde(alias = "type")]
-    pub type_name: String,
+    pub type_name: RelationTypeName,
 
     /// The id of the inbound vertex.
+    #[serde(alias = "inbound")]
     pub inbound_id: Uuid,
 
     /// Textual description of the relation instance.
@@ -40,30 +51,56 @@ pub struct RelationInstance {
     /// a representation of a JSON. Therefore the value can be boolean, number, string,
     /// array or an object. For more information about the data types please look at
     /// https://docs.serde.rs/serde_json/value/enum.Value.html
-    #[serde(default = "HashMap::new")]
-    pub properties: HashMap<String, Value>,
+    #[serde(default = "PropertyMap::new")]
+    pub properties: PropertyMap,
+
+    /// The names of the components which are applied on the relation instance.
+    ///
+    /// By default, no components are applied.
+    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<ComponentName>,
+
+    /// The labels of the relation instance, e.g. `debug` or `level:3`.
+    ///
+    /// Labels group instances independently of their type. By default, the relation instance
+    /// has no labels. Duplicate labels are removed on deserialization.
+    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty", deserialize_with = "deserialize_labels")]
+    pub labels: Vec<String>,
+
+    /// The names of the behaviours which were applied on the relation instance.
+    ///
+    /// The behaviours are only persisted on request, so that the runtime can re-attach them.
+    /// By default, no behaviours are persisted.
+    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    pub behaviours: Vec<BehaviourName>,
 }
 
 impl RelationInstance {
     /// Constructs a new relation instance with the given outbound_id, type, inbound_id and properties
-    pub fn new(outbound_id: Uuid, type_name: String, inbound_id: Uuid, properties: HashMap<String, Value>) -> RelationInstance {
+    pub fn new<S: Into<RelationTypeName>>(outbound_id: Uuid, type_name: S, inbound_id: Uuid, properties: PropertyMap) -> RelationInstance {
         RelationInstance {
             outbound_id,
-            type_name,
+            type_name: type_name.into(),
             inbound_id,
             description: String::new(),
             properties,
+            components: Vec::new(),
+            labels: Vec::new(),
+            behaviours: Vec::new(),
         }
     }
 
     /// Constructs a new relation instance with the given outbound_id, type, inbound_id but without properties
-    pub fn new_without_properties<S: Into<String>>(outbound_id: Uuid, type_name: S, inbound_id: Uuid) -> RelationInstance {
+    pub fn new_without_properties<S: Into<RelationTypeName>>(outbound_id: Uuid, type_name: S, inbound_id: Uuid) -> RelationInstance {
         RelationInstance {
             outbound_id,
             type_name: type_name.into(),
             inbound_id,
             description: String::new(),
-            properties: HashMap::new(),
+            properties: PropertyMap::new(),
+            components: Vec::new(),
+            labels: Vec::new(),
+            behaviours: Vec::new(),
         }
     }
 
@@ -72,57 +109,133 @@ impl RelationInstance {
             .map(|t| EdgeKey::new(self.outbound_id, t, self.inbound_id))
             .ok()
     }
+
+    /// Returns the relation key of this relation instance.
+    pub fn get_relation_key(&self) -> RelationKey {
+        RelationKey::from(self)
+    }
+
+    /// Returns the changes which turn the properties of this relation instance into the properties
+    /// of the given relation instance.
+    pub fn diff(&self, other: &RelationInstance) -> PropertyDiff {
+        PropertyDiff::new(&self.properties, &other.properties)
+    }
+
+    /// Applies the changes to the properties of this relation instance.
+    pub fn apply(&mut self, diff: PropertyDiff) {
+        diff.apply_to(&mut self.properties);
+    }
 }
 
+#[cfg(feature = "graph-db")]
 impl From<EdgeProperties> for RelationInstance {
     fn from(properties: EdgeProperties) -> Self {
         RelationInstance {
             outbound_id: properties.edge.key.outbound_id,
-            type_name: properties.edge.key.t.to_string(),
+            type_name: RelationTypeName::new(properties.edge.key.t.to_string()),
             inbound_id: properties.edge.key.inbound_id,
             description: String::new(),
             properties: properties.props.iter().map(|p| (p.name.to_string(), p.value.clone())).collect(),
+            components: Vec::new(),
+            labels: Vec::new(),
+            behaviours: Vec::new(),
+        }
+    }
+}
+
+/// Fails, if an end is missing, if the type name or a property name is invalid or if a property
+/// occurs multiple times.
+#[cfg(feature = "graph-db")]
+impl TryFrom<&EdgeProperties> for RelationInstance {
+    type Error = InstanceConversionError;
+
+    fn try_from(properties: &EdgeProperties) -> Result<Self, InstanceConversionError> {
+        let key = &properties.edge.key;
+        if key.outbound_id.is_nil() {
+            return Err(InstanceConversionError::MissingOutbound(key.outbound_id));
         }
+        if key.inbound_id.is_nil() {
+            return Err(InstanceConversionError::MissingInbound(key.inbound_id));
+        }
+        Ok(RelationInstance {
+            outbound_id: key.outbound_id,
+            type_name: RelationTypeName::new(convert_type_name(&key.t)?),
+            inbound_id: key.inbound_id,
+            description: String::new(),
+            properties: convert_named_properties(&properties.props)?,
+            components: Vec::new(),
+            labels: Vec::new(),
+            behaviours: Vec::new(),
+        })
     }
 }
 
 impl PropertyInstanceGetter for RelationInstance {
-    fn get<S: Into<String>>(&self, property_name: S) -> Option<Value> {
-        self.properties.get(&property_name.into()).cloned()
+    fn get<S: AsRef<str>>(&self, property_name: S) -> Option<Value> {
+        self.properties.get(property_name.as_ref()).cloned()
     }
 
-    fn as_bool<S: Into<String>>(&self, property_name: S) -> Option<bool> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_bool())
+    fn with_property<S: AsRef<str>, R, F: FnOnce(&Value) -> R>(&self, property_name: S, f: F) -> Option<R> {
+        self.properties.get(property_name.as_ref()).map(f)
     }
 
-    fn as_u64<S: Into<String>>(&self, property_name: S) -> Option<u64> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_u64())
+    fn as_bool<S: AsRef<str>>(&self, property_name: S) -> Option<bool> {
+        self.properties.get(property_name.as_ref()).and_then(|p| p.as_bool())
     }
 
-    fn as_i64<S: Into<String>>(&self, property_name: S) -> Option<i64> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_i64())
+    fn as_u64<S: AsRef<str>>(&self, property_name: S) -> Option<u64> {
+        self.properties.get(property_name.as_ref()).and_then(|p| p.as_u64())
     }
 
-    fn as_f64<S: Into<String>>(&self, property_name: S) -> Option<f64> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_f64())
+    fn as_i64<S: AsRef<str>>(&self, property_name: S) -> Option<i64> {
+        self.properties.get(property_name.as_ref()).and_then(|p| p.as_i64())
     }
 
-    fn as_string<S: Into<String>>(&self, property_name: S) -> Option<String> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_str().map(|s| s.to_string()))
+    fn as_f64<S: AsRef<str>>(&self, property_name: S) -> Option<f64> {
+        self.properties.get(property_name.as_ref()).and_then(|p| p.as_f64())
     }
 
-    fn as_array<S: Into<String>>(&self, property_name: S) -> Option<Vec<Value>> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_array().map(Vec::clone))
+    fn as_string<S: AsRef<str>>(&self, property_name: S) -> Option<String> {
+        self.properties.get(property_name.as_ref()).and_then(|p| p.as_str().map(|s| s.to_string()))
     }
 
-    fn as_object<S: Into<String>>(&self, property_name: S) -> Option<Map<String, Value>> {
-        self.properties.get(&property_name.into()).and_then(|p| p.as_object()).map(Map::clone)
+    fn as_array<S: AsRef<str>>(&self, property_name: S) -> Option<Vec<Value>> {
+        self.properties.get(property_name.as_ref()).and_then(|p| p.as_array().cloned())
+    }
+
+    fn as_object<S: AsRef<str>>(&self, property_name: S) -> Option<Map<String, Value>> {
+        self.properties.get(property_name.as_ref()).and_then(|p| p.as_object().cloned())
     }
 }
 
 impl MutablePropertyInstanceSetter for RelationInstance {
-    fn set<S: Into<String>>(&mut self, property_name: S, value: Value) {
-        let property_value = self.properties.get_mut(&property_name.into()).unwrap();
+    fn set<S: AsRef<str>>(&mut self, property_name: S, value: Value) {
+        let property_value = self.properties.get_mut(property_name.as_ref()).unwrap();
         *property_value = value
     }
 }
+
+/// Relation instances are equal, if the outbound ids, the type names and the inbound ids are equal.
+impl PartialEq for RelationInstance {
+    fn eq(&self, other: &Self) -> bool {
+        self.outbound_id == other.outbound_id && self.type_name == other.type_name && self.inbound_id == other.inbound_id
+    }
+}
+
+impl Eq for RelationInstance {}
+
+impl Hash for RelationInstance {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.outbound_id.hash(state);
+        self.type_name.hash(state);
+        self.inbound_id.hash(state);
+    }
+}
+
+/// Formats the relation instance as `outbound_id--(type_name)-->inbound_id`. The relation key is
+/// returned by `get_relation_key`.
+impl fmt::Display for RelationInstance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}--({})-->{}", self.outbound_id, self.type_name, self.inbound_id)
+    }
+}
@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use dashmap::DashSet;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::reactive_property_container::share_revision;
+use crate::reactive_relation_instance::create_edge_key;
+use crate::{
+    BehaviourName, ComponentName, InstanceConversionError, PropertyMap, ReactiveEntityInstance, ReactivePropertyInstance, ReactiveRelationInstance,
+    RelationInstance, RelationInstanceKey, RelationTypeName, Revision, SocketType, StrictPropertyMode,
+};
+
+/// Builds a relation instance step by step.
+#[derive(Clone, Debug)]
+pub struct RelationInstanceBuilder {
+    outbound_id: Uuid,
+    type_name: RelationTypeName,
+    inbound_id: Uuid,
+    description: String,
+    properties: PropertyMap,
+    components: Vec<ComponentName>,
+    labels: Vec<String>,
+    behaviours: Vec<BehaviourName>,
+}
+
+impl RelationInstanceBuilder {
+    pub fn new<S: Into<RelationTypeName>>(outbound_id: Uuid, type_name: S, inbound_id: Uuid) -> RelationInstanceBuilder {
+        RelationInstanceBuilder {
+            outbound_id,
+            type_name: type_name.into(),
+            inbound_id,
+            description: String::new(),
+            properties: PropertyMap::new(),
+            components: Vec::new(),
+            labels: Vec::new(),
+            behaviours: Vec::new(),
+        }
+    }
+
+    /// Sets the description.
+    pub fn description<S: Into<String>>(mut self, description: S) -> RelationInstanceBuilder {
+        self.description = description.into();
+        self
+    }
+
+    /// Sets the value of the property with the given name.
+    pub fn property<S: Into<String>>(mut self, property_name: S, value: Value) -> RelationInstanceBuilder {
+        self.properties.insert(property_name.into(), value);
+        self
+    }
+
+    /// Sets the values of the given properties.
+    pub fn properties(mut self, properties: PropertyMap) -> RelationInstanceBuilder {
+        self.properties.extend(properties);
+        self
+    }
+
+    /// Adds the component with the given name.
+    pub fn component<C: Into<ComponentName>>(mut self, component: C) -> RelationInstanceBuilder {
+        self.components.push(component.into());
+        self
+    }
+
+    /// Adds the label, e.g. `debug` or `level:3`. Labels which have been added already are ignored.
+    pub fn label<S: Into<String>>(mut self, label: S) -> RelationInstanceBuilder {
+        let label = label.into();
+        if !self.labels.contains(&label) {
+            self.labels.push(label);
+        }
+        self
+    }
+
+    /// Adds the behaviour with the given name, which has to be re-attached by the runtime.
+    pub fn behaviour<B: Into<BehaviourName>>(mut self, behaviour: B) -> RelationInstanceBuilder {
+        self.behaviours.push(behaviour.into());
+        self
+    }
+
+    /// Builds the relation instance.
+    pub fn build(self) -> RelationInstance {
+        RelationInstance {
+            outbound_id: self.outbound_id,
+            type_name: self.type_name,
+            inbound_id: self.inbound_id,
+            description: self.description,
+            properties: self.properties,
+            components: self.components,
+            labels: self.labels,
+            behaviours: self.behaviours,
+        }
+    }
+}
+
+/// Builds a reactive relation instance between two reactive entity instances step by step.
+pub struct ReactiveRelationInstanceBuilder {
+    outbound: Arc<ReactiveEntityInstance>,
+    type_name: RelationTypeName,
+    inbound: Arc<ReactiveEntityInstance>,
+    description: String,
+    properties: PropertyMap,
+    sockets: HashMap<String, SocketType>,
+    components: Vec<ComponentName>,
+    labels: Vec<String>,
+    behaviours: Vec<BehaviourName>,
+}
+
+impl ReactiveRelationInstanceBuilder {
+    pub fn new<S: Into<RelationTypeName>>(
+        outbound: Arc<ReactiveEntityInstance>,
+        type_name: S,
+        inbound: Arc<ReactiveEntityInstance>,
+    ) -> ReactiveRelationInstanceBuilder {
+        ReactiveRelationInstanceBuilder {
+            outbound,
+            type_name: type_name.into(),
+            inbound,
+            description: String::new(),
+            properties: PropertyMap::new(),
+            sockets: HashMap::new(),
+            components: Vec::new(),
+            labels: Vec::new(),
+            behaviours: Vec::new(),
+        }
+    }
+
+    /// Sets the description.
+    pub fn description<S: Into<String>>(mut self, description: S) -> ReactiveRelationInstanceBuilder {
+        self.description = description.into();
+        self
+    }
+
+    /// Sets the value of the property with the given name.
+    pub fn property<S: Into<String>>(mut self, property_name: S, value: Value) -> ReactiveRelationInstanceBuilder {
+        self.properties.insert(property_name.into(), value);
+        self
+    }
+
+    /// Sets the values of the given properties.
+    pub fn properties(mut self, properties: PropertyMap) -> ReactiveRelationInstanceBuilder {
+        self.properties.extend(properties);
+        self
+    }
+
+    /// Sets the socket type of the property with the given name.
+    pub fn socket<S: Into<String>>(mut self, property_name: S, socket_type: SocketType) -> ReactiveRelationInstanceBuilder {
+        self.sockets.insert(property_name.into(), socket_type);
+        self
+    }
+
+    /// Adds the component with the given name.
+    pub fn component<C: Into<ComponentName>>(mut self, component: C) -> ReactiveRelationInstanceBuilder {
+        self.components.push(component.into());
+        self
+    }
+
+    /// Adds the components with the given names.
+    pub fn components(mut self, components: Vec<ComponentName>) -> ReactiveRelationInstanceBuilder {
+        self.components.extend(components);
+        self
+    }
+
+    /// Adds the label, e.g. `debug` or `level:3`. Labels which have been added already are ignored.
+    pub fn label<S: Into<String>>(mut self, label: S) -> ReactiveRelationInstanceBuilder {
+        let label = label.into();
+        if !self.labels.contains(&label) {
+            self.labels.push(label);
+        }
+        self
+    }
+
+    /// Adds the given labels. Labels which have been added already are ignored.
+    pub fn labels(self, labels: Vec<String>) -> ReactiveRelationInstanceBuilder {
+        labels.into_iter().fold(self, |builder, label| builder.label(label))
+    }
+
+    /// Adds the behaviour with the given name.
+    pub fn behaviour<B: Into<BehaviourName>>(mut self, behaviour: B) -> ReactiveRelationInstanceBuilder {
+        self.behaviours.push(behaviour.into());
+        self
+    }
+
+    /// Builds the reactive relation instance. Fails, if the type name is not a valid identifier.
+    pub fn build(self) -> Result<ReactiveRelationInstance, InstanceConversionError> {
+        let key = create_edge_key(self.outbound.id, self.type_name.as_str(), self.inbound.id)?;
+        let id = RelationInstanceKey::from(key.clone()).id();
+        let properties = self
+            .properties
+            .into_iter()
+            .map(|(name, value)| {
+                let socket_type = self.sockets.get(&name).copied().unwrap_or(SocketType::None);
+                let property_instance = ReactivePropertyInstance {
+                    socket_type,
+                    ..ReactivePropertyInstance::new(id, name, value)
+                };
+                (property_instance.name.clone(), property_instance)
+            })
+            .collect();
+        let relation_instance = ReactiveRelationInstance {
+            outbound: self.outbound,
+            type_name: self.type_name,
+            inbound: self.inbound,
+            description: self.description.into(),
+            properties,
+            components: self.components.into_iter().collect::<DashSet<ComponentName>>(),
+            labels: self.labels.into_iter().collect::<DashSet<String>>(),
+            behaviours: self.behaviours.into_iter().collect::<DashSet<BehaviourName>>(),
+            behaviour_states: DashMap::new(),
+            strict_mode: StrictPropertyMode::default(),
+            revision: Revision::default(),
+            observers: Arc::default(),
+            key,
+            id,
+        };
+        share_revision(&relation_instance.properties, &relation_instance.revision);
+        Ok(relation_instance)
+    }
+}
+
+impl RelationInstance {
+    /// Returns a builder for a relation instance.
+    pub fn builder<S: Into<RelationTypeName>>(outbound_id: Uuid, type_name: S, inbound_id: Uuid) -> RelationInstanceBuilder {
+        RelationInstanceBuilder::new(outbound_id, type_name, inbound_id)
+    }
+}
+
+impl ReactiveRelationInstance {
+    /// Returns a builder for a reactive relation instance.
+    pub fn builder<S: Into<RelationTypeName>>(
+        outbound: Arc<ReactiveEntityInstance>,
+        type_name: S,
+        inbound: Arc<ReactiveEntityInstance>,
+    ) -> ReactiveRelationInstanceBuilder {
+        ReactiveRelationInstanceBuilder::new(outbound, type_name, inbound)
+    }
+}
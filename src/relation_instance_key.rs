@@ -0,0 +1,145 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{is_valid_type_name, stable_id, stable_namespace, EdgeKey, Identifier};
+use crate::{ReactiveRelationInstance, RelationInstance};
+
+/// Separates the outbound id, the type name and the inbound id in the textual representation of a relation key.
+pub const RELATION_KEY_SEPARATOR: &str = "--";
+
+/// The name of the namespace of the ids of relation instances.
+const RELATION_INSTANCE_NAMESPACE: &str = "relation_instance";
+
+/// Serializable key of a relation instance.
+///
+/// A relation instance is identified by the id of the outbound entity instance,
+/// the name of the relation type and the id of the inbound entity instance.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RelationInstanceKey {
+    /// The id of the outbound vertex.
+    #[serde(alias = "outbound")]
+    pub outbound_id: Uuid,
+
+    /// The name of the relation type
+    #[serde(alias = "type")]
+    pub type_name: String,
+
+    /// The id of the inbound vertex.
+    #[serde(alias = "inbound")]
+    pub inbound_id: Uuid,
+}
+
+/// The key of a relation instance.
+pub type RelationKey = RelationInstanceKey;
+
+impl RelationInstanceKey {
+    pub fn new<S: Into<String>>(outbound_id: Uuid, type_name: S, inbound_id: Uuid) -> RelationInstanceKey {
+        RelationInstanceKey {
+            outbound_id,
+            type_name: type_name.into(),
+            inbound_id,
+        }
+    }
+
+    /// Returns the edge key. Returns None, if the type name is not a valid identifier.
+    pub fn get_key(&self) -> Option<EdgeKey> {
+        Identifier::from_str(self.type_name.as_str())
+            .map(|t| EdgeKey::new(self.outbound_id, t, self.inbound_id))
+            .ok()
+    }
+
+    /// Returns the id of the relation instance. The id is derived from the key, so the relation
+    /// instances of the same type between the same entity instances have the same id.
+    pub fn id(&self) -> Uuid {
+        stable_id(&stable_namespace(RELATION_INSTANCE_NAMESPACE), self.to_string())
+    }
+}
+
+impl From<EdgeKey> for RelationInstanceKey {
+    fn from(edge_key: EdgeKey) -> Self {
+        RelationInstanceKey {
+            outbound_id: edge_key.outbound_id,
+            type_name: edge_key.t.to_string(),
+            inbound_id: edge_key.inbound_id,
+        }
+    }
+}
+
+impl From<&RelationInstance> for RelationInstanceKey {
+    fn from(relation_instance: &RelationInstance) -> Self {
+        RelationInstanceKey::new(relation_instance.outbound_id, relation_instance.type_name.clone(), relation_instance.inbound_id)
+    }
+}
+
+impl From<&ReactiveRelationInstance> for RelationInstanceKey {
+    fn from(relation_instance: &ReactiveRelationInstance) -> Self {
+        RelationInstanceKey::new(relation_instance.outbound.id, relation_instance.type_name.clone(), relation_instance.inbound.id)
+    }
+}
+
+/// Formats the key as `outbound_id--type_name--inbound_id`, which is the canonical textual
+/// representation of a relation instance.
+impl fmt::Display for RelationInstanceKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}{}{}{}",
+            self.outbound_id, RELATION_KEY_SEPARATOR, self.type_name, RELATION_KEY_SEPARATOR, self.inbound_id
+        )
+    }
+}
+
+/// Parses the canonical textual representation `outbound_id--type_name--inbound_id`.
+///
+/// The notation `outbound_id--(type_name)-->inbound_id` is accepted as well.
+impl FromStr for RelationInstanceKey {
+    type Err = RelationInstanceKeyParseError;
+
+    fn from_str(key: &str) -> Result<Self, Self::Err> {
+        let invalid_format = || RelationInstanceKeyParseError::InvalidFormat(key.to_string());
+        // The hyphenated uuids never contain the separator, but the type name may.
+        let (outbound_id, rest) = key.split_once(RELATION_KEY_SEPARATOR).ok_or_else(invalid_format)?;
+        let (type_name, inbound_id) = rest.rsplit_once(RELATION_KEY_SEPARATOR).ok_or_else(invalid_format)?;
+        let (type_name, inbound_id) = match (type_name.strip_prefix('(').and_then(|t| t.strip_suffix(')')), inbound_id.strip_prefix('>')) {
+            (Some(type_name), Some(inbound_id)) => (type_name, inbound_id),
+            _ => (type_name, inbound_id),
+        };
+        let outbound_id = Uuid::parse_str(outbound_id).map_err(|_| RelationInstanceKeyParseError::InvalidOutboundId(outbound_id.to_string()))?;
+        let inbound_id = Uuid::parse_str(inbound_id).map_err(|_| RelationInstanceKeyParseError::InvalidInboundId(inbound_id.to_string()))?;
+        if !is_valid_type_name(type_name) {
+            return Err(RelationInstanceKeyParseError::InvalidTypeName(type_name.to_string()));
+        }
+        Ok(RelationInstanceKey::new(outbound_id, type_name, inbound_id))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RelationInstanceKeyParseError {
+    /// The key doesn't have the format `outbound_id--type_name--inbound_id`.
+    InvalidFormat(String),
+
+    /// The id of the outbound entity instance isn't a valid uuid.
+    InvalidOutboundId(String),
+
+    /// The name of the relation type isn't a valid type name.
+    InvalidTypeName(String),
+
+    /// The id of the inbound entity instance isn't a valid uuid.
+    InvalidInboundId(String),
+}
+
+impl fmt::Display for RelationInstanceKeyParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RelationInstanceKeyParseError::InvalidFormat(key) => {
+                write!(f, "The relation key {} doesn't have the format outbound_id--type_name--inbound_id", key)
+            }
+            RelationInstanceKeyParseError::InvalidOutboundId(id) => write!(f, "The outbound id {} is not a valid uuid", id),
+            RelationInstanceKeyParseError::InvalidTypeName(type_name) => write!(f, "The type name {} is not valid", type_name),
+            RelationInstanceKeyParseError::InvalidInboundId(id) => write!(f, "The inbound id {} is not a valid uuid", id),
+        }
+    }
+}
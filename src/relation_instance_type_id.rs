@@ -0,0 +1,143 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Identifier;
+use crate::{ReactiveRelationInstance, RelationInstance};
+
+/// Separates the type name and the instance id.
+pub const RELATION_INSTANCE_TYPE_ID_SEPARATOR: &str = "__";
+
+/// The reason why a relation instance type id can't be parsed or converted.
+#[derive(Debug, PartialEq, Eq)]
+pub enum RelationInstanceTypeIdError {
+    /// The type name is empty.
+    EmptyTypeName,
+
+    /// The separator is present, but the instance id is empty.
+    EmptyInstanceId,
+
+    /// The type name contains the separator or ends with an underscore, so the type id can't be
+    /// parsed back.
+    InvalidTypeName(String),
+
+    /// The relation instance type id is not a valid identifier.
+    InvalidIdentifier(String),
+}
+
+impl fmt::Display for RelationInstanceTypeIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RelationInstanceTypeIdError::EmptyTypeName => write!(f, "The type name is empty"),
+            RelationInstanceTypeIdError::EmptyInstanceId => write!(f, "The instance id is empty"),
+            RelationInstanceTypeIdError::InvalidTypeName(type_name) => write!(f, "The type name {} is ambiguous", type_name),
+            RelationInstanceTypeIdError::InvalidIdentifier(type_id) => write!(f, "{} is not a valid identifier", type_id),
+        }
+    }
+}
+
+/// Identifies the type of a relation instance. Multiple relation instances of the same type between
+/// the same entity instances are distinguished by the instance id.
+///
+/// The type id is formatted as `type_name` or as `type_name__instance_id`. The type id is split at
+/// the first separator, so instance ids may contain the separator, but type names must not.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RelationInstanceTypeId {
+    /// The name of the relation type.
+    pub type_name: String,
+
+    /// The optional instance id.
+    pub instance_id: Option<String>,
+}
+
+impl RelationInstanceTypeId {
+    /// Constructs a type id without an instance id.
+    pub fn new<S: Into<String>>(type_name: S) -> RelationInstanceTypeId {
+        RelationInstanceTypeId {
+            type_name: type_name.into(),
+            instance_id: None,
+        }
+    }
+
+    /// Constructs a type id with the given instance id.
+    pub fn new_with_instance_id<S: Into<String>, I: Into<String>>(type_name: S, instance_id: I) -> RelationInstanceTypeId {
+        RelationInstanceTypeId {
+            type_name: type_name.into(),
+            instance_id: Some(instance_id.into()),
+        }
+    }
+
+    /// Returns an error, if the formatted type id can't be parsed back into this type id.
+    pub fn validate(&self) -> Result<(), RelationInstanceTypeIdError> {
+        if self.type_name.is_empty() {
+            return Err(RelationInstanceTypeIdError::EmptyTypeName);
+        }
+        if self.type_name.contains(RELATION_INSTANCE_TYPE_ID_SEPARATOR) || (self.instance_id.is_some() && self.type_name.ends_with('_')) {
+            return Err(RelationInstanceTypeIdError::InvalidTypeName(self.type_name.clone()));
+        }
+        match &self.instance_id {
+            Some(instance_id) if instance_id.is_empty() => Err(RelationInstanceTypeIdError::EmptyInstanceId),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl FromStr for RelationInstanceTypeId {
+    type Err = RelationInstanceTypeIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (type_name, instance_id) = match s.split_once(RELATION_INSTANCE_TYPE_ID_SEPARATOR) {
+            Some((_, "")) => return Err(RelationInstanceTypeIdError::EmptyInstanceId),
+            Some((type_name, instance_id)) => (type_name, Some(instance_id.to_string())),
+            None => (s, None),
+        };
+        if type_name.is_empty() {
+            return Err(RelationInstanceTypeIdError::EmptyTypeName);
+        }
+        Ok(RelationInstanceTypeId {
+            type_name: type_name.to_string(),
+            instance_id,
+        })
+    }
+}
+
+/// Formats the type id as `type_name` or as `type_name__instance_id`.
+impl fmt::Display for RelationInstanceTypeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.instance_id {
+            Some(instance_id) => write!(f, "{}{}{}", self.type_name, RELATION_INSTANCE_TYPE_ID_SEPARATOR, instance_id),
+            None => write!(f, "{}", self.type_name),
+        }
+    }
+}
+
+impl TryFrom<&Identifier> for RelationInstanceTypeId {
+    type Error = RelationInstanceTypeIdError;
+
+    fn try_from(t: &Identifier) -> Result<Self, Self::Error> {
+        RelationInstanceTypeId::from_str(t.as_str())
+    }
+}
+
+impl TryFrom<&RelationInstanceTypeId> for Identifier {
+    type Error = RelationInstanceTypeIdError;
+
+    fn try_from(type_id: &RelationInstanceTypeId) -> Result<Self, Self::Error> {
+        type_id.validate()?;
+        let type_id = type_id.to_string();
+        Identifier::new(type_id.as_str()).map_err(|_| RelationInstanceTypeIdError::InvalidIdentifier(type_id))
+    }
+}
+
+impl RelationInstance {
+    /// Returns the type id of the relation instance.
+    pub fn get_type_id(&self) -> Result<RelationInstanceTypeId, RelationInstanceTypeIdError> {
+        RelationInstanceTypeId::from_str(self.type_name.as_str())
+    }
+}
+
+impl ReactiveRelationInstance {
+    /// Returns the type id of the relation instance.
+    pub fn get_type_id(&self) -> Result<RelationInstanceTypeId, RelationInstanceTypeIdError> {
+        RelationInstanceTypeId::from_str(self.type_name.as_str())
+    }
+}
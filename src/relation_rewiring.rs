@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use crate::EdgeKey;
+use crate::{ReactiveEntityInstance, ReactiveRelationInstance};
+
+/// A relation instance which has been moved from one entity instance to another.
+pub struct RelationRewiring {
+    /// The edge key of the relation instance before rewiring.
+    pub old_key: Option<EdgeKey>,
+
+    /// The rewired relation instance.
+    pub relation_instance: Arc<ReactiveRelationInstance>,
+}
+
+impl RelationRewiring {
+    /// Returns the edge key of the rewired relation instance.
+    pub fn new_key(&self) -> Option<EdgeKey> {
+        self.relation_instance.get_key()
+    }
+}
+
+/// Moves the relation instances which are outgoing from the old entity instance to the new entity instance.
+/// Relation instances of other entity instances are skipped.
+pub fn rewire_outbound(
+    relations: &[Arc<ReactiveRelationInstance>],
+    old_entity: &ReactiveEntityInstance,
+    new_entity: Arc<ReactiveEntityInstance>,
+) -> Vec<RelationRewiring> {
+    relations
+        .iter()
+        .filter(|relation_instance| relation_instance.outbound.id == old_entity.id)
+        .map(|relation_instance| RelationRewiring {
+            old_key: relation_instance.get_key(),
+            relation_instance: Arc::new(relation_instance.rewired(new_entity.clone(), relation_instance.inbound.clone())),
+        })
+        .collect()
+}
+
+/// Moves the relation instances which are incoming to the old entity instance to the new entity instance.
+/// Relation instances of other entity instances are skipped.
+pub fn rewire_inbound(
+    relations: &[Arc<ReactiveRelationInstance>],
+    old_entity: &ReactiveEntityInstance,
+    new_entity: Arc<ReactiveEntityInstance>,
+) -> Vec<RelationRewiring> {
+    relations
+        .iter()
+        .filter(|relation_instance| relation_instance.inbound.id == old_entity.id)
+        .map(|relation_instance| RelationRewiring {
+            old_key: relation_instance.get_key(),
+            relation_instance: Arc::new(relation_instance.rewired(relation_instance.outbound.clone(), new_entity.clone())),
+        })
+        .collect()
+}
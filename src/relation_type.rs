@@ -1,36 +1,48 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 
-use indradb::Identifier;
-use serde::{Deserialize, Serialize};
+use serde::de::{self, DeserializeOwned};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use uuid::Uuid;
 
-use crate::extension::Extension;
-use crate::PropertyType;
+use crate::extension::{get_extension, Extension, ExtensionError};
+use crate::type_inheritance::{resolve_inheritance, InheritableType, InheritanceError, InheritedDefinitions};
+use crate::{
+    validate_identifier, Cardinality, CardinalityError, CategoryPath, ComponentName, ComponentOrEntityTypeName, PropertyType, ReactiveEntityInstance,
+    RelationTypeName, SocketType, ValidationError,
+};
+use crate::{EdgeKey, Identifier};
 
 /// A relation type defines the type of an relation instance.
 ///
 /// The relation type defines the entity types of the outbound and inbound entity instances.
 /// Also the relation type defines the properties of the relation instance.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(remote = "Self")]
 pub struct RelationType {
-    /// The name of the outbound entity type.
-    pub outbound_type: String,
+    /// The outbound entity type, the component of the outbound entity instance or any entity type.
+    #[serde(alias = "outbound")]
+    pub outbound_type: ComponentOrEntityTypeName,
 
     /// The name of the relation type.
     ///
-    /// The name is the unique identifier for relation types.
-    #[serde(alias = "name")]
-    pub type_name: String,
+    /// The name is the unique identifier for relation types. Like the names of the other
+    /// type definitions it's serialized as `name`.
+    #[serde(rename = "name", alias = "type_name", alias = "type")]
+    pub type_name: RelationTypeName,
 
     /// The full type name of the relation type.
     #[serde(default = "String::new")]
     pub full_name: String,
 
-    /// The name of the inbound entity type.
-    pub inbound_type: String,
+    /// The inbound entity type, the component of the inbound entity instance or any entity type.
+    #[serde(alias = "inbound")]
+    pub inbound_type: ComponentOrEntityTypeName,
 
-    /// The relation type belongs to the given group of relation types.
-    #[serde(default = "String::new")]
-    pub group: String,
+    /// The category of the relation type, e.g. `logical/gates`. A flat group name is a category with a single segment.
+    #[serde(default)]
+    pub group: CategoryPath,
 
     /// Textual description of the relation type.
     #[serde(default = "String::new")]
@@ -38,7 +50,7 @@ pub struct RelationType {
 
     /// The names of the components of the relation type.
     #[serde(default = "Vec::new")]
-    pub components: Vec<String>,
+    pub components: Vec<ComponentName>,
 
     /// The properties which are defined by the relation type.
     #[serde(default = "Vec::new")]
@@ -48,40 +60,143 @@ pub struct RelationType {
     #[serde(default = "Vec::new")]
     pub extensions: Vec<Extension>,
 
+    /// The names of the relation types this relation type extends. The components, properties
+    /// and extensions of the base relation types are inherited.
+    #[serde(default = "Vec::new")]
+    pub extends: Vec<String>,
+
+    /// The number of relation instances of this type an outbound entity instance may have.
+    #[serde(default = "Cardinality::many")]
+    pub outbound_cardinality: Cardinality,
+
+    /// The number of relation instances of this type an inbound entity instance may have.
+    #[serde(default = "Cardinality::many")]
+    pub inbound_cardinality: Cardinality,
+
+    /// The version of the relation type. Migrations transform the properties of instances
+    /// of older versions.
+    #[serde(default)]
+    pub version: u32,
+
+    /// Abstract relation types are only used for inheritance or grouping and cannot be instantiated.
+    #[serde(default, rename = "abstract")]
+    pub is_abstract: bool,
+
     #[serde(skip)]
     pub t: Identifier,
 }
 
 impl RelationType {
+    /// Constructs a new relation type. Panics, if the type name isn't a valid type name. A group
+    /// which isn't a valid category path is normalized, see [`CategoryPath::from_group`].
     #[allow(clippy::too_many_arguments)]
-    pub fn new<S: Into<String>>(
+    pub fn new<S: Into<String>, N: Into<RelationTypeName>>(
         outbound_type: S,
-        type_name: S,
+        type_name: N,
         inbound_type: S,
         group: S,
         description: S,
-        components: Vec<String>,
+        components: Vec<ComponentName>,
         properties: Vec<PropertyType>,
         extensions: Vec<Extension>,
     ) -> RelationType {
         let type_name = type_name.into();
         let t = Identifier::from_str(type_name.as_str()).unwrap();
         RelationType {
-            outbound_type: outbound_type.into(),
-            full_name: type_name.clone(),
+            outbound_type: ComponentOrEntityTypeName::from(outbound_type.into()),
+            full_name: type_name.to_string(),
             type_name,
-            inbound_type: inbound_type.into(),
-            group: group.into(),
+            inbound_type: ComponentOrEntityTypeName::from(inbound_type.into()),
+            group: CategoryPath::from_group(&group.into()),
             description: description.into(),
             components,
             properties,
             extensions,
+            extends: Vec::new(),
+            is_abstract: false,
+            version: 0,
+            outbound_cardinality: Cardinality::Many,
+            inbound_cardinality: Cardinality::Many,
             t,
         }
     }
 
+    /// Constructs a new relation type. Fails, if the type name isn't a valid type name, see [`crate::sanitize_type_name`].
+    /// The group is normalized like in `new`, see [`CategoryPath::from_group`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_new<S: Into<String>, N: Into<RelationTypeName>>(
+        outbound_type: S,
+        type_name: N,
+        inbound_type: S,
+        group: S,
+        description: S,
+        components: Vec<ComponentName>,
+        properties: Vec<PropertyType>,
+        extensions: Vec<Extension>,
+    ) -> Result<RelationType, ValidationError> {
+        let type_name = type_name.into();
+        validate_identifier(type_name.as_str())?;
+        Ok(RelationType::new(
+            outbound_type.into(),
+            type_name,
+            inbound_type.into(),
+            group.into(),
+            description.into(),
+            components,
+            properties,
+            extensions,
+        ))
+    }
+
+    /// Sets the version of the relation type.
+    pub fn with_version(mut self, version: u32) -> RelationType {
+        self.version = version;
+        self
+    }
+
+    /// Sets the outbound and inbound cardinality, e.g. `One` and `One` for an one-to-one relation type.
+    pub fn with_cardinality(mut self, outbound_cardinality: Cardinality, inbound_cardinality: Cardinality) -> RelationType {
+        self.outbound_cardinality = outbound_cardinality;
+        self.inbound_cardinality = inbound_cardinality;
+        self
+    }
+
+    /// Extends the relation type with the given name.
+    pub fn with_extends<S: Into<String>>(mut self, base_type_name: S) -> RelationType {
+        self.extends.push(base_type_name.into());
+        self
+    }
+
+    /// Returns the flattened relation type which contains the inherited components, properties and
+    /// extensions of all base relation types. Own definitions override inherited ones with the same
+    /// name. Base relation types must not define the same property with a different data type or
+    /// socket type or the same extension with a different value.
+    pub fn resolve(&self, relation_types: &[RelationType]) -> Result<RelationType, RelationTypeResolveError> {
+        resolve_inheritance(self, relation_types, &mut Vec::new()).map_err(RelationTypeResolveError::from)
+    }
+
+    /// Returns an error, if a relation instance of this type between the given entity instances
+    /// would exceed the cardinality. Edge keys of other relation types are ignored.
+    pub fn can_create(&self, edge_keys: &[EdgeKey], outbound_id: Uuid, inbound_id: Uuid) -> Result<(), CardinalityError> {
+        let edge_keys: Vec<&EdgeKey> = edge_keys.iter().filter(|edge_key| edge_key.t.as_str() == self.type_name).collect();
+        let outbound_count = edge_keys.iter().filter(|edge_key| edge_key.outbound_id == outbound_id).count();
+        if !self.outbound_cardinality.allows(outbound_count) {
+            return Err(CardinalityError::OutboundExceeded(outbound_id, self.outbound_cardinality));
+        }
+        let inbound_count = edge_keys.iter().filter(|edge_key| edge_key.inbound_id == inbound_id).count();
+        if !self.inbound_cardinality.allows(inbound_count) {
+            return Err(CardinalityError::InboundExceeded(inbound_id, self.inbound_cardinality));
+        }
+        Ok(())
+    }
+
+    /// Returns true, if relation instances of this type can be created.
+    pub fn is_instantiable(&self) -> bool {
+        !self.is_abstract
+    }
+
     /// Returns true, if the relation type is a component with the given name.
-    pub fn is_a<S: Into<String>>(&self, component_name: S) -> bool {
+    pub fn is_a<C: Into<ComponentName>>(&self, component_name: C) -> bool {
         self.components.contains(&component_name.into())
     }
 
@@ -92,9 +207,135 @@ impl RelationType {
         self.properties.iter().any(|p| p.name == property_name)
     }
 
+    /// Returns true, if the given entity instances can be the outbound and inbound entity instance.
+    pub fn is_valid_for(&self, outbound: &ReactiveEntityInstance, inbound: &ReactiveEntityInstance) -> bool {
+        self.outbound_type.matches_entity_instance(outbound) && self.inbound_type.matches_entity_instance(inbound)
+    }
+
+    /// Returns the own properties which act as input socket.
+    pub fn inputs(&self) -> impl Iterator<Item = &PropertyType> {
+        self.properties.iter().filter(|property_type| property_type.socket_type == SocketType::Input)
+    }
+
+    /// Returns the own properties which act as output socket.
+    pub fn outputs(&self) -> impl Iterator<Item = &PropertyType> {
+        self.properties.iter().filter(|property_type| property_type.socket_type == SocketType::Output)
+    }
+
+    /// Returns true, if the relation type belongs to the given category or to one of its sub categories.
+    /// Returns false, if the category isn't a valid category path.
+    pub fn is_in_category_subtree<C: TryInto<CategoryPath>>(&self, category: C) -> bool {
+        category.try_into().is_ok_and(|category| self.group.is_in_subtree_of(&category))
+    }
+
     /// Returns true, if the relation type contains an extension with the given name.
     pub fn has_own_extension<S: Into<String>>(&self, extension_name: S) -> bool {
         let extension_name = extension_name.into();
         self.extensions.iter().any(|extension| extension.name == extension_name)
     }
+
+    /// Returns the deserialized payload of the extension with the given name.
+    pub fn get_extension<T: DeserializeOwned, S: AsRef<str>>(&self, extension_name: S) -> Result<T, ExtensionError> {
+        get_extension(&self.extensions, extension_name)
+    }
+}
+
+/// Relation types are equal, if the type names are equal.
+impl PartialEq for RelationType {
+    fn eq(&self, other: &Self) -> bool {
+        self.type_name == other.type_name
+    }
+}
+
+impl Eq for RelationType {}
+
+impl Hash for RelationType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.type_name.hash(state);
+    }
+}
+
+impl Serialize for RelationType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RelationType::serialize(self, serializer)
+    }
+}
+
+/// The identifier is constructed from the name of the relation type.
+impl<'de> Deserialize<'de> for RelationType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut relation_type = RelationType::deserialize(deserializer)?;
+        relation_type.t = Identifier::new(relation_type.type_name.as_str()).map_err(|_| {
+            de::Error::custom(ValidationError::InvalidIdentifier {
+                identifier: relation_type.type_name.to_string(),
+            })
+        })?;
+        Ok(relation_type)
+    }
+}
+
+/// Formats the relation type as `outbound_type--(type_name)-->inbound_type`, e.g. `player--(looks_at)-->camera`.
+impl fmt::Display for RelationType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}--({})-->{}", self.outbound_type, self.type_name, self.inbound_type)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RelationTypeResolveError {
+    /// The base relation type with the given name doesn't exist.
+    MissingBaseType(String),
+
+    /// The relation type with the given name extends itself.
+    Cycle(String),
+
+    /// Base relation types define the property with the given name differently.
+    ConflictingProperty(String),
+
+    /// Base relation types define the extension with the given name differently.
+    ConflictingExtension(String),
+}
+
+impl fmt::Display for RelationTypeResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RelationTypeResolveError::MissingBaseType(type_name) => write!(f, "The base relation type {} doesn't exist", type_name),
+            RelationTypeResolveError::Cycle(type_name) => write!(f, "The relation type {} extends itself", type_name),
+            RelationTypeResolveError::ConflictingProperty(name) => write!(f, "The base relation types define the property {} differently", name),
+            RelationTypeResolveError::ConflictingExtension(name) => write!(f, "The base relation types define the extension {} differently", name),
+        }
+    }
+}
+
+impl InheritableType for RelationType {
+    type Name = RelationTypeName;
+
+    fn inheritance_name(&self) -> &RelationTypeName {
+        &self.type_name
+    }
+
+    fn has_name(&self, name: &str) -> bool {
+        self.type_name == *name
+    }
+
+    fn inherited_definitions(&mut self) -> InheritedDefinitions<'_> {
+        InheritedDefinitions {
+            extends: &mut self.extends,
+            components: &mut self.components,
+            properties: &mut self.properties,
+            extensions: &mut self.extensions,
+            property_groups: None,
+        }
+    }
+}
+
+impl From<InheritanceError> for RelationTypeResolveError {
+    fn from(error: InheritanceError) -> Self {
+        match error {
+            InheritanceError::MissingBaseType(name) => RelationTypeResolveError::MissingBaseType(name),
+            InheritanceError::Cycle(name) => RelationTypeResolveError::Cycle(name),
+            InheritanceError::ConflictingProperty(name) => RelationTypeResolveError::ConflictingProperty(name),
+            InheritanceError::ConflictingExtension(name) => RelationTypeResolveError::ConflictingExtension(name),
+        }
+    }
 }
@@ -0,0 +1,57 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// A monotonically increasing counter which is bumped on every mutation of an instance.
+///
+/// API layers can use the revision for optimistic concurrency control: a client reads
+/// the revision together with the state and the write is rejected, if the instance
+/// has been modified in the meantime.
+///
+/// Clones share the counter. The property instances of an instance share the revision of the
+/// instance and bump it whenever a value is written.
+#[derive(Clone, Debug, Default)]
+pub struct Revision(Arc<AtomicU64>);
+
+impl Revision {
+    /// Returns the current revision.
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// Increments the revision and returns the new revision.
+    pub fn bump(&self) -> u64 {
+        self.0.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    /// Increments the revision, if it equals the expected revision. Returns the new revision
+    /// or the actual revision, if it doesn't equal the expected revision.
+    pub fn bump_if(&self, expected: u64) -> Result<u64, u64> {
+        self.0
+            .compare_exchange(expected, expected + 1, Ordering::SeqCst, Ordering::SeqCst)
+            .map(|revision| revision + 1)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RevisionError {
+    /// The instance has been modified. Contains the expected and the actual revision.
+    Mismatch(u64, u64),
+
+    /// The instance doesn't have the property.
+    MissingProperty(String),
+
+    /// The value has been rejected by the property, e.g. because the property isn't writable or
+    /// the value has been dropped by the type enforcement.
+    Rejected(String),
+}
+
+impl fmt::Display for RevisionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RevisionError::Mismatch(expected, actual) => write!(f, "Expected revision {} but the actual revision is {}", expected, actual),
+            RevisionError::MissingProperty(property_name) => write!(f, "The property {} doesn't exist", property_name),
+            RevisionError::Rejected(property_name) => write!(f, "The value of the property {} has been rejected", property_name),
+        }
+    }
+}
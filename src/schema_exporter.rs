@@ -0,0 +1,121 @@
+use std::collections::BTreeSet;
+
+use serde_json::{json, Map, Value};
+
+use crate::{Component, ComponentName, DataType, EntityType, PropertyType, RelationType};
+
+/// Renders entity types and relation types into JSON Schema component schemas which are
+/// compatible with OpenAPI 3.1 (`components.schemas`) and AsyncAPI (`components.schemas`).
+///
+/// Properties of the components of a type are included in the schema of the type. Only the
+/// mandatory properties are required.
+pub struct SchemaExporter {}
+
+impl SchemaExporter {
+    /// Returns the schema of the given data type. Any value is valid for the empty schema.
+    pub fn data_type_schema(data_type: DataType) -> Value {
+        match data_type {
+            DataType::Null => json!({ "type": "null" }),
+            DataType::Bool => json!({ "type": "boolean" }),
+            DataType::Number => json!({ "type": "number" }),
+            DataType::String => json!({ "type": "string" }),
+            DataType::Array => json!({ "type": "array", "items": {} }),
+            DataType::Object => json!({ "type": "object" }),
+            DataType::Any => json!({}),
+            DataType::Binary => json!({ "type": "string", "contentEncoding": "base64" }),
+            DataType::DateTime => json!({ "type": "string", "format": "date-time" }),
+            DataType::Uuid => json!({ "type": "string", "format": "uuid" }),
+        }
+    }
+
+    /// Returns the schema of the given property type.
+    pub fn property_type_schema(property_type: &PropertyType) -> Value {
+        let mut schema = SchemaExporter::data_type_schema(property_type.data_type);
+        if let Some(schema) = schema.as_object_mut() {
+            if !property_type.description.is_empty() {
+                schema.insert("description".to_string(), json!(property_type.description));
+            }
+            schema.insert("x-socket-type".to_string(), json!(property_type.socket_type.to_string().to_lowercase()));
+            if !property_type.label.is_empty() {
+                schema.insert("title".to_string(), json!(property_type.label));
+            }
+            if !property_type.group.is_empty() {
+                schema.insert("x-group".to_string(), json!(property_type.group));
+            }
+            if let Some(order) = property_type.order {
+                schema.insert("x-order".to_string(), json!(order));
+            }
+        }
+        schema
+    }
+
+    /// Returns the schema of the properties of the given entity type.
+    pub fn entity_type_schema(entity_type: &EntityType, components: &[Component]) -> Value {
+        let mut schema = SchemaExporter::object_schema(&entity_type.description, &entity_type.components, &entity_type.properties, components);
+        if let Some(schema) = schema.as_object_mut() {
+            schema.insert("title".to_string(), json!(entity_type.name));
+            if !entity_type.group.is_empty() {
+                schema.insert("x-group".to_string(), json!(entity_type.group));
+            }
+        }
+        schema
+    }
+
+    /// Returns the schema of the properties of the given relation type.
+    pub fn relation_type_schema(relation_type: &RelationType, components: &[Component]) -> Value {
+        let mut schema = SchemaExporter::object_schema(&relation_type.description, &relation_type.components, &relation_type.properties, components);
+        if let Some(schema) = schema.as_object_mut() {
+            schema.insert("title".to_string(), json!(relation_type.type_name));
+            schema.insert("x-outbound-type".to_string(), json!(relation_type.outbound_type));
+            schema.insert("x-inbound-type".to_string(), json!(relation_type.inbound_type));
+            if !relation_type.group.is_empty() {
+                schema.insert("x-group".to_string(), json!(relation_type.group));
+            }
+        }
+        schema
+    }
+
+    /// Exports the given types as component schemas. The result can be used as
+    /// `components` object of an OpenAPI or AsyncAPI document.
+    pub fn export(entity_types: &[EntityType], relation_types: &[RelationType], components: &[Component]) -> Value {
+        let mut schemas = Map::new();
+        for entity_type in entity_types {
+            schemas.insert(entity_type.name.to_string(), SchemaExporter::entity_type_schema(entity_type, components));
+        }
+        for relation_type in relation_types {
+            schemas.insert(relation_type.type_name.to_string(), SchemaExporter::relation_type_schema(relation_type, components));
+        }
+        json!({ "schemas": schemas })
+    }
+
+    fn object_schema(description: &str, component_names: &[ComponentName], property_types: &[PropertyType], components: &[Component]) -> Value {
+        let mut properties = Map::new();
+        let mut required = BTreeSet::new();
+        // Properties of the type override the properties of the components with the same name
+        let property_types = components
+            .iter()
+            .filter(|component| component_names.contains(&component.name))
+            .flat_map(|component| component.properties.iter())
+            .chain(property_types.iter());
+        for property_type in property_types {
+            properties.insert(property_type.name.clone(), SchemaExporter::property_type_schema(property_type));
+            if property_type.is_mandatory() {
+                required.insert(property_type.name.clone());
+            } else {
+                required.remove(&property_type.name);
+            }
+        }
+        let mut schema = json!({
+            "type": "object",
+            "properties": properties,
+        });
+        // An empty list of required properties is omitted
+        if !required.is_empty() {
+            schema["required"] = json!(required);
+        }
+        if !description.is_empty() {
+            schema["description"] = json!(description);
+        }
+        schema
+    }
+}
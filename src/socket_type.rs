@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 /// The socket type defines if the property acts as an input or output socket
 /// or is an hidden property
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Copy, Eq)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
 #[serde(rename_all = "lowercase")]
 pub enum SocketType {
     /// The property doesn't act as input or output socket.
@@ -0,0 +1,80 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+/// Specifies how an instance handles values which are set on a property which doesn't exist.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum UnknownPropertyPolicy {
+    /// The value is ignored.
+    #[default]
+    Ignore,
+
+    /// The property is created with the value.
+    Create,
+
+    /// The value is rejected. `try_set` returns an error, the infallible setters drop the value.
+    /// In both cases the event observers of the instance are notified with
+    /// `InstanceEvent::UnknownPropertyRejected`.
+    Error,
+}
+
+/// The unknown property policy of an instance, which can be switched at runtime.
+#[derive(Debug, Default)]
+pub struct StrictPropertyMode(AtomicU8);
+
+impl StrictPropertyMode {
+    pub fn new(policy: UnknownPropertyPolicy) -> StrictPropertyMode {
+        StrictPropertyMode(AtomicU8::new(policy as u8))
+    }
+
+    /// Returns the current policy.
+    pub fn get(&self) -> UnknownPropertyPolicy {
+        match self.0.load(Ordering::SeqCst) {
+            1 => UnknownPropertyPolicy::Create,
+            2 => UnknownPropertyPolicy::Error,
+            _ => UnknownPropertyPolicy::Ignore,
+        }
+    }
+
+    /// Switches the policy.
+    pub fn set(&self, policy: UnknownPropertyPolicy) {
+        self.0.store(policy as u8, Ordering::SeqCst);
+    }
+}
+
+/// A value has been set on the property with the given name, which doesn't exist.
+#[derive(Debug, PartialEq, Eq)]
+pub struct UnknownPropertyError(pub String);
+
+impl fmt::Display for UnknownPropertyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The property {} doesn't exist", self.0)
+    }
+}
+
+/// The reason why a value hasn't been set on a property.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SetPropertyError {
+    /// The property doesn't exist.
+    UnknownProperty(UnknownPropertyError),
+
+    /// The property with the given name is read only or immutable and already initialized.
+    NotWritable(String),
+}
+
+impl fmt::Display for SetPropertyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SetPropertyError::UnknownProperty(error) => write!(f, "{}", error),
+            SetPropertyError::NotWritable(name) => write!(f, "The property {} is not writable", name),
+        }
+    }
+}
+
+impl From<UnknownPropertyError> for SetPropertyError {
+    fn from(error: UnknownPropertyError) -> Self {
+        SetPropertyError::UnknownProperty(error)
+    }
+}
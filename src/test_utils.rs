@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use random_string::generate;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    generate_id, DataType, EntityInstance, EntityType, EntityTypeName, Flow, PropertyMap, PropertyType, ReactiveEntityInstance, ReactiveRelationInstance,
+    RelationInstance, RelationType, RelationTypeName,
+};
+
+const CHARSET_LETTERS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// The name of the default entity type.
+pub const DEFAULT_ENTITY_TYPE_NAME: &str = "test_entity";
+
+/// The name of the default relation type.
+pub const DEFAULT_RELATION_TYPE_NAME: &str = "test_relation";
+
+/// Returns a random string of 10 letters.
+pub fn r_string() -> String {
+    generate(10, CHARSET_LETTERS).to_string()
+}
+
+/// Returns a random string of 255 letters, which is the maximum length of a type name.
+pub fn r_string_255() -> String {
+    generate(255, CHARSET_LETTERS).to_string()
+}
+
+/// Returns a random string of 1000 letters, which is too long for a type name.
+pub fn r_string_1000() -> String {
+    generate(1000, CHARSET_LETTERS).to_string()
+}
+
+/// Returns a random string as JSON value.
+pub fn r_json_string() -> Value {
+    json!(r_string())
+}
+
+/// Returns an entity type with an input, an output and a name property.
+pub fn default_entity_type() -> EntityType {
+    EntityType::new(
+        EntityTypeName::new(DEFAULT_ENTITY_TYPE_NAME),
+        "test",
+        "An entity type for tests",
+        Vec::new(),
+        vec![
+            PropertyType::input("input", DataType::Number),
+            PropertyType::output("output", DataType::Number),
+            PropertyType::new("name", DataType::String),
+        ],
+        Vec::new(),
+    )
+}
+
+/// Returns a relation type with a weight property, which connects entity instances of the default entity type.
+pub fn default_relation_type() -> RelationType {
+    RelationType::new(
+        DEFAULT_ENTITY_TYPE_NAME,
+        RelationTypeName::new(DEFAULT_RELATION_TYPE_NAME),
+        DEFAULT_ENTITY_TYPE_NAME,
+        "test",
+        "A relation type for tests",
+        Vec::new(),
+        vec![PropertyType::new("weight", DataType::Number)],
+        Vec::new(),
+    )
+}
+
+/// Returns the properties of the given property types, initialized with the default values of their data types.
+fn default_properties(property_types: &[PropertyType]) -> PropertyMap {
+    property_types
+        .iter()
+        .map(|property_type| (property_type.name.clone(), property_type.data_type.default_value()))
+        .collect()
+}
+
+/// Returns a relation instance of the default relation type.
+fn relation_instance_between(outbound_id: Uuid, inbound_id: Uuid) -> RelationInstance {
+    let relation_type = default_relation_type();
+    RelationInstance::new(outbound_id, relation_type.type_name, inbound_id, default_properties(&relation_type.properties))
+}
+
+/// Returns a new entity instance of the given entity type with the components of the entity type.
+/// The properties are initialized with the default values of their data types.
+pub fn entity_instance_of(entity_type: &EntityType) -> EntityInstance {
+    let mut entity_instance = EntityInstance::new(entity_type.name.clone(), generate_id(), default_properties(&entity_type.properties));
+    entity_instance.components = entity_type.components.clone();
+    entity_instance
+}
+
+/// Returns a new reactive entity instance of the given entity type.
+pub fn reactive_entity_instance_of(entity_type: &EntityType) -> Arc<ReactiveEntityInstance> {
+    Arc::new(ReactiveEntityInstance::from(entity_instance_of(entity_type)))
+}
+
+/// Returns a relation instance of the default relation type between the given entity instances.
+pub fn connected_relation(outbound: &EntityInstance, inbound: &EntityInstance) -> RelationInstance {
+    relation_instance_between(outbound.id, inbound.id)
+}
+
+/// Returns a reactive relation instance of the default relation type between the given reactive entity instances.
+pub fn connected_reactive_relation(outbound: Arc<ReactiveEntityInstance>, inbound: Arc<ReactiveEntityInstance>) -> Arc<ReactiveRelationInstance> {
+    let relation_instance = relation_instance_between(outbound.id, inbound.id);
+    Arc::new(ReactiveRelationInstance::from_instance(outbound, inbound, relation_instance).expect("The default relation type name is a valid identifier"))
+}
+
+/// Returns a flow of the default entity type, which contains two entity instances of the default
+/// entity type connected by a relation instance of the default relation type.
+pub fn default_flow() -> Flow {
+    let entity_type = default_entity_type();
+    let mut flow = Flow::from_instance_with_name(entity_instance_of(&entity_type), "test_flow");
+    let outbound = entity_instance_of(&entity_type);
+    let inbound = entity_instance_of(&entity_type);
+    flow.relation_instances.push(connected_relation(&outbound, &inbound));
+    flow.entity_instances.push(outbound);
+    flow.entity_instances.push(inbound);
+    flow
+}
@@ -0,0 +1,85 @@
+use arbitrary::{Arbitrary, Unstructured};
+use rand::RngCore;
+
+use crate::{arbitrary_identifier, arbitrary_value, validate_identifier, EntityInstance, EntityType, Flow, PropertyType, RelationInstance, RelationType};
+
+const ITERATIONS: usize = 100;
+
+fn random_bytes() -> Vec<u8> {
+    let mut bytes = vec![0u8; 4096];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    bytes
+}
+
+#[test]
+fn arbitrary_identifier_test() {
+    for _ in 0..ITERATIONS {
+        let bytes = random_bytes();
+        let mut u = Unstructured::new(&bytes);
+        assert!(validate_identifier(arbitrary_identifier(&mut u).unwrap()).is_ok());
+    }
+    assert!(validate_identifier(arbitrary_identifier(&mut Unstructured::new(&[])).unwrap()).is_ok());
+}
+
+#[test]
+fn arbitrary_value_test() {
+    for _ in 0..ITERATIONS {
+        let bytes = random_bytes();
+        let mut u = Unstructured::new(&bytes);
+        let property_type = PropertyType::arbitrary(&mut u).unwrap();
+        let value = arbitrary_value(&mut u, property_type.data_type).unwrap();
+        assert!(property_type.data_type.is_valid(&value));
+        assert_eq!(value, serde_json::from_str::<serde_json::Value>(&value.to_string()).unwrap());
+    }
+}
+
+#[test]
+fn arbitrary_types_serde_test() {
+    for _ in 0..ITERATIONS {
+        let bytes = random_bytes();
+        let mut u = Unstructured::new(&bytes);
+        let entity_type = EntityType::arbitrary(&mut u).unwrap();
+        let json = serde_json::to_value(&entity_type).unwrap();
+        let deserialized: EntityType = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(entity_type, deserialized);
+        assert_eq!(json, serde_json::to_value(&deserialized).unwrap());
+
+        let relation_type = RelationType::arbitrary(&mut u).unwrap();
+        let json = serde_json::to_value(&relation_type).unwrap();
+        let deserialized: RelationType = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(relation_type, deserialized);
+        assert_eq!(json, serde_json::to_value(&deserialized).unwrap());
+    }
+}
+
+#[test]
+fn arbitrary_instances_serde_test() {
+    for _ in 0..ITERATIONS {
+        let bytes = random_bytes();
+        let mut u = Unstructured::new(&bytes);
+        let entity_instance = EntityInstance::arbitrary(&mut u).unwrap();
+        assert!(validate_identifier(&entity_instance.type_name).is_ok());
+        let deserialized: EntityInstance = serde_json::from_str(&serde_json::to_string(&entity_instance).unwrap()).unwrap();
+        assert_eq!(entity_instance, deserialized);
+
+        let relation_instance = RelationInstance::arbitrary(&mut u).unwrap();
+        assert!(relation_instance.get_key().is_some());
+        let deserialized: RelationInstance = serde_json::from_str(&serde_json::to_string(&relation_instance).unwrap()).unwrap();
+        assert_eq!(relation_instance, deserialized);
+        assert_eq!(relation_instance.content_hash(), deserialized.content_hash());
+    }
+}
+
+#[test]
+fn arbitrary_flow_test() {
+    for _ in 0..ITERATIONS {
+        let bytes = random_bytes();
+        let flow = Flow::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+        assert!(flow.validate().is_valid());
+        let deserialized: Flow = serde_json::from_str(&serde_json::to_string(&flow).unwrap()).unwrap();
+        assert!(deserialized.validate().is_valid());
+        assert_eq!(flow.content_hash(), deserialized.content_hash());
+    }
+    // The generated flow is valid, even if the data is exhausted
+    assert!(Flow::arbitrary(&mut Unstructured::new(&[])).unwrap().validate().is_valid());
+}
@@ -0,0 +1,56 @@
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Float64Type, TimestampMillisecondType};
+use arrow_array::Array;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{entity_instances_to_arrow, Component, ComponentName, DataType, EntityInstance, EntityType, EntityTypeName, PropertyMap, PropertyType};
+
+fn create_sensor(value: serde_json::Value, measured: &str) -> EntityInstance {
+    let mut properties = PropertyMap::new();
+    properties.insert("value".to_string(), value);
+    properties.insert("measured".to_string(), json!(measured));
+    properties.insert("config".to_string(), json!({ "unit": "celsius" }));
+    properties.insert("label".to_string(), json!("kitchen"));
+    EntityInstance::new(EntityTypeName::new("sensor"), Uuid::new_v4(), properties)
+}
+
+fn create_sensor_type() -> EntityType {
+    EntityType::new(
+        EntityTypeName::new("sensor"),
+        "",
+        "",
+        vec![ComponentName::new("labeled")],
+        vec![
+            PropertyType::new("value", DataType::Number),
+            PropertyType::new("measured", DataType::DateTime),
+            PropertyType::new("config", DataType::Object),
+        ],
+        Vec::new(),
+    )
+}
+
+#[test]
+fn entity_instances_to_arrow_test() {
+    let labeled = Component::new(ComponentName::new("labeled"), vec![PropertyType::new("label", DataType::String)]);
+    let sensors = vec![create_sensor(json!(21.5), "2022-01-01T00:00:00Z"), create_sensor(json!("hot"), "yesterday")];
+    let record_batch = entity_instances_to_arrow(&create_sensor_type(), &[labeled], &sensors).unwrap();
+    assert_eq!(2, record_batch.num_rows());
+    let names: Vec<String> = record_batch.schema().fields().iter().map(|field| field.name().clone()).collect();
+    assert_eq!(vec!["id", "label", "value", "measured", "config"], names);
+    assert_eq!(sensors[0].id.to_string(), record_batch.column(0).as_string::<i32>().value(0));
+    assert_eq!("kitchen", record_batch.column(1).as_string::<i32>().value(1));
+    let values = record_batch.column(2).as_primitive::<Float64Type>();
+    assert_eq!(21.5, values.value(0));
+    assert!(values.is_null(1));
+    let measured = record_batch.column(3).as_primitive::<TimestampMillisecondType>();
+    assert_eq!(1640995200000, measured.value(0));
+    assert!(measured.is_null(1));
+    assert_eq!(r#"{"unit":"celsius"}"#, record_batch.column(4).as_string::<i32>().value(0));
+}
+
+#[test]
+fn entity_instances_to_arrow_invalid_type_test() {
+    let player = EntityInstance::new_without_properties(EntityTypeName::new("player"), Uuid::new_v4());
+    assert!(entity_instances_to_arrow(&create_sensor_type(), &[], &[player]).is_err());
+}
@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use crate::tests::utils::create_random_entity_instance;
+use crate::BehaviourDependencyGraph;
+use crate::BehaviourName;
+use crate::BehaviourType;
+
+fn behaviour_types() -> Vec<BehaviourType> {
+    vec![
+        BehaviourType::new(BehaviourName::new("add"), vec!["lhs".to_string(), "rhs".to_string()], vec!["result".to_string()]),
+        BehaviourType::new(BehaviourName::new("sub"), vec!["lhs".to_string(), "rhs".to_string()], vec!["result".to_string()]),
+        BehaviourType::new(BehaviourName::new("log"), vec!["result".to_string()], Vec::new()),
+    ]
+}
+
+#[test]
+fn behaviour_dependency_graph_test() {
+    let entity_instance = Arc::new(create_random_entity_instance("result"));
+    entity_instance.add_behaviour(BehaviourName::new("add"));
+    entity_instance.add_behaviour(BehaviourName::new("log"));
+    let graph = BehaviourDependencyGraph::from_entity_instances(std::slice::from_ref(&entity_instance), &behaviour_types());
+    assert_eq!(4, graph.dependencies.len());
+    assert_eq!(vec!["add"], graph.get_writers(entity_instance.id, "result"));
+    assert_eq!(vec!["log"], graph.get_readers(entity_instance.id, "result"));
+    assert_eq!(vec!["log"], graph.get_dependents(entity_instance.id, "add"));
+    assert!(graph.get_dependents(entity_instance.id, "log").is_empty());
+    assert!(!graph.has_conflicts());
+}
+
+#[test]
+fn behaviour_dependency_graph_conflict_test() {
+    let entity_instance = Arc::new(create_random_entity_instance("result"));
+    entity_instance.add_behaviour(BehaviourName::new("add"));
+    entity_instance.add_behaviour(BehaviourName::new("sub"));
+    let other_entity_instance = Arc::new(create_random_entity_instance("result"));
+    other_entity_instance.add_behaviour(BehaviourName::new("add"));
+    let graph = BehaviourDependencyGraph::from_entity_instances(&[entity_instance.clone(), other_entity_instance], &behaviour_types());
+    let conflicts = graph.get_conflicts();
+    assert_eq!(1, conflicts.len());
+    let conflict = conflicts.first().unwrap();
+    assert_eq!(entity_instance.id, conflict.id);
+    assert_eq!("result", conflict.property_name);
+    assert_eq!(vec!["add", "sub"], conflict.behaviours);
+}
@@ -0,0 +1,70 @@
+use serde_json::json;
+
+use crate::tests::utils::create_random_entity_instance::create_random_entity_instance;
+use crate::{BehaviourName, BehaviourStateError, BehaviourStateMachine};
+
+fn lifecycle() -> BehaviourStateMachine {
+    BehaviourStateMachine::new(vec!["created".to_string(), "connected".to_string(), "paused".to_string()])
+        .with_transition("created", "connected")
+        .with_transition("connected", "paused")
+        .with_transition("paused", "connected")
+}
+
+#[test]
+fn behaviour_state_machine_test() {
+    let mut state_machine = lifecycle();
+    assert_eq!("created", state_machine.get_state());
+    assert!(state_machine.can_transition_to("connected"));
+    assert!(!state_machine.can_transition_to("paused"));
+    assert_eq!(vec!["connected"], state_machine.get_next_states());
+
+    assert_eq!(
+        Err(BehaviourStateError::InvalidTransition("created".to_string(), "paused".to_string())),
+        state_machine.transition_to("paused")
+    );
+    assert_eq!(Err(BehaviourStateError::UnknownState("stopped".to_string())), state_machine.transition_to("stopped"));
+    assert!(state_machine.transition_to("connected").is_ok());
+    assert!(state_machine.transition_to("paused").is_ok());
+    assert!(state_machine.is_in("paused"));
+
+    state_machine.reset();
+    assert!(state_machine.is_in("created"));
+}
+
+#[test]
+fn behaviour_state_machine_serde_test() {
+    let state_machine: BehaviourStateMachine = serde_json::from_value(json!({
+        "states": ["created", "connected"],
+        "transitions": [{ "from": "created", "to": "connected" }],
+        "initial_state": "created"
+    }))
+    .unwrap();
+    assert_eq!("created", state_machine.get_state());
+
+    let mut state_machine = lifecycle();
+    state_machine.transition_to("connected").unwrap();
+    let json = serde_json::to_value(&state_machine).unwrap();
+    assert_eq!(json!("connected"), json["current_state"]);
+    let state_machine: BehaviourStateMachine = serde_json::from_value(json).unwrap();
+    assert!(state_machine.is_in("connected"));
+}
+
+#[test]
+fn reactive_entity_instance_behaviour_state_test() {
+    let entity_instance = create_random_entity_instance("x");
+    assert_eq!(
+        Err(BehaviourStateError::MissingStateMachine("timer".to_string())),
+        entity_instance.transition_behaviour("timer", "connected")
+    );
+
+    entity_instance.add_behaviour(BehaviourName::new("timer"));
+    entity_instance.set_behaviour_state_machine(BehaviourName::new("timer"), lifecycle());
+    assert_eq!(Some("created".to_string()), entity_instance.get_behaviour_state("timer"));
+    let revision = entity_instance.revision();
+    assert!(entity_instance.transition_behaviour("timer", "connected").is_ok());
+    assert_eq!(Some("connected".to_string()), entity_instance.get_behaviour_state("timer"));
+    assert!(entity_instance.revision() > revision);
+
+    entity_instance.remove_behaviour(BehaviourName::new("timer"));
+    assert!(entity_instance.get_behaviour_state_machine("timer").is_none());
+}
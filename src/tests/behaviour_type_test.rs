@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::tests::utils::create_random_entity_instance::create_random_entity_instance_with_type;
+use crate::tests::utils::r_string;
+use crate::BehaviourName;
+use crate::BehaviourType;
+use crate::ComponentName;
+use crate::EntityTypeName;
+use crate::Extension;
+use crate::ReactiveRelationInstance;
+use crate::RelationTypeName;
+
+#[test]
+fn behaviour_type_test() {
+    let name = r_string();
+    let mut behaviour_type = BehaviourType::new(BehaviourName::new(name.clone()), vec!["lhs".to_string(), "rhs".to_string()], vec!["result".to_string()]);
+    assert_eq!(name, behaviour_type.name);
+    assert!(behaviour_type.reads("lhs"));
+    assert!(!behaviour_type.reads("result"));
+    assert!(behaviour_type.writes("result"));
+    assert!(!behaviour_type.writes("lhs"));
+    behaviour_type.extensions.push(Extension::new("extension_name", json!("extension_value")));
+    assert!(behaviour_type.has_extension("extension_name"));
+    assert!(!behaviour_type.has_extension(r_string()));
+
+    let behaviour_type: BehaviourType = serde_json::from_str(r#"{"name": "add", "reads": ["lhs"]}"#).unwrap();
+    assert_eq!("add", behaviour_type.name);
+    assert!(behaviour_type.reads("lhs"));
+    assert!(behaviour_type.writes.is_empty());
+}
+
+#[test]
+fn behaviour_type_applicable_test() {
+    let behaviour_type = BehaviourType::new(BehaviourName::new("blink"), Vec::new(), vec!["color".to_string()])
+        .with_description("Blinks the color")
+        .with_entity_type(EntityTypeName::new("light"))
+        .with_relation_type(RelationTypeName::new("connector"))
+        .with_component(ComponentName::new("labeled"));
+    assert_eq!("Blinks the color", behaviour_type.description);
+    assert!(behaviour_type.applies_to_entity_type("light"));
+    assert!(behaviour_type.applies_to_relation_type("connector"));
+    assert!(behaviour_type.applies_to_component("labeled"));
+
+    let light = Arc::new(create_random_entity_instance_with_type("light", "color"));
+    let sprite = Arc::new(create_random_entity_instance_with_type("sprite", "color"));
+    assert!(behaviour_type.is_applicable_to_entity(&light));
+    assert!(!behaviour_type.is_applicable_to_entity(&sprite));
+    sprite.add_component(ComponentName::new("labeled"));
+    assert!(behaviour_type.is_applicable_to_entity(&sprite));
+    let relation_instance = ReactiveRelationInstance::builder(light, RelationTypeName::new("connector"), sprite)
+        .build()
+        .unwrap();
+    assert!(behaviour_type.is_applicable_to_relation(&relation_instance));
+
+    let behaviour_type: BehaviourType = serde_json::from_str(r#"{"name": "add", "entity_types": ["add"]}"#).unwrap();
+    assert!(behaviour_type.applies_to_entity_type("add"));
+    assert!(behaviour_type.components.is_empty());
+}
@@ -0,0 +1,104 @@
+use serde_json::json;
+
+use crate::{CategoryPath, CategoryPathError, EntityType, EntityTypeName, FlowType, RelationType, RelationTypeName};
+
+#[test]
+fn category_path_test() {
+    let category_path = CategoryPath::new("logical/gates").unwrap();
+    assert_eq!(vec!["logical".to_string(), "gates".to_string()], category_path.segments());
+    assert_eq!(2, category_path.depth());
+    assert_eq!(Some("gates"), category_path.name());
+    assert_eq!(Some(CategoryPath::new("logical").unwrap()), category_path.parent());
+    assert_eq!("logical/gates", category_path.to_string());
+    assert_eq!(category_path, CategoryPath::from_segments(["logical", "gates"]).unwrap());
+    assert_eq!(category_path, "logical/gates".parse::<CategoryPath>().unwrap());
+    assert_eq!(CategoryPath::new("logical/gates/and").unwrap(), category_path.join(CategoryPath::new("and").unwrap()));
+    assert!(CategoryPath::default().is_empty());
+    assert!(CategoryPath::default().parent().is_none());
+    assert!(CategoryPath::new("").unwrap().is_empty());
+}
+
+#[test]
+fn category_path_invalid_test() {
+    assert_eq!(Err(CategoryPathError::EmptySegment), CategoryPath::new("logical//gates"));
+    assert_eq!(Err(CategoryPathError::EmptySegment), CategoryPath::new("/logical"));
+    assert_eq!(Err(CategoryPathError::EmptySegment), CategoryPath::new("logical/"));
+    assert_eq!(Err(CategoryPathError::UnexpectedWhitespace(" gates".to_string())), CategoryPath::new("logical/ gates"));
+    assert_eq!(Err(CategoryPathError::UnexpectedWhitespace(" ".to_string())), CategoryPath::new(" "));
+    assert_eq!(Err(CategoryPathError::EmptySegment), CategoryPath::from_segments(["logical", ""]));
+    assert_eq!(
+        Err(CategoryPathError::UnexpectedSeparator("logical/gates".to_string())),
+        CategoryPath::from_segments(["logical/gates"])
+    );
+    assert_ne!(CategoryPath::new("logical/gates").unwrap(), "logical/gates/and");
+    // A string which isn't a valid category path is normalized
+    assert_eq!(
+        CategoryPath::new("logical/gates").unwrap(),
+        serde_json::from_value::<CategoryPath>(json!("logical//gates")).unwrap()
+    );
+    assert!(serde_json::from_value::<CategoryPath>(json!(["logical", " gates"])).is_err());
+}
+
+#[test]
+fn category_path_subtree_test() {
+    let gates = CategoryPath::new("logical/gates").unwrap();
+    assert!(gates.is_in_subtree_of(&CategoryPath::new("logical").unwrap()));
+    assert!(gates.is_in_subtree_of(&gates));
+    assert!(gates.is_in_subtree_of(&CategoryPath::default()));
+    assert!(!gates.is_in_subtree_of(&CategoryPath::new("logical/gates/and").unwrap()));
+    assert!(!gates.is_in_subtree_of(&CategoryPath::new("logic").unwrap()));
+}
+
+#[test]
+fn category_path_serde_test() {
+    let category_path: CategoryPath = serde_json::from_value(json!("logical/gates")).unwrap();
+    assert_eq!("logical/gates", category_path);
+    assert_eq!(json!("logical/gates"), serde_json::to_value(&category_path).unwrap());
+    let category_path: CategoryPath = serde_json::from_value(json!(["logical", "gates"])).unwrap();
+    assert_eq!("logical/gates", category_path);
+}
+
+#[test]
+fn type_category_test() {
+    let entity_type: EntityType = serde_json::from_value(json!({ "name": "and", "group": "logical" })).unwrap();
+    assert_eq!("logical", entity_type.group);
+    assert!(entity_type.is_in_category_subtree("logical"));
+    assert!(!entity_type.is_in_category_subtree("logical/gates"));
+    assert!(!entity_type.is_in_category_subtree("logical/"));
+    assert_eq!(json!("logical"), serde_json::to_value(&entity_type).unwrap()["group"]);
+
+    let relation_type = RelationType::new("*", RelationTypeName::new("connector"), "*", "logical/connectors", "", Vec::new(), Vec::new(), Vec::new());
+    assert!(relation_type.is_in_category_subtree("logical"));
+    assert!(!relation_type.is_in_category_subtree("arithmetic"));
+
+    let mut flow_type = FlowType::new("counter", "generic_flow");
+    assert!(flow_type.is_in_category_subtree(CategoryPath::default()));
+    flow_type.group = CategoryPath::new("logical/counters").unwrap();
+    assert!(flow_type.is_in_category_subtree("logical"));
+}
+
+#[test]
+fn legacy_group_test() {
+    // Groups which aren't valid category paths are normalized into valid category paths
+    let entity_type: EntityType = serde_json::from_value(json!({ "name": "and", "group": "Input / Output" })).unwrap();
+    assert_eq!(vec!["Input".to_string(), "Output".to_string()], entity_type.group.segments());
+    assert_eq!("Input / Output", entity_type.group);
+    assert_eq!(json!("Input/Output"), serde_json::to_value(&entity_type).unwrap()["group"]);
+    assert_eq!(Ok(entity_type.group.clone()), CategoryPath::from_segments(entity_type.group.segments().to_vec()));
+
+    let relation_type: RelationType =
+        serde_json::from_value(json!({ "outbound_type": "*", "name": "connector", "inbound_type": "*", "group": " logic" })).unwrap();
+    assert_eq!(vec!["logic".to_string()], relation_type.group.segments());
+
+    // The constructors apply the same policy
+    assert!(CategoryPath::new("Input / Output").is_err());
+    let entity_type = EntityType::new(EntityTypeName::new("and"), "Input / Output", "", Vec::new(), Vec::new(), Vec::new());
+    assert_eq!(2, entity_type.group.depth());
+    let entity_type = EntityType::try_new(EntityTypeName::new("and"), "Input / Output", "", Vec::new(), Vec::new(), Vec::new()).unwrap();
+    assert_eq!(2, entity_type.group.depth());
+    let relation_type = RelationType::new("*", RelationTypeName::new("connector"), "*", " logic", "", Vec::new(), Vec::new(), Vec::new());
+    assert_eq!(Some("logic"), relation_type.group.name());
+    let relation_type =
+        RelationType::try_new("*", RelationTypeName::new("connector"), "*", "logical//connectors/", "", Vec::new(), Vec::new(), Vec::new()).unwrap();
+    assert_eq!("logical/connectors", relation_type.group);
+}
@@ -0,0 +1,46 @@
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{binary_to_value, from_cbor, value_to_binary, CborError, EntityInstance, EntityTypeName, Flow, PropertyMap, RelationInstance, RelationTypeName};
+
+#[test]
+fn entity_instance_cbor_test() {
+    let mut properties = PropertyMap::new();
+    properties.insert("payload".to_string(), json!({ "bytes": [0, 255, 16], "scale": 1.5, "big": u64::MAX, "negative": -3 }));
+    let entity_instance = EntityInstance::new(EntityTypeName::new("sensor"), Uuid::new_v4(), properties);
+    let bytes = entity_instance.to_cbor().unwrap();
+    let decoded = EntityInstance::from_cbor(&bytes).unwrap();
+    assert_eq!(entity_instance, decoded);
+    assert!(bytes.len() < serde_json::to_vec(&entity_instance).unwrap().len());
+}
+
+#[test]
+fn binary_value_cbor_test() {
+    let data: Vec<u8> = (0..=255).collect();
+    let mut properties = PropertyMap::new();
+    properties.insert("data".to_string(), binary_to_value(&data));
+    let entity_instance = EntityInstance::new(EntityTypeName::new("blob"), Uuid::new_v4(), properties);
+    let decoded = EntityInstance::from_cbor(&entity_instance.to_cbor().unwrap()).unwrap();
+    assert_eq!(entity_instance, decoded);
+    assert_eq!(Some(data), value_to_binary(decoded.properties.get("data").unwrap()));
+}
+
+#[test]
+fn flow_cbor_test() {
+    let wrapper = EntityInstance::new_without_properties(EntityTypeName::new("generic_flow"), Uuid::new_v4());
+    let sensor = EntityInstance::new_without_properties(EntityTypeName::new("sensor"), Uuid::new_v4());
+    let mut flow = Flow::from(wrapper.clone());
+    flow.entity_instances.push(sensor.clone());
+    flow.relation_instances
+        .push(RelationInstance::new_without_properties(wrapper.id, RelationTypeName::new("default_connector"), sensor.id));
+    let decoded = Flow::from_cbor(&flow.to_cbor().unwrap()).unwrap();
+    assert_eq!(flow.id, decoded.id);
+    assert_eq!(flow.entity_instances, decoded.entity_instances);
+    assert_eq!(flow.relation_instances, decoded.relation_instances);
+}
+
+#[test]
+fn cbor_decode_error_test() {
+    assert!(matches!(RelationInstance::from_cbor(&[0xff, 0x00]), Err(CborError::Decode(_))));
+    assert!(matches!(from_cbor::<EntityInstance>(&[]), Err(CborError::Decode(_))));
+}
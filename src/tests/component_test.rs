@@ -1,5 +1,7 @@
+use std::collections::HashSet;
+
 use crate::tests::utils::r_string;
-use crate::{Component, DataType, Extension, PropertyType};
+use crate::{get_extension, Component, ComponentName, DataType, Extension, ExtensionError, PropertyGroup, PropertyType};
 use serde_json::json;
 
 #[test]
@@ -17,22 +19,25 @@ fn component_test() {
     let extension = Extension {
         name: extension_name.to_string(),
         extension: extension_value.clone(),
+        version: None,
     };
     extensions.push(extension);
     let extension = Extension::new("other_extension", extension_value.clone());
     extensions.push(extension);
 
     let mut component = Component {
-        name: component_name.clone(),
+        name: ComponentName::new(component_name.clone()),
         description: description.clone(),
         properties: property_types,
         extensions,
+        property_groups: Vec::new(),
+        version: 0,
     };
     let component_name_2 = r_string();
 
     assert_eq!(component_name, component.name);
     assert_eq!(description, component.description);
-    component.name = component_name_2.clone();
+    component.name = ComponentName::new(component_name_2.clone());
     assert_ne!(component_name, component.name);
     assert_eq!(component_name_2, component.name);
     assert_eq!(extension_name.clone(), component.extensions.first().unwrap().name);
@@ -51,7 +56,7 @@ fn create_new_component_test() {
     let property_name = r_string();
     let property_type = PropertyType::new(property_name.clone(), DataType::String);
     property_types.push(property_type.clone());
-    let component = Component::new(component_name.clone(), property_types.clone());
+    let component = Component::new(ComponentName::new(component_name.clone()), property_types.clone());
     assert_eq!(component_name, component.name);
     assert_eq!(property_name.clone(), component.properties.first().unwrap().name);
     assert_eq!(property_type.data_type, component.properties.first().unwrap().data_type);
@@ -74,12 +79,13 @@ fn create_new_component_with_extensions_test() {
     let extension = Extension {
         name: extension_name.to_string(),
         extension: extension_value.clone(),
+        version: None,
     };
     extensions.push(extension);
     let extension = Extension::new("other_extension", extension_value.clone());
     extensions.push(extension);
 
-    let component = Component::new_with_extensions(component_name.clone(), property_types.clone(), extensions.clone());
+    let component = Component::new_with_extensions(ComponentName::new(component_name.clone()), property_types.clone(), extensions.clone());
     assert_eq!(component_name, component.name);
     assert_eq!(property_name.clone(), component.properties.first().unwrap().name);
     assert_eq!(property_type.data_type, component.properties.first().unwrap().data_type);
@@ -95,7 +101,7 @@ fn create_new_component_with_extensions_test() {
 #[test]
 fn create_component_without_properties_test() {
     let component_name = r_string();
-    let component = Component::new_without_properties(component_name.clone());
+    let component = Component::new_without_properties(ComponentName::new(component_name.clone()));
     assert_eq!(component_name, component.name);
 }
 
@@ -106,7 +112,83 @@ fn component_has_property_test() {
     let property_name = r_string();
     let property_type = PropertyType::new(property_name.clone(), DataType::String);
     property_types.push(property_type.clone());
-    let component = Component::new(component_name.clone(), property_types.clone());
+    let component = Component::new(ComponentName::new(component_name.clone()), property_types.clone());
     assert!(component.has_property(property_name));
     assert!(!component.has_property(r_string()));
 }
+
+#[test]
+fn component_eq_test() {
+    let name = r_string();
+    let component_1 = Component::new_without_properties(ComponentName::new(name.clone()));
+    let component_2 = Component::new(ComponentName::new(name.clone()), vec![PropertyType::new(r_string(), DataType::String)]);
+    let component_3 = Component::new_without_properties(ComponentName::new(r_string()));
+    assert_eq!(component_1, component_2);
+    assert_ne!(component_1, component_3);
+    let components: HashSet<Component> = vec![component_1, component_2, component_3].into_iter().collect();
+    assert_eq!(2, components.len());
+}
+
+#[test]
+fn component_get_extension_test() {
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct Palette {
+        color: String,
+        order: u32,
+    }
+    let component = Component::new_with_extensions(
+        ComponentName::new(r_string()),
+        Vec::new(),
+        vec![
+            Extension::new("palette", json!({ "color": "red", "order": 2 })),
+            Extension::new("broken", json!(["red"])),
+        ],
+    );
+    let palette: Palette = component.get_extension("palette").unwrap();
+    assert_eq!(
+        Palette {
+            color: "red".to_string(),
+            order: 2
+        },
+        palette
+    );
+    assert!(matches!(component.get_extension::<Palette, _>("broken"), Err(ExtensionError::Invalid(name, _)) if name == "broken"));
+    assert!(matches!(component.get_extension::<Palette, _>("missing"), Err(ExtensionError::Missing(name)) if name == "missing"));
+    let count: u32 = get_extension(&[Extension::new("count", json!(3))], "count").unwrap();
+    assert_eq!(3, count);
+}
+
+#[test]
+fn component_property_groups_test() {
+    let mut component = Component::new(
+        ComponentName::new(r_string()),
+        vec![
+            PropertyType::new("x", DataType::Number).with_group("Transform"),
+            PropertyType::new("visible", DataType::Bool).with_group("Rendering"),
+            PropertyType::new("debug", DataType::Bool).with_group("Advanced"),
+            PropertyType::new("y", DataType::Number).with_group("Transform"),
+            PropertyType::new("z", DataType::Number),
+        ],
+    );
+    component.property_groups = vec![
+        PropertyGroup::new("Advanced", 10),
+        PropertyGroup::new("Transform", 0),
+        PropertyGroup::new("Rendering", 0),
+    ];
+    let names: Vec<&str> = component.get_property_groups().iter().map(|g| g.name.as_str()).collect();
+    assert_eq!(vec!["Transform", "Rendering", "Advanced"], names);
+    assert_eq!("Advanced", component.get_property_group_of("debug").unwrap().name);
+    assert!(component.get_property_group_of("z").is_none());
+    assert!(component.get_property_group_of("missing").is_none());
+    let members: Vec<&str> = component.get_property_group_members("Transform").iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(vec!["x", "y"], members);
+
+    let component: Component = serde_json::from_value(json!({
+        "name": "transform",
+        "properties": [ { "name": "x", "data_type": "number", "group": "Transform" } ],
+        "property_groups": [ { "name": "Transform" } ]
+    }))
+    .unwrap();
+    assert_eq!(0, component.property_groups[0].order);
+    assert_eq!("Transform", component.get_property_group_of("x").unwrap().name);
+}
@@ -0,0 +1,131 @@
+use serde_json::json;
+
+use crate::{
+    Component, ComponentName, ComposeError, DataType, EntityType, EntityTypeName, Extension, PropertyMap, PropertyType, RelationType, RelationTypeName,
+    SocketType,
+};
+
+fn components() -> Vec<Component> {
+    vec![
+        Component::new_with_extensions(
+            ComponentName::new("labeled"),
+            vec![PropertyType::new("label", DataType::String)],
+            vec![Extension::new("icon", json!({ "name": "label" }))],
+        ),
+        Component::new(
+            ComponentName::new("named"),
+            vec![PropertyType::new("name", DataType::String), PropertyType::new("label", DataType::String)],
+        ),
+        Component::new(ComponentName::new("counter"), vec![PropertyType::output("count", DataType::Number)]),
+    ]
+}
+
+#[test]
+fn compose_entity_type_test() {
+    let entity_type = EntityType::new(
+        EntityTypeName::new("counter"),
+        "",
+        "",
+        vec![ComponentName::new("labeled"), ComponentName::new("named"), ComponentName::new("counter")],
+        vec![
+            PropertyType::output("count", DataType::Number).with_unit("ticks"),
+            PropertyType::input("trigger", DataType::Bool),
+        ],
+        vec![Extension::new("icon", json!({ "name": "counter" }))],
+    );
+    assert!(!entity_type.has_own_property("label"));
+    let composed = entity_type.compose(&components()).unwrap();
+    let property_names: Vec<&str> = composed.properties.iter().map(|property_type| property_type.name.as_str()).collect();
+    assert_eq!(vec!["label", "name", "count", "trigger"], property_names);
+    assert!(composed.has_property("label"));
+    assert_eq!(Some("ticks".to_string()), composed.get_property("count").unwrap().unit);
+    assert_eq!(SocketType::Output, composed.get_property("count").unwrap().socket_type);
+    assert_eq!(1, composed.extensions.len());
+    assert!(composed.has_extension("icon"));
+    assert_eq!(json!({ "name": "counter" }), composed.extensions[0].extension);
+
+    let mut properties = PropertyMap::new();
+    properties.insert("label".to_string(), json!("Counter"));
+    properties.insert("name".to_string(), json!("counter"));
+    properties.insert("count".to_string(), json!(0));
+    assert!(!composed.validate(&properties).is_valid());
+    properties.insert("trigger".to_string(), json!(false));
+    assert!(composed.validate(&properties).is_valid());
+}
+
+#[test]
+fn compose_relation_type_test() {
+    let relation_type = RelationType::new(
+        "*",
+        RelationTypeName::new("connector"),
+        "*",
+        "",
+        "",
+        vec![ComponentName::new("labeled")],
+        Vec::new(),
+        Vec::new(),
+    );
+    let composed = relation_type.compose(&components()).unwrap();
+    assert!(composed.has_property("label"));
+    assert!(composed.has_extension("icon"));
+    assert!(!composed.has_property("name"));
+
+    let relation_type = RelationType::new(
+        "*",
+        RelationTypeName::new("connector"),
+        "*",
+        "",
+        "",
+        vec![ComponentName::new("missing")],
+        Vec::new(),
+        Vec::new(),
+    );
+    assert_eq!(ComposeError::MissingComponent("missing".to_string()), relation_type.compose(&components()).unwrap_err());
+}
+
+#[test]
+fn compose_conflict_test() {
+    let mut components = components();
+    components.push(Component::new(ComponentName::new("counting"), vec![PropertyType::input("count", DataType::Number)]));
+    components.push(Component::new_with_extensions(
+        ComponentName::new("iconified"),
+        Vec::new(),
+        vec![Extension::new("icon", json!({ "name": "other" }))],
+    ));
+    let entity_type = EntityType::new(
+        EntityTypeName::new("counter"),
+        "",
+        "",
+        vec![ComponentName::new("counter"), ComponentName::new("counting")],
+        Vec::new(),
+        Vec::new(),
+    );
+    assert_eq!(ComposeError::ConflictingProperty("count".to_string()), entity_type.compose(&components).unwrap_err());
+    let entity_type = EntityType::new(
+        EntityTypeName::new("counter"),
+        "",
+        "",
+        vec![ComponentName::new("counter")],
+        vec![PropertyType::new("count", DataType::String)],
+        Vec::new(),
+    );
+    assert_eq!(ComposeError::ConflictingProperty("count".to_string()), entity_type.compose(&components).unwrap_err());
+    let entity_type = EntityType::new(
+        EntityTypeName::new("counter"),
+        "",
+        "",
+        vec![ComponentName::new("counter")],
+        vec![PropertyType::input("count", DataType::Number)],
+        Vec::new(),
+    );
+    assert_eq!(ComposeError::ConflictingProperty("count".to_string()), entity_type.compose(&components).unwrap_err());
+    let entity_type = EntityType::new(
+        EntityTypeName::new("counter"),
+        "",
+        "",
+        vec![ComponentName::new("labeled"), ComponentName::new("iconified")],
+        Vec::new(),
+        Vec::new(),
+    );
+    assert_eq!(ComposeError::ConflictingExtension("icon".to_string()), entity_type.compose(&components).unwrap_err());
+}
@@ -0,0 +1,67 @@
+use std::sync::{Arc, RwLock};
+
+use uuid::Uuid;
+
+use crate::tests::utils::create_random_entity_instance::create_random_entity_instance;
+use crate::tests::utils::r_string;
+use crate::{
+    BehaviourName, ComponentName, CompositionChange, CompositionEvent, CompositionTarget, ReactiveRelationInstance, RelationInstanceKey, RelationTypeName,
+};
+
+#[test]
+fn composition_event_entity_test() {
+    let entity_instance = create_random_entity_instance(r_string());
+    let events: Arc<RwLock<Vec<CompositionEvent>>> = Arc::new(RwLock::new(Vec::new()));
+    let events_2 = events.clone();
+    let handle_id = Uuid::new_v4().as_u128();
+    entity_instance.observe_composition(handle_id, move |event| events_2.write().unwrap().push(event.clone()));
+
+    entity_instance.add_component(ComponentName::new("labeled"));
+    // Adding an existing component doesn't change the composition
+    entity_instance.add_component(ComponentName::new("labeled"));
+    entity_instance.add_behaviour(BehaviourName::new("blink"));
+    entity_instance.remove_component(ComponentName::new("labeled"));
+    entity_instance.remove_behaviour(BehaviourName::new("blink"));
+    entity_instance.remove_behaviour(BehaviourName::new("blink"));
+    // Changes of other instances are not observed
+    create_random_entity_instance(r_string()).add_component(ComponentName::new("labeled"));
+
+    let target = CompositionTarget::Entity(entity_instance.id);
+    let changes: Vec<(CompositionChange, String)> = events.read().unwrap().iter().map(|event| (event.change, event.name.clone())).collect();
+    assert_eq!(
+        vec![
+            (CompositionChange::ComponentAdded, "labeled".to_string()),
+            (CompositionChange::BehaviourAdded, "blink".to_string()),
+            (CompositionChange::ComponentRemoved, "labeled".to_string()),
+            (CompositionChange::BehaviourRemoved, "blink".to_string()),
+        ],
+        changes
+    );
+    assert!(events.read().unwrap().iter().all(|event| event.target == target));
+
+    entity_instance.remove_composition_observer(handle_id);
+    entity_instance.add_component(ComponentName::new("labeled"));
+    assert_eq!(4, events.read().unwrap().len());
+}
+
+#[test]
+fn composition_event_relation_test() {
+    let outbound = Arc::new(create_random_entity_instance(r_string()));
+    let inbound = Arc::new(create_random_entity_instance(r_string()));
+    let relation_instance = ReactiveRelationInstance::builder(outbound, RelationTypeName::new(r_string()), inbound)
+        .build()
+        .unwrap();
+    let target = CompositionTarget::Relation(RelationInstanceKey::from(&relation_instance));
+    let events: Arc<RwLock<Vec<CompositionEvent>>> = Arc::new(RwLock::new(Vec::new()));
+    let events_2 = events.clone();
+    let handle_id = Uuid::new_v4().as_u128();
+    relation_instance.observe_composition(handle_id, move |event| events_2.write().unwrap().push(event.clone()));
+
+    relation_instance.add_behaviour(BehaviourName::new("connector"));
+    relation_instance.remove_composition_observer(handle_id);
+    relation_instance.remove_behaviour(BehaviourName::new("connector"));
+
+    assert_eq!(1, events.read().unwrap().len());
+    assert_eq!(CompositionChange::BehaviourAdded, events.read().unwrap()[0].change);
+    assert_eq!(target, events.read().unwrap()[0].target);
+}
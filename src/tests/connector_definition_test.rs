@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::tests::utils::{create_random_entity_instance, r_string};
+use crate::{
+    Connector, ConnectorDefinition, ConnectorDefinitionError, InstanceConversionError, PropertyInstanceGetter, PropertyMap, RelationInstance, RelationTypeName,
+};
+use crate::{BUFFER_SIZE, FUNCTION_NAME, INBOUND_PROPERTY_NAME, OUTBOUND_PROPERTY_NAME, THROTTLE};
+
+#[test]
+fn connector_definition_properties_test() {
+    let connector_definition = ConnectorDefinition::new("value", "trigger");
+    let properties = connector_definition.get_properties();
+    assert_eq!(2, properties.len());
+    assert_eq!(json!("value"), properties[OUTBOUND_PROPERTY_NAME]);
+    assert_eq!(json!("trigger"), properties[INBOUND_PROPERTY_NAME]);
+
+    let properties = connector_definition.with_buffer_size(10).get_properties();
+    assert_eq!(json!(10), properties[BUFFER_SIZE]);
+}
+
+#[test]
+fn connector_definition_validate_test() {
+    let outbound_property_name = r_string();
+    let inbound_property_name = r_string();
+    let outbound = create_random_entity_instance(outbound_property_name.clone());
+    let inbound = create_random_entity_instance(inbound_property_name.clone());
+
+    let connector_definition = ConnectorDefinition::new(outbound_property_name.clone(), inbound_property_name.clone());
+    assert!(connector_definition.validate(&outbound, &inbound).is_ok());
+    assert_eq!(
+        Err(ConnectorDefinitionError::InvalidBufferSize),
+        connector_definition.clone().with_buffer_size(0).validate(&outbound, &inbound)
+    );
+    assert_eq!(
+        Err(ConnectorDefinitionError::EmptyPropertyName(OUTBOUND_PROPERTY_NAME)),
+        ConnectorDefinition::new(String::new(), inbound_property_name.clone()).validate(&outbound, &inbound)
+    );
+    assert_eq!(
+        Err(ConnectorDefinitionError::MissingOutboundProperty(inbound_property_name.clone())),
+        ConnectorDefinition::new(inbound_property_name.clone(), inbound_property_name.clone()).validate(&outbound, &inbound)
+    );
+    assert_eq!(
+        Err(ConnectorDefinitionError::MissingInboundProperty(outbound_property_name.clone())),
+        ConnectorDefinition::new(outbound_property_name.clone(), outbound_property_name.clone()).validate(&outbound, &inbound)
+    );
+}
+
+#[test]
+fn connector_definition_create_relation_instance_test() {
+    let outbound = Arc::new(create_random_entity_instance("value"));
+    let inbound = Arc::new(create_random_entity_instance("trigger"));
+    let mut properties = PropertyMap::new();
+    properties.insert("delay".to_string(), json!(100));
+    let relation_instance = ConnectorDefinition::new("value", "trigger")
+        .with_buffer_size(4)
+        .create_relation_instance(outbound.clone(), RelationTypeName::new("buffered_connector"), inbound.clone(), properties)
+        .unwrap();
+    assert_eq!("buffered_connector", relation_instance.type_name);
+    assert_eq!("value", relation_instance.as_string(OUTBOUND_PROPERTY_NAME).unwrap());
+    assert_eq!("trigger", relation_instance.as_string(INBOUND_PROPERTY_NAME).unwrap());
+    assert_eq!(4, relation_instance.as_u64(BUFFER_SIZE).unwrap());
+    assert_eq!(100, relation_instance.as_u64("delay").unwrap());
+
+    let result = ConnectorDefinition::new("value", "missing").create_relation_instance(
+        outbound.clone(),
+        RelationTypeName::new("default_connector"),
+        inbound.clone(),
+        PropertyMap::new(),
+    );
+    assert!(result.is_err());
+
+    // An invalid type name is reported instead of panicking
+    let result = ConnectorDefinition::new("value", "trigger").create_relation_instance(outbound, RelationTypeName::new(""), inbound, PropertyMap::new());
+    assert_eq!(
+        Some(ConnectorDefinitionError::InvalidRelationInstance(InstanceConversionError::InvalidIdentifier(String::new()))),
+        result.err()
+    );
+}
+
+#[test]
+fn connector_relation_instance_test() {
+    let connector = Connector::new("value", "trigger").with_function_name("negate").with_throttle(50);
+    let properties = connector.get_properties();
+    assert_eq!(json!("negate"), properties[FUNCTION_NAME]);
+    assert_eq!(json!(50), properties[THROTTLE]);
+
+    let outbound_id = Uuid::new_v4();
+    let inbound_id = Uuid::new_v4();
+    let relation_instance = connector.to_relation_instance(outbound_id, RelationTypeName::new("default_connector"), inbound_id);
+    assert_eq!(outbound_id, relation_instance.outbound_id);
+    assert_eq!("default_connector", relation_instance.type_name);
+    assert_eq!(Some(connector.clone()), Connector::from_properties(&relation_instance));
+    assert_eq!(
+        None,
+        Connector::from_properties(&RelationInstance::new_without_properties(outbound_id, RelationTypeName::new("default_connector"), inbound_id))
+    );
+
+    let outbound = create_random_entity_instance("value");
+    let inbound = create_random_entity_instance("trigger");
+    assert_eq!(Err(ConnectorDefinitionError::InvalidThrottle), connector.with_throttle(0).validate(&outbound, &inbound));
+}
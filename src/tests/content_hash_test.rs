@@ -0,0 +1,141 @@
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{value_content_hash, EntityInstance, EntityTypeName, Flow, PropertyMap, RelationInstance, RelationTypeName};
+
+#[test]
+fn entity_instance_content_hash_test() {
+    let id = Uuid::parse_str("0b2c0d4e-6a5f-4b1e-9c3d-2a1b0c9d8e7f").unwrap();
+    let mut properties = PropertyMap::new();
+    properties.insert("a".to_string(), json!({ "x": 1, "y": [true, null, "s"] }));
+    properties.insert("b".to_string(), json!(2.0));
+    let entity_instance = EntityInstance::new(EntityTypeName::new("player"), id, properties);
+    let hash = entity_instance.content_hash();
+    // The hash is stable
+    assert_eq!(hash, entity_instance.clone().content_hash());
+    assert_eq!(
+        0x0bde_88eb_d8ee_b272,
+        EntityInstance::new_without_properties(EntityTypeName::new("player"), Uuid::nil()).content_hash()
+    );
+    let mut reordered = PropertyMap::new();
+    reordered.insert("b".to_string(), json!(2));
+    reordered.insert("a".to_string(), serde_json::from_str(r#"{ "y": [true, null, "s"], "x": 1.0 }"#).unwrap());
+    assert_eq!(hash, EntityInstance::new(EntityTypeName::new("player"), id, reordered).content_hash());
+
+    let mut changed = entity_instance.clone();
+    changed.properties.insert("b".to_string(), json!(3));
+    assert_ne!(hash, changed.content_hash());
+    assert_ne!(
+        hash,
+        EntityInstance {
+            type_name: EntityTypeName::new("camera"),
+            ..entity_instance.clone()
+        }
+        .content_hash()
+    );
+    assert_ne!(
+        hash,
+        EntityInstance {
+            id: Uuid::new_v4(),
+            ..entity_instance.clone()
+        }
+        .content_hash()
+    );
+    // The description isn't part of the content
+    assert_eq!(
+        hash,
+        EntityInstance {
+            description: "Player".to_string(),
+            ..entity_instance
+        }
+        .content_hash()
+    );
+
+    let mut string_property = PropertyMap::new();
+    string_property.insert("ab".to_string(), json!("c"));
+    let mut other_string_property = PropertyMap::new();
+    other_string_property.insert("a".to_string(), json!("bc"));
+    assert_ne!(
+        EntityInstance::new(EntityTypeName::new("player"), id, string_property).content_hash(),
+        EntityInstance::new(EntityTypeName::new("player"), id, other_string_property).content_hash()
+    );
+}
+
+#[test]
+fn relation_instance_content_hash_test() {
+    let outbound_id = Uuid::new_v4();
+    let inbound_id = Uuid::new_v4();
+    let mut properties = PropertyMap::new();
+    properties.insert("weight".to_string(), json!(0.5));
+    let relation_instance = RelationInstance::new(outbound_id, RelationTypeName::new("connector"), inbound_id, properties);
+    let hash = relation_instance.content_hash();
+    assert_eq!(hash, relation_instance.clone().content_hash());
+    let reversed = RelationInstance::new(inbound_id, RelationTypeName::new("connector"), outbound_id, relation_instance.properties.clone());
+    assert_ne!(hash, reversed.content_hash());
+    let mut changed = relation_instance;
+    changed.properties.insert("weight".to_string(), json!(0.25));
+    assert_ne!(hash, changed.content_hash());
+}
+
+#[test]
+fn flow_content_hash_test() {
+    let wrapper = EntityInstance::new_without_properties(EntityTypeName::new("generic_flow"), Uuid::new_v4());
+    let a = EntityInstance::new_without_properties(EntityTypeName::new("a"), Uuid::new_v4());
+    let b = EntityInstance::new_without_properties(EntityTypeName::new("b"), Uuid::new_v4());
+    let mut flow = Flow::from(wrapper.clone());
+    flow.entity_instances.push(a.clone());
+    flow.entity_instances.push(b.clone());
+    flow.relation_instances
+        .push(RelationInstance::new(a.id, RelationTypeName::new("connector"), b.id, PropertyMap::new()));
+    let hash = flow.content_hash();
+
+    let mut reordered = Flow::from(wrapper);
+    reordered.entity_instances.insert(0, b);
+    reordered.entity_instances.push(a.clone());
+    reordered.relation_instances = flow.relation_instances.clone();
+    assert_eq!(hash, reordered.content_hash());
+
+    flow.entity_instances[1].properties.insert("x".to_string(), json!(1));
+    assert_ne!(hash, flow.content_hash());
+}
+
+#[test]
+fn labels_content_hash_test() {
+    let id = Uuid::parse_str("0b2c0d4e-6a5f-4b1e-9c3d-2a1b0c9d8e7f").unwrap();
+    let entity_instance = EntityInstance::new_without_properties(EntityTypeName::new("player"), id);
+    let hash = entity_instance.content_hash();
+    let labeled = EntityInstance {
+        labels: vec!["debug".to_string(), "level:3".to_string()],
+        ..entity_instance.clone()
+    };
+    assert_ne!(hash, labeled.content_hash());
+    // The order of the labels doesn't matter
+    let reordered = EntityInstance {
+        labels: vec!["level:3".to_string(), "debug".to_string()],
+        ..entity_instance
+    };
+    assert_eq!(labeled.content_hash(), reordered.content_hash());
+
+    let relation_instance = RelationInstance::new_without_properties(Uuid::nil(), RelationTypeName::new("connector"), id);
+    let hash = relation_instance.content_hash();
+    let labeled = RelationInstance {
+        labels: vec!["debug".to_string()],
+        ..relation_instance
+    };
+    assert_ne!(hash, labeled.content_hash());
+}
+
+#[test]
+fn value_content_hash_test() {
+    // The hash is stable across platforms and releases
+    assert_eq!(0xbf82_9831_cb98_d3f1, value_content_hash(&json!("alice")));
+    let object: serde_json::Value = serde_json::from_str(r#"{ "b": [1, 2.0], "a": null }"#).unwrap();
+    assert_eq!(value_content_hash(&json!({ "a": null, "b": [1.0, 2] })), value_content_hash(&object));
+    assert_ne!(value_content_hash(&json!("1")), value_content_hash(&json!(1)));
+    // Integral floats beyond the range of i64 are equal to the same unsigned integers
+    assert_eq!(
+        value_content_hash(&json!(9_223_372_036_854_775_808.0)),
+        value_content_hash(&json!(9_223_372_036_854_775_808u64))
+    );
+    assert_ne!(value_content_hash(&json!(9_223_372_036_854_775_808.0)), value_content_hash(&json!(i64::MAX)));
+}
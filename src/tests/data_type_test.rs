@@ -1,7 +1,10 @@
-use serde_json::Value;
+use serde_json::{json, Value};
+use uuid::Uuid;
 
 use crate::tests::utils::r_string;
 use crate::DataType;
+use crate::{binary_to_value, uuid_to_value, value_to_binary, value_to_uuid};
+use crate::{coerce_to_bool, coerce_to_f64, coerce_to_i64, coerce_to_string};
 
 #[test]
 fn data_type_should_be_created_using_static_method_call() {
@@ -54,10 +57,21 @@ fn data_type_from_str() {
     assert_eq!(DataType::Any, DataType::from("Any"));
     assert_eq!(DataType::Any, DataType::from("ANY"));
 
+    assert_eq!(DataType::Binary, DataType::from("binary"));
+    assert_eq!(DataType::DateTime, DataType::from("DateTime"));
+    assert_eq!(DataType::Uuid, DataType::from("UUID"));
+
     // Fallback to String
     assert_eq!(DataType::String, DataType::from(r_string().as_str()));
 }
 
+#[test]
+fn data_type_parse_test() {
+    assert_eq!(Some(DataType::Number), DataType::parse("Number"));
+    assert_eq!(Some(DataType::DateTime), DataType::parse("datetime"));
+    assert_eq!(None, DataType::parse("numbr"));
+}
+
 #[test]
 fn data_type_display() {
     assert_eq!("Bool", format!("{}", DataType::Bool));
@@ -66,3 +80,84 @@ fn data_type_display() {
     assert_eq!("Array", format!("{}", DataType::Array));
     assert_eq!("Object", format!("{}", DataType::Object));
 }
+
+#[test]
+fn data_type_serde_test() {
+    assert_eq!(json!("binary"), serde_json::to_value(DataType::Binary).unwrap());
+    assert_eq!(json!("datetime"), serde_json::to_value(DataType::DateTime).unwrap());
+    assert_eq!(json!("uuid"), serde_json::to_value(DataType::Uuid).unwrap());
+    assert_eq!(DataType::Uuid, serde_json::from_value::<DataType>(json!("uuid")).unwrap());
+}
+
+#[test]
+fn data_type_binary_test() {
+    let data = vec![0u8, 1, 2, 254, 255];
+    let value = binary_to_value(&data);
+    assert_eq!(json!("AAEC/v8="), value);
+    assert_eq!(data, value_to_binary(&value).unwrap());
+    assert!(value_to_binary(&json!("not base64!")).is_none());
+    assert!(DataType::Binary.is_valid(&value));
+    assert!(!DataType::Binary.is_valid(&json!(1)));
+    assert_eq!(Vec::<u8>::new(), value_to_binary(&DataType::Binary.default_value()).unwrap());
+}
+
+#[test]
+fn data_type_uuid_test() {
+    let uuid = Uuid::new_v4();
+    let value = uuid_to_value(uuid);
+    assert_eq!(json!(uuid.to_string()), value);
+    assert_eq!(uuid, value_to_uuid(&value).unwrap());
+    assert!(value_to_uuid(&json!(r_string())).is_none());
+    assert_eq!(Uuid::nil(), value_to_uuid(&DataType::Uuid.default_value()).unwrap());
+    assert!(DataType::Uuid.is_valid(&value));
+    assert!(DataType::Any.is_valid(&json!(null)));
+    assert!(!DataType::Number.is_valid(&json!("1")));
+}
+
+#[test]
+fn data_type_coerce() {
+    assert_eq!(Some(json!(12)), DataType::Number.coerce(&json!("12")));
+    assert_eq!(Some(json!(1.5)), DataType::Number.coerce(&json!(" 1.5 ")));
+    assert_eq!(Some(json!(0)), DataType::Number.coerce(&json!(false)));
+    assert_eq!(None, DataType::Number.coerce(&json!("one")));
+    assert_eq!(Some(json!(true)), DataType::Bool.coerce(&json!(2)));
+    assert_eq!(Some(json!(false)), DataType::Bool.coerce(&json!("false")));
+    assert_eq!(Some(json!(true)), DataType::Bool.coerce(&json!(" Yes ")));
+    assert_eq!(Some(json!(false)), DataType::Bool.coerce(&json!("off")));
+    assert_eq!(Some(json!("4")), DataType::String.coerce(&json!(4)));
+    assert_eq!(Some(json!([4, "a"])), DataType::Array.coerce(&json!(" [4, \"a\"] ")));
+    assert_eq!(None, DataType::Array.coerce(&json!(4)));
+    assert_eq!(None, DataType::Array.coerce(&json!("4")));
+    assert_eq!(None, DataType::Array.coerce(&json!({ "a": 4 })));
+    assert_eq!(None, DataType::Object.coerce(&json!(4)));
+}
+
+#[test]
+fn coerce_value_test() {
+    assert_eq!(Some(42), coerce_to_i64(&json!("42")));
+    assert_eq!(Some(-3), coerce_to_i64(&json!(" -3.0 ")));
+    assert_eq!(Some(3), coerce_to_i64(&json!(3.0)));
+    assert_eq!(Some(1), coerce_to_i64(&json!(true)));
+    assert_eq!(None, coerce_to_i64(&json!(3.5)));
+    assert_eq!(None, coerce_to_i64(&json!("abc")));
+    assert_eq!(None, coerce_to_i64(&json!(u64::MAX)));
+
+    assert_eq!(Some(3.0), coerce_to_f64(&json!(3)));
+    assert_eq!(Some(1.5), coerce_to_f64(&json!("1.5")));
+    assert_eq!(Some(0.0), coerce_to_f64(&json!(false)));
+    assert_eq!(None, coerce_to_f64(&json!(null)));
+
+    assert_eq!(Some(true), coerce_to_bool(&json!(1)));
+    assert_eq!(Some(false), coerce_to_bool(&json!(0.0)));
+    assert_eq!(Some(true), coerce_to_bool(&json!("Yes")));
+    assert_eq!(Some(false), coerce_to_bool(&json!("off")));
+    assert_eq!(None, coerce_to_bool(&json!("maybe")));
+    for value in [json!(2), json!(" TRUE "), json!("no"), json!("maybe"), json!(null)] {
+        assert_eq!(DataType::Bool.coerce(&value).and_then(|value| value.as_bool()), coerce_to_bool(&value));
+    }
+
+    assert_eq!(Some("42".to_string()), coerce_to_string(&json!(42)));
+    assert_eq!(Some("true".to_string()), coerce_to_string(&json!(true)));
+    assert_eq!(Some("abc".to_string()), coerce_to_string(&json!("abc")));
+    assert_eq!(None, coerce_to_string(&json!([1])));
+}
@@ -1,13 +1,16 @@
-use std::collections::HashMap;
+use std::collections::HashSet;
 use std::ops::Index;
+#[cfg(feature = "graph-db")]
 use std::str::FromStr;
 
+#[cfg(feature = "graph-db")]
 use indradb::{Identifier, NamedProperty, Vertex, VertexProperties};
 use serde_json::json;
 use uuid::Uuid;
 
 use crate::tests::utils::r_string;
 use crate::EntityInstance;
+use crate::{DataType, EntityType, EntityTypeName, PropertyMap, PropertyType, ValidationError};
 use crate::{MutablePropertyInstanceSetter, PropertyInstanceGetter};
 
 #[test]
@@ -17,13 +20,16 @@ fn entity_instance_test() {
     let description = r_string();
     let property_name = r_string();
     let property_value = json!(r_string());
-    let mut properties = HashMap::new();
+    let mut properties = PropertyMap::new();
     properties.insert(property_name.clone(), property_value.clone());
     let entity_instance = EntityInstance {
-        type_name: type_name.clone(),
+        type_name: EntityTypeName::new(type_name.clone()),
         id: uuid.clone(),
         description: description.to_string(),
         properties: properties.clone(),
+        components: Vec::new(),
+        labels: Vec::new(),
+        behaviours: Vec::new(),
     };
     assert_eq!(type_name.clone(), entity_instance.type_name.clone());
     assert_eq!(uuid.clone(), entity_instance.id.clone());
@@ -40,9 +46,9 @@ fn create_entity_instance_test() {
     let type_name = r_string();
     let property_name = r_string();
     let property_value = json!(r_string());
-    let mut properties = HashMap::new();
+    let mut properties = PropertyMap::new();
     properties.insert(property_name.clone(), property_value.clone());
-    let entity_instance = EntityInstance::new(type_name.clone(), uuid.clone(), properties.clone());
+    let entity_instance = EntityInstance::new(EntityTypeName::new(type_name.clone()), uuid.clone(), properties.clone());
     assert_eq!(type_name.clone(), entity_instance.type_name.clone());
     assert_eq!(uuid.clone(), entity_instance.id.clone());
     assert_eq!(properties.clone(), properties.clone());
@@ -55,12 +61,13 @@ fn create_entity_instance_test() {
 fn create_entity_instance_without_properties_test() {
     let uuid = Uuid::new_v4();
     let type_name = r_string();
-    let entity_instance = EntityInstance::new_without_properties(type_name.clone(), uuid.clone());
+    let entity_instance = EntityInstance::new_without_properties(EntityTypeName::new(type_name.clone()), uuid.clone());
     assert_eq!(type_name.clone(), entity_instance.type_name.clone());
     assert_eq!(uuid.clone(), entity_instance.id.clone());
     assert!(entity_instance.get(r_string()).is_none());
 }
 
+#[cfg(feature = "graph-db")]
 #[test]
 fn create_entity_instance_from_vertex_properties() {
     let uuid = Uuid::new_v4();
@@ -89,9 +96,9 @@ fn entity_instance_typed_getter_test() {
     let uuid = Uuid::new_v4();
     let type_name = r_string();
     let property_name = r_string();
-    let mut properties = HashMap::new();
+    let mut properties = PropertyMap::new();
     properties.insert(property_name.clone(), json!(false));
-    let mut i = EntityInstance::new(type_name.clone(), uuid.clone(), properties.clone());
+    let mut i = EntityInstance::new(EntityTypeName::new(type_name.clone()), uuid.clone(), properties.clone());
     i.set(property_name.clone(), json!(true));
     assert!(i.as_bool(property_name.clone()).unwrap());
     i.set(property_name.clone(), json!(false));
@@ -116,3 +123,135 @@ fn entity_instance_typed_getter_test() {
     i.set(property_name.clone(), o.clone());
     assert_eq!(json!("v"), i.as_object(property_name.clone()).unwrap().index("k").clone());
 }
+
+#[test]
+fn entity_instance_eq_test() {
+    let id = Uuid::new_v4();
+    let mut entity_instance_1 = EntityInstance::new_without_properties(EntityTypeName::new(r_string()), id);
+    entity_instance_1.properties.insert(r_string(), json!(1));
+    let mut entity_instance_2 = entity_instance_1.clone();
+    assert_eq!(entity_instance_1, entity_instance_2);
+    assert_ne!(
+        entity_instance_1,
+        EntityInstance::new(EntityTypeName::new(r_string()), Uuid::new_v4(), entity_instance_1.properties.clone())
+    );
+
+    let property_name = entity_instance_1.properties.keys().next().unwrap().clone();
+    entity_instance_2.set(property_name, json!(2));
+    assert_ne!(entity_instance_1, entity_instance_2);
+
+    // The hash is identity-based
+    let entity_instances: HashSet<EntityInstance> = vec![entity_instance_1.clone(), entity_instance_1].into_iter().collect();
+    assert_eq!(1, entity_instances.len());
+}
+
+#[test]
+fn entity_instance_display_test() {
+    let id = Uuid::new_v4();
+    let entity_instance = EntityInstance::new_without_properties(EntityTypeName::new("player"), id);
+    assert_eq!(format!("player({})", id), entity_instance.to_string());
+}
+
+#[test]
+fn entity_instance_non_primitive_getter_test() {
+    let mut entity_instance = EntityInstance::new_without_properties(EntityTypeName::new(r_string()), Uuid::new_v4());
+    let uuid = Uuid::new_v4();
+    entity_instance.properties.insert("binary".to_string(), json!("AAEC"));
+    entity_instance.properties.insert("uuid".to_string(), json!(uuid.to_string()));
+    assert_eq!(vec![0u8, 1, 2], entity_instance.as_binary("binary").unwrap());
+    assert_eq!(uuid, entity_instance.as_uuid("uuid").unwrap());
+    assert!(entity_instance.as_uuid("binary").is_none());
+    assert!(entity_instance.as_uuid(r_string()).is_none());
+}
+
+#[test]
+fn entity_instance_builder_test() {
+    let id = Uuid::new_v4();
+    let entity_instance = EntityInstance::builder()
+        .type_name(EntityTypeName::new("player"))
+        .id(id)
+        .description("The player")
+        .property("name", json!("Jane"))
+        .property("score", json!(0))
+        .build();
+    assert_eq!("player", entity_instance.type_name);
+    assert_eq!(id, entity_instance.id);
+    assert_eq!("The player", entity_instance.description);
+    assert_eq!(json!("Jane"), entity_instance.get("name").unwrap());
+    assert_eq!(2, entity_instance.properties.len());
+    assert_ne!(id, EntityInstance::builder().type_name(EntityTypeName::new("player")).build().id);
+}
+
+#[test]
+fn entity_instance_builder_checked_test() {
+    let entity_type = EntityType::new(
+        EntityTypeName::new("player"),
+        "",
+        "",
+        Vec::new(),
+        vec![PropertyType::new("name", DataType::String), PropertyType::new("score", DataType::Number)],
+        Vec::new(),
+    );
+    let entity_instance = EntityInstance::builder()
+        .property("name", json!("Jane"))
+        .property("score", json!(0))
+        .build_checked(&entity_type)
+        .unwrap();
+    assert_eq!("player", entity_instance.type_name);
+
+    let report = EntityInstance::builder().property("name", json!(1)).build_checked(&entity_type).unwrap_err();
+    assert_eq!(
+        vec![
+            ValidationError::InvalidDataType {
+                property_name: "name".to_string(),
+                expected: DataType::String
+            },
+            ValidationError::MissingProperty {
+                property_name: "score".to_string()
+            },
+        ],
+        report.errors
+    );
+}
+
+#[test]
+fn entity_instance_serde_names_test() {
+    let id = Uuid::new_v4();
+    let entity_instance: EntityInstance = serde_json::from_value(json!({ "type": "player", "id": id })).unwrap();
+    assert_eq!("player", entity_instance.type_name);
+    let json = serde_json::to_value(&entity_instance).unwrap();
+    assert_eq!(json!("player"), json["type_name"]);
+    assert!(json.get("type").is_none());
+    let entity_instance: EntityInstance = serde_json::from_value(json).unwrap();
+    assert_eq!("player", entity_instance.type_name);
+    assert_eq!(id, entity_instance.id);
+}
+
+#[test]
+fn entity_instance_get_by_pointer_test() {
+    let mut properties = PropertyMap::new();
+    properties.insert("config".to_string(), json!({ "window": { "width": 800, "titles": ["main"] } }));
+    let entity_instance = EntityInstance::new(EntityTypeName::new("app"), Uuid::new_v4(), properties);
+    assert_eq!(json!(800), entity_instance.get_by_pointer("config", "/window/width").unwrap());
+    assert_eq!(json!("main"), entity_instance.get_by_pointer("config", "/window/titles/0").unwrap());
+    assert_eq!(entity_instance.get("config"), entity_instance.get_by_pointer("config", ""));
+    assert!(entity_instance.get_by_pointer("config", "/window/height").is_none());
+    assert!(entity_instance.get_by_pointer("missing", "/window").is_none());
+    assert_eq!(Some(true), entity_instance.with_property("config", |value| value.is_object()));
+}
+
+#[test]
+fn entity_instance_coerce_test() {
+    let mut properties = PropertyMap::new();
+    properties.insert("count".to_string(), json!("42"));
+    properties.insert("enabled".to_string(), json!(1));
+    properties.insert("ratio".to_string(), json!(3));
+    properties.insert("label".to_string(), json!(7.5));
+    let entity_instance = EntityInstance::new(EntityTypeName::new("sensor"), Uuid::new_v4(), properties);
+    assert_eq!(Some(42), entity_instance.coerce_i64("count"));
+    assert_eq!(None, entity_instance.as_i64("count"));
+    assert_eq!(Some(true), entity_instance.coerce_bool("enabled"));
+    assert_eq!(Some(3.0), entity_instance.coerce_f64("ratio"));
+    assert_eq!(Some("7.5".to_string()), entity_instance.coerce_string("label"));
+    assert_eq!(None, entity_instance.coerce_i64("missing"));
+}
@@ -0,0 +1,128 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use serde_json::json;
+use serde_json::Value;
+
+use crate::tests::utils::{create_random_entity_instance, create_random_entity_instance_with_type, create_random_relation_instance_with_properties, r_string};
+use crate::{
+    migrate_entity_instance, BehaviourName, ComponentName, PropertyInstanceGetter, PropertyInstanceSetter, PropertyReplacementPolicy, ReactiveFlow,
+    ReactivePropertyContainer, ReplacementPolicy,
+};
+
+#[test]
+fn migrate_entity_instance_test() {
+    let old_entity = create_random_entity_instance("value");
+    old_entity.set("value", json!(42));
+    old_entity.add_property("legacy", json!("x"));
+    old_entity.add_component(ComponentName::new("counter"));
+    old_entity.add_behaviour(BehaviourName::new("increment"));
+    let new_entity = create_random_entity_instance("value");
+
+    let received = Arc::new(AtomicUsize::new(0));
+    let r = received.clone();
+    old_entity.properties.get("value").unwrap().stream.read().unwrap().observe(move |_: &Value| {
+        r.fetch_add(1, Ordering::SeqCst);
+    });
+
+    let policy = ReplacementPolicy {
+        behaviours: false,
+        ..ReplacementPolicy::default()
+    };
+    migrate_entity_instance(&old_entity, &new_entity, &policy);
+    assert_eq!(json!(42), new_entity.get("value").unwrap());
+    assert!(new_entity.get("legacy").is_none());
+    assert!(new_entity.is_a(ComponentName::new("counter")));
+    assert!(!new_entity.behaves_as(BehaviourName::new("increment")));
+    new_entity.set("value", json!(43));
+    assert_eq!(1, received.load(Ordering::SeqCst));
+
+    let new_entity = create_random_entity_instance("value");
+    let policy = ReplacementPolicy {
+        properties: PropertyReplacementPolicy::CopyAll,
+        observers: false,
+        ..ReplacementPolicy::default()
+    };
+    migrate_entity_instance(&old_entity, &new_entity, &policy);
+    assert_eq!(json!("x"), new_entity.get("legacy").unwrap());
+    assert!(new_entity.behaves_as(BehaviourName::new("increment")));
+    new_entity.set("value", json!(44));
+    assert_eq!(1, received.load(Ordering::SeqCst));
+
+    let new_entity = create_random_entity_instance("value");
+    let value = new_entity.get("value").unwrap();
+    let policy = ReplacementPolicy {
+        properties: PropertyReplacementPolicy::Keep,
+        ..ReplacementPolicy::default()
+    };
+    migrate_entity_instance(&old_entity, &new_entity, &policy);
+    assert_eq!(value, new_entity.get("value").unwrap());
+}
+
+#[test]
+fn migrate_entity_instance_observers_test() {
+    let old_entity = create_random_entity_instance("value");
+    let new_entity = create_random_entity_instance("value");
+    let counter = |counter: &Arc<AtomicUsize>| {
+        let counter = counter.clone();
+        move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }
+    };
+    let old_received = Arc::new(AtomicUsize::new(0));
+    let increment = counter(&old_received);
+    old_entity.properties.get("value").unwrap().observe_with_handle(move |_| increment(), 1);
+    let new_received = Arc::new(AtomicUsize::new(0));
+    let increment = counter(&new_received);
+    new_entity.properties.get("value").unwrap().observe_with_handle(move |_| increment(), 2);
+    let all_received = Arc::new(AtomicUsize::new(0));
+    let increment = counter(&all_received);
+    let handle_id = new_entity.observe_all(move |_, _| increment());
+
+    migrate_entity_instance(&old_entity, &new_entity, &ReplacementPolicy::default());
+    new_entity.set("value", json!(1));
+    assert_eq!(1, old_received.load(Ordering::SeqCst));
+    assert_eq!(1, new_received.load(Ordering::SeqCst));
+    assert_eq!(1, all_received.load(Ordering::SeqCst));
+
+    // The observers of the new entity instance can still be removed
+    new_entity.properties.get("value").unwrap().remove_observer(2);
+    new_entity.remove_all_observer(handle_id);
+    new_entity.set("value", json!(2));
+    assert_eq!(2, old_received.load(Ordering::SeqCst));
+    assert_eq!(1, new_received.load(Ordering::SeqCst));
+    assert_eq!(1, all_received.load(Ordering::SeqCst));
+}
+
+#[test]
+fn reactive_flow_replace_entity_test() {
+    let property_name = r_string();
+    let wrapper = Arc::new(create_random_entity_instance_with_type("generic_flow", "test"));
+    let reactive_flow = ReactiveFlow::new(wrapper.clone());
+    let old_entity = Arc::new(create_random_entity_instance(r_string()));
+    let new_entity = Arc::new(create_random_entity_instance(r_string()));
+    reactive_flow.add_entity(old_entity.clone());
+    reactive_flow.add_relation(Arc::new(create_random_relation_instance_with_properties(
+        wrapper.clone(),
+        old_entity.clone(),
+        property_name.clone(),
+    )));
+    reactive_flow.add_relation(Arc::new(create_random_relation_instance_with_properties(
+        old_entity.clone(),
+        old_entity.clone(),
+        property_name.clone(),
+    )));
+
+    let rewired = reactive_flow.replace_entity(old_entity.clone(), new_entity.clone(), &ReplacementPolicy::default());
+    assert_eq!(2, rewired.len());
+    assert!(!reactive_flow.has_entity_by_id(old_entity.id));
+    assert!(reactive_flow.has_entity_by_id(new_entity.id));
+    assert_eq!(2, reactive_flow.relation_instances.read().unwrap().len());
+    assert!(reactive_flow
+        .relation_instances
+        .read()
+        .unwrap()
+        .keys()
+        .all(|edge_key| edge_key.outbound_id != old_entity.id && edge_key.inbound_id != old_entity.id));
+    assert!(rewired.iter().any(|r| r.outbound.id == new_entity.id && r.inbound.id == new_entity.id));
+}
@@ -1,7 +1,9 @@
+use std::collections::HashSet;
+
 use serde_json::json;
 
 use crate::tests::utils::r_string;
-use crate::{DataType, EntityType, Extension, PropertyType};
+use crate::{ComponentName, DataType, EntityType, EntityTypeName, EntityTypeResolveError, Extension, PropertyGroup, PropertyType};
 
 #[test]
 fn create_entity_type_test() {
@@ -12,7 +14,7 @@ fn create_entity_type_test() {
 
     let component_name = r_string();
     let mut component_names = Vec::new();
-    component_names.push(component_name.clone());
+    component_names.push(ComponentName::new(component_name.clone()));
 
     let mut property_types = Vec::new();
     let property_name = "property_name";
@@ -25,12 +27,13 @@ fn create_entity_type_test() {
     let extension = Extension {
         name: extension_name.to_string(),
         extension: extension_value.clone(),
+        version: None,
     };
     extensions.push(extension);
     let extension = Extension::new("other_extension", extension_value.clone());
     extensions.push(extension);
 
-    let entity_type = EntityType::new(entity_type_name, group, description, component_names, property_types, extensions);
+    let entity_type = EntityType::new(EntityTypeName::new(entity_type_name), group, description, component_names, property_types, extensions);
 
     assert_eq!(entity_type_name, entity_type.name);
 
@@ -40,7 +43,7 @@ fn create_entity_type_test() {
 
     assert_eq!(component_name, *entity_type.components.first().unwrap());
 
-    assert!(entity_type.is_a(component_name.clone()));
+    assert!(entity_type.is_a(ComponentName::new(component_name.clone())));
 
     assert_eq!(property_name, entity_type.properties.first().unwrap().name);
 
@@ -52,3 +55,207 @@ fn create_entity_type_test() {
     assert!(entity_type.has_own_extension(extension_name));
     assert!(!entity_type.has_own_extension(r_string()));
 }
+
+#[test]
+fn entity_type_eq_test() {
+    let name = r_string();
+    let entity_type_1 = EntityType::new(EntityTypeName::new(name.clone()), r_string(), r_string(), Vec::new(), Vec::new(), Vec::new());
+    let entity_type_2 = EntityType::new(
+        EntityTypeName::new(name.clone()),
+        r_string(),
+        r_string(),
+        vec![ComponentName::new(r_string())],
+        Vec::new(),
+        Vec::new(),
+    );
+    let entity_type_3 = EntityType::new(EntityTypeName::new(r_string()), r_string(), r_string(), Vec::new(), Vec::new(), Vec::new());
+    assert_eq!(entity_type_1, entity_type_2);
+    assert_ne!(entity_type_1, entity_type_3);
+    let entity_types: HashSet<EntityType> = vec![entity_type_1, entity_type_2, entity_type_3].into_iter().collect();
+    assert_eq!(2, entity_types.len());
+}
+
+#[test]
+fn entity_type_display_test() {
+    let entity_type = EntityType::new(EntityTypeName::new("player"), "", "", Vec::new(), Vec::new(), Vec::new());
+    assert_eq!("player", entity_type.to_string());
+}
+
+#[test]
+fn entity_type_resolve_test() {
+    let renderable = EntityType::new(
+        EntityTypeName::new("renderable"),
+        "",
+        "",
+        vec![ComponentName::new("renderable")],
+        vec![PropertyType::new("visible", DataType::Bool), PropertyType::new("z_index", DataType::Number)],
+        Vec::new(),
+    );
+    let sprite = EntityType::new(
+        EntityTypeName::new("sprite"),
+        "",
+        "",
+        Vec::new(),
+        vec![PropertyType::new("texture", DataType::String)],
+        Vec::new(),
+    )
+    .with_extends("renderable");
+    let animated = EntityType::new(
+        EntityTypeName::new("animated"),
+        "",
+        "",
+        Vec::new(),
+        vec![PropertyType::new("frame", DataType::Number)],
+        Vec::new(),
+    )
+    .with_extends("renderable");
+    let animated_sprite = EntityType::new(
+        EntityTypeName::new("animated_sprite"),
+        "",
+        "",
+        Vec::new(),
+        vec![PropertyType::new("z_index", DataType::String)],
+        Vec::new(),
+    )
+    .with_extends("sprite")
+    .with_extends("animated");
+    let entity_types = vec![renderable.clone(), sprite.clone(), animated.clone(), animated_sprite.clone()];
+
+    let resolved = sprite.resolve(&entity_types).unwrap();
+    assert!(resolved.extends.is_empty());
+    assert!(resolved.is_a(ComponentName::new("renderable")));
+    assert!(resolved.has_own_property("visible"));
+    assert!(resolved.has_own_property("texture"));
+
+    let resolved = animated_sprite.resolve(&entity_types).unwrap();
+    assert_eq!(vec!["renderable".to_string()], resolved.components);
+    assert_eq!(4, resolved.properties.len());
+    assert!(resolved.has_own_property("frame"));
+    assert_eq!(DataType::String, resolved.properties.iter().find(|p| p.name == "z_index").unwrap().data_type);
+
+    assert_eq!(
+        Err(EntityTypeResolveError::MissingBaseType("renderable".to_string())),
+        sprite.resolve(std::slice::from_ref(&sprite)).map(|_| ())
+    );
+    let cyclic = renderable.clone().with_extends("animated_sprite");
+    assert_eq!(
+        Err(EntityTypeResolveError::Cycle("animated_sprite".to_string())),
+        animated_sprite.resolve(&[cyclic, sprite, animated, animated_sprite.clone()]).map(|_| ())
+    );
+}
+
+#[test]
+fn entity_type_resolve_conflict_test() {
+    let a = EntityType::new(EntityTypeName::new("a"), "", "", Vec::new(), vec![PropertyType::new("value", DataType::Bool)], Vec::new());
+    let b = EntityType::new(EntityTypeName::new("b"), "", "", Vec::new(), vec![PropertyType::new("value", DataType::Number)], Vec::new());
+    let c = EntityType::new(EntityTypeName::new("c"), "", "", Vec::new(), Vec::new(), Vec::new())
+        .with_extends("a")
+        .with_extends("b");
+    assert_eq!(
+        Err(EntityTypeResolveError::ConflictingProperty("value".to_string())),
+        c.resolve(&[a, b, c.clone()]).map(|_| ())
+    );
+
+    let extension = |value: &str| Extension {
+        name: "color".to_string(),
+        extension: json!(value),
+        version: None,
+    };
+    let a = EntityType::new(EntityTypeName::new("a"), "", "", Vec::new(), Vec::new(), vec![extension("red")]);
+    let b = EntityType::new(EntityTypeName::new("b"), "", "", Vec::new(), Vec::new(), vec![extension("blue")]);
+    assert_eq!(
+        Err(EntityTypeResolveError::ConflictingExtension("color".to_string())),
+        c.resolve(&[a.clone(), b.clone(), c.clone()]).map(|_| ())
+    );
+    // Own extensions don't conflict with inherited ones
+    let b = EntityType::new(EntityTypeName::new("b"), "", "", Vec::new(), Vec::new(), vec![extension("red")]);
+    let d = EntityType::new(EntityTypeName::new("d"), "", "", Vec::new(), Vec::new(), vec![extension("green")])
+        .with_extends("a")
+        .with_extends("b");
+    let resolved = d.resolve(&[a, b]).unwrap();
+    assert_eq!(1, resolved.extensions.len());
+    assert_eq!(json!("green"), resolved.extensions[0].extension);
+}
+
+#[test]
+fn entity_type_abstract_test() {
+    let entity_type = EntityType::new(EntityTypeName::new("sprite"), "", "", Vec::new(), Vec::new(), Vec::new());
+    assert!(entity_type.is_instantiable());
+    let entity_type: EntityType = serde_json::from_value(json!({ "name": "renderable", "abstract": true })).unwrap();
+    assert!(entity_type.is_abstract);
+    assert!(!entity_type.is_instantiable());
+    assert_eq!(json!(true), serde_json::to_value(&entity_type).unwrap()["abstract"]);
+}
+
+#[test]
+fn entity_type_deserialize_identifier_test() {
+    let entity_type: EntityType = serde_json::from_value(json!({ "name": "player" })).unwrap();
+    assert_eq!("player", entity_type.t.as_str());
+    let json = serde_json::to_value(&entity_type).unwrap();
+    assert!(json.get("t").is_none());
+    let error = serde_json::from_value::<EntityType>(json!({ "name": "" })).unwrap_err();
+    assert_eq!(" is not a valid identifier", error.to_string());
+}
+
+#[test]
+fn entity_type_property_groups_test() {
+    let mut base = EntityType::new(
+        EntityTypeName::new("renderable"),
+        "",
+        "",
+        Vec::new(),
+        vec![PropertyType::new("visible", DataType::Bool).with_group("Rendering")],
+        Vec::new(),
+    );
+    base.property_groups = vec![PropertyGroup::new("Rendering", 1)];
+    let mut sprite = EntityType::new(
+        EntityTypeName::new("sprite"),
+        "",
+        "",
+        Vec::new(),
+        vec![PropertyType::new("texture", DataType::String).with_group("Sprite")],
+        Vec::new(),
+    )
+    .with_extends("renderable");
+    sprite.property_groups = vec![PropertyGroup::new("Sprite", 0)];
+    assert!(sprite.get_property_group_of("visible").is_none());
+
+    let resolved = sprite.resolve(&[base]).unwrap();
+    let names: Vec<&str> = resolved.get_property_groups().iter().map(|g| g.name.as_str()).collect();
+    assert_eq!(vec!["Sprite", "Rendering"], names);
+    assert_eq!("Rendering", resolved.get_property_group_of("visible").unwrap().name);
+}
+
+#[test]
+fn entity_type_serde_names_test() {
+    let entity_type: EntityType = serde_json::from_value(json!({ "name": "player" })).unwrap();
+    let json = serde_json::to_value(&entity_type).unwrap();
+    assert_eq!(json!("player"), json["name"]);
+    let entity_type: EntityType = serde_json::from_value(json).unwrap();
+    assert_eq!("player", entity_type.name);
+    for legacy_name in ["type_name", "type"] {
+        let entity_type: EntityType = serde_json::from_value(json!({ legacy_name: "player" })).unwrap();
+        assert_eq!("player", entity_type.t.as_str());
+    }
+}
+
+#[test]
+fn entity_type_sockets_test() {
+    let entity_type = EntityType::new(
+        EntityTypeName::new("add"),
+        "",
+        "",
+        Vec::new(),
+        vec![
+            PropertyType::input("lhs", DataType::Number),
+            PropertyType::output("result", DataType::Number),
+            PropertyType::new("label", DataType::String),
+            PropertyType::input("rhs", DataType::Number),
+        ],
+        Vec::new(),
+    );
+    let inputs: Vec<&str> = entity_type.inputs().map(|p| p.name.as_str()).collect();
+    assert_eq!(vec!["lhs", "rhs"], inputs);
+    let outputs: Vec<&str> = entity_type.outputs().map(|p| p.name.as_str()).collect();
+    assert_eq!(vec!["result"], outputs);
+}
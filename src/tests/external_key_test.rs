@@ -0,0 +1,66 @@
+use uuid::Uuid;
+
+use crate::tests::utils::create_random_entity_instance::create_entity_instance_with_type;
+use crate::{Component, ComponentName, ExternalKey, ExternalKeyError, ExternalKeyMapper, KeyStrategy, RelationInstanceKey};
+
+#[test]
+fn external_key_instance_test() {
+    let entity_instance = create_entity_instance_with_type("player", "x");
+    let id = entity_instance.id;
+
+    let mapper = ExternalKeyMapper::default();
+    assert_eq!(Ok(id.to_string()), mapper.entity_key(&entity_instance));
+    assert_eq!(Ok(id), mapper.parse_instance_key(&id.to_string()));
+
+    let mapper = ExternalKeyMapper::new(KeyStrategy::Simple);
+    let key = mapper.entity_key(&entity_instance).unwrap();
+    assert_eq!(32, key.len());
+    assert_eq!(Ok(id), mapper.parse_instance_key(&key));
+
+    let mapper = ExternalKeyMapper::new(KeyStrategy::TypePrefixed).with_separator(":");
+    assert_eq!(Ok(format!("player:{}", id)), mapper.entity_key(&entity_instance));
+    assert_eq!(Ok(id), mapper.parse_instance_key(&format!("player:{}", id)));
+    assert_eq!(Err(ExternalKeyError::InvalidKey("player".to_string())), mapper.parse_instance_key("player"));
+    assert_eq!(Err(ExternalKeyError::SeparatorInName("a:b".to_string())), mapper.instance_key("a:b", id));
+    let error: Box<dyn std::error::Error> = mapper.parse_instance_key("player").unwrap_err().into();
+    assert_eq!("player is not a valid key", error.to_string());
+}
+
+#[test]
+fn external_key_relation_test() {
+    let outbound_id = Uuid::new_v4();
+    let inbound_id = Uuid::new_v4();
+    let relation_instance_key = RelationInstanceKey::new(outbound_id, "looks_at", inbound_id);
+
+    let mapper = ExternalKeyMapper::default();
+    assert_eq!(
+        ExternalKey::Composite(vec![outbound_id.to_string(), "looks_at".to_string(), inbound_id.to_string()]),
+        mapper.relation_key(&relation_instance_key)
+    );
+    let key = mapper.relation_string_key(&relation_instance_key).unwrap();
+    assert_eq!(format!("{}/looks_at/{}", outbound_id, inbound_id), key);
+    assert_eq!(Ok(relation_instance_key), mapper.parse_relation_string_key(&key));
+    assert!(mapper.parse_relation_string_key("a/b").is_err());
+    assert_eq!(
+        Err(ExternalKeyError::SeparatorInName("a/b".to_string())),
+        mapper.relation_string_key(&RelationInstanceKey::new(outbound_id, "a/b", inbound_id))
+    );
+}
+
+#[test]
+fn external_key_type_and_collision_test() {
+    let mapper = ExternalKeyMapper::default();
+    assert_eq!(
+        Ok("component/labeled".to_string()),
+        mapper.component_key(&Component::new(ComponentName::new("labeled"), Vec::new()))
+    );
+
+    let id = Uuid::new_v4();
+    assert!(mapper
+        .check_collisions(vec![("a".to_string(), id), ("b".to_string(), Uuid::new_v4()), ("a".to_string(), id)])
+        .is_ok());
+    assert_eq!(
+        Err(ExternalKeyError::Collision("a".to_string())),
+        mapper.check_collisions(vec![("a".to_string(), id), ("a".to_string(), Uuid::new_v4())])
+    );
+}
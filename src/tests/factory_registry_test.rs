@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::tests::utils::create_random_entity_instance::create_random_entity_instance_with_type;
+use crate::tests::utils::r_string;
+use crate::{
+    get_factory_registry, BehaviourName, ComponentName, DataType, EntityType, EntityTypeName, FactoryRegistry, PropertyInstanceGetter, PropertyInstanceSetter,
+    PropertyType, ReactiveEntityInstance, RelationTypeName,
+};
+use crate::{ReactiveRelationInstance, RelationType};
+
+#[test]
+fn factory_registry_entity_hook_test() {
+    let type_name = r_string();
+    let entity_type = EntityType::new(
+        EntityTypeName::new(type_name.as_str()),
+        "",
+        "",
+        Vec::new(),
+        vec![PropertyType::new("width", DataType::Number)],
+        Vec::new(),
+    );
+    get_factory_registry().register_entity_hook(type_name.clone(), |entity_instance| {
+        entity_instance.add_component(ComponentName::new("labeled"));
+        entity_instance.set("width", json!(100));
+    });
+    get_factory_registry().register_entity_hook(type_name.clone(), |entity_instance| entity_instance.add_behaviour(BehaviourName::new("resizable")));
+    assert!(get_factory_registry().has_entity_hooks(&type_name));
+
+    let entity_instance = ReactiveEntityInstance::new_from_type(&entity_type, &[]);
+    assert!(entity_instance.is_a(ComponentName::new("labeled")));
+    assert!(entity_instance.behaves_as(BehaviourName::new("resizable")));
+    assert_eq!(json!(100), entity_instance.get("width").unwrap());
+
+    get_factory_registry().unregister_entity_hooks(&type_name);
+    assert!(!get_factory_registry().has_entity_hooks(&type_name));
+    let entity_instance = ReactiveEntityInstance::new_from_type(&entity_type, &[]);
+    assert!(!entity_instance.is_a(ComponentName::new("labeled")));
+    assert_eq!(json!(0), entity_instance.get("width").unwrap());
+}
+
+#[test]
+fn factory_registry_relation_hook_test() {
+    let type_name = r_string();
+    let relation_type = RelationType::new("*", RelationTypeName::new(type_name.as_str()), "*", "", "", Vec::new(), Vec::new(), Vec::new());
+    get_factory_registry().register_relation_hook(type_name.clone(), |relation_instance| relation_instance.add_behaviour(BehaviourName::new("connector")));
+    let outbound = Arc::new(create_random_entity_instance_with_type(r_string(), r_string()));
+    let inbound = Arc::new(create_random_entity_instance_with_type(r_string(), r_string()));
+    let relation_instance = ReactiveRelationInstance::new_from_type(outbound, &relation_type, inbound, &[]).unwrap();
+    assert!(relation_instance.behaves_as(BehaviourName::new("connector")));
+    get_factory_registry().unregister_relation_hooks(&type_name);
+}
+
+#[test]
+fn factory_registry_wildcard_test() {
+    let registry = FactoryRegistry::new();
+    let type_name = r_string();
+    registry.register_entity_hook(type_name.clone(), |entity_instance| entity_instance.add_component(ComponentName::new("second")));
+    registry.register_entity_hook("*", |entity_instance| {
+        assert!(!entity_instance.is_a(ComponentName::new("second")));
+        entity_instance.add_component(ComponentName::new("first"));
+    });
+    let entity_instance = create_random_entity_instance_with_type(type_name, r_string());
+    registry.apply_entity_hooks(&entity_instance);
+    assert!(entity_instance.is_a(ComponentName::new("first")));
+    assert!(entity_instance.is_a(ComponentName::new("second")));
+
+    let other = create_random_entity_instance_with_type(r_string(), r_string());
+    registry.apply_entity_hooks(&other);
+    assert!(other.is_a(ComponentName::new("first")));
+    assert!(!other.is_a(ComponentName::new("second")));
+
+    registry.clear();
+    let cleared = create_random_entity_instance_with_type(r_string(), r_string());
+    registry.apply_entity_hooks(&cleared);
+    assert!(!cleared.is_a(ComponentName::new("first")));
+}
@@ -0,0 +1,69 @@
+use rand::Rng;
+use serde_json::json;
+
+use crate::tests::utils::create_random_entity_instance;
+use crate::{
+    f64_to_value, f64_to_value_with_policy, get_non_finite_float_policy, value_to_f64, with_non_finite_float_policy, EntityInstance, NonFiniteFloatError,
+    NonFiniteFloatPolicy, PropertyInstanceGetter, PropertyInstanceSetter, ReactiveEntityInstance,
+};
+
+#[test]
+fn f64_round_trip_test() {
+    let mut rng = rand::thread_rng();
+    let mut values: Vec<f64> = (0..1000).map(|_| f64::from_bits(rng.gen::<u64>())).filter(|value| value.is_finite()).collect();
+    values.extend([0.1, 1.0 / 3.0, f64::MIN_POSITIVE, f64::MAX, f64::MIN, f64::EPSILON, -0.0, 5e-324]);
+    for value in values {
+        let json = f64_to_value_with_policy(value, NonFiniteFloatPolicy::Reject).unwrap();
+        let deserialized: serde_json::Value = serde_json::from_str(&serde_json::to_string(&json).unwrap()).unwrap();
+        assert_eq!(value.to_bits(), value_to_f64(&deserialized).unwrap().to_bits(), "{} drifted", value);
+    }
+}
+
+#[test]
+fn f64_propagation_round_trip_test() {
+    let value: f64 = 0.1 + 0.2;
+    let entity_instance = create_random_entity_instance("x");
+    entity_instance.set("x", json!(value));
+    let entity_instance: EntityInstance =
+        serde_json::from_str(&serde_json::to_string(&EntityInstance::from(std::sync::Arc::new(entity_instance))).unwrap()).unwrap();
+    let reactive_entity_instance = ReactiveEntityInstance::from(entity_instance);
+    assert_eq!(value.to_bits(), reactive_entity_instance.get_f64("x").unwrap().to_bits());
+}
+
+#[test]
+fn non_finite_float_policy_test() {
+    assert_eq!(Err(NonFiniteFloatError(f64::INFINITY)), f64_to_value_with_policy(f64::INFINITY, NonFiniteFloatPolicy::Reject));
+    assert_eq!(Ok(json!(null)), f64_to_value_with_policy(f64::NAN, NonFiniteFloatPolicy::Null));
+    assert_eq!(Ok(json!("Infinity")), f64_to_value_with_policy(f64::INFINITY, NonFiniteFloatPolicy::String));
+    assert_eq!(Ok(json!("-Infinity")), f64_to_value_with_policy(f64::NEG_INFINITY, NonFiniteFloatPolicy::String));
+    assert!(value_to_f64(&f64_to_value_with_policy(f64::NAN, NonFiniteFloatPolicy::String).unwrap())
+        .unwrap()
+        .is_nan());
+    assert_eq!(Some(f64::NEG_INFINITY), value_to_f64(&json!("-Infinity")));
+    assert_eq!(None, value_to_f64(&json!("infinite")));
+    assert_eq!(None, value_to_f64(&json!(null)));
+}
+
+// The global policy isn't changed, because the tests run in parallel
+#[test]
+fn non_finite_float_thread_policy_test() {
+    let entity_instance = create_random_entity_instance("x");
+    with_non_finite_float_policy(NonFiniteFloatPolicy::String, || {
+        assert!(entity_instance.set_f64("x", f64::NAN).is_ok());
+        assert!(entity_instance.get_f64("x").unwrap().is_nan());
+        with_non_finite_float_policy(NonFiniteFloatPolicy::Reject, || {
+            assert!(entity_instance.set_f64("x", f64::INFINITY).is_err());
+            assert!(entity_instance.get_f64("x").unwrap().is_nan());
+            assert!(entity_instance.set_f64("x", 1.5).is_ok());
+            assert_eq!(Some(1.5), entity_instance.get_f64("x"));
+        });
+        // The previous policy of the thread is restored
+        assert_eq!(NonFiniteFloatPolicy::String, get_non_finite_float_policy());
+    });
+    // Other threads use the global policy
+    with_non_finite_float_policy(NonFiniteFloatPolicy::Reject, || {
+        let policy = std::thread::spawn(get_non_finite_float_policy).join().unwrap();
+        assert_eq!(NonFiniteFloatPolicy::default(), policy);
+    });
+    assert_eq!(Ok(json!(null)), f64_to_value(f64::NAN));
+}
@@ -0,0 +1,97 @@
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::tests::utils::{create_random_entity_instance, create_random_entity_instance_with_type, create_random_relation_instance_with_properties};
+use crate::{
+    ComponentName, EntityInstance, Flow, PropertyInstanceGetter, PropertyInstanceSetter, PropertyMap, ReactiveFlow, ReactiveInstanceLabels,
+    ReactivePropertyContainer, RelationInstance, RelationInstanceKey,
+};
+
+#[test]
+fn reactive_flow_changes_since_test() {
+    let wrapper_entity_instance = Arc::new(create_random_entity_instance_with_type("generic_flow", "test"));
+    let reactive_flow = ReactiveFlow::new(wrapper_entity_instance.clone());
+    let a = Arc::new(create_random_entity_instance("x"));
+    let b = Arc::new(create_random_entity_instance("x"));
+    a.add_property("y", json!(0));
+    reactive_flow.add_entity(a.clone());
+    reactive_flow.add_entity(b.clone());
+    let a_b = Arc::new(create_random_relation_instance_with_properties(a.clone(), b.clone(), "weight".to_string()));
+    reactive_flow.add_relation(a_b.clone());
+
+    let checkpoint = reactive_flow.checkpoint();
+    let changes = reactive_flow.changes_since(&checkpoint).unwrap();
+    assert_eq!(1, changes.flow.entity_instances.len());
+    assert!(changes.flow.entity_instances[0].properties.is_empty());
+    assert!(changes.flow.relation_instances.is_empty());
+
+    a.set("x", json!(42));
+    a_b.set("weight", json!(0.5));
+    let c = Arc::new(create_random_entity_instance("z"));
+    reactive_flow.add_entity(c.clone());
+    let changes = reactive_flow.changes_since(&checkpoint).unwrap();
+    assert_eq!(reactive_flow.id, changes.flow.id);
+    assert_eq!(reactive_flow.id, changes.flow.entity_instances[0].id);
+    assert_eq!(3, changes.flow.entity_instances.len());
+    let a_changes = changes.flow.entity_instances.iter().find(|entity_instance| entity_instance.id == a.id).unwrap();
+    assert_eq!(1, a_changes.properties.len());
+    assert_eq!(json!(42), a_changes.get("x").unwrap());
+    let c_changes = changes.flow.entity_instances.iter().find(|entity_instance| entity_instance.id == c.id).unwrap();
+    assert_eq!(c.get("z"), c_changes.get("z"));
+    assert!(changes.flow.entity_instances.iter().all(|entity_instance| entity_instance.id != b.id));
+    assert_eq!(1, changes.flow.relation_instances.len());
+    assert_eq!(json!(0.5), changes.flow.relation_instances[0].get("weight").unwrap());
+
+    // The changes are merged into the flow document
+    let mut flow = Flow::try_from(Arc::new(ReactiveFlow::new(wrapper_entity_instance))).unwrap();
+    flow.entity_instances.push(EntityInstance::from(a.clone()));
+    flow.relation_instances
+        .push(RelationInstance::new(a.id, a_b.type_name.clone(), b.id, PropertyMap::new()));
+    flow.entity_instances[1].properties.insert("x".to_string(), json!(1));
+    flow.apply_changes(changes);
+    assert_eq!(3, flow.entity_instances.len());
+    assert_eq!(json!(42), flow.entity_instances[1].get("x").unwrap());
+    assert_eq!(json!(0), flow.entity_instances[1].get("y").unwrap());
+    assert_eq!(json!(0.5), flow.relation_instances[0].get("weight").unwrap());
+}
+
+#[test]
+fn reactive_flow_changes_since_removed_and_tagged_test() {
+    let wrapper_entity_instance = Arc::new(create_random_entity_instance_with_type("generic_flow", "test"));
+    let reactive_flow = Arc::new(ReactiveFlow::new(wrapper_entity_instance.clone()));
+    let a = Arc::new(create_random_entity_instance("x"));
+    let b = Arc::new(create_random_entity_instance("x"));
+    let c = Arc::new(create_random_entity_instance("x"));
+    for entity_instance in [&a, &b, &c] {
+        reactive_flow.add_entity(entity_instance.clone());
+    }
+    let a_b = Arc::new(create_random_relation_instance_with_properties(a.clone(), b.clone(), "weight".to_string()));
+    let a_c = Arc::new(create_random_relation_instance_with_properties(a.clone(), c.clone(), "weight".to_string()));
+    reactive_flow.add_relation(a_b.clone());
+    reactive_flow.add_relation(a_c.clone());
+    let mut flow = Flow::try_from(reactive_flow.clone()).unwrap();
+
+    let checkpoint = reactive_flow.checkpoint();
+    a.add_label("selected");
+    a_b.add_component(ComponentName::new("weighted"));
+    reactive_flow.remove_entity_with_relations(c.id);
+    let changes = reactive_flow.changes_since(&checkpoint).unwrap();
+    // Only the labels and the components have been changed
+    let a_changes = changes.flow.entity_instances.iter().find(|entity_instance| entity_instance.id == a.id).unwrap();
+    assert!(a_changes.properties.is_empty());
+    assert_eq!(vec!["selected".to_string()], a_changes.labels);
+    assert!(changes.flow.entity_instances.iter().all(|entity_instance| entity_instance.id != b.id));
+    assert_eq!(1, changes.flow.relation_instances.len());
+    assert_eq!(vec![ComponentName::new("weighted")], changes.flow.relation_instances[0].components);
+    assert_eq!(vec![c.id], changes.removed_entity_instances);
+    assert_eq!(vec![RelationInstanceKey::from(a_c.as_ref())], changes.removed_relation_instances);
+
+    flow.apply_changes(changes);
+    assert!(flow.entity_instances.iter().all(|entity_instance| entity_instance.id != c.id));
+    assert_eq!(1, flow.relation_instances.len());
+    assert_eq!(vec![ComponentName::new("weighted")], flow.relation_instances[0].components);
+    let a_instance = flow.entity_instances.iter().find(|entity_instance| entity_instance.id == a.id).unwrap();
+    assert_eq!(vec!["selected".to_string()], a_instance.labels);
+}
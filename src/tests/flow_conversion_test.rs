@@ -0,0 +1,102 @@
+#[cfg(feature = "graph-db")]
+use indradb::{BulkInsertItem, Edge, EdgeKey, EdgeProperties, Identifier, NamedProperty, Vertex, VertexProperties};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    EntityInstance, EntityTypeName, Flow, FlowConversionError, InstanceConversionError, PropertyInstanceGetter, PropertyMap, RelationInstance, RelationTypeName,
+};
+
+fn create_flow() -> Flow {
+    let wrapper = EntityInstance::new_without_properties(EntityTypeName::new("generic_flow"), Uuid::new_v4());
+    let mut properties = PropertyMap::new();
+    properties.insert("rhs".to_string(), json!(2));
+    properties.insert("lhs".to_string(), json!(1));
+    let add = EntityInstance::new(EntityTypeName::new("add"), Uuid::new_v4(), properties);
+    let mut flow = Flow::from(wrapper.clone());
+    flow.entity_instances.push(add.clone());
+    let mut properties = PropertyMap::new();
+    properties.insert("weight".to_string(), json!(0.5));
+    flow.relation_instances
+        .push(RelationInstance::new(wrapper.id, RelationTypeName::new("default_connector"), add.id, properties));
+    flow
+}
+
+#[test]
+fn flow_to_bulk_insert_items_test() {
+    let flow = create_flow();
+    let add = &flow.entity_instances[1];
+    let key: EdgeKey = flow.relation_instances[0].get_key().unwrap().into();
+    let items = flow.to_bulk_insert_items().unwrap();
+    assert_eq!(6, items.len());
+    assert!(matches!(&items[0], BulkInsertItem::Vertex(vertex) if vertex.id == flow.id && vertex.t.as_str() == "generic_flow"));
+    assert!(matches!(&items[1], BulkInsertItem::Vertex(vertex) if vertex.id == add.id && vertex.t.as_str() == "add"));
+    assert!(matches!(&items[2], BulkInsertItem::Edge(edge_key) if *edge_key == key));
+    assert!(matches!(&items[3], BulkInsertItem::VertexProperty(id, name, value) if *id == add.id && name.as_str() == "lhs" && *value == json!(1)));
+    assert!(matches!(&items[4], BulkInsertItem::VertexProperty(id, name, value) if *id == add.id && name.as_str() == "rhs" && *value == json!(2)));
+    assert!(matches!(&items[5], BulkInsertItem::EdgeProperty(edge_key, name, value) if *edge_key == key && name.as_str() == "weight" && *value == json!(0.5)));
+}
+
+#[test]
+fn flow_to_bulk_insert_items_invalid_test() {
+    let mut flow = create_flow();
+    flow.relation_instances[0].type_name = RelationTypeName::default();
+    assert_eq!(InstanceConversionError::InvalidIdentifier(String::new()), flow.to_bulk_insert_items().err().unwrap());
+
+    let mut flow = create_flow();
+    flow.entity_instances[1].properties.insert(String::new(), json!(3));
+    assert_eq!(InstanceConversionError::InvalidPropertyName(String::new()), flow.to_bulk_insert_items().err().unwrap());
+}
+
+fn vertex_properties(id: Uuid, type_name: &str) -> VertexProperties {
+    let props = vec![NamedProperty::new(Identifier::new("value").unwrap(), json!(1))];
+    VertexProperties::new(Vertex::with_id(id, Identifier::new(type_name).unwrap()), props)
+}
+
+fn edge_properties(outbound_id: Uuid, inbound_id: Uuid) -> EdgeProperties {
+    let key = EdgeKey::new(outbound_id, Identifier::new("default_connector").unwrap(), inbound_id);
+    EdgeProperties::new(Edge::new_with_current_datetime(key), Vec::new())
+}
+
+#[test]
+fn flow_try_from_graph_test() {
+    let wrapper_id = Uuid::new_v4();
+    let add_id = Uuid::new_v4();
+    let outside_id = Uuid::new_v4();
+    let vertices = vec![
+        vertex_properties(add_id, "add"),
+        vertex_properties(wrapper_id, "generic_flow"),
+        vertex_properties(add_id, "add"),
+    ];
+    let edges = vec![
+        edge_properties(wrapper_id, add_id),
+        edge_properties(add_id, outside_id),
+        edge_properties(wrapper_id, add_id),
+    ];
+    let flow = Flow::try_from_graph(wrapper_id, &vertices, &edges).unwrap();
+    assert_eq!(wrapper_id, flow.id);
+    assert_eq!("generic_flow", flow.type_name);
+    assert_eq!(2, flow.entity_instances.len());
+    assert_eq!(wrapper_id, flow.entity_instances[0].id);
+    assert_eq!(add_id, flow.entity_instances[1].id);
+    assert_eq!(json!(1), flow.entity_instances[1].get("value").unwrap());
+    assert_eq!(1, flow.relation_instances.len());
+    assert_eq!(add_id, flow.relation_instances[0].inbound_id);
+}
+
+#[test]
+fn flow_try_from_graph_invalid_test() {
+    let wrapper_id = Uuid::new_v4();
+    let vertices = vec![vertex_properties(Uuid::new_v4(), "add")];
+    assert_eq!(
+        FlowConversionError::MissingWrapper(wrapper_id),
+        Flow::try_from_graph(wrapper_id, &vertices, &[]).err().unwrap()
+    );
+
+    let mut wrapper = vertex_properties(wrapper_id, "generic_flow");
+    wrapper.props.push(NamedProperty::new(Identifier::new("value").unwrap(), json!(2)));
+    assert_eq!(
+        FlowConversionError::InvalidInstance(InstanceConversionError::DuplicateProperty("value".to_string())),
+        Flow::try_from_graph(wrapper_id, &[wrapper], &[]).err().unwrap()
+    );
+}
@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::assert_flow_state;
+use crate::check_flow_state;
+use crate::tests::utils::create_random_entity_instance;
+use crate::tests::utils::create_random_entity_instance_with_type;
+use crate::FlowExpectation;
+use crate::FlowExpectationFailure;
+use crate::InstanceSelector;
+use crate::PropertyInstanceSetter;
+use crate::PropertyMatcher;
+use crate::ReactiveFlow;
+
+#[test]
+fn flow_expectation_test() {
+    let wrapper_entity_instance = Arc::new(create_random_entity_instance_with_type("generic_flow", "result"));
+    let reactive_flow = ReactiveFlow::new(wrapper_entity_instance.clone());
+    let counter = Arc::new(create_random_entity_instance_with_type("counter", "count"));
+    reactive_flow.add_entity(counter.clone());
+    wrapper_entity_instance.set("result", json!("done"));
+    counter.set("count", json!(5));
+
+    let expectations = vec![
+        FlowExpectation::new(InstanceSelector::Id(wrapper_entity_instance.id)).property("result", PropertyMatcher::Equals(json!("done"))),
+        FlowExpectation::new(InstanceSelector::Type("counter".to_string()))
+            .property("count", PropertyMatcher::Between(1.0, 10.0))
+            .property("count", PropertyMatcher::GreaterThan(4.0))
+            .property("count", PropertyMatcher::NotEquals(json!(0))),
+    ];
+    assert!(check_flow_state(&reactive_flow, &expectations).is_empty());
+    assert_flow_state(&reactive_flow, &expectations);
+
+    let expectations = vec![
+        FlowExpectation::new(InstanceSelector::Type("counter".to_string()))
+            .property("count", PropertyMatcher::LessThan(5.0))
+            .property("missing", PropertyMatcher::Exists),
+        FlowExpectation::new(InstanceSelector::Component("unknown".to_string())),
+    ];
+    let failures = check_flow_state(&reactive_flow, &expectations);
+    assert_eq!(3, failures.len());
+    assert_eq!(format!("counter({}).count: expected < 5 but was 5", counter.id), failures[0].to_string());
+    assert_eq!(
+        format!("counter({}).missing: expected exists but the property doesn't exist", counter.id),
+        failures[1].to_string()
+    );
+    assert!(matches!(failures[2], FlowExpectationFailure::NoInstance(_)));
+    assert_eq!("No entity instance with component unknown", failures[2].to_string());
+}
+
+#[test]
+#[should_panic(expected = "The flow state doesn't match 1 expectation(s)")]
+fn assert_flow_state_test() {
+    let entity_instance = Arc::new(create_random_entity_instance("x"));
+    entity_instance.set("x", json!(1));
+    let entity_instances = vec![entity_instance.clone()];
+    assert_flow_state(
+        &entity_instances,
+        &[FlowExpectation::new(InstanceSelector::Id(entity_instance.id)).property("x", PropertyMatcher::Equals(json!(2)))],
+    );
+}
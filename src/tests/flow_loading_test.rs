@@ -0,0 +1,83 @@
+use std::io::Cursor;
+use std::sync::Arc;
+
+use crate::tests::utils::create_random_entity_instance;
+use crate::tests::utils::create_random_entity_instance_with_type;
+use crate::Flow;
+use crate::FlowLoadingError;
+use crate::FlowLoadingProgress;
+use crate::ReactiveFlow;
+use crate::ReactiveFlowConstructionError;
+use crate::FLOW_LOADING_PROGRESS_INTERVAL;
+
+fn create_flow(entity_count: usize) -> Flow {
+    let wrapper_entity_instance = Arc::new(create_random_entity_instance_with_type("generic_flow", "test"));
+    let reactive_flow = ReactiveFlow::new(wrapper_entity_instance);
+    for _ in 0..entity_count {
+        reactive_flow.add_entity(Arc::new(create_random_entity_instance("x")));
+    }
+    Flow::try_from(reactive_flow).unwrap()
+}
+
+#[test]
+fn flow_from_reader_with_progress_test() {
+    let flow = create_flow(10);
+    let flow_json = serde_json::to_vec(&flow).unwrap();
+    let mut reports: Vec<FlowLoadingProgress> = Vec::new();
+    let loaded_flow = Flow::from_reader_with_progress(Cursor::new(flow_json.clone()), |progress| {
+        reports.push(progress.clone());
+        true
+    })
+    .unwrap();
+    assert_eq!(flow.id, loaded_flow.id);
+    let last = reports.last().unwrap();
+    assert_eq!(flow_json.len() as u64, last.bytes_read);
+    assert_eq!(11, last.entities_total);
+    assert_eq!(0, last.relations_total);
+
+    let result = Flow::from_reader_with_progress(Cursor::new(flow_json), |progress| progress.bytes_read == 0);
+    assert!(matches!(result, Err(FlowLoadingError::Cancelled)));
+
+    let result = Flow::from_reader_with_progress(Cursor::new(b"{ \"id\": 1 }".to_vec()), |_| true);
+    assert!(matches!(result, Err(FlowLoadingError::Json(_))));
+    let error: Box<dyn std::error::Error> = Box::new(result.unwrap_err());
+    assert!(error.source().is_some());
+}
+
+#[test]
+fn flow_from_reader_with_progress_interval_test() {
+    let flow = create_flow(2000);
+    let flow_json = serde_json::to_vec(&flow).unwrap();
+    assert!(flow_json.len() as u64 > 2 * FLOW_LOADING_PROGRESS_INTERVAL);
+    let mut reports: Vec<FlowLoadingProgress> = Vec::new();
+    Flow::from_reader_with_progress(Cursor::new(flow_json.clone()), |progress| {
+        reports.push(progress.clone());
+        true
+    })
+    .unwrap();
+    // The progress is reported once per interval, at the end of the input and after deserialization
+    let max_reports = flow_json.len() as u64 / FLOW_LOADING_PROGRESS_INTERVAL + 2;
+    assert!(reports.len() as u64 <= max_reports);
+    assert!(reports.len() >= 3);
+    assert!(reports.windows(2).all(|reports| reports[0].bytes_read <= reports[1].bytes_read));
+}
+
+#[test]
+fn reactive_flow_try_from_with_progress_test() {
+    let flow = create_flow(9);
+    let mut reports: Vec<FlowLoadingProgress> = Vec::new();
+    let reactive_flow = ReactiveFlow::try_from_with_progress(flow.clone(), 4, |progress| {
+        reports.push(progress.clone());
+        true
+    })
+    .unwrap();
+    assert_eq!(10, reactive_flow.entity_instances.read().unwrap().len());
+    // Before the chunks 0..4, 4..8, 8..10 and after the construction
+    let entities_done: Vec<usize> = reports.iter().map(|progress| progress.entities_done).collect();
+    assert_eq!(vec![0, 4, 8, 10], entities_done);
+    assert!(reports.last().unwrap().is_done());
+    assert!(!reports.first().unwrap().is_done());
+
+    let result = ReactiveFlow::try_from_with_progress(flow, 4, |progress| progress.entities_done < 4);
+    assert!(matches!(result, Err(ReactiveFlowConstructionError::Cancelled)));
+}
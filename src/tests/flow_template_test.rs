@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    DataType, EntityInstance, EntityTypeName, Flow, FlowLayer, FlowTemplateError, FlowVariable, PropertyInstanceGetter, PropertyMap, RelationInstance,
+    RelationInstanceKey, RelationTypeName,
+};
+
+fn template() -> Flow {
+    let mut properties = PropertyMap::new();
+    properties.insert("title".to_string(), json!("${window_title}"));
+    properties.insert("caption".to_string(), json!("${window_title} (${width} x ${height})"));
+    properties.insert("size".to_string(), json!({ "width": "${width:number}", "height": ["${height}"] }));
+    let wrapper = EntityInstance::new(EntityTypeName::new("window"), Uuid::new_v4(), properties);
+    let mut flow = Flow::from(wrapper.clone());
+    let value = EntityInstance::new_without_properties(EntityTypeName::new("value"), Uuid::new_v4());
+    let mut properties = PropertyMap::new();
+    properties.insert("weight".to_string(), json!("${weight:number}"));
+    flow.relation_instances
+        .push(RelationInstance::new(wrapper.id, RelationTypeName::new("connector"), value.id, properties));
+    let mut layer = FlowLayer::new("layer");
+    layer.entity_instances.push(value.id);
+    layer.relation_instances.push(RelationInstanceKey::new(wrapper.id, "connector", value.id));
+    flow.layers.push(layer);
+    flow.variables.push(FlowVariable::input("title", wrapper.id, "title"));
+    flow.entity_instances.push(value);
+    flow
+}
+
+fn variables() -> HashMap<String, serde_json::Value> {
+    let mut variables = HashMap::new();
+    variables.insert("window_title".to_string(), json!("Main"));
+    variables.insert("width".to_string(), json!("800"));
+    variables.insert("height".to_string(), json!(600));
+    variables.insert("weight".to_string(), json!(true));
+    variables
+}
+
+#[test]
+fn flow_instantiate_test() {
+    let template = template();
+    let flow = template.instantiate(&variables()).unwrap();
+    assert!(flow.validate().is_valid());
+    assert_ne!(template.id, flow.id);
+    let wrapper = flow.entity_instances.iter().find(|entity_instance| entity_instance.id == flow.id).unwrap();
+    assert_eq!(json!("Main"), wrapper.get("title").unwrap());
+    assert_eq!(json!("Main (800 x 600)"), wrapper.get("caption").unwrap());
+    assert_eq!(json!({ "width": 800, "height": [600] }), wrapper.get("size").unwrap());
+    let value = flow
+        .entity_instances
+        .iter()
+        .find(|entity_instance| entity_instance.type_name == "value")
+        .unwrap();
+    let relation_instance = &flow.relation_instances[0];
+    assert_eq!(flow.id, relation_instance.outbound_id);
+    assert_eq!(value.id, relation_instance.inbound_id);
+    assert_eq!(json!(1), relation_instance.get("weight").unwrap());
+    assert_eq!(vec![value.id], flow.layers[0].entity_instances);
+    assert_eq!(RelationInstanceKey::new(flow.id, "connector", value.id), flow.layers[0].relation_instances[0]);
+    assert_eq!(flow.id, flow.variables[0].entity_id);
+
+    // The template is unchanged and can be instantiated again
+    assert_eq!(json!("${window_title}"), template.entity_instances[0].get("title").unwrap());
+    assert_ne!(flow.id, template.instantiate(&variables()).unwrap().id);
+}
+
+#[test]
+fn flow_template_variables_test() {
+    let mut names = template().get_template_variables().unwrap();
+    names.sort();
+    assert_eq!(vec!["height", "weight", "width", "window_title"], names);
+}
+
+#[test]
+fn flow_template_error_test() {
+    let template = template();
+    let mut variables = variables();
+    variables.remove("height");
+    assert_eq!(FlowTemplateError::MissingVariable("height".to_string()), template.instantiate(&variables).unwrap_err());
+
+    let mut variables = self::variables();
+    variables.insert("width".to_string(), json!("wide"));
+    assert_eq!(
+        FlowTemplateError::InvalidConversion("width".to_string(), DataType::Number),
+        template.instantiate(&variables).unwrap_err()
+    );
+
+    let mut flow = Flow::from(EntityInstance::new_without_properties(EntityTypeName::new("window"), Uuid::new_v4()));
+    flow.entity_instances[0].properties.insert("title".to_string(), json!("${window_title"));
+    assert_eq!(
+        FlowTemplateError::UnterminatedPlaceholder("${window_title".to_string()),
+        flow.instantiate(&self::variables()).unwrap_err()
+    );
+
+    flow.entity_instances[0].properties.insert("title".to_string(), json!("${width:numbr}"));
+    assert_eq!(
+        FlowTemplateError::UnknownDataType("width".to_string(), "numbr".to_string()),
+        flow.instantiate(&self::variables()).unwrap_err()
+    );
+    assert!(flow.get_template_variables().is_err());
+}
+
+#[test]
+fn flow_template_escape_test() {
+    let mut flow = Flow::from(EntityInstance::new_without_properties(EntityTypeName::new("window"), Uuid::new_v4()));
+    flow.entity_instances[0]
+        .properties
+        .insert("title".to_string(), json!("$${window_title} is ${window_title}, $$${width}"));
+    flow.entity_instances[0].properties.insert("literal".to_string(), json!("$${height}"));
+    assert_eq!(vec!["window_title"], flow.get_template_variables().unwrap());
+    let flow = flow.instantiate(&variables()).unwrap();
+    assert_eq!(json!("${window_title} is Main, $${width}"), flow.entity_instances[0].get("title").unwrap());
+    assert_eq!(json!("${height}"), flow.entity_instances[0].get("literal").unwrap());
+}
@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+
+use serde_json::json;
 use uuid::Uuid;
 
 use crate::tests::utils::create_entity_instance_with_type;
@@ -18,6 +21,9 @@ fn flow_test() {
         description: flow_description.to_string(),
         entity_instances: Vec::new(),
         relation_instances: Vec::new(),
+        layers: Vec::new(),
+        type_versions: HashMap::new(),
+        variables: Vec::new(),
     };
 
     assert_eq!(flow_type_name.clone(), flow.type_name.clone());
@@ -46,3 +52,33 @@ fn flow_from_instance_with_name_test() {
     assert_eq!("generic_flow", flow.type_name);
     assert_eq!(flow_name, flow.name);
 }
+
+#[test]
+fn flow_serde_names_test() {
+    let wrapper_id = Uuid::new_v4();
+    let entity_id = Uuid::new_v4();
+    let legacy_flow: Flow = serde_json::from_value(json!({
+        "id": wrapper_id,
+        "type": "generic_flow",
+        "entities": [
+            { "type": "generic_flow", "id": wrapper_id },
+            { "type": "add", "id": entity_id }
+        ],
+        "relations": [
+            { "outbound": wrapper_id, "type": "default_connector", "inbound": entity_id }
+        ]
+    }))
+    .unwrap();
+    assert_eq!("generic_flow", legacy_flow.type_name);
+    assert_eq!(2, legacy_flow.entity_instances.len());
+    assert_eq!(entity_id, legacy_flow.relation_instances[0].inbound_id);
+
+    let json = serde_json::to_value(&legacy_flow).unwrap();
+    assert_eq!(json!("generic_flow"), json["type_name"]);
+    assert_eq!(json!("add"), json["entity_instances"][1]["type_name"]);
+    assert_eq!(json!(wrapper_id), json["relation_instances"][0]["outbound_id"]);
+    let flow: Flow = serde_json::from_value(json).unwrap();
+    assert_eq!(legacy_flow.type_name, flow.type_name);
+    assert_eq!(2, flow.entity_instances.len());
+    assert_eq!("default_connector", flow.relation_instances[0].type_name);
+}
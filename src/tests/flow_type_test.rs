@@ -0,0 +1,68 @@
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    DataType, EntityInstance, EntityTypeName, Flow, FlowType, FlowVariable, PropertyMap, PropertyType, RelationInstance, RelationTypeName, SocketType,
+    ValidationError,
+};
+
+fn create_flow_type() -> FlowType {
+    FlowType::new("adder", "generic_flow")
+        .with_entity_type(EntityTypeName::new("add"))
+        .with_relation_type(RelationTypeName::new("default_connector"))
+        .with_variable(PropertyType::input("lhs", DataType::Number))
+        .with_variable(PropertyType::output("result", DataType::Number))
+}
+
+#[test]
+fn flow_conforms_to_flow_type_test() {
+    let wrapper = EntityInstance::new_without_properties(EntityTypeName::new("generic_flow"), Uuid::new_v4());
+    let mut properties = PropertyMap::new();
+    properties.insert("lhs".to_string(), json!(1));
+    properties.insert("result".to_string(), json!(1));
+    let add = EntityInstance::new(EntityTypeName::new("add"), Uuid::new_v4(), properties);
+    let mut flow = Flow::from(wrapper.clone());
+    flow.entity_instances.push(add.clone());
+    flow.relation_instances
+        .push(RelationInstance::new_without_properties(wrapper.id, RelationTypeName::new("default_connector"), add.id));
+    flow.variables.push(FlowVariable::input("lhs", add.id, "lhs"));
+    flow.variables.push(FlowVariable::output("result", add.id, "result"));
+    assert!(flow.conforms_to(&create_flow_type()).is_valid());
+}
+
+#[test]
+fn flow_doesnt_conform_to_flow_type_test() {
+    let wrapper = EntityInstance::new_without_properties(EntityTypeName::new("scene"), Uuid::new_v4());
+    let mut properties = PropertyMap::new();
+    properties.insert("lhs".to_string(), json!("1"));
+    let add = EntityInstance::new(EntityTypeName::new("sub"), Uuid::new_v4(), properties);
+    let mut flow = Flow::from(wrapper);
+    flow.entity_instances.push(add.clone());
+    flow.variables.push(FlowVariable::output("lhs", add.id, "lhs"));
+    let report = flow.conforms_to(&create_flow_type());
+    assert_eq!(
+        vec![
+            ValidationError::InvalidType {
+                expected: "generic_flow".to_string(),
+                actual: "scene".to_string()
+            },
+            ValidationError::MissingEntityType { type_name: "add".to_string() },
+            ValidationError::MissingRelationType {
+                type_name: "default_connector".to_string()
+            },
+            ValidationError::InvalidSocketType {
+                property_name: "lhs".to_string(),
+                expected: SocketType::Input,
+                actual: SocketType::Output
+            },
+            ValidationError::InvalidDataType {
+                property_name: "lhs".to_string(),
+                expected: DataType::Number
+            },
+            ValidationError::MissingVariable {
+                variable_name: "result".to_string()
+            },
+        ],
+        report.errors
+    );
+}
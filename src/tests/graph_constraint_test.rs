@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::tests::utils::create_random_entity_instance::create_random_entity_instance_with_type;
+use crate::{
+    evaluate_constraints, ConstraintViolation, GraphConstraint, ReactiveEntityInstance, ReactiveFlow, ReactiveRelationInstance, RelationDirection,
+    RelationTypeName,
+};
+use crate::{PropertyInstanceSetter, RelationInstanceKey};
+
+fn entity(type_name: &str) -> Arc<ReactiveEntityInstance> {
+    Arc::new(create_random_entity_instance_with_type(type_name, "name"))
+}
+
+#[test]
+fn graph_constraint_relation_count_test() {
+    let player = entity("player");
+    let camera_1 = entity("camera");
+    let camera_2 = entity("camera");
+    let current_camera = Arc::new(
+        ReactiveRelationInstance::builder(player.clone(), RelationTypeName::new("current_camera"), camera_1.clone())
+            .build()
+            .unwrap(),
+    );
+    let constraint: GraphConstraint = serde_json::from_value(json!({
+        "constraint": "relation_count",
+        "entity_type": "camera",
+        "relation_type": "current_camera",
+        "direction": "inbound",
+        "other_type": "player",
+        "min": 1,
+        "max": 1
+    }))
+    .unwrap();
+    let violations = constraint.evaluate(&[player, camera_1, camera_2.clone()], &[current_camera]);
+    assert_eq!(
+        vec![ConstraintViolation::RelationCount {
+            id: camera_2.id,
+            relation_type: "current_camera".to_string(),
+            direction: RelationDirection::Inbound,
+            count: 0,
+            min: 1,
+            max: Some(1),
+        }],
+        violations
+    );
+    assert!(violations[0].to_string().contains("requires 1 to 1"));
+}
+
+#[test]
+fn graph_constraint_properties_test() {
+    let player_1 = entity("player");
+    let player_2 = entity("player");
+    let player_3 = entity("player");
+    player_1.set("name", json!("alice"));
+    player_2.set("name", json!("alice"));
+    player_3.set("name", json!("bob"));
+    let connector = Arc::new(
+        ReactiveRelationInstance::builder(player_1.clone(), RelationTypeName::new("connector"), player_2.clone())
+            .property("outbound_property_name", json!("missing"))
+            .build()
+            .unwrap(),
+    );
+    let constraints = vec![
+        GraphConstraint::PropertyExists {
+            entity_type: "player".to_string(),
+            property_name: "name".to_string(),
+        },
+        GraphConstraint::UniqueProperty {
+            entity_type: "player".to_string(),
+            property_name: "name".to_string(),
+        },
+        GraphConstraint::ReferencedPropertyExists {
+            relation_type: "connector".to_string(),
+            name_property: "outbound_property_name".to_string(),
+            end: RelationDirection::Outbound,
+        },
+    ];
+    let mut ids = vec![player_1.id, player_2.id];
+    ids.sort();
+    let violations = evaluate_constraints(&constraints, &[player_1.clone(), player_2, player_3], std::slice::from_ref(&connector));
+    assert_eq!(
+        vec![
+            ConstraintViolation::DuplicateValue {
+                ids,
+                property_name: "name".to_string(),
+                value: json!("alice"),
+            },
+            ConstraintViolation::MissingReferencedProperty {
+                key: RelationInstanceKey::from(connector.as_ref()),
+                end: RelationDirection::Outbound,
+                property_name: "missing".to_string(),
+            },
+        ],
+        violations
+    );
+    assert_eq!("duplicate_value", serde_json::to_value(&violations[0]).unwrap()["kind"]);
+
+    let flow = ReactiveFlow::new(player_1);
+    assert!(flow.evaluate_constraints(&constraints).is_empty());
+}
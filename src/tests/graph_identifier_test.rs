@@ -0,0 +1,48 @@
+use std::str::FromStr;
+
+use uuid::Uuid;
+
+use crate::tests::utils::{r_string, r_string_1000, r_string_255};
+use crate::{EdgeKey, Identifier};
+
+#[test]
+fn identifier_test() {
+    let name = r_string_255();
+    let identifier = Identifier::new(name.as_str()).unwrap();
+    assert_eq!(name.as_str(), identifier.as_str());
+    assert_eq!(name, identifier.to_string());
+    assert_eq!(identifier, Identifier::from_str(name.as_str()).unwrap());
+    assert!(Identifier::new("").is_err());
+    assert!(Identifier::new(r_string_1000()).is_err());
+}
+
+#[test]
+fn edge_key_test() {
+    let outbound_id = Uuid::new_v4();
+    let inbound_id = Uuid::new_v4();
+    let t = Identifier::new(r_string()).unwrap();
+    let edge_key = EdgeKey::new(outbound_id, t.clone(), inbound_id);
+    assert_eq!(outbound_id, edge_key.outbound_id);
+    assert_eq!(t, edge_key.t);
+    assert_eq!(inbound_id, edge_key.inbound_id);
+    let reversed = edge_key.reversed();
+    assert_eq!(inbound_id, reversed.outbound_id);
+    assert_eq!(outbound_id, reversed.inbound_id);
+    assert_eq!(edge_key, reversed.reversed());
+}
+
+#[cfg(feature = "graph-db")]
+#[test]
+fn graph_db_conversion_test() {
+    let t = Identifier::new(r_string()).unwrap();
+    let graph_identifier: indradb::Identifier = t.clone().into();
+    assert_eq!(t.as_str(), graph_identifier.as_str());
+    assert_eq!(t, Identifier::try_from(graph_identifier).unwrap());
+
+    let edge_key = EdgeKey::new(Uuid::new_v4(), t, Uuid::new_v4());
+    let graph_edge_key: indradb::EdgeKey = edge_key.clone().into();
+    assert_eq!(edge_key.outbound_id, graph_edge_key.outbound_id);
+    assert_eq!(edge_key.t.as_str(), graph_edge_key.t.as_str());
+    assert_eq!(edge_key.inbound_id, graph_edge_key.inbound_id);
+    assert_eq!(edge_key, EdgeKey::try_from(graph_edge_key).unwrap());
+}
@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::tests::utils::{create_random_entity_instance, create_random_relation_instance_with_properties, r_string};
+use crate::{GraphPartition, ReactiveFlow};
+
+/// Two cliques of four entity instances which are connected by a single edge.
+fn create_two_cliques() -> (Vec<Uuid>, Vec<Uuid>, Vec<(Uuid, Uuid)>) {
+    let a: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+    let b: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+    let mut edges = Vec::new();
+    for clique in [&a, &b] {
+        for i in 0..clique.len() {
+            for j in i + 1..clique.len() {
+                edges.push((clique[i], clique[j]));
+            }
+        }
+    }
+    edges.push((a[0], b[0]));
+    (a, b, edges)
+}
+
+#[test]
+fn graph_partition_test() {
+    let (a, b, edges) = create_two_cliques();
+    let entity_instances: Vec<Uuid> = a.iter().chain(b.iter()).copied().collect();
+    let partition = GraphPartition::partition(&entity_instances, &edges, 2);
+    assert_eq!(2, partition.shard_count);
+    assert_eq!(vec![4, 4], partition.get_shard_sizes());
+    assert_eq!(1, partition.get_cut_size(&edges));
+    let shard_a = partition.get_shard(a[0]).unwrap();
+    assert!(a.iter().all(|id| partition.get_shard(*id) == Some(shard_a)));
+    assert_ne!(shard_a, partition.get_shard(b[0]).unwrap());
+    let mut members = a.clone();
+    members.sort();
+    assert_eq!(members, partition.get_shard_members(shard_a));
+    assert_eq!(None, partition.get_shard(Uuid::new_v4()));
+
+    // The partitioning doesn't depend on the order of the input
+    let mut reversed = entity_instances.clone();
+    reversed.reverse();
+    assert_eq!(partition, GraphPartition::partition(&reversed, &edges, 2));
+}
+
+#[test]
+fn graph_partition_stable_shard_ids_test() {
+    let (a, b, edges) = create_two_cliques();
+    let entity_instances: Vec<Uuid> = a.iter().chain(b.iter()).copied().collect();
+    let partition = GraphPartition::partition(&entity_instances, &edges, 2);
+    let shard_a = partition.get_shard(a[0]).unwrap();
+
+    // Swap the shards of the previous partition
+    let mut previous = partition.clone();
+    for shard in previous.shards.values_mut() {
+        *shard = 1 - *shard;
+    }
+    let repartition = GraphPartition::repartition(&entity_instances, &edges, 2, Some(&previous));
+    assert_eq!(Some(1 - shard_a), repartition.get_shard(a[0]));
+
+    // New entity instances join the community of their neighbours
+    let c = Uuid::new_v4();
+    let mut entity_instances = entity_instances;
+    entity_instances.push(c);
+    let mut edges = edges;
+    edges.push((c, b[1]));
+    edges.push((c, b[2]));
+    let repartition = GraphPartition::repartition(&entity_instances, &edges, 2, Some(&partition));
+    assert_eq!(partition.get_shard(a[0]), repartition.get_shard(a[0]));
+    assert_eq!(partition.get_shard(b[0]), repartition.get_shard(c));
+}
+
+#[test]
+fn graph_partition_flow_test() {
+    let wrapper_entity_instance = Arc::new(create_random_entity_instance(r_string()));
+    let reactive_flow = ReactiveFlow::new(wrapper_entity_instance.clone());
+    let entity_instance = Arc::new(create_random_entity_instance(r_string()));
+    reactive_flow.add_entity(entity_instance.clone());
+    let relation_instance = Arc::new(create_random_relation_instance_with_properties(
+        wrapper_entity_instance.clone(),
+        entity_instance.clone(),
+        r_string(),
+    ));
+    reactive_flow.add_relation(relation_instance);
+
+    let partition = GraphPartition::partition_flow(&reactive_flow, 4);
+    assert_eq!(4, partition.shard_count);
+    assert_eq!(2, partition.shards.len());
+    assert_eq!(partition.get_shard(wrapper_entity_instance.id), partition.get_shard(entity_instance.id));
+}
@@ -0,0 +1,75 @@
+use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+use futures::executor::block_on;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    ComponentName, DataType, EntityInstance, EntityType, EntityTypeName, Flow, GraphQLEntityType, GraphQLFlow, PropertyMap, PropertyType, RelationInstance,
+    RelationTypeName, SocketType,
+};
+
+struct Query {
+    flow: Flow,
+    entity_type: EntityType,
+}
+
+#[Object]
+impl Query {
+    async fn flow(&self) -> GraphQLFlow {
+        GraphQLFlow(self.flow.clone())
+    }
+
+    async fn entity_type(&self) -> GraphQLEntityType {
+        GraphQLEntityType(self.entity_type.clone())
+    }
+}
+
+fn execute(query: Query, request: &str) -> serde_json::Value {
+    let schema = Schema::new(query, EmptyMutation, EmptySubscription);
+    let response = block_on(schema.execute(request));
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    response.data.into_json().unwrap()
+}
+
+#[test]
+fn graphql_test() {
+    let wrapper_id = Uuid::new_v4();
+    let mut properties = PropertyMap::new();
+    properties.insert("name".to_string(), json!("alice"));
+    properties.insert("score".to_string(), json!(3));
+    let player = EntityInstance::new(EntityTypeName::new("player"), Uuid::new_v4(), properties);
+    let mut flow = Flow::from_instance_with_name(EntityInstance::new_without_properties(EntityTypeName::new("generic_flow"), wrapper_id), "game");
+    flow.relation_instances
+        .push(RelationInstance::new_without_properties(wrapper_id, RelationTypeName::new("contains"), player.id));
+    flow.entity_instances.push(player.clone());
+    let entity_type = EntityType::new(
+        EntityTypeName::new("player"),
+        "game",
+        "",
+        vec![ComponentName::new("named")],
+        vec![PropertyType::new_with_socket("score", DataType::Number, SocketType::Output)],
+        Vec::new(),
+    );
+
+    let data = execute(
+        Query { flow, entity_type },
+        r#"{
+            flow {
+                id name
+                entityInstances { typeName properties(names: ["score"]) { name value } }
+                relationInstances { outboundId typeName inboundId }
+            }
+            entityType { name components properties { name dataType socketType kind } }
+        }"#,
+    );
+    assert_eq!(json!(wrapper_id.to_string()), data["flow"]["id"]);
+    assert_eq!(json!("game"), data["flow"]["name"]);
+    assert_eq!(json!("player"), data["flow"]["entityInstances"][1]["typeName"]);
+    assert_eq!(json!([{ "name": "score", "value": 3 }]), data["flow"]["entityInstances"][1]["properties"]);
+    assert_eq!(json!(player.id.to_string()), data["flow"]["relationInstances"][0]["inboundId"]);
+    assert_eq!(json!(["named"]), data["entityType"]["components"]);
+    assert_eq!(
+        json!([{ "name": "score", "dataType": "NUMBER", "socketType": "OUTPUT", "kind": "VALUE_PROPERTY" }]),
+        data["entityType"]["properties"]
+    );
+}
@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use uuid::Uuid;
+
+use crate::generate_id;
+use crate::stable_id;
+use crate::stable_namespace;
+use crate::test_utils::default_entity_type;
+use crate::test_utils::default_flow;
+use crate::EntityInstance;
+use crate::EntityTypeName;
+use crate::GlobalIdGenerator;
+use crate::IdGenerator;
+use crate::NamespacedIdGenerator;
+use crate::PropertyMap;
+use crate::RandomIdGenerator;
+use crate::ReactiveEntityInstance;
+use crate::ReactiveFlow;
+use crate::SequentialIdGenerator;
+
+#[test]
+fn random_id_generator_test() {
+    let id_generator = RandomIdGenerator::default();
+    let id = id_generator.generate();
+    assert_eq!(4, id.get_version_num());
+    assert_ne!(id, id_generator.generate());
+}
+
+#[test]
+fn namespaced_id_generator_test() {
+    let namespace = Uuid::new_v4();
+    let id_generator_1 = NamespacedIdGenerator::new(namespace);
+    let id_generator_2 = NamespacedIdGenerator::new(namespace);
+    let ids_1: Vec<Uuid> = (0..3).map(|_| id_generator_1.generate()).collect();
+    let ids_2: Vec<Uuid> = (0..3).map(|_| id_generator_2.generate()).collect();
+    assert_eq!(ids_1, ids_2);
+    assert_eq!(5, ids_1[0].get_version_num());
+    assert_ne!(ids_1[0], ids_1[1]);
+    assert_ne!(ids_1[0], NamespacedIdGenerator::new(Uuid::new_v4()).generate());
+    assert_eq!(id_generator_1.generate_for("player"), id_generator_2.generate_for("player"));
+}
+
+#[test]
+fn stable_id_test() {
+    let namespace = stable_namespace("inexor-rgf-plugin-system");
+    assert_eq!(Uuid::parse_str("942d9b80-95c5-51d4-8ea9-dfb4be868e7c").unwrap(), namespace);
+    let id = stable_id(&namespace, "system_event_started");
+    assert_eq!(Uuid::parse_str("08d869e4-6509-542b-89a4-c331f5f6e46c").unwrap(), id);
+    assert_ne!(id, stable_id(&stable_namespace("inexor-rgf-plugin-flow"), "system_event_started"));
+    assert_eq!(id, NamespacedIdGenerator::new(namespace).generate_for("system_event_started"));
+
+    let entity_instance = EntityInstance::new_with_stable_id(&namespace, "system_event_started", EntityTypeName::new("system_event"), PropertyMap::new());
+    assert_eq!(id, entity_instance.id);
+    assert_eq!("system_event", entity_instance.type_name);
+    assert_eq!(
+        id,
+        EntityInstance::builder()
+            .type_name(EntityTypeName::new("system_event"))
+            .stable_id(&namespace, "system_event_started")
+            .build()
+            .id
+    );
+}
+
+#[test]
+fn sequential_id_generator_test() {
+    let id_generator = SequentialIdGenerator::new(1);
+    assert_eq!(Uuid::from_u128(1), id_generator.generate());
+    assert_eq!(Uuid::from_u128(2), id_generator.generate());
+    assert_eq!(Uuid::nil(), SequentialIdGenerator::default().generate());
+}
+
+#[test]
+fn injected_id_generator_test() {
+    let id_generator = SequentialIdGenerator::new(1000);
+    let entity_instance = EntityInstance::builder()
+        .type_name(EntityTypeName::new("system_event"))
+        .generated_id(&id_generator)
+        .build();
+    assert_eq!(Uuid::from_u128(1000), entity_instance.id);
+
+    let entity_type = default_entity_type();
+    let reactive_entity_instance = ReactiveEntityInstance::new_from_type_with_id_generator(&entity_type, &[], &id_generator);
+    assert_eq!(Uuid::from_u128(1001), reactive_entity_instance.id);
+
+    let flow = default_flow();
+    let instantiated = flow.instantiate_with_id_generator(&HashMap::new(), &id_generator).unwrap();
+    let ids: Vec<Uuid> = instantiated.entity_instances.iter().map(|entity_instance| entity_instance.id).collect();
+    assert_eq!(vec![Uuid::from_u128(1002), Uuid::from_u128(1003), Uuid::from_u128(1004)], ids);
+    assert_eq!(Uuid::from_u128(1002), instantiated.id);
+
+    let reactive_flow = ReactiveFlow::try_from(flow).unwrap();
+    let duplicate = reactive_flow.duplicate_with_id_generator(&id_generator);
+    let mut ids: Vec<Uuid> = duplicate.entity_instances.read().unwrap().keys().cloned().collect();
+    ids.sort();
+    assert_eq!(vec![Uuid::from_u128(1005), Uuid::from_u128(1006), Uuid::from_u128(1007)], ids);
+}
+
+#[test]
+fn global_id_generator_test() {
+    // Without a registered id generator, random ids are generated
+    assert_eq!(4, generate_id().get_version_num());
+    assert_eq!(4, GlobalIdGenerator::default().generate().get_version_num());
+}
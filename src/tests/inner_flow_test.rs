@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::tests::utils::create_random_entity_instance_with_type;
+use crate::{EdgeKey, EntityTypeName, Identifier};
+use crate::{EntityType, Extension, ExtensionError, PropertyInstanceGetter, ReactiveFlowConstructionError, INNER_FLOW_EXTENSION};
+
+fn create_sensor_type(wrapper_id: Uuid, filter_id: Uuid) -> EntityType {
+    let inner_flow = json!({
+        "id": wrapper_id,
+        "type": "sensor",
+        "entities": [
+            { "type": "sensor", "id": wrapper_id, "properties": { "threshold": 5 } },
+            { "type": "filter", "id": filter_id, "properties": { "value": 0 } }
+        ],
+        "relations": [
+            { "outbound_id": wrapper_id, "type_name": "connector", "inbound_id": filter_id, "properties": {} }
+        ]
+    });
+    let extension = Extension {
+        name: INNER_FLOW_EXTENSION.to_string(),
+        extension: inner_flow,
+        version: None,
+    };
+    EntityType::new(EntityTypeName::new("sensor"), "", "", Vec::new(), Vec::new(), vec![extension])
+}
+
+#[test]
+fn inner_flow_test() {
+    let wrapper_id = Uuid::new_v4();
+    let filter_id = Uuid::new_v4();
+    let entity_type = create_sensor_type(wrapper_id, filter_id);
+    let inner_flow = entity_type.get_inner_flow().unwrap();
+    assert_eq!(wrapper_id, inner_flow.id);
+    assert_eq!(2, inner_flow.entity_instances.len());
+
+    let sensor_1 = Arc::new(create_random_entity_instance_with_type("sensor", "value"));
+    let sensor_2 = Arc::new(create_random_entity_instance_with_type("sensor", "value"));
+    let reactive_flow_1 = entity_type.instantiate_inner_flow(sensor_1.clone()).unwrap().unwrap();
+    let reactive_flow_2 = entity_type.instantiate_inner_flow(sensor_2.clone()).unwrap().unwrap();
+
+    // The created entity instance is the wrapper entity instance
+    assert_eq!(sensor_1.id, reactive_flow_1.id);
+    assert!(Arc::ptr_eq(&sensor_1, &reactive_flow_1.get_entity(sensor_1.id).unwrap()));
+    assert_eq!(5, sensor_1.as_u64("threshold").unwrap());
+
+    // Each inner flow has its own entity instances
+    let filter_1 = reactive_flow_1
+        .entity_instances
+        .read()
+        .unwrap()
+        .values()
+        .find(|e| e.type_name == "filter")
+        .unwrap()
+        .clone();
+    let filter_2 = reactive_flow_2
+        .entity_instances
+        .read()
+        .unwrap()
+        .values()
+        .find(|e| e.type_name == "filter")
+        .unwrap()
+        .clone();
+    assert_ne!(filter_id, filter_1.id);
+    assert_ne!(filter_1.id, filter_2.id);
+
+    let t = Identifier::new("connector").unwrap();
+    let relation_instance = reactive_flow_1.get_relation(EdgeKey::new(sensor_1.id, t, filter_1.id)).unwrap();
+    assert!(Arc::ptr_eq(&sensor_1, &relation_instance.outbound));
+    assert!(Arc::ptr_eq(&filter_1, &relation_instance.inbound));
+}
+
+#[test]
+fn inner_flow_missing_test() {
+    let entity_type = EntityType::new(EntityTypeName::new("sensor"), "", "", Vec::new(), Vec::new(), Vec::new());
+    assert!(matches!(entity_type.get_inner_flow(), Err(ExtensionError::Missing(_))));
+    assert!(entity_type
+        .instantiate_inner_flow(Arc::new(create_random_entity_instance_with_type("sensor", "value")))
+        .is_none());
+
+    // The inner flow doesn't contain the wrapper entity instance
+    let mut entity_type = create_sensor_type(Uuid::new_v4(), Uuid::new_v4());
+    entity_type.extensions[0].extension["id"] = json!(Uuid::new_v4());
+    let result = entity_type.instantiate_inner_flow(Arc::new(create_random_entity_instance_with_type("sensor", "value")));
+    assert!(matches!(result, Some(Err(ReactiveFlowConstructionError::MissingWrapperInstance))));
+
+    // The inner flow extension doesn't contain a flow
+    entity_type.extensions[0].extension = json!("not a flow");
+    assert!(matches!(entity_type.get_inner_flow(), Err(ExtensionError::Invalid(_, _))));
+    let result = entity_type.instantiate_inner_flow(Arc::new(create_random_entity_instance_with_type("sensor", "value")));
+    assert!(matches!(result, Some(Err(ReactiveFlowConstructionError::InvalidInnerFlow(ExtensionError::Invalid(_, _))))));
+}
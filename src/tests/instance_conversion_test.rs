@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+#[cfg(feature = "graph-db")]
+use indradb::{Edge, EdgeKey, EdgeProperties, Identifier, NamedProperty, Vertex, VertexProperties};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::tests::utils::create_random_entity_instance;
+use crate::{EntityInstance, InstanceConversionError, PropertyInstanceGetter, ReactiveEntityInstance, ReactiveRelationInstance, RelationInstance};
+
+fn named_property(name: &str, value: serde_json::Value) -> NamedProperty {
+    NamedProperty::new(Identifier::new(name).unwrap(), value)
+}
+
+fn edge_properties(outbound_id: Uuid, inbound_id: Uuid, props: Vec<NamedProperty>) -> EdgeProperties {
+    let key = EdgeKey::new(outbound_id, Identifier::new("connector").unwrap(), inbound_id);
+    EdgeProperties::new(Edge::new_with_current_datetime(key), props)
+}
+
+#[test]
+fn entity_instance_try_from_vertex_properties_test() {
+    let vertex = Vertex::new(Identifier::new("add").unwrap());
+    let vertex_properties = VertexProperties::new(vertex.clone(), vec![named_property("lhs", json!(1))]);
+    let entity_instance = EntityInstance::try_from(&vertex_properties).unwrap();
+    assert_eq!("add", entity_instance.type_name);
+    assert_eq!(vertex.id, entity_instance.id);
+    assert_eq!(json!(1), entity_instance.get("lhs").unwrap());
+
+    let reactive_entity_instance = ReactiveEntityInstance::try_from(&vertex_properties).unwrap();
+    assert_eq!(vertex.id, reactive_entity_instance.id);
+    assert_eq!(1, reactive_entity_instance.as_u64("lhs").unwrap());
+}
+
+#[test]
+fn entity_instance_try_from_duplicate_property_test() {
+    let vertex = Vertex::new(Identifier::new("add").unwrap());
+    let vertex_properties = VertexProperties::new(vertex, vec![named_property("lhs", json!(1)), named_property("lhs", json!(2))]);
+    assert_eq!(
+        InstanceConversionError::DuplicateProperty("lhs".to_string()),
+        EntityInstance::try_from(&vertex_properties).unwrap_err()
+    );
+}
+
+#[test]
+fn relation_instance_try_from_edge_properties_test() {
+    let outbound_id = Uuid::new_v4();
+    let inbound_id = Uuid::new_v4();
+    let relation_instance = RelationInstance::try_from(&edge_properties(outbound_id, inbound_id, vec![named_property("weight", json!(3))])).unwrap();
+    assert_eq!(outbound_id, relation_instance.outbound_id);
+    assert_eq!("connector", relation_instance.type_name);
+    assert_eq!(inbound_id, relation_instance.inbound_id);
+    assert_eq!(json!(3), relation_instance.get("weight").unwrap());
+
+    assert_eq!(
+        InstanceConversionError::MissingOutbound(Uuid::nil()),
+        RelationInstance::try_from(&edge_properties(Uuid::nil(), inbound_id, Vec::new())).unwrap_err()
+    );
+    assert_eq!(
+        InstanceConversionError::MissingInbound(Uuid::nil()),
+        RelationInstance::try_from(&edge_properties(outbound_id, Uuid::nil(), Vec::new())).unwrap_err()
+    );
+}
+
+#[test]
+fn reactive_relation_instance_try_from_edge_properties_test() {
+    let outbound = Arc::new(create_random_entity_instance("value"));
+    let inbound = Arc::new(create_random_entity_instance("value"));
+    let properties = edge_properties(outbound.id, inbound.id, vec![named_property("weight", json!(3))]);
+    let relation_instance = ReactiveRelationInstance::try_from((outbound.clone(), inbound.clone(), &properties)).unwrap();
+    assert_eq!(outbound.id, relation_instance.outbound.id);
+    assert_eq!(inbound.id, relation_instance.inbound.id);
+    assert_eq!(3, relation_instance.as_u64("weight").unwrap());
+
+    let result = ReactiveRelationInstance::try_from((inbound.clone(), inbound.clone(), &properties));
+    assert_eq!(InstanceConversionError::MissingOutbound(outbound.id), result.err().unwrap());
+    let result = ReactiveRelationInstance::try_from((outbound.clone(), outbound.clone(), &properties));
+    assert_eq!(InstanceConversionError::MissingInbound(inbound.id), result.err().unwrap());
+}
+
+#[test]
+fn instance_conversion_error_display_test() {
+    assert_eq!(
+        "The property lhs occurs multiple times",
+        InstanceConversionError::DuplicateProperty("lhs".to_string()).to_string()
+    );
+}
@@ -0,0 +1,98 @@
+use std::sync::{Arc, RwLock};
+
+use serde_json::json;
+
+use crate::tests::utils::{create_random_entity_instance, create_random_relation_instance_with_properties, r_string};
+use crate::BehaviourName;
+use crate::ComponentName;
+use crate::InstanceEvent;
+use crate::PropertyInstanceSetter;
+use crate::ReactivePropertyContainer;
+
+#[test]
+fn reactive_entity_instance_observe_events_test() {
+    let entity_instance = create_random_entity_instance("x");
+    let events = Arc::new(RwLock::new(Vec::new()));
+    let recorded = events.clone();
+    let handle_id = entity_instance.observe_events(move |event| recorded.write().unwrap().push(event.clone()));
+    entity_instance.set("x", json!(1));
+    entity_instance.add_property("y", json!(0));
+    entity_instance.add_component(ComponentName::new("positionable"));
+    entity_instance.add_behaviour(BehaviourName::new("move"));
+    entity_instance.remove_behaviour(BehaviourName::new("move"));
+    assert_eq!(
+        vec![
+            InstanceEvent::PropertyChanged {
+                name: "x".to_string(),
+                value: json!(1)
+            },
+            InstanceEvent::PropertyAdded {
+                name: "y".to_string(),
+                value: json!(0)
+            },
+            InstanceEvent::ComponentAdded("positionable".to_string()),
+            InstanceEvent::BehaviourAdded("move".to_string()),
+            InstanceEvent::BehaviourRemoved("move".to_string()),
+        ],
+        *events.read().unwrap()
+    );
+
+    entity_instance.remove_event_observer(handle_id);
+    entity_instance.set("y", json!(2));
+    entity_instance.add_property("z", json!(0));
+    entity_instance.remove_component(ComponentName::new("positionable"));
+    assert_eq!(5, events.read().unwrap().len());
+}
+
+#[test]
+fn reactive_relation_instance_observe_events_test() {
+    let outbound = Arc::new(create_random_entity_instance(r_string()));
+    let inbound = Arc::new(create_random_entity_instance(r_string()));
+    let relation_instance = create_random_relation_instance_with_properties(outbound, inbound, "weight".to_string());
+    let events = Arc::new(RwLock::new(Vec::new()));
+    let recorded = events.clone();
+    let handle_id = relation_instance.observe_events(move |event| recorded.write().unwrap().push(event.clone()));
+    relation_instance.add_property("label", json!(""));
+    relation_instance.set("label", json!("a"));
+    relation_instance.add_component(ComponentName::new("weighted"));
+    assert_eq!(
+        vec![
+            InstanceEvent::PropertyAdded {
+                name: "label".to_string(),
+                value: json!("")
+            },
+            InstanceEvent::PropertyChanged {
+                name: "label".to_string(),
+                value: json!("a")
+            },
+            InstanceEvent::ComponentAdded("weighted".to_string()),
+        ],
+        *events.read().unwrap()
+    );
+    relation_instance.remove_event_observer(handle_id);
+    relation_instance.set("weight", json!(2));
+    assert_eq!(3, events.read().unwrap().len());
+}
+
+#[cfg(feature = "futures")]
+#[test]
+fn reactive_entity_instance_event_stream_test() {
+    use futures::executor::block_on;
+    use futures::StreamExt;
+
+    let entity_instance = Arc::new(create_random_entity_instance("x"));
+    let mut stream = entity_instance.as_event_stream();
+    entity_instance.set("x", json!(1));
+    entity_instance.add_component(ComponentName::new("positionable"));
+    assert_eq!(
+        InstanceEvent::PropertyChanged {
+            name: "x".to_string(),
+            value: json!(1)
+        },
+        block_on(stream.next()).unwrap()
+    );
+    assert_eq!(InstanceEvent::ComponentAdded("positionable".to_string()), block_on(stream.next()).unwrap());
+    drop(stream);
+    // The observer has been removed
+    entity_instance.set("x", json!(2));
+}
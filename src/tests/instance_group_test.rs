@@ -0,0 +1,56 @@
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::tests::utils::r_string;
+use crate::InstanceGroup;
+use crate::MutablePropertyInstanceSetter;
+use crate::PropertyInstanceGetter;
+use crate::RelationInstanceKey;
+
+#[test]
+fn instance_group_membership_test() {
+    let name = r_string();
+    let mut group = InstanceGroup::new(name.clone());
+    assert_eq!(name, group.name);
+    assert!(group.is_empty());
+
+    let id_1 = Uuid::new_v4();
+    let id_2 = Uuid::new_v4();
+    assert!(group.add_entity(id_1));
+    assert!(!group.add_entity(id_1));
+    assert!(group.add_entity(id_2));
+    assert!(group.has_entity(id_1));
+    assert!(!group.has_entity(Uuid::new_v4()));
+
+    let key = RelationInstanceKey::new(id_1, r_string(), id_2);
+    assert!(group.add_relation(key.clone()));
+    assert!(!group.add_relation(key.clone()));
+    assert!(group.has_relation(&key));
+    assert_eq!(3, group.len());
+
+    assert!(group.remove_entity(id_2));
+    assert!(!group.remove_entity(id_2));
+    assert!(group.has_relation(&key));
+    group.remove_entity_and_relations(id_1);
+    assert!(!group.has_relation(&key));
+    assert!(group.is_empty());
+}
+
+#[test]
+fn instance_group_properties_test() {
+    let mut group = InstanceGroup::new(r_string());
+    assert!(group.get("visible").is_none());
+    group.set("visible", json!(true));
+    assert!(group.as_bool("visible").unwrap());
+}
+
+#[test]
+fn instance_group_serde_test() {
+    let id = Uuid::new_v4();
+    let group: InstanceGroup = serde_json::from_str(&format!(r#"{{"name": "selection", "entities": ["{}"]}}"#, id)).unwrap();
+    assert_eq!("selection", group.name);
+    assert!(group.has_entity(id));
+    assert!(group.relation_instances.is_empty());
+    let json = serde_json::to_value(&group).unwrap();
+    assert_eq!(json!(id.to_string()), json["entity_instances"][0]);
+}
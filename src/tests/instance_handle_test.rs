@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use crate::tests::utils::{create_random_entity_instance, create_random_entity_instance_with_type, create_random_relation_instance_with_properties};
+use crate::{PropertyInstanceGetter, ReactiveFlow, RelationInstanceKey};
+
+#[test]
+fn entity_instance_handle_test() {
+    let entity_instance = Arc::new(create_random_entity_instance("x"));
+    let handle = entity_instance.handle();
+    assert_eq!(entity_instance.id, handle.id);
+    assert!(handle.is_alive());
+    assert_eq!(entity_instance.get("x"), handle.upgrade().unwrap().get("x"));
+    drop(entity_instance);
+    assert!(!handle.is_alive());
+    assert!(handle.upgrade().is_none());
+}
+
+#[test]
+fn relation_instance_handle_test() {
+    let outbound = Arc::new(create_random_entity_instance("x"));
+    let inbound = Arc::new(create_random_entity_instance("y"));
+    let relation_instance = Arc::new(create_random_relation_instance_with_properties(outbound.clone(), inbound.clone(), "z".to_string()));
+    let handle = relation_instance.handle();
+    assert_eq!(RelationInstanceKey::from(relation_instance.as_ref()), handle.key);
+    let outbound_handle = relation_instance.outbound_handle();
+    assert_eq!(outbound.id, outbound_handle.id);
+    assert_eq!(inbound.id, relation_instance.inbound_handle().id);
+
+    // The relation instance keeps its outbound entity instance alive, the handles don't
+    drop(outbound);
+    assert!(outbound_handle.is_alive());
+    drop(relation_instance);
+    assert!(!handle.is_alive());
+    assert!(!outbound_handle.is_alive());
+}
+
+#[test]
+fn remove_entity_with_relations_test() {
+    let wrapper_entity_instance = Arc::new(create_random_entity_instance_with_type("generic_flow", "test"));
+    let reactive_flow = ReactiveFlow::new(wrapper_entity_instance.clone());
+    let e1 = Arc::new(create_random_entity_instance("x"));
+    let e2 = Arc::new(create_random_entity_instance("x"));
+    let e3 = Arc::new(create_random_entity_instance("x"));
+    let r1 = Arc::new(create_random_relation_instance_with_properties(e1.clone(), e2.clone(), "x".to_string()));
+    let r2 = Arc::new(create_random_relation_instance_with_properties(e2.clone(), e3.clone(), "x".to_string()));
+    let r3 = Arc::new(create_random_relation_instance_with_properties(e1.clone(), e3.clone(), "x".to_string()));
+    for entity_instance in [&e1, &e2, &e3] {
+        reactive_flow.add_entity(entity_instance.clone());
+    }
+    for relation_instance in [&r1, &r2, &r3] {
+        reactive_flow.add_relation(relation_instance.clone());
+    }
+    let e2_handle = e2.handle();
+    let r1_handle = r1.handle();
+    drop(e2);
+    drop(r1);
+    drop(r2);
+
+    let removed = reactive_flow.remove_entity_with_relations(e2_handle.id);
+    assert_eq!(2, removed.len());
+    assert!(!reactive_flow.has_entity_by_id(e2_handle.id));
+    assert!(reactive_flow.has_relation(r3.clone()));
+    assert_eq!(2, reactive_flow.relations_removed.read().unwrap().len());
+    assert_eq!(vec![e2_handle.id], *reactive_flow.entities_removed.read().unwrap());
+    assert!(e2_handle.is_alive());
+    drop(removed);
+    // Nothing refers to the removed instances anymore
+    assert!(!e2_handle.is_alive());
+    assert!(!r1_handle.is_alive());
+}
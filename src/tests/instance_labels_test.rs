@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::tests::utils::{create_random_entity_instance, create_random_entity_instance_with_type, create_random_relation_instance_with_properties};
+use crate::{
+    get_label_value, EntityInstance, EntityTypeName, InstanceLabels, MutableInstanceLabels, ReactiveEntityInstance, ReactiveFlow, ReactiveInstanceLabels,
+    RelationInstance, RelationTypeName,
+};
+
+#[test]
+fn get_label_value_test() {
+    assert_eq!(Some("3"), get_label_value("level:3", "level"));
+    assert_eq!(Some("a:b"), get_label_value("level:a:b", "level"));
+    assert_eq!(None, get_label_value("level", "level"));
+    assert_eq!(None, get_label_value("lvl:3", "level"));
+}
+
+#[test]
+fn entity_instance_labels_test() {
+    let mut entity_instance = EntityInstance::builder().type_name(EntityTypeName::new("player")).label("debug").build();
+    assert!(entity_instance.has_label("debug"));
+    assert!(!entity_instance.add_label("debug"));
+    assert!(entity_instance.add_label("level:3"));
+    assert_eq!(Some("3".to_string()), entity_instance.get_label_value("level"));
+    assert_eq!(None, entity_instance.get_label_value("debug"));
+    assert!(entity_instance.remove_label("debug"));
+    assert!(!entity_instance.remove_label("debug"));
+    assert_eq!(vec!["level:3".to_string()], entity_instance.labels);
+
+    let serialized = serde_json::to_value(&entity_instance).unwrap();
+    assert_eq!(json!(["level:3"]), serialized["labels"]);
+    let deserialized: EntityInstance = serde_json::from_value(json!({ "type": "player", "id": Uuid::new_v4() })).unwrap();
+    assert!(deserialized.labels.is_empty());
+    assert!(serde_json::to_value(&deserialized).unwrap().get("labels").is_none());
+    // Duplicate labels are removed
+    let deserialized: EntityInstance = serde_json::from_value(json!({ "type": "player", "id": Uuid::new_v4(), "labels": ["b", "a", "b"] })).unwrap();
+    assert_eq!(vec!["b".to_string(), "a".to_string()], deserialized.labels);
+
+    let reactive_entity_instance = Arc::new(ReactiveEntityInstance::from(entity_instance));
+    assert!(reactive_entity_instance.has_label("level:3"));
+    assert_eq!(vec!["level:3".to_string()], EntityInstance::from(reactive_entity_instance).labels);
+}
+
+#[test]
+fn relation_instance_labels_test() {
+    let mut relation_instance = RelationInstance::builder(Uuid::new_v4(), RelationTypeName::new("connector"), Uuid::new_v4())
+        .label("debug")
+        .build();
+    assert!(relation_instance.has_label("debug"));
+    assert!(relation_instance.add_label("level:1"));
+    assert_eq!(Some("1".to_string()), relation_instance.get_label_value("level"));
+    assert!(relation_instance.remove_label("debug"));
+    assert_eq!(vec!["level:1".to_string()], relation_instance.labels);
+
+    let mut serialized = serde_json::to_value(&relation_instance).unwrap();
+    serialized["labels"] = json!(["level:1", "level:1"]);
+    let deserialized: RelationInstance = serde_json::from_value(serialized).unwrap();
+    assert_eq!(vec!["level:1".to_string()], deserialized.labels);
+}
+
+#[test]
+fn reactive_instance_labels_test() {
+    let wrapper_entity_instance = Arc::new(create_random_entity_instance_with_type("generic_flow", "test"));
+    let reactive_flow = ReactiveFlow::new(wrapper_entity_instance);
+    let a = Arc::new(create_random_entity_instance("x"));
+    let b = Arc::new(create_random_entity_instance("x"));
+    reactive_flow.add_entity(a.clone());
+    reactive_flow.add_entity(b.clone());
+    let revision = a.revision.get();
+    assert!(a.add_label("debug"));
+    assert!(!a.add_label("debug"));
+    assert!(a.add_label("level:3"));
+    assert_eq!(revision + 2, a.revision.get());
+    assert_eq!(vec!["debug".to_string(), "level:3".to_string()], a.get_labels());
+    assert_eq!(Some("3".to_string()), a.get_label_value("level"));
+    let entity_instances = reactive_flow.get_entities_by_label("debug");
+    assert_eq!(1, entity_instances.len());
+    assert_eq!(a.id, entity_instances[0].id);
+    assert!(a.remove_label("debug"));
+    assert!(reactive_flow.get_entities_by_label("debug").is_empty());
+
+    let a_b = Arc::new(create_random_relation_instance_with_properties(a.clone(), b.clone(), "x".to_string()));
+    reactive_flow.add_relation(a_b.clone());
+    assert!(a_b.add_label("debug"));
+    assert!(a_b.has_label("debug"));
+    assert_eq!(1, reactive_flow.get_relations_by_label("debug").len());
+    let duplicate = a_b.duplicate(b, a);
+    assert!(duplicate.has_label("debug"));
+    assert!(RelationInstance::from(a_b.clone()).has_label("debug"));
+    assert!(a_b.remove_label("debug"));
+    assert!(a_b.get_labels().is_empty());
+}
+
+#[test]
+fn builder_labels_are_unique_test() {
+    let entity_instance = EntityInstance::builder()
+        .type_name(EntityTypeName::new("player"))
+        .label("debug")
+        .label("debug")
+        .build();
+    assert_eq!(vec!["debug".to_string()], entity_instance.labels);
+    let relation_instance = RelationInstance::builder(Uuid::new_v4(), RelationTypeName::new("connector"), Uuid::new_v4())
+        .label("debug")
+        .label("debug")
+        .build();
+    assert_eq!(vec!["debug".to_string()], relation_instance.labels);
+}
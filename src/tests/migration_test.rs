@@ -0,0 +1,106 @@
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    remove_property_value, DataType, EntityInstance, EntityType, EntityTypeName, Flow, MigrationError, Migrations, PropertyMap, PropertyType, RelationInstance,
+    RelationType, RelationTypeName,
+};
+
+fn create_migrations() -> Migrations {
+    let mut migrations = Migrations::new();
+    // Version 1 renamed "val" to "value"
+    migrations.register("counter", 0, |properties| {
+        if let Some(value) = remove_property_value(properties, "val") {
+            properties.insert("value".to_string(), value);
+        }
+    });
+    // Version 2 added "step"
+    migrations.register("counter", 1, |properties| {
+        properties.entry("step".to_string()).or_insert(json!(1));
+    });
+    migrations
+}
+
+#[test]
+fn migrate_properties_test() {
+    let migrations = create_migrations();
+    assert!(migrations.has_migration("counter", 1));
+    assert!(!migrations.has_migration("counter", 2));
+
+    let mut properties = PropertyMap::new();
+    properties.insert("val".to_string(), json!(5));
+    assert!(migrations.migrate("counter", &mut properties, 0, 2).is_ok());
+    assert_eq!(Some(&json!(5)), properties.get("value"));
+    assert_eq!(Some(&json!(1)), properties.get("step"));
+    assert!(!properties.contains_key("val"));
+
+    // Missing migrations don't modify the properties
+    let mut properties = PropertyMap::new();
+    properties.insert("val".to_string(), json!(5));
+    assert_eq!(
+        Err(MigrationError::MissingMigration("counter".to_string(), 2)),
+        migrations.migrate("counter", &mut properties, 0, 3)
+    );
+    assert!(properties.contains_key("val"));
+    assert_eq!(
+        Err(MigrationError::Downgrade("counter".to_string(), 2, 1)),
+        migrations.migrate("counter", &mut properties, 2, 1)
+    );
+    assert!(migrations.migrate("counter", &mut properties, 1, 1).is_ok());
+}
+
+#[test]
+fn migrate_flow_test() {
+    let migrations = create_migrations();
+    let entity_type = EntityType::new(
+        EntityTypeName::new("counter"),
+        "",
+        "",
+        Vec::new(),
+        vec![PropertyType::new("value", DataType::Number)],
+        Vec::new(),
+    )
+    .with_version(2);
+    let relation_type = RelationType::new("counter", RelationTypeName::new("connector"), "counter", "", "", Vec::new(), Vec::new(), Vec::new()).with_version(1);
+
+    let wrapper = EntityInstance::new_without_properties(EntityTypeName::new("generic_flow"), Uuid::new_v4());
+    let mut properties = PropertyMap::new();
+    properties.insert("val".to_string(), json!(5));
+    let counter = EntityInstance::new(EntityTypeName::new("counter"), Uuid::new_v4(), properties);
+    let mut flow = Flow::from(wrapper.clone());
+    flow.entity_instances.push(counter.clone());
+    flow.relation_instances
+        .push(RelationInstance::new_without_properties(wrapper.id, RelationTypeName::new("connector"), counter.id));
+
+    // Types without a recorded version are current
+    let mut current_flow = flow.clone();
+    assert!(migrations
+        .migrate_flow(&mut current_flow, std::slice::from_ref(&entity_type), std::slice::from_ref(&relation_type))
+        .is_ok());
+    assert_eq!(Some(2), current_flow.get_type_version("counter"));
+    assert_eq!(Some(1), current_flow.get_type_version("connector"));
+    assert!(current_flow.entity_instances[1].properties.contains_key("val"));
+
+    flow.type_versions.insert("counter".to_string(), 0);
+    flow.type_versions.insert("connector".to_string(), 0);
+
+    // The relation type has no migration
+    assert_eq!(
+        Err(MigrationError::MissingMigration("connector".to_string(), 0)),
+        migrations.migrate_flow(&mut flow, std::slice::from_ref(&entity_type), std::slice::from_ref(&relation_type))
+    );
+    assert_eq!(Some(0), flow.get_type_version("counter"));
+    assert!(flow.entity_instances[1].properties.contains_key("val"));
+
+    assert!(migrations.migrate_flow(&mut flow, std::slice::from_ref(&entity_type), &[]).is_ok());
+    assert_eq!(Some(2), flow.get_type_version("counter"));
+    assert_eq!(json!(5), flow.entity_instances[1].properties["value"]);
+    assert_eq!(json!(1), flow.entity_instances[1].properties["step"]);
+
+    // Migrating again is a no-op
+    assert!(migrations.migrate_flow(&mut flow, &[entity_type], &[]).is_ok());
+    assert_eq!(2, flow.entity_instances[1].properties.len());
+
+    let flow: Flow = serde_json::from_value(serde_json::to_value(&flow).unwrap()).unwrap();
+    assert_eq!(Some(2), flow.get_type_version("counter"));
+}
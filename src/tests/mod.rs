@@ -1,21 +1,117 @@
+mod category_path_test;
 mod data_type_test;
 mod socket_type_test;
+mod validation_test;
+mod version_test;
 
+mod behaviour_state_machine_test;
+mod behaviour_type_test;
 mod component_test;
+mod composed_type_test;
+mod connector_definition_test;
 mod entity_type_test;
 mod property_type_test;
+mod relation_instance_key_test;
 mod relation_type_test;
+mod schema_exporter_test;
+mod type_name_test;
+mod type_system_test;
+mod well_known_extension_test;
 
+#[cfg(feature = "arrow")]
+mod arrow_export_test;
 mod entity_instance_test;
+mod entity_replacement_test;
+mod external_key_test;
 mod relation_instance_test;
+mod relation_instance_type_id_test;
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary_model_test;
+#[cfg(feature = "cbor")]
+mod cbor_test;
+mod content_hash_test;
+mod float_policy_test;
+mod flow_checkpoint_test;
+#[cfg(feature = "graph-db")]
+mod flow_conversion_test;
+mod flow_expectation_test;
+mod flow_loading_test;
+mod flow_template_test;
 mod flow_test;
+mod flow_type_test;
+mod graph_constraint_test;
+mod graph_identifier_test;
+mod graph_partition_test;
+#[cfg(all(feature = "graphql", feature = "futures"))]
+mod graphql_test;
+mod id_generator_test;
+mod inner_flow_test;
+#[cfg(feature = "graph-db")]
+mod instance_conversion_test;
+mod instance_event_test;
+mod instance_group_test;
+mod instance_handle_test;
+mod instance_labels_test;
+mod migration_test;
+mod observer_guard_test;
+mod prioritized_observers_test;
+mod projection_test;
 
+mod composition_event_test;
+mod factory_registry_test;
 mod reactive_entity_instance_test;
+mod reactive_instance_test;
+mod reactive_property_container_test;
 mod reactive_property_instance_test;
 mod reactive_relation_instance_test;
 
 mod reactive_flow_test;
+mod relation_rewiring_test;
+
+#[cfg(feature = "rayon")]
+mod parallel_tick_test;
+mod propagation_audit_test;
+mod propagation_suspension_test;
+mod propagation_tracer_test;
+mod property_aggregation_test;
+mod property_declaration_test;
+mod property_dependency_graph_test;
+mod property_diff_test;
+mod property_expression_test;
+mod property_flags_test;
+mod property_history_test;
+#[cfg(feature = "jsonpatch")]
+mod property_json_patch_test;
+#[cfg(feature = "jsonpath")]
+mod property_jsonpath_test;
+#[cfg(feature = "preserve_order")]
+mod property_map_test;
+#[cfg(feature = "derive")]
+mod property_mapping_test;
+#[cfg(feature = "metrics")]
+mod property_metrics_test;
+mod property_nested_test;
+mod property_object_test;
+mod property_observer_test;
+mod property_router_test;
+mod property_statistics_test;
+#[cfg(feature = "time")]
+mod property_time_test;
+mod property_transaction_test;
+mod property_validator_test;
+#[cfg(feature = "futures")]
+mod property_value_stream_test;
+#[cfg(feature = "tokio")]
+mod property_value_watch_test;
+
+mod test_utils_test;
+mod tick_profiler_test;
+mod topic_mapping_test;
+mod type_enforcement_test;
+
+mod behaviour_dependency_graph_test;
+mod prelude_test;
 
 #[tarpaulin::skip]
 pub mod utils;
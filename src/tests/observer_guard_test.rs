@@ -0,0 +1,94 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use serde_json::json;
+use serde_json::Value;
+
+use crate::tests::utils::{create_random_entity_instance, create_random_relation_instance_with_properties};
+use crate::{InstanceEvent, ObserverGuard, PropertyInstanceSetter};
+
+fn counter() -> (Arc<AtomicUsize>, Arc<AtomicUsize>) {
+    let count = Arc::new(AtomicUsize::new(0));
+    (count.clone(), count)
+}
+
+#[test]
+fn observer_guard_property_test() {
+    let entity_instance = Arc::new(create_random_entity_instance("x"));
+    let (count, c) = counter();
+    let guard = entity_instance.properties.get("x").unwrap().observe_guarded(move |_: &Value| {
+        c.fetch_add(1, Ordering::SeqCst);
+    });
+    entity_instance.set("x", json!(1));
+    assert_eq!(1, count.load(Ordering::SeqCst));
+    drop(guard);
+    entity_instance.set("x", json!(2));
+    assert_eq!(1, count.load(Ordering::SeqCst));
+}
+
+#[test]
+fn observer_guard_entity_instance_test() {
+    let entity_instance = Arc::new(create_random_entity_instance("x"));
+    let (count, c) = counter();
+    let guard = entity_instance.observe_all_guarded(move |_, _| {
+        c.fetch_add(1, Ordering::SeqCst);
+    });
+    let (events, e) = counter();
+    let event_guard = entity_instance.observe_events_guarded(move |event| {
+        if matches!(event, InstanceEvent::PropertyChanged { .. }) {
+            e.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+    entity_instance.set("x", json!(1));
+    assert_eq!(1, count.load(Ordering::SeqCst));
+    assert_eq!(1, events.load(Ordering::SeqCst));
+
+    guard.disconnect();
+    entity_instance.set("x", json!(2));
+    assert_eq!(1, count.load(Ordering::SeqCst));
+    assert_eq!(2, events.load(Ordering::SeqCst));
+
+    // Forgetting the guard keeps the observer connected
+    let handle_id = event_guard.forget();
+    entity_instance.set("x", json!(3));
+    assert_eq!(3, events.load(Ordering::SeqCst));
+    entity_instance.remove_event_observer(handle_id);
+    entity_instance.set("x", json!(4));
+    assert_eq!(3, events.load(Ordering::SeqCst));
+}
+
+#[test]
+fn observer_guard_relation_instance_test() {
+    let outbound = Arc::new(create_random_entity_instance("x"));
+    let inbound = Arc::new(create_random_entity_instance("y"));
+    let relation_instance = Arc::new(create_random_relation_instance_with_properties(outbound, inbound, "z".to_string()));
+    let (count, c) = counter();
+    let guard = relation_instance.observe_all_guarded(move |_, _| {
+        c.fetch_add(1, Ordering::SeqCst);
+    });
+    relation_instance.set("z", json!(1));
+    assert_eq!(1, count.load(Ordering::SeqCst));
+
+    // The guard doesn't keep the relation instance alive and can be dropped on another thread
+    // The property is shared, e.g. with a replacing instance
+    let property_instance = relation_instance.properties.get("z").unwrap().share();
+    let stream = property_instance.stream.clone();
+    drop(relation_instance);
+    stream.read().unwrap().send(&json!(2));
+    assert_eq!(2, count.load(Ordering::SeqCst));
+    std::thread::spawn(move || drop(guard)).join().unwrap();
+    // The observer is disconnected from the stream, which outlives the relation instance
+    stream.read().unwrap().send(&json!(3));
+    assert_eq!(2, count.load(Ordering::SeqCst));
+}
+
+#[test]
+fn observer_guard_custom_test() {
+    let (count, c) = counter();
+    let guard = ObserverGuard::new(42, move |handle_id| {
+        c.fetch_add(handle_id as usize, Ordering::SeqCst);
+    });
+    assert_eq!(42, guard.handle_id());
+    drop(guard);
+    assert_eq!(42, count.load(Ordering::SeqCst));
+}
@@ -0,0 +1,85 @@
+use std::sync::{Arc, Mutex};
+
+use serde_json::{json, Value};
+
+use crate::tests::utils::create_random_entity_instance;
+use crate::tests::utils::create_random_relation_instance_with_properties;
+use crate::{tick_entity_instances_parallel, PropertyInstanceSetter, ReactiveFlow, ReactivePropertyContainer};
+
+#[test]
+fn reactive_entity_instance_tick_parallel_test() {
+    let instance = create_random_entity_instance("b");
+    instance.add_property("a", json!(0));
+    instance.add_property("c", json!(0));
+    instance.add_property("d", json!(0));
+    instance.set_tick_priority("c", 10);
+    instance.set_tick_priority("d", 10);
+    instance.set_tick_priority("a", -1);
+    let ticked = Arc::new(Mutex::new(Vec::new()));
+    for property_name in ["a", "b", "c", "d"] {
+        let ticked = ticked.clone();
+        instance
+            .properties
+            .get(property_name)
+            .unwrap()
+            .stream
+            .read()
+            .unwrap()
+            .observe(move |_: &Value| ticked.lock().unwrap().push(property_name));
+    }
+    instance.set("b", json!(1));
+    ticked.lock().unwrap().clear();
+    assert!(instance.is_dirty());
+    instance.tick_parallel();
+    assert!(!instance.is_dirty());
+    let ticked = ticked.lock().unwrap();
+    assert_eq!(4, ticked.len());
+    // Properties with the same priority are ticked in no particular order
+    let mut first_group = ticked[0..2].to_vec();
+    first_group.sort();
+    assert_eq!(vec!["c", "d"], first_group);
+    assert_eq!(vec!["b", "a"], ticked[2..4].to_vec());
+}
+
+#[test]
+fn tick_entity_instances_parallel_test() {
+    let entity_instances: Vec<_> = (0..100).map(|_| Arc::new(create_random_entity_instance("x"))).collect();
+    let ticks = Arc::new(Mutex::new(0));
+    for entity_instance in entity_instances.iter() {
+        let ticks = ticks.clone();
+        entity_instance
+            .properties
+            .get("x")
+            .unwrap()
+            .stream
+            .read()
+            .unwrap()
+            .observe(move |_: &Value| *ticks.lock().unwrap() += 1);
+        entity_instance.set("x", json!(1));
+    }
+    *ticks.lock().unwrap() = 0;
+    tick_entity_instances_parallel(&entity_instances);
+    assert_eq!(100, *ticks.lock().unwrap());
+    assert!(entity_instances.iter().all(|entity_instance| !entity_instance.is_dirty()));
+}
+
+#[test]
+fn reactive_flow_tick_parallel_test() {
+    let wrapper_entity_instance = Arc::new(create_random_entity_instance("x"));
+    let reactive_flow = ReactiveFlow::new(wrapper_entity_instance.clone());
+    let inbound = Arc::new(create_random_entity_instance("x"));
+    reactive_flow.add_entity(inbound.clone());
+    let relation_instance = Arc::new(create_random_relation_instance_with_properties(wrapper_entity_instance.clone(), inbound, "x".to_string()));
+    reactive_flow.add_relation(relation_instance.clone());
+    let ticks = Arc::new(Mutex::new(0));
+    for property_instance in [
+        wrapper_entity_instance.properties.get("x").unwrap(),
+        relation_instance.properties.get("x").unwrap(),
+    ] {
+        let ticks = ticks.clone();
+        property_instance.stream.read().unwrap().observe(move |_: &Value| *ticks.lock().unwrap() += 1);
+    }
+    reactive_flow.tick_parallel();
+    // The relation instances are ticked as well
+    assert_eq!(2, *ticks.lock().unwrap());
+}
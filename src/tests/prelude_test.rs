@@ -0,0 +1,42 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::prelude::*;
+
+#[test]
+fn prelude_test() {
+    let entity_instance = Arc::new(ReactiveEntityInstance::from(
+        EntityInstanceBuilder::new()
+            .type_name(EntityTypeName::new("number"))
+            .property("value", json!(1))
+            .build(),
+    ));
+    entity_instance.set("value", json!(2));
+    assert_eq!(Some(2), entity_instance.as_u64("value"));
+    let relation_instance = ReactiveRelationInstance::builder(entity_instance.clone(), RelationTypeName::new("connector"), entity_instance.clone())
+        .build()
+        .unwrap();
+    assert_eq!(entity_instance.id, RelationInstanceKey::from(&relation_instance).outbound_id);
+}
+
+#[test]
+fn api_modules_test() {
+    let _: crate::types::EntityType = crate::types::EntityType::new(EntityTypeName::new("number"), "", "", Vec::new(), Vec::new(), Vec::new());
+    let _: crate::instances::EntityInstance = crate::instances::EntityInstanceBuilder::new().type_name(EntityTypeName::new("number")).build();
+    let _: crate::reactive::Revision = crate::reactive::Revision::default();
+    let _: crate::flow::ProjectionTable = crate::flow::Projection::new().project(&[], &[]);
+    let _: crate::types::CategoryPath = crate::types::CategoryPath::default();
+    let _: crate::instances::PropertyDiff = crate::instances::PropertyDiff::default();
+    let instant: crate::reactive::Instant = crate::reactive::Instant::now();
+    let _: crate::flow::FlowCheckpoint = crate::flow::FlowCheckpoint {
+        instant,
+        entities_added: 0,
+        relations_added: 0,
+        entities_removed: 0,
+        relations_removed: 0,
+        entity_tags: HashMap::new(),
+        relation_tags: HashMap::new(),
+    };
+}
@@ -0,0 +1,98 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+
+use serde_json::json;
+use serde_json::Value;
+
+use crate::tests::utils::create_random_entity_instance;
+use crate::{generate_id, PropertyInstanceSetter};
+
+#[test]
+fn prioritized_observers_order_test() {
+    let entity_instance = Arc::new(create_random_entity_instance("x"));
+    let calls = Arc::new(RwLock::new(Vec::new()));
+    let property_instance = entity_instance.properties.get("x").unwrap();
+    let mut handle_ids = Vec::new();
+    for (name, priority) in [("propagator", 0), ("validator", 10), ("clamper", 5), ("logger", 0), ("checker", 10)] {
+        let calls = calls.clone();
+        handle_ids.push(property_instance.observe_with_priority(priority, move |_: &Value| calls.write().unwrap().push(name)));
+    }
+    assert_eq!(5, property_instance.prioritized_observers.len());
+    drop(property_instance);
+
+    entity_instance.set("x", json!(1));
+    assert_eq!(vec!["validator", "checker", "clamper", "propagator", "logger"], *calls.read().unwrap());
+
+    calls.write().unwrap().clear();
+    let property_instance = entity_instance.properties.get("x").unwrap();
+    property_instance.remove_observer(handle_ids[1]);
+    drop(property_instance);
+    entity_instance.set("x", json!(2));
+    assert_eq!(vec!["checker", "clamper", "propagator", "logger"], *calls.read().unwrap());
+
+    calls.write().unwrap().clear();
+    entity_instance.properties.get("x").unwrap().destroy();
+    entity_instance.set("x", json!(3));
+    assert!(calls.read().unwrap().is_empty());
+    assert!(entity_instance.properties.get("x").unwrap().prioritized_observers.is_empty());
+}
+
+#[test]
+fn prioritized_observers_remove_last_test() {
+    let entity_instance = create_random_entity_instance("x");
+    let calls = Arc::new(RwLock::new(Vec::new()));
+    let c = calls.clone();
+    let property_instance = entity_instance.properties.get("x").unwrap();
+    let handle_id = property_instance.observe_with_priority(1, move |value: &Value| c.write().unwrap().push(value.clone()));
+    property_instance.set(json!(1));
+    property_instance.remove_observer(handle_id);
+    property_instance.set(json!(2));
+    assert_eq!(vec![json!(1)], *calls.read().unwrap());
+}
+
+#[test]
+fn prioritized_observers_default_priority_test() {
+    let entity_instance = create_random_entity_instance("x");
+    let calls = Arc::new(RwLock::new(Vec::new()));
+    let property_instance = entity_instance.properties.get("x").unwrap();
+    for (name, priority) in [("first", None), ("late", Some(-1)), ("second", Some(0)), ("early", Some(1)), ("third", None)] {
+        let calls = calls.clone();
+        let observer = move |_: &Value| calls.write().unwrap().push(name);
+        match priority {
+            Some(priority) => {
+                property_instance.observe_with_priority(priority, observer);
+            }
+            None => property_instance.observe_with_handle(observer, generate_id().as_u128()),
+        }
+    }
+    property_instance.set(json!(1));
+    assert_eq!(vec!["early", "first", "second", "third", "late"], *calls.read().unwrap());
+}
+
+#[test]
+fn prioritized_observers_observe_while_dispatching_test() {
+    let entity_instance = Arc::new(create_random_entity_instance("x"));
+    let calls = Arc::new(RwLock::new(Vec::new()));
+    let registered = Arc::new(AtomicBool::new(false));
+    {
+        let calls = calls.clone();
+        let entity_instance_2 = Arc::downgrade(&entity_instance);
+        let property_instance = entity_instance.properties.get("x").unwrap();
+        property_instance.observe_with_priority(0, move |_: &Value| {
+            calls.write().unwrap().push("outer");
+            // The observer registers another observer on the same property while the value is dispatched
+            if !registered.swap(true, Ordering::SeqCst) {
+                let calls = calls.clone();
+                let entity_instance = entity_instance_2.upgrade().unwrap();
+                let property_instance = entity_instance.properties.get("x").unwrap();
+                property_instance.observe_with_priority(0, move |_: &Value| calls.write().unwrap().push("inner"));
+            }
+        });
+    }
+    entity_instance.set("x", json!(1));
+    // The observer which has been registered while dispatching is invoked from the next value on
+    assert_eq!(vec!["outer"], *calls.read().unwrap());
+    entity_instance.set("x", json!(2));
+    assert_eq!(vec!["outer", "outer", "inner"], *calls.read().unwrap());
+    assert_eq!(2, entity_instance.properties.get("x").unwrap().prioritized_observers.len());
+}
@@ -0,0 +1,129 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::tests::utils::create_random_entity_instance::create_random_entity_instance_with_type;
+#[cfg(feature = "arrow")]
+use crate::ReactivePropertyContainer;
+use crate::{
+    ColumnSource, Projection, ProjectionTable, PropertyInstanceSetter, ReactiveEntityInstance, ReactiveFlow, ReactiveRelationInstance, RelationTypeName,
+};
+
+fn create_player<S: Into<String>>(name: S) -> Arc<ReactiveEntityInstance> {
+    let player = create_random_entity_instance_with_type("player", "name");
+    player.set("name", json!(name.into()));
+    Arc::new(player)
+}
+
+#[test]
+fn projection_test() {
+    let alice = create_player("alice");
+    let bob = create_player("bob");
+    let carol = create_player("carol");
+    let camera = Arc::new(create_random_entity_instance_with_type("camera", "name"));
+    let follows = Arc::new(
+        ReactiveRelationInstance::builder(alice.clone(), RelationTypeName::new("follows"), bob.clone())
+            .property("since", json!(2020))
+            .build()
+            .unwrap(),
+    );
+
+    let projection = Projection::new()
+        .for_type("player")
+        .join("follows")
+        .property("name")
+        .relation_property("since")
+        .joined_property("name")
+        .column("missing", ColumnSource::Property("missing".to_string()));
+    let table = projection.project(&[alice.clone(), bob, carol, camera], &[follows]);
+    assert_eq!(vec!["name", "relation.since", "joined.name", "missing"], table.columns);
+    assert_eq!(
+        vec![
+            vec![json!("alice"), json!(2020), json!("bob"), json!(null)],
+            vec![json!("bob"), json!(null), json!(null), json!(null)],
+            vec![json!("carol"), json!(null), json!(null), json!(null)],
+        ],
+        table.rows
+    );
+    assert_eq!(Some(vec![json!("alice"), json!("bob"), json!("carol")]), table.get_column("name"));
+    assert_eq!(json!("bob"), table.to_records()[0]["joined.name"]);
+
+    let flow = ReactiveFlow::new(alice.clone());
+    let table = flow.project(&Projection::new().id().type_name());
+    assert_eq!(vec![vec![json!(alice.id.to_string()), json!("player")]], table.rows);
+}
+
+#[test]
+fn projection_relations_test() {
+    let alice = create_player("alice");
+    let bob = create_player("bob");
+    let camera = Arc::new(create_random_entity_instance_with_type("camera", "name"));
+    let follows = Arc::new(
+        ReactiveRelationInstance::builder(alice.clone(), RelationTypeName::new("follows"), bob.clone())
+            .property("since", json!(2020))
+            .build()
+            .unwrap(),
+    );
+    let looks_at = Arc::new(
+        ReactiveRelationInstance::builder(bob.clone(), RelationTypeName::new("looks_at"), camera.clone())
+            .build()
+            .unwrap(),
+    );
+    let relation_instances = vec![follows.clone(), looks_at.clone()];
+
+    let projection = Projection::new().property("name").relation_type_name().relation_property("since").joined_id();
+    let table = projection.project_relations(&relation_instances);
+    assert_eq!(vec!["name", "relation.type_name", "relation.since", "joined.id"], table.columns);
+    assert_eq!(
+        vec![
+            vec![json!("alice"), json!("follows"), json!(2020), json!(bob.id.to_string())],
+            vec![json!("bob"), json!("looks_at"), json!(null), json!(camera.id.to_string())],
+        ],
+        table.rows
+    );
+
+    // Only relation instances of the joined relation type are projected
+    let table = projection.clone().join("looks_at").project_relations(&relation_instances);
+    assert_eq!(Some(vec![json!("bob")]), table.get_column("name"));
+    let table = projection.for_type("camera").project_relations(&relation_instances);
+    assert!(table.rows.is_empty());
+
+    let flow = ReactiveFlow::new(alice.clone());
+    flow.add_entity(bob.clone());
+    flow.add_entity(camera.clone());
+    flow.add_relation(follows);
+    flow.add_relation(looks_at);
+    let table = flow.project_relations(&Projection::new().relation_type_name());
+    assert_eq!(2, table.rows.len());
+}
+
+#[test]
+fn projection_table_missing_values_test() {
+    let table: ProjectionTable = serde_json::from_value(json!({ "columns": ["a", "b"], "rows": [[1, 2], [3]] })).unwrap();
+    assert_eq!(Some(vec![json!(2), json!(null)]), table.get_column("b"));
+}
+
+#[cfg(feature = "csv")]
+#[test]
+fn projection_csv_test() {
+    let alice = create_player("alice, the first");
+    let table = Projection::new().property("name").property("missing").project(&[alice], &[]);
+    assert_eq!("name,missing\n\"alice, the first\",\n", table.to_csv().unwrap());
+}
+
+#[cfg(feature = "arrow")]
+#[test]
+fn projection_arrow_test() {
+    let alice = create_player("alice");
+    alice.add_property("score", json!(3));
+    let bob = create_player("bob");
+    let table = Projection::new().property("name").property("score").project(&[alice, bob], &[]);
+    let record_batch = table.to_arrow().unwrap();
+    assert_eq!(2, record_batch.num_rows());
+    assert_eq!(&arrow_schema::DataType::Utf8, record_batch.schema().field(0).data_type());
+    assert_eq!(&arrow_schema::DataType::Int64, record_batch.schema().field(1).data_type());
+    assert_eq!(1, record_batch.column(1).null_count());
+
+    let table: ProjectionTable = serde_json::from_value(json!({ "columns": ["a", "b"], "rows": [[1, 2], [3]] })).unwrap();
+    assert_eq!(1, table.to_arrow().unwrap().column(1).null_count());
+}
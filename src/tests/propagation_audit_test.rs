@@ -0,0 +1,72 @@
+use std::panic;
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::tests::utils::create_random_entity_instance;
+use crate::tests::utils::PROPAGATION_TRACER_LOCK;
+use crate::DeterminismViolation;
+use crate::PropagationAudit;
+use crate::PropagationTracer;
+use crate::PropertyInstanceSetter;
+use crate::{clear_propagation_tracer, get_propagation_tracer, set_propagation_tracer, ReactivePropertyContainer};
+
+#[test]
+fn propagation_audit_test() {
+    let _lock = PROPAGATION_TRACER_LOCK.lock().unwrap();
+    let entity_instance = create_random_entity_instance("a");
+    entity_instance.add_property("b", json!(0));
+    let ids = vec![entity_instance.id];
+
+    let mut recording_1 = PropagationAudit::record(|| entity_instance.tick());
+    let mut recording_2 = PropagationAudit::record(|| entity_instance.tick());
+    recording_1.retain_instances(&ids);
+    recording_2.retain_instances(&ids);
+    assert_eq!(2, recording_1.events.len());
+    assert_eq!(1, recording_1.get_instances().len());
+    assert!(recording_1.is_deterministic_with(&recording_2));
+
+    let mut recording_3 = PropagationAudit::record(|| {
+        entity_instance.set("b", json!(1));
+        entity_instance.set("a", json!(1));
+    });
+    recording_3.retain_instances(&ids);
+    let violations = recording_1.compare(&recording_3);
+    assert_eq!(1, violations.len());
+    match violations.first().unwrap() {
+        DeterminismViolation::OrderMismatch { index, expected, actual } => {
+            assert_eq!(0, *index);
+            assert_eq!("a", expected.property_name);
+            assert_eq!("b", actual.property_name);
+        }
+        violation => panic!("Unexpected violation {:?}", violation),
+    }
+
+    let mut recording_4 = PropagationAudit::record(|| entity_instance.set("a", json!(2)));
+    recording_4.retain_instances(&ids);
+    match recording_1.compare(&recording_4).first().unwrap() {
+        DeterminismViolation::DifferentEvents { index, expected, actual } => {
+            assert_eq!(1, *index);
+            assert_eq!("b", expected.as_ref().unwrap().property_name);
+            assert!(actual.is_none());
+        }
+        violation => panic!("Unexpected violation {:?}", violation),
+    }
+}
+
+#[test]
+fn propagation_audit_restores_tracer_test() {
+    let _lock = PROPAGATION_TRACER_LOCK.lock().unwrap();
+    let tracer: PropagationTracer = Arc::new(|_| {});
+    set_propagation_tracer(tracer.clone());
+    let audit = PropagationAudit::start();
+    assert!(!Arc::ptr_eq(&tracer, &get_propagation_tracer().unwrap()));
+    drop(audit);
+    assert!(Arc::ptr_eq(&tracer, &get_propagation_tracer().unwrap()));
+
+    // The tracer is restored if the audited run panics
+    let result = panic::catch_unwind(|| PropagationAudit::record(|| panic!("The audited run panics")));
+    assert!(result.is_err());
+    assert!(Arc::ptr_eq(&tracer, &get_propagation_tracer().unwrap()));
+    clear_propagation_tracer();
+}
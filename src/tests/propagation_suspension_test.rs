@@ -0,0 +1,147 @@
+use std::sync::{Arc, RwLock};
+
+use serde_json::json;
+use serde_json::Value;
+
+use crate::tests::utils::{create_random_entity_instance, create_random_entity_instance_with_type, create_random_relation_instance_with_properties, r_string};
+use crate::{is_propagation_suspended, PropertyInstanceGetter, PropertyInstanceSetter, ReactiveEntityInstance, ReactiveFlow, SuspensionMode};
+
+fn record(entity_instance: &ReactiveEntityInstance, property_name: &str) -> Arc<RwLock<Vec<Value>>> {
+    let received = Arc::new(RwLock::new(Vec::new()));
+    let r = received.clone();
+    entity_instance
+        .properties
+        .get(property_name)
+        .unwrap()
+        .stream
+        .read()
+        .unwrap()
+        .observe(move |value: &Value| {
+            r.write().unwrap().push(value.clone());
+        });
+    received
+}
+
+#[test]
+fn suspend_entity_instance_propagation_test() {
+    let entity_instance = create_random_entity_instance("value");
+    let received = record(&entity_instance, "value");
+    {
+        let _guard = entity_instance.suspend_propagation(SuspensionMode::Consolidate);
+        assert!(is_propagation_suspended(entity_instance.id));
+        {
+            let _nested = entity_instance.suspend_propagation(SuspensionMode::Consolidate);
+            entity_instance.set("value", json!(1));
+        }
+        entity_instance.set("value", json!(2));
+        entity_instance.set("value", json!(3));
+        // The values are set but not propagated
+        assert_eq!(json!(3), entity_instance.get("value").unwrap());
+        assert!(received.read().unwrap().is_empty());
+    }
+    assert!(!is_propagation_suspended(entity_instance.id));
+    assert_eq!(vec![json!(3)], *received.read().unwrap());
+
+    {
+        let _guard = entity_instance.suspend_propagation(SuspensionMode::Drop);
+        entity_instance.set("value", json!(4));
+    }
+    assert_eq!(json!(4), entity_instance.get("value").unwrap());
+    assert_eq!(1, received.read().unwrap().len());
+
+    entity_instance.set("value", json!(5));
+    assert_eq!(vec![json!(3), json!(5)], *received.read().unwrap());
+
+    // The innermost suspension decides whether a value is propagated on resume
+    {
+        let _guard = entity_instance.suspend_propagation(SuspensionMode::Consolidate);
+        entity_instance.set("value", json!(6));
+        {
+            let _nested = entity_instance.suspend_propagation(SuspensionMode::Drop);
+            entity_instance.set("value", json!(7));
+        }
+    }
+    assert_eq!(json!(7), entity_instance.get("value").unwrap());
+    assert_eq!(2, received.read().unwrap().len());
+    {
+        let _guard = entity_instance.suspend_propagation(SuspensionMode::Drop);
+        {
+            let _nested = entity_instance.suspend_propagation(SuspensionMode::Consolidate);
+            entity_instance.set("value", json!(8));
+        }
+        assert_eq!(2, received.read().unwrap().len());
+    }
+    assert_eq!(vec![json!(3), json!(5), json!(8)], *received.read().unwrap());
+}
+
+#[test]
+fn resumed_propagation_is_profiled_test() {
+    let entity_instance = create_random_entity_instance("value");
+    let guard = entity_instance.suspend_propagation(SuspensionMode::Consolidate);
+    entity_instance.set("value", json!(1));
+    entity_instance.set("value", json!(2));
+    let report = crate::profile(|| drop(guard));
+    assert_eq!(1, report.get_property(entity_instance.id, "value").unwrap().calls);
+}
+
+#[test]
+fn suspend_relation_instance_propagation_test() {
+    let property_name = r_string();
+    let outbound = Arc::new(create_random_entity_instance("value"));
+    let relation_instance = create_random_relation_instance_with_properties(outbound.clone(), outbound.clone(), property_name.clone());
+    assert!(relation_instance
+        .properties
+        .iter()
+        .all(|property_instance| property_instance.id == relation_instance.id));
+    let received = Arc::new(RwLock::new(Vec::new()));
+    let r = received.clone();
+    relation_instance
+        .properties
+        .get(property_name.as_str())
+        .unwrap()
+        .stream
+        .read()
+        .unwrap()
+        .observe(move |value: &Value| {
+            r.write().unwrap().push(value.clone());
+        });
+    {
+        let _guard = relation_instance.suspend_propagation(SuspensionMode::Consolidate);
+        assert!(is_propagation_suspended(relation_instance.id));
+        // The outbound entity instance is not suspended
+        assert!(!is_propagation_suspended(outbound.id));
+        relation_instance.set(property_name.clone(), json!(1));
+        relation_instance.set(property_name.clone(), json!(2));
+        assert!(received.read().unwrap().is_empty());
+    }
+    assert_eq!(vec![json!(2)], *received.read().unwrap());
+}
+
+#[test]
+fn suspend_flow_propagation_test() {
+    let property_name = r_string();
+    let wrapper = Arc::new(create_random_entity_instance_with_type("generic_flow", "test"));
+    let reactive_flow = ReactiveFlow::new(wrapper.clone());
+    let entity_instance = Arc::new(create_random_entity_instance("value"));
+    let other = create_random_entity_instance("value");
+    reactive_flow.add_entity(entity_instance.clone());
+    let relation_instance = Arc::new(create_random_relation_instance_with_properties(
+        wrapper.clone(),
+        entity_instance.clone(),
+        property_name.clone(),
+    ));
+    reactive_flow.add_relation(relation_instance.clone());
+    let received = record(&entity_instance, "value");
+    let other_received = record(&other, "value");
+
+    let guard = reactive_flow.suspend_propagation(SuspensionMode::Consolidate);
+    entity_instance.set("value", json!(1));
+    relation_instance.set(property_name.clone(), json!(1));
+    other.set("value", json!(1));
+    assert!(received.read().unwrap().is_empty());
+    assert!(is_propagation_suspended(relation_instance.id));
+    // Instances outside of the flow are not suspended
+    assert_eq!(vec![json!(1)], *other_received.read().unwrap());
+    drop(guard);
+    assert_eq!(vec![json!(1)], *received.read().unwrap());
+}
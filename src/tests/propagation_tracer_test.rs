@@ -0,0 +1,45 @@
+use std::sync::{Arc, RwLock};
+
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::tests::utils::r_string;
+use crate::tests::utils::PROPAGATION_TRACER_LOCK;
+use crate::{clear_propagation_tracer, set_propagation_tracer, PropagationTrace, ReactivePropertyInstance};
+
+#[test]
+fn propagation_tracer_test() {
+    let _lock = PROPAGATION_TRACER_LOCK.lock().unwrap();
+    let id = Uuid::new_v4();
+    let property_name = r_string();
+    let property_instance = ReactivePropertyInstance::new(id, property_name.clone(), json!(0));
+
+    let traces: Arc<RwLock<Vec<PropagationTrace>>> = Arc::new(RwLock::new(Vec::new()));
+    {
+        let traces = traces.clone();
+        set_propagation_tracer(Arc::new(move |trace: &PropagationTrace| {
+            // Other tests may run concurrently
+            if trace.id == id {
+                traces.write().unwrap().push(trace.clone());
+            }
+        }));
+    }
+
+    property_instance.set(json!(1));
+    property_instance.set_no_propagate(json!(2));
+    property_instance.tick();
+
+    clear_propagation_tracer();
+    property_instance.set(json!(3));
+
+    let traces = traces.read().unwrap();
+    assert_eq!(3, traces.len());
+    assert_eq!(property_name, traces[0].property_name);
+    assert_eq!(0, traces[0].old_value);
+    assert_eq!(1, traces[0].new_value);
+    assert!(!traces[0].suppressed);
+    assert_eq!(1, traces[1].old_value);
+    assert_eq!(2, traces[1].new_value);
+    assert!(traces[1].suppressed);
+    assert_eq!(2, traces[2].new_value);
+}
@@ -0,0 +1,71 @@
+use std::sync::{Arc, RwLock};
+
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::tests::utils::r_string;
+use crate::ReactivePropertyInstance;
+
+#[test]
+fn property_aggregation_sum_over_test() {
+    let property_instance = ReactivePropertyInstance::new(Uuid::new_v4(), r_string(), json!(1));
+    let (sum, _guard) = property_instance.sum_over("sum", 3);
+    assert_eq!("sum", sum.name.as_ref());
+    assert_eq!(property_instance.id, sum.id);
+    assert_eq!(json!(1.0), sum.get());
+    property_instance.set(json!(2));
+    assert_eq!(json!(3.0), sum.get());
+    property_instance.set(json!(3));
+    assert_eq!(json!(6.0), sum.get());
+    property_instance.set(json!(4));
+    assert_eq!(json!(9.0), sum.get());
+    // Non-numeric values are ignored
+    property_instance.set(json!("x"));
+    assert_eq!(json!(9.0), sum.get());
+}
+
+#[test]
+fn property_aggregation_avg_over_test() {
+    let property_instance = ReactivePropertyInstance::new(Uuid::new_v4(), r_string(), json!("x"));
+    let (avg, _guard) = property_instance.avg_over("avg", 2);
+    assert_eq!(json!(null), avg.get());
+    property_instance.set(json!(2));
+    assert_eq!(json!(2.0), avg.get());
+    property_instance.set(json!(4));
+    assert_eq!(json!(3.0), avg.get());
+    property_instance.set(json!(8));
+    assert_eq!(json!(6.0), avg.get());
+}
+
+#[test]
+fn property_aggregation_min_max_test() {
+    let property_instance = ReactivePropertyInstance::new(Uuid::new_v4(), r_string(), json!(5));
+    let (min, _min_guard) = property_instance.min("min");
+    let (max, _max_guard) = property_instance.max("max");
+    let derived_value = Arc::new(RwLock::new(json!(null)));
+    let observed_value = derived_value.clone();
+    min.stream
+        .read()
+        .unwrap()
+        .observe(move |value| *observed_value.write().unwrap() = value.clone());
+    property_instance.set(json!(3));
+    property_instance.set(json!(7));
+    assert_eq!(json!(3.0), min.get());
+    assert_eq!(json!(7.0), max.get());
+    assert_eq!(json!(3.0), *derived_value.read().unwrap());
+    drop(max);
+    property_instance.set(json!(1));
+    assert_eq!(json!(1.0), min.get());
+}
+
+#[test]
+fn property_aggregation_guard_test() {
+    let property_instance = ReactivePropertyInstance::new(Uuid::new_v4(), r_string(), json!(1));
+    let (sum, guard) = property_instance.sum_over("sum", 3);
+    property_instance.set(json!(2));
+    assert_eq!(json!(3.0), sum.get());
+    // The derived property isn't updated after the guard has been dropped
+    drop(guard);
+    property_instance.set(json!(3));
+    assert_eq!(json!(3.0), sum.get());
+}
@@ -0,0 +1,31 @@
+use crate::{properties, DataType, EntityType, EntityTypeName, SocketType};
+
+properties!(
+    /// The properties of a counter.
+    CounterProperties {
+        TRIGGER: "trigger" => DataType::Bool, SocketType::Input;
+        RESULT: "result" => DataType::Number, SocketType::Output;
+        STEP: "step" => DataType::Number
+    }
+);
+
+#[test]
+fn property_declaration_test() {
+    assert_eq!("trigger", CounterProperties::TRIGGER);
+    assert_eq!("result", CounterProperties::RESULT);
+    assert_eq!(&["trigger", "result", "step"], CounterProperties::NAMES);
+    assert!(CounterProperties::contains(CounterProperties::STEP));
+    assert!(!CounterProperties::contains("value"));
+
+    let property_types = CounterProperties::property_types();
+    assert_eq!(3, property_types.len());
+    assert_eq!(CounterProperties::TRIGGER, property_types[0].name);
+    assert_eq!(DataType::Bool, property_types[0].data_type);
+    assert_eq!(SocketType::Input, property_types[0].socket_type);
+    assert_eq!(SocketType::Output, property_types[1].socket_type);
+    assert_eq!(DataType::Number, property_types[2].data_type);
+    assert_eq!(SocketType::None, property_types[2].socket_type);
+
+    let entity_type = EntityType::new(EntityTypeName::new("counter"), "", "", Vec::new(), CounterProperties::property_types(), Vec::new());
+    assert!(entity_type.has_own_property(CounterProperties::RESULT));
+}
@@ -0,0 +1,112 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use serde_json::json;
+use serde_json::Value;
+
+use crate::tests::utils::create_random_entity_instance;
+use crate::{
+    DataType, Extension, PropertyDependencyError, PropertyDependencyGraph, PropertyInstanceGetter, PropertyInstanceSetter, PropertyType,
+    ReactivePropertyContainer, DERIVED_FROM_EXTENSION,
+};
+
+fn product(values: &[Value]) -> Value {
+    json!(values.iter().map(|value| value.as_f64().unwrap_or(0.0)).product::<f64>())
+}
+
+#[test]
+fn property_dependency_graph_introspection_test() {
+    let mut graph = PropertyDependencyGraph::new();
+    graph
+        .add_derived_with("area", vec!["width".to_string(), "height".to_string()], product)
+        .unwrap();
+    graph.add_derived("volume", vec!["area".to_string(), "depth".to_string()]).unwrap();
+    assert_eq!(vec!["width".to_string(), "height".to_string()], graph.get_dependencies("area"));
+    assert_eq!(vec!["area".to_string(), "volume".to_string()], graph.get_dependents("width"));
+    assert!(graph.depends_on("volume", "height"));
+    assert!(!graph.depends_on("area", "depth"));
+    assert_eq!(4, graph.get_edges().len());
+    assert_eq!(
+        Err(PropertyDependencyError::Cycle("width".to_string())),
+        graph.add_derived("width", vec!["volume".to_string()])
+    );
+    assert_eq!(Err(PropertyDependencyError::Cycle("x".to_string())), graph.add_derived("x", vec!["x".to_string()]));
+
+    let mut derived = PropertyType::new("area", DataType::Number);
+    derived.extensions.push(Extension::new(DERIVED_FROM_EXTENSION, json!(["width", "height"])));
+    let mut label = PropertyType::new("label", DataType::String);
+    label.extensions.push(Extension::new(DERIVED_FROM_EXTENSION, json!("name")));
+    let graph = PropertyDependencyGraph::from_property_types(&[PropertyType::new("width", DataType::Number), derived, label]).unwrap();
+    assert_eq!(vec!["area".to_string(), "label".to_string()], graph.get_derived_property_names());
+    assert_eq!(vec!["name".to_string()], graph.get_dependencies("label"));
+}
+
+#[test]
+fn property_dependency_graph_propagation_test() {
+    let entity_instance = Arc::new(create_random_entity_instance("width"));
+    for name in ["height", "depth", "area", "volume", "summary"] {
+        entity_instance.add_property(name, json!(0));
+    }
+    entity_instance.set("depth", json!(2));
+    let mut graph = PropertyDependencyGraph::new();
+    graph
+        .add_derived_with("area", vec!["width".to_string(), "height".to_string()], product)
+        .unwrap();
+    graph
+        .add_derived_with("volume", vec!["area".to_string(), "depth".to_string()], product)
+        .unwrap();
+    graph.add_derived("summary", vec!["volume".to_string()]).unwrap();
+    let graph = Arc::new(graph);
+    graph.connect(&entity_instance);
+
+    let updates = Arc::new(AtomicUsize::new(0));
+    let u = updates.clone();
+    entity_instance
+        .properties
+        .get("volume")
+        .unwrap()
+        .stream
+        .read()
+        .unwrap()
+        .observe(move |_: &Value| {
+            u.fetch_add(1, Ordering::SeqCst);
+        });
+
+    entity_instance.set("width", json!(3));
+    entity_instance.set("height", json!(4));
+    assert_eq!(json!(12.0), entity_instance.get("area").unwrap());
+    assert_eq!(json!(24.0), entity_instance.get("volume").unwrap());
+    assert_eq!(2, updates.load(Ordering::SeqCst));
+    assert!(graph.is_stale("summary"));
+    graph.clear_stale("summary");
+    assert!(graph.get_stale().is_empty());
+
+    entity_instance.set("depth", json!(1));
+    assert_eq!(json!(12.0), entity_instance.get("volume").unwrap());
+    assert_eq!(vec!["summary".to_string()], graph.get_stale());
+
+    graph.disconnect(&entity_instance);
+    entity_instance.set("width", json!(1));
+    assert_eq!(json!(12.0), entity_instance.get("area").unwrap());
+}
+
+#[test]
+fn property_dependency_graph_panic_test() {
+    let entity_instance = create_random_entity_instance("width");
+    entity_instance.add_property("double", json!(0));
+    let mut graph = PropertyDependencyGraph::new();
+    graph
+        .add_derived_with("double", vec!["width".to_string()], |values: &[Value]| {
+            let width = values[0].as_i64().unwrap();
+            assert_ne!(13, width, "unlucky width");
+            json!(width * 2)
+        })
+        .unwrap();
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| graph.propagate(&entity_instance, "width", &json!(13))));
+    assert!(result.is_err());
+
+    // The panic doesn't leave the graph marked as propagating
+    graph.propagate(&entity_instance, "width", &json!(4));
+    assert_eq!(json!(8), entity_instance.get("double").unwrap());
+}
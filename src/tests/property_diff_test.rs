@@ -0,0 +1,56 @@
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{EntityInstance, EntityTypeName, PropertyDiff, PropertyInstanceGetter, PropertyMap, RelationInstance, RelationTypeName};
+
+fn properties(entries: &[(&str, serde_json::Value)]) -> PropertyMap {
+    entries.iter().map(|(name, value)| (name.to_string(), value.clone())).collect()
+}
+
+#[test]
+fn property_diff_test() {
+    let from = properties(&[("a", json!(1)), ("b", json!("x")), ("c", json!(true)), ("d", json!(null))]);
+    let to = properties(&[("a", json!(1)), ("b", json!("y")), ("e", json!([1]))]);
+    let diff = PropertyDiff::new(&from, &to);
+    assert_eq!(properties(&[("e", json!([1]))]), diff.added);
+    assert_eq!(vec!["c", "d"], diff.removed);
+    assert_eq!(properties(&[("b", json!("y"))]), diff.changed);
+    assert!(!diff.is_empty());
+    assert!(PropertyDiff::new(&to, &to).is_empty());
+
+    let json = serde_json::to_value(&diff).unwrap();
+    assert_eq!(diff, serde_json::from_value(json).unwrap());
+}
+
+#[test]
+fn entity_instance_diff_apply_test() {
+    let id = Uuid::new_v4();
+    let mut a = EntityInstance::new(
+        EntityTypeName::new("player"),
+        id,
+        properties(&[("name", json!("alice")), ("score", json!(1)), ("team", json!("red"))]),
+    );
+    let b = EntityInstance::new(
+        EntityTypeName::new("player"),
+        id,
+        properties(&[("name", json!("alice")), ("score", json!(2)), ("level", json!(3))]),
+    );
+    let diff = a.diff(&b);
+    a.apply(diff);
+    assert_eq!(b.properties, a.properties);
+    assert_eq!(json!(2), a.get("score").unwrap());
+    assert!(a.get("team").is_none());
+    assert!(a.diff(&b).is_empty());
+}
+
+#[test]
+fn relation_instance_diff_apply_test() {
+    let outbound_id = Uuid::new_v4();
+    let inbound_id = Uuid::new_v4();
+    let mut a = RelationInstance::new(outbound_id, RelationTypeName::new("follows"), inbound_id, properties(&[("weight", json!(1))]));
+    let b = RelationInstance::new(outbound_id, RelationTypeName::new("follows"), inbound_id, properties(&[("since", json!("2022"))]));
+    let diff = a.diff(&b);
+    assert_eq!(vec!["weight"], diff.removed);
+    a.apply(diff);
+    assert_eq!(b.properties, a.properties);
+}
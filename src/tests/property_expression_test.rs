@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use serde_json::json;
+use serde_json::Value;
+
+use crate::tests::utils::create_random_entity_instance;
+use crate::{
+    connect_computed_properties, DataType, Expression, ExpressionError, Extension, PropertyDependencyError, PropertyInstanceGetter, PropertyInstanceSetter,
+    PropertyType, ReactivePropertyContainer, EXPRESSION_EXTENSION, MAX_EXPRESSION_DEPTH,
+};
+
+fn computed(name: &str, expression: &str) -> PropertyType {
+    let mut property_type = PropertyType::new(name, DataType::Number);
+    property_type.extensions.push(Extension::new(EXPRESSION_EXTENSION, json!(expression)));
+    property_type
+}
+
+#[test]
+fn expression_parse_test() {
+    let expression = Expression::parse("(width + height) * 2 - min(width, 3)").unwrap();
+    assert_eq!(vec!["width".to_string(), "height".to_string()], expression.get_properties());
+
+    assert_eq!(Err(ExpressionError::UnexpectedEnd), Expression::parse("a +"));
+    assert_eq!(Err(ExpressionError::UnexpectedCharacter('#', 2)), Expression::parse("a # b"));
+    assert_eq!(Err(ExpressionError::UnexpectedToken(")".to_string(), 1)), Expression::parse("a)"));
+    assert_eq!(Err(ExpressionError::UnknownFunction("pow".to_string())), Expression::parse("pow(a, 2)"));
+    assert_eq!(Err(ExpressionError::InvalidArgumentCount("abs".to_string(), 2)), Expression::parse("abs(a, b)"));
+}
+
+#[test]
+fn expression_depth_test() {
+    let nested = |depth: usize| format!("{}a{}", "(".repeat(depth), ")".repeat(depth));
+    assert!(Expression::parse(nested(MAX_EXPRESSION_DEPTH)).is_ok());
+    assert_eq!(
+        Err(ExpressionError::TooDeeplyNested(MAX_EXPRESSION_DEPTH)),
+        Expression::parse(nested(MAX_EXPRESSION_DEPTH + 1))
+    );
+    assert_eq!(Err(ExpressionError::TooDeeplyNested(MAX_EXPRESSION_DEPTH)), Expression::parse("-".repeat(100_000) + "a"));
+    assert!(matches!(Expression::parse("abs(".repeat(100_000)), Err(ExpressionError::TooDeeplyNested(_))));
+
+    // Long chains of operators result in deep expressions as well
+    assert!(Expression::parse(vec!["a"; MAX_EXPRESSION_DEPTH].join(" + ")).is_ok());
+    assert!(matches!(Expression::parse(vec!["a"; 100_000].join(" + ")), Err(ExpressionError::TooDeeplyNested(_))));
+}
+
+#[test]
+fn expression_evaluate_test() {
+    let resolve = |name: &str| match name {
+        "a" => Some(json!(7)),
+        "b" => Some(json!(2)),
+        "c" => Some(json!(0.5)),
+        "s" => Some(json!("x")),
+        _ => None,
+    };
+    let evaluate = |source: &str| Expression::parse(source).unwrap().evaluate(resolve);
+    assert_eq!(json!(11), evaluate("a + b * 2"));
+    assert_eq!(json!(18), evaluate("(a + b) * 2"));
+    assert_eq!(json!(1), evaluate("a % b"));
+    assert_eq!(json!(3.5), evaluate("a / b"));
+    assert_eq!(json!(-5), evaluate("-a + b"));
+    assert_eq!(json!(7.5), evaluate("a + c"));
+    assert_eq!(json!(4), evaluate("round(a / b)"));
+    assert_eq!(json!(0.5), evaluate("min(a, b, c)"));
+    assert_eq!(json!(7), evaluate("max(abs(-a), b)"));
+    assert_eq!(Value::Null, evaluate("a + s"));
+    assert_eq!(Value::Null, evaluate("a + missing"));
+
+    // Rounded results which don't fit into an integer are not saturated
+    let try_evaluate = |source: &str| Expression::parse(source).unwrap().try_evaluate(resolve);
+    assert_eq!(Ok(json!(4)), try_evaluate("round(a / b)"));
+    assert_eq!(Err(ExpressionError::IntegerOverflow("round".to_string())), try_evaluate("round(10000000000000000000.5)"));
+    assert_eq!(Err(ExpressionError::IntegerOverflow("floor".to_string())), try_evaluate("floor(-a * 10000000000000000000.0)"));
+    assert_eq!(Value::Null, evaluate("ceil(10000000000000000000.5)"));
+}
+
+#[test]
+fn computed_properties_test() {
+    let entity_instance = Arc::new(create_random_entity_instance("width"));
+    entity_instance.add_property("height", json!(3));
+    entity_instance.set("width", json!(2));
+    let property_types = vec![computed("area", "width * height"), computed("double_area", "area * 2")];
+    let graph = connect_computed_properties(&entity_instance, &property_types).unwrap();
+    assert_eq!(Some(6), entity_instance.as_i64("area"));
+    assert_eq!(Some(12), entity_instance.as_i64("double_area"));
+
+    entity_instance.set("height", json!(5));
+    assert_eq!(Some(10), entity_instance.as_i64("area"));
+    assert_eq!(Some(20), entity_instance.as_i64("double_area"));
+
+    graph.disconnect(&entity_instance);
+    entity_instance.set("height", json!(1));
+    assert_eq!(Some(10), entity_instance.as_i64("area"));
+
+    let result = connect_computed_properties(&entity_instance, &[computed("invalid", "width *")]);
+    assert!(matches!(result, Err(PropertyDependencyError::InvalidExpression(name, ExpressionError::UnexpectedEnd)) if name == "invalid"));
+}
@@ -0,0 +1,90 @@
+use std::sync::Arc;
+use std::thread;
+
+use serde_json::json;
+
+use crate::tests::utils::create_random_entity_instance;
+use crate::{value_has_flag, value_with_flag, PropertyFlag, PropertyInstanceGetter, PropertyInstanceSetter, PropertyMutability, ReactivePropertyContainer};
+
+const READ: u64 = 0b001;
+const WRITE: u64 = 0b010;
+const EXECUTE: u64 = 0b100;
+
+#[test]
+fn value_flag_test() {
+    let value = json!(READ | EXECUTE);
+    assert!(value_has_flag(&value, &PropertyFlag::Bit(READ)));
+    assert!(!value_has_flag(&value, &PropertyFlag::Bit(WRITE)));
+    assert!(value_has_flag(&value, &PropertyFlag::Bit(READ | EXECUTE)));
+    assert_eq!(json!(READ | WRITE | EXECUTE), value_with_flag(&value, &PropertyFlag::Bit(WRITE), true));
+    assert_eq!(json!(EXECUTE), value_with_flag(&value, &PropertyFlag::Bit(READ), false));
+
+    let value = json!(["visible"]);
+    assert!(value_has_flag(&value, &"visible".into()));
+    assert!(!value_has_flag(&value, &"selected".into()));
+    assert_eq!(json!(["visible", "selected"]), value_with_flag(&value, &"selected".into(), true));
+    // Named flags are not added twice
+    assert_eq!(json!(["visible"]), value_with_flag(&value, &"visible".into(), true));
+    assert_eq!(json!([]), value_with_flag(&value, &"visible".into(), false));
+
+    // Other representations are treated as the empty set
+    assert!(!value_has_flag(&json!("visible"), &"visible".into()));
+    assert_eq!(json!(WRITE), value_with_flag(&json!("x"), &PropertyFlag::Bit(WRITE), true));
+}
+
+#[test]
+fn property_flags_test() {
+    let entity_instance = create_random_entity_instance("permissions");
+    entity_instance.add_property("state", json!([]));
+    entity_instance.set("permissions", json!(0));
+
+    entity_instance.set_flag("permissions", READ);
+    entity_instance.set_flag("permissions", WRITE);
+    assert!(entity_instance.has_flag("permissions", READ | WRITE));
+    entity_instance.clear_flag("permissions", READ);
+    assert!(!entity_instance.has_flag("permissions", READ));
+    assert_eq!(WRITE, entity_instance.as_u64("permissions").unwrap());
+    assert!(entity_instance.toggle_flag("permissions", EXECUTE));
+    assert!(!entity_instance.toggle_flag("permissions", EXECUTE));
+
+    entity_instance.set_flag("state", "active");
+    assert!(entity_instance.has_flag("state", "active"));
+    assert!(!entity_instance.toggle_flag("state", "active"));
+    assert_eq!(json!([]), entity_instance.get("state").unwrap());
+
+    // Missing properties are not created
+    entity_instance.set_flag("missing", READ);
+    assert!(!entity_instance.has_flag("missing", READ));
+    assert!(!entity_instance.toggle_flag("missing", READ));
+    assert!(entity_instance.get("missing").is_none());
+}
+
+#[test]
+fn property_flags_toggle_concurrently_test() {
+    let entity_instance = Arc::new(create_random_entity_instance("permissions"));
+    entity_instance.set("permissions", json!(0));
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let entity_instance = entity_instance.clone();
+            thread::spawn(move || {
+                for _ in 0..1000 {
+                    entity_instance.toggle_flag("permissions", EXECUTE);
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    // No toggle is lost, so the even number of toggles clears the flag
+    assert_eq!(0, entity_instance.as_u64("permissions").unwrap());
+
+    // Rejected values are not reported as toggled
+    entity_instance
+        .properties
+        .get_mut("permissions")
+        .unwrap()
+        .set_mutability(PropertyMutability::ReadOnly);
+    assert!(!entity_instance.toggle_flag("permissions", EXECUTE));
+    assert_eq!(0, entity_instance.as_u64("permissions").unwrap());
+}
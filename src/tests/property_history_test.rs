@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use serde_json::json;
+use serde_json::Value;
+
+use crate::tests::utils::create_random_entity_instance;
+use crate::tests::utils::r_string;
+use crate::PropertyHistory;
+use crate::PropertyInstanceGetter;
+use crate::PropertyInstanceSetter;
+use crate::PropertyMutability;
+use crate::ReactivePropertyContainer;
+
+#[test]
+fn property_history_undo_redo_test() {
+    let property_name = r_string();
+    let entity_instance = create_random_entity_instance(property_name.clone());
+    let initial_value = entity_instance.get(property_name.clone()).unwrap();
+
+    let history = PropertyHistory::new(10);
+    history.attach(&entity_instance);
+    assert!(!history.can_undo());
+    assert!(!history.undo(&entity_instance));
+
+    entity_instance.set(property_name.clone(), json!(1));
+    entity_instance.set(property_name.clone(), json!(2));
+    assert_eq!(2, history.changes().len());
+    assert_eq!(initial_value, history.changes().first().unwrap().old_value);
+
+    assert!(history.undo(&entity_instance));
+    assert_eq!(1, entity_instance.as_u64(property_name.clone()).unwrap());
+    assert!(history.undo(&entity_instance));
+    assert_eq!(initial_value, entity_instance.get(property_name.clone()).unwrap());
+    assert!(!history.can_undo());
+    assert!(history.can_redo());
+
+    assert!(history.redo(&entity_instance));
+    assert_eq!(1, entity_instance.as_u64(property_name.clone()).unwrap());
+
+    // A new change discards the redo stack
+    entity_instance.set(property_name.clone(), json!(3));
+    assert!(!history.can_redo());
+    assert_eq!(2, history.changes().len());
+
+    history.detach(&entity_instance);
+    entity_instance.set(property_name.clone(), json!(4));
+    assert_eq!(2, history.changes().len());
+}
+
+#[test]
+fn property_history_depth_test() {
+    let property_name = r_string();
+    let entity_instance = create_random_entity_instance(property_name.clone());
+    let history = PropertyHistory::new(3);
+    history.attach(&entity_instance);
+    for i in 0..10 {
+        entity_instance.set(property_name.clone(), json!(i));
+    }
+    let changes = history.changes();
+    assert_eq!(3, changes.len());
+    assert_eq!(9, changes.last().unwrap().new_value);
+    assert_eq!(6, changes.first().unwrap().old_value);
+}
+
+#[test]
+fn property_history_rejected_undo_test() {
+    let property_name = r_string();
+    let entity_instance = create_random_entity_instance(property_name.clone());
+    let history = PropertyHistory::new(10);
+    history.attach(&entity_instance);
+    entity_instance.set(property_name.clone(), json!(1));
+    entity_instance
+        .properties
+        .get_mut(property_name.as_str())
+        .unwrap()
+        .set_mutability(PropertyMutability::ReadOnly);
+
+    // The change stays on the undo stack, if the old value is rejected
+    assert!(!history.undo(&entity_instance));
+    assert_eq!(1, entity_instance.as_u64(property_name.clone()).unwrap());
+    assert!(history.can_undo());
+    assert!(!history.can_redo());
+}
+
+#[test]
+fn property_history_records_changes_while_undoing_test() {
+    let entity_instance = Arc::new(create_random_entity_instance("x"));
+    entity_instance.add_property("y", json!(0));
+    let history = PropertyHistory::new(10);
+    history.attach(&*entity_instance);
+    entity_instance.set("x", json!(1));
+
+    // An observer of x modifies y while the undo is applied: the change of y is recorded
+    let entity_instance_2 = Arc::downgrade(&entity_instance);
+    entity_instance.properties.get("x").unwrap().observe_with_priority(-1, move |value: &Value| {
+        if let Some(entity_instance) = entity_instance_2.upgrade() {
+            entity_instance.set("y", value.clone());
+        }
+    });
+    let x_0 = history.changes().first().unwrap().old_value.clone();
+    assert!(history.undo(&*entity_instance));
+    let changes = history.changes();
+    assert_eq!(1, changes.len());
+    assert_eq!("y", changes[0].property_name);
+    assert_eq!(x_0, changes[0].new_value);
+}
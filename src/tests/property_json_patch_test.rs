@@ -0,0 +1,96 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use json_patch::Patch;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    EntityInstance, EntityTypeName, PropertyInstanceGetter, PropertyMap, PropertyPatchError, ReactiveEntityInstance, RelationInstance, RelationTypeName,
+};
+
+fn patch(operations: serde_json::Value) -> Patch {
+    serde_json::from_value(operations).unwrap()
+}
+
+fn properties(value: serde_json::Value) -> PropertyMap {
+    serde_json::from_value(value).unwrap()
+}
+
+#[test]
+fn entity_instance_apply_json_patch_test() {
+    let mut entity_instance = EntityInstance::new(
+        EntityTypeName::new("player"),
+        Uuid::new_v4(),
+        properties(json!({ "name": "alice", "tags": ["a"], "team": "red" })),
+    );
+    let result = entity_instance.apply_json_patch(&patch(json!([
+        { "op": "replace", "path": "/name", "value": "bob" },
+        { "op": "add", "path": "/tags/-", "value": "b" },
+        { "op": "remove", "path": "/team" },
+        { "op": "copy", "from": "/name", "path": "/nickname" }
+    ])));
+    assert!(result.is_ok());
+    assert_eq!(properties(json!({ "name": "bob", "tags": ["a", "b"], "nickname": "bob" })), entity_instance.properties);
+
+    // The failing test operation reverts the previous operations
+    let result = entity_instance.apply_json_patch(&patch(json!([
+        { "op": "replace", "path": "/name", "value": "carol" },
+        { "op": "test", "path": "/name", "value": "dave" }
+    ])));
+    assert!(matches!(result, Err(PropertyPatchError::Patch(_))));
+    assert_eq!(json!("bob"), entity_instance.get("name").unwrap());
+
+    let result = entity_instance.apply_json_patch(&patch(json!([{ "op": "replace", "path": "", "value": 1 }])));
+    assert_eq!(Err(PropertyPatchError::NotAnObject), result);
+}
+
+#[test]
+fn relation_instance_apply_json_patch_test() {
+    let mut relation_instance = RelationInstance::new(Uuid::new_v4(), RelationTypeName::new("follows"), Uuid::new_v4(), properties(json!({ "weight": 1 })));
+    relation_instance
+        .apply_json_patch(&patch(json!([{ "op": "move", "from": "/weight", "path": "/strength" }])))
+        .unwrap();
+    assert_eq!(properties(json!({ "strength": 1 })), relation_instance.properties);
+}
+
+#[test]
+fn reactive_entity_instance_apply_json_patch_test() {
+    let entity_instance = EntityInstance::new(
+        EntityTypeName::new("player"),
+        Uuid::new_v4(),
+        properties(json!({ "position": { "x": 0, "y": 0 }, "name": "alice" })),
+    );
+    let reactive_entity_instance = ReactiveEntityInstance::from(entity_instance);
+    let propagations = Arc::new(AtomicUsize::new(0));
+    let counter = propagations.clone();
+    reactive_entity_instance
+        .properties
+        .get("position")
+        .unwrap()
+        .stream
+        .read()
+        .unwrap()
+        .observe(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+    let result = reactive_entity_instance.apply_json_patch(&patch(json!([
+        { "op": "replace", "path": "/position/x", "value": 1 },
+        { "op": "replace", "path": "/position/y", "value": 2 },
+        { "op": "test", "path": "/name", "value": "alice" },
+        { "op": "add", "path": "/score", "value": 3 }
+    ])));
+    assert!(result.is_ok());
+    assert_eq!(json!({ "x": 1, "y": 2 }), reactive_entity_instance.get("position").unwrap());
+    assert_eq!(json!(3), reactive_entity_instance.get("score").unwrap());
+    assert_eq!(1, propagations.load(Ordering::SeqCst));
+
+    let result = reactive_entity_instance.apply_json_patch(&patch(json!([
+        { "op": "replace", "path": "/position/x", "value": 5 },
+        { "op": "remove", "path": "/name" }
+    ])));
+    assert_eq!(Err(PropertyPatchError::RemoveProperty("name".to_string())), result);
+    assert_eq!(json!({ "x": 1, "y": 2 }), reactive_entity_instance.get("position").unwrap());
+    assert_eq!(1, propagations.load(Ordering::SeqCst));
+}
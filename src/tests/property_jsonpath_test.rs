@@ -0,0 +1,36 @@
+use serde_json::json;
+
+use crate::get_jsonpath_property_name;
+use crate::tests::utils::create_random_entity_instance;
+use crate::PropertyInstanceGetter;
+use crate::PropertyInstanceSetter;
+
+#[test]
+fn get_jsonpath_property_name_test() {
+    assert_eq!("items", get_jsonpath_property_name("$.items[?(@.active)].id").unwrap());
+    assert_eq!("items", get_jsonpath_property_name("$.items").unwrap());
+    assert_eq!("items", get_jsonpath_property_name("$.items.length").unwrap());
+    assert_eq!("my items", get_jsonpath_property_name("$['my items'][0]").unwrap());
+    assert_eq!("items", get_jsonpath_property_name("$[\"items\"]").unwrap());
+    assert!(get_jsonpath_property_name("$").is_none());
+    assert!(get_jsonpath_property_name("$.*").is_none());
+    assert!(get_jsonpath_property_name("items").is_none());
+}
+
+#[test]
+fn get_jsonpath_test() {
+    let entity_instance = create_random_entity_instance("items");
+    entity_instance.set(
+        "items",
+        json!([
+            { "id": 1, "active": true },
+            { "id": 2, "active": false },
+            { "id": 3, "active": true }
+        ]),
+    );
+    assert_eq!(vec![json!(1), json!(3)], entity_instance.get_jsonpath("$.items[?(@.active == true)].id").unwrap());
+    assert_eq!(vec![json!(2)], entity_instance.get_jsonpath("$.items[1].id").unwrap());
+    assert!(entity_instance.get_jsonpath("$.items[5].id").unwrap().is_empty());
+    assert!(entity_instance.get_jsonpath("$.unknown[0]").is_none());
+    assert!(entity_instance.get_jsonpath("$.items[?(").is_none());
+}
@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{remove_property_value, EntityInstance, EntityTypeName, PropertyMap, ReactiveEntityInstance, ReactivePropertyContainer};
+
+#[test]
+fn remove_property_test() {
+    let mut properties = PropertyMap::new();
+    properties.insert("a".to_string(), json!(1));
+    properties.insert("b".to_string(), json!(2));
+    properties.insert("c".to_string(), json!(3));
+    assert_eq!(Some(json!(1)), remove_property_value(&mut properties, "a"));
+    assert_eq!(None, remove_property_value(&mut properties, "a"));
+    assert_eq!(vec!["b", "c"], properties.keys().collect::<Vec<&String>>());
+}
+
+#[test]
+fn reactive_entity_instance_properties_order_test() {
+    let reactive_entity_instance = ReactiveEntityInstance::from(EntityInstance::new_without_properties(EntityTypeName::new("player"), Uuid::new_v4()));
+    for name in ["c", "a", "b"] {
+        reactive_entity_instance.add_property(name, json!(0));
+    }
+    let entity_instance = EntityInstance::from(Arc::new(reactive_entity_instance));
+    let names: Vec<&String> = entity_instance.properties.keys().collect();
+    assert_eq!(vec!["a", "b", "c"], names);
+}
+
+#[test]
+fn entity_instance_preserve_order_test() {
+    let mut properties = PropertyMap::new();
+    for name in ["z", "a", "m"] {
+        properties.insert(name.to_string(), json!(0));
+    }
+    let id = Uuid::new_v4();
+    let entity_instance = EntityInstance::new(EntityTypeName::new("player"), id, properties);
+    let json = serde_json::to_string(&entity_instance.properties).unwrap();
+    assert_eq!(r#"{"z":0,"a":0,"m":0}"#, json);
+    let deserialized: EntityInstance = serde_json::from_str(&serde_json::to_string(&entity_instance).unwrap()).unwrap();
+    assert_eq!(vec!["z", "a", "m"], deserialized.properties.keys().collect::<Vec<&String>>());
+}
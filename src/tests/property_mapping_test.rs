@@ -0,0 +1,132 @@
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    remove_property_value, DataType, EntityInstance, EntityTypeName, PropertyInstanceGetter, PropertyMapping, PropertyMappingError, ReactiveEntityInstance,
+};
+
+#[derive(PropertyMapping, Debug, PartialEq)]
+struct Player {
+    name: String,
+    #[property(name = "hit_points")]
+    health: i64,
+    alive: bool,
+    inventory: Vec<String>,
+    title: Option<String>,
+    #[property(skip)]
+    selected: bool,
+}
+
+fn create_player() -> Player {
+    Player {
+        name: "Alice".to_string(),
+        health: 100,
+        alive: true,
+        inventory: vec!["sword".to_string()],
+        title: None,
+        selected: true,
+    }
+}
+
+#[test]
+fn property_mapping_property_types_test() {
+    let property_types = Player::property_types();
+    let property_types: Vec<(&str, DataType)> = property_types.iter().map(|p| (p.name.as_str(), p.data_type)).collect();
+    assert_eq!(
+        vec![
+            ("name", DataType::String),
+            ("hit_points", DataType::Number),
+            ("alive", DataType::Bool),
+            ("inventory", DataType::Array),
+            ("title", DataType::String),
+        ],
+        property_types
+    );
+}
+
+#[test]
+fn property_mapping_entity_instance_test() {
+    let player = create_player();
+    let entity_instance = player.to_entity_instance(EntityTypeName::new("player"), Uuid::new_v4()).unwrap();
+    assert_eq!(json!(100), entity_instance.get("hit_points").unwrap());
+    assert_eq!(json!(["sword"]), entity_instance.get("inventory").unwrap());
+    assert!(entity_instance.get("selected").is_none());
+
+    let mapped = Player::from_instance(&entity_instance).unwrap();
+    assert_eq!(Player { selected: false, ..player }, mapped);
+}
+
+#[test]
+fn property_mapping_reactive_entity_instance_test() {
+    let player = create_player();
+    let reactive_entity_instance = ReactiveEntityInstance::from(player.to_entity_instance(EntityTypeName::new("player"), Uuid::new_v4()).unwrap());
+    let mut changed = Player::from_instance(&reactive_entity_instance).unwrap();
+    changed.health = 50;
+    changed.title = Some("Hero".to_string());
+    changed.apply_to(&reactive_entity_instance).unwrap();
+    assert_eq!(json!(50), reactive_entity_instance.get("hit_points").unwrap());
+    assert_eq!(Some("Hero".to_string()), Player::from_instance(&reactive_entity_instance).unwrap().title);
+}
+
+#[test]
+fn property_mapping_error_test() {
+    let mut properties = create_player().to_properties().unwrap();
+    remove_property_value(&mut properties, "title");
+    properties.insert("alive".to_string(), json!("yes"));
+    let entity_instance = EntityInstance::new(EntityTypeName::new("player"), Uuid::new_v4(), properties.clone());
+    assert!(matches!(Player::from_instance(&entity_instance), Err(PropertyMappingError::InvalidValue(name, _)) if name == "alive"));
+
+    properties.insert("alive".to_string(), json!(true));
+    assert!(Player::from_instance(&EntityInstance::new(EntityTypeName::new("player"), Uuid::new_v4(), properties.clone()))
+        .unwrap()
+        .title
+        .is_none());
+    remove_property_value(&mut properties, "name");
+    assert_eq!(
+        Err(PropertyMappingError::MissingProperty("name".to_string())),
+        Player::from_instance(&EntityInstance::new(EntityTypeName::new("player"), Uuid::new_v4(), properties))
+    );
+}
+
+#[derive(PropertyMapping, Debug, PartialEq)]
+struct Position {
+    x: f64,
+    path: Vec<f64>,
+}
+
+#[test]
+fn property_mapping_non_finite_test() {
+    let position = Position { x: f64::NAN, path: Vec::new() };
+    assert!(matches!(position.to_properties(), Err(PropertyMappingError::InvalidField(name, _)) if name == "x"));
+
+    let position = Position {
+        x: 1.0,
+        path: vec![0.5, f64::INFINITY],
+    };
+    assert!(
+        matches!(position.to_entity_instance(EntityTypeName::new("position"), Uuid::new_v4()), Err(PropertyMappingError::InvalidField(name, _)) if name == "path")
+    );
+
+    let reactive_entity_instance = ReactiveEntityInstance::from(
+        Position { x: 1.0, path: vec![0.5] }
+            .to_entity_instance(EntityTypeName::new("position"), Uuid::new_v4())
+            .unwrap(),
+    );
+    assert!(Position { x: f64::NAN, path: Vec::new() }.apply_to(&reactive_entity_instance).is_err());
+    assert_eq!(json!(1.0), reactive_entity_instance.get("x").unwrap());
+}
+
+#[derive(PropertyMapping, Debug, PartialEq)]
+struct Session {
+    id: Uuid,
+}
+
+#[test]
+fn property_mapping_uuid_test() {
+    let property_types = Session::property_types();
+    assert_eq!(DataType::Uuid, property_types[0].data_type);
+    let session = Session { id: Uuid::new_v4() };
+    let entity_instance = session.to_entity_instance(EntityTypeName::new("session"), Uuid::new_v4()).unwrap();
+    assert_eq!(json!(session.id.to_string()), entity_instance.get("id").unwrap());
+    assert_eq!(session, Session::from_instance(&entity_instance).unwrap());
+}
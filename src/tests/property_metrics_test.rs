@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::tests::utils::r_string;
+use crate::{
+    clear_metrics_recorder, has_metrics_recorder, instrument_observer, set_metrics_recorder, suspend_instance_propagation, CountingMetricsRecorder, Metric,
+    MetricCounts, MetricsRecorder, ReactivePropertyInstance, SuspensionMode,
+};
+
+#[test]
+fn property_metrics_test() {
+    let id = Uuid::new_v4();
+    let property_name = r_string();
+    let property_instance = ReactivePropertyInstance::new(id, property_name.clone(), json!(0));
+    property_instance.observe_with_priority(0, |_| {});
+    property_instance.observe_with_handle(|_| {}, Uuid::new_v4().as_u128());
+    property_instance
+        .stream
+        .read()
+        .unwrap()
+        .observe_with_handle(instrument_observer(id, property_name.clone(), |_| {}), Uuid::new_v4().as_u128());
+
+    // Other tests may run concurrently, so only the counters of this property are checked
+    let recorder = Arc::new(CountingMetricsRecorder::new());
+    set_metrics_recorder(recorder.clone());
+    assert!(has_metrics_recorder());
+
+    property_instance.set(json!(1));
+    property_instance.set_no_propagate(json!(2));
+    property_instance.tick();
+    {
+        let _guard = suspend_instance_propagation(vec![id], SuspensionMode::Consolidate);
+        property_instance.set(json!(3));
+    }
+
+    // The replaced recorder doesn't record the metrics anymore
+    let replacement = Arc::new(CountingMetricsRecorder::new());
+    set_metrics_recorder(replacement.clone());
+    property_instance.tick();
+    clear_metrics_recorder();
+    property_instance.set(json!(4));
+    assert_eq!(1, replacement.get_property(id, property_name.clone()).ticks);
+
+    assert_eq!(
+        MetricCounts {
+            property_sets: 3,
+            propagations: 3,
+            suppressed_propagations: 1,
+            ticks: 1,
+            observer_invocations: 9,
+        },
+        recorder.get_property(id, property_name.clone())
+    );
+    assert_eq!(recorder.get_property(id, property_name), recorder.get_instance(id));
+    assert_eq!(MetricCounts::default(), recorder.get_instance(Uuid::new_v4()));
+}
+
+#[test]
+fn counting_metrics_recorder_test() {
+    let recorder = CountingMetricsRecorder::new();
+    let id_1 = Uuid::new_v4();
+    let id_2 = Uuid::new_v4();
+    let id_3 = Uuid::new_v4();
+    for _ in 0..3 {
+        recorder.record(Metric::Propagation, id_1, "value");
+    }
+    recorder.record(Metric::Propagation, id_2, "value");
+    recorder.record(Metric::PropertySet, id_2, "value");
+    recorder.record(Metric::Tick, id_2, "other");
+    recorder.record(Metric::Tick, id_3, "value");
+
+    assert_eq!(3, recorder.get_property(id_1, "value").get(Metric::Propagation));
+    assert_eq!(
+        MetricCounts {
+            property_sets: 1,
+            propagations: 1,
+            ticks: 1,
+            ..MetricCounts::default()
+        },
+        recorder.get_instance(id_2)
+    );
+
+    let hot_properties = recorder.hot_properties(Metric::Propagation, 2);
+    assert_eq!(2, hot_properties.len());
+    assert_eq!(id_1, hot_properties[0].id);
+    assert_eq!(id_2, hot_properties[1].id);
+
+    let types: HashMap<Uuid, &str> = HashMap::from([(id_1, "counter"), (id_2, "counter")]);
+    let by_type = recorder.get_by_type(|id| types.get(&id).map(|type_name| type_name.to_string()));
+    assert_eq!(1, by_type.len());
+    assert_eq!(4, by_type["counter"].propagations);
+    assert_eq!(1, by_type["counter"].ticks);
+
+    recorder.reset();
+    assert!(recorder.get_properties().is_empty());
+}
@@ -0,0 +1,80 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::tests::utils::create_random_entity_instance;
+use crate::{
+    split_nested_path, EntityInstance, EntityTypeName, MutablePropertyInstanceSetter, NestedPropertyError, PropertyInstanceGetter, PropertyInstanceSetter,
+    PropertyMap, PropertyMutability,
+};
+
+#[test]
+fn split_nested_path_test() {
+    assert_eq!(("transform", vec!["position", "x"]), split_nested_path("transform.position.x").unwrap());
+    assert_eq!(("transform", Vec::<&str>::new()), split_nested_path("transform").unwrap());
+    for path in ["", ".x", "transform..x", "transform."] {
+        assert_eq!(Err(NestedPropertyError::InvalidPath(path.to_string())), split_nested_path(path));
+    }
+}
+
+#[test]
+fn entity_instance_nested_test() {
+    let mut properties = PropertyMap::new();
+    properties.insert("transform".to_string(), json!({ "position": { "x": 0, "y": 0 }, "scale": 1 }));
+    let mut entity_instance = EntityInstance::new(EntityTypeName::new("sprite"), Uuid::new_v4(), properties);
+    entity_instance.set_nested("transform.position.x", json!(3.5)).unwrap();
+    entity_instance.set_nested("transform.rotation.z", json!(90)).unwrap();
+    assert_eq!(
+        json!({ "position": { "x": 3.5, "y": 0 }, "rotation": { "z": 90 }, "scale": 1 }),
+        entity_instance.get("transform").unwrap()
+    );
+    assert_eq!(json!(3.5), entity_instance.get_nested("transform.position.x").unwrap());
+    assert!(entity_instance.get_nested("transform.position.z").is_none());
+    assert_eq!(
+        Err(NestedPropertyError::NotAnObject("transform.scale".to_string())),
+        entity_instance.set_nested("transform.scale.x", json!(2))
+    );
+    assert_eq!(
+        Err(NestedPropertyError::MissingProperty("config".to_string())),
+        entity_instance.set_nested("config.width", json!(2))
+    );
+}
+
+#[test]
+fn reactive_entity_instance_set_nested_test() {
+    let reactive_entity_instance = create_random_entity_instance("transform");
+    reactive_entity_instance.set("transform", json!({ "position": { "x": 0 } }));
+    let propagations = Arc::new(AtomicUsize::new(0));
+    let counter = propagations.clone();
+    reactive_entity_instance
+        .properties
+        .get("transform")
+        .unwrap()
+        .stream
+        .read()
+        .unwrap()
+        .observe(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+    reactive_entity_instance.set_nested("transform.position.x", json!(3.5)).unwrap();
+    assert_eq!(json!({ "position": { "x": 3.5 } }), reactive_entity_instance.get("transform").unwrap());
+    assert_eq!(1, propagations.load(Ordering::SeqCst));
+}
+
+#[test]
+fn reactive_entity_instance_set_nested_read_only_test() {
+    let reactive_entity_instance = create_random_entity_instance("transform");
+    reactive_entity_instance.set("transform", json!({ "position": { "x": 0 } }));
+    reactive_entity_instance
+        .properties
+        .get_mut("transform")
+        .unwrap()
+        .set_mutability(PropertyMutability::ReadOnly);
+    assert_eq!(
+        Err(NestedPropertyError::Rejected("transform".to_string())),
+        reactive_entity_instance.set_nested("transform.position.x", json!(3.5))
+    );
+    assert_eq!(json!({ "position": { "x": 0 } }), reactive_entity_instance.get("transform").unwrap());
+}
@@ -0,0 +1,111 @@
+use std::sync::{Arc, RwLock};
+use std::thread;
+
+use serde_json::{json, Map, Value};
+use uuid::Uuid;
+
+use crate::tests::utils::create_random_entity_instance;
+use crate::{
+    merge_object_value, EntityInstance, EntityTypeName, MutablePropertyInstanceSetter, PropertyInstanceGetter, PropertyInstanceSetter, PropertyMap,
+    ReactivePropertyContainer,
+};
+
+fn object(value: Value) -> Map<String, Value> {
+    value.as_object().unwrap().clone()
+}
+
+#[test]
+fn merge_object_value_test() {
+    let mut value = json!({ "window": { "width": 800, "height": 600 }, "title": "main" });
+    assert!(merge_object_value(&mut value, object(json!({ "window": { "width": 1024 }, "fullscreen": true })), true));
+    assert_eq!(json!({ "window": { "width": 1024, "height": 600 }, "title": "main", "fullscreen": true }), value);
+    assert!(merge_object_value(&mut value, object(json!({ "window": { "width": 640 } })), false));
+    assert_eq!(json!({ "window": { "width": 640 }, "title": "main", "fullscreen": true }), value);
+    assert!(!merge_object_value(&mut json!([1]), object(json!({ "a": 1 })), false));
+}
+
+#[test]
+fn entity_instance_merge_object_test() {
+    let mut properties = PropertyMap::new();
+    properties.insert("config".to_string(), json!({ "window": { "width": 800 }, "title": "main" }));
+    properties.insert("name".to_string(), json!("app"));
+    let mut entity_instance = EntityInstance::new(EntityTypeName::new("app"), Uuid::new_v4(), properties);
+    assert!(entity_instance.merge_object_deep("config", object(json!({ "window": { "height": 600 } }))));
+    assert!(entity_instance.merge_object("config", object(json!({ "title": "other" }))));
+    assert_eq!(json!({ "window": { "width": 800, "height": 600 }, "title": "other" }), entity_instance.get("config").unwrap());
+    assert_eq!(Some(json!("other")), entity_instance.remove_key("config", "title"));
+    assert_eq!(None, entity_instance.remove_key("config", "title"));
+    assert!(!entity_instance.merge_object("name", object(json!({ "a": 1 }))));
+    assert!(!entity_instance.merge_object("missing", object(json!({ "a": 1 }))));
+}
+
+#[test]
+fn reactive_entity_instance_merge_object_test() {
+    let reactive_entity_instance = create_random_entity_instance("config");
+    reactive_entity_instance.set("config", json!({ "width": 800 }));
+    let propagated = Arc::new(RwLock::new(Vec::new()));
+    let p = propagated.clone();
+    reactive_entity_instance
+        .properties
+        .get("config")
+        .unwrap()
+        .stream
+        .read()
+        .unwrap()
+        .observe(move |value| p.write().unwrap().push(value.clone()));
+    assert!(reactive_entity_instance.merge_object("config", object(json!({ "height": 600, "title": "main" }))));
+    assert_eq!(Some(json!("main")), reactive_entity_instance.remove_key("config", "title"));
+    assert_eq!(None, reactive_entity_instance.remove_key("config", "title"));
+    assert_eq!(
+        vec![json!({ "width": 800, "height": 600, "title": "main" }), json!({ "width": 800, "height": 600 })],
+        *propagated.read().unwrap()
+    );
+}
+
+#[test]
+fn reactive_entity_instance_concurrent_merge_object_test() {
+    let reactive_entity_instance = Arc::new(create_random_entity_instance("config"));
+    reactive_entity_instance.set("config", json!({}));
+    let handles: Vec<_> = (0..8)
+        .map(|i| {
+            let reactive_entity_instance = reactive_entity_instance.clone();
+            thread::spawn(move || {
+                for j in 0..16 {
+                    let mut partial = Map::new();
+                    partial.insert(format!("{}_{}", i, j), json!(j));
+                    assert!(reactive_entity_instance.merge_object("config", partial));
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    // No modification is lost
+    assert_eq!(128, reactive_entity_instance.as_object("config").unwrap().len());
+}
+
+#[test]
+fn reactive_entity_instance_merge_object_observer_removes_property_test() {
+    let reactive_entity_instance = Arc::new(create_random_entity_instance("config"));
+    reactive_entity_instance.set("config", json!({}));
+    let entity = Arc::downgrade(&reactive_entity_instance);
+    let removed = Arc::new(RwLock::new(None));
+    let r = removed.clone();
+    reactive_entity_instance
+        .properties
+        .get("config")
+        .unwrap()
+        .stream
+        .read()
+        .unwrap()
+        .observe(move |_| {
+            // The observer modifies the properties of the instance which propagates the value
+            if let Some(entity) = entity.upgrade() {
+                *r.write().unwrap() = entity.remove_property("config");
+            }
+        });
+    assert!(reactive_entity_instance.merge_object("config", object(json!({ "width": 800 }))));
+    assert_eq!(Some(json!({ "width": 800 })), *removed.read().unwrap());
+    assert!(!reactive_entity_instance.properties.contains_key("config"));
+}
@@ -0,0 +1,61 @@
+use std::sync::{Arc, RwLock};
+
+use serde_json::json;
+
+use crate::tests::utils::{create_random_entity_instance, create_random_relation_instance_with_properties, r_string};
+use crate::PropertyInstanceSetter;
+use crate::ReactivePropertyContainer;
+
+#[test]
+fn reactive_entity_instance_observe_all_test() {
+    let entity_instance = create_random_entity_instance("x");
+    let changes = Arc::new(RwLock::new(Vec::new()));
+    let recorded = changes.clone();
+    let handle_id = entity_instance.observe_all(move |property_name, value| {
+        recorded.write().unwrap().push((property_name.to_string(), value.clone()));
+    });
+    entity_instance.set("x", json!(1));
+    entity_instance.add_property("y", json!(0));
+    entity_instance.set("y", json!(2));
+    assert_eq!(vec![("x".to_string(), json!(1)), ("y".to_string(), json!(2))], *changes.read().unwrap());
+
+    entity_instance.remove_all_observer(handle_id);
+    entity_instance.set("x", json!(3));
+    entity_instance.add_property("z", json!(0));
+    entity_instance.set("z", json!(4));
+    assert_eq!(2, changes.read().unwrap().len());
+}
+
+#[test]
+fn reactive_relation_instance_observe_all_test() {
+    let outbound = Arc::new(create_random_entity_instance(r_string()));
+    let inbound = Arc::new(create_random_entity_instance(r_string()));
+    let relation_instance = create_random_relation_instance_with_properties(outbound, inbound, "weight".to_string());
+    let changes = Arc::new(RwLock::new(Vec::new()));
+    let recorded = changes.clone();
+    let handle_id = relation_instance.observe_all(move |property_name, value| {
+        recorded.write().unwrap().push((property_name.to_string(), value.clone()));
+    });
+    relation_instance.set("weight", json!(1));
+    relation_instance.add_property("label", json!(""));
+    relation_instance.set("label", json!("a"));
+    assert_eq!(vec![("weight".to_string(), json!(1)), ("label".to_string(), json!("a"))], *changes.read().unwrap());
+
+    relation_instance.remove_all_observer(handle_id);
+    relation_instance.set("weight", json!(2));
+    assert_eq!(2, changes.read().unwrap().len());
+}
+
+#[test]
+fn observers_are_stored_on_the_instance_test() {
+    let entity_instance = create_random_entity_instance("x");
+    let handle_id = entity_instance.observe_all(|_, _| {});
+    let event_handle_id = entity_instance.observe_events(|_| {});
+    assert_eq!(2, entity_instance.observers.len());
+    let other_instance = create_random_entity_instance("x");
+    assert!(other_instance.observers.is_empty());
+
+    entity_instance.remove_all_observer(handle_id);
+    entity_instance.remove_event_observer(event_handle_id);
+    assert!(entity_instance.observers.is_empty());
+}
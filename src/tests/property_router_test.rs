@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::generate_id;
+use crate::tests::utils::create_random_entity_instance;
+use crate::Extension;
+use crate::PropertyInstanceGetter;
+use crate::PropertyInstanceSetter;
+use crate::PropertyRouter;
+use crate::RoutingStrategy;
+use crate::ROUTING_EXTENSION;
+
+#[test]
+fn routing_strategy_from_extensions_test() {
+    let extensions = vec![Extension::new(ROUTING_EXTENSION, json!({ "strategy": "weighted_random", "weights": [3, 1] }))];
+    assert_eq!(
+        RoutingStrategy::WeightedRandom { weights: vec![3, 1] },
+        RoutingStrategy::from_extensions(&extensions).unwrap()
+    );
+    let extensions = vec![Extension::new(ROUTING_EXTENSION, json!({ "strategy": "round_robin" }))];
+    assert_eq!(RoutingStrategy::RoundRobin, RoutingStrategy::from_extensions(&extensions).unwrap());
+    let extensions = vec![Extension::new(ROUTING_EXTENSION, json!({ "strategy": "hash", "key": "user" }))];
+    assert_eq!(
+        RoutingStrategy::Hash { key: Some("user".to_string()) },
+        RoutingStrategy::from_extensions(&extensions).unwrap()
+    );
+    assert!(RoutingStrategy::from_extensions(&[]).is_none());
+}
+
+#[test]
+fn property_router_round_robin_test() {
+    let outbound = Arc::new(create_random_entity_instance("out"));
+    let inbound_1 = Arc::new(create_random_entity_instance("in"));
+    let inbound_2 = Arc::new(create_random_entity_instance("in"));
+    let router = PropertyRouter::new(RoutingStrategy::RoundRobin);
+    assert!(router.select(&json!(1)).is_none());
+    router.add_target(&inbound_1, "in");
+    router.add_target(&inbound_2, "in");
+    assert!(router.connect(&outbound, "out"));
+    assert!(!router.connect(&outbound, "unknown"));
+
+    outbound.set("out", json!(1));
+    outbound.set("out", json!(2));
+    outbound.set("out", json!(3));
+    assert_eq!(json!(3), inbound_1.get("in").unwrap());
+    assert_eq!(json!(2), inbound_2.get("in").unwrap());
+
+    router.disconnect(&outbound, "out");
+    outbound.set("out", json!(4));
+    assert_eq!(json!(3), inbound_1.get("in").unwrap());
+    assert_eq!(json!(2), inbound_2.get("in").unwrap());
+}
+
+#[test]
+fn property_router_add_target_while_routing_test() {
+    let outbound = Arc::new(create_random_entity_instance("out"));
+    let inbound_1 = Arc::new(create_random_entity_instance("in"));
+    let inbound_2 = Arc::new(create_random_entity_instance("in"));
+    let router = PropertyRouter::new(RoutingStrategy::RoundRobin);
+    router.add_target(&inbound_1, "in");
+    assert!(router.connect(&outbound, "out"));
+    let r = router.clone();
+    let target = inbound_2.clone();
+    inbound_1
+        .properties
+        .get("in")
+        .unwrap()
+        .observe_with_handle(move |_| r.add_target(&target, "in"), generate_id().as_u128());
+
+    outbound.set("out", json!(1));
+    assert_eq!(2, router.get_target_count());
+    outbound.set("out", json!(2));
+    assert_eq!(json!(2), inbound_2.get("in").unwrap());
+}
+
+#[test]
+fn property_router_weighted_random_test() {
+    let inbound_1 = Arc::new(create_random_entity_instance("in"));
+    let inbound_2 = Arc::new(create_random_entity_instance("in"));
+    let inbound_3 = Arc::new(create_random_entity_instance("in"));
+    let router = PropertyRouter::with_seed(RoutingStrategy::WeightedRandom { weights: vec![3, 0] }, 42);
+    router.add_target(&inbound_1, "in");
+    router.add_target(&inbound_2, "in");
+    router.add_target(&inbound_3, "in");
+    let mut counts = [0; 3];
+    for _ in 0..4000 {
+        counts[router.select(&json!(null)).unwrap()] += 1;
+    }
+    // The second input has no weight, the third input has the default weight 1
+    assert_eq!(0, counts[1]);
+    assert!(counts[0] > counts[2] * 2);
+    assert!(counts[2] > 0);
+}
+
+#[test]
+fn property_router_hash_test() {
+    let inbound_1 = Arc::new(create_random_entity_instance("in"));
+    let inbound_2 = Arc::new(create_random_entity_instance("in"));
+    let router = PropertyRouter::new(RoutingStrategy::Hash { key: Some("user".to_string()) });
+    router.add_target(&inbound_1, "in");
+    router.add_target(&inbound_2, "in");
+    let index = router.select(&json!({ "user": "alice", "n": 1 })).unwrap();
+    assert_eq!(index, router.select(&json!({ "user": "alice", "n": 2 })).unwrap());
+    // The hash is stable, so the value is always routed to the same input
+    assert_eq!(1, index);
+
+    router.route(&json!({ "user": "alice", "n": 3 }));
+    let target = if index == 0 { &inbound_1 } else { &inbound_2 };
+    assert_eq!(json!({ "user": "alice", "n": 3 }), target.get("in").unwrap());
+}
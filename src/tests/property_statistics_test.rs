@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::tests::utils::create_random_entity_instance;
+use crate::tests::utils::r_string;
+use crate::DataType;
+use crate::Extension;
+use crate::PropertyInstanceGetter;
+use crate::PropertyInstanceSetter;
+use crate::PropertyStatistics;
+use crate::PropertyType;
+use crate::StatisticsPropertyDecorator;
+use crate::STATISTICS_DEFAULT_WINDOW;
+use crate::STATISTICS_EXTENSION;
+
+#[test]
+fn property_statistics_test() {
+    let mut statistics = PropertyStatistics::new(2);
+    assert!(statistics.mean().is_none());
+    assert!(statistics.average().is_none());
+    statistics.update(1.0);
+    statistics.update(5.0);
+    statistics.update(3.0);
+    assert_eq!(3, statistics.count);
+    assert_eq!(1.0, statistics.min.unwrap());
+    assert_eq!(5.0, statistics.max.unwrap());
+    assert_eq!(3.0, statistics.mean().unwrap());
+    assert_eq!(4.0, statistics.average().unwrap());
+    assert_eq!(-2.0, statistics.rate_of_change);
+}
+
+#[test]
+fn statistics_property_decorator_get_window_test() {
+    let mut property_type = PropertyType::new("value", DataType::Number);
+    assert!(StatisticsPropertyDecorator::get_window(&property_type).is_none());
+    property_type.extensions.push(Extension::new(STATISTICS_EXTENSION, json!({})));
+    assert_eq!(STATISTICS_DEFAULT_WINDOW, StatisticsPropertyDecorator::get_window(&property_type).unwrap());
+    property_type.extensions = vec![Extension::new(STATISTICS_EXTENSION, json!({ "window": 3 }))];
+    assert_eq!(3, StatisticsPropertyDecorator::get_window(&property_type).unwrap());
+}
+
+#[test]
+fn statistics_property_decorator_test() {
+    let property_name = r_string();
+    let entity_instance = Arc::new(create_random_entity_instance(property_name.clone()));
+    let mut property_type = PropertyType::new(property_name.clone(), DataType::Number);
+    property_type.extensions.push(Extension::new(STATISTICS_EXTENSION, json!({ "window": 2 })));
+    let guards = StatisticsPropertyDecorator::decorate_entity(&entity_instance, &[property_type]);
+    assert_eq!(1, guards.len());
+    for sibling_property_name in StatisticsPropertyDecorator::get_property_names(property_name.clone()) {
+        assert!(entity_instance.properties.contains_key(sibling_property_name.as_str()));
+    }
+
+    entity_instance.set(property_name.clone(), json!(2));
+    entity_instance.set(property_name.clone(), json!(10));
+    entity_instance.set(property_name.clone(), json!(6));
+    // Non-numeric values are ignored
+    entity_instance.set(property_name.clone(), json!(r_string()));
+
+    assert_eq!(2.0, entity_instance.as_f64(format!("{}_min", property_name)).unwrap());
+    assert_eq!(10.0, entity_instance.as_f64(format!("{}_max", property_name)).unwrap());
+    assert_eq!(6.0, entity_instance.as_f64(format!("{}_mean", property_name)).unwrap());
+    assert_eq!(8.0, entity_instance.as_f64(format!("{}_average", property_name)).unwrap());
+    assert_eq!(-4.0, entity_instance.as_f64(format!("{}_rate_of_change", property_name)).unwrap());
+
+    // The derived properties are read only
+    entity_instance.set(format!("{}_max", property_name), json!(100));
+    assert_eq!(10.0, entity_instance.as_f64(format!("{}_max", property_name)).unwrap());
+
+    // Dropping the guards stops maintaining the statistics
+    drop(guards);
+    entity_instance.set(property_name.clone(), json!(20));
+    assert_eq!(10.0, entity_instance.as_f64(format!("{}_max", property_name)).unwrap());
+}
+
+#[test]
+fn statistics_property_decorator_missing_property_test() {
+    let entity_instance = Arc::new(create_random_entity_instance(r_string()));
+    let property_name = r_string();
+    assert!(StatisticsPropertyDecorator::decorate_entity_property(&entity_instance, property_name.clone(), 2).is_none());
+    // No sibling properties are added for a property which doesn't exist
+    for sibling_property_name in StatisticsPropertyDecorator::get_property_names(property_name) {
+        assert!(!entity_instance.properties.contains_key(sibling_property_name.as_str()));
+    }
+}
@@ -0,0 +1,58 @@
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::tests::utils::create_random_entity_instance;
+use crate::{date_time_to_value, value_to_date_time, DataType, DateTimeError, PropertyInstanceGetter, PropertyInstanceSetter, ReactivePropertyInstance};
+
+#[test]
+fn date_time_value_test() {
+    let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+    let date_time = offset.with_ymd_and_hms(2022, 3, 14, 15, 9, 26).unwrap();
+    let value = date_time_to_value(&date_time);
+    assert_eq!(json!("2022-03-14T15:09:26+02:00"), value);
+    assert_eq!(Ok(date_time), value_to_date_time(&value));
+    assert_eq!(json!("2022-03-14T13:09:26Z"), date_time_to_value(&date_time.with_timezone(&Utc)));
+
+    assert_eq!(Err(DateTimeError::NotAString(json!(1))), value_to_date_time(&json!(1)));
+    assert_eq!(Err(DateTimeError::Invalid("yesterday".to_string())), value_to_date_time(&json!("yesterday")));
+}
+
+#[test]
+fn date_time_data_type_test() {
+    assert_eq!(json!("1970-01-01T00:00:00Z"), DataType::DateTime.default_value());
+    assert!(DataType::DateTime.is_valid(&DataType::DateTime.default_value()));
+    assert!(DataType::DateTime.is_valid(&date_time_to_value(&Utc.with_ymd_and_hms(2021, 5, 1, 12, 30, 0).unwrap())));
+    assert!(!DataType::DateTime.is_valid(&json!("")));
+}
+
+#[test]
+fn date_time_accessor_test() {
+    let entity_instance = create_random_entity_instance("created");
+    let date_time = Utc.with_ymd_and_hms(2022, 3, 14, 15, 9, 26).unwrap();
+    entity_instance.set_date_time("created", date_time);
+    assert_eq!(json!("2022-03-14T15:09:26Z"), entity_instance.get("created").unwrap());
+    assert_eq!(Some(date_time), entity_instance.as_date_time("created").map(|d| d.with_timezone(&Utc)));
+
+    assert!(entity_instance.set_date_time_str("created", "2022-03-14T17:09:26+02:00").is_ok());
+    let stored: DateTime<FixedOffset> = entity_instance.as_date_time("created").unwrap();
+    assert_eq!(2 * 3600, stored.offset().local_minus_utc());
+    assert!(entity_instance.set_date_time_str("created", "14.03.2022").is_err());
+    assert_eq!(json!("2022-03-14T17:09:26+02:00"), entity_instance.get("created").unwrap());
+
+    entity_instance.set("created", json!(42));
+    assert!(entity_instance.as_date_time("created").is_none());
+}
+
+#[test]
+fn reactive_property_instance_date_time_test() {
+    let property_instance = ReactivePropertyInstance::new(Uuid::new_v4(), "created", json!("2022-03-14T17:09:26+02:00"));
+    let stored = property_instance.as_date_time().unwrap();
+    assert_eq!(2 * 3600, stored.offset().local_minus_utc());
+    assert_eq!(
+        Some(Utc.with_ymd_and_hms(2022, 3, 14, 15, 9, 26).unwrap()),
+        property_instance.as_date_time().map(|d| d.with_timezone(&Utc))
+    );
+    property_instance.set(json!("14.03.2022"));
+    assert!(property_instance.as_date_time().is_none());
+}
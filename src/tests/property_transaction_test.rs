@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::tests::utils::create_random_entity_instance;
+use crate::PropertyInstanceGetter;
+use crate::PropertyInstanceSetter;
+use crate::PropertyMutability;
+use crate::ReactivePropertyContainer;
+use crate::ReactivePropertyInstance;
+
+#[test]
+fn set_many_test() {
+    let entity_instance = Arc::new(create_random_entity_instance("x"));
+    entity_instance
+        .properties
+        .insert("x".into(), ReactivePropertyInstance::new(entity_instance.id, "x", json!(0)));
+    entity_instance
+        .properties
+        .insert("y".into(), ReactivePropertyInstance::new(entity_instance.id, "y", json!(0)));
+
+    // The observer of x reads y: it must see the new value of y
+    let observed_y = Arc::new(AtomicU64::new(0));
+    {
+        let observed_y = observed_y.clone();
+        let entity_instance_2 = Arc::downgrade(&entity_instance);
+        entity_instance.properties.get("x").unwrap().stream.read().unwrap().observe_with_handle(
+            move |_| {
+                let y = entity_instance_2.upgrade().unwrap().properties.get("y").unwrap().as_u64().unwrap();
+                observed_y.store(y, Ordering::SeqCst);
+            },
+            Uuid::new_v4().as_u128(),
+        );
+    }
+
+    let mut values = HashMap::new();
+    values.insert("x".into(), json!(1));
+    values.insert("y".into(), json!(2));
+    entity_instance.set_many(values);
+    assert_eq!(1, entity_instance.as_u64("x").unwrap());
+    assert_eq!(2, entity_instance.as_u64("y").unwrap());
+    assert_eq!(2, observed_y.load(Ordering::SeqCst));
+}
+
+#[test]
+fn set_many_event_and_read_only_test() {
+    let entity_instance = create_random_entity_instance("x");
+    entity_instance
+        .properties
+        .insert("event".into(), ReactivePropertyInstance::new_event(entity_instance.id, "event"));
+    let mut read_only = ReactivePropertyInstance::new(entity_instance.id, "read_only", json!(0));
+    read_only.set_mutability(PropertyMutability::ReadOnly);
+    entity_instance.properties.insert("read_only".into(), read_only);
+    // The property x is propagated last
+    entity_instance.set_tick_priority("x", -1);
+
+    let propagated = Arc::new(RwLock::new(Vec::new()));
+    for property_name in ["event", "read_only", "x"] {
+        let propagated = propagated.clone();
+        entity_instance
+            .properties
+            .get(property_name)
+            .unwrap()
+            .stream
+            .read()
+            .unwrap()
+            .observe(move |value| propagated.write().unwrap().push((property_name, value.clone())));
+    }
+
+    let mut values = HashMap::new();
+    values.insert("event".into(), json!("fired"));
+    values.insert("read_only".into(), json!(1));
+    values.insert("x".into(), json!(2));
+    entity_instance.set_many(values);
+
+    // The payload is emitted instead of being stored as value and the rejected value is not propagated
+    assert_eq!(vec![("event", json!("fired")), ("x", json!(2))], *propagated.read().unwrap());
+    let event = entity_instance.properties.get("event").unwrap();
+    assert_eq!(vec![json!("fired")], event.drain_events());
+    assert_eq!(json!(null), event.get());
+    assert_eq!(0, entity_instance.as_u64("read_only").unwrap());
+}
+
+#[test]
+fn transaction_test() {
+    let entity_instance = create_random_entity_instance("x");
+    entity_instance.add_property("y", json!(0));
+    entity_instance.set("x", json!(0));
+
+    let mut transaction = entity_instance.begin_transaction();
+    transaction.set("x", json!(1)).set("y", json!(2));
+    assert_eq!(1, *transaction.get_staged("x").unwrap());
+    // Nothing has been written before commit
+    assert_eq!(0, entity_instance.as_u64("x").unwrap());
+    transaction.commit();
+    assert_eq!(1, entity_instance.as_u64("x").unwrap());
+    assert_eq!(2, entity_instance.as_u64("y").unwrap());
+
+    let mut transaction = entity_instance.begin_transaction();
+    transaction.set("x", json!(3));
+    transaction.rollback();
+    assert_eq!(1, entity_instance.as_u64("x").unwrap());
+}
@@ -1,5 +1,5 @@
 use crate::tests::utils::r_string;
-use crate::{DataType, PropertyType, SocketType};
+use crate::{group_property_types, ConnectError, DataType, Extension, PropertyKind, PropertyMutability, PropertyType, SocketType};
 
 #[test]
 fn property_type_test() {
@@ -10,7 +10,14 @@ fn property_type_test() {
         description: String::new(),
         data_type: DataType::String,
         socket_type: SocketType::None,
+        kind: PropertyKind::ValueProperty,
+        mutability: PropertyMutability::Mutable,
+        unit: None,
+        semantic: None,
         extensions: Vec::new(),
+        order: None,
+        group: String::new(),
+        label: String::new(),
     };
 
     assert_eq!(property_name.clone(), property_type.name);
@@ -26,7 +33,14 @@ fn property_type_serde_test() {
         description: String::new(),
         data_type: DataType::String,
         socket_type: SocketType::None,
+        kind: PropertyKind::ValueProperty,
+        mutability: PropertyMutability::Mutable,
+        unit: None,
+        semantic: None,
         extensions: Vec::new(),
+        order: None,
+        group: String::new(),
+        label: String::new(),
     };
 
     let result = serde_json::to_string_pretty(&property_type.clone());
@@ -75,3 +89,129 @@ fn property_type_output_socket_test() {
     assert_eq!(DataType::String, property_type.data_type);
     assert_eq!(SocketType::Output, property_type.socket_type);
 }
+
+#[test]
+fn property_type_is_connectable_to_test() {
+    let output = PropertyType::output(r_string(), DataType::Number);
+    let input = PropertyType::input(r_string(), DataType::Number);
+    assert!(output.is_connectable_to(&input).is_ok());
+    assert_eq!(Err(ConnectError::NotAnOutput(input.name.clone(), SocketType::Input)), input.is_connectable_to(&output));
+    let hidden = PropertyType::new(r_string(), DataType::Number);
+    assert_eq!(Err(ConnectError::NotAnInput(hidden.name.clone(), SocketType::None)), output.is_connectable_to(&hidden));
+
+    // Safe widening
+    assert_eq!(
+        Err(ConnectError::IncompatibleDataTypes(DataType::Bool, DataType::Number)),
+        PropertyType::output(r_string(), DataType::Bool).is_connectable_to(&input)
+    );
+    assert_eq!(
+        Err(ConnectError::IncompatibleDataTypes(DataType::Any, DataType::Number)),
+        PropertyType::output(r_string(), DataType::Any).is_connectable_to(&input)
+    );
+    let string_input = PropertyType::input(r_string(), DataType::String);
+    assert!(PropertyType::output(r_string(), DataType::Uuid).is_connectable_to(&string_input).is_ok());
+    assert!(output.is_connectable_to(&PropertyType::input(r_string(), DataType::Any)).is_ok());
+    assert_eq!(
+        Err(ConnectError::IncompatibleDataTypes(DataType::Number, DataType::String)),
+        output.is_connectable_to(&string_input)
+    );
+    assert!(PropertyType::output(r_string(), DataType::String)
+        .is_connectable_to(&PropertyType::input(r_string(), DataType::Uuid))
+        .is_err());
+
+    // Event properties are only connectable to event properties
+    let event_output = PropertyType::event(r_string(), DataType::Object, SocketType::Output);
+    let event_input = PropertyType::event(r_string(), DataType::Object, SocketType::Input);
+    assert!(event_output.is_event());
+    assert!(event_output.is_connectable_to(&event_input).is_ok());
+    assert_eq!(
+        Err(ConnectError::IncompatibleKinds(PropertyKind::EventProperty, PropertyKind::ValueProperty)),
+        event_output.is_connectable_to(&PropertyType::input(r_string(), DataType::Object))
+    );
+}
+
+#[test]
+fn property_type_presentation_test() {
+    let property_type: PropertyType = serde_json::from_value(serde_json::json!({ "name": "x", "data_type": "number" })).unwrap();
+    assert_eq!(None, property_type.order);
+    assert!(property_type.group.is_empty());
+    assert_eq!("x", property_type.get_label());
+
+    let property_type = |name: &str, group: &str, order: Option<i32>| PropertyType {
+        order,
+        group: group.to_string(),
+        label: name.to_uppercase(),
+        ..PropertyType::new(name, DataType::Number)
+    };
+    let property_types = vec![
+        property_type("z", "transform", None),
+        property_type("y", "transform", Some(2)),
+        property_type("name", "", None),
+        property_type("x", "transform", Some(1)),
+        property_type("b", "transform", None),
+    ];
+    assert_eq!("X", property_types[3].get_label());
+    let groups = group_property_types(&property_types);
+    assert_eq!(2, groups.len());
+    assert_eq!("transform", groups[0].0);
+    let names: Vec<&str> = groups[0].1.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(vec!["x", "y", "b", "z"], names);
+    assert_eq!("", groups[1].0);
+    assert_eq!(1, groups[1].1.len());
+}
+
+#[test]
+fn property_type_unit_test() {
+    let meters = PropertyType::output("distance", DataType::Number).with_unit("meters").with_semantic("length");
+    assert_eq!(Some("meters".to_string()), meters.unit);
+    assert_eq!(Some("length".to_string()), meters.semantic);
+
+    let input = PropertyType::input("input", DataType::Number);
+    assert!(meters.is_unit_compatible(&input));
+    assert!(meters.is_connectable_to(&input).is_ok());
+    assert!(meters.is_connectable_to(&input.clone().with_unit("meters")).is_ok());
+    assert_eq!(
+        Err(ConnectError::IncompatibleUnits("meters".to_string(), "degrees".to_string())),
+        meters.is_connectable_to(&input.with_unit("degrees"))
+    );
+
+    let property_type: PropertyType = serde_json::from_value(serde_json::json!({ "name": "delay", "data_type": "number", "unit": "ms" })).unwrap();
+    assert_eq!(Some("ms".to_string()), property_type.unit);
+    assert_eq!(None, property_type.semantic);
+}
+
+#[test]
+fn property_type_extension_test() {
+    let property_type = PropertyType::input("speed", DataType::Number)
+        .with_extension(Extension::new("slider", serde_json::json!({ "min": 0, "max": 10 })))
+        .with_extension(Extension::new("slider", serde_json::json!({ "min": 0, "max": 100 })));
+    assert_eq!(1, property_type.extensions.len());
+    assert!(property_type.has_extension("slider"));
+    assert!(!property_type.has_extension("color_picker"));
+    assert_eq!(
+        serde_json::json!({ "min": 0, "max": 100 }),
+        property_type.get_extension::<serde_json::Value, _>("slider").unwrap()
+    );
+
+    let property_type: PropertyType = serde_json::from_value(serde_json::json!({
+        "name": "speed",
+        "data_type": "number",
+        "extensions": [ { "name": "slider", "extension": { "min": 0, "max": 10 } } ]
+    }))
+    .unwrap();
+    assert!(property_type.has_extension("slider"));
+}
+
+#[test]
+fn property_type_mutability_test() {
+    let property_type: PropertyType = serde_json::from_value(serde_json::json!({ "name": "x", "data_type": "number" })).unwrap();
+    assert_eq!(PropertyMutability::Mutable, property_type.mutability);
+    assert!(!property_type.is_immutable());
+
+    let property_type = PropertyType::new("id", DataType::String).immutable();
+    assert!(property_type.is_immutable());
+    let json = serde_json::to_value(&property_type).unwrap();
+    assert_eq!("immutable", json["mutability"]);
+    let property_type: PropertyType = serde_json::from_value(json).unwrap();
+    assert_eq!(PropertyMutability::Immutable, property_type.mutability);
+}
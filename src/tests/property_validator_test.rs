@@ -0,0 +1,68 @@
+use serde_json::json;
+
+use crate::tests::utils::create_random_entity_instance;
+use crate::{
+    register_validator, unregister_validators, validate_value, DataType, Extension, PropertyInstanceGetter, PropertyInstanceSetter, PropertyType,
+    ValidationError, ValidatorKey,
+};
+
+#[test]
+fn validate_value_test() {
+    register_validator("test_email", |_, value| match value.as_str() {
+        Some(email) if email.contains('@') => Ok(()),
+        _ => Err("not an email address".to_string()),
+    });
+    register_validator("test_range", |property_type, value| {
+        let max: f64 = property_type.get_extension("test_range").map_err(|e| e.to_string())?;
+        match value.as_f64() {
+            Some(value) if value <= max => Ok(()),
+            _ => Err(format!("greater than {}", max)),
+        }
+    });
+    let mut email = PropertyType::new("email", DataType::String);
+    email.extensions.push(Extension::new("test_email", json!(true)));
+    let mut volume = PropertyType::new("volume", DataType::Number);
+    volume.extensions.push(Extension::new("test_range", json!(10)));
+
+    assert!(validate_value(&email, &json!("a@b.c")).is_ok());
+    assert_eq!(
+        Err(ValidationError::InvalidValue {
+            property_name: "email".to_string(),
+            reason: "not an email address".to_string()
+        }),
+        validate_value(&email, &json!("abc"))
+    );
+    assert_eq!(
+        Err(ValidationError::InvalidDataType {
+            property_name: "email".to_string(),
+            expected: DataType::String
+        }),
+        validate_value(&email, &json!(1))
+    );
+    assert!(validate_value(&volume, &json!(10)).is_ok());
+    assert!(validate_value(&volume, &json!(11)).is_err());
+    // Validators only apply to property types with the extension
+    assert!(validate_value(&PropertyType::new("name", DataType::String), &json!("abc")).is_ok());
+
+    unregister_validators("test_email");
+    assert!(validate_value(&email, &json!("abc")).is_ok());
+    unregister_validators(ValidatorKey::Extension("test_range".to_string()));
+}
+
+#[test]
+fn set_checked_test() {
+    register_validator("test_color", |_, value| match value.as_str() {
+        Some(color) if color.starts_with('#') && color.len() == 7 => Ok(()),
+        _ => Err("not a color".to_string()),
+    });
+    let mut color = PropertyType::new("color", DataType::String);
+    color.extensions.push(Extension::new("test_color", json!(true)));
+    let entity_instance = create_random_entity_instance("color");
+
+    assert!(entity_instance.set_checked(&color, json!("#ff0000")).is_ok());
+    assert_eq!(json!("#ff0000"), entity_instance.get("color").unwrap());
+    assert!(entity_instance.set_checked(&color, json!("red")).is_err());
+    assert!(entity_instance.set_checked(&color, json!(1)).is_err());
+    assert_eq!(json!("#ff0000"), entity_instance.get("color").unwrap());
+    unregister_validators("test_color");
+}
@@ -0,0 +1,30 @@
+use futures::executor::block_on;
+use futures::StreamExt;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::tests::utils::r_string;
+use crate::ReactivePropertyInstance;
+
+#[test]
+fn property_value_stream_test() {
+    let property_instance = ReactivePropertyInstance::new(Uuid::new_v4(), r_string(), json!(0));
+    let mut stream = property_instance.as_stream();
+    property_instance.set(json!(1));
+    property_instance.set(json!(2));
+    property_instance.set_no_propagate(json!(3));
+    property_instance.tick();
+    assert_eq!(json!(1), block_on(stream.next()).unwrap());
+    assert_eq!(json!(2), block_on(stream.next()).unwrap());
+    assert_eq!(json!(3), block_on(stream.next()).unwrap());
+
+    // Dropping the stream removes the observer
+    let mut other_stream = property_instance.as_stream();
+    assert_eq!(2, property_instance.prioritized_observers.len());
+    drop(stream);
+    assert_eq!(1, property_instance.prioritized_observers.len());
+    property_instance.set(json!(4));
+    assert_eq!(json!(4), block_on(other_stream.next()).unwrap());
+    drop(other_stream);
+    assert!(property_instance.prioritized_observers.is_empty());
+}
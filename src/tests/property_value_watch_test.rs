@@ -0,0 +1,23 @@
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::tests::utils::r_string;
+use crate::ReactivePropertyInstance;
+
+#[test]
+fn property_value_watch_test() {
+    let property_instance = ReactivePropertyInstance::new(Uuid::new_v4(), r_string(), json!(0));
+    let receiver = property_instance.watch();
+    assert_eq!(json!(0), *receiver.borrow());
+    property_instance.set(json!(1));
+    assert_eq!(json!(1), *receiver.borrow());
+    property_instance.set(json!(2));
+    assert_eq!(json!(2), *receiver.borrow());
+    assert_eq!(1, property_instance.prioritized_observers.len());
+
+    // Dropping the receiver removes the observer
+    drop(receiver);
+    property_instance.set(json!(3));
+    assert!(property_instance.prioritized_observers.is_empty());
+    property_instance.set(json!(4));
+}
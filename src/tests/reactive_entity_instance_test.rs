@@ -1,25 +1,44 @@
 extern crate test;
 
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::process::Termination;
+#[cfg(feature = "graph-db")]
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
 use test::Bencher;
 
 use dashmap::DashMap;
 use dashmap::DashSet;
-use indradb::Identifier;
-use indradb::NamedProperty;
-use indradb::Vertex;
-use indradb::VertexProperties;
+#[cfg(feature = "graph-db")]
+use indradb::{Identifier, NamedProperty, Vertex, VertexProperties};
 use serde_json::json;
 use uuid::Uuid;
 
 use crate::tests::utils::{create_random_entity_instance, r_json_string, r_string};
+use crate::BehaviourName;
+use crate::Component;
+use crate::ComponentName;
+use crate::DataType;
 use crate::EntityInstance;
+use crate::EntityType;
+use crate::EntityTypeName;
+use crate::InstanceEvent;
 use crate::PropertyInstanceGetter;
 use crate::PropertyInstanceSetter;
+use crate::PropertyMutability;
+use crate::PropertyType;
 use crate::ReactiveEntityInstance;
+use crate::ReactivePropertyContainer;
 use crate::ReactivePropertyInstance;
+use crate::Revision;
+use crate::RevisionError;
+use crate::SetPropertyError;
+use crate::SocketType;
+use crate::StrictPropertyMode;
+use crate::UnknownPropertyError;
+use crate::UnknownPropertyPolicy;
 
 #[test]
 fn reactive_entity_instance_test() {
@@ -31,47 +50,52 @@ fn reactive_entity_instance_test() {
 
     let properties = DashMap::new();
     properties.insert(
-        property_name.clone(),
+        property_name.as_str().into(),
         ReactivePropertyInstance::new(Uuid::new_v4(), property_name.clone(), property_value.clone()),
     );
 
     let component_name = r_string();
     let component_name_2 = r_string();
-    let components = DashSet::new();
-    components.insert(component_name.clone());
+    let components: DashSet<ComponentName> = DashSet::new();
+    components.insert(ComponentName::new(component_name.clone()));
 
     let behaviour_name = r_string();
     let behaviour_name_2 = r_string();
-    let behaviours = DashSet::new();
-    behaviours.insert(behaviour_name.clone());
+    let behaviours: DashSet<BehaviourName> = DashSet::new();
+    behaviours.insert(BehaviourName::new(behaviour_name.clone()));
 
     let reactive_entity_instance = Arc::new(ReactiveEntityInstance {
-        type_name: type_name.clone(),
+        type_name: EntityTypeName::new(type_name.clone()),
         id: uuid.clone(),
-        description: description.clone(),
+        description: description.clone().into(),
         properties,
         components,
+        labels: DashSet::new(),
         behaviours,
+        behaviour_states: DashMap::new(),
+        strict_mode: StrictPropertyMode::default(),
+        revision: Revision::default(),
+        observers: Arc::default(),
     });
     assert_eq!(type_name.clone(), reactive_entity_instance.type_name.clone());
     assert_eq!(uuid.clone(), reactive_entity_instance.id.clone());
     assert_eq!(description.clone(), reactive_entity_instance.description.clone());
 
-    assert!(reactive_entity_instance.is_a(component_name.clone()));
-    assert!(!reactive_entity_instance.is_a(component_name_2.clone()));
-    assert!(!reactive_entity_instance.is_a(r_string()));
-    reactive_entity_instance.add_component(component_name_2.clone());
-    assert!(reactive_entity_instance.is_a(component_name_2.clone()));
-    reactive_entity_instance.remove_component(component_name.clone());
-    assert!(!reactive_entity_instance.is_a(component_name.clone()));
-
-    assert!(reactive_entity_instance.behaves_as(behaviour_name.clone()));
-    assert!(!reactive_entity_instance.behaves_as(behaviour_name_2.clone()));
-    assert!(!reactive_entity_instance.behaves_as(r_string()));
-    reactive_entity_instance.add_behaviour(behaviour_name_2.clone());
-    assert!(reactive_entity_instance.behaves_as(behaviour_name_2.clone()));
-    reactive_entity_instance.remove_behaviour(behaviour_name.clone());
-    assert!(!reactive_entity_instance.behaves_as(behaviour_name.clone()));
+    assert!(reactive_entity_instance.is_a(ComponentName::new(component_name.clone())));
+    assert!(!reactive_entity_instance.is_a(ComponentName::new(component_name_2.clone())));
+    assert!(!reactive_entity_instance.is_a(ComponentName::new(r_string())));
+    reactive_entity_instance.add_component(ComponentName::new(component_name_2.clone()));
+    assert!(reactive_entity_instance.is_a(ComponentName::new(component_name_2.clone())));
+    reactive_entity_instance.remove_component(ComponentName::new(component_name.clone()));
+    assert!(!reactive_entity_instance.is_a(ComponentName::new(component_name.clone())));
+
+    assert!(reactive_entity_instance.behaves_as(BehaviourName::new(behaviour_name.clone())));
+    assert!(!reactive_entity_instance.behaves_as(BehaviourName::new(behaviour_name_2.clone())));
+    assert!(!reactive_entity_instance.behaves_as(BehaviourName::new(r_string())));
+    reactive_entity_instance.add_behaviour(BehaviourName::new(behaviour_name_2.clone()));
+    assert!(reactive_entity_instance.behaves_as(BehaviourName::new(behaviour_name_2.clone())));
+    reactive_entity_instance.remove_behaviour(BehaviourName::new(behaviour_name.clone()));
+    assert!(!reactive_entity_instance.behaves_as(BehaviourName::new(behaviour_name.clone())));
 
     let entity_instance: EntityInstance = reactive_entity_instance.into();
     assert_eq!(type_name.clone(), entity_instance.type_name.clone());
@@ -81,6 +105,7 @@ fn reactive_entity_instance_test() {
     assert_eq!(property_value, *entity_instance.properties.get(property_name.as_str()).unwrap());
 }
 
+#[cfg(feature = "graph-db")]
 #[test]
 fn reactive_entity_instance_from_vertex_properties_test() {
     let uuid = Uuid::new_v4();
@@ -101,7 +126,7 @@ fn reactive_entity_instance_from_vertex_properties_test() {
     let reactive_entity_instance = Arc::new(ReactiveEntityInstance::from(vertex_properties));
     assert_eq!(type_name.clone(), reactive_entity_instance.type_name.clone());
     assert_eq!(uuid.clone(), reactive_entity_instance.id.clone());
-    assert_eq!(property_name.clone(), reactive_entity_instance.properties.get(property_name.as_str()).unwrap().name);
+    assert_eq!(property_name.as_str(), reactive_entity_instance.properties.get(property_name.as_str()).unwrap().name.as_ref());
     assert_eq!(property_value.clone(), reactive_entity_instance.properties.get(property_name.as_str()).unwrap().get());
 
     let entity_instance: EntityInstance = reactive_entity_instance.into();
@@ -153,6 +178,17 @@ fn reactive_entity_instance_typed_eq_bool_test() {
     assert_eq!(instance1.get(property_name.clone()), instance2.get(property_name.clone()));
 }
 
+#[test]
+fn reactive_entity_instance_tick_order_test() {
+    let instance = create_random_entity_instance("b");
+    instance.add_property("c", json!(0));
+    instance.add_property("a", json!(0));
+    assert_eq!(vec!["a".into(), "b".into(), "c".into()] as Vec<Arc<str>>, instance.get_tick_order());
+    instance.set_tick_priority("c", 10);
+    instance.set_tick_priority("a", -1);
+    assert_eq!(vec!["c".into(), "b".into(), "a".into()] as Vec<Arc<str>>, instance.get_tick_order());
+}
+
 #[bench]
 fn create_reactive_entity_instance_benchmark(bencher: &mut Bencher) -> impl Termination {
     let uuid = Uuid::new_v4();
@@ -164,25 +200,368 @@ fn create_reactive_entity_instance_benchmark(bencher: &mut Bencher) -> impl Term
     bencher.iter(move || {
         let properties = DashMap::new();
         properties.insert(
-            property_name.clone(),
+            property_name.as_str().into(),
             ReactivePropertyInstance::new(Uuid::new_v4(), property_name.clone(), property_value.clone()),
         );
 
         let component_name = r_string();
-        let components = DashSet::new();
-        components.insert(component_name.clone());
+        let components: DashSet<ComponentName> = DashSet::new();
+        components.insert(ComponentName::new(component_name.clone()));
 
         let behaviour_name = r_string();
-        let behaviours = DashSet::new();
-        behaviours.insert(behaviour_name.clone());
+        let behaviours: DashSet<BehaviourName> = DashSet::new();
+        behaviours.insert(BehaviourName::new(behaviour_name.clone()));
 
         let _reactive_entity_instance = Arc::new(ReactiveEntityInstance {
-            type_name: type_name.clone(),
+            type_name: EntityTypeName::new(type_name.clone()),
             id: uuid.clone(),
-            description: description.clone(),
+            description: description.clone().into(),
             properties,
             components,
+            labels: DashSet::new(),
             behaviours,
+            behaviour_states: DashMap::new(),
+            strict_mode: StrictPropertyMode::default(),
+            revision: Revision::default(),
+            observers: Arc::default(),
         });
     })
 }
+
+#[bench]
+fn reactive_entity_instance_as_f64_benchmark(bencher: &mut Bencher) -> impl Termination {
+    let reactive_entity_instance = create_random_entity_instance("a");
+    reactive_entity_instance.add_property("x", json!(0.5));
+    bencher.iter(move || {
+        assert_eq!(Some(0.5), reactive_entity_instance.as_f64("x"));
+    })
+}
+
+#[bench]
+fn reactive_entity_instance_as_string_benchmark(bencher: &mut Bencher) -> impl Termination {
+    let reactive_entity_instance = create_random_entity_instance("a");
+    reactive_entity_instance.add_property("x", json!({ "values": vec![r_string(); 64], "name": r_string() }));
+    reactive_entity_instance.add_property("y", json!(r_string()));
+    bencher.iter(move || {
+        assert!(reactive_entity_instance.as_string("y").is_some());
+        assert!(reactive_entity_instance.as_string("x").is_none());
+    })
+}
+
+#[test]
+fn reactive_entity_instance_revision_test() {
+    let property_name = r_string();
+    let reactive_entity_instance = create_random_entity_instance(property_name.clone());
+    assert_eq!(0, reactive_entity_instance.revision());
+    reactive_entity_instance.set(property_name.clone(), json!(1));
+    assert_eq!(1, reactive_entity_instance.revision());
+    reactive_entity_instance.add_component(ComponentName::new(r_string()));
+    reactive_entity_instance.add_property(r_string(), json!(true));
+    assert_eq!(3, reactive_entity_instance.revision());
+
+    assert_eq!(Ok(4), reactive_entity_instance.set_if_revision(property_name.clone(), json!(2), 3));
+    assert_eq!(json!(2), reactive_entity_instance.get(property_name.clone()).unwrap());
+    assert_eq!(
+        Err(RevisionError::Mismatch(3, 4)),
+        reactive_entity_instance.set_if_revision(property_name.clone(), json!(3), 3)
+    );
+    assert_eq!(json!(2), reactive_entity_instance.get(property_name.clone()).unwrap());
+    let missing = r_string();
+    assert_eq!(
+        Err(RevisionError::MissingProperty(missing.clone())),
+        reactive_entity_instance.set_if_revision(missing, json!(3), 4)
+    );
+    assert_eq!(4, reactive_entity_instance.revision());
+
+    // Setting the value of the property instance directly bumps the revision of the instance
+    reactive_entity_instance.properties.get(property_name.as_str()).unwrap().set(json!(5));
+    assert_eq!(5, reactive_entity_instance.revision());
+    // Rejected values don't bump the revision
+    reactive_entity_instance
+        .properties
+        .get_mut(property_name.as_str())
+        .unwrap()
+        .set_mutability(PropertyMutability::ReadOnly);
+    reactive_entity_instance.set(property_name.clone(), json!(6));
+    assert_eq!(
+        Err(RevisionError::Rejected(property_name.clone())),
+        reactive_entity_instance.set_if_revision(property_name.clone(), json!(6), 5)
+    );
+    assert_eq!(5, reactive_entity_instance.revision());
+    assert_eq!(json!(5), reactive_entity_instance.get(property_name.clone()).unwrap());
+}
+
+#[test]
+fn reactive_entity_instance_new_from_type_test() {
+    let labeled = Component::new(
+        ComponentName::new("labeled"),
+        vec![PropertyType::new("label", DataType::String), PropertyType::new("visible", DataType::Bool)],
+    );
+    let unused = Component::new(ComponentName::new("unused"), vec![PropertyType::new("unused", DataType::Number)]);
+    let entity_type = EntityType::new(
+        EntityTypeName::new("sprite"),
+        "",
+        "",
+        vec![ComponentName::new("labeled"), ComponentName::new("missing")],
+        vec![PropertyType::new("visible", DataType::Number), PropertyType::new("position", DataType::Array)],
+        Vec::new(),
+    );
+    let reactive_entity_instance = ReactiveEntityInstance::new_from_type(&entity_type, &[labeled, unused]);
+    assert_eq!("sprite", reactive_entity_instance.type_name);
+    assert_eq!(3, reactive_entity_instance.properties.len());
+    assert_eq!(json!(""), reactive_entity_instance.get("label").unwrap());
+    assert_eq!(json!(0), reactive_entity_instance.get("visible").unwrap());
+    assert_eq!(json!([]), reactive_entity_instance.get("position").unwrap());
+    assert!(reactive_entity_instance.get("unused").is_none());
+    assert!(reactive_entity_instance.is_a(ComponentName::new("labeled")));
+    assert!(reactive_entity_instance.is_a(ComponentName::new("missing")));
+    assert_ne!(reactive_entity_instance.id, ReactiveEntityInstance::new_from_type(&entity_type, &[]).id);
+}
+
+#[test]
+fn reactive_entity_instance_sockets_test() {
+    let entity_type = EntityType::new(
+        EntityTypeName::new("add"),
+        "",
+        "",
+        Vec::new(),
+        vec![
+            PropertyType::input("lhs", DataType::Number),
+            PropertyType::input("rhs", DataType::Number),
+            PropertyType::output("result", DataType::Number),
+            PropertyType::new("label", DataType::String),
+        ],
+        Vec::new(),
+    );
+    let reactive_entity_instance = ReactiveEntityInstance::new_from_type(&entity_type, &[]);
+    let mut inputs: Vec<String> = reactive_entity_instance.inputs().map(|p| p.key().to_string()).collect();
+    inputs.sort();
+    assert_eq!(vec!["lhs", "rhs"], inputs);
+    let outputs: Vec<String> = reactive_entity_instance.outputs().map(|p| p.key().to_string()).collect();
+    assert_eq!(vec!["result"], outputs);
+    assert!(reactive_entity_instance.duplicate(Uuid::new_v4()).properties.get("result").unwrap().is_output());
+
+    reactive_entity_instance.set_socket_type("label", SocketType::Output);
+    assert_eq!(2, reactive_entity_instance.outputs().count());
+}
+
+#[test]
+fn reactive_entity_instance_event_property_test() {
+    let entity_type = EntityType::new(
+        EntityTypeName::new("button"),
+        "",
+        "",
+        Vec::new(),
+        vec![
+            PropertyType::input("label", DataType::String),
+            PropertyType::event("clicked", DataType::Object, SocketType::Output),
+        ],
+        Vec::new(),
+    );
+    let reactive_entity_instance = ReactiveEntityInstance::new_from_type(&entity_type, &[]);
+    assert!(reactive_entity_instance.properties.get("clicked").unwrap().is_event());
+    assert!(!reactive_entity_instance.properties.get("label").unwrap().is_event());
+
+    reactive_entity_instance.emit("clicked", json!({ "x": 1 }));
+    reactive_entity_instance.set("clicked", json!({ "x": 2 }));
+    assert_eq!(vec![json!({ "x": 1 }), json!({ "x": 2 })], reactive_entity_instance.drain_events("clicked"));
+    assert!(reactive_entity_instance.drain_events("clicked").is_empty());
+    assert_eq!(json!(null), reactive_entity_instance.get("clicked").unwrap());
+
+    reactive_entity_instance.add_event_property("pressed");
+    assert!(reactive_entity_instance.properties.get("pressed").unwrap().is_event());
+    assert!(reactive_entity_instance.drain_events("missing").is_empty());
+}
+
+#[test]
+fn reactive_entity_instance_set_description_test() {
+    let reactive_entity_instance = create_random_entity_instance(r_string());
+    let descriptions = Arc::new(std::sync::RwLock::new(Vec::new()));
+    let descriptions_2 = descriptions.clone();
+    reactive_entity_instance
+        .description
+        .observe_with_handle(move |description| descriptions_2.write().unwrap().push(description.clone()), 1);
+
+    let revision = reactive_entity_instance.revision();
+    reactive_entity_instance.set_description("The player");
+    assert_eq!("The player", reactive_entity_instance.description);
+    assert_eq!(revision + 1, reactive_entity_instance.revision());
+    assert_eq!("The player", EntityInstance::from(Arc::new(reactive_entity_instance)).description);
+    assert_eq!(vec!["The player".to_string()], *descriptions.read().unwrap());
+}
+
+#[test]
+fn reactive_entity_instance_unknown_property_policy_test() {
+    let reactive_entity_instance = create_random_entity_instance("value");
+    reactive_entity_instance.set("valeu", json!(1));
+    assert!(reactive_entity_instance.get("valeu").is_none());
+    assert_eq!(Ok(()), reactive_entity_instance.try_set("valeu", json!(1)));
+
+    reactive_entity_instance.set_unknown_property_policy(UnknownPropertyPolicy::Create);
+    reactive_entity_instance.set("created", json!(2));
+    assert_eq!(json!(2), reactive_entity_instance.get("created").unwrap());
+
+    reactive_entity_instance.set_unknown_property_policy(UnknownPropertyPolicy::Error);
+    assert_eq!(Err(UnknownPropertyError("valeu".to_string()).into()), reactive_entity_instance.try_set("valeu", json!(3)));
+    assert_eq!(Ok(()), reactive_entity_instance.try_set("value", json!(3)));
+    assert_eq!(json!(3), reactive_entity_instance.get("value").unwrap());
+}
+
+#[test]
+fn reactive_entity_instance_try_set_immutable_test() {
+    let entity_type = EntityType::new(
+        EntityTypeName::new("account"),
+        "",
+        "",
+        Vec::new(),
+        vec![PropertyType::new("id", DataType::String).immutable()],
+        Vec::new(),
+    );
+    let reactive_entity_instance = ReactiveEntityInstance::new_from_type(&entity_type, &[]);
+    assert_eq!(Ok(()), reactive_entity_instance.try_set("id", json!("a")));
+    assert_eq!(Err(SetPropertyError::NotWritable("id".to_string())), reactive_entity_instance.try_set("id", json!("b")));
+    assert_eq!(json!("a"), reactive_entity_instance.get("id").unwrap());
+
+    // The mutability isn't part of the entity instance
+    let reactive_entity_instance = ReactiveEntityInstance::from(EntityInstance::from(Arc::new(reactive_entity_instance)));
+    assert_eq!(Ok(()), reactive_entity_instance.try_set("id", json!("b")));
+}
+
+#[test]
+fn reactive_entity_instance_strict_set_test() {
+    let reactive_entity_instance = create_random_entity_instance("value");
+    reactive_entity_instance.set_unknown_property_policy(UnknownPropertyPolicy::Error);
+    let rejected = Arc::new(RwLock::new(Vec::new()));
+    let r = rejected.clone();
+    reactive_entity_instance.observe_events(move |event| {
+        if let InstanceEvent::UnknownPropertyRejected { name, .. } = event {
+            r.write().unwrap().push(name.clone());
+        }
+    });
+    // The infallible setters don't panic, the dropped values are reported to the event observers
+    reactive_entity_instance.set("valeu", json!(1));
+    reactive_entity_instance.set_no_propagate("valeu", json!(1));
+    reactive_entity_instance.set_many(HashMap::from([("valeu".to_string(), json!(1)), ("value".to_string(), json!(2))]));
+    assert!(reactive_entity_instance.get("valeu").is_none());
+    assert_eq!(json!(2), reactive_entity_instance.get("value").unwrap());
+    assert_eq!(vec!["valeu"; 3], *rejected.read().unwrap());
+}
+
+#[test]
+fn reactive_entity_instance_dirty_test() {
+    let reactive_entity_instance = create_random_entity_instance("a");
+    reactive_entity_instance.add_property("b", json!(0));
+    reactive_entity_instance.add_property("c", json!(0));
+    assert!(!reactive_entity_instance.is_dirty());
+
+    reactive_entity_instance.set("c", json!(1));
+    reactive_entity_instance.set_no_propagate("a", json!(1));
+    assert!(reactive_entity_instance.is_dirty());
+    assert_eq!(vec!["a".to_string(), "c".to_string()], reactive_entity_instance.changed_properties());
+
+    reactive_entity_instance.tick();
+    assert!(!reactive_entity_instance.is_dirty());
+
+    reactive_entity_instance.set("b", json!(1));
+    assert_eq!(vec!["b".to_string()], reactive_entity_instance.changed_properties());
+    reactive_entity_instance.clear_dirty();
+    assert!(reactive_entity_instance.changed_properties().is_empty());
+}
+
+#[test]
+fn reactive_entity_instance_last_modified_test() {
+    let reactive_entity_instance = create_random_entity_instance("a");
+    reactive_entity_instance.add_property("b", json!(0));
+    assert!(reactive_entity_instance.last_modified().is_none());
+
+    let before = Instant::now();
+    reactive_entity_instance.set("a", json!(1));
+    let a_modified = reactive_entity_instance.properties.get("a").unwrap().last_modified().unwrap();
+    assert!(a_modified >= before);
+    assert!(reactive_entity_instance.properties.get("b").unwrap().last_modified().is_none());
+
+    reactive_entity_instance.set_no_propagate("b", json!(1));
+    let b_modified = reactive_entity_instance.properties.get("b").unwrap().last_modified().unwrap();
+    assert!(b_modified >= a_modified);
+    assert_eq!(Some(b_modified), reactive_entity_instance.last_modified());
+}
+
+#[test]
+fn reactive_entity_instance_duplicate_test() {
+    let reactive_entity_instance = create_random_entity_instance("a");
+    reactive_entity_instance.add_component(ComponentName::new("named"));
+    reactive_entity_instance.add_behaviour(BehaviourName::new("logger"));
+    reactive_entity_instance.set_value_history("a", 3);
+    let values = Arc::new(std::sync::RwLock::new(Vec::new()));
+    let values_2 = values.clone();
+    reactive_entity_instance
+        .properties
+        .get("a")
+        .unwrap()
+        .stream
+        .read()
+        .unwrap()
+        .observe_with_handle(move |value| values_2.write().unwrap().push(value.clone()), 1);
+
+    let id = Uuid::new_v4();
+    let duplicate = reactive_entity_instance.duplicate(id);
+    assert_eq!(id, duplicate.id);
+    assert_eq!(reactive_entity_instance.type_name, duplicate.type_name);
+    assert_eq!(reactive_entity_instance.get("a"), duplicate.get("a"));
+    assert_eq!(id, duplicate.properties.get("a").unwrap().id);
+    assert!(duplicate.is_a(ComponentName::new("named")));
+    assert!(!duplicate.behaves_as(BehaviourName::new("logger")));
+    assert_eq!(3, duplicate.properties.get("a").unwrap().value_history.capacity);
+
+    duplicate.set("a", json!("duplicate"));
+    assert_ne!(Some(json!("duplicate")), reactive_entity_instance.get("a"));
+    assert!(values.read().unwrap().is_empty());
+    reactive_entity_instance.set("a", json!("original"));
+    assert_eq!(Some(json!("duplicate")), duplicate.get("a"));
+    assert_eq!(vec![json!("original")], *values.read().unwrap());
+}
+
+#[test]
+fn reactive_entity_instance_components_round_trip_test() {
+    let reactive_entity_instance = Arc::new(create_random_entity_instance(r_string()));
+    reactive_entity_instance.add_component(ComponentName::new("movable"));
+    reactive_entity_instance.add_component(ComponentName::new("labeled"));
+    let entity_instance: EntityInstance = reactive_entity_instance.clone().into();
+    assert_eq!(vec!["labeled".to_string(), "movable".to_string()], entity_instance.components);
+
+    let json = serde_json::to_value(&entity_instance).unwrap();
+    assert_eq!(json!(["labeled", "movable"]), json["components"]);
+    let entity_instance: EntityInstance = serde_json::from_value(json).unwrap();
+    let restored = ReactiveEntityInstance::from(entity_instance);
+    assert!(restored.is_a(ComponentName::new("labeled")));
+    assert!(restored.is_a(ComponentName::new("movable")));
+
+    let entity_instance: EntityInstance = Arc::new(create_random_entity_instance(r_string())).into();
+    assert!(serde_json::to_value(&entity_instance).unwrap().get("components").is_none());
+}
+
+#[test]
+// The hash only depends on the identity, which is immutable
+#[allow(clippy::mutable_key_type)]
+fn reactive_entity_instance_identity_test() {
+    let entity_instance = EntityInstance::new_without_properties(EntityTypeName::new("player"), Uuid::new_v4());
+    let a = Arc::new(ReactiveEntityInstance::from(entity_instance.clone()));
+    let b = Arc::new(ReactiveEntityInstance::from(entity_instance));
+    let c = Arc::new(create_random_entity_instance(r_string()));
+    assert!(a == b);
+    assert!(a != c);
+    assert!(a.same_instance(&a.clone()));
+    assert!(!a.same_instance(&b));
+
+    let deduplicated: HashSet<Arc<ReactiveEntityInstance>> = vec![a.clone(), b, a, c].into_iter().collect();
+    assert_eq!(2, deduplicated.len());
+}
+
+#[test]
+fn reactive_entity_instance_get_by_pointer_test() {
+    let reactive_entity_instance = create_random_entity_instance("config");
+    reactive_entity_instance.set("config", json!({ "window": { "width": 800 } }));
+    assert_eq!(json!(800), reactive_entity_instance.get_by_pointer("config", "/window/width").unwrap());
+    assert!(reactive_entity_instance.get_by_pointer("config", "/window/height").is_none());
+    assert!(reactive_entity_instance.get_by_pointer("missing", "/window").is_none());
+}
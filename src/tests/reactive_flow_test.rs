@@ -1,15 +1,25 @@
+use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::convert::TryInto;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
-use serde_json::json;
+use dashmap::DashMap;
+use serde_json::{json, Value};
 use uuid::Uuid;
 
-use crate::tests::utils::{create_random_entity_instance, create_random_entity_instance_with_type, create_random_relation_instance, r_string};
+use crate::tests::utils::{
+    create_random_entity_instance, create_random_entity_instance_with_type, create_random_relation_instance, create_random_relation_instance_with_properties,
+    r_string,
+};
+use crate::BehaviourName;
 use crate::Flow;
+use crate::FlowLayer;
+use crate::FlowVariable;
+use crate::FlowVariableError;
 use crate::PropertyInstanceGetter;
 use crate::PropertyInstanceSetter;
 use crate::ReactiveFlow;
+use crate::ReactivePropertyInstance;
 
 #[test]
 fn reactive_flow_test() {
@@ -115,3 +125,313 @@ fn reactive_flow_test_try_into() {
     assert_eq!(wrapper_entity_instance.id, reactive_flow_copy.id);
     assert_eq!(wrapper_entity_instance.type_name, reactive_flow_copy.type_name);
 }
+
+#[test]
+fn reactive_flow_override_test() {
+    let wrapper_entity_instance = Arc::new(create_random_entity_instance_with_type("generic_flow", "test"));
+    let reactive_flow = ReactiveFlow::new(wrapper_entity_instance.clone());
+    let authored_value = wrapper_entity_instance.get("test").unwrap();
+    assert!(!reactive_flow.has_override(wrapper_entity_instance.id, "test"));
+    assert!(!reactive_flow.set_override(wrapper_entity_instance.id, r_string(), json!(1)));
+    assert!(!reactive_flow.set_override(Uuid::new_v4(), "test", json!(1)));
+
+    assert!(reactive_flow.set_override(wrapper_entity_instance.id, "test", json!("override")));
+    assert!(reactive_flow.has_override(wrapper_entity_instance.id, "test"));
+    assert_eq!("override", reactive_flow.get_override(wrapper_entity_instance.id, "test").unwrap());
+    assert_eq!("override", reactive_flow.as_string("test").unwrap());
+    assert_eq!(1, reactive_flow.get_overrides().len());
+
+    // The override is not part of the authored flow content
+    let flow = Flow::try_from(reactive_flow).unwrap();
+    assert_eq!(authored_value, *flow.entity_instances.first().unwrap().properties.get("test").unwrap());
+
+    let wrapper_entity_instance = Arc::new(create_random_entity_instance_with_type("generic_flow", "test"));
+    let authored_value = wrapper_entity_instance.get("test").unwrap();
+    let reactive_flow = ReactiveFlow::new(wrapper_entity_instance.clone());
+    reactive_flow.set_override(wrapper_entity_instance.id, "test", json!("override"));
+    let overrides = reactive_flow.get_overrides();
+    reactive_flow.clear_override(wrapper_entity_instance.id, "test");
+    assert!(!reactive_flow.has_override(wrapper_entity_instance.id, "test"));
+    assert_eq!(authored_value, reactive_flow.get("test").unwrap());
+
+    reactive_flow.apply_overrides(overrides);
+    assert_eq!("override", reactive_flow.as_string("test").unwrap());
+    reactive_flow.clear_overrides();
+    assert!(reactive_flow.get_overrides().is_empty());
+    assert_eq!(authored_value, reactive_flow.get("test").unwrap());
+
+    // A value which is set while the override is active is restored
+    reactive_flow.set_override(wrapper_entity_instance.id, "test", json!("override"));
+    reactive_flow.set_override(wrapper_entity_instance.id, "test", json!("override 2"));
+    reactive_flow.set("test", json!("authored"));
+    // The override takes precedence over the values which are set
+    assert_eq!("override 2", reactive_flow.as_string("test").unwrap());
+    wrapper_entity_instance.set("test", json!("authored"));
+    assert_eq!("override 2", reactive_flow.as_string("test").unwrap());
+    reactive_flow.clear_override(wrapper_entity_instance.id, "test");
+    assert_eq!("authored", reactive_flow.as_string("test").unwrap());
+    reactive_flow.set("test", json!("changed"));
+    assert!(reactive_flow.overridden_values.read().unwrap().is_empty());
+    reactive_flow.set_override(wrapper_entity_instance.id, "test", json!("override"));
+    reactive_flow.set("test", json!("authored 2"));
+    let flow = Flow::try_from(reactive_flow).unwrap();
+    assert_eq!("authored 2", *flow.entity_instances.first().unwrap().properties.get("test").unwrap());
+}
+
+#[test]
+fn reactive_flow_layer_test() {
+    let wrapper_entity_instance = Arc::new(create_random_entity_instance_with_type("generic_flow", "test"));
+    let reactive_flow = ReactiveFlow::new(wrapper_entity_instance.clone());
+    let second_entity_instance = Arc::new(create_random_entity_instance(r_string()));
+    let third_entity_instance = Arc::new(create_random_entity_instance(r_string()));
+    reactive_flow.add_entity(second_entity_instance.clone());
+    reactive_flow.add_entity(third_entity_instance.clone());
+    let relation_instance = Arc::new(create_random_relation_instance_with_properties(
+        second_entity_instance.clone(),
+        third_entity_instance.clone(),
+        r_string(),
+    ));
+    reactive_flow.add_relation(relation_instance.clone());
+    let edge_key = relation_instance.get_key().unwrap();
+
+    let mut flow: Flow = reactive_flow.try_into().unwrap();
+    let mut layer = FlowLayer::new("subsystem");
+    layer.entity_instances.push(third_entity_instance.id);
+    flow.layers.push(layer);
+    assert!(flow.get_layer("subsystem").unwrap().enabled);
+    assert!(flow.is_entity_enabled(third_entity_instance.id));
+
+    // Disabling the layer excludes the entity instance and the relations from or to it
+    assert!(flow.set_layer_enabled("subsystem", false));
+    assert!(!flow.set_layer_enabled(r_string(), false));
+    assert!(!flow.is_entity_enabled(third_entity_instance.id));
+    assert!(flow.is_entity_enabled(second_entity_instance.id));
+    let reactive_flow = ReactiveFlow::try_from(flow.clone()).unwrap();
+    assert!(reactive_flow.has_entity_by_id(second_entity_instance.id));
+    assert!(!reactive_flow.has_entity_by_id(third_entity_instance.id));
+    assert!(!reactive_flow.has_relation_by_key(edge_key.clone()));
+
+    // The content of the disabled layer is kept
+    assert_eq!(3, flow.entity_instances.len());
+    assert_eq!(1, flow.relation_instances.len());
+
+    assert!(flow.set_layer_enabled("subsystem", true));
+    let reactive_flow = ReactiveFlow::try_from(flow).unwrap();
+    assert!(reactive_flow.has_entity_by_id(third_entity_instance.id));
+    assert!(reactive_flow.has_relation_by_key(edge_key));
+}
+
+#[test]
+fn reactive_flow_round_trip_test() {
+    let wrapper_entity_instance = Arc::new(create_random_entity_instance_with_type("generic_flow", "test"));
+    let reactive_flow = ReactiveFlow::new(wrapper_entity_instance.clone());
+    let second_entity_instance = Arc::new(create_random_entity_instance(r_string()));
+    let third_entity_instance = Arc::new(create_random_entity_instance(r_string()));
+    reactive_flow.add_entity(second_entity_instance.clone());
+    reactive_flow.add_entity(third_entity_instance.clone());
+    let relation_instance = Arc::new(create_random_relation_instance_with_properties(
+        second_entity_instance.clone(),
+        third_entity_instance.clone(),
+        r_string(),
+    ));
+    reactive_flow.add_relation(relation_instance.clone());
+
+    let mut flow: Flow = reactive_flow.try_into().unwrap();
+    let flow_name = r_string();
+    flow.name = flow_name.clone();
+    let mut layer = FlowLayer::new("subsystem");
+    layer.entity_instances.push(third_entity_instance.id);
+    layer.enabled = false;
+    flow.layers.push(layer);
+    flow.type_versions.insert("generic_flow".to_string(), 3);
+
+    // Flow -> ReactiveFlow -> Flow keeps the name, the layers, the type versions and the instances of disabled layers
+    let reactive_flow = ReactiveFlow::try_from(flow).unwrap();
+    assert_eq!(flow_name, reactive_flow.name);
+    assert!(!reactive_flow.has_entity_by_id(third_entity_instance.id));
+    let flow: Flow = reactive_flow.try_into().unwrap();
+    assert_eq!(flow_name, flow.name);
+    assert_eq!(1, flow.layers.len());
+    assert!(!flow.get_layer("subsystem").unwrap().enabled);
+    assert!(flow.get_layer("subsystem").unwrap().has_entity(third_entity_instance.id));
+    assert_eq!(Some(3), flow.get_type_version("generic_flow"));
+    assert_eq!(3, flow.entity_instances.len());
+    assert_eq!(1, flow.relation_instances.len());
+}
+
+#[test]
+fn reactive_flow_variable_test() {
+    let wrapper_entity_instance = Arc::new(create_random_entity_instance_with_type("generic_flow", "test"));
+    let reactive_flow = ReactiveFlow::new(wrapper_entity_instance.clone());
+    let input_entity_instance = Arc::new(create_random_entity_instance("lhs"));
+    let output_entity_instance = Arc::new(create_random_entity_instance("result"));
+    reactive_flow.add_entity(input_entity_instance.clone());
+    reactive_flow.add_entity(output_entity_instance.clone());
+
+    let mut flow: Flow = reactive_flow.try_into().unwrap();
+    flow.variables.push(FlowVariable::input("a", input_entity_instance.id, "lhs"));
+    flow.variables.push(FlowVariable::output("sum", output_entity_instance.id, "result"));
+    assert!(flow.validate_variables().is_ok());
+    assert_eq!(vec!["a"], flow.get_inputs().iter().map(|variable| variable.name.as_str()).collect::<Vec<&str>>());
+    assert_eq!(vec!["sum"], flow.get_outputs().iter().map(|variable| variable.name.as_str()).collect::<Vec<&str>>());
+    assert_eq!("lhs", flow.get_variable("a").unwrap().property_name);
+
+    let reactive_flow = ReactiveFlow::try_from(flow.clone()).unwrap();
+    assert!(reactive_flow.set_variable_value("a", json!(1)));
+    assert_eq!(Some(json!(1)), reactive_flow.get_variable_value("a"));
+    assert!(!reactive_flow.set_variable_value("sum", json!(2)));
+    assert!(!reactive_flow.set_variable_value("missing", json!(2)));
+    assert!(reactive_flow.get_variable_value("sum").is_some());
+    let flow: Flow = reactive_flow.try_into().unwrap();
+    assert_eq!(2, flow.variables.len());
+
+    let mut invalid = flow.clone();
+    invalid.variables.push(FlowVariable::input("a", input_entity_instance.id, "lhs"));
+    assert_eq!(Err(FlowVariableError::Duplicate("a".to_string())), invalid.validate_variables());
+    let mut invalid = flow.clone();
+    invalid.variables.push(FlowVariable::input("b", input_entity_instance.id, "rhs"));
+    assert_eq!(Err(FlowVariableError::MissingProperty("b".to_string(), "rhs".to_string())), invalid.validate_variables());
+    let mut invalid = flow;
+    let id = Uuid::new_v4();
+    invalid.variables.push(FlowVariable::input("c", id, "lhs"));
+    assert_eq!(Err(FlowVariableError::MissingEntityInstance("c".to_string(), id)), invalid.validate_variables());
+}
+
+#[test]
+fn reactive_flow_duplicate_test() {
+    let wrapper_entity_instance = Arc::new(create_random_entity_instance_with_type("generic_flow", "test"));
+    let reactive_flow = ReactiveFlow::new(wrapper_entity_instance.clone());
+    let entity_instance = Arc::new(create_random_entity_instance("lhs"));
+    let outer_entity_instance = Arc::new(create_random_entity_instance("rhs"));
+    let relation_instance = Arc::new(create_random_relation_instance_with_properties(
+        wrapper_entity_instance.clone(),
+        entity_instance.clone(),
+        r_string(),
+    ));
+    let outer_relation_instance = Arc::new(create_random_relation_instance_with_properties(
+        entity_instance.clone(),
+        outer_entity_instance.clone(),
+        r_string(),
+    ));
+    reactive_flow.add_entity(entity_instance.clone());
+    reactive_flow.add_relation(relation_instance.clone());
+    reactive_flow.add_relation(outer_relation_instance.clone());
+    reactive_flow.add_variable(FlowVariable::input("a", entity_instance.id, "lhs"));
+    assert!(reactive_flow.set_override(entity_instance.id, "lhs", json!(1)));
+
+    let mut ids = HashMap::new();
+    let new_id = Uuid::new_v4();
+    ids.insert(reactive_flow.id, new_id);
+    let duplicate = reactive_flow.duplicate_with_ids(&mut ids);
+    assert_eq!(new_id, duplicate.id);
+    assert_eq!(2, ids.len());
+    let new_entity_id = ids[&entity_instance.id];
+    assert_ne!(entity_instance.id, new_entity_id);
+    assert!(duplicate.has_entity_by_id(new_entity_id));
+    assert!(!duplicate.has_entity_by_id(entity_instance.id));
+    assert_eq!(Some(json!(1)), duplicate.get_entity(new_entity_id).unwrap().get("lhs"));
+    assert_eq!(Some(json!(1)), duplicate.get_override(new_entity_id, "lhs"));
+    duplicate.get_entity(new_entity_id).unwrap().set("lhs", json!(2));
+    duplicate.clear_override(new_entity_id, "lhs");
+    assert_eq!(Some(json!(2)), duplicate.get_entity(new_entity_id).unwrap().get("lhs"));
+    assert_eq!(new_entity_id, duplicate.get_variable("a").unwrap().entity_id);
+
+    let relation_instances = duplicate.relation_instances.read().unwrap();
+    assert_eq!(2, relation_instances.len());
+    let duplicated_relation_instance = relation_instances
+        .values()
+        .find(|duplicated| duplicated.type_name == relation_instance.type_name)
+        .unwrap();
+    assert_eq!(new_id, duplicated_relation_instance.outbound.id);
+    assert!(Arc::ptr_eq(&duplicated_relation_instance.inbound, &duplicate.get_entity(new_entity_id).unwrap()));
+    let duplicated_outer_relation_instance = relation_instances
+        .values()
+        .find(|duplicated| duplicated.type_name == outer_relation_instance.type_name)
+        .unwrap();
+    assert_eq!(new_entity_id, duplicated_outer_relation_instance.outbound.id);
+    assert!(Arc::ptr_eq(&duplicated_outer_relation_instance.inbound, &outer_entity_instance));
+
+    duplicate.get_entity(new_entity_id).unwrap().set("lhs", json!(2));
+    assert_eq!(Some(json!(1)), entity_instance.get("lhs"));
+    assert!(!reactive_flow.duplicate().has_entity_by_id(reactive_flow.id));
+}
+
+#[test]
+fn reactive_flow_persist_behaviours_test() {
+    let wrapper_entity_instance = Arc::new(create_random_entity_instance_with_type("generic_flow", "test"));
+    let reactive_flow = Arc::new(ReactiveFlow::new(wrapper_entity_instance.clone()));
+    let entity_instance = Arc::new(create_random_entity_instance("lhs"));
+    let relation_instance = Arc::new(create_random_relation_instance_with_properties(
+        wrapper_entity_instance.clone(),
+        entity_instance.clone(),
+        r_string(),
+    ));
+    entity_instance.add_behaviour(BehaviourName::new("sum"));
+    entity_instance.add_behaviour(BehaviourName::new("log"));
+    relation_instance.add_behaviour(BehaviourName::new("default_connector"));
+    reactive_flow.add_entity(entity_instance.clone());
+    reactive_flow.add_relation(relation_instance.clone());
+
+    let flow = Flow::try_from(reactive_flow.clone()).unwrap();
+    assert!(flow.entity_instances.iter().all(|entity_instance| entity_instance.behaviours.is_empty()));
+    assert!(flow.relation_instances[0].behaviours.is_empty());
+
+    let flow = Flow::from_reactive_with_behaviours(&reactive_flow).unwrap();
+    let persisted = flow.entity_instances.iter().find(|e| e.id == entity_instance.id).unwrap();
+    assert_eq!(vec!["log".to_string(), "sum".to_string()], persisted.behaviours);
+    assert_eq!(vec!["default_connector".to_string()], flow.relation_instances[0].behaviours);
+
+    // The runtime re-attaches the persisted behaviours
+    let json = serde_json::to_value(&flow).unwrap();
+    let flow: Flow = serde_json::from_value(json).unwrap();
+    let reactive_flow = ReactiveFlow::try_from(flow.clone()).unwrap();
+    assert!(!reactive_flow.get_entity(entity_instance.id).unwrap().behaves_as(BehaviourName::new("sum")));
+    assert_eq!(2, flow.entity_instances.iter().find(|e| e.id == entity_instance.id).unwrap().behaviours.len());
+}
+
+#[test]
+fn reactive_flow_tick_all_test() {
+    let wrapper_entity_instance = Arc::new(create_random_entity_instance_with_type("generic_flow", "test"));
+    let reactive_flow = ReactiveFlow::new(wrapper_entity_instance.clone());
+    let a = Arc::new(create_random_entity_instance("x"));
+    let b = Arc::new(create_random_entity_instance("x"));
+    let c = Arc::new(create_random_entity_instance("x"));
+    let c_b = Arc::new(create_random_relation_instance_with_properties(c.clone(), b.clone(), "x".to_string()));
+    let b_a = Arc::new(create_random_relation_instance_with_properties(b.clone(), a.clone(), "x".to_string()));
+    let a_c = Arc::new(create_random_relation_instance_with_properties(a.clone(), c.clone(), "x".to_string()));
+    let ticked = Arc::new(Mutex::new(Vec::new()));
+    let observe = |properties: &DashMap<Arc<str>, ReactivePropertyInstance>, label: &'static str| {
+        let ticked = ticked.clone();
+        properties
+            .get("x")
+            .unwrap()
+            .stream
+            .read()
+            .unwrap()
+            .observe(move |_: &Value| ticked.lock().unwrap().push(label));
+    };
+    observe(&a.properties, "a");
+    observe(&b.properties, "b");
+    observe(&c.properties, "c");
+    observe(&c_b.properties, "c_b");
+    observe(&b_a.properties, "b_a");
+    for entity_instance in [&a, &b, &c] {
+        reactive_flow.add_entity(entity_instance.clone());
+    }
+    reactive_flow.add_relation(c_b.clone());
+    reactive_flow.add_relation(b_a.clone());
+
+    reactive_flow.tick_all();
+    let order = ticked.lock().unwrap().clone();
+    assert_eq!(vec!["c", "c_b", "b", "b_a", "a"], order);
+
+    // Cycles are broken, each instance is ticked exactly once
+    observe(&a_c.properties, "a_c");
+    reactive_flow.add_relation(a_c.clone());
+    ticked.lock().unwrap().clear();
+    reactive_flow.tick_all();
+    let mut order = ticked.lock().unwrap().clone();
+    assert_eq!(6, order.len());
+    order.sort();
+    assert_eq!(vec!["a", "a_c", "b", "b_a", "c", "c_b"], order);
+}
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::tests::utils::{create_random_entity_instance, create_random_relation_instance_with_properties};
+use crate::{BehaviourName, ComponentName, PropertyInstanceGetter, ReactiveInstance, ReactivePropertyContainer};
+
+/// A behaviour which only cares about properties and is written once for both kinds of instances.
+fn increment<I: ReactiveInstance>(instance: &I, property_name: &str) {
+    if !instance.has_property(property_name) {
+        instance.add_property(property_name, json!(0));
+    }
+    let value = instance.as_i64(property_name).unwrap_or_default();
+    instance.set(property_name, json!(value + 1));
+    instance.add_behaviour(BehaviourName::new("increment"));
+}
+
+#[test]
+fn reactive_instance_test() {
+    let outbound = Arc::new(create_random_entity_instance("x"));
+    let inbound = Arc::new(create_random_entity_instance("y"));
+    let relation_instance = create_random_relation_instance_with_properties(outbound.clone(), inbound, "z".to_string());
+
+    increment(outbound.as_ref(), "counter");
+    increment(outbound.as_ref(), "counter");
+    increment(&relation_instance, "counter");
+    assert_eq!(2, outbound.as_i64("counter").unwrap());
+    assert_eq!(1, relation_instance.as_i64("counter").unwrap());
+    assert_eq!(vec!["increment".to_string()], ReactiveInstance::get_behaviours(outbound.as_ref()));
+    assert!(ReactiveInstance::behaves_as(&relation_instance, BehaviourName::new("increment")));
+    assert_eq!(vec!["counter".to_string(), "z".to_string()], relation_instance.get_property_names());
+    assert_eq!(relation_instance.type_name, ReactiveInstance::type_name(&relation_instance));
+}
+
+#[test]
+fn reactive_instance_components_test() {
+    fn apply<I: ReactiveInstance>(instance: &I) {
+        instance.add_component(ComponentName::new("b"));
+        instance.add_component(ComponentName::new("a"));
+        assert!(instance.is_a(ComponentName::new("a")));
+        assert_eq!(vec!["a".to_string(), "b".to_string()], instance.get_components());
+        let revision = instance.revision();
+        instance.remove_component(ComponentName::new("a"));
+        assert!(!instance.is_a(ComponentName::new("a")));
+        assert!(instance.revision() > revision);
+        instance.set("x", json!(1));
+        assert!(instance.is_dirty());
+        instance.tick();
+        assert!(!instance.is_dirty());
+    }
+    let outbound = Arc::new(create_random_entity_instance("x"));
+    let inbound = Arc::new(create_random_entity_instance("y"));
+    let relation_instance = create_random_relation_instance_with_properties(outbound.clone(), inbound, "x".to_string());
+    apply(outbound.as_ref());
+    apply(&relation_instance);
+}
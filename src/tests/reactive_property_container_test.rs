@@ -0,0 +1,207 @@
+use std::sync::{Arc, Mutex};
+
+use dashmap::DashMap;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::tests::utils::{create_random_entity_instance, create_random_relation_instance_with_properties};
+use crate::{
+    generate_id, ComponentName, InstanceEvent, InstanceObservers, PropertyInstanceGetter, PropertyInstanceSetter, ReactiveFlow, ReactivePropertyContainer,
+    ReactivePropertyInstance, Revision, UnknownPropertyError, UnknownPropertyPolicy,
+};
+
+/// A minimal container which gets the property accessors from the shared implementation.
+#[derive(Default)]
+struct Settings {
+    id: Uuid,
+    properties: DashMap<Arc<str>, ReactivePropertyInstance>,
+    revision: Revision,
+    observers: InstanceObservers,
+}
+
+impl ReactivePropertyContainer for Settings {
+    fn properties(&self) -> &DashMap<Arc<str>, ReactivePropertyInstance> {
+        &self.properties
+    }
+
+    fn revision_counter(&self) -> &Revision {
+        &self.revision
+    }
+
+    fn unknown_property_policy(&self) -> UnknownPropertyPolicy {
+        UnknownPropertyPolicy::Error
+    }
+
+    fn new_property_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn observers(&self) -> &InstanceObservers {
+        &self.observers
+    }
+}
+
+#[test]
+fn reactive_property_container_test() {
+    let settings = Settings::default();
+    settings.add_property("volume", json!(5));
+    assert!(settings.has_property("volume"));
+    settings.set("volume", json!(7));
+    assert_eq!(7, settings.as_i64("volume").unwrap());
+    assert_eq!(Err(UnknownPropertyError("brightness".to_string()).into()), settings.try_set("brightness", json!(1)));
+    assert_eq!(2, settings.revision.get());
+
+    assert_eq!(Some(json!(7)), settings.remove_property("volume"));
+    assert_eq!(None, settings.remove_property("volume"));
+    assert!(!settings.has_property("volume"));
+    assert_eq!(3, settings.revision.get());
+}
+
+#[test]
+fn reactive_property_container_remove_property_test() {
+    let outbound = Arc::new(create_random_entity_instance("x"));
+    let inbound = Arc::new(create_random_entity_instance("y"));
+    let relation_instance = create_random_relation_instance_with_properties(outbound.clone(), inbound, "z".to_string());
+
+    let revision = outbound.revision();
+    assert_eq!(outbound.get("x"), outbound.remove_property("x"));
+    assert!(outbound.get("x").is_none());
+    assert!(outbound.revision() > revision);
+
+    relation_instance.add_property("w", json!(true));
+    assert_eq!(vec!["w".to_string(), "z".to_string()], relation_instance.get_property_names());
+    assert_eq!(Some(json!(true)), relation_instance.remove_property("w"));
+    assert_eq!(vec!["z".to_string()], relation_instance.get_property_names());
+}
+
+#[test]
+fn reactive_property_container_destroy_test() {
+    let entity_instance = create_random_entity_instance("x");
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let observed_events = events.clone();
+    entity_instance.observe_events(move |event| observed_events.lock().unwrap().push(event.clone()));
+    let values = Arc::new(Mutex::new(Vec::new()));
+    let observed_values = values.clone();
+    entity_instance.observe_all(move |_, value| observed_values.lock().unwrap().push(value.clone()));
+    let compositions = Arc::new(Mutex::new(Vec::new()));
+    let observed_compositions = compositions.clone();
+    entity_instance.observe_composition(generate_id().as_u128(), move |event| observed_compositions.lock().unwrap().push(event.name.clone()));
+    let stream_values = Arc::new(Mutex::new(Vec::new()));
+    let observed_stream_values = stream_values.clone();
+    entity_instance
+        .properties
+        .get("x")
+        .unwrap()
+        .stream
+        .read()
+        .unwrap()
+        .observe(move |value| observed_stream_values.lock().unwrap().push(value.clone()));
+
+    entity_instance.set("x", json!(1));
+    entity_instance.destroy();
+    entity_instance.set("x", json!(2));
+    entity_instance.add_property("y", json!(3));
+    entity_instance.set("y", json!(4));
+    entity_instance.add_component(ComponentName::new("positionable"));
+
+    assert_eq!(
+        vec![
+            InstanceEvent::PropertyChanged {
+                name: "x".to_string(),
+                value: json!(1)
+            },
+            InstanceEvent::Destroyed
+        ],
+        *events.lock().unwrap()
+    );
+    assert_eq!(vec![json!(1)], *values.lock().unwrap());
+    assert!(compositions.lock().unwrap().is_empty());
+    // The observers which have been subscribed to the stream directly are kept
+    assert_eq!(vec![json!(1), json!(2)], *stream_values.lock().unwrap());
+    // The values are still accessible
+    assert_eq!(json!(2), entity_instance.get("x").unwrap());
+}
+
+#[test]
+fn reactive_flow_destroy_test() {
+    let reactive_flow = ReactiveFlow::new(Arc::new(create_random_entity_instance("x")));
+    let shared_entity_instance = Arc::new(create_random_entity_instance("x"));
+    let entity_instance = Arc::new(create_random_entity_instance("x"));
+    reactive_flow.add_entity(shared_entity_instance.clone());
+    reactive_flow.add_entity(entity_instance.clone());
+    reactive_flow.add_relation(Arc::new(create_random_relation_instance_with_properties(
+        shared_entity_instance.clone(),
+        entity_instance.clone(),
+        "weight".to_string(),
+    )));
+
+    let destroyed = Arc::new(Mutex::new(Vec::new()));
+    for instance in reactive_flow.entity_instances.read().unwrap().values() {
+        let id = instance.id;
+        let observed_destroyed = destroyed.clone();
+        instance.observe_events(move |event| {
+            if *event == InstanceEvent::Destroyed {
+                observed_destroyed.lock().unwrap().push(id);
+            }
+        });
+    }
+    // Temporary references don't prevent the teardown
+    let values = Arc::new(Mutex::new(Vec::new()));
+    let observed_values = values.clone();
+    entity_instance
+        .properties
+        .get("x")
+        .unwrap()
+        .observe_with_handle(move |value| observed_values.lock().unwrap().push(value.clone()), generate_id().as_u128());
+
+    assert!(!reactive_flow.is_destroyed());
+    reactive_flow.destroy();
+    reactive_flow.destroy();
+    assert!(reactive_flow.is_destroyed());
+    let destroyed = destroyed.lock().unwrap();
+    assert_eq!(3, destroyed.len());
+    assert!(destroyed.contains(&reactive_flow.id));
+    assert!(destroyed.contains(&entity_instance.id));
+    assert!(destroyed.contains(&shared_entity_instance.id));
+    entity_instance.set("x", json!(1));
+    assert!(values.lock().unwrap().is_empty());
+}
+
+#[test]
+fn reactive_property_instance_destroy_shared_stream_test() {
+    let entity_instance = create_random_entity_instance("x");
+    let property_instance = entity_instance.properties.get("x").unwrap();
+    let shared_property_instance = property_instance.share();
+
+    let values = Arc::new(Mutex::new(Vec::new()));
+    let observed_values = values.clone();
+    property_instance.observe_with_handle(move |value| observed_values.lock().unwrap().push(("own", value.clone())), generate_id().as_u128());
+    let observed_values = values.clone();
+    shared_property_instance.observe_with_handle(move |value| observed_values.lock().unwrap().push(("shared", value.clone())), generate_id().as_u128());
+
+    property_instance.destroy();
+    property_instance.set(json!(1));
+    // Only the observers which have been registered through the destroyed property instance are removed
+    assert_eq!(vec![("shared", json!(1))], *values.lock().unwrap());
+}
+
+#[test]
+fn reactive_property_container_remove_property_event_test() {
+    let entity_instance = create_random_entity_instance("x");
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let observed_events = events.clone();
+    entity_instance.observe_events(move |event| observed_events.lock().unwrap().push(event.clone()));
+    let values = Arc::new(Mutex::new(Vec::new()));
+    let observed_values = values.clone();
+    entity_instance.observe_all(move |_, value| observed_values.lock().unwrap().push(value.clone()));
+
+    let stream = entity_instance.properties.get("x").unwrap().stream.clone();
+    let value = entity_instance.get("x").unwrap();
+    assert_eq!(Some(value.clone()), entity_instance.remove_property("x"));
+    // The removed property is still alive, but the observers of the instance are detached
+    stream.read().unwrap().send(&json!(1));
+
+    assert_eq!(vec![InstanceEvent::PropertyRemoved { name: "x".to_string(), value }], *events.lock().unwrap());
+    assert!(values.lock().unwrap().is_empty());
+}
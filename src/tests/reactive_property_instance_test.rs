@@ -1,10 +1,13 @@
 extern crate test;
 
+use std::collections::VecDeque;
 use std::ops::DerefMut;
 use std::process::Termination;
+use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::sync::RwLock;
 use std::thread;
 use test::Bencher;
@@ -15,8 +18,24 @@ use serde_json::json;
 use stopwatch2::Stopwatch;
 use uuid::Uuid;
 
+use crate::intern_string;
+use crate::release_unused_interned_strings;
 use crate::tests::utils::r_string;
+use crate::tests::utils::r_string_1000;
+use crate::ConflationPolicy;
+use crate::DataType;
+use crate::PrioritizedObservers;
+use crate::PropertyConflation;
+use crate::PropertyInterning;
+use crate::PropertyKind;
+use crate::PropertyMutability;
+use crate::PropertyType;
+use crate::PropertyTypeEnforcement;
+use crate::PropertyValueHistory;
 use crate::ReactivePropertyInstance;
+use crate::Revision;
+use crate::SocketType;
+use crate::DEFAULT_EVENT_CAPACITY;
 
 #[test]
 fn reactive_property_instance_test() {
@@ -30,14 +49,32 @@ fn reactive_property_instance_test() {
 
     let reactive_property_instance = ReactivePropertyInstance {
         id: uuid,
-        name: property_name.clone(),
+        name: property_name.as_str().into(),
         stream: Arc::new(RwLock::new(Stream::new())),
+        prioritized_observers: PrioritizedObservers::default(),
+        observer_handles: Mutex::new(Vec::new()),
+        write_interceptor: Arc::new(RwLock::new(None)),
         value: RwLock::new(initial_property_value_json),
+        tick_priority: 0,
+        conflation: PropertyConflation::default(),
+        interning: PropertyInterning::default(),
+        kind: PropertyKind::ValueProperty,
+        socket_type: SocketType::None,
+        events: Mutex::new(VecDeque::new()),
+        event_capacity: DEFAULT_EVENT_CAPACITY,
+        unit: None,
+        semantic: None,
+        type_enforcement: PropertyTypeEnforcement::default(),
+        mutability: PropertyMutability::Mutable,
+        dirty: AtomicBool::new(false),
+        last_modified: RwLock::new(None),
+        value_history: PropertyValueHistory::default(),
+        revision: Revision::default(),
     };
 
     // Check that the meta data is correct
     assert_eq!(uuid, reactive_property_instance.id);
-    assert_eq!(property_name.clone(), reactive_property_instance.name);
+    assert_eq!(property_name.as_str(), reactive_property_instance.name.as_ref());
     assert_eq!(initial_property_value.as_str(), reactive_property_instance.value.read().unwrap().as_str().unwrap());
 
     // Set: Send to "stream", write inner "value"
@@ -106,7 +143,7 @@ fn create_reactive_property_instance_test() {
     let reactive_property_instance = ReactivePropertyInstance::new(uuid, property_name.clone(), initial_property_value_json);
 
     assert_eq!(uuid, reactive_property_instance.id);
-    assert_eq!(property_name.clone(), reactive_property_instance.name);
+    assert_eq!(property_name.as_str(), reactive_property_instance.name.as_ref());
     assert_eq!(initial_property_value.as_str(), reactive_property_instance.value.read().unwrap().as_str().unwrap());
 
     // Set: Send to "stream", write "value"
@@ -223,6 +260,91 @@ fn reactive_property_instance_eq_string_test() {
     assert!(instance1 != instance2);
 }
 
+#[test]
+fn reactive_property_instance_keep_latest_test() {
+    let mut property_instance = ReactivePropertyInstance::new(Uuid::new_v4(), r_string(), json!(0));
+    property_instance.set_conflation_policy(ConflationPolicy::KeepLatest);
+    let property_instance = Arc::new(property_instance);
+    let observed = Arc::new(RwLock::new(Vec::new()));
+    {
+        let observed = observed.clone();
+        let inner_property_instance = Arc::downgrade(&property_instance);
+        property_instance.stream.read().unwrap().observe_with_handle(
+            move |value| {
+                let value = value.as_u64().unwrap();
+                observed.write().unwrap().push(value);
+                // Setting the property while it is propagated doesn't block but is propagated afterwards
+                if value < 3 {
+                    inner_property_instance.upgrade().unwrap().set(json!(value + 1));
+                }
+            },
+            Uuid::new_v4().as_u128(),
+        );
+    }
+    property_instance.set(json!(0));
+    assert_eq!(vec![0, 1, 2, 3], *observed.read().unwrap());
+    assert_eq!(3, property_instance.as_u64().unwrap());
+}
+
+#[test]
+fn reactive_property_instance_interning_test() {
+    let type_name = r_string();
+    let mut property_instance_1 = ReactivePropertyInstance::new(Uuid::new_v4(), "type", json!(type_name.clone()));
+    let mut property_instance_2 = ReactivePropertyInstance::new(Uuid::new_v4(), "type", json!(type_name.clone()));
+    property_instance_1.set_interning(true);
+    property_instance_2.set_interning(true);
+    assert!(Arc::ptr_eq(&property_instance_1.name, &property_instance_2.name));
+    assert_eq!(type_name, property_instance_1.as_string().unwrap());
+    assert!(Arc::ptr_eq(&property_instance_1.interning.get().unwrap(), &property_instance_2.interning.get().unwrap()));
+    assert!(Arc::ptr_eq(&intern_string(&type_name), &property_instance_1.interning.get().unwrap()));
+    assert!(Arc::ptr_eq(
+        &property_instance_1.get_shared_string().unwrap(),
+        &property_instance_2.get_shared_string().unwrap()
+    ));
+    assert!(property_instance_1 == property_instance_2);
+
+    // Non-string values are not interned
+    property_instance_1.set(json!(123));
+    assert!(property_instance_1.interning.get().is_none());
+    assert_eq!(123, property_instance_1.as_u64().unwrap());
+    property_instance_1.set(json!(type_name.clone()));
+    assert_eq!(type_name, property_instance_1.as_string().unwrap());
+
+    // Observers receive the value
+    let observed_value = Arc::new(RwLock::new(json!(null)));
+    let inner_observed_value = observed_value.clone();
+    property_instance_2.stream.read().unwrap().observe(move |value| {
+        *inner_observed_value.write().unwrap() = value.clone();
+    });
+    let new_type_name = r_string();
+    property_instance_2.set(json!(new_type_name.clone()));
+    assert_eq!(new_type_name, *observed_value.read().unwrap());
+    assert_eq!(new_type_name, property_instance_2.as_string().unwrap());
+
+    property_instance_2.set_interning(false);
+    assert!(property_instance_2.interning.get().is_none());
+    assert_eq!(new_type_name, property_instance_2.as_string().unwrap());
+    assert_eq!(new_type_name, property_instance_2.get_shared_string().unwrap().as_ref());
+
+    // The pool doesn't keep strings alive which are not used anymore
+    let interned = Arc::downgrade(&intern_string(&r_string()));
+    assert!(interned.upgrade().is_none());
+    let interned = intern_string(&new_type_name);
+    drop(property_instance_2);
+    assert!(Arc::ptr_eq(&interned, &intern_string(&new_type_name)));
+    release_unused_interned_strings();
+    assert!(Arc::ptr_eq(&interned, &intern_string(&new_type_name)));
+}
+
+#[test]
+fn reactive_property_instance_with_value_test() {
+    let mut property_instance = ReactivePropertyInstance::new(Uuid::new_v4(), "values", json!([1, 2, 3]));
+    assert_eq!(Some(3), property_instance.with_value(|value| value.as_array().map(Vec::len)));
+    property_instance.set_interning(true);
+    property_instance.set(json!("abc"));
+    assert_eq!(Some(3), property_instance.with_value(|value| value.as_str().map(str::len)));
+}
+
 #[test]
 fn reactive_property_instance_stream_test() {
     let instance1 = ReactivePropertyInstance::new(Uuid::new_v4(), r_string(), json!(0));
@@ -405,3 +527,264 @@ fn reactive_property_instance_stream_mt_benchmark() {
         s1.elapsed()
     );
 }
+
+#[bench]
+fn create_reactive_property_instances_benchmark(bencher: &mut Bencher) -> impl Termination {
+    let type_name = r_string();
+    bencher.iter(move || {
+        let _property_instances: Vec<ReactivePropertyInstance> = (0..1000)
+            .map(|_| ReactivePropertyInstance::new(Uuid::new_v4(), "type", json!(type_name.clone())))
+            .collect();
+    })
+}
+
+#[bench]
+fn create_interned_reactive_property_instances_benchmark(bencher: &mut Bencher) -> impl Termination {
+    let type_name = r_string();
+    bencher.iter(move || {
+        let _property_instances: Vec<ReactivePropertyInstance> = (0..1000)
+            .map(|_| {
+                let mut property_instance = ReactivePropertyInstance::new(Uuid::new_v4(), "type", json!(null));
+                property_instance.set_interning(true);
+                property_instance.set(json!(type_name.clone()));
+                property_instance
+            })
+            .collect();
+    })
+}
+
+#[test]
+fn reactive_property_instance_interning_memory_test() {
+    // Without interning each instance holds an allocation of the string value,
+    // with interning all instances share a single allocation
+    let type_name = r_string_1000();
+    let count = 1000;
+    let property_instances: Vec<ReactivePropertyInstance> = (0..count)
+        .map(|_| ReactivePropertyInstance::new(Uuid::new_v4(), "type", json!(type_name.clone())))
+        .collect();
+    let bytes: usize = property_instances
+        .iter()
+        .map(|property_instance| property_instance.value.read().unwrap().as_str().unwrap().len())
+        .sum();
+    assert_eq!(count * type_name.len(), bytes);
+
+    let interned_property_instances: Vec<ReactivePropertyInstance> = property_instances
+        .into_iter()
+        .map(|mut property_instance| {
+            property_instance.set_interning(true);
+            property_instance
+        })
+        .collect();
+    let interned = intern_string(&type_name);
+    // All instances plus the local reference, the pool only holds a weak reference
+    assert_eq!(count + 1, Arc::strong_count(&interned));
+    assert!(interned_property_instances
+        .iter()
+        .all(|property_instance| property_instance.value.read().unwrap().is_null()));
+}
+
+#[test]
+fn reactive_property_instance_event_test() {
+    let reactive_property_instance = ReactivePropertyInstance::new_event(Uuid::new_v4(), r_string());
+    assert!(reactive_property_instance.is_event());
+    let received = Arc::new(RwLock::new(Vec::new()));
+    let received_2 = received.clone();
+    reactive_property_instance
+        .stream
+        .read()
+        .unwrap()
+        .observe(move |payload| received_2.write().unwrap().push(payload.clone()));
+
+    // Each payload is propagated and queued, even if it is equal to the previous payload
+    reactive_property_instance.emit(json!(1));
+    reactive_property_instance.emit(json!(1));
+    reactive_property_instance.set(json!(2));
+    assert_eq!(3, reactive_property_instance.pending_events());
+    assert_eq!(vec![json!(1), json!(1), json!(2)], *received.read().unwrap());
+
+    // Event properties don't hold a last value and are not ticked
+    reactive_property_instance.tick();
+    assert_eq!(3, received.read().unwrap().len());
+    assert_eq!(json!(null), reactive_property_instance.get());
+
+    assert_eq!(vec![json!(1), json!(1), json!(2)], reactive_property_instance.drain_events());
+    assert_eq!(0, reactive_property_instance.pending_events());
+}
+
+#[test]
+fn reactive_property_instance_event_capacity_test() {
+    let mut reactive_property_instance = ReactivePropertyInstance::new_event(Uuid::new_v4(), r_string());
+    assert_eq!(DEFAULT_EVENT_CAPACITY, reactive_property_instance.event_capacity);
+    for payload in 0..5 {
+        reactive_property_instance.emit(json!(payload));
+    }
+    // The oldest payloads are dropped
+    reactive_property_instance.set_event_capacity(3);
+    assert_eq!(3, reactive_property_instance.pending_events());
+    reactive_property_instance.emit(json!(5));
+    assert_eq!(vec![json!(3), json!(4), json!(5)], reactive_property_instance.drain_events());
+}
+
+#[test]
+fn reactive_property_instance_from_property_type_test() {
+    let property_type = PropertyType::new("angle", DataType::Number).with_unit("degrees").with_semantic("rotation");
+    let reactive_property_instance = ReactivePropertyInstance::from_property_type(Uuid::new_v4(), &property_type);
+    assert_eq!("angle", reactive_property_instance.name.as_ref());
+    assert_eq!(json!(0), reactive_property_instance.get());
+    assert_eq!(Some("degrees".to_string()), reactive_property_instance.unit);
+    assert_eq!(Some("rotation".to_string()), reactive_property_instance.semantic);
+    assert!(!reactive_property_instance.is_event());
+}
+
+#[test]
+fn reactive_property_instance_value_history_test() {
+    let mut reactive_property_instance = ReactivePropertyInstance::new(Uuid::new_v4(), "position", json!(0));
+    reactive_property_instance.set(json!(1));
+    assert!(reactive_property_instance.history().is_empty());
+    assert_eq!(None, reactive_property_instance.previous());
+
+    reactive_property_instance.set_value_history(3);
+    assert_eq!(vec![json!(1)], reactive_property_instance.history());
+    assert_eq!(None, reactive_property_instance.previous());
+
+    reactive_property_instance.set(json!(2));
+    assert_eq!(Some(json!(1)), reactive_property_instance.previous());
+    reactive_property_instance.set(json!(3));
+    reactive_property_instance.set_no_propagate(json!(4));
+    assert_eq!(vec![json!(2), json!(3), json!(4)], reactive_property_instance.history());
+    assert_eq!(Some(json!(3)), reactive_property_instance.previous());
+
+    reactive_property_instance.set_value_history(0);
+    reactive_property_instance.set(json!(5));
+    assert!(reactive_property_instance.history().is_empty());
+}
+
+#[test]
+fn reactive_property_instance_immutable_test() {
+    let property_type = PropertyType::new("id", DataType::String).immutable();
+    let reactive_property_instance = ReactivePropertyInstance::from_property_type(Uuid::new_v4(), &property_type);
+    assert!(reactive_property_instance.is_writable());
+    let received = Arc::new(AtomicU64::new(0));
+    let r = received.clone();
+    reactive_property_instance.stream.read().unwrap().observe(move |_| {
+        r.fetch_add(1, Ordering::SeqCst);
+    });
+
+    // The first write initializes the value
+    reactive_property_instance.set(json!("a"));
+    assert!(!reactive_property_instance.is_writable());
+    reactive_property_instance.set(json!("b"));
+    reactive_property_instance.set_no_propagate(json!("c"));
+    assert_eq!(json!("a"), reactive_property_instance.get());
+    assert_eq!(1, received.load(Ordering::SeqCst));
+
+    let duplicate = reactive_property_instance.duplicate(Uuid::new_v4());
+    duplicate.set(json!("d"));
+    assert_eq!(json!("a"), duplicate.get());
+
+    let mut reactive_property_instance = ReactivePropertyInstance::new(Uuid::new_v4(), "x", json!(0));
+    reactive_property_instance.set(json!(1));
+    reactive_property_instance.set_mutability(PropertyMutability::Immutable);
+    reactive_property_instance.set(json!(2));
+    assert_eq!(json!(1), reactive_property_instance.get());
+}
+
+#[test]
+fn reactive_property_instance_immutable_concurrent_test() {
+    let property_type = PropertyType::new("id", DataType::Number).immutable();
+    let reactive_property_instance = Arc::new(ReactivePropertyInstance::from_property_type(Uuid::new_v4(), &property_type));
+    let received = Arc::new(AtomicU64::new(0));
+    let r = received.clone();
+    reactive_property_instance.stream.read().unwrap().observe(move |_| {
+        r.fetch_add(1, Ordering::SeqCst);
+    });
+    let handles: Vec<_> = (0..8)
+        .map(|i| {
+            let reactive_property_instance = reactive_property_instance.clone();
+            thread::spawn(move || reactive_property_instance.set(json!(i)))
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    // Exactly one write initializes the value
+    assert_eq!(1, received.load(Ordering::SeqCst));
+}
+
+#[test]
+fn reactive_property_instance_share_test() {
+    let property_type = PropertyType::new("id", DataType::String).immutable();
+    let mut reactive_property_instance = ReactivePropertyInstance::from_property_type(Uuid::new_v4(), &property_type);
+    reactive_property_instance.set_value_history(4);
+    reactive_property_instance.set(json!("a"));
+    let received = Arc::new(AtomicU64::new(0));
+    let r = received.clone();
+    reactive_property_instance.stream.read().unwrap().observe(move |_| {
+        r.fetch_add(1, Ordering::SeqCst);
+    });
+
+    let shared = reactive_property_instance.share();
+    assert_eq!(reactive_property_instance.socket_type, shared.socket_type);
+    assert_eq!(reactive_property_instance.type_enforcement, shared.type_enforcement);
+    assert_eq!(PropertyMutability::Immutable, shared.mutability);
+    assert_eq!(reactive_property_instance.last_modified(), shared.last_modified());
+    assert_eq!(reactive_property_instance.history(), shared.history());
+    shared.set(json!("b"));
+    assert_eq!(json!("a"), shared.get());
+    shared.send(&json!("c"));
+    assert_eq!(1, received.load(Ordering::SeqCst));
+}
+
+#[test]
+fn reactive_property_instance_array_test() {
+    let reactive_property_instance = ReactivePropertyInstance::new(Uuid::new_v4(), "items", json!([1]));
+    let propagated = Arc::new(RwLock::new(Vec::new()));
+    let p = propagated.clone();
+    reactive_property_instance.stream.read().unwrap().observe(move |value| {
+        p.write().unwrap().push(value.clone());
+    });
+    assert!(reactive_property_instance.push(json!(3)));
+    assert!(reactive_property_instance.insert_at(1, json!(2)));
+    assert!(!reactive_property_instance.insert_at(4, json!(5)));
+    assert_eq!(Some(3), reactive_property_instance.len());
+    assert_eq!(Some(json!(3)), reactive_property_instance.pop());
+    assert_eq!(Some(json!(1)), reactive_property_instance.remove_at(0));
+    assert_eq!(None, reactive_property_instance.remove_at(1));
+    assert_eq!(json!([2]), reactive_property_instance.get());
+    assert_eq!(Some(false), reactive_property_instance.is_empty());
+    assert_eq!(vec![json!([1, 3]), json!([1, 2, 3]), json!([1, 2]), json!([2])], *propagated.read().unwrap());
+
+    let reactive_property_instance = ReactivePropertyInstance::new(Uuid::new_v4(), "name", json!("alice"));
+    assert!(!reactive_property_instance.push(json!(1)));
+    assert_eq!(None, reactive_property_instance.pop());
+    assert_eq!(None, reactive_property_instance.len());
+    assert_eq!(json!("alice"), reactive_property_instance.get());
+}
+
+#[test]
+fn reactive_property_instance_array_concurrent_test() {
+    let reactive_property_instance = Arc::new(ReactivePropertyInstance::new(Uuid::new_v4(), "items", json!([])));
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let reactive_property_instance = reactive_property_instance.clone();
+            thread::spawn(move || {
+                for i in 0..100 {
+                    reactive_property_instance.push(json!(i));
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(Some(400), reactive_property_instance.len());
+}
+
+#[test]
+fn reactive_property_instance_uuid_test() {
+    let uuid = Uuid::new_v4();
+    let reactive_property_instance = ReactivePropertyInstance::new(Uuid::new_v4(), "ref", json!(uuid.to_string()));
+    assert_eq!(Some(uuid), reactive_property_instance.as_uuid());
+    reactive_property_instance.set(json!("2021-05-01T12:30:00Z"));
+    assert!(reactive_property_instance.as_uuid().is_none());
+}
@@ -1,25 +1,37 @@
-use std::collections::HashMap;
+use std::collections::HashSet;
+#[cfg(feature = "graph-db")]
 use std::str::FromStr;
 use std::sync::Arc;
 
 use dashmap::DashMap;
 use dashmap::DashSet;
-use indradb::Edge;
-use indradb::EdgeKey;
-use indradb::EdgeProperties;
-use indradb::Identifier;
-use indradb::NamedProperty;
+#[cfg(feature = "graph-db")]
+use indradb::{Edge, EdgeProperties, NamedProperty};
 use serde_json::json;
 use uuid::Uuid;
 
-use crate::tests::utils::create_random_entity_instance::create_random_entity_instance;
+use crate::tests::utils::create_random_entity_instance::{create_random_entity_instance, create_random_entity_instance_with_type};
 use crate::tests::utils::{r_json_string, r_string};
+use crate::Component;
+use crate::ComponentOrEntityTypeName;
+use crate::DataType;
+use crate::EntityInstance;
+use crate::InstanceConversionError;
 use crate::PropertyInstanceGetter;
 use crate::PropertyInstanceSetter;
+use crate::PropertyMap;
+use crate::PropertyType;
 use crate::ReactiveEntityInstance;
 use crate::ReactivePropertyInstance;
 use crate::ReactiveRelationInstance;
 use crate::RelationInstance;
+use crate::RelationInstanceKey;
+use crate::RelationInstanceTypeError;
+use crate::RelationType;
+use crate::Revision;
+use crate::SocketType;
+use crate::StrictPropertyMode;
+use crate::{BehaviourName, ComponentName, EdgeKey, EntityTypeName, Identifier, ReactivePropertyContainer, RelationTypeName};
 
 #[test]
 fn reactive_relation_instance_test() {
@@ -32,49 +44,56 @@ fn reactive_relation_instance_test() {
 
     let properties = DashMap::new();
     properties.insert(
-        property_name.clone(),
+        property_name.as_str().into(),
         ReactivePropertyInstance::new(Uuid::new_v4(), property_name.clone(), property_value.clone()),
     );
 
     let component_name = r_string();
     let component_name_2 = r_string();
-    let components = DashSet::new();
-    components.insert(component_name.clone());
+    let components: DashSet<ComponentName> = DashSet::new();
+    components.insert(ComponentName::new(component_name.clone()));
 
     let behaviour_name = r_string();
     let behaviour_name_2 = r_string();
-    let behaviours = DashSet::new();
-    behaviours.insert(behaviour_name.clone());
+    let behaviours: DashSet<BehaviourName> = DashSet::new();
+    behaviours.insert(BehaviourName::new(behaviour_name.clone()));
 
     let reactive_relation_instance = Arc::new(ReactiveRelationInstance {
         outbound: outbound_entity.clone(),
-        type_name: relation_type_name.clone(),
+        type_name: RelationTypeName::new(relation_type_name.clone()),
         inbound: inbound_entity.clone(),
-        description: relation_description.clone(),
+        description: relation_description.clone().into(),
         properties,
         components,
+        labels: DashSet::new(),
         behaviours,
+        behaviour_states: DashMap::new(),
+        strict_mode: StrictPropertyMode::default(),
+        revision: Revision::default(),
+        observers: Arc::default(),
+        key: EdgeKey::new(outbound_entity.id, Identifier::new(relation_type_name.clone()).unwrap(), inbound_entity.id),
+        id: RelationInstanceKey::new(outbound_entity.id, relation_type_name.clone(), inbound_entity.id).id(),
     });
     assert_eq!(relation_type_name.clone(), reactive_relation_instance.type_name.clone());
     assert_eq!(outbound_entity.id, reactive_relation_instance.outbound.id);
     assert_eq!(inbound_entity.id, reactive_relation_instance.inbound.id);
     assert_eq!(relation_description.clone(), reactive_relation_instance.description.clone());
 
-    assert!(reactive_relation_instance.is_a(component_name.clone()));
-    assert!(!reactive_relation_instance.is_a(component_name_2.clone()));
-    assert!(!reactive_relation_instance.is_a(r_string()));
-    reactive_relation_instance.add_component(component_name_2.clone());
-    assert!(reactive_relation_instance.is_a(component_name_2.clone()));
-    reactive_relation_instance.remove_component(component_name.clone());
-    assert!(!reactive_relation_instance.is_a(component_name.clone()));
-
-    assert!(reactive_relation_instance.behaves_as(behaviour_name.clone()));
-    assert!(!reactive_relation_instance.behaves_as(behaviour_name_2.clone()));
-    assert!(!reactive_relation_instance.behaves_as(r_string()));
-    reactive_relation_instance.add_behaviour(behaviour_name_2.clone());
-    assert!(reactive_relation_instance.behaves_as(behaviour_name_2.clone()));
-    reactive_relation_instance.remove_behaviour(behaviour_name.clone());
-    assert!(!reactive_relation_instance.behaves_as(behaviour_name.clone()));
+    assert!(reactive_relation_instance.is_a(ComponentName::new(component_name.clone())));
+    assert!(!reactive_relation_instance.is_a(ComponentName::new(component_name_2.clone())));
+    assert!(!reactive_relation_instance.is_a(ComponentName::new(r_string())));
+    reactive_relation_instance.add_component(ComponentName::new(component_name_2.clone()));
+    assert!(reactive_relation_instance.is_a(ComponentName::new(component_name_2.clone())));
+    reactive_relation_instance.remove_component(ComponentName::new(component_name.clone()));
+    assert!(!reactive_relation_instance.is_a(ComponentName::new(component_name.clone())));
+
+    assert!(reactive_relation_instance.behaves_as(BehaviourName::new(behaviour_name.clone())));
+    assert!(!reactive_relation_instance.behaves_as(BehaviourName::new(behaviour_name_2.clone())));
+    assert!(!reactive_relation_instance.behaves_as(BehaviourName::new(r_string())));
+    reactive_relation_instance.add_behaviour(BehaviourName::new(behaviour_name_2.clone()));
+    assert!(reactive_relation_instance.behaves_as(BehaviourName::new(behaviour_name_2.clone())));
+    reactive_relation_instance.remove_behaviour(BehaviourName::new(behaviour_name.clone()));
+    assert!(!reactive_relation_instance.behaves_as(BehaviourName::new(behaviour_name.clone())));
 
     let relation_instance: RelationInstance = reactive_relation_instance.into();
     assert_eq!(outbound_entity.id, relation_instance.outbound_id);
@@ -91,36 +110,53 @@ fn reactive_relation_instance_from_edge_properties_test() {
     let outbound_type_name = r_string();
     let outbound_description = r_string();
     let outbound_entity = Arc::new(ReactiveEntityInstance {
-        type_name: outbound_type_name.clone(),
+        type_name: EntityTypeName::new(outbound_type_name.clone()),
         id: outbound_id.clone(),
-        description: outbound_description.clone(),
+        description: outbound_description.clone().into(),
         properties: DashMap::new(),
         components: DashSet::new(),
+        labels: DashSet::new(),
         behaviours: DashSet::new(),
+        behaviour_states: DashMap::new(),
+        strict_mode: StrictPropertyMode::default(),
+        revision: Revision::default(),
+        observers: Arc::default(),
     });
 
     let inbound_id = Uuid::new_v4();
     let inbound_type_name = r_string();
     let inbound_description = r_string();
     let inbound_entity = Arc::new(ReactiveEntityInstance {
-        type_name: inbound_type_name.clone(),
+        type_name: EntityTypeName::new(inbound_type_name.clone()),
         id: inbound_id.clone(),
-        description: inbound_description.clone(),
+        description: inbound_description.clone().into(),
         properties: DashMap::new(),
         components: DashSet::new(),
+        labels: DashSet::new(),
         behaviours: DashSet::new(),
+        behaviour_states: DashMap::new(),
+        strict_mode: StrictPropertyMode::default(),
+        revision: Revision::default(),
+        observers: Arc::default(),
     });
 
     let relation_type_name = r_string();
     let relation_description = r_string();
     let reactive_relation_instance = Arc::new(ReactiveRelationInstance {
         outbound: outbound_entity.clone(), // Arc::clone -> Reference Counted
-        type_name: relation_type_name.clone(),
+        type_name: RelationTypeName::new(relation_type_name.clone()),
         inbound: inbound_entity.clone(), // Arc::clone -> Reference Counted
-        description: relation_description.clone(),
+        description: relation_description.clone().into(),
         properties: DashMap::new(),
         components: DashSet::new(),
+        labels: DashSet::new(),
         behaviours: DashSet::new(),
+        behaviour_states: DashMap::new(),
+        strict_mode: StrictPropertyMode::default(),
+        revision: Revision::default(),
+        observers: Arc::default(),
+        key: EdgeKey::new(outbound_entity.id, Identifier::new(relation_type_name.clone()).unwrap(), inbound_entity.id),
+        id: RelationInstanceKey::new(outbound_entity.id, relation_type_name.clone(), inbound_entity.id).id(),
     });
 
     assert_eq!(relation_type_name.clone(), reactive_relation_instance.type_name.clone());
@@ -135,6 +171,7 @@ fn reactive_relation_instance_from_edge_properties_test() {
     assert_eq!(relation_description.clone(), relation_instance.description.clone());
 }
 
+#[cfg(feature = "graph-db")]
 #[test]
 fn reactive_relation_instance_typed_getter_test() {
     let property_name = r_string();
@@ -177,27 +214,28 @@ fn reactive_relation_instance_with_properties_typed_getter_test() {
     assert_eq!(s, i.as_string(property_name.clone()).unwrap());
 }
 
+#[cfg(feature = "graph-db")]
 fn create_random_relation_instance(
     outbound_entity: Arc<ReactiveEntityInstance>,
     inbound_entity: Arc<ReactiveEntityInstance>,
     property_name: String,
 ) -> ReactiveRelationInstance {
     let type_name = r_string();
-    let t = Identifier::from_str(type_name.as_str()).unwrap();
+    let t = indradb::Identifier::from_str(type_name.as_str()).unwrap();
     let property_value = r_string();
     let property_value_json = json!(property_value);
     let property = NamedProperty {
-        name: Identifier::new(property_name).unwrap(),
+        name: indradb::Identifier::new(property_name).unwrap(),
         value: property_value_json,
     };
     let properties = vec![property];
     let outbound_id = outbound_entity.id;
     let inbound_id = inbound_entity.id;
-    let edge_key = EdgeKey::new(outbound_id, t, inbound_id);
+    let edge_key = indradb::EdgeKey::new(outbound_id, t, inbound_id);
     let edge_properties = EdgeProperties::new(Edge::new_with_current_datetime(edge_key), properties.clone());
     let outbound_entity = outbound_entity.clone();
     let inbound_entity = outbound_entity.clone();
-    ReactiveRelationInstance::from(outbound_entity, inbound_entity, edge_properties)
+    ReactiveRelationInstance::from(outbound_entity, inbound_entity, edge_properties).unwrap()
 }
 
 pub fn create_random_relation_instance_with_properties(
@@ -205,7 +243,222 @@ pub fn create_random_relation_instance_with_properties(
     inbound_entity: Arc<ReactiveEntityInstance>,
     property_name: String,
 ) -> ReactiveRelationInstance {
-    let mut properties = HashMap::new();
+    let mut properties = PropertyMap::new();
     properties.insert(property_name.clone(), json!(r_string()));
-    ReactiveRelationInstance::create_with_properties(outbound_entity.clone(), r_string(), inbound_entity.clone(), properties)
+    ReactiveRelationInstance::create_with_properties(outbound_entity.clone(), RelationTypeName::new(r_string()), inbound_entity.clone(), properties).unwrap()
+}
+
+#[test]
+fn reactive_relation_instance_display_test() {
+    let outbound_entity = Arc::new(ReactiveEntityInstance::from(EntityInstance::new_without_properties(
+        EntityTypeName::new("player"),
+        Uuid::new_v4(),
+    )));
+    let inbound_entity = Arc::new(ReactiveEntityInstance::from(EntityInstance::new_without_properties(
+        EntityTypeName::new("camera"),
+        Uuid::new_v4(),
+    )));
+    let relation_instance = ReactiveRelationInstance::create_with_properties(
+        outbound_entity.clone(),
+        RelationTypeName::new("looks_at"),
+        inbound_entity.clone(),
+        PropertyMap::new(),
+    )
+    .unwrap();
+    assert_eq!(format!("player({})", outbound_entity.id), outbound_entity.to_string());
+    assert_eq!(
+        format!("player({})--(looks_at)-->camera({})", outbound_entity.id, inbound_entity.id),
+        relation_instance.to_string()
+    );
+    assert_eq!(
+        format!("{}--looks_at--{}", outbound_entity.id, inbound_entity.id),
+        relation_instance.get_relation_key().to_string()
+    );
+}
+
+#[test]
+fn reactive_relation_instance_builder_test() {
+    let outbound = Arc::new(create_random_entity_instance(r_string()));
+    let inbound = Arc::new(create_random_entity_instance(r_string()));
+    let relation_instance = ReactiveRelationInstance::builder(outbound.clone(), RelationTypeName::new("connector"), inbound.clone())
+        .description("Connects two numbers")
+        .property("weight", json!(1))
+        .component(ComponentName::new("connector"))
+        .behaviour(BehaviourName::new("default_connector"))
+        .build()
+        .unwrap();
+    assert_eq!(outbound.id, relation_instance.outbound.id);
+    assert_eq!(inbound.id, relation_instance.inbound.id);
+    assert_eq!("connector", relation_instance.type_name);
+    assert_eq!("Connects two numbers", relation_instance.description);
+    assert_eq!(json!(1), relation_instance.get("weight").unwrap());
+    assert!(relation_instance.is_a(ComponentName::new("connector")));
+    assert!(relation_instance.behaves_as(BehaviourName::new("default_connector")));
+    assert_eq!(outbound.id, relation_instance.get_key().unwrap().outbound_id);
+}
+
+#[test]
+fn reactive_relation_instance_new_from_type_test() {
+    let weighted = Component::new(ComponentName::new("weighted"), vec![PropertyType::new("weight", DataType::Number)]);
+    let relation_type = RelationType::new(
+        "player",
+        RelationTypeName::new("looks_at"),
+        "component:camera",
+        "",
+        "",
+        vec![ComponentName::new("weighted")],
+        vec![
+            PropertyType::new("distance", DataType::Number),
+            PropertyType::event("changed", DataType::Object, SocketType::Output),
+        ],
+        Vec::new(),
+    );
+    let player = Arc::new(create_random_entity_instance_with_type("player", "x"));
+    let camera = Arc::new(create_random_entity_instance_with_type("camera", "x"));
+    camera.add_component(ComponentName::new("camera"));
+
+    let relation_instance = ReactiveRelationInstance::new_from_type(player.clone(), &relation_type, camera.clone(), &[weighted]).unwrap();
+    assert_eq!("looks_at", relation_instance.type_name);
+    assert_eq!(3, relation_instance.properties.len());
+    assert_eq!(json!(0), relation_instance.get("weight").unwrap());
+    assert_eq!(json!(0), relation_instance.get("distance").unwrap());
+    assert!(relation_instance.properties.get("changed").unwrap().is_event());
+    assert!(relation_instance.components.contains("weighted"));
+
+    assert_eq!(
+        Err(RelationInstanceTypeError::InvalidOutbound(
+            camera.id,
+            ComponentOrEntityTypeName::EntityType(EntityTypeName::new("player"))
+        )),
+        ReactiveRelationInstance::new_from_type(camera.clone(), &relation_type, camera.clone(), &[]).map(|_| ())
+    );
+    assert_eq!(
+        Err(RelationInstanceTypeError::InvalidInbound(
+            player.id,
+            ComponentOrEntityTypeName::Component(ComponentName::new("camera"))
+        )),
+        ReactiveRelationInstance::new_from_type(player.clone(), &relation_type, player.clone(), &[]).map(|_| ())
+    );
+    let mut abstract_relation_type = relation_type.clone();
+    abstract_relation_type.is_abstract = true;
+    assert_eq!(
+        Err(RelationInstanceTypeError::Abstract("looks_at".to_string())),
+        ReactiveRelationInstance::new_from_type(player, &abstract_relation_type, camera, &[]).map(|_| ())
+    );
+}
+
+#[test]
+fn reactive_relation_instance_set_description_test() {
+    let outbound_entity = Arc::new(create_random_entity_instance(r_string()));
+    let inbound_entity = Arc::new(create_random_entity_instance(r_string()));
+    let relation_instance = ReactiveRelationInstance::builder(outbound_entity, RelationTypeName::new(r_string()), inbound_entity)
+        .description("Connects two numbers")
+        .build()
+        .unwrap();
+    let observed = Arc::new(std::sync::RwLock::new(String::new()));
+    let observed_2 = observed.clone();
+    relation_instance
+        .description
+        .observe_with_handle(move |description| *observed_2.write().unwrap() = description.clone(), 1);
+
+    relation_instance.set_description("Connects two strings");
+    assert_eq!("Connects two strings", relation_instance.description);
+    assert_eq!("Connects two strings", *observed.read().unwrap());
+
+    relation_instance.description.remove(1);
+    relation_instance.set_description("Connects two booleans");
+    assert_eq!("Connects two strings", *observed.read().unwrap());
+}
+
+#[test]
+fn reactive_relation_instance_components_round_trip_test() {
+    let outbound = Arc::new(create_random_entity_instance(r_string()));
+    let inbound = Arc::new(create_random_entity_instance(r_string()));
+    let reactive_relation_instance = Arc::new(create_random_relation_instance_with_properties(outbound.clone(), inbound.clone(), r_string()));
+    reactive_relation_instance.add_component(ComponentName::new("weighted"));
+    let relation_instance: RelationInstance = reactive_relation_instance.into();
+    assert_eq!(vec!["weighted".to_string()], relation_instance.components);
+
+    let json = serde_json::to_value(&relation_instance).unwrap();
+    let relation_instance: RelationInstance = serde_json::from_value(json).unwrap();
+    let restored = ReactiveRelationInstance::from_instance(outbound, inbound, relation_instance).unwrap();
+    assert!(restored.is_a(ComponentName::new("weighted")));
+}
+
+#[test]
+fn reactive_relation_instance_key_test() {
+    let outbound_entity = Arc::new(create_random_entity_instance(r_string()));
+    let inbound_entity = Arc::new(create_random_entity_instance(r_string()));
+    let relation_instance = ReactiveRelationInstance::builder(outbound_entity.clone(), RelationTypeName::new("looks_at"), inbound_entity.clone())
+        .build()
+        .unwrap();
+    let key = relation_instance.key();
+    assert_eq!(outbound_entity.id, key.outbound_id);
+    assert_eq!("looks_at", key.t.as_str());
+    assert_eq!(inbound_entity.id, key.inbound_id);
+    assert_eq!(Some(key.clone()), relation_instance.get_key());
+
+    let rewired = relation_instance.rewired(inbound_entity.clone(), outbound_entity.clone());
+    assert_eq!(EdgeKey::new(inbound_entity.id, key.t.clone(), outbound_entity.id), *rewired.key());
+
+    let result = ReactiveRelationInstance::builder(outbound_entity.clone(), RelationTypeName::new(""), inbound_entity.clone()).build();
+    assert_eq!(InstanceConversionError::InvalidIdentifier("".to_string()), result.err().unwrap());
+}
+
+#[test]
+fn reactive_relation_instance_invalid_type_name_test() {
+    let outbound_entity = Arc::new(create_random_entity_instance(r_string()));
+    let inbound_entity = Arc::new(create_random_entity_instance(r_string()));
+    let result =
+        ReactiveRelationInstance::create_with_properties(outbound_entity.clone(), RelationTypeName::new(""), inbound_entity.clone(), PropertyMap::new());
+    assert_eq!(InstanceConversionError::InvalidIdentifier("".to_string()), result.err().unwrap());
+    let relation_instance = RelationInstance::new_without_properties(outbound_entity.id, RelationTypeName::new(""), inbound_entity.id);
+    assert!(ReactiveRelationInstance::from_instance(outbound_entity, inbound_entity, relation_instance).is_err());
+}
+
+#[test]
+// The hash only depends on the identity, which is immutable
+#[allow(clippy::mutable_key_type)]
+fn reactive_relation_instance_identity_test() {
+    let outbound_entity = Arc::new(create_random_entity_instance(r_string()));
+    let inbound_entity = Arc::new(create_random_entity_instance(r_string()));
+    let a = Arc::new(
+        ReactiveRelationInstance::builder(outbound_entity.clone(), RelationTypeName::new("looks_at"), inbound_entity.clone())
+            .build()
+            .unwrap(),
+    );
+    let b = Arc::new(
+        ReactiveRelationInstance::builder(outbound_entity.clone(), RelationTypeName::new("looks_at"), inbound_entity.clone())
+            .build()
+            .unwrap(),
+    );
+    let c = Arc::new(
+        ReactiveRelationInstance::builder(inbound_entity, RelationTypeName::new("looks_at"), outbound_entity)
+            .build()
+            .unwrap(),
+    );
+    assert!(a == b);
+    assert!(a != c);
+    assert!(a.same_instance(&a.clone()));
+    assert!(!a.same_instance(&b));
+
+    let deduplicated: HashSet<Arc<ReactiveRelationInstance>> = vec![a.clone(), b, a, c].into_iter().collect();
+    assert_eq!(2, deduplicated.len());
+}
+
+#[test]
+fn reactive_relation_instance_builder_socket_test() {
+    let outbound_entity = Arc::new(create_random_entity_instance(r_string()));
+    let inbound_entity = Arc::new(create_random_entity_instance(r_string()));
+    let relation_instance = ReactiveRelationInstance::builder(outbound_entity, RelationTypeName::new("connector"), inbound_entity)
+        .property("weight", json!(1))
+        .property("result", json!(0))
+        .socket("weight", SocketType::Input)
+        .build()
+        .unwrap();
+    assert!(relation_instance.properties.get("weight").unwrap().is_input());
+    assert_eq!(SocketType::None, relation_instance.properties.get("result").unwrap().socket_type);
+
+    relation_instance.set_socket_type("result", SocketType::Output);
+    assert_eq!(vec!["result".to_string()], relation_instance.outputs().map(|p| p.key().to_string()).collect::<Vec<String>>());
 }
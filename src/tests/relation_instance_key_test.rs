@@ -0,0 +1,51 @@
+use std::str::FromStr;
+
+use uuid::Uuid;
+
+use crate::{RelationInstance, RelationInstanceKey, RelationInstanceKeyParseError, RelationKey, RelationTypeName};
+
+#[test]
+fn relation_key_display_test() {
+    let outbound_id = Uuid::new_v4();
+    let inbound_id = Uuid::new_v4();
+    let key = RelationKey::new(outbound_id, "connector", inbound_id);
+    assert_eq!(format!("{}--connector--{}", outbound_id, inbound_id), key.to_string());
+}
+
+#[test]
+fn relation_key_from_str_test() {
+    let outbound_id = Uuid::new_v4();
+    let inbound_id = Uuid::new_v4();
+    let key = RelationInstanceKey::new(outbound_id, "connector", inbound_id);
+    assert_eq!(Ok(key.clone()), RelationInstanceKey::from_str(&key.to_string()));
+    assert_eq!(Ok(key), format!("{}--(connector)-->{}", outbound_id, inbound_id).parse());
+
+    // The type name may contain the separator
+    let key = RelationInstanceKey::new(outbound_id, "default--connector", inbound_id);
+    assert_eq!(Ok(key.clone()), key.to_string().parse());
+
+    assert_eq!(
+        Err(RelationInstanceKeyParseError::InvalidFormat("connector".to_string())),
+        RelationInstanceKey::from_str("connector")
+    );
+    assert_eq!(
+        Err(RelationInstanceKeyParseError::InvalidOutboundId("x".to_string())),
+        RelationInstanceKey::from_str(&format!("x--connector--{}", inbound_id))
+    );
+    assert_eq!(
+        Err(RelationInstanceKeyParseError::InvalidInboundId("x".to_string())),
+        RelationInstanceKey::from_str(&format!("{}--connector--x", outbound_id))
+    );
+    assert_eq!(
+        Err(RelationInstanceKeyParseError::InvalidTypeName("con nector".to_string())),
+        RelationInstanceKey::from_str(&format!("{}--con nector--{}", outbound_id, inbound_id))
+    );
+}
+
+#[test]
+fn relation_instance_get_relation_key_test() {
+    let outbound_id = Uuid::new_v4();
+    let inbound_id = Uuid::new_v4();
+    let relation_instance = RelationInstance::new(outbound_id, RelationTypeName::new("connector"), inbound_id, Default::default());
+    assert_eq!(RelationInstanceKey::new(outbound_id, "connector", inbound_id), relation_instance.get_relation_key());
+}
@@ -1,18 +1,18 @@
-use std::collections::HashMap;
+use std::collections::HashSet;
+#[cfg(feature = "graph-db")]
 use std::str::FromStr;
 
-use indradb::Edge;
-use indradb::EdgeKey;
-use indradb::EdgeProperties;
-use indradb::Identifier;
-use indradb::NamedProperty;
+#[cfg(feature = "graph-db")]
+use indradb::{Edge, EdgeProperties, NamedProperty};
 use serde_json::json;
 use uuid::Uuid;
 
 use crate::tests::utils::{r_string, r_string_1000, r_string_255};
 use crate::MutablePropertyInstanceSetter;
 use crate::PropertyInstanceGetter;
+use crate::PropertyMap;
 use crate::RelationInstance;
+use crate::{EdgeKey, Identifier, RelationTypeName};
 
 #[test]
 fn relation_instance_test() {
@@ -22,14 +22,17 @@ fn relation_instance_test() {
     let description = r_string();
     let property_name = r_string();
     let property_value = json!(r_string());
-    let mut properties = HashMap::new();
+    let mut properties = PropertyMap::new();
     properties.insert(property_name.clone(), property_value.clone());
     let relation_instance = RelationInstance {
         outbound_id,
-        type_name: type_name.clone(),
+        type_name: RelationTypeName::new(type_name.clone()),
         inbound_id,
         description: description.to_string(),
         properties: properties.clone(),
+        components: Vec::new(),
+        labels: Vec::new(),
+        behaviours: Vec::new(),
     };
     assert_eq!(outbound_id.clone(), relation_instance.outbound_id.clone());
     assert_eq!(type_name.clone(), relation_instance.type_name.clone());
@@ -45,10 +48,13 @@ fn relation_instance_test() {
 fn invalid_type_name_test() {
     let relation_instance = RelationInstance {
         outbound_id: Uuid::new_v4(),
-        type_name: r_string_1000(),
+        type_name: RelationTypeName::new(r_string_1000()),
         inbound_id: Uuid::new_v4(),
         description: r_string(),
-        properties: HashMap::new(),
+        properties: PropertyMap::new(),
+        components: Vec::new(),
+        labels: Vec::new(),
+        behaviours: Vec::new(),
     };
     assert!(relation_instance.get_key().is_none());
 }
@@ -57,10 +63,13 @@ fn invalid_type_name_test() {
 fn valid_type_name_test() {
     let relation_instance = RelationInstance {
         outbound_id: Uuid::new_v4(),
-        type_name: r_string_255(),
+        type_name: RelationTypeName::new(r_string_255()),
         inbound_id: Uuid::new_v4(),
         description: r_string(),
-        properties: HashMap::new(),
+        properties: PropertyMap::new(),
+        components: Vec::new(),
+        labels: Vec::new(),
+        behaviours: Vec::new(),
     };
     assert!(relation_instance.get_key().is_some());
 }
@@ -72,9 +81,9 @@ fn create_relation_instance_test() {
     let type_name = r_string();
     let property_name = r_string();
     let property_value = json!(r_string());
-    let mut properties = HashMap::new();
+    let mut properties = PropertyMap::new();
     properties.insert(property_name.clone(), property_value.clone());
-    let relation_instance = RelationInstance::new(outbound_id, type_name.clone(), inbound_id, properties.clone());
+    let relation_instance = RelationInstance::new(outbound_id, RelationTypeName::new(type_name.clone()), inbound_id, properties.clone());
     assert_eq!(outbound_id.clone(), relation_instance.outbound_id.clone());
     assert_eq!(type_name.clone(), relation_instance.type_name.clone());
     assert_eq!(inbound_id.clone(), relation_instance.inbound_id.clone());
@@ -84,21 +93,22 @@ fn create_relation_instance_test() {
     assert_eq!(property_value.clone(), relation_instance.get(property_name.clone()).unwrap());
 }
 
+#[cfg(feature = "graph-db")]
 #[test]
 fn create_relation_instance_from_edge_properties() {
     let outbound_id = Uuid::new_v4();
     let inbound_id = Uuid::new_v4();
     let type_name = r_string();
-    let t = Identifier::from_str(type_name.as_str()).unwrap();
+    let t = indradb::Identifier::from_str(type_name.as_str()).unwrap();
     let property_name = r_string();
     let property_value = r_string();
     let property_value_json = json!(property_value);
     let property = NamedProperty {
-        name: Identifier::new(&property_name).unwrap(),
+        name: indradb::Identifier::new(&property_name).unwrap(),
         value: property_value_json,
     };
     let properties = vec![property];
-    let edge_key = EdgeKey::new(outbound_id, t, inbound_id);
+    let edge_key = indradb::EdgeKey::new(outbound_id, t, inbound_id);
     let edge_properties = EdgeProperties::new(Edge::new_with_current_datetime(edge_key), properties.clone());
     let relation_instance = RelationInstance::from(edge_properties);
     assert_eq!(outbound_id.clone(), relation_instance.outbound_id.clone());
@@ -113,9 +123,9 @@ fn relation_instance_typed_getter_test() {
     let inbound_id = Uuid::new_v4();
     let type_name = r_string();
     let property_name = r_string();
-    let mut properties = HashMap::new();
+    let mut properties = PropertyMap::new();
     properties.insert(property_name.clone(), json!(false));
-    let mut i = RelationInstance::new(outbound_id, type_name.clone(), inbound_id, properties.clone());
+    let mut i = RelationInstance::new(outbound_id, RelationTypeName::new(type_name.clone()), inbound_id, properties.clone());
     i.set(property_name.clone(), json!(true));
     assert!(i.as_bool(property_name.clone()).unwrap());
     i.set(property_name.clone(), json!(false));
@@ -137,15 +147,83 @@ fn relation_instance_get_key_test() {
     let inbound_id = Uuid::new_v4();
     let type_name = r_string();
     let description = r_string();
-    let properties = HashMap::new();
+    let properties = PropertyMap::new();
     let relation_instance = RelationInstance {
         outbound_id,
-        type_name: type_name.clone(),
+        type_name: RelationTypeName::new(type_name.clone()),
         inbound_id,
         description: description.to_string(),
         properties: properties.clone(),
+        components: Vec::new(),
+        labels: Vec::new(),
+        behaviours: Vec::new(),
     };
     let edge_key = relation_instance.get_key();
     assert!(edge_key.is_some());
     assert_eq!(EdgeKey::new(outbound_id, Identifier::new(type_name.clone()).unwrap(), inbound_id), edge_key.unwrap());
 }
+
+#[test]
+fn relation_instance_eq_test() {
+    let outbound_id = Uuid::new_v4();
+    let inbound_id = Uuid::new_v4();
+    let type_name = r_string();
+    let relation_instance_1 = RelationInstance::new_without_properties(outbound_id, RelationTypeName::new(type_name.clone()), inbound_id);
+    let mut relation_instance_2 = RelationInstance::new_without_properties(outbound_id, RelationTypeName::new(type_name.clone()), inbound_id);
+    relation_instance_2.properties.insert(r_string(), json!(1));
+    let relation_instance_3 = RelationInstance::new_without_properties(inbound_id, RelationTypeName::new(type_name.clone()), outbound_id);
+    assert_eq!(relation_instance_1, relation_instance_2);
+    assert_ne!(relation_instance_1, relation_instance_3);
+    let relation_instances: HashSet<RelationInstance> = vec![relation_instance_1, relation_instance_2, relation_instance_3].into_iter().collect();
+    assert_eq!(2, relation_instances.len());
+}
+
+#[test]
+fn relation_instance_display_test() {
+    let outbound_id = Uuid::new_v4();
+    let inbound_id = Uuid::new_v4();
+    let relation_instance = RelationInstance::new_without_properties(outbound_id, RelationTypeName::new("looks_at"), inbound_id);
+    assert_eq!(format!("{}--(looks_at)-->{}", outbound_id, inbound_id), relation_instance.to_string());
+    assert_eq!(format!("{}--looks_at--{}", outbound_id, inbound_id), relation_instance.get_relation_key().to_string());
+}
+
+#[test]
+fn relation_instance_builder_test() {
+    let outbound_id = Uuid::new_v4();
+    let inbound_id = Uuid::new_v4();
+    let mut properties = PropertyMap::new();
+    properties.insert("weight".to_string(), json!(1));
+    let relation_instance = RelationInstance::builder(outbound_id, RelationTypeName::new("connector"), inbound_id)
+        .description("Connects two numbers")
+        .properties(properties)
+        .property("enabled", json!(true))
+        .build();
+    assert_eq!(outbound_id, relation_instance.outbound_id);
+    assert_eq!("connector", relation_instance.type_name);
+    assert_eq!(inbound_id, relation_instance.inbound_id);
+    assert_eq!("Connects two numbers", relation_instance.description);
+    assert_eq!(json!(1), relation_instance.get("weight").unwrap());
+    assert_eq!(json!(true), relation_instance.get("enabled").unwrap());
+}
+
+#[test]
+fn relation_instance_serde_names_test() {
+    let outbound_id = Uuid::new_v4();
+    let inbound_id = Uuid::new_v4();
+    let relation_instance: RelationInstance = serde_json::from_value(json!({
+        "outbound": outbound_id,
+        "type": "owns",
+        "inbound": inbound_id
+    }))
+    .unwrap();
+    assert_eq!(outbound_id, relation_instance.outbound_id);
+    assert_eq!("owns", relation_instance.type_name);
+    assert_eq!(inbound_id, relation_instance.inbound_id);
+    let json = serde_json::to_value(&relation_instance).unwrap();
+    assert_eq!(json!(outbound_id), json["outbound_id"]);
+    assert_eq!(json!("owns"), json["type_name"]);
+    assert_eq!(json!(inbound_id), json["inbound_id"]);
+    let relation_instance: RelationInstance = serde_json::from_value(json).unwrap();
+    assert_eq!(outbound_id, relation_instance.outbound_id);
+    assert_eq!(inbound_id, relation_instance.inbound_id);
+}
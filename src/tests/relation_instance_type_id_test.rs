@@ -0,0 +1,56 @@
+use std::str::FromStr;
+
+use uuid::Uuid;
+
+use crate::Identifier;
+use crate::{RelationInstance, RelationInstanceTypeId, RelationInstanceTypeIdError, RelationTypeName};
+
+#[test]
+fn relation_instance_type_id_parse_test() {
+    let type_id = RelationInstanceTypeId::from_str("connector__a1b2c3").unwrap();
+    assert_eq!(RelationInstanceTypeId::new_with_instance_id("connector", "a1b2c3"), type_id);
+    assert_eq!("connector__a1b2c3", type_id.to_string());
+
+    let type_id = RelationInstanceTypeId::from_str("connector").unwrap();
+    assert_eq!(RelationInstanceTypeId::new("connector"), type_id);
+    assert_eq!("connector", type_id.to_string());
+
+    assert_eq!(Err(RelationInstanceTypeIdError::EmptyTypeName), RelationInstanceTypeId::from_str(""));
+    assert_eq!(Err(RelationInstanceTypeIdError::EmptyTypeName), RelationInstanceTypeId::from_str("__a1b2c3"));
+    assert_eq!(Err(RelationInstanceTypeIdError::EmptyInstanceId), RelationInstanceTypeId::from_str("connector__"));
+}
+
+#[test]
+fn relation_instance_type_id_separator_test() {
+    let type_id = RelationInstanceTypeId::from_str("default_connector__value__result").unwrap();
+    assert_eq!(RelationInstanceTypeId::new_with_instance_id("default_connector", "value__result"), type_id);
+    assert_eq!(type_id, RelationInstanceTypeId::try_from(&Identifier::try_from(&type_id).unwrap()).unwrap());
+
+    let type_id = RelationInstanceTypeId::new_with_instance_id("default__connector", "a1b2c3");
+    assert_eq!(
+        Err(RelationInstanceTypeIdError::InvalidTypeName("default__connector".to_string())),
+        Identifier::try_from(&type_id)
+    );
+    let type_id = RelationInstanceTypeId::new("default__connector");
+    assert_eq!(
+        Err(RelationInstanceTypeIdError::InvalidTypeName("default__connector".to_string())),
+        Identifier::try_from(&type_id)
+    );
+    let type_id = RelationInstanceTypeId::new_with_instance_id("connector_", "a1b2c3");
+    assert_eq!(Err(RelationInstanceTypeIdError::InvalidTypeName("connector_".to_string())), Identifier::try_from(&type_id));
+    assert!(RelationInstanceTypeId::new("connector_").validate().is_ok());
+}
+
+#[test]
+fn relation_instance_type_id_identifier_test() {
+    let type_id = RelationInstanceTypeId::new_with_instance_id("connector", "a1b2c3");
+    let t = Identifier::try_from(&type_id).unwrap();
+    assert_eq!("connector__a1b2c3", t.as_str());
+    assert_eq!(type_id, RelationInstanceTypeId::try_from(&t).unwrap());
+
+    let type_id = RelationInstanceTypeId::new("x".repeat(256));
+    assert_eq!(Err(RelationInstanceTypeIdError::InvalidIdentifier("x".repeat(256))), Identifier::try_from(&type_id));
+
+    let relation_instance = RelationInstance::new_without_properties(Uuid::new_v4(), RelationTypeName::new("connector__a1b2c3"), Uuid::new_v4());
+    assert_eq!(Some("a1b2c3".to_string()), relation_instance.get_type_id().unwrap().instance_id);
+}
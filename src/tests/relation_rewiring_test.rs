@@ -0,0 +1,85 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use serde_json::json;
+use serde_json::Value;
+
+use crate::tests::utils::{create_random_entity_instance, create_random_entity_instance_with_type, create_random_relation_instance_with_properties, r_string};
+use crate::{rewire_inbound, rewire_outbound, ComponentName, PropertyInstanceGetter, PropertyInstanceSetter, ReactiveFlow};
+
+#[test]
+fn rewire_outbound_test() {
+    let property_name = r_string();
+    let old_entity = Arc::new(create_random_entity_instance(r_string()));
+    let new_entity = Arc::new(create_random_entity_instance(r_string()));
+    let inbound = Arc::new(create_random_entity_instance(r_string()));
+    let relation_instance = Arc::new(create_random_relation_instance_with_properties(old_entity.clone(), inbound.clone(), property_name.clone()));
+    relation_instance.add_component(ComponentName::new("connector"));
+    let other = Arc::new(create_random_relation_instance_with_properties(inbound.clone(), old_entity.clone(), property_name.clone()));
+
+    let received = Arc::new(AtomicUsize::new(0));
+    let r = received.clone();
+    relation_instance
+        .properties
+        .get(property_name.as_str())
+        .unwrap()
+        .stream
+        .read()
+        .unwrap()
+        .observe(move |_: &Value| {
+            r.fetch_add(1, Ordering::SeqCst);
+        });
+
+    let rewirings = rewire_outbound(&[relation_instance.clone(), other.clone()], &old_entity, new_entity.clone());
+    assert_eq!(1, rewirings.len());
+    let rewiring = &rewirings[0];
+    assert_eq!(relation_instance.get_key(), rewiring.old_key);
+    let new_key = rewiring.new_key().unwrap();
+    assert_eq!(new_entity.id, new_key.outbound_id);
+    assert_eq!(inbound.id, new_key.inbound_id);
+    assert_eq!(relation_instance.type_name, rewiring.relation_instance.type_name);
+    assert!(rewiring.relation_instance.is_a(ComponentName::new("connector")));
+    assert_eq!(relation_instance.get(property_name.clone()), rewiring.relation_instance.get(property_name.clone()));
+
+    // The observers of the old relation instance are preserved
+    rewiring.relation_instance.set(property_name.clone(), json!(1));
+    assert_eq!(1, received.load(Ordering::SeqCst));
+
+    let rewirings = rewire_inbound(&[relation_instance, other], &old_entity, new_entity.clone());
+    assert_eq!(1, rewirings.len());
+    assert_eq!(new_entity.id, rewirings[0].new_key().unwrap().inbound_id);
+    assert_eq!(inbound.id, rewirings[0].new_key().unwrap().outbound_id);
+}
+
+#[test]
+fn reactive_flow_rewire_test() {
+    let property_name = r_string();
+    let wrapper = Arc::new(create_random_entity_instance_with_type("generic_flow", "test"));
+    let reactive_flow = ReactiveFlow::new(wrapper.clone());
+    let old_entity = Arc::new(create_random_entity_instance(r_string()));
+    let new_entity = Arc::new(create_random_entity_instance(r_string()));
+    reactive_flow.add_entity(old_entity.clone());
+    reactive_flow.add_entity(new_entity.clone());
+    let outgoing = Arc::new(create_random_relation_instance_with_properties(old_entity.clone(), wrapper.clone(), property_name.clone()));
+    let incoming = Arc::new(create_random_relation_instance_with_properties(wrapper.clone(), old_entity.clone(), property_name.clone()));
+    reactive_flow.add_relation(outgoing.clone());
+    reactive_flow.add_relation(incoming.clone());
+    reactive_flow.relations_added.write().unwrap().clear();
+
+    let rewired = reactive_flow.rewire_outbound(&old_entity, new_entity.clone());
+    assert_eq!(1, rewired.len());
+    assert!(!reactive_flow.has_relation_by_key(outgoing.get_key().unwrap()));
+    assert!(reactive_flow.has_relation_by_key(rewired[0].get_key().unwrap()));
+    assert!(reactive_flow.has_relation_by_key(incoming.get_key().unwrap()));
+
+    let rewired = reactive_flow.rewire_inbound(&old_entity, new_entity.clone());
+    assert_eq!(1, rewired.len());
+    assert!(!reactive_flow.has_relation_by_key(incoming.get_key().unwrap()));
+    assert_eq!(new_entity.id, rewired[0].inbound.id);
+
+    assert_eq!(
+        vec![outgoing.get_key().unwrap(), incoming.get_key().unwrap()],
+        *reactive_flow.relations_removed.read().unwrap()
+    );
+    assert_eq!(2, reactive_flow.relations_added.read().unwrap().len());
+}
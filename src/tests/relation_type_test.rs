@@ -1,10 +1,21 @@
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
 use serde_json::json;
+use uuid::Uuid;
 
-use crate::tests::utils::r_string;
+use crate::tests::utils::{create_random_entity_instance, create_random_entity_instance_with_type, r_string};
+use crate::Cardinality;
+use crate::CardinalityError;
+use crate::CardinalityParseError;
+use crate::ComponentOrEntityTypeName;
 use crate::DataType;
 use crate::Extension;
 use crate::PropertyType;
 use crate::RelationType;
+use crate::RelationTypeResolveError;
+use crate::{ComponentName, EdgeKey, EntityTypeName, Identifier, RelationTypeName};
 
 #[test]
 fn create_relation_type_test() {
@@ -21,7 +32,7 @@ fn create_relation_type_test() {
     let extension_name = r_string();
     let extension_value = json!("JSON");
     let mut component_names = Vec::new();
-    component_names.push(component_name.clone());
+    component_names.push(ComponentName::new(component_name.clone()));
     let mut behaviour_names = Vec::new();
     behaviour_names.push(behaviour_name.clone());
     let mut property_types = Vec::new();
@@ -31,11 +42,12 @@ fn create_relation_type_test() {
     let extension = Extension {
         name: extension_name.clone(),
         extension: extension_value.clone(),
+        version: None,
     };
     extensions.push(extension.clone());
     let relation_type = RelationType::new(
         outbound_type.clone(),
-        type_name.clone(),
+        RelationTypeName::new(type_name.clone()),
         inbound_type.clone(),
         group.into(),
         description.into(),
@@ -45,12 +57,12 @@ fn create_relation_type_test() {
     );
 
     assert_eq!(type_name, relation_type.type_name);
-    assert_eq!(outbound_type, relation_type.outbound_type);
-    assert_eq!(inbound_type, relation_type.inbound_type);
+    assert_eq!(ComponentOrEntityTypeName::EntityType(EntityTypeName::new(outbound_type)), relation_type.outbound_type);
+    assert_eq!(ComponentOrEntityTypeName::EntityType(EntityTypeName::new(inbound_type)), relation_type.inbound_type);
     assert_eq!(group, relation_type.group);
     assert_eq!(description, relation_type.description);
     assert_eq!(component_name, *relation_type.components.first().unwrap());
-    assert!(relation_type.is_a(component_name.clone()));
+    assert!(relation_type.is_a(ComponentName::new(component_name.clone())));
     assert_eq!(property_name, *relation_type.properties.first().unwrap().name);
     assert!(relation_type.has_own_property(property_name.clone()));
     assert_eq!(extension_name.clone(), relation_type.extensions.get(0).unwrap().name);
@@ -58,3 +70,310 @@ fn create_relation_type_test() {
     assert!(relation_type.has_own_extension(extension_name));
     assert!(!relation_type.has_own_extension(r_string()));
 }
+
+#[test]
+fn relation_type_eq_test() {
+    let type_name = r_string();
+    let relation_type_1 = RelationType::new(
+        r_string(),
+        RelationTypeName::new(type_name.clone()),
+        r_string(),
+        r_string(),
+        r_string(),
+        Vec::new(),
+        Vec::new(),
+        Vec::new(),
+    );
+    let relation_type_2 = RelationType::new(
+        r_string(),
+        RelationTypeName::new(type_name.clone()),
+        r_string(),
+        r_string(),
+        r_string(),
+        Vec::new(),
+        Vec::new(),
+        Vec::new(),
+    );
+    let relation_type_3 = RelationType::new(
+        r_string(),
+        RelationTypeName::new(r_string()),
+        r_string(),
+        r_string(),
+        r_string(),
+        Vec::new(),
+        Vec::new(),
+        Vec::new(),
+    );
+    assert_eq!(relation_type_1, relation_type_2);
+    assert_ne!(relation_type_1, relation_type_3);
+    let relation_types: HashSet<RelationType> = vec![relation_type_1, relation_type_2, relation_type_3].into_iter().collect();
+    assert_eq!(2, relation_types.len());
+}
+
+#[test]
+fn relation_type_display_test() {
+    let relation_type = RelationType::new("player", RelationTypeName::new("looks_at"), "camera", "", "", Vec::new(), Vec::new(), Vec::new());
+    assert_eq!("player--(looks_at)-->camera", relation_type.to_string());
+}
+
+#[test]
+fn cardinality_parse_test() {
+    assert_eq!(Ok(Cardinality::One), "one".parse());
+    assert_eq!(Ok(Cardinality::Optional), Cardinality::try_from("Optional"));
+    assert_eq!(Ok(Cardinality::Many), Cardinality::from_str(&Cardinality::Many.to_string()));
+    assert_eq!(Err(CardinalityParseError::Unknown("several".to_string())), Cardinality::from_str("several"));
+}
+
+#[test]
+fn relation_type_cardinality_test() {
+    let relation_type = RelationType::new("player", RelationTypeName::new("current_camera"), "camera", "", "", Vec::new(), Vec::new(), Vec::new());
+    assert_eq!(Cardinality::Many, relation_type.outbound_cardinality);
+    assert_eq!(Cardinality::Many, relation_type.inbound_cardinality);
+    let relation_type = relation_type.with_cardinality(Cardinality::One, Cardinality::Optional);
+
+    let t = Identifier::new("current_camera").unwrap();
+    let other_t = Identifier::new("looks_at").unwrap();
+    let player = Uuid::new_v4();
+    let camera_1 = Uuid::new_v4();
+    let camera_2 = Uuid::new_v4();
+    let other_player = Uuid::new_v4();
+
+    assert!(relation_type.can_create(&[], player, camera_1).is_ok());
+    let edge_keys = vec![EdgeKey::new(player, other_t, camera_2)];
+    assert!(relation_type.can_create(&edge_keys, player, camera_1).is_ok());
+    let edge_keys = vec![EdgeKey::new(player, t.clone(), camera_1)];
+    assert_eq!(
+        Err(CardinalityError::OutboundExceeded(player, Cardinality::One)),
+        relation_type.can_create(&edge_keys, player, camera_2)
+    );
+    assert_eq!(
+        Err(CardinalityError::InboundExceeded(camera_1, Cardinality::Optional)),
+        relation_type.can_create(&edge_keys, other_player, camera_1)
+    );
+    assert!(relation_type.can_create(&edge_keys, other_player, camera_2).is_ok());
+}
+
+#[test]
+fn relation_type_cardinality_serde_test() {
+    let relation_type: RelationType = serde_json::from_value(json!({
+        "outbound_type": "player",
+        "type_name": "current_camera",
+        "inbound_type": "camera",
+        "outbound_cardinality": "one",
+        "inbound_cardinality": "optional"
+    }))
+    .unwrap();
+    assert_eq!(Cardinality::One, relation_type.outbound_cardinality);
+    assert_eq!(Cardinality::Optional, relation_type.inbound_cardinality);
+    let relation_type: RelationType = serde_json::from_value(json!({
+        "outbound_type": "player",
+        "type_name": "looks_at",
+        "inbound_type": "camera"
+    }))
+    .unwrap();
+    assert_eq!(Cardinality::Many, relation_type.outbound_cardinality);
+}
+
+#[test]
+fn relation_type_deserialize_identifier_test() {
+    let relation_type: RelationType = serde_json::from_value(json!({
+        "outbound_type": "player",
+        "type_name": "looks_at",
+        "inbound_type": "camera"
+    }))
+    .unwrap();
+    assert_eq!("looks_at", relation_type.t.as_str());
+    let edge_key = EdgeKey::new(Uuid::new_v4(), relation_type.t.clone(), Uuid::new_v4());
+    assert_eq!("looks_at", edge_key.t.as_str());
+    assert!(serde_json::from_value::<RelationType>(json!({
+        "outbound_type": "player",
+        "type_name": "a".repeat(256),
+        "inbound_type": "camera"
+    }))
+    .is_err());
+}
+
+#[test]
+fn relation_type_component_or_entity_type_name_test() {
+    let relation_type = RelationType::new("component:labeled", RelationTypeName::new("connector"), "*", "", "", Vec::new(), Vec::new(), Vec::new());
+    assert_eq!(ComponentOrEntityTypeName::Component(ComponentName::new("labeled")), relation_type.outbound_type);
+    assert_eq!(ComponentOrEntityTypeName::Wildcard, relation_type.inbound_type);
+    assert_eq!("component:labeled--(connector)-->*", relation_type.to_string());
+
+    let outbound = create_random_entity_instance(r_string());
+    let inbound = create_random_entity_instance(r_string());
+    assert!(!relation_type.is_valid_for(&outbound, &inbound));
+    outbound.add_component(ComponentName::new("labeled"));
+    assert!(relation_type.is_valid_for(&outbound, &inbound));
+
+    let relation_type = RelationType::new(
+        outbound.type_name.as_str(),
+        RelationTypeName::new("connector"),
+        "player",
+        "",
+        "",
+        Vec::new(),
+        Vec::new(),
+        Vec::new(),
+    );
+    assert!(!relation_type.is_valid_for(&outbound, &inbound));
+    assert!(relation_type.is_valid_for(&outbound, &create_random_entity_instance_with_type("player", "name")));
+
+    let json = serde_json::to_value(&relation_type).unwrap();
+    assert_eq!(json!("player"), json["inbound_type"]);
+    let relation_type: RelationType = serde_json::from_value(json!({
+        "outbound_type": "*",
+        "type_name": "connector",
+        "inbound_type": "component:labeled"
+    }))
+    .unwrap();
+    assert_eq!(ComponentOrEntityTypeName::Wildcard, relation_type.outbound_type);
+    assert_eq!(ComponentOrEntityTypeName::Component(ComponentName::new("labeled")), relation_type.inbound_type);
+}
+
+#[test]
+fn relation_type_resolve_test() {
+    let extension = |name: &str, value: &str| Extension {
+        name: name.to_string(),
+        extension: json!(value),
+        version: None,
+    };
+    let connector = RelationType::new(
+        "*",
+        RelationTypeName::new("connector"),
+        "*",
+        "",
+        "",
+        vec![ComponentName::new("connector")],
+        vec![
+            PropertyType::new("outbound_property_name", DataType::String),
+            PropertyType::new("inbound_property_name", DataType::String),
+        ],
+        vec![extension("palette", "connector"), extension("color", "grey")],
+    );
+    let debounced_connector = RelationType::new(
+        "*",
+        RelationTypeName::new("debounced_connector"),
+        "*",
+        "",
+        "",
+        vec![ComponentName::new("debounce")],
+        vec![PropertyType::new("debounce_time", DataType::Number)],
+        vec![extension("color", "blue")],
+    )
+    .with_extends("connector");
+    let slow_debounced_connector = RelationType::new(
+        "*",
+        RelationTypeName::new("slow_debounced_connector"),
+        "*",
+        "",
+        "",
+        Vec::new(),
+        vec![PropertyType::new("debounce_time", DataType::String)],
+        Vec::new(),
+    )
+    .with_extends("debounced_connector");
+    let relation_types = vec![connector.clone(), debounced_connector.clone(), slow_debounced_connector.clone()];
+
+    let resolved = debounced_connector.resolve(&relation_types).unwrap();
+    assert_eq!("debounced_connector", resolved.type_name);
+    assert!(resolved.extends.is_empty());
+    assert_eq!(vec!["connector".to_string(), "debounce".to_string()], resolved.components);
+    assert!(resolved.has_own_property("outbound_property_name"));
+    assert!(resolved.has_own_property("inbound_property_name"));
+    assert!(resolved.has_own_property("debounce_time"));
+    assert_eq!(2, resolved.extensions.len());
+    assert_eq!(json!("blue"), resolved.extensions.iter().find(|e| e.name == "color").unwrap().extension);
+
+    let resolved = slow_debounced_connector.resolve(&relation_types).unwrap();
+    assert_eq!(3, resolved.properties.len());
+    assert_eq!(DataType::String, resolved.properties.iter().find(|p| p.name == "debounce_time").unwrap().data_type);
+
+    assert_eq!(Ok(connector.clone()), connector.resolve(&relation_types));
+    assert_eq!(
+        Err(RelationTypeResolveError::MissingBaseType("connector".to_string())),
+        debounced_connector.resolve(&[]).map(|_| ())
+    );
+    let cyclic = connector.clone().with_extends("slow_debounced_connector");
+    assert_eq!(
+        Err(RelationTypeResolveError::Cycle("connector".to_string())),
+        cyclic.resolve(&[cyclic.clone(), debounced_connector, slow_debounced_connector]).map(|_| ())
+    );
+}
+
+#[test]
+fn relation_type_resolve_conflict_test() {
+    let relation_type = |type_name: &str, properties: Vec<PropertyType>, extensions: Vec<Extension>| {
+        RelationType::new("*", RelationTypeName::new(type_name), "*", "", "", Vec::new(), properties, extensions)
+    };
+    let a = relation_type("a", vec![PropertyType::new("value", DataType::Bool)], Vec::new());
+    let b = relation_type("b", vec![PropertyType::new("value", DataType::Number)], Vec::new());
+    let c = relation_type("c", Vec::new(), Vec::new()).with_extends("a").with_extends("b");
+    assert_eq!(vec!["a".to_string(), "b".to_string()], c.extends);
+    assert_eq!(
+        Err(RelationTypeResolveError::ConflictingProperty("value".to_string())),
+        c.resolve(&[a, b, c.clone()]).map(|_| ())
+    );
+
+    let extension = |value: &str| Extension {
+        name: "color".to_string(),
+        extension: json!(value),
+        version: None,
+    };
+    let a = relation_type("a", Vec::new(), vec![extension("red")]);
+    let b = relation_type("b", Vec::new(), vec![extension("blue")]);
+    assert_eq!(
+        Err(RelationTypeResolveError::ConflictingExtension("color".to_string())),
+        c.resolve(&[a.clone(), b, c.clone()]).map(|_| ())
+    );
+    let b = relation_type("b", vec![PropertyType::new("value", DataType::Bool)], vec![extension("red")]);
+    let resolved = c.resolve(&[a, b, c.clone()]).unwrap();
+    assert_eq!(1, resolved.properties.len());
+    assert_eq!(1, resolved.extensions.len());
+
+    // The base relation types are deserialized as a list
+    let relation_type: RelationType = serde_json::from_value(json!({
+        "outbound_type": "*",
+        "type_name": "c",
+        "inbound_type": "*",
+        "extends": ["a", "b"]
+    }))
+    .unwrap();
+    assert_eq!(c.extends, relation_type.extends);
+}
+
+#[test]
+fn relation_type_abstract_test() {
+    let relation_type = RelationType::new("*", RelationTypeName::new("connector"), "*", "", "", Vec::new(), Vec::new(), Vec::new());
+    assert!(relation_type.is_instantiable());
+    let relation_type: RelationType = serde_json::from_value(json!({
+        "outbound_type": "*",
+        "type_name": "base_connector",
+        "inbound_type": "*",
+        "abstract": true
+    }))
+    .unwrap();
+    assert!(!relation_type.is_instantiable());
+}
+
+#[test]
+fn relation_type_serde_names_test() {
+    let relation_type = RelationType::new("player", RelationTypeName::new("owns"), "item", "", "", Vec::new(), Vec::new(), Vec::new());
+    let json = serde_json::to_value(&relation_type).unwrap();
+    assert_eq!(json!("owns"), json["name"]);
+    assert!(json.get("type_name").is_none());
+    let relation_type: RelationType = serde_json::from_value(json).unwrap();
+    assert_eq!("owns", relation_type.t.as_str());
+
+    let relation_type: RelationType = serde_json::from_value(json!({
+        "outbound": "player",
+        "type_name": "owns",
+        "inbound": "item"
+    }))
+    .unwrap();
+    assert_eq!("owns", relation_type.type_name);
+    assert_eq!(ComponentOrEntityTypeName::EntityType(EntityTypeName::new("player")), relation_type.outbound_type);
+    assert_eq!(ComponentOrEntityTypeName::EntityType(EntityTypeName::new("item")), relation_type.inbound_type);
+    let relation_type: RelationType = serde_json::from_value(json!({ "outbound_type": "player", "type": "owns", "inbound_type": "item" })).unwrap();
+    assert_eq!("owns", relation_type.type_name);
+}
@@ -0,0 +1,87 @@
+use serde_json::json;
+
+use crate::Component;
+use crate::ComponentName;
+use crate::DataType;
+use crate::EntityType;
+use crate::EntityTypeName;
+use crate::PropertyType;
+use crate::RelationType;
+use crate::RelationTypeName;
+use crate::SchemaExporter;
+use crate::SocketType;
+
+#[test]
+fn data_type_schema_test() {
+    assert_eq!(json!({ "type": "boolean" }), SchemaExporter::data_type_schema(DataType::Bool));
+    assert_eq!(json!({ "type": "number" }), SchemaExporter::data_type_schema(DataType::Number));
+    assert_eq!(json!({ "type": "string" }), SchemaExporter::data_type_schema(DataType::String));
+    assert_eq!(json!({ "type": "object" }), SchemaExporter::data_type_schema(DataType::Object));
+    assert_eq!(json!({}), SchemaExporter::data_type_schema(DataType::Any));
+    assert_eq!(json!({ "type": "null" }), SchemaExporter::data_type_schema(DataType::Null));
+}
+
+#[test]
+fn export_schemas_test() {
+    let component = Component::new(ComponentName::new("named"), vec![PropertyType::new("name", DataType::String)]);
+    let entity_type = EntityType::new(
+        EntityTypeName::new("sensor"),
+        "devices",
+        "A sensor",
+        vec![ComponentName::new("named")],
+        vec![
+            PropertyType::output("value", DataType::Number),
+            PropertyType::event("triggered", DataType::Bool, SocketType::Output),
+            PropertyType::new("nothing", DataType::Null),
+        ],
+        Vec::new(),
+    );
+    let relation_type = RelationType::new(
+        "sensor",
+        RelationTypeName::new("connected_to"),
+        "sensor",
+        "",
+        "",
+        Vec::new(),
+        vec![PropertyType::new("weight", DataType::Number)],
+        Vec::new(),
+    );
+
+    let schemas = SchemaExporter::export(&[entity_type], &[relation_type], &[component]);
+    let sensor = &schemas["schemas"]["sensor"];
+    assert_eq!("object", sensor["type"]);
+    assert_eq!("A sensor", sensor["description"]);
+    assert_eq!("devices", sensor["x-group"]);
+    assert_eq!("number", sensor["properties"]["value"]["type"]);
+    assert_eq!("output", sensor["properties"]["value"]["x-socket-type"]);
+    // Component properties are included
+    assert_eq!("string", sensor["properties"]["name"]["type"]);
+    // Event properties and null properties are not mandatory
+    assert_eq!(json!(["name", "value"]), sensor["required"]);
+    assert_eq!("null", sensor["properties"]["nothing"]["type"]);
+
+    let connected_to = &schemas["schemas"]["connected_to"];
+    assert_eq!("sensor", connected_to["x-outbound-type"]);
+    assert_eq!("sensor", connected_to["x-inbound-type"]);
+    assert_eq!("number", connected_to["properties"]["weight"]["type"]);
+}
+
+#[test]
+fn export_schemas_optional_properties_test() {
+    let entity_type = EntityType::new(
+        EntityTypeName::new("trigger"),
+        "",
+        "",
+        Vec::new(),
+        vec![
+            PropertyType::event("triggered", DataType::Bool, SocketType::Output),
+            PropertyType::new("nothing", DataType::Null),
+        ],
+        Vec::new(),
+    );
+    let schemas = SchemaExporter::export(&[entity_type], &[], &[]);
+    let trigger = &schemas["schemas"]["trigger"];
+    assert_eq!("boolean", trigger["properties"]["triggered"]["type"]);
+    // The list of required properties is omitted, if no property is mandatory
+    assert!(trigger.get("required").is_none());
+}
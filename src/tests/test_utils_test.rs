@@ -0,0 +1,48 @@
+use crate::test_utils::{
+    connected_reactive_relation, connected_relation, default_entity_type, default_flow, default_relation_type, entity_instance_of, reactive_entity_instance_of,
+    DEFAULT_ENTITY_TYPE_NAME, DEFAULT_RELATION_TYPE_NAME,
+};
+use crate::{validate_properties, PropertyInstanceGetter};
+
+#[test]
+fn entity_instance_of_test() {
+    let entity_type = default_entity_type();
+    let entity_instance = entity_instance_of(&entity_type);
+    assert_eq!(DEFAULT_ENTITY_TYPE_NAME, entity_instance.type_name);
+    assert!(validate_properties(&entity_instance.properties, &entity_type.properties).is_valid());
+    assert_ne!(entity_instance.id, entity_instance_of(&entity_type).id);
+}
+
+#[test]
+fn connected_relation_test() {
+    let entity_type = default_entity_type();
+    let relation_type = default_relation_type();
+    let outbound = entity_instance_of(&entity_type);
+    let inbound = entity_instance_of(&entity_type);
+    let relation_instance = connected_relation(&outbound, &inbound);
+    assert_eq!(outbound.id, relation_instance.outbound_id);
+    assert_eq!(DEFAULT_RELATION_TYPE_NAME, relation_instance.type_name);
+    assert_eq!(inbound.id, relation_instance.inbound_id);
+    assert!(validate_properties(&relation_instance.properties, &relation_type.properties).is_valid());
+    assert!(relation_type.outbound_type.to_string().contains(DEFAULT_ENTITY_TYPE_NAME));
+}
+
+#[test]
+fn connected_reactive_relation_test() {
+    let entity_type = default_entity_type();
+    let outbound = reactive_entity_instance_of(&entity_type);
+    let inbound = reactive_entity_instance_of(&entity_type);
+    let relation_instance = connected_reactive_relation(outbound.clone(), inbound.clone());
+    assert_eq!(outbound.id, relation_instance.outbound.id);
+    assert_eq!(inbound.id, relation_instance.inbound.id);
+    assert!(relation_instance.get("weight").is_some());
+    assert!(outbound.get("input").is_some());
+}
+
+#[test]
+fn default_flow_test() {
+    let flow = default_flow();
+    assert!(flow.validate().is_valid());
+    assert_eq!(3, flow.entity_instances.len());
+    assert_eq!(1, flow.relation_instances.len());
+}
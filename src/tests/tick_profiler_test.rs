@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use serde_json::json;
+use serde_json::Value;
+
+use crate::tests::utils::{create_random_entity_instance, create_random_entity_instance_with_type};
+use crate::{profile, ProfileReport, PropertyInstanceSetter, ReactiveFlow};
+
+#[test]
+fn tick_profiler_test() {
+    let wrapper = Arc::new(create_random_entity_instance_with_type("generic_flow", "test"));
+    let reactive_flow = ReactiveFlow::new(wrapper.clone());
+    let source = Arc::new(create_random_entity_instance("value"));
+    let target = Arc::new(create_random_entity_instance("value"));
+    reactive_flow.add_entity(source.clone());
+    let t = target.clone();
+    source.properties.get("value").unwrap().stream.read().unwrap().observe(move |value: &Value| {
+        t.set("value", value.clone());
+    });
+
+    let report = reactive_flow.tick_profiled();
+    assert_eq!(1, report.get_property(source.id, "value").unwrap().calls);
+    assert_eq!(1, report.get_property(target.id, "value").unwrap().calls);
+    assert_eq!(1, report.get_instance(target.id).unwrap().calls);
+
+    let nested = format!("{}.value;{}.value", source.id, target.id);
+    assert!(report.stacks.iter().any(|(stack, _)| *stack == nested));
+    assert!(report.to_folded().lines().any(|line| line.starts_with(&format!("{} ", nested))));
+
+    let deserialized: ProfileReport = serde_json::from_value(serde_json::to_value(&report).unwrap()).unwrap();
+    assert_eq!(report, deserialized);
+
+    // Profiling is opt-in
+    source.set("value", json!(1));
+    assert!(profile(|| {}).get_property(source.id, "value").is_none());
+}
+
+#[test]
+fn tick_profiler_scope_test() {
+    let entity_instance = Arc::new(create_random_entity_instance("value"));
+
+    // Propagations of other threads are not recorded
+    let e = entity_instance.clone();
+    let report = profile(|| std::thread::spawn(move || e.set("value", json!(1))).join().unwrap());
+    assert!(report.get_property(entity_instance.id, "value").is_none());
+
+    // A panic ends the profile
+    let e = entity_instance.clone();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        profile(|| {
+            e.set("value", json!(2));
+            panic!("tick failed");
+        })
+    }));
+    assert!(result.is_err());
+    entity_instance.set("value", json!(3));
+    let report = profile(|| entity_instance.set("value", json!(4)));
+    assert_eq!(1, report.get_property(entity_instance.id, "value").unwrap().calls);
+
+    // Nested profiles record their own propagations
+    let e = entity_instance.clone();
+    let report = profile(|| {
+        entity_instance.set("value", json!(5));
+        let nested = profile(|| e.set("value", json!(6)));
+        assert_eq!(1, nested.get_property(e.id, "value").unwrap().calls);
+        entity_instance.set("value", json!(7));
+    });
+    assert_eq!(2, report.get_property(entity_instance.id, "value").unwrap().calls);
+}
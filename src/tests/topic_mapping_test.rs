@@ -0,0 +1,66 @@
+use uuid::Uuid;
+
+use crate::tests::utils::create_random_entity_instance;
+use crate::tests::utils::r_string;
+use crate::PropertyAddress;
+use crate::TopicMapping;
+
+#[test]
+fn topic_mapping_resolve_test() {
+    let mut topic_mapping = TopicMapping::new();
+    let id = Uuid::new_v4();
+    topic_mapping.bind("player/1", id, vec!["position".to_string(), "health".to_string()]);
+
+    let address = topic_mapping.resolve("player/1/position").unwrap();
+    assert_eq!(id, address.id);
+    assert_eq!("position", address.property_name);
+    assert_eq!("player/1/health", topic_mapping.get_topic(&PropertyAddress::new(id, "health")).unwrap());
+    assert!(topic_mapping.resolve("player/2/position").is_none());
+    assert!(topic_mapping.resolve("player").is_none());
+    assert!(topic_mapping.get_topic(&PropertyAddress::new(Uuid::new_v4(), "health")).is_none());
+
+    topic_mapping.unbind(id);
+    assert!(topic_mapping.resolve("player/1/position").is_none());
+}
+
+#[test]
+fn topic_mapping_rebind_test() {
+    let mut topic_mapping = TopicMapping::new();
+    let id_1 = Uuid::new_v4();
+    let id_2 = Uuid::new_v4();
+    topic_mapping.bind("player/1", id_1, vec!["position".to_string()]);
+    topic_mapping.bind("player/1", id_2, vec!["health".to_string()]);
+
+    // The previous owner of the topic path is unbound
+    assert_eq!(id_2, topic_mapping.resolve("player/1/health").unwrap().id);
+    assert!(topic_mapping.get_topic(&PropertyAddress::new(id_1, "position")).is_none());
+    assert_eq!(vec!["player/1/health".to_string()], topic_mapping.get_topics());
+}
+
+#[test]
+fn topic_mapping_matches_test() {
+    assert!(TopicMapping::matches("player/1/position", "player/1/position"));
+    assert!(TopicMapping::matches("player/+/position", "player/1/position"));
+    assert!(TopicMapping::matches("player/#", "player/1/position"));
+    assert!(TopicMapping::matches("#", "player/1/position"));
+    assert!(!TopicMapping::matches("player/+", "player/1/position"));
+    assert!(!TopicMapping::matches("player/2/position", "player/1/position"));
+    assert!(!TopicMapping::matches("player/#/position", "player/1/position"));
+}
+
+#[test]
+fn topic_mapping_expand_test() {
+    let mut topic_mapping = TopicMapping::new();
+    let property_name = r_string();
+    let entity_instance_1 = create_random_entity_instance(property_name.clone());
+    let entity_instance_2 = create_random_entity_instance(property_name.clone());
+    topic_mapping.bind_entity("player/1", &entity_instance_1);
+    topic_mapping.bind_entity("player/2", &entity_instance_2);
+
+    assert_eq!(2, topic_mapping.expand(format!("player/+/{}", property_name)).len());
+    assert_eq!(2, topic_mapping.expand("player/#").len());
+    let addresses = topic_mapping.expand("player/2/+");
+    assert_eq!(1, addresses.len());
+    assert_eq!(entity_instance_2.id, addresses.first().unwrap().id);
+    assert!(topic_mapping.expand("enemy/#").is_empty());
+}
@@ -0,0 +1,64 @@
+use std::sync::{Arc, Mutex};
+
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::tests::utils::create_random_entity_instance;
+use crate::{clear_type_mismatch_handler, set_type_mismatch_handler, DataType, PropertyInstanceGetter, PropertyInstanceSetter, ReactivePropertyInstance};
+use crate::{ReactivePropertyContainer, TypeEnforcementPolicy, TypeMismatch};
+
+#[test]
+fn type_enforcement_reject_test() {
+    let mut property_instance = ReactivePropertyInstance::new(Uuid::new_v4(), "value", json!(1));
+    property_instance.set("a".into());
+    assert_eq!(json!("a"), property_instance.get());
+
+    property_instance.set_type_enforcement(TypeEnforcementPolicy::Reject, DataType::Number);
+    property_instance.set(json!(2));
+    property_instance.set(json!("3"));
+    assert_eq!(json!(2), property_instance.get());
+    property_instance.set_no_propagate(json!(true));
+    assert_eq!(json!(2), property_instance.get());
+}
+
+#[test]
+fn type_enforcement_coerce_test() {
+    let mut property_instance = ReactivePropertyInstance::new(Uuid::new_v4(), "value", json!(0));
+    property_instance.set_type_enforcement(TypeEnforcementPolicy::Coerce, DataType::Number);
+    property_instance.set(json!("3"));
+    assert_eq!(json!(3), property_instance.get());
+    property_instance.set(json!(true));
+    assert_eq!(json!(1), property_instance.get());
+    property_instance.set(json!("x"));
+    assert_eq!(json!(1), property_instance.get());
+}
+
+#[test]
+fn type_enforcement_report_test() {
+    let entity_instance = create_random_entity_instance("value");
+    let id = entity_instance.id;
+    entity_instance
+        .properties
+        .get_mut("value")
+        .unwrap()
+        .set_type_enforcement(TypeEnforcementPolicy::Disabled, DataType::Bool);
+    entity_instance.set_type_enforcement_policy("value", TypeEnforcementPolicy::Report);
+
+    let mismatches: Arc<Mutex<Vec<TypeMismatch>>> = Arc::new(Mutex::new(Vec::new()));
+    let recorded = mismatches.clone();
+    set_type_mismatch_handler(Arc::new(move |mismatch: &TypeMismatch| {
+        if mismatch.id == id {
+            recorded.lock().unwrap().push(mismatch.clone());
+        }
+    }));
+    entity_instance.set("value", json!(true));
+    entity_instance.set("value", json!("no"));
+    clear_type_mismatch_handler();
+
+    assert_eq!(json!("no"), entity_instance.get("value").unwrap());
+    let mismatches = mismatches.lock().unwrap();
+    assert_eq!(1, mismatches.len());
+    assert_eq!("value", mismatches[0].property_name);
+    assert_eq!(DataType::Bool, mismatches[0].data_type);
+    assert!(mismatches[0].accepted);
+}
@@ -0,0 +1,113 @@
+use std::str::FromStr;
+
+use serde_json::json;
+
+use crate::tests::utils::{r_string_1000, r_string_255};
+use crate::{
+    is_valid_type_name, sanitize_type_name, validate_identifier, BehaviourName, ComponentName, EntityInstance, EntityType, EntityTypeName,
+    ReactiveEntityInstance, RelationType, RelationTypeName, ValidationError,
+};
+
+#[test]
+fn type_name_test() {
+    let type_name = EntityTypeName::new("player");
+    assert_eq!("player", type_name.as_str());
+    assert_eq!("player", format!("{}", type_name));
+    assert_eq!(type_name, "player");
+    assert_eq!(type_name, "player".to_string());
+    assert_eq!(type_name, "player".parse::<EntityTypeName>().unwrap());
+    assert_eq!("player".to_string(), String::from(type_name.clone()));
+    assert_eq!("player".to_string(), type_name.into_inner());
+}
+
+#[test]
+fn type_name_from_str_test() {
+    assert_eq!(Ok(ComponentName::new("named")), ComponentName::from_str("named"));
+    assert_eq!(Err(ValidationError::InvalidIdentifier { identifier: String::new() }), "".parse::<ComponentName>());
+}
+
+#[test]
+fn type_name_serde_test() {
+    let behaviour_name = BehaviourName::new("blink");
+    let value = serde_json::to_value(&behaviour_name).unwrap();
+    assert_eq!(json!("blink"), value);
+    assert_eq!(behaviour_name, serde_json::from_value::<BehaviourName>(value).unwrap());
+    let relation_type_name: RelationTypeName = serde_json::from_str(r#""connects""#).unwrap();
+    assert_eq!("connects", relation_type_name.as_str());
+}
+
+#[test]
+fn type_name_arguments_test() {
+    let component_name = ComponentName::new("named");
+    let entity_instance = EntityInstance::builder()
+        .type_name(EntityTypeName::new("player"))
+        .component(&component_name)
+        .behaviour(BehaviourName::new("blink"))
+        .build();
+    assert_eq!("player", entity_instance.type_name);
+    assert_eq!(vec!["named".to_string()], entity_instance.components);
+    let reactive_entity_instance = ReactiveEntityInstance::from(entity_instance);
+    assert!(reactive_entity_instance.is_a(component_name.clone()));
+    assert!(reactive_entity_instance.is_a(ComponentName::new("named")));
+    reactive_entity_instance.remove_component(component_name);
+    assert!(!reactive_entity_instance.is_a(ComponentName::new("named")));
+}
+
+#[test]
+fn is_valid_type_name_test() {
+    assert!(is_valid_type_name("player"));
+    assert!(is_valid_type_name("value-number_2"));
+    assert!(is_valid_type_name("größe"));
+    assert!(is_valid_type_name(&r_string_255()));
+    assert!(!is_valid_type_name(&r_string_1000()));
+    assert!(!is_valid_type_name(""));
+    assert!(!is_valid_type_name("my player"));
+    assert!(!is_valid_type_name("component:player"));
+    assert_eq!(is_valid_type_name("my player"), validate_identifier("my player").is_ok());
+}
+
+#[test]
+fn sanitize_type_name_test() {
+    assert_eq!("player", sanitize_type_name("player"));
+    assert_eq!("my_player_", sanitize_type_name("my player!"));
+    assert_eq!("_", sanitize_type_name(""));
+    let long = format!("{}ü", r_string_255().get(..254).unwrap());
+    let sanitized = sanitize_type_name(&long);
+    assert_eq!(254, sanitized.len());
+    assert!(is_valid_type_name(&sanitized));
+    assert_eq!(255, sanitize_type_name(&r_string_1000()).len());
+}
+
+#[test]
+fn type_try_new_test() {
+    assert!(EntityType::try_new(EntityTypeName::new("player"), "", "", Vec::new(), Vec::new(), Vec::new()).is_ok());
+    assert_eq!(
+        ValidationError::InvalidIdentifier {
+            identifier: "my player".to_string()
+        },
+        EntityType::try_new(EntityTypeName::new("my player"), "", "", Vec::new(), Vec::new(), Vec::new()).unwrap_err()
+    );
+    assert!(RelationType::try_new("*", RelationTypeName::new("connector"), "component:value", "", "", Vec::new(), Vec::new(), Vec::new()).is_ok());
+    assert!(RelationType::try_new("*", RelationTypeName::new(""), "*", "", "", Vec::new(), Vec::new(), Vec::new()).is_err());
+}
+
+#[test]
+fn type_name_fields_test() {
+    let entity_instance = EntityInstance::builder()
+        .type_name(EntityTypeName::new("player"))
+        .component(ComponentName::new("named"))
+        .behaviour(BehaviourName::new("blink"))
+        .build();
+    let type_name: &EntityTypeName = &entity_instance.type_name;
+    let components: &Vec<ComponentName> = &entity_instance.components;
+    assert_eq!("player", *type_name);
+    assert_eq!(vec![ComponentName::new("named")], *components);
+    // The names are serialized as plain strings
+    let value = serde_json::to_value(&entity_instance).unwrap();
+    assert_eq!(json!("player"), value["type_name"]);
+    assert_eq!(json!(["named"]), value["components"]);
+    assert_eq!(json!(["blink"]), value["behaviours"]);
+    let deserialized: EntityInstance = serde_json::from_value(value).unwrap();
+    assert_eq!(entity_instance.type_name, deserialized.type_name);
+    assert_eq!(entity_instance.behaviours, deserialized.behaviours);
+}
@@ -0,0 +1,177 @@
+use uuid::Uuid;
+
+use crate::{
+    BehaviourName, BehaviourType, Component, ComponentName, DataType, EntityInstance, EntityType, EntityTypeName, Flow, PropertyType, RelationType,
+    RelationTypeName, TypeSystem, TypeSystemError,
+};
+
+fn create_type_system() -> TypeSystem {
+    let mut type_system = TypeSystem::new();
+    type_system.add_component(Component::new(
+        ComponentName::new("labeled"),
+        vec![PropertyType::new("label", DataType::String), PropertyType::new("color", DataType::String)],
+    ));
+    type_system.add_entity_type(EntityType::new(
+        EntityTypeName::new("sprite"),
+        "",
+        "",
+        vec![ComponentName::new("labeled")],
+        vec![PropertyType::new("color", DataType::Number)],
+        Vec::new(),
+    ));
+    type_system.add_entity_type(
+        EntityType::new(
+            EntityTypeName::new("animated_sprite"),
+            "",
+            "",
+            Vec::new(),
+            vec![PropertyType::new("frame", DataType::Number)],
+            Vec::new(),
+        )
+        .with_extends("sprite"),
+    );
+    type_system.add_relation_type(RelationType::new(
+        "sprite",
+        RelationTypeName::new("parent_of"),
+        "sprite",
+        "",
+        "",
+        vec![ComponentName::new("labeled")],
+        Vec::new(),
+        Vec::new(),
+    ));
+    type_system.add_flow_type(Flow::from_instance_with_name(
+        EntityInstance::new_without_properties(EntityTypeName::new("generic_flow"), Uuid::new_v4()),
+        "scene",
+    ));
+    type_system
+}
+
+#[test]
+fn type_system_lookup_test() {
+    let mut type_system = create_type_system();
+    assert!(type_system.has_component("labeled"));
+    assert!(type_system.has_entity_type("sprite"));
+    assert!(type_system.has_relation_type("parent_of"));
+    assert!(type_system.has_flow_type("scene"));
+    assert!(!type_system.has_entity_type("parent_of"));
+    assert_eq!(
+        vec!["sprite"],
+        type_system
+            .get_entity_types_by_component(ComponentName::new("labeled"))
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect::<Vec<&str>>()
+    );
+    assert_eq!(1, type_system.get_relation_types_by_component("labeled").len());
+
+    type_system.add_entity_type(EntityType::new(EntityTypeName::new("sprite"), "replaced", "", Vec::new(), Vec::new(), Vec::new()));
+    assert_eq!(2, type_system.entity_types.len());
+    assert_eq!("replaced", type_system.get_entity_type("sprite").unwrap().group);
+    type_system.remove_flow_type("scene");
+    assert!(!type_system.has_flow_type("scene"));
+}
+
+#[test]
+fn type_system_resolve_test() {
+    let type_system = create_type_system();
+    let resolved = type_system.resolve_entity_type("animated_sprite").unwrap();
+    let names: Vec<&str> = resolved.properties.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(vec!["label", "color", "frame"], names);
+    assert_eq!(DataType::Number, resolved.properties[1].data_type);
+
+    let resolved = type_system.resolve_relation_type("parent_of").unwrap();
+    assert_eq!(2, resolved.properties.len());
+
+    assert_eq!(
+        Err(TypeSystemError::MissingEntityType("unknown".to_string())),
+        type_system.resolve_entity_type("unknown").map(|_| ())
+    );
+    let mut type_system = type_system;
+    type_system.remove_component("labeled");
+    assert_eq!(
+        Err(TypeSystemError::MissingComponent("labeled".to_string())),
+        type_system.resolve_entity_type("sprite").map(|_| ())
+    );
+}
+
+#[test]
+fn type_system_compose_test() {
+    let type_system = create_type_system();
+    let composed = type_system.compose_relation_type("parent_of").unwrap();
+    assert_eq!("parent_of", composed.relation_type.type_name.as_str());
+    assert!(composed.has_property("label"));
+    assert!(composed.has_property("color"));
+
+    // The own property of the entity type is defined differently than the property of the component
+    assert_eq!(
+        Err(TypeSystemError::ConflictingProperty("color".to_string())),
+        type_system.compose_entity_type("animated_sprite").map(|_| ())
+    );
+    // Resolving the entity type overrides the property of the component
+    let resolved = type_system.resolve_entity_type("animated_sprite").unwrap();
+    assert_eq!(DataType::Number, resolved.properties[1].data_type);
+}
+
+#[test]
+fn type_system_serde_test() {
+    let type_system = create_type_system();
+    let type_system: TypeSystem = serde_json::from_value(serde_json::to_value(&type_system).unwrap()).unwrap();
+    assert_eq!(1, type_system.components.len());
+    assert_eq!(2, type_system.entity_types.len());
+    assert!(type_system.has_relation_type("parent_of"));
+    assert!(type_system.has_flow_type("scene"));
+    let type_system: TypeSystem = serde_json::from_str("{}").unwrap();
+    assert!(type_system.entity_types.is_empty());
+}
+
+#[test]
+fn type_system_behaviour_types_test() {
+    let mut type_system = create_type_system();
+    type_system.add_behaviour_type(
+        BehaviourType::new(BehaviourName::new("blink"), Vec::new(), vec!["color".to_string()]).with_component(ComponentName::new("labeled")),
+    );
+    type_system.add_behaviour_type(
+        BehaviourType::new(BehaviourName::new("animate"), Vec::new(), vec!["frame".to_string()]).with_entity_type(EntityTypeName::new("animated_sprite")),
+    );
+    assert!(type_system.has_behaviour_type("blink"));
+    assert_eq!(
+        vec!["blink"],
+        type_system
+            .get_behaviour_types_by_entity_type("sprite")
+            .iter()
+            .map(|b| b.name.as_str())
+            .collect::<Vec<&str>>()
+    );
+    assert_eq!(
+        vec!["animate"],
+        type_system
+            .get_behaviour_types_by_entity_type("animated_sprite")
+            .iter()
+            .map(|b| b.name.as_str())
+            .collect::<Vec<&str>>()
+    );
+    assert_eq!(1, type_system.get_behaviour_types_by_relation_type("parent_of").len());
+    type_system.remove_behaviour_type("blink");
+    assert!(type_system.get_behaviour_type("blink").is_none());
+}
+
+#[test]
+fn type_system_query_test() {
+    let type_system = create_type_system();
+    let names = |relation_types: Vec<&RelationType>| relation_types.iter().map(|t| t.type_name.to_string()).collect::<Vec<String>>();
+    assert_eq!(vec!["parent_of"], names(type_system.get_relation_types_between("sprite", "sprite")));
+    assert!(type_system.get_relation_types_between("animated_sprite", "sprite").is_empty());
+    assert!(type_system.get_relation_types_between("sprite", "camera").is_empty());
+    assert_eq!(vec!["parent_of"], names(type_system.get_relation_types_by_property("label")));
+    assert!(type_system.get_relation_types_by_data_type(DataType::Number).is_empty());
+
+    let names = |entity_types: Vec<&EntityType>| entity_types.iter().map(|t| t.name.to_string()).collect::<Vec<String>>();
+    assert_eq!(vec!["sprite"], names(type_system.get_entity_types_by_property("label")));
+    assert_eq!(vec!["animated_sprite"], names(type_system.get_entity_types_by_property("frame")));
+    assert_eq!(vec!["sprite", "animated_sprite"], names(type_system.get_entity_types_by_data_type(DataType::Number)));
+    assert_eq!(vec!["sprite"], names(type_system.get_entity_types_by_data_type(DataType::String)));
+
+    assert_eq!(1, type_system.get_components_by_data_type(DataType::String).len());
+    assert!(type_system.get_components_by_data_type(DataType::Bool).is_empty());
+}
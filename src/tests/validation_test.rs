@@ -0,0 +1,221 @@
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    validate_identifier, validate_properties, Component, ComponentName, DataType, EntityInstance, EntityType, EntityTypeName, Flow, PropertyMap, PropertyType,
+    RelationInstance, RelationType, RelationTypeName, SocketType, TypeSystem, ValidationError, ValidationReport,
+};
+
+#[test]
+fn validate_properties_test() {
+    let property_types = vec![PropertyType::new("name", DataType::String), PropertyType::new("age", DataType::Number)];
+    let mut properties = PropertyMap::new();
+    properties.insert("name".to_string(), json!(1));
+    let report = validate_properties(&properties, &property_types);
+    assert!(!report.is_valid());
+    assert_eq!(
+        vec![
+            ValidationError::InvalidDataType {
+                property_name: "name".to_string(),
+                expected: DataType::String
+            },
+            ValidationError::MissingProperty {
+                property_name: "age".to_string()
+            },
+        ],
+        report.errors
+    );
+    // The report can be returned as error
+    let error: Box<dyn std::error::Error> = report.into_result().unwrap_err().into();
+    assert!(error.to_string().ends_with("\nThe property age doesn't exist"));
+    properties.insert("name".to_string(), json!("Jane"));
+    properties.insert("age".to_string(), json!(42));
+    assert!(validate_properties(&properties, &property_types).into_result().is_ok());
+}
+
+#[test]
+fn validate_identifier_test() {
+    assert!(validate_identifier("generic_flow").is_ok());
+    assert!(validate_identifier("a".repeat(256)).is_err());
+    assert_eq!(Err(ValidationError::InvalidIdentifier { identifier: String::new() }), validate_identifier(""));
+}
+
+#[test]
+fn validate_connect_error_test() {
+    let outbound = PropertyType::new_with_socket("value", DataType::Number, SocketType::Input);
+    let inbound = PropertyType::new_with_socket("value", DataType::Number, SocketType::Input);
+    let error: ValidationError = outbound.is_connectable_to(&inbound).unwrap_err().into();
+    assert_eq!(
+        ValidationError::InvalidSocketType {
+            property_name: "value".to_string(),
+            expected: SocketType::Output,
+            actual: SocketType::Input
+        },
+        error
+    );
+}
+
+#[test]
+fn validate_flow_test() {
+    let wrapper = EntityInstance::new_without_properties(EntityTypeName::new("generic_flow"), Uuid::new_v4());
+    let mut flow = Flow::from(wrapper.clone());
+    assert!(flow.validate().is_valid());
+    let missing = Uuid::new_v4();
+    flow.relation_instances
+        .push(RelationInstance::new_without_properties(wrapper.id, RelationTypeName::new("connector"), missing));
+    flow.relation_instances
+        .push(RelationInstance::new_without_properties(missing, RelationTypeName::new(""), wrapper.id));
+    let report = flow.validate();
+    assert_eq!(
+        vec![
+            ValidationError::DanglingReference { id: missing },
+            ValidationError::InvalidIdentifier { identifier: String::new() },
+        ],
+        report.errors
+    );
+}
+
+fn type_system() -> TypeSystem {
+    let mut type_system = TypeSystem::new();
+    type_system.add_component(Component::new(ComponentName::new("value"), vec![PropertyType::new("value", DataType::Number)]));
+    type_system.add_entity_type(EntityType::new(EntityTypeName::new("generic_flow"), "", "", Vec::new(), Vec::new(), Vec::new()));
+    type_system.add_entity_type(EntityType::new(
+        EntityTypeName::new("number"),
+        "",
+        "",
+        vec![ComponentName::new("value")],
+        Vec::new(),
+        Vec::new(),
+    ));
+    type_system.add_entity_type(EntityType::new(
+        EntityTypeName::new("text"),
+        "",
+        "",
+        Vec::new(),
+        vec![PropertyType::new("text", DataType::String)],
+        Vec::new(),
+    ));
+    type_system.add_relation_type(RelationType::new(
+        "component:value",
+        RelationTypeName::new("default_connector"),
+        "component:value",
+        "",
+        "",
+        Vec::new(),
+        vec![PropertyType::new("weight", DataType::Number)],
+        Vec::new(),
+    ));
+    type_system
+}
+
+#[test]
+fn validate_flow_types_test() {
+    let type_system = type_system();
+    let wrapper = EntityInstance::new_without_properties(EntityTypeName::new("generic_flow"), Uuid::new_v4());
+    let mut flow = Flow::from(wrapper.clone());
+    let mut properties = PropertyMap::new();
+    properties.insert("value".to_string(), json!(1));
+    let number_1 = EntityInstance::new(EntityTypeName::new("number"), Uuid::new_v4(), properties.clone());
+    let number_2 = EntityInstance::new(EntityTypeName::new("number"), Uuid::new_v4(), properties);
+    let mut properties = PropertyMap::new();
+    properties.insert("weight".to_string(), json!(0.5));
+    flow.relation_instances.push(RelationInstance::new(
+        number_1.id,
+        RelationTypeName::new("default_connector__value__value"),
+        number_2.id,
+        properties,
+    ));
+    flow.entity_instances.push(number_1.clone());
+    flow.entity_instances.push(number_2);
+    assert!(flow.validate_types(&type_system).is_valid());
+
+    let unknown = EntityInstance::new_without_properties(EntityTypeName::new("unknown"), Uuid::new_v4());
+    let mut text = EntityInstance::new_without_properties(EntityTypeName::new("text"), Uuid::new_v4());
+    text.properties.insert("text".to_string(), json!(1));
+    flow.relation_instances
+        .push(RelationInstance::new_without_properties(number_1.id, RelationTypeName::new("default_connector"), text.id));
+    flow.relation_instances
+        .push(RelationInstance::new_without_properties(number_1.id, RelationTypeName::new("unknown"), unknown.id));
+    flow.entity_instances.push(unknown.clone());
+    flow.entity_instances.push(text.clone());
+    let report = flow.validate_types(&type_system);
+    assert_eq!(
+        vec![
+            ValidationError::InvalidEntityInstance {
+                id: unknown.id,
+                errors: vec![ValidationError::UnknownEntityType {
+                    type_name: "unknown".to_string()
+                }],
+            },
+            ValidationError::InvalidEntityInstance {
+                id: text.id,
+                errors: vec![ValidationError::InvalidDataType {
+                    property_name: "text".to_string(),
+                    expected: DataType::String
+                }],
+            },
+            ValidationError::InvalidRelationInstance {
+                outbound_id: number_1.id,
+                type_name: "default_connector".to_string(),
+                inbound_id: text.id,
+                errors: vec![
+                    ValidationError::MissingProperty {
+                        property_name: "weight".to_string()
+                    },
+                    ValidationError::InvalidType {
+                        expected: "component:value".to_string(),
+                        actual: "text".to_string()
+                    },
+                ],
+            },
+            ValidationError::InvalidRelationInstance {
+                outbound_id: number_1.id,
+                type_name: "unknown".to_string(),
+                inbound_id: unknown.id,
+                errors: vec![ValidationError::UnknownRelationType {
+                    type_name: "unknown".to_string()
+                }],
+            },
+        ],
+        report.errors
+    );
+    assert!(report.errors[0].to_string().contains("The entity type unknown doesn't exist"));
+    assert!(report
+        .errors
+        .last()
+        .unwrap()
+        .to_string()
+        .starts_with(&format!("The relation instance {}--unknown--{} is invalid", number_1.id, unknown.id)));
+}
+
+#[test]
+fn validate_flow_types_invalid_definition_test() {
+    let mut type_system = type_system();
+    type_system.remove_component("value");
+    let number = EntityInstance::new_without_properties(EntityTypeName::new("number"), Uuid::new_v4());
+    let flow = Flow::from(number.clone());
+    let report = flow.validate_types(&type_system);
+    assert_eq!(
+        vec![ValidationError::InvalidEntityInstance {
+            id: number.id,
+            errors: vec![ValidationError::InvalidTypeDefinition {
+                type_name: "number".to_string(),
+                reason: "The component value doesn't exist".to_string()
+            }],
+        }],
+        report.errors
+    );
+}
+
+#[test]
+fn validation_report_serde_test() {
+    let mut report = ValidationReport::new();
+    report.add(ValidationError::MissingProperty {
+        property_name: "name".to_string(),
+    });
+    let value = serde_json::to_value(&report).unwrap();
+    assert_eq!(json!({ "errors": [{ "kind": "missing_property", "property_name": "name" }] }), value);
+    let deserialized: ValidationReport = serde_json::from_value(value).unwrap();
+    assert_eq!(report, deserialized);
+    assert_eq!("The property name doesn't exist", report.to_string());
+}
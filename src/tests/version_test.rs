@@ -0,0 +1,81 @@
+use std::str::FromStr;
+
+use serde_json::json;
+
+use crate::{Extension, Version, VersionReq};
+
+fn matches(req: &str, version: &str) -> bool {
+    VersionReq::from_str(req).unwrap().matches(&Version::from_str(version).unwrap())
+}
+
+#[test]
+fn version_test() {
+    let version = Version::from_str("1.2.3").unwrap();
+    assert_eq!(Version::new(1, 2, 3), version);
+    assert_eq!("1.2.3", version.to_string());
+    assert_eq!("2.0.0-beta.1", Version::from_str("2.0.0-beta.1").unwrap().to_string());
+    assert!(Version::from_str("1.2").is_err());
+    assert!(Version::from_str("1.x.3").is_err());
+    assert!(Version::from_str("1.2.3-").is_err());
+    assert!(Version::from_str("1.0.0-alpha.01").is_err());
+
+    // Versions which are equal are ordered equally
+    let a = Version::from_str("1.0.0-alpha.1+build.1").unwrap();
+    let b = Version::from_str("1.0.0-alpha.1+build.2").unwrap();
+    assert_ne!(a, b);
+    assert_ne!(std::cmp::Ordering::Equal, a.cmp(&b));
+
+    let mut versions: Vec<Version> = ["1.0.0", "1.0.0-alpha.10", "0.9.9", "1.0.0-alpha.2", "1.0.0-beta"]
+        .iter()
+        .map(|v| Version::from_str(v).unwrap())
+        .collect();
+    versions.sort();
+    let versions: Vec<String> = versions.iter().map(Version::to_string).collect();
+    assert_eq!(vec!["0.9.9", "1.0.0-alpha.2", "1.0.0-alpha.10", "1.0.0-beta", "1.0.0"], versions);
+}
+
+#[test]
+fn version_req_test() {
+    assert!(matches("1.2.3", "1.9.0"));
+    assert!(!matches("^1.2.3", "1.2.2"));
+    assert!(!matches("^1.2.3", "2.0.0"));
+    assert!(matches("^0.2.3", "0.2.9"));
+    assert!(!matches("^0.2.3", "0.3.0"));
+    assert!(!matches("^0.0.3", "0.0.4"));
+    assert!(matches("~1.2.3", "1.2.9"));
+    assert!(!matches("~1.2.3", "1.3.0"));
+    assert!(matches("~1", "1.9.0"));
+    assert!(matches("=1.2", "1.2.7"));
+    assert!(!matches("=1.2.3", "1.2.4"));
+    assert!(matches(">=1.2, <1.5", "1.4.9"));
+    assert!(!matches(">=1.2, <1.5", "1.5.0"));
+    assert!(matches(">1.2", "1.3.0"));
+    assert!(!matches(">1.2", "1.2.9"));
+    assert!(matches("<=1.2", "1.2.9"));
+    assert!(matches("*", "7.0.0"));
+
+    // Pre-releases only match requirements with a pre-release of the same version
+    assert!(!matches("^1.0.0", "1.1.0-beta"));
+    assert!(matches(">=1.1.0-alpha", "1.1.0-beta"));
+    assert!(!matches(">=1.1.0-alpha", "1.2.0-beta"));
+
+    assert!(VersionReq::from_str(">=x").is_err());
+    assert_eq!(">=1.2, <1.5", VersionReq::from_str(">=1.2, <1.5").unwrap().to_string());
+}
+
+#[test]
+fn extension_version_test() {
+    let extension: Extension = serde_json::from_value(json!({ "name": "palette", "extension": {}, "version": "1.4.0" })).unwrap();
+    assert_eq!(Some(Version::new(1, 4, 0)), extension.version);
+    assert!(extension.is_compatible_with(&VersionReq::from_str("^1.2").unwrap()));
+    assert!(!extension.is_compatible_with(&VersionReq::from_str("^2").unwrap()));
+    assert_eq!(json!("1.4.0"), serde_json::to_value(&extension).unwrap()["version"]);
+
+    let legacy = Extension::new("palette", json!({}));
+    assert!(!legacy.is_compatible_with(&VersionReq::from_str("^1").unwrap()));
+    assert!(legacy.is_compatible_with(&VersionReq::from_str("*").unwrap()));
+    let extension = Extension::new_with_version("palette", json!({}), Version::new(2, 0, 0));
+    assert!(extension.is_compatible_with(&VersionReq::from_str(">=2").unwrap()));
+
+    assert!(serde_json::from_value::<Extension>(json!({ "name": "palette", "extension": {}, "version": "one" })).is_err());
+}
@@ -0,0 +1,65 @@
+use serde_json::json;
+
+use crate::{
+    Component, ComponentName, DataType, Docs, EntityType, EntityTypeName, Extension, ExtensionError, Icon, PaletteCategory, PropertyType, RelationType,
+    RelationTypeName, WellKnownExtension, WellKnownExtensions, EXTENSION_DOCS, EXTENSION_ICON, EXTENSION_PALETTE_CATEGORY,
+};
+
+#[test]
+fn well_known_extension_to_extension_test() {
+    let extension = Docs {
+        text: "Adds two numbers".to_string(),
+        url: None,
+    }
+    .to_extension();
+    assert_eq!(EXTENSION_DOCS, extension.name);
+    assert_eq!(json!({ "text": "Adds two numbers" }), extension.extension);
+
+    let extension = Icon {
+        name: "plus".to_string(),
+        color: Some("#00ff00".to_string()),
+    }
+    .to_extension();
+    assert_eq!(EXTENSION_ICON, extension.name);
+    assert_eq!(json!({ "name": "plus", "color": "#00ff00" }), extension.extension);
+}
+
+#[test]
+fn entity_type_well_known_extensions_test() {
+    let palette_category = PaletteCategory {
+        category: "Arithmetic".to_string(),
+        order: 1,
+    };
+    let entity_type = EntityType::new(
+        EntityTypeName::new("add"),
+        "arithmetic",
+        "",
+        Vec::new(),
+        vec![PropertyType::input("lhs", DataType::Number)],
+        vec![
+            Extension::new(EXTENSION_DOCS, json!({ "text": "Adds two numbers", "url": "https://example.org/add" })),
+            palette_category.to_extension(),
+        ],
+    );
+    let docs = entity_type.docs().unwrap();
+    assert_eq!("Adds two numbers", docs.text);
+    assert_eq!(Some("https://example.org/add".to_string()), docs.url);
+    assert_eq!(palette_category, entity_type.palette_category().unwrap());
+    assert!(matches!(entity_type.icon(), Err(ExtensionError::Missing(name)) if name == EXTENSION_ICON));
+}
+
+#[test]
+fn invalid_well_known_extension_test() {
+    let mut component = Component::new(ComponentName::new("labeled"), Vec::new());
+    component.extensions.push(Extension::new(EXTENSION_ICON, json!("plus")));
+    assert!(matches!(component.icon(), Err(ExtensionError::Invalid(..))));
+
+    let mut relation_type = RelationType::new("*", RelationTypeName::new("connector"), "*", "", "", Vec::new(), Vec::new(), Vec::new());
+    relation_type
+        .extensions
+        .push(Extension::new(EXTENSION_PALETTE_CATEGORY, json!({ "category": "Connectors" })));
+    assert_eq!(0, relation_type.palette_category().unwrap().order);
+
+    let property_type = PropertyType::new("value", DataType::Number).with_extension(Extension::new(EXTENSION_DOCS, json!({ "text": "The value" })));
+    assert_eq!("The value", property_type.docs().unwrap().text);
+}
@@ -0,0 +1,207 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::Instant;
+
+/// The time spent propagating the values of a property.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PropertyProfile {
+    /// The id of the instance which contains the property.
+    pub id: Uuid,
+
+    /// The name of the property.
+    pub property_name: String,
+
+    /// The number of propagations.
+    pub calls: u64,
+
+    /// The time spent in the observers of the property including nested propagations, in nanoseconds.
+    pub total_ns: u64,
+
+    /// The time spent in the observers of the property excluding nested propagations, in nanoseconds.
+    pub self_ns: u64,
+}
+
+/// The time spent propagating the values of the properties of an instance.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct InstanceProfile {
+    /// The id of the instance.
+    pub id: Uuid,
+
+    /// The number of propagations.
+    pub calls: u64,
+
+    /// The time spent in the observers of the properties excluding nested propagations, in nanoseconds.
+    pub self_ns: u64,
+}
+
+/// The profile of the propagations which have happened while profiling.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProfileReport {
+    /// The wall time of the profiled run, in nanoseconds.
+    pub duration_ns: u64,
+
+    /// The profiles of the properties, sorted by self time (descending).
+    pub properties: Vec<PropertyProfile>,
+
+    /// The profiles of the instances, sorted by self time (descending).
+    pub instances: Vec<InstanceProfile>,
+
+    /// The self time of each propagation stack, in nanoseconds. The frames of a stack are
+    /// separated by `;` and each frame is formatted as `id.property_name`.
+    pub stacks: Vec<(String, u64)>,
+}
+
+impl ProfileReport {
+    /// Returns the profile of the property of the instance with the given id.
+    pub fn get_property<S: AsRef<str>>(&self, id: Uuid, property_name: S) -> Option<&PropertyProfile> {
+        self.properties
+            .iter()
+            .find(|profile| profile.id == id && profile.property_name == property_name.as_ref())
+    }
+
+    /// Returns the profile of the instance with the given id.
+    pub fn get_instance(&self, id: Uuid) -> Option<&InstanceProfile> {
+        self.instances.iter().find(|profile| profile.id == id)
+    }
+
+    /// Returns the stacks in the folded format (`frame;frame;frame self_ns` per line) which is
+    /// understood by flamegraph tools.
+    pub fn to_folded(&self) -> String {
+        self.stacks.iter().map(|(stack, self_ns)| format!("{} {}\n", stack, self_ns)).collect()
+    }
+}
+
+#[derive(Default)]
+struct ProfileData {
+    properties: HashMap<(Uuid, String), PropertyProfile>,
+    stacks: HashMap<String, u64>,
+}
+
+/// A propagation which is in progress on this thread.
+struct Frame {
+    name: String,
+    child_ns: u64,
+}
+
+thread_local! {
+    /// The data which is recorded on this thread, if a profile is running.
+    static PROFILE_DATA: RefCell<Option<ProfileData>> = const { RefCell::new(None) };
+
+    static STACK: RefCell<Vec<Frame>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Records the propagations on this thread as long as the guard exists. Dropping the guard
+/// restores the previous state, even if the profiled function panics.
+struct ProfilingGuard {
+    previous_data: Option<ProfileData>,
+    previous_stack: Vec<Frame>,
+}
+
+impl ProfilingGuard {
+    fn start() -> ProfilingGuard {
+        ProfilingGuard {
+            previous_data: PROFILE_DATA.with(|data| data.replace(Some(ProfileData::default()))),
+            previous_stack: STACK.with(|stack| stack.take()),
+        }
+    }
+
+    /// Returns the recorded data.
+    fn finish(self) -> ProfileData {
+        PROFILE_DATA.with(|data| data.borrow_mut().take()).unwrap_or_default()
+    }
+}
+
+impl Drop for ProfilingGuard {
+    fn drop(&mut self) {
+        PROFILE_DATA.with(|data| *data.borrow_mut() = self.previous_data.take());
+        STACK.with(|stack| *stack.borrow_mut() = std::mem::take(&mut self.previous_stack));
+    }
+}
+
+/// Profiles the propagations which happen while the function is running, e.g. the tick of a flow.
+///
+/// Only the propagations of the current thread are recorded. Profiles can be nested; the
+/// propagations of a nested profile are not included in the outer profile.
+pub fn profile<F: FnOnce()>(f: F) -> ProfileReport {
+    let guard = ProfilingGuard::start();
+    let start = Instant::now();
+    f();
+    let duration_ns = start.elapsed().as_nanos() as u64;
+    let data = guard.finish();
+
+    let mut properties: Vec<PropertyProfile> = data.properties.into_values().collect();
+    properties.sort_by(|a, b| b.self_ns.cmp(&a.self_ns).then_with(|| a.property_name.cmp(&b.property_name)));
+    let mut instances: HashMap<Uuid, InstanceProfile> = HashMap::new();
+    for property in properties.iter() {
+        let instance = instances.entry(property.id).or_insert(InstanceProfile {
+            id: property.id,
+            calls: 0,
+            self_ns: 0,
+        });
+        instance.calls += property.calls;
+        instance.self_ns += property.self_ns;
+    }
+    let mut instances: Vec<InstanceProfile> = instances.into_values().collect();
+    instances.sort_by(|a, b| b.self_ns.cmp(&a.self_ns).then_with(|| a.id.cmp(&b.id)));
+    let mut stacks: Vec<(String, u64)> = data.stacks.into_iter().collect();
+    stacks.sort();
+    ProfileReport {
+        duration_ns,
+        properties,
+        instances,
+        stacks,
+    }
+}
+
+/// Records the time spent in the propagation of a value of the property, if profiling is enabled.
+pub(crate) fn profile_propagation<F: FnOnce()>(id: Uuid, property_name: &str, propagate: F) {
+    if PROFILE_DATA.with(|data| data.borrow().is_none()) {
+        propagate();
+        return;
+    }
+    STACK.with(|stack| {
+        stack.borrow_mut().push(Frame {
+            name: format!("{}.{}", id, property_name),
+            child_ns: 0,
+        })
+    });
+    let start = Instant::now();
+    propagate();
+    let total_ns = start.elapsed().as_nanos() as u64;
+    let (path, frame) = STACK.with(|stack| {
+        let mut stack = stack.borrow_mut();
+        let frame = stack.pop().unwrap();
+        if let Some(parent) = stack.last_mut() {
+            parent.child_ns += total_ns;
+        }
+        let path: Vec<&str> = stack
+            .iter()
+            .map(|frame| frame.name.as_str())
+            .chain(std::iter::once(frame.name.as_str()))
+            .collect();
+        (path.join(";"), frame)
+    });
+    let self_ns = total_ns.saturating_sub(frame.child_ns);
+    PROFILE_DATA.with(|data| {
+        let mut data = data.borrow_mut();
+        let data = match data.as_mut() {
+            Some(data) => data,
+            None => return,
+        };
+        let profile = data.properties.entry((id, property_name.to_string())).or_insert(PropertyProfile {
+            id,
+            property_name: property_name.to_string(),
+            calls: 0,
+            total_ns: 0,
+            self_ns: 0,
+        });
+        profile.calls += 1;
+        profile.total_ns += total_ns;
+        profile.self_ns += self_ns;
+        *data.stacks.entry(path).or_insert(0) += self_ns;
+    });
+}
@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::ReactiveEntityInstance;
+
+/// The separator of the levels of a topic.
+pub const TOPIC_LEVEL_SEPARATOR: char = '/';
+
+/// Wildcard which matches exactly one topic level.
+pub const TOPIC_SINGLE_LEVEL_WILDCARD: &str = "+";
+
+/// Wildcard which matches any number of topic levels. Must be the last level.
+pub const TOPIC_MULTI_LEVEL_WILDCARD: &str = "#";
+
+/// Addresses a property of an instance.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PropertyAddress {
+    /// The id of the instance.
+    pub id: Uuid,
+
+    /// The name of the property.
+    pub property_name: String,
+}
+
+impl PropertyAddress {
+    pub fn new<S: Into<String>>(id: Uuid, property_name: S) -> PropertyAddress {
+        PropertyAddress {
+            id,
+            property_name: property_name.into(),
+        }
+    }
+}
+
+/// Maps hierarchical topic strings to property addresses and back.
+///
+/// An instance is bound to a topic path (e.g. `player/1`). The properties of the
+/// instance are addressed by appending the property name as last topic level
+/// (e.g. `player/1/position`). Subscriptions may contain the wildcards `+` and
+/// `#` (MQTT style). This is the canonical mapping layer which protocol plugins
+/// (MQTT, OSC, WebSocket) can share.
+#[derive(Clone, Debug, Default)]
+pub struct TopicMapping {
+    /// The instance ids by topic path.
+    pub instances: HashMap<String, Uuid>,
+
+    /// The topic paths by instance id.
+    pub paths: HashMap<Uuid, String>,
+
+    /// The names of the known properties by instance id. Used to expand wildcard subscriptions.
+    pub properties: HashMap<Uuid, Vec<String>>,
+}
+
+impl TopicMapping {
+    pub fn new() -> TopicMapping {
+        TopicMapping::default()
+    }
+
+    /// Binds the instance with the given id and properties to the given topic path. The previous
+    /// binding of the instance and the previous owner of the topic path are unbound.
+    pub fn bind<S: Into<String>>(&mut self, path: S, id: Uuid, property_names: Vec<String>) {
+        let path = path.into().trim_matches(TOPIC_LEVEL_SEPARATOR).to_string();
+        self.unbind(id);
+        if let Some(previous_id) = self.instances.get(&path).copied() {
+            self.unbind(previous_id);
+        }
+        self.instances.insert(path.clone(), id);
+        self.paths.insert(id, path);
+        self.properties.insert(id, property_names);
+    }
+
+    /// Binds the entity instance and its properties to the given topic path.
+    pub fn bind_entity<S: Into<String>>(&mut self, path: S, entity_instance: &ReactiveEntityInstance) {
+        let property_names = entity_instance.properties.iter().map(|property| property.key().to_string()).collect();
+        self.bind(path, entity_instance.id, property_names);
+    }
+
+    /// Removes the binding of the instance with the given id.
+    pub fn unbind(&mut self, id: Uuid) {
+        if let Some(path) = self.paths.remove(&id) {
+            self.instances.remove(&path);
+        }
+        self.properties.remove(&id);
+    }
+
+    /// Resolves the given topic to a property address.
+    pub fn resolve<S: Into<String>>(&self, topic: S) -> Option<PropertyAddress> {
+        let topic = topic.into();
+        let topic = topic.trim_matches(TOPIC_LEVEL_SEPARATOR);
+        let (path, property_name) = topic.rsplit_once(TOPIC_LEVEL_SEPARATOR)?;
+        self.instances.get(path).map(|id| PropertyAddress::new(*id, property_name))
+    }
+
+    /// Returns the topic of the given property address.
+    pub fn get_topic(&self, address: &PropertyAddress) -> Option<String> {
+        self.paths
+            .get(&address.id)
+            .map(|path| format!("{}{}{}", path, TOPIC_LEVEL_SEPARATOR, address.property_name))
+    }
+
+    /// Returns all known topics.
+    pub fn get_topics(&self) -> Vec<String> {
+        let mut topics = Vec::new();
+        for (id, path) in self.paths.iter() {
+            if let Some(property_names) = self.properties.get(id) {
+                for property_name in property_names {
+                    topics.push(format!("{}{}{}", path, TOPIC_LEVEL_SEPARATOR, property_name));
+                }
+            }
+        }
+        topics
+    }
+
+    /// Expands the given subscription (which may contain wildcards) to the addresses of all matching properties.
+    pub fn expand<S: Into<String>>(&self, subscription: S) -> Vec<PropertyAddress> {
+        let subscription = subscription.into();
+        self.get_topics()
+            .iter()
+            .filter(|topic| TopicMapping::matches(&subscription, topic))
+            .filter_map(|topic| self.resolve(topic.as_str()))
+            .collect()
+    }
+
+    /// Returns true, if the topic matches the given subscription filter.
+    pub fn matches(filter: &str, topic: &str) -> bool {
+        let mut filter_levels = filter.trim_matches(TOPIC_LEVEL_SEPARATOR).split(TOPIC_LEVEL_SEPARATOR);
+        let mut topic_levels = topic.trim_matches(TOPIC_LEVEL_SEPARATOR).split(TOPIC_LEVEL_SEPARATOR);
+        loop {
+            match (filter_levels.next(), topic_levels.next()) {
+                (Some(TOPIC_MULTI_LEVEL_WILDCARD), _) => return filter_levels.next().is_none(),
+                (Some(TOPIC_SINGLE_LEVEL_WILDCARD), Some(_)) => {}
+                (Some(filter_level), Some(topic_level)) => {
+                    if filter_level != topic_level {
+                        return false;
+                    }
+                }
+                (None, None) => return true,
+                _ => return false,
+            }
+        }
+    }
+}
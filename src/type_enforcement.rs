@@ -0,0 +1,118 @@
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::DataType;
+
+/// Specifies how a reactive property handles values which don't match its data type.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TypeEnforcementPolicy {
+    /// Every value is accepted without checking.
+    #[default]
+    Disabled,
+
+    /// Mismatching values are dropped and reported.
+    Reject,
+
+    /// Mismatching values are coerced into the data type. Values which can't be coerced are
+    /// dropped and reported.
+    Coerce,
+
+    /// Mismatching values are accepted and reported.
+    Report,
+}
+
+/// A value which doesn't match the data type of a property.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TypeMismatch {
+    /// The id of the instance which contains the property.
+    pub id: Uuid,
+
+    /// The name of the property.
+    pub property_name: String,
+
+    /// The data type of the property.
+    pub data_type: DataType,
+
+    /// The value which has been set.
+    pub value: Value,
+
+    /// True, if the value has been accepted anyway.
+    pub accepted: bool,
+}
+
+impl fmt::Display for TypeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The value {} of the property {} of {} is not of type {}",
+            self.value, self.property_name, self.id, self.data_type
+        )
+    }
+}
+
+/// Callback which is called whenever a value doesn't match the data type of a property.
+pub type TypeMismatchHandler = Arc<dyn Fn(&TypeMismatch) + Send + Sync>;
+
+static TYPE_MISMATCH_HANDLER: RwLock<Option<TypeMismatchHandler>> = RwLock::new(None);
+
+/// Registers the global type mismatch handler. Replaces a previously registered handler.
+pub fn set_type_mismatch_handler(handler: TypeMismatchHandler) {
+    *TYPE_MISMATCH_HANDLER.write().unwrap() = Some(handler);
+}
+
+/// Unregisters the global type mismatch handler.
+pub fn clear_type_mismatch_handler() {
+    *TYPE_MISMATCH_HANDLER.write().unwrap() = None;
+}
+
+/// The type enforcement policy and the data type of a reactive property.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PropertyTypeEnforcement {
+    /// The type enforcement policy.
+    pub policy: TypeEnforcementPolicy,
+
+    /// The data type the values are checked against.
+    pub data_type: DataType,
+}
+
+impl PropertyTypeEnforcement {
+    pub fn new(policy: TypeEnforcementPolicy, data_type: DataType) -> PropertyTypeEnforcement {
+        PropertyTypeEnforcement { policy, data_type }
+    }
+
+    /// Returns the value which should be stored or none, if the value has to be dropped.
+    pub(crate) fn enforce(&self, id: Uuid, property_name: &str, value: Value) -> Option<Value> {
+        if self.policy == TypeEnforcementPolicy::Disabled || self.data_type.is_valid(&value) {
+            return Some(value);
+        }
+        let result = match self.policy {
+            TypeEnforcementPolicy::Coerce => self.data_type.coerce(&value),
+            TypeEnforcementPolicy::Report => Some(value.clone()),
+            _ => None,
+        };
+        if result.is_none() || self.policy == TypeEnforcementPolicy::Report {
+            let handler = TYPE_MISMATCH_HANDLER.read().unwrap().clone();
+            if let Some(handler) = handler {
+                handler(&TypeMismatch {
+                    id,
+                    property_name: property_name.to_string(),
+                    data_type: self.data_type,
+                    value,
+                    accepted: result.is_some(),
+                });
+            }
+        }
+        result
+    }
+}
+
+impl Default for PropertyTypeEnforcement {
+    fn default() -> Self {
+        PropertyTypeEnforcement::new(TypeEnforcementPolicy::Disabled, DataType::Any)
+    }
+}
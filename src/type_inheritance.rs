@@ -0,0 +1,164 @@
+use std::mem;
+
+use crate::{ComponentName, Extension, PropertyGroup, PropertyType};
+
+/// The definitions of a type which are inherited from its base types.
+pub(crate) struct InheritedDefinitions<'a> {
+    pub(crate) extends: &'a mut Vec<String>,
+    pub(crate) components: &'a mut Vec<ComponentName>,
+    pub(crate) properties: &'a mut Vec<PropertyType>,
+    pub(crate) extensions: &'a mut Vec<Extension>,
+    /// The property groups, if the type kind has property groups.
+    pub(crate) property_groups: Option<&'a mut Vec<PropertyGroup>>,
+}
+
+/// A type which inherits the definitions of base types of the same kind.
+pub(crate) trait InheritableType: Clone {
+    type Name: PartialEq + Clone + ToString;
+
+    /// Returns the name which identifies the type in the inheritance path.
+    fn inheritance_name(&self) -> &Self::Name;
+
+    /// Returns true, if the type has the given name.
+    fn has_name(&self, name: &str) -> bool;
+
+    /// Returns the inheritable definitions of the type.
+    fn inherited_definitions(&mut self) -> InheritedDefinitions<'_>;
+}
+
+/// The inheritance of a type can't be resolved.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum InheritanceError {
+    /// The base type with the given name doesn't exist.
+    MissingBaseType(String),
+
+    /// The type with the given name extends itself.
+    Cycle(String),
+
+    /// The property with the given name is inherited differently.
+    ConflictingProperty(String),
+
+    /// The extension with the given name is inherited differently.
+    ConflictingExtension(String),
+}
+
+/// An inherited definition conflicts with a definition which is already contained.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum InheritanceConflict {
+    /// The property with the given name has a different data type or socket type.
+    Property(String),
+
+    /// The extension with the given name has a different value.
+    Extension(String),
+}
+
+impl From<InheritanceConflict> for InheritanceError {
+    fn from(conflict: InheritanceConflict) -> Self {
+        match conflict {
+            InheritanceConflict::Property(name) => InheritanceError::ConflictingProperty(name),
+            InheritanceConflict::Extension(name) => InheritanceError::ConflictingExtension(name),
+        }
+    }
+}
+
+/// Returns the flattened type which contains the inherited definitions of all base types. Own
+/// definitions override inherited ones with the same name.
+pub(crate) fn resolve_inheritance<T: InheritableType>(inheritable: &T, types: &[T], path: &mut Vec<T::Name>) -> Result<T, InheritanceError> {
+    if path.contains(inheritable.inheritance_name()) {
+        return Err(InheritanceError::Cycle(inheritable.inheritance_name().to_string()));
+    }
+    path.push(inheritable.inheritance_name().clone());
+    let mut resolved = inheritable.clone();
+    let definitions = resolved.inherited_definitions();
+    let extends = mem::take(definitions.extends);
+    let own_components = mem::take(definitions.components);
+    let own_properties = mem::take(definitions.properties);
+    let own_extensions = mem::take(definitions.extensions);
+    let own_property_groups = definitions.property_groups.map(mem::take);
+    for base_type_name in extends.iter() {
+        let mut base_type = types
+            .iter()
+            .find(|base_type| base_type.has_name(base_type_name))
+            .ok_or_else(|| InheritanceError::MissingBaseType(base_type_name.clone()))
+            .and_then(|base_type| resolve_inheritance(base_type, types, path))?;
+        let base_definitions = base_type.inherited_definitions();
+        let definitions = resolved.inherited_definitions();
+        inherit_components(definitions.components, base_definitions.components.drain(..));
+        inherit_properties(definitions.properties, base_definitions.properties.drain(..))?;
+        inherit_extensions(definitions.extensions, base_definitions.extensions.drain(..))?;
+        if let (Some(property_groups), Some(base_property_groups)) = (definitions.property_groups, base_definitions.property_groups) {
+            for property_group in base_property_groups.drain(..) {
+                if !property_groups.iter().any(|g| g.name == property_group.name) {
+                    property_groups.push(property_group);
+                }
+            }
+        }
+    }
+    path.pop();
+    let definitions = resolved.inherited_definitions();
+    inherit_components(definitions.components, own_components);
+    override_properties(definitions.properties, own_properties);
+    override_extensions(definitions.extensions, own_extensions);
+    if let (Some(property_groups), Some(own_property_groups)) = (definitions.property_groups, own_property_groups) {
+        for property_group in own_property_groups {
+            property_groups.retain(|g| g.name != property_group.name);
+            property_groups.push(property_group);
+        }
+    }
+    Ok(resolved)
+}
+
+/// Adds the inherited components which are not yet contained.
+pub(crate) fn inherit_components<I: IntoIterator<Item = ComponentName>>(components: &mut Vec<ComponentName>, inherited: I) {
+    for component in inherited {
+        if !components.contains(&component) {
+            components.push(component);
+        }
+    }
+}
+
+/// Adds the inherited property types. A property type which is already contained must have the
+/// same data type and socket type.
+pub(crate) fn inherit_properties<I: IntoIterator<Item = PropertyType>>(properties: &mut Vec<PropertyType>, inherited: I) -> Result<(), InheritanceConflict> {
+    for property_type in inherited {
+        match properties.iter().find(|p| p.name == property_type.name) {
+            Some(p) if is_conflicting_property(p, &property_type) => return Err(InheritanceConflict::Property(property_type.name)),
+            Some(_) => {}
+            None => properties.push(property_type),
+        }
+    }
+    Ok(())
+}
+
+/// Adds the inherited extensions. An extension which is already contained must have the same value.
+pub(crate) fn inherit_extensions<I: IntoIterator<Item = Extension>>(extensions: &mut Vec<Extension>, inherited: I) -> Result<(), InheritanceConflict> {
+    for extension in inherited {
+        match extensions.iter().find(|e| e.name == extension.name) {
+            Some(e) if e.extension != extension.extension => return Err(InheritanceConflict::Extension(extension.name)),
+            Some(_) => {}
+            None => extensions.push(extension),
+        }
+    }
+    Ok(())
+}
+
+/// Adds the own property types, which replace inherited property types with the same name.
+pub(crate) fn override_properties<I: IntoIterator<Item = PropertyType>>(properties: &mut Vec<PropertyType>, own: I) {
+    for property_type in own {
+        properties.retain(|p| p.name != property_type.name);
+        properties.push(property_type);
+    }
+}
+
+/// Adds the own extensions, which replace inherited extensions with the same name.
+pub(crate) fn override_extensions<I: IntoIterator<Item = Extension>>(extensions: &mut Vec<Extension>, own: I) {
+    for extension in own {
+        extensions.retain(|e| e.name != extension.name);
+        extensions.push(extension);
+    }
+}
+
+/// Returns true, if the property types have a different data type or socket type.
+pub(crate) fn is_conflicting_property(property_type: &PropertyType, other: &PropertyType) -> bool {
+    property_type.data_type != other.data_type || property_type.socket_type != other.socket_type
+}
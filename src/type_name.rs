@@ -0,0 +1,171 @@
+use std::borrow::Borrow;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{validate_identifier, ValidationError};
+
+/// The maximum length of a type name in bytes.
+pub const MAX_TYPE_NAME_LENGTH: usize = 255;
+
+/// Replaces invalid characters of type names.
+pub const TYPE_NAME_REPLACEMENT_CHARACTER: char = '_';
+
+/// Returns true, if the character is allowed in type names: letters, numbers, dashes and underscores.
+pub fn is_valid_type_name_character(c: char) -> bool {
+    c == '-' || c == '_' || c.is_alphanumeric()
+}
+
+/// Returns true, if the name is a valid type name. Type names are valid identifiers of the graph
+/// database: they are not empty, are limited to 255 bytes and only contain letters, numbers, dashes
+/// and underscores.
+pub fn is_valid_type_name(name: &str) -> bool {
+    !name.is_empty() && name.len() <= MAX_TYPE_NAME_LENGTH && name.chars().all(is_valid_type_name_character)
+}
+
+/// Turns the name into a valid type name. Invalid characters are replaced by underscores and the
+/// name is truncated to 255 bytes. An empty name becomes `_`. Valid type names are not changed.
+pub fn sanitize_type_name(name: &str) -> String {
+    let mut sanitized = String::new();
+    for c in name.chars() {
+        let c = if is_valid_type_name_character(c) {
+            c
+        } else {
+            TYPE_NAME_REPLACEMENT_CHARACTER
+        };
+        if sanitized.len() + c.len_utf8() > MAX_TYPE_NAME_LENGTH {
+            break;
+        }
+        sanitized.push(c);
+    }
+    if sanitized.is_empty() {
+        sanitized.push(TYPE_NAME_REPLACEMENT_CHARACTER);
+    }
+    sanitized
+}
+
+/// Declares a newtype for a kind of name. Names are constructed explicitly with `new` or parsed
+/// with `FromStr`, so names of different kinds can't be mixed up. Names convert into strings and
+/// compare with strings.
+macro_rules! type_name {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        #[serde(transparent)]
+        pub struct $name(String);
+
+        impl $name {
+            /// Constructs the name without validating it.
+            pub fn new<S: Into<String>>(name: S) -> $name {
+                $name(name.into())
+            }
+
+            /// Returns the name as string slice.
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// Returns the name as string.
+            pub fn into_inner(self) -> String {
+                self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        /// Fails, if the name is not a valid identifier.
+        impl FromStr for $name {
+            type Err = ValidationError;
+
+            fn from_str(name: &str) -> Result<Self, Self::Err> {
+                validate_identifier(name)?;
+                Ok($name(name.to_string()))
+            }
+        }
+
+        impl From<&$name> for $name {
+            fn from(name: &$name) -> Self {
+                name.clone()
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(name: $name) -> Self {
+                name.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl Borrow<str> for $name {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl PartialEq<String> for $name {
+            fn eq(&self, other: &String) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl PartialEq<$name> for str {
+            fn eq(&self, other: &$name) -> bool {
+                self == other.0
+            }
+        }
+
+        impl PartialEq<$name> for &str {
+            fn eq(&self, other: &$name) -> bool {
+                *self == other.0
+            }
+        }
+
+        impl PartialEq<$name> for String {
+            fn eq(&self, other: &$name) -> bool {
+                *self == other.0
+            }
+        }
+    };
+}
+
+type_name!(
+    /// The name of an entity type.
+    EntityTypeName
+);
+
+type_name!(
+    /// The name of a relation type.
+    RelationTypeName
+);
+
+type_name!(
+    /// The name of a component.
+    ComponentName
+);
+
+type_name!(
+    /// The name of a behaviour.
+    BehaviourName
+);
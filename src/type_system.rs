@@ -0,0 +1,409 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::type_inheritance::{inherit_extensions, inherit_properties, is_conflicting_property, override_extensions, override_properties};
+use crate::{
+    BehaviourType, Component, ComponentName, ComposeError, ComposedEntityType, ComposedRelationType, DataType, EntityType, EntityTypeResolveError, Extension,
+    Flow, PropertyType, RelationType, RelationTypeResolveError,
+};
+
+/// The components, entity types, relation types, flow types and behaviour types of a type system.
+///
+/// Types are identified by name. Adding a type replaces the type with the same name.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct TypeSystem {
+    /// The components.
+    #[serde(default = "Vec::new")]
+    pub components: Vec<Component>,
+
+    /// The entity types.
+    #[serde(default = "Vec::new")]
+    pub entity_types: Vec<EntityType>,
+
+    /// The relation types.
+    #[serde(default = "Vec::new")]
+    pub relation_types: Vec<RelationType>,
+
+    /// The flow types. A flow type is a flow which is used as template.
+    #[serde(default = "Vec::new")]
+    pub flow_types: Vec<Flow>,
+
+    /// The behaviour types.
+    #[serde(default = "Vec::new")]
+    pub behaviour_types: Vec<BehaviourType>,
+}
+
+impl TypeSystem {
+    pub fn new() -> TypeSystem {
+        TypeSystem::default()
+    }
+
+    /// Adds the component. A component with the same name is replaced.
+    pub fn add_component(&mut self, component: Component) {
+        self.remove_component(component.name.clone());
+        self.components.push(component);
+    }
+
+    /// Removes the component with the given name.
+    pub fn remove_component<S: AsRef<str>>(&mut self, name: S) {
+        self.components.retain(|component| component.name != name.as_ref());
+    }
+
+    /// Returns true, if a component with the given name exists.
+    pub fn has_component<S: AsRef<str>>(&self, name: S) -> bool {
+        self.get_component(name).is_some()
+    }
+
+    /// Returns the component with the given name.
+    pub fn get_component<S: AsRef<str>>(&self, name: S) -> Option<&Component> {
+        self.components.iter().find(|component| component.name == name.as_ref())
+    }
+
+    /// Adds the entity type. An entity type with the same name is replaced.
+    pub fn add_entity_type(&mut self, entity_type: EntityType) {
+        self.remove_entity_type(entity_type.name.clone());
+        self.entity_types.push(entity_type);
+    }
+
+    /// Removes the entity type with the given name.
+    pub fn remove_entity_type<S: AsRef<str>>(&mut self, name: S) {
+        self.entity_types.retain(|entity_type| entity_type.name != name.as_ref());
+    }
+
+    /// Returns true, if an entity type with the given name exists.
+    pub fn has_entity_type<S: AsRef<str>>(&self, name: S) -> bool {
+        self.get_entity_type(name).is_some()
+    }
+
+    /// Returns the entity type with the given name.
+    pub fn get_entity_type<S: AsRef<str>>(&self, name: S) -> Option<&EntityType> {
+        self.entity_types.iter().find(|entity_type| entity_type.name == name.as_ref())
+    }
+
+    /// Returns the entity types which are composed with the component with the given name.
+    pub fn get_entity_types_by_component<C: Into<ComponentName>>(&self, component_name: C) -> Vec<&EntityType> {
+        let component_name = component_name.into();
+        self.entity_types
+            .iter()
+            .filter(|entity_type| entity_type.is_a(component_name.clone()))
+            .collect()
+    }
+
+    /// Returns the entity types which have a property with the given name. The properties of the
+    /// components are included.
+    pub fn get_entity_types_by_property<S: AsRef<str>>(&self, property_name: S) -> Vec<&EntityType> {
+        self.entity_types
+            .iter()
+            .filter(|entity_type| self.has_property(&entity_type.components, &entity_type.properties, |p| p.name == property_name.as_ref()))
+            .collect()
+    }
+
+    /// Returns the entity types which have a property of the given data type. The properties of
+    /// the components are included.
+    pub fn get_entity_types_by_data_type(&self, data_type: DataType) -> Vec<&EntityType> {
+        self.entity_types
+            .iter()
+            .filter(|entity_type| self.has_property(&entity_type.components, &entity_type.properties, |p| p.data_type == data_type))
+            .collect()
+    }
+
+    /// Adds the relation type. A relation type with the same name is replaced.
+    pub fn add_relation_type(&mut self, relation_type: RelationType) {
+        self.remove_relation_type(relation_type.type_name.clone());
+        self.relation_types.push(relation_type);
+    }
+
+    /// Removes the relation type with the given name.
+    pub fn remove_relation_type<S: AsRef<str>>(&mut self, type_name: S) {
+        self.relation_types.retain(|relation_type| relation_type.type_name != type_name.as_ref());
+    }
+
+    /// Returns true, if a relation type with the given name exists.
+    pub fn has_relation_type<S: AsRef<str>>(&self, type_name: S) -> bool {
+        self.get_relation_type(type_name).is_some()
+    }
+
+    /// Returns the relation type with the given name.
+    pub fn get_relation_type<S: AsRef<str>>(&self, type_name: S) -> Option<&RelationType> {
+        self.relation_types.iter().find(|relation_type| relation_type.type_name == type_name.as_ref())
+    }
+
+    /// Returns the relation types which are composed with the component with the given name.
+    pub fn get_relation_types_by_component<S: AsRef<str>>(&self, component_name: S) -> Vec<&RelationType> {
+        self.relation_types
+            .iter()
+            .filter(|relation_type| relation_type.components.iter().any(|component| component == component_name.as_ref()))
+            .collect()
+    }
+
+    /// Returns the relation types which can connect entity instances of the given entity types.
+    /// Relation types between unknown entity types are not returned.
+    pub fn get_relation_types_between<S: AsRef<str>>(&self, outbound_type_name: S, inbound_type_name: S) -> Vec<&RelationType> {
+        let (outbound_type, inbound_type) = match (self.get_entity_type(outbound_type_name), self.get_entity_type(inbound_type_name)) {
+            (Some(outbound_type), Some(inbound_type)) => (outbound_type, inbound_type),
+            _ => return Vec::new(),
+        };
+        self.relation_types
+            .iter()
+            .filter(|relation_type| {
+                relation_type.outbound_type.matches_entity_type(outbound_type) && relation_type.inbound_type.matches_entity_type(inbound_type)
+            })
+            .collect()
+    }
+
+    /// Returns the relation types which have a property with the given name. The properties of the
+    /// components are included.
+    pub fn get_relation_types_by_property<S: AsRef<str>>(&self, property_name: S) -> Vec<&RelationType> {
+        self.relation_types
+            .iter()
+            .filter(|relation_type| self.has_property(&relation_type.components, &relation_type.properties, |p| p.name == property_name.as_ref()))
+            .collect()
+    }
+
+    /// Returns the relation types which have a property of the given data type. The properties of
+    /// the components are included.
+    pub fn get_relation_types_by_data_type(&self, data_type: DataType) -> Vec<&RelationType> {
+        self.relation_types
+            .iter()
+            .filter(|relation_type| self.has_property(&relation_type.components, &relation_type.properties, |p| p.data_type == data_type))
+            .collect()
+    }
+
+    /// Returns the components which have a property of the given data type.
+    pub fn get_components_by_data_type(&self, data_type: DataType) -> Vec<&Component> {
+        self.components
+            .iter()
+            .filter(|component| component.properties.iter().any(|p| p.data_type == data_type))
+            .collect()
+    }
+
+    /// Returns true, if one of the properties or one of the properties of the components matches.
+    fn has_property<P: Fn(&PropertyType) -> bool>(&self, component_names: &[ComponentName], properties: &[PropertyType], predicate: P) -> bool {
+        properties.iter().any(&predicate)
+            || component_names
+                .iter()
+                .filter_map(|component_name| self.get_component(component_name))
+                .any(|component| component.properties.iter().any(&predicate))
+    }
+
+    /// Adds the behaviour type. A behaviour type with the same name is replaced.
+    pub fn add_behaviour_type(&mut self, behaviour_type: BehaviourType) {
+        self.remove_behaviour_type(behaviour_type.name.clone());
+        self.behaviour_types.push(behaviour_type);
+    }
+
+    /// Removes the behaviour type with the given name.
+    pub fn remove_behaviour_type<S: AsRef<str>>(&mut self, name: S) {
+        self.behaviour_types.retain(|behaviour_type| behaviour_type.name != name.as_ref());
+    }
+
+    /// Returns true, if a behaviour type with the given name exists.
+    pub fn has_behaviour_type<S: AsRef<str>>(&self, name: S) -> bool {
+        self.get_behaviour_type(name).is_some()
+    }
+
+    /// Returns the behaviour type with the given name.
+    pub fn get_behaviour_type<S: AsRef<str>>(&self, name: S) -> Option<&BehaviourType> {
+        self.behaviour_types.iter().find(|behaviour_type| behaviour_type.name == name.as_ref())
+    }
+
+    /// Returns the behaviour types which can be applied to entity instances of the entity type
+    /// with the given name, either directly or by one of the components of the entity type.
+    pub fn get_behaviour_types_by_entity_type<S: AsRef<str>>(&self, type_name: S) -> Vec<&BehaviourType> {
+        let components = self
+            .get_entity_type(type_name.as_ref())
+            .map(|entity_type| entity_type.components.clone())
+            .unwrap_or_default();
+        self.behaviour_types
+            .iter()
+            .filter(|behaviour_type| {
+                behaviour_type.applies_to_entity_type(type_name.as_ref()) || components.iter().any(|component| behaviour_type.applies_to_component(component))
+            })
+            .collect()
+    }
+
+    /// Returns the behaviour types which can be applied to relation instances of the relation type
+    /// with the given name, either directly or by one of the components of the relation type.
+    pub fn get_behaviour_types_by_relation_type<S: AsRef<str>>(&self, type_name: S) -> Vec<&BehaviourType> {
+        let components = self
+            .get_relation_type(type_name.as_ref())
+            .map(|relation_type| relation_type.components.clone())
+            .unwrap_or_default();
+        self.behaviour_types
+            .iter()
+            .filter(|behaviour_type| {
+                behaviour_type.applies_to_relation_type(type_name.as_ref()) || components.iter().any(|component| behaviour_type.applies_to_component(component))
+            })
+            .collect()
+    }
+
+    /// Adds the flow type. A flow type with the same name is replaced.
+    pub fn add_flow_type(&mut self, flow_type: Flow) {
+        self.remove_flow_type(flow_type.name.clone());
+        self.flow_types.push(flow_type);
+    }
+
+    /// Removes the flow type with the given name.
+    pub fn remove_flow_type<S: AsRef<str>>(&mut self, name: S) {
+        self.flow_types.retain(|flow_type| flow_type.name != name.as_ref());
+    }
+
+    /// Returns true, if a flow type with the given name exists.
+    pub fn has_flow_type<S: AsRef<str>>(&self, name: S) -> bool {
+        self.get_flow_type(name).is_some()
+    }
+
+    /// Returns the flow type with the given name.
+    pub fn get_flow_type<S: AsRef<str>>(&self, name: S) -> Option<&Flow> {
+        self.flow_types.iter().find(|flow_type| flow_type.name == name.as_ref())
+    }
+
+    /// Returns the entity type with the given name including the inherited definitions and
+    /// the properties of its components. Own properties override component properties.
+    pub fn resolve_entity_type<S: AsRef<str>>(&self, name: S) -> Result<EntityType, TypeSystemError> {
+        let entity_type = self
+            .get_entity_type(name.as_ref())
+            .ok_or_else(|| TypeSystemError::MissingEntityType(name.as_ref().to_string()))?;
+        let mut resolved = entity_type.resolve(&self.entity_types).map_err(TypeSystemError::EntityType)?;
+        resolved.properties = self.merge_component_properties(&resolved.components, &resolved.properties)?;
+        Ok(resolved)
+    }
+
+    /// Returns the relation type with the given name including the inherited definitions and
+    /// the properties of its components. Own properties override component properties.
+    pub fn resolve_relation_type<S: AsRef<str>>(&self, type_name: S) -> Result<RelationType, TypeSystemError> {
+        let relation_type = self
+            .get_relation_type(type_name.as_ref())
+            .ok_or_else(|| TypeSystemError::MissingRelationType(type_name.as_ref().to_string()))?;
+        let mut resolved = relation_type.resolve(&self.relation_types).map_err(TypeSystemError::RelationType)?;
+        resolved.properties = self.merge_component_properties(&resolved.components, &resolved.properties)?;
+        Ok(resolved)
+    }
+
+    fn merge_component_properties(&self, component_names: &[ComponentName], properties: &[PropertyType]) -> Result<Vec<PropertyType>, TypeSystemError> {
+        let mut merged: Vec<PropertyType> = Vec::new();
+        for component_name in component_names {
+            let component = self
+                .get_component(component_name)
+                .ok_or_else(|| TypeSystemError::MissingComponent(component_name.to_string()))?;
+            override_properties(&mut merged, component.properties.iter().cloned());
+        }
+        override_properties(&mut merged, properties.iter().cloned());
+        Ok(merged)
+    }
+
+    /// Returns the entity type with the given name including the inherited definitions together
+    /// with the effective properties and extensions of its components. Own properties override
+    /// component properties with the same data type and socket type and own extensions override
+    /// component extensions. Properties and extensions which are defined differently are reported
+    /// as conflict.
+    pub fn compose_entity_type<S: AsRef<str>>(&self, name: S) -> Result<ComposedEntityType, TypeSystemError> {
+        let entity_type = self
+            .get_entity_type(name.as_ref())
+            .ok_or_else(|| TypeSystemError::MissingEntityType(name.as_ref().to_string()))?;
+        let entity_type = entity_type.resolve(&self.entity_types).map_err(TypeSystemError::EntityType)?;
+        let (properties, extensions) = compose_components(&entity_type.components, &entity_type.properties, &entity_type.extensions, |component_name| {
+            self.get_component(component_name)
+        })?;
+        Ok(ComposedEntityType {
+            entity_type,
+            properties,
+            extensions,
+        })
+    }
+
+    /// Returns the relation type with the given name including the inherited definitions together
+    /// with the effective properties and extensions of its components, see
+    /// [`TypeSystem::compose_entity_type`].
+    pub fn compose_relation_type<S: AsRef<str>>(&self, type_name: S) -> Result<ComposedRelationType, TypeSystemError> {
+        let relation_type = self
+            .get_relation_type(type_name.as_ref())
+            .ok_or_else(|| TypeSystemError::MissingRelationType(type_name.as_ref().to_string()))?;
+        let relation_type = relation_type.resolve(&self.relation_types).map_err(TypeSystemError::RelationType)?;
+        let (properties, extensions) = compose_components(&relation_type.components, &relation_type.properties, &relation_type.extensions, |component_name| {
+            self.get_component(component_name)
+        })?;
+        Ok(ComposedRelationType {
+            relation_type,
+            properties,
+            extensions,
+        })
+    }
+}
+
+/// Resolves the effective properties and extensions of a type from the properties and extensions
+/// of its components and its own properties and extensions.
+pub(crate) fn compose_components<'a, F: Fn(&ComponentName) -> Option<&'a Component>>(
+    component_names: &[ComponentName],
+    own_properties: &[PropertyType],
+    own_extensions: &[Extension],
+    get_component: F,
+) -> Result<(Vec<PropertyType>, Vec<Extension>), ComposeError> {
+    let mut properties: Vec<PropertyType> = Vec::new();
+    let mut extensions: Vec<Extension> = Vec::new();
+    for component_name in component_names {
+        let component = get_component(component_name).ok_or_else(|| ComposeError::MissingComponent(component_name.to_string()))?;
+        inherit_properties(&mut properties, component.properties.iter().cloned())?;
+        inherit_extensions(&mut extensions, component.extensions.iter().cloned())?;
+    }
+    for property_type in own_properties {
+        if properties
+            .iter()
+            .any(|p| p.name == property_type.name && is_conflicting_property(p, property_type))
+        {
+            return Err(ComposeError::ConflictingProperty(property_type.name.clone()));
+        }
+    }
+    override_properties(&mut properties, own_properties.iter().cloned());
+    override_extensions(&mut extensions, own_extensions.iter().cloned());
+    Ok((properties, extensions))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum TypeSystemError {
+    /// The component with the given name doesn't exist.
+    MissingComponent(String),
+
+    /// The entity type with the given name doesn't exist.
+    MissingEntityType(String),
+
+    /// The relation type with the given name doesn't exist.
+    MissingRelationType(String),
+
+    /// The inheritance of the entity type can't be resolved.
+    EntityType(EntityTypeResolveError),
+
+    /// The inheritance of the relation type can't be resolved.
+    RelationType(RelationTypeResolveError),
+
+    /// The property with the given name is defined differently by the components or the type.
+    ConflictingProperty(String),
+
+    /// The extension with the given name is defined differently by the components.
+    ConflictingExtension(String),
+}
+
+impl fmt::Display for TypeSystemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeSystemError::MissingComponent(name) => write!(f, "The component {} doesn't exist", name),
+            TypeSystemError::MissingEntityType(name) => write!(f, "The entity type {} doesn't exist", name),
+            TypeSystemError::MissingRelationType(type_name) => write!(f, "The relation type {} doesn't exist", type_name),
+            TypeSystemError::EntityType(error) => write!(f, "{}", error),
+            TypeSystemError::RelationType(error) => write!(f, "{}", error),
+            TypeSystemError::ConflictingProperty(name) => write!(f, "The property {} is defined differently", name),
+            TypeSystemError::ConflictingExtension(name) => write!(f, "The components define the extension {} differently", name),
+        }
+    }
+}
+
+impl From<ComposeError> for TypeSystemError {
+    fn from(error: ComposeError) -> Self {
+        match error {
+            ComposeError::MissingComponent(name) => TypeSystemError::MissingComponent(name),
+            ComposeError::ConflictingProperty(name) => TypeSystemError::ConflictingProperty(name),
+            ComposeError::ConflictingExtension(name) => TypeSystemError::ConflictingExtension(name),
+        }
+    }
+}
@@ -0,0 +1,29 @@
+//! The types of the model: components, entity types, relation types, behaviour types and their
+//! property types.
+
+#[cfg(feature = "arbitrary")]
+pub use crate::arbitrary_model::*;
+pub use crate::behaviour_type::*;
+pub use crate::category_path::*;
+pub use crate::component::*;
+pub use crate::component_or_entity_type_name::*;
+pub use crate::composed_type::*;
+pub use crate::data_type::*;
+pub use crate::entity_type::*;
+pub use crate::extension::*;
+pub use crate::float_policy::*;
+pub use crate::flow_type::*;
+pub use crate::graph_identifier::*;
+pub use crate::property_group::*;
+pub use crate::property_kind::*;
+pub use crate::property_mutability::*;
+pub use crate::property_type::*;
+pub use crate::relation_cardinality::*;
+pub use crate::relation_type::*;
+pub use crate::schema_exporter::*;
+pub use crate::socket_type::*;
+pub use crate::type_name::*;
+pub use crate::type_system::*;
+pub use crate::validation::*;
+pub use crate::version::*;
+pub use crate::well_known_extension::*;
@@ -0,0 +1,348 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    is_valid_type_name, validate_value, ConnectError, DataType, EntityInstance, EntityType, Flow, PropertyKind, PropertyMap, PropertyType, RelationInstance,
+    RelationInstanceKey, SocketType, TypeSystem, TypeSystemError,
+};
+
+/// A reason why a type, an instance or a flow is invalid.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ValidationError {
+    /// The property with the given name doesn't exist.
+    MissingProperty { property_name: String },
+
+    /// The value of the property isn't of the expected data type.
+    InvalidDataType { property_name: String, expected: DataType },
+
+    /// The data type of the outbound property doesn't widen to the data type of the inbound property.
+    IncompatibleDataTypes { outbound: DataType, inbound: DataType },
+
+    /// A value property and an event property are connected.
+    IncompatibleKinds { outbound: PropertyKind, inbound: PropertyKind },
+
+    /// The unit of the outbound property differs from the unit of the inbound property.
+    IncompatibleUnits { outbound: String, inbound: String },
+
+    /// The property isn't of the expected socket type.
+    InvalidSocketType {
+        property_name: String,
+        expected: SocketType,
+        actual: SocketType,
+    },
+
+    /// A validator rejected the value of the property.
+    InvalidValue { property_name: String, reason: String },
+
+    /// The entity instance with the given id is referenced but doesn't exist.
+    DanglingReference { id: Uuid },
+
+    /// The name isn't a valid identifier.
+    InvalidIdentifier { identifier: String },
+
+    /// The instance isn't of the expected type.
+    InvalidType { expected: String, actual: String },
+
+    /// There is no instance of the entity type.
+    MissingEntityType { type_name: String },
+
+    /// There is no instance of the relation type.
+    MissingRelationType { type_name: String },
+
+    /// The variable with the given name isn't declared.
+    MissingVariable { variable_name: String },
+
+    /// The entity type with the given name doesn't exist.
+    UnknownEntityType { type_name: String },
+
+    /// The relation type with the given name doesn't exist.
+    UnknownRelationType { type_name: String },
+
+    /// The type with the given name exists, but its definition can't be resolved.
+    InvalidTypeDefinition { type_name: String, reason: String },
+
+    /// The entity instance with the given id doesn't match its entity type.
+    InvalidEntityInstance { id: Uuid, errors: Vec<ValidationError> },
+
+    /// The relation instance doesn't match its relation type.
+    InvalidRelationInstance {
+        outbound_id: Uuid,
+        type_name: String,
+        inbound_id: Uuid,
+        errors: Vec<ValidationError>,
+    },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::MissingProperty { property_name } => write!(f, "The property {} doesn't exist", property_name),
+            ValidationError::InvalidDataType { property_name, expected } => write!(f, "The value of {} is not of the data type {}", property_name, expected),
+            ValidationError::IncompatibleDataTypes { outbound, inbound } => write!(f, "The data type {} cannot be converted into {}", outbound, inbound),
+            ValidationError::IncompatibleKinds { outbound, inbound } => write!(f, "A {} cannot be connected to a {}", outbound, inbound),
+            ValidationError::IncompatibleUnits { outbound, inbound } => write!(f, "The unit {} cannot be connected to the unit {}", outbound, inbound),
+            ValidationError::InvalidSocketType {
+                property_name,
+                expected,
+                actual,
+            } => write!(f, "The property {} is not an {} socket but {}", property_name, expected, actual),
+            ValidationError::InvalidValue { property_name, reason } => write!(f, "The value of {} is invalid: {}", property_name, reason),
+            ValidationError::DanglingReference { id } => write!(f, "The entity instance {} doesn't exist", id),
+            ValidationError::InvalidIdentifier { identifier } => write!(f, "{} is not a valid identifier", identifier),
+            ValidationError::InvalidType { expected, actual } => write!(f, "The type {} is not the expected type {}", actual, expected),
+            ValidationError::MissingEntityType { type_name } => write!(f, "There is no instance of the entity type {}", type_name),
+            ValidationError::MissingRelationType { type_name } => write!(f, "There is no instance of the relation type {}", type_name),
+            ValidationError::MissingVariable { variable_name } => write!(f, "The variable {} is not declared", variable_name),
+            ValidationError::UnknownEntityType { type_name } => write!(f, "The entity type {} doesn't exist", type_name),
+            ValidationError::UnknownRelationType { type_name } => write!(f, "The relation type {} doesn't exist", type_name),
+            ValidationError::InvalidTypeDefinition { type_name, reason } => write!(f, "The type {} is invalid: {}", type_name, reason),
+            ValidationError::InvalidEntityInstance { id, errors } => write!(f, "The entity instance {} is invalid: {}", id, join_errors(errors)),
+            ValidationError::InvalidRelationInstance {
+                outbound_id,
+                type_name,
+                inbound_id,
+                errors,
+            } => write!(
+                f,
+                "The relation instance {} is invalid: {}",
+                RelationInstanceKey::new(*outbound_id, type_name.clone(), *inbound_id),
+                join_errors(errors)
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+fn join_errors(errors: &[ValidationError]) -> String {
+    errors.iter().map(ValidationError::to_string).collect::<Vec<String>>().join(", ")
+}
+
+impl From<ConnectError> for ValidationError {
+    fn from(error: ConnectError) -> Self {
+        match error {
+            ConnectError::NotAnOutput(property_name, actual) => ValidationError::InvalidSocketType {
+                property_name,
+                expected: SocketType::Output,
+                actual,
+            },
+            ConnectError::NotAnInput(property_name, actual) => ValidationError::InvalidSocketType {
+                property_name,
+                expected: SocketType::Input,
+                actual,
+            },
+            ConnectError::IncompatibleDataTypes(outbound, inbound) => ValidationError::IncompatibleDataTypes { outbound, inbound },
+            ConnectError::IncompatibleKinds(outbound, inbound) => ValidationError::IncompatibleKinds { outbound, inbound },
+            ConnectError::IncompatibleUnits(outbound, inbound) => ValidationError::IncompatibleUnits { outbound, inbound },
+        }
+    }
+}
+
+/// The result of a validation which collects all validation errors.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// The validation errors.
+    #[serde(default = "Vec::new")]
+    pub errors: Vec<ValidationError>,
+}
+
+impl ValidationReport {
+    pub fn new() -> ValidationReport {
+        ValidationReport::default()
+    }
+
+    /// Returns true, if there are no validation errors.
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Adds the validation error.
+    pub fn add(&mut self, error: ValidationError) {
+        self.errors.push(error);
+    }
+
+    /// Adds the validation error of the result, if any.
+    pub fn check<T>(&mut self, result: Result<T, ValidationError>) {
+        if let Err(error) = result {
+            self.add(error);
+        }
+    }
+
+    /// Adds the validation errors of the given report.
+    pub fn merge(&mut self, report: ValidationReport) {
+        self.errors.extend(report.errors);
+    }
+
+    /// Returns the report as error, if there are validation errors.
+    pub fn into_result(self) -> Result<(), ValidationReport> {
+        if self.is_valid() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let errors: Vec<String> = self.errors.iter().map(|error| error.to_string()).collect();
+        write!(f, "{}", errors.join("\n"))
+    }
+}
+
+impl std::error::Error for ValidationReport {}
+
+/// Returns an error, if the name isn't a valid identifier.
+pub fn validate_identifier<S: AsRef<str>>(name: S) -> Result<(), ValidationError> {
+    if is_valid_type_name(name.as_ref()) {
+        return Ok(());
+    }
+    Err(ValidationError::InvalidIdentifier {
+        identifier: name.as_ref().to_string(),
+    })
+}
+
+/// Validates the property values against the property types. Every property type must have a valid value.
+pub fn validate_properties(properties: &PropertyMap, property_types: &[PropertyType]) -> ValidationReport {
+    let mut report = ValidationReport::new();
+    for property_type in property_types {
+        match properties.get(&property_type.name) {
+            Some(value) => report.check(validate_value(property_type, value)),
+            None => report.add(ValidationError::MissingProperty {
+                property_name: property_type.name.clone(),
+            }),
+        }
+    }
+    report
+}
+
+impl Flow {
+    /// Validates the type names of the instances and the references of the relation instances.
+    pub fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport::new();
+        if !self.entity_instances.iter().any(|entity_instance| entity_instance.id == self.id) {
+            report.add(ValidationError::DanglingReference { id: self.id });
+        }
+        for entity_instance in self.entity_instances.iter() {
+            report.check(validate_identifier(&entity_instance.type_name));
+        }
+        for relation_instance in self.relation_instances.iter() {
+            report.check(validate_identifier(&relation_instance.type_name));
+            for id in [relation_instance.outbound_id, relation_instance.inbound_id] {
+                let error = ValidationError::DanglingReference { id };
+                if !self.entity_instances.iter().any(|entity_instance| entity_instance.id == id) && !report.errors.contains(&error) {
+                    report.add(error);
+                }
+            }
+        }
+        report
+    }
+
+    /// Validates the instances against the types of the type system. The entity types and relation
+    /// types of the instances must exist and the property values must be of the data types of the
+    /// properties of the types, including inherited properties and the properties of components. The
+    /// outbound and inbound entity instances of relation instances must match the outbound and
+    /// inbound types of the relation types. Validators are not applied.
+    pub fn validate_types(&self, type_system: &TypeSystem) -> ValidationReport {
+        let mut report = ValidationReport::new();
+        let mut entity_types: Vec<(&EntityInstance, Option<EntityType>)> = Vec::new();
+        for entity_instance in self.entity_instances.iter() {
+            let entity_type = match type_system.resolve_entity_type(&entity_instance.type_name) {
+                Ok(entity_type) => entity_type,
+                Err(error) => {
+                    report.add(ValidationError::InvalidEntityInstance {
+                        id: entity_instance.id,
+                        errors: vec![type_system_error(entity_instance.type_name.as_str(), error)],
+                    });
+                    entity_types.push((entity_instance, None));
+                    continue;
+                }
+            };
+            let errors = validate_data_types(&entity_instance.properties, &entity_type.properties);
+            if !errors.is_empty() {
+                report.add(ValidationError::InvalidEntityInstance {
+                    id: entity_instance.id,
+                    errors,
+                });
+            }
+            entity_types.push((entity_instance, Some(entity_type)));
+        }
+        for relation_instance in self.relation_instances.iter() {
+            let errors = validate_relation_instance_type(relation_instance, type_system, &entity_types);
+            if !errors.is_empty() {
+                report.add(ValidationError::InvalidRelationInstance {
+                    outbound_id: relation_instance.outbound_id,
+                    type_name: relation_instance.type_name.to_string(),
+                    inbound_id: relation_instance.inbound_id,
+                    errors,
+                });
+            }
+        }
+        report
+    }
+}
+
+fn validate_relation_instance_type(
+    relation_instance: &RelationInstance,
+    type_system: &TypeSystem,
+    entity_types: &[(&EntityInstance, Option<EntityType>)],
+) -> Vec<ValidationError> {
+    let type_name = relation_instance
+        .get_type_id()
+        .map(|type_id| type_id.type_name)
+        .unwrap_or_else(|_| relation_instance.type_name.to_string());
+    let relation_type = match type_system.resolve_relation_type(&type_name) {
+        Ok(relation_type) => relation_type,
+        Err(TypeSystemError::MissingRelationType(type_name)) => return vec![ValidationError::UnknownRelationType { type_name }],
+        Err(error) => return vec![type_system_error(&type_name, error)],
+    };
+    let mut errors = validate_data_types(&relation_instance.properties, &relation_type.properties);
+    let endpoints = [
+        (relation_instance.outbound_id, &relation_type.outbound_type),
+        (relation_instance.inbound_id, &relation_type.inbound_type),
+    ];
+    for (id, expected) in endpoints {
+        // Dangling references and unknown entity types are reported by other checks
+        if let Some((_, Some(entity_type))) = entity_types.iter().find(|(entity_instance, _)| entity_instance.id == id) {
+            if !expected.matches_entity_type(entity_type) {
+                errors.push(ValidationError::InvalidType {
+                    expected: expected.to_string(),
+                    actual: entity_type.name.to_string(),
+                });
+            }
+        }
+    }
+    errors
+}
+
+/// Validates that every property type has a value of its data type.
+fn validate_data_types(properties: &PropertyMap, property_types: &[PropertyType]) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    for property_type in property_types {
+        match properties.get(&property_type.name) {
+            Some(value) if !property_type.data_type.is_valid(value) => errors.push(ValidationError::InvalidDataType {
+                property_name: property_type.name.clone(),
+                expected: property_type.data_type,
+            }),
+            Some(_) => {}
+            None => errors.push(ValidationError::MissingProperty {
+                property_name: property_type.name.clone(),
+            }),
+        }
+    }
+    errors
+}
+
+fn type_system_error(type_name: &str, error: TypeSystemError) -> ValidationError {
+    match error {
+        TypeSystemError::MissingEntityType(type_name) => ValidationError::UnknownEntityType { type_name },
+        TypeSystemError::MissingRelationType(type_name) => ValidationError::UnknownRelationType { type_name },
+        error => ValidationError::InvalidTypeDefinition {
+            type_name: type_name.to_string(),
+            reason: error.to_string(),
+        },
+    }
+}
@@ -0,0 +1,8 @@
+//! Semantic versions and version requirements of extension payloads.
+//!
+//! The parsing, the ordering and the matching of requirements follow the semver specification
+//! and are implemented by the `semver` crate. Build metadata is kept but ignored by the
+//! comparison of requirements.
+
+pub use semver::Error as VersionError;
+pub use semver::{Comparator as VersionComparator, Op as VersionOp, Version, VersionReq};
@@ -0,0 +1,120 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{get_extension, Component, EntityType, Extension, ExtensionError, PropertyType, RelationType};
+
+/// The name of the extension which contains the documentation of a type.
+pub const EXTENSION_DOCS: &str = "docs";
+
+/// The name of the extension which contains the icon of a type.
+pub const EXTENSION_ICON: &str = "icon";
+
+/// The name of the extension which contains the category of a type in the palette of an editor.
+pub const EXTENSION_PALETTE_CATEGORY: &str = "palette_category";
+
+/// An extension with a well-known name and payload format.
+pub trait WellKnownExtension: Serialize + DeserializeOwned {
+    /// The name of the extension.
+    const NAME: &'static str;
+
+    /// Returns the extension with the serialized payload.
+    fn to_extension(&self) -> Extension {
+        Extension::new(Self::NAME, serde_json::to_value(self).unwrap_or(Value::Null))
+    }
+
+    /// Returns the deserialized payload of the extension with the name of this extension.
+    fn from_extensions(extensions: &[Extension]) -> Result<Self, ExtensionError> {
+        get_extension(extensions, Self::NAME)
+    }
+}
+
+/// The documentation of a type.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Docs {
+    /// The documentation as markdown.
+    pub text: String,
+
+    /// The url of further documentation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl WellKnownExtension for Docs {
+    const NAME: &'static str = EXTENSION_DOCS;
+}
+
+/// The icon of a type.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Icon {
+    /// The name of the icon in the icon set of the editor.
+    pub name: String,
+
+    /// The color of the icon, e.g. `#ff0000`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+}
+
+impl WellKnownExtension for Icon {
+    const NAME: &'static str = EXTENSION_ICON;
+}
+
+/// The category of a type in the palette of an editor.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PaletteCategory {
+    /// The name of the category.
+    pub category: String,
+
+    /// The position of the type within the category.
+    #[serde(default)]
+    pub order: i32,
+}
+
+impl WellKnownExtension for PaletteCategory {
+    const NAME: &'static str = EXTENSION_PALETTE_CATEGORY;
+}
+
+/// Typed getters for the well-known extensions of a type.
+pub trait WellKnownExtensions {
+    /// Returns the extensions of the type.
+    fn extensions(&self) -> &[Extension];
+
+    /// Returns the documentation of the type.
+    fn docs(&self) -> Result<Docs, ExtensionError> {
+        Docs::from_extensions(self.extensions())
+    }
+
+    /// Returns the icon of the type.
+    fn icon(&self) -> Result<Icon, ExtensionError> {
+        Icon::from_extensions(self.extensions())
+    }
+
+    /// Returns the category of the type in the palette of an editor.
+    fn palette_category(&self) -> Result<PaletteCategory, ExtensionError> {
+        PaletteCategory::from_extensions(self.extensions())
+    }
+}
+
+impl WellKnownExtensions for Component {
+    fn extensions(&self) -> &[Extension] {
+        &self.extensions
+    }
+}
+
+impl WellKnownExtensions for EntityType {
+    fn extensions(&self) -> &[Extension] {
+        &self.extensions
+    }
+}
+
+impl WellKnownExtensions for RelationType {
+    fn extensions(&self) -> &[Extension] {
+        &self.extensions
+    }
+}
+
+impl WellKnownExtensions for PropertyType {
+    fn extensions(&self) -> &[Extension] {
+        &self.extensions
+    }
+}